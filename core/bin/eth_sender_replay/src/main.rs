@@ -0,0 +1,135 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::{
+    configs::{wallets::Wallets, GenesisConfigWrapper, PostgresSecrets},
+    full_config_schema,
+    sources::ConfigFilePaths,
+    ContractsConfig, EthConfig, PostgresConfig,
+};
+use zksync_dal::{ConnectionPool, Core};
+use zksync_eth_sender::replay::{load_fee_history, replay, ReplayParams};
+use zksync_types::L1BatchNumber;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Offline replay of eth_sender aggregation against historical L1 fees",
+    long_about = "Offline replay of eth_sender aggregation against historical L1 fees. \
+        The tool rewrites eth_txs in a database transaction that is always rolled back, so the database is not modified."
+)]
+struct Cli {
+    /// Path to a JSON file with historical L1 fees (an array of objects with `block_number`, `timestamp`,
+    /// `base_fee_per_gas` and `base_fee_per_blob_gas` fields).
+    #[arg(long)]
+    fee_history: PathBuf,
+    /// Last L1 batch for which operations are kept in the database. Operations for all subsequent L1 batches
+    /// are replayed.
+    #[arg(long)]
+    last_l1_batch_to_keep: u32,
+    /// Execution delay enforced by the validator timelock, in seconds.
+    #[arg(long, default_value_t = 0)]
+    execution_delay_secs: u64,
+    /// Path to write the JSON report to. If not specified, the report is printed to stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Path to yaml config. If set, it will be used instead of env vars
+    #[arg(long)]
+    config_path: Option<PathBuf>,
+    /// Path to yaml contracts config. If set, it will be used instead of env vars
+    #[arg(long)]
+    contracts_config_path: Option<PathBuf>,
+    /// Path to yaml secrets config. If set, it will be used instead of env vars
+    #[arg(long)]
+    secrets_path: Option<PathBuf>,
+    /// Path to yaml wallets config. If set, it will be used instead of env vars
+    #[arg(long)]
+    wallets_path: Option<PathBuf>,
+    /// Path to yaml genesis config. If set, it will be used instead of env vars
+    #[arg(long)]
+    genesis_path: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Cli::parse();
+
+    let config_file_paths = ConfigFilePaths {
+        general: opts.config_path,
+        secrets: opts.secrets_path,
+        wallets: opts.wallets_path,
+        genesis: opts.genesis_path,
+        contracts: opts.contracts_config_path,
+        ..ConfigFilePaths::default()
+    };
+    let config_sources =
+        tokio::task::spawn_blocking(|| config_file_paths.into_config_sources("ZKSYNC_")).await??;
+    let _guard = config_sources.observability()?.install()?;
+
+    let schema = full_config_schema();
+    let mut repo = config_sources.build_repository(&schema);
+    let genesis = repo
+        .parse::<GenesisConfigWrapper>()?
+        .genesis
+        .context("genesis config is required")?;
+    let eth: EthConfig = repo.parse()?;
+    let contracts: ContractsConfig = repo.parse()?;
+    let wallets: Wallets = repo.parse()?;
+    let postgres_config: PostgresConfig = repo.parse()?;
+    let database_secrets: PostgresSecrets = repo.parse()?;
+
+    let fee_history_path = opts.fee_history;
+    let fee_history =
+        tokio::task::spawn_blocking(move || load_fee_history(&fee_history_path)).await??;
+    tracing::info!(
+        "Loaded {} fee records for L1 blocks #{}..=#{}",
+        fee_history.len(),
+        fee_history[0].block_number,
+        fee_history[fee_history.len() - 1].block_number
+    );
+
+    let params = ReplayParams {
+        sender: eth.sender,
+        gas_adjuster: eth.gas_adjuster,
+        commitment_mode: genesis.l1_batch_commit_data_generator_mode,
+        l1_chain_id: genesis.l1_chain_id.into(),
+        l2_chain_id: genesis.l2_chain_id,
+        diamond_proxy_address: contracts.l1.diamond_proxy_addr,
+        validator_timelock_address: contracts.l1.validator_timelock_addr,
+        operator_address: wallets
+            .operator_signer()
+            .context("operator wallet is not configured")?
+            .address(),
+        blob_operator_address: wallets
+            .blob_operator_signer()
+            .map(|wallet| wallet.address()),
+        last_l1_batch_to_keep: L1BatchNumber(opts.last_l1_batch_to_keep),
+        execution_delay: Duration::from_secs(opts.execution_delay_secs),
+    };
+
+    let pool = ConnectionPool::<Core>::builder(
+        database_secrets.master_url()?,
+        postgres_config.max_connections()?,
+    )
+    .build()
+    .await
+    .context("failed to build a connection pool")?;
+    let report = replay(pool, params, &fee_history).await?;
+    tracing::info!(
+        "Replayed {} operations with total cost {} wei",
+        report.operations.len(),
+        report.total_cost()
+    );
+
+    let report = serde_json::to_string_pretty(&report)?;
+    if let Some(output) = opts.output {
+        tokio::fs::write(&output, report)
+            .await
+            .with_context(|| format!("failed writing report to {output:?}"))?;
+    } else {
+        println!("{report}");
+    }
+    Ok(())
+}
@@ -213,9 +213,11 @@ impl From<&LocalConfig> for InternalApiConfigBase {
             estimate_gas_optimize_search: web3_rpc.estimate_gas_optimize_search,
             req_entities_limit: web3_rpc.req_entities_limit as usize,
             fee_history_limit: web3_rpc.fee_history_limit,
+            trace_filter_block_range_limit: web3_rpc.trace_filter_block_range_limit,
             filters_disabled: web3_rpc.filters_disabled,
             l1_to_l2_txs_paused: false,
             eth_call_gas_cap: web3_rpc.eth_call_gas_cap,
+            preconfirmation_timeout: web3_rpc.preconfirmation_timeout,
         }
     }
 }
@@ -231,6 +233,10 @@ impl From<&LocalConfig> for TxSenderConfig {
                 .unwrap(),
             gas_price_scale_factor: web3_rpc.gas_price_scale_factor,
             max_nonce_ahead: web3_rpc.max_nonce_ahead,
+            // Pending transactions are not stored locally on the EN, so these limits are enforced
+            // by the main node.
+            max_pending_txs_per_account: None,
+            replacement_fee_bump_percent: 0,
             vm_execution_cache_misses_limit: web3_rpc.vm_execution_cache_misses_limit,
             // We set these values to the maximum since we don't know the actual values
             // and they will be enforced by the main node anyway.
@@ -201,11 +201,14 @@ fn parsing_from_full_env() {
         EN_MAX_TX_SIZE_BYTES=1000000
         EN_VM_EXECUTION_CACHE_MISSES_LIMIT=1000
         EN_FEE_HISTORY_LIMIT=100
+        EN_TRACE_FILTER_BLOCK_RANGE_LIMIT=500
         EN_MAX_BATCH_REQUEST_SIZE=50
         EN_MAX_RESPONSE_BODY_SIZE_MB=5
         EN_MAX_RESPONSE_BODY_SIZE_OVERRIDES_MB="zks_getProof=100,eth_call=2"
         EN_PUBSUB_POLLING_INTERVAL_MS=200
         EN_MAX_NONCE_AHEAD=33
+        EN_MAX_PENDING_TXS_PER_ACCOUNT=10
+        EN_REPLACEMENT_FEE_BUMP_PERCENT=10
         EN_VM_CONCURRENCY_LIMIT=100
         EN_FACTORY_DEPS_CACHE_SIZE_MB=100
         EN_INITIAL_WRITES_CACHE_SIZE_MB=50
@@ -226,9 +229,17 @@ fn parsing_from_full_env() {
 
         # NEW PARAMS: From Web3RpcConfig
         EN_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=1000
+        EN_HTTP_RATE_LIMITS_API_KEYS="alice=0123456789abcdef"
+        EN_HTTP_RATE_LIMITS_API_KEY_HEADER=x-rpc-key
+        EN_HTTP_RATE_LIMITS_API_KEY_IN_PATH=true
+        EN_HTTP_RATE_LIMITS_REQUESTS_PER_MINUTE=600
+        EN_HTTP_RATE_LIMITS_COMPUTE_UNITS_PER_MINUTE=6000
+        EN_HTTP_RATE_LIMITS_RESPONSE_SIZE_PER_MINUTE_MB=100
+        EN_HTTP_RATE_LIMITS_METHOD_WEIGHTS="eth_call=10"
         EN_LATEST_VALUES_MAX_BLOCK_LAG=30
         EN_WHITELISTED_TOKENS_FOR_AA=0x0000000000000000000000000000000000000001
         EN_REQUEST_TIMEOUT_SEC=20
+        EN_PRECONFIRMATION_TIMEOUT_MS=1500
         EN_GAS_PRICE_SCALE_FACTOR_OPEN_BATCH=1.35
         # NEW PARAMS: From HealthcheckConfig
         EN_HEALTHCHECK_EXPOSE_CONFIG=true
@@ -269,6 +280,9 @@ fn parsing_from_full_env() {
         EN_SNAPSHOTS_OBJECT_STORE_BUCKET_BASE_URL=zksync-era-mainnet-external-node-snapshots
         EN_SNAPSHOTS_OBJECT_STORE_MAX_RETRIES=5
         EN_SNAPSHOTS_OBJECT_STORE_LOCAL_MIRROR_PATH=/tmp/object-store
+        EN_SNAPSHOTS_OBJECT_STORE_COMPRESS_OBJECTS=true
+        EN_SNAPSHOTS_OBJECT_STORE_ENCRYPTION_KEY_PATH=/etc/object-store/encryption.key
+        EN_SNAPSHOTS_OBJECT_STORE_ENCRYPTED_BUCKETS=witness_inputs
 
         EN_PRUNING_ENABLED=true
         EN_PRUNING_CHUNK_SIZE=5
@@ -341,6 +355,7 @@ fn test_parsing_general_config(source: impl ConfigSource + Clone) {
     assert_eq!(config.max_tx_size, ByteSize(1_000_000));
     assert_eq!(config.vm_execution_cache_misses_limit, Some(1_000));
     assert_eq!(config.fee_history_limit, 100);
+    assert_eq!(config.trace_filter_block_range_limit, 500);
     assert_eq!(config.max_batch_request_size.get(), 50);
     assert_eq!(config.max_response_body_size, ByteSize(5 << 20));
     assert_eq!(
@@ -352,6 +367,8 @@ fn test_parsing_general_config(source: impl ConfigSource + Clone) {
     );
     assert_eq!(config.pubsub_polling_interval, Duration::from_millis(200));
     assert_eq!(config.max_nonce_ahead, 33);
+    assert_eq!(config.max_pending_txs_per_account, NonZeroU32::new(10));
+    assert_eq!(config.replacement_fee_bump_percent, 10);
     assert_eq!(config.vm_concurrency_limit, 100);
     assert_eq!(config.factory_deps_cache_size, ByteSize(100 << 20));
     assert_eq!(config.initial_writes_cache_size, ByteSize(50 << 20));
@@ -372,8 +389,23 @@ fn test_parsing_general_config(source: impl ConfigSource + Clone) {
     assert_eq!(config.gas_price_scale_factor, 1.4);
     assert_eq!(config.gas_price_scale_factor_open_batch, Some(1.35));
     assert_eq!(config.request_timeout, Some(Duration::from_secs(20)));
+    assert_eq!(config.preconfirmation_timeout, Duration::from_millis(1_500));
     assert_eq!(config.http_port, 2_950);
     assert_eq!(config.ws_port, 2_951);
+    let rate_limits = config.http_rate_limits;
+    assert_eq!(
+        rate_limits.api_keys.iter().collect::<Vec<_>>(),
+        [("alice", "0123456789abcdef")]
+    );
+    assert_eq!(rate_limits.api_key_header, "x-rpc-key");
+    assert!(rate_limits.api_key_in_path);
+    assert_eq!(rate_limits.requests_per_minute, NonZeroU32::new(600));
+    assert_eq!(rate_limits.compute_units_per_minute, NonZeroU32::new(6_000));
+    assert_eq!(
+        rate_limits.response_size_per_minute,
+        Some(ByteSize(100 << 20))
+    );
+    assert_eq!(rate_limits.method_weights.get("eth_call").get(), 10);
 
     let config: HealthCheckConfig = tester.for_config().test_complete(source.clone()).unwrap();
     assert_eq!(config.slow_time_limit, Some(Duration::from_millis(75)));
@@ -465,6 +497,12 @@ fn test_parsing_general_config(source: impl ConfigSource + Clone) {
         object_store.local_mirror_path.unwrap().as_os_str(),
         "/tmp/object-store"
     );
+    assert!(object_store.compress_objects);
+    assert_eq!(
+        object_store.encryption_key_path.unwrap().as_os_str(),
+        "/etc/object-store/encryption.key"
+    );
+    assert_eq!(object_store.encrypted_buckets, ["witness_inputs"]);
 
     let config: PruningConfig = tester.for_config().test_complete(source.clone()).unwrap();
     assert!(config.enabled);
@@ -652,6 +690,9 @@ fn eigen_da_client_from_env() {
         EN_DA_CERT_VERIFIER_ROUTER_ADDR="0x0000000000000000000000000000000000000123"
         EN_DA_OPERATOR_STATE_RETRIEVER_ADDR="0x0000000000000000000000000000000000000124"
         EN_DA_REGISTRY_COORDINATOR_ADDR="0x0000000000000000000000000000000000000125"
+        EN_DA_RELAY_REGISTRY_ADDR="0x0000000000000000000000000000000000000126"
+        EN_DA_RELAY_CLIENT_KEYS="0,1"
+        EN_DA_SRS_POINTS_PATH="/resources/g1.point"
         EN_DA_BLOB_VERSION="0"
 
         # Secrets
@@ -686,6 +727,15 @@ fn eigen_da_client_from_env() {
         config.registry_coordinator_addr,
         "0x0000000000000000000000000000000000000125"
     );
+    assert_eq!(
+        config.relay_registry_addr.as_deref(),
+        Some("0x0000000000000000000000000000000000000126")
+    );
+    assert_eq!(config.relay_client_keys, [0, 1]);
+    assert_eq!(
+        config.srs_points_path.as_deref(),
+        Some(Path::new("/resources/g1.point"))
+    );
 
     let secrets: DataAvailabilitySecrets = tester.for_config().test_complete(env.clone()).unwrap();
     let DataAvailabilitySecrets::Eigen(secrets) = secrets else {
@@ -196,15 +196,11 @@ impl ExternalNodeBuilder {
             self.config.local.node_sync.validate_seal_criteria,
         );
 
-        // We only need call traces on the external node if the `debug_` namespace is enabled.
+        // We only need call traces on the external node if the `debug_` or `trace_` namespace is enabled.
         // TODO(PLA-1153): this is backwards / unobvious. Can readily use `config.state_keeper.save_call_traces` instead.
-        let save_call_traces = self
-            .config
-            .local
-            .api
-            .web3_json_rpc
-            .api_namespaces
-            .contains(&Namespace::Debug);
+        let api_namespaces = &self.config.local.api.web3_json_rpc.api_namespaces;
+        let save_call_traces = api_namespaces.contains(&Namespace::Debug)
+            || api_namespaces.contains(&Namespace::Trace);
         let main_node_batch_executor_builder_layer =
             MainBatchExecutorLayer::new(save_call_traces, OPTIONAL_BYTECODE_COMPRESSION);
 
@@ -538,6 +534,7 @@ impl ExternalNodeBuilder {
             polling_interval: config.pubsub_polling_interval,
             request_timeout: config.request_timeout,
             websocket_requests_per_minute_limit: Some(config.websocket_requests_per_minute_limit),
+            http_rate_limits: Some(config.http_rate_limits.clone()),
         })
     }
 
@@ -588,7 +585,9 @@ impl ExternalNodeBuilder {
 
     fn add_ws_web3_api_layer(mut self) -> anyhow::Result<Self> {
         // TODO: Support websocket requests per minute limit
-        let optional_config = self.web3_api_optional_config()?;
+        let mut optional_config = self.web3_api_optional_config()?;
+        // Not relevant for WS server, so we reset to prevent a logged warning.
+        optional_config.http_rate_limits = None;
         let internal_api_config_base: InternalApiConfigBase = (&self.config.local).into();
 
         self.node.add_layer(Web3ServerLayer::ws(
@@ -0,0 +1,108 @@
+use std::{
+    fs,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use clap::{Parser, Subcommand};
+use zksync_multivm::interface::utils::VmDump;
+
+use crate::replay::{minimize, replay, tx_count, VmKind};
+
+mod replay;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Tool to replay and minimize VM dumps",
+    long_about = "Tool to replay and minimize VM dumps. Dumps are JSON files created on divergences \
+        between the legacy and fast VMs, and are stored in the `vm_dumps` object store bucket."
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Replays a VM dump, printing the outcome of each transaction and the first divergence between VMs, if any.
+    Replay {
+        /// Path to the VM dump.
+        path: PathBuf,
+        /// VM to replay the dump on.
+        #[arg(long, value_enum, default_value_t = VmKind::Shadow)]
+        vm: VmKind,
+    },
+    /// Shrinks a VM dump to the minimal prefix of L2 blocks and transactions that reproduces
+    /// the first divergence between VMs.
+    Minimize {
+        /// Path to the VM dump.
+        path: PathBuf,
+        /// Path to write the minimized dump to.
+        #[arg(long)]
+        output: PathBuf,
+    },
+}
+
+fn load_dump(path: &Path) -> anyhow::Result<VmDump> {
+    let file = fs::File::open(path).with_context(|| format!("failed opening {path:?}"))?;
+    serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("failed deserializing VM dump from {path:?}"))
+}
+
+fn save_dump(path: &Path, dump: &VmDump) -> anyhow::Result<()> {
+    let file = fs::File::create(path).with_context(|| format!("failed creating {path:?}"))?;
+    serde_json::to_writer(BufWriter::new(file), dump)
+        .with_context(|| format!("failed serializing VM dump to {path:?}"))
+}
+
+impl Command {
+    fn run(self) -> anyhow::Result<()> {
+        match self {
+            Self::Replay { path, vm } => {
+                let dump = load_dump(&path)?;
+                let output = replay(dump, vm);
+                for outcome in &output.outcomes {
+                    println!("{outcome}");
+                }
+
+                if let Some(divergence) = output.divergence {
+                    println!("\nFirst divergence at {}", divergence.position);
+                    if let Some(tx_hash) = divergence.tx_hash {
+                        println!("Diverging transaction: {tx_hash:?}");
+                    }
+                    println!("{}", divergence.errors);
+                    anyhow::bail!("VM execution diverged");
+                } else if vm == VmKind::Shadow {
+                    println!("\nNo divergences found");
+                }
+            }
+            Self::Minimize { path, output } => {
+                let dump = load_dump(&path)?;
+                let (block_count, tx_count_before) = (dump.l2_blocks.len(), tx_count(&dump));
+                let (minimized_dump, divergence) = minimize(dump)?;
+                save_dump(&output, &minimized_dump)?;
+
+                println!(
+                    "Minimized dump from {block_count} L2 blocks / {tx_count_before} transactions to \
+                     {} L2 blocks / {} transactions; saved to {output:?}",
+                    minimized_dump.l2_blocks.len(),
+                    tx_count(&minimized_dump)
+                );
+                println!("Divergence at {}", divergence.position);
+                if let Some(tx_hash) = divergence.tx_hash {
+                    println!("Diverging transaction: {tx_hash:?}");
+                }
+                println!("{}", divergence.errors);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let _guard = zksync_vlog::ObservabilityBuilder::new().try_build()?;
+    Cli::parse().command.run()
+}
@@ -0,0 +1,503 @@
+//! Replaying and minimizing VM dumps.
+
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context as _;
+use clap::ValueEnum;
+use zksync_multivm::{
+    interface::{
+        storage::{ReadStorage, StorageView},
+        utils::{DivergenceHandler, VmDump},
+        ExecutionResult, L2BlockEnv, VmFactory, VmInterface, VmInterfaceExt,
+    },
+    vm_fast::FastValidationTracer,
+    vm_latest::HistoryEnabled,
+    FastVmInstance, LegacyVmInstance,
+};
+use zksync_types::{block::L2BlockExecutionData, L2BlockNumber, H256};
+
+/// VM used to replay a dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum VmKind {
+    /// Legacy VM for the protocol version specified in the dump.
+    Legacy,
+    /// Fast VM running in isolation.
+    Fast,
+    /// Fast VM shadowed by the legacy VM; reports the first divergence between the VMs.
+    Shadow,
+}
+
+/// Position of a VM action in a dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DumpPosition {
+    /// 0-based index of the L2 block in the dump.
+    pub block_index: usize,
+    pub l2_block_number: L2BlockNumber,
+    /// 0-based index of the transaction in the L2 block, or `None` for the start of the L2 block.
+    pub tx_index: Option<usize>,
+}
+
+impl fmt::Display for DumpPosition {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.tx_index {
+            Some(tx_index) => write!(
+                formatter,
+                "L2 block #{}, transaction #{tx_index}",
+                self.l2_block_number
+            ),
+            None => write!(formatter, "start of L2 block #{}", self.l2_block_number),
+        }
+    }
+}
+
+/// Outcome of a replayed transaction.
+#[derive(Debug)]
+pub(crate) struct TxOutcome {
+    pub position: DumpPosition,
+    pub tx_hash: H256,
+    pub result: ExecutionResult,
+    pub gas_used: u64,
+    /// Error compressing published bytecodes, if any.
+    pub compression_error: Option<String>,
+}
+
+impl fmt::Display for TxOutcome {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{} ({:?}): ", self.position, self.tx_hash)?;
+        match &self.result {
+            ExecutionResult::Success { .. } => write!(formatter, "success")?,
+            ExecutionResult::Revert { output } => write!(formatter, "reverted: {output}")?,
+            ExecutionResult::Halt { reason } => write!(formatter, "halted: {reason}")?,
+        }
+        write!(formatter, ", gas used: {}", self.gas_used)?;
+        if let Some(err) = &self.compression_error {
+            write!(formatter, ", bytecode compression failed: {err}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Divergence between the legacy and fast VMs.
+#[derive(Debug)]
+pub(crate) struct Divergence {
+    /// Position of the first diverging action.
+    pub position: DumpPosition,
+    /// Hash of the diverging transaction, if the divergence was detected when executing a transaction.
+    pub tx_hash: Option<H256>,
+    /// Human-readable divergence errors.
+    pub errors: String,
+    /// Dump produced by the shadow VM when detecting the divergence. Contains the prefix of the replayed dump
+    /// up to and including the diverging action, and only storage slots accessed during the execution.
+    pub dump: VmDump,
+}
+
+/// Output of replaying a VM dump.
+#[derive(Debug)]
+pub(crate) struct ReplayOutput {
+    /// Outcomes of all replayed transactions. If a divergence is detected, replay stops after the diverging transaction.
+    pub outcomes: Vec<TxOutcome>,
+    /// First divergence. Always `None` unless the dump is replayed on [`VmKind::Shadow`].
+    pub divergence: Option<Divergence>,
+}
+
+type CapturedDivergence = Arc<Mutex<Option<(String, VmDump)>>>;
+
+/// Replays the dump on the specified VM.
+pub(crate) fn replay(dump: VmDump, vm_kind: VmKind) -> ReplayOutput {
+    let storage = StorageView::new(dump.storage).to_rc_ptr();
+    let l1_batch_env = dump.l1_batch_env;
+    let system_env = dump.system_env;
+    tracing::info!(
+        "Replaying L1 batch #{} ({} L2 blocks) on {vm_kind:?} VM",
+        l1_batch_env.number,
+        dump.l2_blocks.len()
+    );
+
+    match vm_kind {
+        VmKind::Legacy => {
+            let vm = LegacyVmInstance::<_, HistoryEnabled>::new(l1_batch_env, system_env, storage);
+            replay_l2_blocks(vm, dump.l2_blocks, None)
+        }
+        VmKind::Fast => {
+            let vm = FastVmInstance::<_, (), FastValidationTracer>::fast(
+                l1_batch_env,
+                system_env,
+                storage,
+            );
+            replay_l2_blocks(vm, dump.l2_blocks, None)
+        }
+        VmKind::Shadow => {
+            let vm = FastVmInstance::shadowed(l1_batch_env, system_env, storage);
+            replay_shadowed(vm, dump.l2_blocks)
+        }
+    }
+}
+
+fn replay_shadowed<S: ReadStorage>(
+    mut vm: FastVmInstance<S, (), FastValidationTracer>,
+    l2_blocks: Vec<L2BlockExecutionData>,
+) -> ReplayOutput {
+    let captured = CapturedDivergence::default();
+    if let FastVmInstance::Shadowed(vm) = &mut vm {
+        let captured = captured.clone();
+        vm.set_divergence_handler(DivergenceHandler::new(move |errors, dump| {
+            // The shadow VM is dropped after the first divergence, so the handler is called at most once.
+            *captured.lock().unwrap() = Some((errors.to_string(), dump));
+        }));
+    }
+    replay_l2_blocks(vm, l2_blocks, Some(&captured))
+}
+
+fn replay_l2_blocks<Vm: VmInterface>(
+    mut vm: Vm,
+    l2_blocks: Vec<L2BlockExecutionData>,
+    captured_divergence: Option<&CapturedDivergence>,
+) -> ReplayOutput {
+    let take_divergence = |position, tx_hash| {
+        let (errors, dump) = captured_divergence?.lock().unwrap().take()?;
+        Some(Divergence {
+            position,
+            tx_hash,
+            errors,
+            dump,
+        })
+    };
+
+    let mut outcomes = vec![];
+    for (block_index, l2_block) in l2_blocks.into_iter().enumerate() {
+        let mut position = DumpPosition {
+            block_index,
+            l2_block_number: l2_block.number,
+            tx_index: None,
+        };
+        if block_index > 0 {
+            // The first block is started when creating the VM.
+            vm.start_new_l2_block(L2BlockEnv::from_l2_block_data(&l2_block));
+            if let Some(divergence) = take_divergence(position, None) {
+                return ReplayOutput {
+                    outcomes,
+                    divergence: Some(divergence),
+                };
+            }
+        }
+
+        for (tx_index, tx) in l2_block.txs.into_iter().enumerate() {
+            position.tx_index = Some(tx_index);
+            let tx_hash = tx.hash();
+            let (compression_result, result) =
+                vm.execute_transaction_with_bytecode_compression(tx, true);
+            let compression_error = compression_result.err().map(|err| err.to_string());
+            let outcome = TxOutcome {
+                position,
+                tx_hash,
+                result: result.result,
+                gas_used: result.statistics.gas_used,
+                compression_error,
+            };
+            tracing::debug!("{outcome}");
+            outcomes.push(outcome);
+
+            if let Some(divergence) = take_divergence(position, Some(tx_hash)) {
+                return ReplayOutput {
+                    outcomes,
+                    divergence: Some(divergence),
+                };
+            }
+        }
+    }
+    ReplayOutput {
+        outcomes,
+        divergence: None,
+    }
+}
+
+/// Truncates the dump so that the action at `position` is the last one.
+fn truncate_dump(mut dump: VmDump, position: DumpPosition) -> VmDump {
+    dump.l2_blocks.truncate(position.block_index + 1);
+    let last_block = dump
+        .l2_blocks
+        .last_mut()
+        .expect("position is outside the dump");
+    last_block
+        .txs
+        .truncate(position.tx_index.map_or(0, |idx| idx + 1));
+    dump
+}
+
+/// Checks whether replaying the dump on the shadow VM results in a divergence at the last action in the dump.
+fn reproduces_divergence(
+    dump: &VmDump,
+    replay_on_shadow_vm: &impl Fn(VmDump) -> ReplayOutput,
+) -> bool {
+    let last_block_index = dump.l2_blocks.len().checked_sub(1);
+    let last_tx_index = dump
+        .l2_blocks
+        .last()
+        .and_then(|block| block.txs.len().checked_sub(1));
+
+    // The dump storage may be incomplete, in which case the VM panics when accessing a missing storage slot.
+    let output = panic::catch_unwind(AssertUnwindSafe(|| replay_on_shadow_vm(dump.clone())));
+    let Ok(output) = output else {
+        tracing::warn!("Replaying the candidate dump panicked");
+        return false;
+    };
+    output.divergence.is_some_and(|divergence| {
+        Some(divergence.position.block_index) == last_block_index
+            && divergence.position.tx_index == last_tx_index
+    })
+}
+
+/// Shrinks the dump to the minimal prefix of L2 blocks and transactions that reproduces the first divergence
+/// between the legacy and fast VMs.
+pub(crate) fn minimize(dump: VmDump) -> anyhow::Result<(VmDump, Divergence)> {
+    minimize_with(dump, |dump| replay(dump, VmKind::Shadow))
+}
+
+/// Same as [`minimize()`], but with a custom function replaying dumps on the shadow VM.
+fn minimize_with(
+    dump: VmDump,
+    replay_on_shadow_vm: impl Fn(VmDump) -> ReplayOutput,
+) -> anyhow::Result<(VmDump, Divergence)> {
+    let output = replay_on_shadow_vm(dump.clone());
+    let mut divergence = output
+        .divergence
+        .context("replaying the dump didn't result in a VM divergence")?;
+    tracing::info!(
+        "First divergence is at {}; minimizing the dump",
+        divergence.position
+    );
+
+    // The dump created by the shadow VM on divergence has the minimal storage snapshot, so we try it first.
+    // If it doesn't reproduce the divergence for whatever reason, we fall back to truncating the original dump.
+    let shadow_dump = divergence.dump.clone();
+    if reproduces_divergence(&shadow_dump, &replay_on_shadow_vm) {
+        return Ok((shadow_dump, divergence));
+    }
+    tracing::info!("Dump created by the shadow VM doesn't reproduce the divergence; truncating the original dump");
+
+    let truncated_dump = truncate_dump(dump, divergence.position);
+    anyhow::ensure!(
+        reproduces_divergence(&truncated_dump, &replay_on_shadow_vm),
+        "truncated dump doesn't reproduce the divergence; it may be non-deterministic"
+    );
+    divergence.dump = truncated_dump.clone();
+    Ok((truncated_dump, divergence))
+}
+
+/// Returns the total number of transactions in the dump.
+pub(crate) fn tx_count(dump: &VmDump) -> usize {
+    dump.l2_blocks.iter().map(|block| block.txs.len()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, fs, path::Path};
+
+    use assert_matches::assert_matches;
+    use test_casing::test_casing;
+    use zksync_multivm::interface::{storage::StorageWithOverrides, utils::ShadowVm};
+    use zksync_types::{get_nonce_key, h256_to_u256, u256_to_h256};
+
+    use super::*;
+
+    /// Small dump with a single L2 block containing a single (reverted) transaction.
+    fn load_test_dump() -> VmDump {
+        // We rely on the fact that unit tests are executed from the crate directory.
+        let path =
+            Path::new("../../lib/multivm/tests/vm_dumps/estimate_fee_for_transfer_to_self.json");
+        let raw = fs::read_to_string(path).expect("failed reading VM dump");
+        serde_json::from_str(&raw).expect("failed deserializing VM dump")
+    }
+
+    /// Creates a dump with the specified number of transactions in each L2 block.
+    fn dump_with_blocks(tx_counts: &[usize]) -> VmDump {
+        let mut dump = load_test_dump();
+        let first_block = dump.l2_blocks[0].clone();
+        let tx = first_block.txs[0].clone();
+        dump.l2_blocks = tx_counts
+            .iter()
+            .enumerate()
+            .map(|(i, &tx_count)| L2BlockExecutionData {
+                number: first_block.number + i as u32,
+                txs: vec![tx.clone(); tx_count],
+                ..first_block.clone()
+            })
+            .collect();
+        dump
+    }
+
+    fn block_sizes(dump: &VmDump) -> Vec<usize> {
+        dump.l2_blocks.iter().map(|block| block.txs.len()).collect()
+    }
+
+    /// Replays the dump on the shadow VM, with the fast VM reading an incremented nonce of the first transaction initiator.
+    /// This makes the fast VM reject the first transaction, which diverges from the legacy VM behavior.
+    fn replay_with_tampered_fast_vm(dump: VmDump) -> ReplayOutput {
+        let initiator = dump.l2_blocks[0].txs[0].initiator_account();
+        let nonce_key = get_nonce_key(&initiator);
+        let main_storage = StorageWithOverrides::new(dump.storage.clone());
+        let mut fast_vm_storage = StorageWithOverrides::new(dump.storage);
+        let nonce = h256_to_u256(fast_vm_storage.read_value(&nonce_key));
+        fast_vm_storage.set_value(nonce_key, u256_to_h256(nonce + 1));
+
+        let vm = FastVmInstance::Shadowed(ShadowVm::with_custom_shadow(
+            dump.l1_batch_env,
+            dump.system_env,
+            StorageView::new(main_storage).to_rc_ptr(),
+            StorageView::new(fast_vm_storage).to_rc_ptr(),
+        ));
+        replay_shadowed(vm, dump.l2_blocks)
+    }
+
+    /// Minimizes the dump, tampering with the fast VM storage only for the specified 0-based replay calls.
+    fn minimize_with_tampering(
+        dump: VmDump,
+        tampered_calls: &[usize],
+    ) -> anyhow::Result<(VmDump, Divergence)> {
+        let call_idx = Cell::new(0);
+        minimize_with(dump, |dump| {
+            let idx = call_idx.replace(call_idx.get() + 1);
+            if tampered_calls.contains(&idx) {
+                replay_with_tampered_fast_vm(dump)
+            } else {
+                replay(dump, VmKind::Shadow)
+            }
+        })
+    }
+
+    fn assert_first_tx_divergence(dump: &VmDump, divergence: &Divergence) {
+        assert_eq!(
+            divergence.position,
+            DumpPosition {
+                block_index: 0,
+                l2_block_number: dump.l2_blocks[0].number,
+                tx_index: Some(0),
+            }
+        );
+        assert_eq!(divergence.tx_hash, Some(dump.l2_blocks[0].txs[0].hash()));
+        assert!(!divergence.errors.is_empty());
+    }
+
+    #[test]
+    fn truncating_dump_at_block_start() {
+        let dump = dump_with_blocks(&[2, 3, 1]);
+        let position = DumpPosition {
+            block_index: 1,
+            l2_block_number: dump.l2_blocks[1].number,
+            tx_index: None,
+        };
+        let truncated = truncate_dump(dump.clone(), position);
+        assert_eq!(block_sizes(&truncated), [2, 0]);
+        assert_eq!(truncated.l2_blocks[1].number, dump.l2_blocks[1].number);
+        assert_eq!(truncated.storage, dump.storage);
+    }
+
+    #[test]
+    fn truncating_dump_at_transaction() {
+        let dump = dump_with_blocks(&[2, 3, 1]);
+        let position = DumpPosition {
+            block_index: 1,
+            l2_block_number: dump.l2_blocks[1].number,
+            tx_index: Some(1),
+        };
+        let truncated = truncate_dump(dump.clone(), position);
+        assert_eq!(block_sizes(&truncated), [2, 2]);
+
+        let position = DumpPosition {
+            block_index: 0,
+            l2_block_number: dump.l2_blocks[0].number,
+            tx_index: Some(0),
+        };
+        let truncated = truncate_dump(dump.clone(), position);
+        assert_eq!(block_sizes(&truncated), [1]);
+
+        let position = DumpPosition {
+            block_index: 2,
+            l2_block_number: dump.l2_blocks[2].number,
+            tx_index: Some(0),
+        };
+        let truncated = truncate_dump(dump.clone(), position);
+        assert_eq!(truncated, dump);
+    }
+
+    #[test_casing(3, [VmKind::Legacy, VmKind::Fast, VmKind::Shadow])]
+    #[test]
+    fn replaying_dump(vm_kind: VmKind) {
+        let dump = load_test_dump();
+        let expected_tx_hash = dump.l2_blocks[0].txs[0].hash();
+        let output = replay(dump.clone(), vm_kind);
+
+        assert!(output.divergence.is_none(), "{:?}", output.divergence);
+        assert_eq!(output.outcomes.len(), 1);
+        let outcome = &output.outcomes[0];
+        assert_eq!(
+            outcome.position,
+            DumpPosition {
+                block_index: 0,
+                l2_block_number: dump.l2_blocks[0].number,
+                tx_index: Some(0),
+            }
+        );
+        assert_eq!(outcome.tx_hash, expected_tx_hash);
+        // The transaction has a low gas limit, so it's reverted.
+        assert_matches!(outcome.result, ExecutionResult::Revert { .. });
+        assert!(outcome.gas_used > 0);
+    }
+
+    #[test]
+    fn replaying_dump_with_divergence() {
+        let dump = dump_with_blocks(&[2, 3, 1]);
+        let output = replay_with_tampered_fast_vm(dump.clone());
+
+        // Replay stops after the diverging transaction.
+        assert_eq!(output.outcomes.len(), 1);
+        let divergence = output.divergence.expect("no divergence");
+        assert_first_tx_divergence(&dump, &divergence);
+        assert_eq!(block_sizes(&divergence.dump), [1]);
+    }
+
+    #[test]
+    fn minimizing_dump_with_divergence() {
+        let dump = dump_with_blocks(&[2, 3, 1]);
+        let (minimized_dump, divergence) = minimize_with_tampering(dump.clone(), &[0, 1]).unwrap();
+
+        assert_first_tx_divergence(&dump, &divergence);
+        // The dump created by the shadow VM reproduces the divergence, so it's returned as is.
+        assert_eq!(block_sizes(&minimized_dump), [1]);
+        assert_eq!(minimized_dump.l2_blocks[0].number, dump.l2_blocks[0].number);
+        assert_eq!(minimized_dump, divergence.dump);
+    }
+
+    #[test]
+    fn minimizing_dump_with_truncation_fallback() {
+        let dump = dump_with_blocks(&[2, 3, 1]);
+        // The second replay call checks the dump created by the shadow VM; it doesn't reproduce the divergence.
+        let (minimized_dump, divergence) = minimize_with_tampering(dump.clone(), &[0, 2]).unwrap();
+
+        assert_first_tx_divergence(&dump, &divergence);
+        assert_eq!(block_sizes(&minimized_dump), [1]);
+        assert_eq!(
+            minimized_dump,
+            truncate_dump(dump.clone(), divergence.position)
+        );
+        assert_eq!(minimized_dump.storage, dump.storage);
+        assert_eq!(minimized_dump, divergence.dump);
+    }
+
+    #[test]
+    fn minimizing_dump_with_non_reproducible_divergence() {
+        let dump = dump_with_blocks(&[2, 3, 1]);
+        let err = minimize_with_tampering(dump, &[0]).unwrap_err().to_string();
+        assert!(err.contains("doesn't reproduce the divergence"), "{err}");
+    }
+
+    #[test]
+    fn minimizing_dump_without_divergence() {
+        let err = minimize(load_test_dump()).unwrap_err().to_string();
+        assert!(err.contains("didn't result in a VM divergence"), "{err}");
+    }
+}
@@ -168,8 +168,11 @@ impl MainNodeBuilder {
 
     fn add_pk_signing_client_layer(mut self) -> anyhow::Result<Self> {
         let gas_adjuster = try_load_config!(self.configs.eth).gas_adjuster;
-        let operator = try_load_config!(self.wallets.operator);
-        let blob_operator = self.wallets.blob_operator.clone();
+        let operator = self
+            .wallets
+            .operator_signer()
+            .context("operator wallet or remote signer")?;
+        let blob_operator = self.wallets.blob_operator_signer();
         self.node.add_layer(PKSigningEthClientLayer::new(
             gas_adjuster,
             operator,
@@ -276,8 +279,14 @@ impl MainNodeBuilder {
                 .0 as usize,
             max_open_files: db_config.experimental.state_keeper_db_max_open_files,
         };
-        let state_keeper_layer =
+        let mut state_keeper_layer =
             StateKeeperLayer::new(db_config.state_keeper_db_path, rocksdb_options);
+        if let Some(sequencer_wallet) = &self.wallets.sequencer {
+            state_keeper_layer = state_keeper_layer.with_preconfirmation_signer(
+                sequencer_wallet.private_key().clone(),
+                self.genesis_config.l2_chain_id,
+            );
+        }
         self.node
             .add_layer(persistence_layer)
             .add_layer(mempool_io_layer)
@@ -456,6 +465,7 @@ impl MainNodeBuilder {
             websocket_requests_per_minute_limit: Some(
                 rpc_config.websocket_requests_per_minute_limit,
             ),
+            http_rate_limits: Some(rpc_config.http_rate_limits.clone()),
             request_timeout: rpc_config.request_timeout,
             with_extended_tracing: rpc_config.extended_api_tracing,
             // Pruning isn't supposed to be enabled for the main node at the moment, but we use a reasonable value just in case.
@@ -486,7 +496,9 @@ impl MainNodeBuilder {
     }
 
     fn add_ws_web3_api_layer(mut self) -> anyhow::Result<Self> {
-        let (internal_config_base, optional_config) = self.create_api_config()?;
+        let (internal_config_base, mut optional_config) = self.create_api_config()?;
+        // Not relevant for WS server, so we reset to prevent a logged warning.
+        optional_config.http_rate_limits = None;
 
         let api = self
             .configs
@@ -39,6 +39,7 @@ pub mod bytecode;
 pub mod commitment;
 mod conversions;
 mod errors;
+pub mod mempool;
 pub mod network;
 pub mod protocol_version;
 pub mod prover_dal;
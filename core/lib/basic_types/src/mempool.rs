@@ -0,0 +1,15 @@
+//! Mempool types shared between the configuration and the mempool implementation.
+
+use serde::{Deserialize, Serialize};
+
+/// Policy used to order L2 transactions from different accounts in the mempool.
+/// Transactions from the same account are always ordered by nonce.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MempoolOrdering {
+    /// Transactions are ordered by the time they were received.
+    #[default]
+    Fifo,
+    /// Transactions with the higher priority fee per gas go first; ties are broken by the time transactions were received.
+    PriorityFee,
+}
@@ -1,5 +1,6 @@
 use std::{
     collections::{HashMap, HashSet},
+    fmt,
     net::{Ipv6Addr, SocketAddr},
     num::{NonZeroU32, NonZeroUsize},
     str::FromStr,
@@ -159,6 +160,7 @@ pub enum Namespace {
     Pubsub,
     Snapshots,
     Unstable,
+    Trace,
 }
 
 impl Namespace {
@@ -271,8 +273,162 @@ impl WellKnown for MaxResponseSizeOverrides {
     const DE: Self::Deserializer = OrString(Entries::WELL_KNOWN.named("method", "size_mb"));
 }
 
+/// Parses comma-separated `<name>=<value>` entries.
+fn parse_named_entries<T>(s: &str, value_name: &str) -> anyhow::Result<HashMap<String, T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    let mut entries = HashMap::new();
+    for part in s.split(',') {
+        let (name, value) = part
+            .split_once('=')
+            .with_context(|| format!("Part `{part}` doesn't have form <name>=<{value_name}>"))?;
+        let name = name.trim();
+        let value = value
+            .trim()
+            .parse()
+            .map_err(Into::<anyhow::Error>::into)
+            .with_context(|| {
+                format!("`{value}` specified for `{name}` is not a valid {value_name}")
+            })?;
+        if entries.insert(name.to_owned(), value).is_some() {
+            anyhow::bail!("Entry for `{name}` is redefined");
+        }
+    }
+    Ok(entries)
+}
+
+/// Weights of RPC methods in compute units used by [`HttpRateLimitsConfig`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MethodWeights(HashMap<String, NonZeroU32>);
+
+impl<S: Into<String>> FromIterator<(S, NonZeroU32)> for MethodWeights {
+    fn from_iter<I: IntoIterator<Item = (S, NonZeroU32)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(method_name, weight)| (method_name.into(), weight))
+                .collect(),
+        )
+    }
+}
+
+impl ToEntries<String, NonZeroU32> for MethodWeights {
+    fn to_entries(&self) -> impl Iterator<Item = (&String, &NonZeroU32)> {
+        self.0.iter()
+    }
+}
+
+impl FromStr for MethodWeights {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_named_entries(s, "weight").map(Self)
+    }
+}
+
+impl WellKnown for MethodWeights {
+    type Deserializer = OrString<NamedEntries<String, NonZeroU32>>;
+    const DE: Self::Deserializer = OrString(Entries::WELL_KNOWN.named("method", "weight"));
+}
+
+impl MethodWeights {
+    /// Gets the weight of the specified method. Methods without an explicitly specified weight have the unit weight.
+    pub fn get(&self, method_name: &str) -> NonZeroU32 {
+        self.0.get(method_name).copied().unwrap_or(NonZeroU32::MIN)
+    }
+}
+
+/// Known API keys used by [`HttpRateLimitsConfig`], mapped from their human-readable names.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ApiKeys(HashMap<String, String>);
+
+impl fmt::Debug for ApiKeys {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Do not expose API keys in logs.
+        formatter.debug_list().entries(self.0.keys()).finish()
+    }
+}
+
+impl<N: Into<String>, K: Into<String>> FromIterator<(N, K)> for ApiKeys {
+    fn from_iter<I: IntoIterator<Item = (N, K)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(name, key)| (name.into(), key.into()))
+                .collect(),
+        )
+    }
+}
+
+impl ToEntries<String, String> for ApiKeys {
+    fn to_entries(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.0.iter()
+    }
+}
+
+impl FromStr for ApiKeys {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_named_entries(s, "key").map(Self)
+    }
+}
+
+impl WellKnown for ApiKeys {
+    type Deserializer = OrString<NamedEntries<String, String>>;
+    const DE: Self::Deserializer = OrString(Entries::WELL_KNOWN.named("name", "key"));
+}
+
+impl ApiKeys {
+    /// Iterates over `(name, key)` pairs.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, &str)> + '_ {
+        self.0
+            .iter()
+            .map(|(name, key)| (name.as_str(), key.as_str()))
+    }
+}
+
+/// Built-in limits for the HTTP JSON-RPC server enforced per API key. Budgets are replenished continuously,
+/// so that each API key can spend at most the specified amount over any minute. Requests exceeding a budget are rejected
+/// with the 429 error code. Limits are disabled unless at least one of them is specified.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct HttpRateLimitsConfig {
+    /// Known API keys keyed by their names; names are used in metric labels. Requests with a missing or unknown API key
+    /// share a single `anonymous` budget.
+    #[config(default, secret)]
+    pub api_keys: ApiKeys,
+    /// Name of the HTTP header containing the API key.
+    #[config(default_t = "x-api-key".into())]
+    pub api_key_header: String,
+    /// Whether the API key may be supplied as the URL path, e.g. `http://localhost:3050/<api_key>`.
+    #[config(default)]
+    pub api_key_in_path: bool,
+    /// Maximum number of requests per minute for each API key. Each call in a batch request is counted separately.
+    pub requests_per_minute: Option<NonZeroU32>,
+    /// Maximum number of compute units per minute for each API key. Each call costs the weight of the called method.
+    pub compute_units_per_minute: Option<NonZeroU32>,
+    /// Maximum total size of responses per minute for each API key. Since the response size is only known
+    /// after a call is executed, the budget can be temporarily overdrawn by a single call.
+    pub response_size_per_minute: Option<ByteSize>,
+    /// Weights of RPC methods in compute units. Methods not mentioned here have the unit weight.
+    #[config(default)]
+    pub method_weights: MethodWeights,
+}
+
+impl HttpRateLimitsConfig {
+    /// Checks whether any of the limits is specified.
+    pub fn is_enabled(&self) -> bool {
+        self.requests_per_minute.is_some()
+            || self.compute_units_per_minute.is_some()
+            || self.response_size_per_minute.is_some()
+    }
+}
+
 /// Response size limits for JSON-RPC servers.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MaxResponseSize {
     /// Global limit applied to all RPC methods. Measured in bytes.
     pub global: usize,
@@ -280,6 +436,13 @@ pub struct MaxResponseSize {
     pub overrides: MaxResponseSizeOverrides,
 }
 
+impl MaxResponseSize {
+    /// Gets the limit in bytes for the specified method, taking overrides into account.
+    pub fn for_method(&self, method_name: &str) -> usize {
+        self.overrides.get(method_name).unwrap_or(self.global)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(derive(Default))]
 pub struct Web3JsonRpcConfig {
@@ -314,6 +477,14 @@ pub struct Web3JsonRpcConfig {
     /// Tx nonce: how far ahead from the committed nonce can it be.
     #[config(default_t = 50)]
     pub max_nonce_ahead: u32,
+    /// Maximum number of pending (i.e., not included into a block) transactions per initiator account.
+    /// If not set, the number of pending transactions is only limited by `max_nonce_ahead`.
+    #[config(default)]
+    pub max_pending_txs_per_account: Option<NonZeroU32>,
+    /// Minimum bump of `max_fee_per_gas` and `max_priority_fee_per_gas` (in percent) required to replace
+    /// a pending transaction with the same nonce. Zero means that any replacement is accepted.
+    #[config(default)]
+    pub replacement_fee_bump_percent: u32,
     /// The multiplier to use when suggesting gas price. Should be higher than one,
     /// otherwise if the L1 prices soar, the suggested gas price won't be sufficient to be included in block.
     /// This value is only used when there is no open batch.
@@ -362,6 +533,9 @@ pub struct Web3JsonRpcConfig {
     /// Limit for fee history block range.
     #[config(default_t = 1_024)]
     pub fee_history_limit: u64,
+    /// Maximum number of L2 blocks that can be queried in a single `trace_filter` call.
+    #[config(default_t = 1_000)]
+    pub trace_filter_block_range_limit: u32,
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
     #[config(default_t = NonZeroUsize::new(500).unwrap())]
     pub max_batch_request_size: NonZeroUsize,
@@ -375,12 +549,19 @@ pub struct Web3JsonRpcConfig {
     pub max_response_body_size_overrides: MaxResponseSizeOverrides,
     /// Maximum number of requests per minute for the WebSocket server.
     /// The value is per active connection.
-    /// Not used for the HTTP server; for it, rate limiting is expected to be configured on the infra level.
+    /// Not used for the HTTP server; for it, see `http_rate_limits`.
     #[config(default_t = NonZeroU32::new(6_000).unwrap())]
     pub websocket_requests_per_minute_limit: NonZeroU32,
+    /// Built-in per-API key limits for the HTTP server. Alternatively, rate limiting can be configured on the infra level.
+    #[config(nest)]
+    pub http_rate_limits: HttpRateLimitsConfig,
     /// Server-side request timeout. A request will be dropped with a 503 error code if its execution exceeds this limit.
     /// If not specified, no server-side request timeout is enforced.
     pub request_timeout: Option<Duration>,
+    /// Maximum time to wait for a preconfirmation of a transaction submitted via `unstable_sendRawTransactionWithDetailedOutput`
+    /// before responding without it. Only used if the node issues preconfirmations.
+    #[config(default_t = Duration::from_secs(5))]
+    pub preconfirmation_timeout: Duration,
     /// Tree API URL used to proxy `getProof` calls to the tree. For external nodes, it's not necessary to specify
     /// since the server can communicate with the tree in-process.
     #[config(alias = "tree_api_remote_url")]
@@ -499,6 +680,17 @@ mod tests {
         assert_eq!(scaled.get("eth_getTransactionReceipt"), Some(usize::MAX));
         assert_eq!(scaled.get("zks_getProof"), Some(32_000));
         assert_eq!(scaled.get("eth_blockNumber"), None);
+
+        for invalid_overrides in [
+            "eth_call",
+            "eth_call=what",
+            "eth_call=0",
+            "eth_call=1,eth_call=None",
+        ] {
+            invalid_overrides
+                .parse::<MaxResponseSizeOverrides>()
+                .unwrap_err();
+        }
     }
 
     fn expected_config() -> ApiConfig {
@@ -512,6 +704,8 @@ mod tests {
                 subscriptions_limit: 10000,
                 pubsub_polling_interval: Duration::from_millis(200),
                 max_nonce_ahead: 5,
+                max_pending_txs_per_account: NonZeroU32::new(10),
+                replacement_fee_bump_percent: 10,
                 estimate_gas_scale_factor: 1.0f64,
                 gas_price_scale_factor: 1.2,
                 estimate_gas_acceptable_overestimation: 1000,
@@ -524,6 +718,7 @@ mod tests {
                 latest_values_cache_size: ByteSize::new(256, SizeUnit::MiB),
                 latest_values_max_block_lag: NonZeroU32::new(50).unwrap(),
                 fee_history_limit: 100,
+                trace_filter_block_range_limit: 500,
                 max_batch_request_size: NonZeroUsize::new(200).unwrap(),
                 max_response_body_size: ByteSize::new(15, SizeUnit::MiB),
                 max_response_body_size_overrides: [
@@ -534,7 +729,24 @@ mod tests {
                 .into_iter()
                 .collect(),
                 websocket_requests_per_minute_limit: NonZeroU32::new(10).unwrap(),
+                http_rate_limits: HttpRateLimitsConfig {
+                    api_keys: [("alice", "0123456789abcdef"), ("bob", "fedcba9876543210")]
+                        .into_iter()
+                        .collect(),
+                    api_key_header: "x-rpc-key".into(),
+                    api_key_in_path: true,
+                    requests_per_minute: NonZeroU32::new(600),
+                    compute_units_per_minute: NonZeroU32::new(6_000),
+                    response_size_per_minute: Some(ByteSize::new(100, SizeUnit::MiB)),
+                    method_weights: [
+                        ("eth_call", NonZeroU32::new(10).unwrap()),
+                        ("debug_traceCall", NonZeroU32::new(100).unwrap()),
+                    ]
+                    .into_iter()
+                    .collect(),
+                },
                 request_timeout: Some(Duration::from_secs(20)),
+                preconfirmation_timeout: Duration::from_secs(2),
                 tree_api_url: Some("http://tree/".into()),
                 mempool_cache_update_interval: Duration::from_millis(50),
                 mempool_cache_size: 10000,
@@ -570,6 +782,8 @@ mod tests {
             API_WEB3_JSON_RPC_SUBSCRIPTIONS_LIMIT=10000
             API_WEB3_JSON_RPC_PUBSUB_POLLING_INTERVAL=200
             API_WEB3_JSON_RPC_MAX_NONCE_AHEAD=5
+            API_WEB3_JSON_RPC_MAX_PENDING_TXS_PER_ACCOUNT=10
+            API_WEB3_JSON_RPC_REPLACEMENT_FEE_BUMP_PERCENT=10
             API_WEB3_JSON_RPC_GAS_PRICE_SCALE_FACTOR=1.2
             API_WEB3_JSON_RPC_GAS_PRICE_SCALE_FACTOR_OPEN_BATCH=1.3
             API_WEB3_JSON_RPC_ESTIMATE_GAS_OPTIMIZE_SEARCH=true
@@ -583,13 +797,22 @@ mod tests {
             API_WEB3_JSON_RPC_MAX_TX_SIZE=1000000
             API_WEB3_JSON_RPC_VM_CONCURRENCY_LIMIT=512
             API_WEB3_JSON_RPC_REQUEST_TIMEOUT="20 sec"
+            API_WEB3_JSON_RPC_PRECONFIRMATION_TIMEOUT="2 sec"
             API_WEB3_JSON_RPC_FACTORY_DEPS_CACHE_SIZE_MB=128
             API_WEB3_JSON_RPC_INITIAL_WRITES_CACHE_SIZE_MB=32
             API_WEB3_JSON_RPC_LATEST_VALUES_CACHE_SIZE_MB=256
             API_WEB3_JSON_RPC_LATEST_VALUES_MAX_BLOCK_LAG=50
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
+            API_WEB3_JSON_RPC_TRACE_FILTER_BLOCK_RANGE_LIMIT=500
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
+            API_WEB3_JSON_RPC_HTTP_RATE_LIMITS_API_KEYS="alice=0123456789abcdef,bob=fedcba9876543210"
+            API_WEB3_JSON_RPC_HTTP_RATE_LIMITS_API_KEY_HEADER=x-rpc-key
+            API_WEB3_JSON_RPC_HTTP_RATE_LIMITS_API_KEY_IN_PATH=true
+            API_WEB3_JSON_RPC_HTTP_RATE_LIMITS_REQUESTS_PER_MINUTE=600
+            API_WEB3_JSON_RPC_HTTP_RATE_LIMITS_COMPUTE_UNITS_PER_MINUTE=6000
+            API_WEB3_JSON_RPC_HTTP_RATE_LIMITS_RESPONSE_SIZE_PER_MINUTE_MB=100
+            API_WEB3_JSON_RPC_HTTP_RATE_LIMITS_METHOD_WEIGHTS="eth_call=10, debug_traceCall=100"
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_SIZE=10000
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_UPDATE_INTERVAL=50
             API_CONTRACT_VERIFICATION_PORT="3070"
@@ -624,8 +847,21 @@ mod tests {
             req_entities_limit: 10000
             filters_limit: 10000
             fee_history_limit: 100
+            trace_filter_block_range_limit: 500
             subscriptions_limit: 10000
             websocket_requests_per_minute_limit: 10
+            http_rate_limits:
+              api_keys:
+                alice: "0123456789abcdef"
+                bob: "fedcba9876543210"
+              api_key_header: x-rpc-key
+              api_key_in_path: true
+              requests_per_minute: 600
+              compute_units_per_minute: 6000
+              response_size_per_minute_mb: 100
+              method_weights:
+                eth_call: 10
+                debug_traceCall: 100
             vm_concurrency_limit: 512
             vm_execution_cache_misses_limit: 1000
             max_response_body_size_mb: 15
@@ -642,6 +878,8 @@ mod tests {
             mempool_cache_update_interval: 50
             pubsub_polling_interval: 200
             max_nonce_ahead: 5
+            max_pending_txs_per_account: 10
+            replacement_fee_bump_percent: 10
             gas_price_scale_factor: 1.2
             gas_price_scale_factor_open_batch: 1.3
             estimate_gas_scale_factor: 1
@@ -657,6 +895,7 @@ mod tests {
             estimate_gas_optimize_search: true
             eth_call_gas_cap: null
             request_timeout_sec: 20
+            preconfirmation_timeout_ms: 2000
             tree_api_url: "http://tree/"
           prometheus:
             listener_port: 3312
@@ -687,8 +926,25 @@ mod tests {
             req_entities_limit: 10000
             filters_limit: 10000
             fee_history_limit: 100
+            trace_filter_block_range_limit: 500
             subscriptions_limit: 10000
             websocket_requests_per_minute_limit: 10
+            http_rate_limits:
+              api_keys:
+                - name: alice
+                  key: "0123456789abcdef"
+                - name: bob
+                  key: "fedcba9876543210"
+              api_key_header: x-rpc-key
+              api_key_in_path: true
+              requests_per_minute: 600
+              compute_units_per_minute: 6000
+              response_size_per_minute: 100 MB
+              method_weights:
+                - method: eth_call
+                  weight: 10
+                - method: debug_traceCall
+                  weight: 100
             vm_concurrency_limit: 512
             vm_execution_cache_misses_limit: 1000
             max_response_body_size: 15 MB
@@ -705,6 +961,8 @@ mod tests {
             mempool_cache_update_interval: 50
             pubsub_polling_interval: 200ms
             max_nonce_ahead: 5
+            max_pending_txs_per_account: 10
+            replacement_fee_bump_percent: 10
             gas_price_scale_factor: 1.2
             gas_price_scale_factor_open_batch: 1.3
             estimate_gas_scale_factor: 1
@@ -720,6 +978,7 @@ mod tests {
             estimate_gas_optimize_search: true
             eth_call_gas_cap: null
             request_timeout: 20s
+            preconfirmation_timeout: 2s
             tree_api_url: "http://tree/"
           prometheus:
             listener_port: 3312
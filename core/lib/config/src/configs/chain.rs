@@ -1,4 +1,8 @@
-use std::{collections::HashSet, time::Duration};
+use std::{
+    collections::HashSet,
+    num::{NonZeroU32, NonZeroU64},
+    time::Duration,
+};
 
 use serde::{Deserialize, Serialize};
 use smart_config::{
@@ -6,7 +10,7 @@ use smart_config::{
     metadata::{SizeUnit, TimeUnit},
     ByteSize, DescribeConfig, DeserializeConfig,
 };
-use zksync_basic_types::Address;
+use zksync_basic_types::{mempool::MempoolOrdering, Address};
 
 use crate::utils::{Fallback, ZERO_TO_ONE};
 
@@ -90,6 +94,22 @@ pub struct SealCriteriaConfig {
     /// the recursion layers' circuits.
     #[config(default_t = 31_100)]
     pub max_circuits_per_batch: usize,
+    /// Maximum gas that transactions targeting a single contract may use in an L2 block. Once the limit is hit,
+    /// further transactions to the contract are postponed to the next L2 block. If not set, gas is not limited.
+    #[config(default)]
+    pub max_gas_per_contract_per_l2_block: Option<NonZeroU64>,
+    /// Maximum number of transactions targeting a single contract in an L2 block. If not set, the number of transactions
+    /// is not limited.
+    #[config(default)]
+    pub max_txs_per_contract_per_l2_block: Option<NonZeroU32>,
+    /// Maximum gas that transactions targeting a single contract may use in an L1 batch. Once the limit is hit,
+    /// the batch is sealed. If not set, gas is not limited.
+    #[config(default)]
+    pub max_gas_per_contract_per_batch: Option<NonZeroU64>,
+    /// Maximum number of transactions targeting a single contract in an L1 batch. If not set, the number of transactions
+    /// is not limited.
+    #[config(default)]
+    pub max_txs_per_contract_per_batch: Option<NonZeroU32>,
 }
 
 impl SealCriteriaConfig {
@@ -106,6 +126,10 @@ impl SealCriteriaConfig {
             close_block_at_eth_params_percentage: 0.95,
             close_block_at_gas_percentage: 0.95,
             max_circuits_per_batch: 24100,
+            max_gas_per_contract_per_l2_block: None,
+            max_txs_per_contract_per_l2_block: None,
+            max_gas_per_contract_per_batch: None,
+            max_txs_per_contract_per_batch: None,
         }
     }
 }
@@ -239,6 +263,11 @@ pub struct MempoolConfig {
     /// Minor version from which the high priority L2 transactions are allowed and prioritized.
     #[config(default)]
     pub high_priority_l2_tx_protocol_version: Option<u64>,
+    /// Policy used to order L2 transactions from different accounts: `fifo` or `priority_fee`.
+    /// With `priority_fee`, the declared `max_priority_fee_per_gas` only affects the inclusion order and eviction;
+    /// it is not charged on top of the base fee.
+    #[config(default, with = Serde![str])]
+    pub ordering: MempoolOrdering,
 }
 
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
@@ -294,6 +323,10 @@ mod tests {
                 reject_tx_at_gas_percentage: 0.5,
                 max_pubdata_per_batch: ByteSize(131_072),
                 max_circuits_per_batch: 24100,
+                max_gas_per_contract_per_l2_block: NonZeroU64::new(10_000_000),
+                max_txs_per_contract_per_l2_block: NonZeroU32::new(20),
+                max_gas_per_contract_per_batch: NonZeroU64::new(50_000_000),
+                max_txs_per_contract_per_batch: NonZeroU32::new(100),
             },
             l1_batch_commit_deadline: Duration::from_millis(2500),
             l2_block_commit_deadline: Duration::from_millis(1000),
@@ -337,6 +370,10 @@ mod tests {
             CHAIN_STATE_KEEPER_MAX_GAS_PER_BATCH="200000000"
             CHAIN_STATE_KEEPER_MAX_PUBDATA_PER_BATCH="131072"
             CHAIN_STATE_KEEPER_MAX_CIRCUITS_PER_BATCH="24100"
+            CHAIN_STATE_KEEPER_MAX_GAS_PER_CONTRACT_PER_L2_BLOCK="10000000"
+            CHAIN_STATE_KEEPER_MAX_TXS_PER_CONTRACT_PER_L2_BLOCK="20"
+            CHAIN_STATE_KEEPER_MAX_GAS_PER_CONTRACT_PER_BATCH="50000000"
+            CHAIN_STATE_KEEPER_MAX_TXS_PER_CONTRACT_PER_BATCH="100"
             CHAIN_STATE_KEEPER_FEE_MODEL_VERSION="V2"
             CHAIN_STATE_KEEPER_VALIDATION_COMPUTATIONAL_GAS_LIMIT="10000000"
             CHAIN_STATE_KEEPER_SAVE_CALL_TRACES="false"
@@ -377,6 +414,10 @@ mod tests {
           validation_computational_gas_limit: 10000000
           save_call_traces: false
           max_circuits_per_batch: 24100
+          max_gas_per_contract_per_l2_block: 10000000
+          max_txs_per_contract_per_l2_block: 20
+          max_gas_per_contract_per_batch: 50000000
+          max_txs_per_contract_per_batch: 100
           l2_block_max_payload_size: 1000000
           protective_reads_persistence_enabled: true
           deployment_allowlist:
@@ -415,6 +456,10 @@ mod tests {
           validation_computational_gas_limit: 10000000
           save_call_traces: false
           max_circuits_per_batch: 24100
+          max_gas_per_contract_per_l2_block: 10000000
+          max_txs_per_contract_per_l2_block: 20
+          max_gas_per_contract_per_batch: 50000000
+          max_txs_per_contract_per_batch: 100
           l2_block_max_payload_size: 1000000 bytes
           protective_reads_persistence_enabled: true
           deployment_allowlist:
@@ -439,6 +484,7 @@ mod tests {
             l1_to_l2_txs_paused: false,
             high_priority_l2_tx_initiator: Some(Address::from_slice(&[0x01; 20])),
             high_priority_l2_tx_protocol_version: Some(29),
+            ordering: MempoolOrdering::PriorityFee,
         }
     }
 
@@ -454,6 +500,7 @@ mod tests {
             CHAIN_MEMPOOL_L1_TO_L2_TXS_PAUSED="false"
             CHAIN_MEMPOOL_HIGH_PRIORITY_L2_TX_INITIATOR="0x0101010101010101010101010101010101010101"
             CHAIN_MEMPOOL_HIGH_PRIORITY_L2_TX_PROTOCOL_VERSION="29"
+            CHAIN_MEMPOOL_ORDERING="priority_fee"
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -474,6 +521,7 @@ mod tests {
           l1_to_l2_txs_paused: false
           high_priority_l2_tx_initiator: "0x0101010101010101010101010101010101010101"
           high_priority_l2_tx_protocol_version: 29
+          ordering: priority_fee
         "#;
 
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -493,6 +541,7 @@ mod tests {
           l1_to_l2_txs_paused: false
           high_priority_l2_tx_initiator: "0x0101010101010101010101010101010101010101"
           high_priority_l2_tx_protocol_version: 29
+          ordering: priority_fee
         "#;
 
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -38,6 +38,9 @@ pub struct AvailGasRelayConfig {
     pub gas_relay_api_url: String,
     #[config(default_t = 5)]
     pub max_retries: usize,
+    /// URL of an Avail node used to fetch dispatched blobs, since the gas relay API doesn't provide access
+    /// to submitted data. If not set, fetching blobs is not supported.
+    pub retrieval_node_url: Option<String>,
 }
 
 #[derive(Clone, Debug, DescribeConfig, DeserializeConfig)]
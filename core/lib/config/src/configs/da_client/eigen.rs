@@ -1,6 +1,8 @@
+use std::path::PathBuf;
+
 use serde::Deserialize;
 use smart_config::{
-    de::{FromSecretString, Optional, Serde},
+    de::{Delimited, FromSecretString, Optional, Serde},
     DescribeConfig, DeserializeConfig,
 };
 use zksync_basic_types::{secrets::PrivateKey, url::SensitiveUrl};
@@ -24,6 +26,14 @@ pub struct EigenConfig {
     pub operator_state_retriever_addr: String,
     /// Address of the registry coordinator
     pub registry_coordinator_addr: String,
+    /// Address of the EigenDA relay registry. Required to fetch dispersed blobs from relays
+    pub relay_registry_addr: Option<String>,
+    /// Keys of the relays to fetch dispersed blobs from
+    #[serde(default)]
+    #[config(default, with = Delimited(","))]
+    pub relay_client_keys: Vec<u32>,
+    /// Path to the file with G1 SRS points used to verify fetched blobs. Required to fetch dispersed blobs from relays
+    pub srs_points_path: Option<PathBuf>,
 }
 
 /// Configuration for the EigenDA secrets.
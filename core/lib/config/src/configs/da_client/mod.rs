@@ -25,7 +25,7 @@ impl From<AvailConfig> for DAClientConfig {
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
+    use std::{path::Path, time::Duration};
 
     use secrecy::ExposeSecret;
     use smart_config::{
@@ -52,6 +52,9 @@ mod tests {
           DA_MODE="GCS"
           DA_MAX_RETRIES="5"
           DA_LOCAL_MIRROR_PATH="/var/cache"
+          DA_COMPRESS_OBJECTS="false"
+          DA_ENCRYPTION_KEY_PATH="/path/to/encryption.key"
+          DA_ENCRYPTED_BUCKETS="data_availability"
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -76,6 +79,9 @@ mod tests {
           file_backed_base_path: ./chains/era/artifacts/
           max_retries: 10
           local_mirror_path: /var/cache
+          compress_objects: false
+          encryption_key_path: null
+          encrypted_buckets: []
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
 
@@ -156,6 +162,7 @@ mod tests {
           avail_client_type: GasRelay
           gas_relay_api_url: https://lens-turbo-api.availproject.org
           max_retries: 4
+          retrieval_node_url: wss://turing-rpc.avail.so/ws
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
 
@@ -177,6 +184,10 @@ mod tests {
             "https://lens-turbo-api.availproject.org"
         );
         assert_eq!(client.max_retries, 4);
+        assert_eq!(
+            client.retrieval_node_url.as_deref(),
+            Some("wss://turing-rpc.avail.so/ws")
+        );
     }
 
     #[test]
@@ -188,6 +199,7 @@ mod tests {
             gas_relay:
               gas_relay_api_url: https://lens-turbo-api.availproject.org
               max_retries: 4
+              retrieval_node_url: wss://turing-rpc.avail.so/ws
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
 
@@ -299,6 +311,9 @@ mod tests {
           DA_CERT_VERIFIER_ROUTER_ADDR="0x0000000000000000000000000000000000000123"
           DA_OPERATOR_STATE_RETRIEVER_ADDR="0x0000000000000000000000000000000000000124"
           DA_REGISTRY_COORDINATOR_ADDR="0x0000000000000000000000000000000000000125"
+          DA_RELAY_REGISTRY_ADDR="0x0000000000000000000000000000000000000126"
+          DA_RELAY_CLIENT_KEYS="0,1,2"
+          DA_SRS_POINTS_PATH="/resources/g1.point"
           DA_BLOB_VERSION="0"
         "#;
         let env = Environment::from_dotenv("test.env", env)
@@ -329,6 +344,19 @@ mod tests {
             config.registry_coordinator_addr,
             "0x0000000000000000000000000000000000000125"
         );
+        assert_eigen_relay_config(&config);
+    }
+
+    fn assert_eigen_relay_config(config: &EigenConfig) {
+        assert_eq!(
+            config.relay_registry_addr.as_deref(),
+            Some("0x0000000000000000000000000000000000000126")
+        );
+        assert_eq!(config.relay_client_keys, [0, 1, 2]);
+        assert_eq!(
+            config.srs_points_path.as_deref(),
+            Some(Path::new("/resources/g1.point"))
+        );
     }
 
     #[test]
@@ -341,6 +369,9 @@ mod tests {
             operator_state_retriever_addr: "0x0000000000000000000000000000000000000124"
             registry_coordinator_addr: "0x0000000000000000000000000000000000000125"
             blob_version: 0
+            relay_registry_addr: "0x0000000000000000000000000000000000000126"
+            relay_client_keys: [0, 1, 2]
+            srs_points_path: /resources/g1.point
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
 
@@ -374,6 +405,7 @@ mod tests {
             config.registry_coordinator_addr,
             "0x0000000000000000000000000000000000000125"
         );
+        assert_eigen_relay_config(config);
     }
 
     #[test]
@@ -386,6 +418,9 @@ mod tests {
             cert_verifier_router_addr: "0x0000000000000000000000000000000000000123"
             operator_state_retriever_addr: "0x0000000000000000000000000000000000000124"
             registry_coordinator_addr: "0x0000000000000000000000000000000000000125"
+            relay_registry_addr: "0x0000000000000000000000000000000000000126"
+            relay_client_keys: [0, 1, 2]
+            srs_points_path: /resources/g1.point
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
 
@@ -54,6 +54,7 @@ impl EthConfig {
                 time_in_mempool_multiplier_cap: None,
                 precommit_params: None,
                 force_use_validator_timelock: false,
+                fee_bumping: FeeBumpingConfig::default(),
             },
             gas_adjuster: GasAdjusterConfig {
                 default_priority_fee_per_gas: 1000000000,
@@ -177,6 +178,9 @@ pub struct SenderConfig {
     /// Allow to force change the validator timelock address.
     #[config(default)]
     pub force_use_validator_timelock: bool,
+    /// Strategies used to escalate fees of in-flight transactions that must be resent.
+    #[config(nest)]
+    pub fee_bumping: FeeBumpingConfig,
 }
 
 /// We send precommit if l2_blocks_to_aggregate OR deadline_sec passed since last precommit or beginning of batch.
@@ -186,6 +190,66 @@ pub struct PrecommitParams {
     pub deadline: Duration,
 }
 
+/// Fee bumping strategies for each operator type. Allows to trade cost against inclusion latency separately
+/// for commit, prove and execute transactions and for Gateway settlement.
+#[derive(Debug, Clone, Default, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct FeeBumpingConfig {
+    /// Strategy for transactions sent by the main operator without blobs.
+    #[config(nest)]
+    pub non_blob: FeeBumpingStrategy,
+    /// Strategy for transactions sent by the blob operator.
+    #[config(nest)]
+    pub blob: FeeBumpingStrategy,
+    /// Strategy for transactions sent to Gateway.
+    #[config(nest)]
+    pub gateway: FeeBumpingStrategy,
+}
+
+/// Strategy escalating fees of an in-flight transaction on resend. Regardless of the strategy, each resend increases fees
+/// by at least the minimum replacement bump accepted by L1 nodes.
+///
+/// Strategies other than `GasAdjuster` compute fees from the first attempt of the transaction or the fees suggested
+/// by the gas adjuster, and stop resending once the max fee per gas (i.e., base fee plus priority fee) would exceed
+/// `max_fee_per_gas`.
+#[derive(Debug, Clone, Default, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(tag = "strategy")]
+pub enum FeeBumpingStrategy {
+    /// Fees are recomputed from the gas adjuster, and a resend is skipped if they don't exceed the previous fees
+    /// by the minimum replacement bump.
+    #[default]
+    #[config(default)]
+    GasAdjuster,
+    /// Fees of the first attempt (or the suggested fees, if they are higher) are increased by a fixed share
+    /// on each resend.
+    Linear {
+        /// Increase per resend as a percentage of the first attempt fees.
+        #[config(default_t = 20)]
+        step_percent: u32,
+        /// Max fee per gas in wei that the strategy is allowed to use.
+        max_fee_per_gas: u64,
+    },
+    /// Fees of the first attempt (or the suggested fees, if they are higher) are multiplied by a fixed factor
+    /// on each resend.
+    Exponential {
+        /// Multiplier applied on each resend.
+        #[config(default_t = 1.5)]
+        multiplier: f64,
+        /// Max fee per gas in wei that the strategy is allowed to use.
+        max_fee_per_gas: u64,
+    },
+    /// Suggested fees are multiplied by a factor growing linearly with the time since the first send,
+    /// so that it reaches the maximum multiplier at the inclusion deadline.
+    Deadline {
+        /// Number of L1 blocks since the first send in which the transaction should be included.
+        target_inclusion_blocks: u32,
+        /// Multiplier over the suggested fees used at and after the deadline.
+        #[config(default_t = 3.0)]
+        max_multiplier: f64,
+        /// Max fee per gas in wei that the strategy is allowed to use.
+        max_fee_per_gas: u64,
+    },
+}
+
 impl PrecommitParams {
     pub fn fast_precommit() -> Self {
         Self {
@@ -301,6 +365,21 @@ mod tests {
                     deadline: Duration::from_secs(1),
                 }),
                 force_use_validator_timelock: false,
+                fee_bumping: FeeBumpingConfig {
+                    non_blob: FeeBumpingStrategy::Linear {
+                        step_percent: 15,
+                        max_fee_per_gas: 200_000_000_000,
+                    },
+                    blob: FeeBumpingStrategy::Exponential {
+                        multiplier: 2.0,
+                        max_fee_per_gas: 500_000_000_000,
+                    },
+                    gateway: FeeBumpingStrategy::Deadline {
+                        target_inclusion_blocks: 10,
+                        max_multiplier: 4.0,
+                        max_fee_per_gas: 100_000_000_000,
+                    },
+                },
             },
             gas_adjuster: GasAdjusterConfig {
                 default_priority_fee_per_gas: 20000000000,
@@ -365,6 +444,16 @@ mod tests {
             ETH_SENDER_SENDER_PRECOMMIT_PARAMS_L2_BLOCKS_TO_AGGREGATE="1"
             ETH_SENDER_SENDER_PRECOMMIT_PARAMS_DEADLINE="1 sec"
             ETH_SENDER_SENDER_TIME_IN_MEMPOOL_MULTIPLIER_CAP="10"
+            ETH_SENDER_SENDER_FEE_BUMPING_NON_BLOB_STRATEGY="Linear"
+            ETH_SENDER_SENDER_FEE_BUMPING_NON_BLOB_STEP_PERCENT="15"
+            ETH_SENDER_SENDER_FEE_BUMPING_NON_BLOB_MAX_FEE_PER_GAS="200000000000"
+            ETH_SENDER_SENDER_FEE_BUMPING_BLOB_STRATEGY="Exponential"
+            ETH_SENDER_SENDER_FEE_BUMPING_BLOB_MULTIPLIER="2"
+            ETH_SENDER_SENDER_FEE_BUMPING_BLOB_MAX_FEE_PER_GAS="500000000000"
+            ETH_SENDER_SENDER_FEE_BUMPING_GATEWAY_STRATEGY="Deadline"
+            ETH_SENDER_SENDER_FEE_BUMPING_GATEWAY_TARGET_INCLUSION_BLOCKS="10"
+            ETH_SENDER_SENDER_FEE_BUMPING_GATEWAY_MAX_MULTIPLIER="4"
+            ETH_SENDER_SENDER_FEE_BUMPING_GATEWAY_MAX_FEE_PER_GAS="100000000000"
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -404,6 +493,20 @@ mod tests {
             precommit_params:
               l2_blocks_to_aggregate: 1
               deadline: 1 sec
+            fee_bumping:
+              non_blob:
+                strategy: Linear
+                step_percent: 15
+                max_fee_per_gas: 200000000000
+              blob:
+                strategy: Exponential
+                multiplier: 2.0
+                max_fee_per_gas: 500000000000
+              gateway:
+                strategy: Deadline
+                target_inclusion_blocks: 10
+                max_multiplier: 4.0
+                max_fee_per_gas: 100000000000
           gas_adjuster:
             default_priority_fee_per_gas: 20000000000
             max_base_fee_samples: 10000
@@ -461,6 +564,20 @@ mod tests {
             precommit_params:
               l2_blocks_to_aggregate: 1
               deadline: 1 sec
+            fee_bumping:
+              non_blob:
+                strategy: Linear
+                step_percent: 15
+                max_fee_per_gas: 200000000000
+              blob:
+                strategy: Exponential
+                multiplier: 2.0
+                max_fee_per_gas: 500000000000
+              gateway:
+                strategy: Deadline
+                target_inclusion_blocks: 10
+                max_multiplier: 4.0
+                max_fee_per_gas: 100000000000
           gas_adjuster:
             default_priority_fee_per_gas: 20000000000
             max_base_fee_samples: 10000
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
-use smart_config::{DescribeConfig, DeserializeConfig};
+use smart_config::{de::Delimited, DescribeConfig, DeserializeConfig};
+use zksync_basic_types::Address;
 
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(derive(Default))]
@@ -18,6 +19,45 @@ pub struct ForcedPriceClientConfig {
     pub next_value_fluctuation: u32,
 }
 
+/// Chainlink `AggregatorV3` price feed on L1 quoting the base token price in ETH.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct ChainlinkPriceFeedConfig {
+    /// Address of the price feed contract.
+    pub feed_address: Address,
+    /// Maximum age of the latest feed answer. Older answers are considered stale and are rejected.
+    #[config(default_t = Duration::from_secs(3_600))]
+    pub max_staleness: Duration,
+}
+
+/// Uniswap V3 pool on L1 pairing the base token with WETH, used as a time-weighted average price oracle.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct UniswapV3TwapConfig {
+    /// Address of the pool contract.
+    pub pool_address: Address,
+    /// Address of the WETH token on L1. The pool must pair the base token with this token.
+    pub weth_address: Address,
+    /// Interval over which the price is averaged. Longer intervals make the price harder to manipulate,
+    /// but slower to react to market changes.
+    #[config(default_t = Duration::from_secs(1_800))]
+    pub twap_interval: Duration,
+}
+
+/// Client aggregating several price sources.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct AggregatedPriceClientConfig {
+    /// Aggregated price sources, e.g. `chainlink,uniswap_v3,coingecko`. Each source is configured
+    /// in the same way as if it was used on its own.
+    #[config(with = Delimited(","))]
+    pub sources: Vec<String>,
+    /// Minimum number of sources that must return a ratio for the aggregated ratio to be returned.
+    #[config(default_t = 2)]
+    pub min_sources: usize,
+    /// Maximum relative deviation of any source from the median ratio, e.g. 0.05 for 5%.
+    /// If the sources disagree more than that, no ratio is returned.
+    #[config(default_t = 0.05)]
+    pub max_deviation: f64,
+}
+
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(derive(Default))]
 pub struct ExternalPriceApiClientConfig {
@@ -29,6 +69,12 @@ pub struct ExternalPriceApiClientConfig {
     pub client_timeout: Duration,
     #[config(nest)]
     pub forced: Option<ForcedPriceClientConfig>,
+    #[config(nest)]
+    pub chainlink: Option<ChainlinkPriceFeedConfig>,
+    #[config(nest)]
+    pub uniswap_v3: Option<UniswapV3TwapConfig>,
+    #[config(nest)]
+    pub aggregated: Option<AggregatedPriceClientConfig>,
 }
 
 #[cfg(test)]
@@ -49,6 +95,26 @@ mod tests {
                 fluctuation: Some(10),
                 next_value_fluctuation: 3,
             }),
+            chainlink: Some(ChainlinkPriceFeedConfig {
+                feed_address: "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984"
+                    .parse()
+                    .unwrap(),
+                max_staleness: Duration::from_secs(7_200),
+            }),
+            uniswap_v3: Some(UniswapV3TwapConfig {
+                pool_address: "0x1d42064fc4beb5f8aaf85f4617ae8b3b5b8bd801"
+                    .parse()
+                    .unwrap(),
+                weth_address: "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+                    .parse()
+                    .unwrap(),
+                twap_interval: Duration::from_secs(600),
+            }),
+            aggregated: Some(AggregatedPriceClientConfig {
+                sources: vec!["chainlink".to_owned(), "uniswap_v3".to_owned()],
+                min_sources: 2,
+                max_deviation: 0.1,
+            }),
         }
     }
 
@@ -63,6 +129,14 @@ mod tests {
             EXTERNAL_PRICE_API_CLIENT_FORCED_DENOMINATOR=1
             EXTERNAL_PRICE_API_CLIENT_FORCED_FLUCTUATION=10
             EXTERNAL_PRICE_API_CLIENT_FORCED_NEXT_VALUE_FLUCTUATION=3
+            EXTERNAL_PRICE_API_CLIENT_CHAINLINK_FEED_ADDRESS=0x1f9840a85d5af5bf1d1762f925bdaddc4201f984
+            EXTERNAL_PRICE_API_CLIENT_CHAINLINK_MAX_STALENESS_SEC=7200
+            EXTERNAL_PRICE_API_CLIENT_UNISWAP_V3_POOL_ADDRESS=0x1d42064fc4beb5f8aaf85f4617ae8b3b5b8bd801
+            EXTERNAL_PRICE_API_CLIENT_UNISWAP_V3_WETH_ADDRESS=0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2
+            EXTERNAL_PRICE_API_CLIENT_UNISWAP_V3_TWAP_INTERVAL_SEC=600
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_SOURCES=chainlink,uniswap_v3
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_MIN_SOURCES=2
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_MAX_DEVIATION=0.1
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -84,6 +158,17 @@ mod tests {
           forced_next_value_fluctuation: 3
           forced:
             fluctuation: 10
+          chainlink:
+            feed_address: "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984"
+            max_staleness_sec: 7200
+          uniswap_v3:
+            pool_address: "0x1d42064fc4beb5f8aaf85f4617ae8b3b5b8bd801"
+            weth_address: "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+            twap_interval_sec: 600
+          aggregated:
+            sources: [chainlink, uniswap_v3]
+            min_sources: 2
+            max_deviation: 0.1
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: ExternalPriceApiClientConfig = test_complete(yaml).unwrap();
@@ -40,6 +40,9 @@ mod tests {
                 },
                 max_retries: 5,
                 local_mirror_path: Some("/var/cache".into()),
+                compress_objects: true,
+                encryption_key_path: Some("/path/to/encryption.key".into()),
+                encrypted_buckets: vec!["proofs_fri".to_owned()],
             },
         }
     }
@@ -56,6 +59,9 @@ mod tests {
             FRI_PROVER_PROVER_OBJECT_STORE_GCS_CREDENTIAL_FILE_PATH="/path/to/credentials1.json"
             FRI_PROVER_PROVER_OBJECT_STORE_MAX_RETRIES="5"
             FRI_PROVER_PROVER_OBJECT_STORE_LOCAL_MIRROR_PATH="/var/cache"
+            FRI_PROVER_PROVER_OBJECT_STORE_COMPRESS_OBJECTS="true"
+            FRI_PROVER_PROVER_OBJECT_STORE_ENCRYPTION_KEY_PATH="/path/to/encryption.key"
+            FRI_PROVER_PROVER_OBJECT_STORE_ENCRYPTED_BUCKETS="proofs_fri"
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -78,6 +84,9 @@ mod tests {
             gcs_credential_file_path: /path/to/credentials1.json
             max_retries: 5
             local_mirror_path: /var/cache
+            compress_objects: true
+            encryption_key_path: /path/to/encryption.key
+            encrypted_buckets: [proofs_fri]
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: FriProverConfig = Tester::default()
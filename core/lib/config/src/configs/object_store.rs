@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use smart_config::{DescribeConfig, DeserializeConfig};
+use smart_config::{de::Delimited, DescribeConfig, DeserializeConfig};
 
 /// Configuration for the object store
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
@@ -19,6 +19,21 @@ pub struct ObjectStoreConfig {
     /// **Important.** Mirroring logic assumes that objects in the underlying store are immutable. If this is not the case,
     /// the mirrored objects may become stale.
     pub local_mirror_path: Option<PathBuf>,
+    /// Whether to compress objects using zstd before storing them.
+    ///
+    /// Stored objects are prefixed with a header specifying their encoding, so objects stored with compression disabled
+    /// (including ones stored before this option was introduced) remain readable when compression is enabled and vice versa.
+    #[config(default)]
+    pub compress_objects: bool,
+    /// Path to a file with a hex-encoded 32-byte key. If specified, objects in `encrypted_buckets` are encrypted
+    /// using AES-256-GCM with this key before storing them. Unencrypted objects remain readable; encrypted objects
+    /// cannot be read if the key is not specified.
+    pub encryption_key_path: Option<PathBuf>,
+    /// Names of buckets (e.g., `witness_inputs`) in which objects are encrypted. Objects in other buckets are stored
+    /// unencrypted, so that buckets shared with other parties (e.g., storage snapshots) remain readable.
+    /// Must be non-empty if `encryption_key_path` is specified.
+    #[config(default, with = Delimited(","))]
+    pub encrypted_buckets: Vec<String>,
 }
 
 impl Default for ObjectStoreConfig {
@@ -35,6 +50,9 @@ impl ObjectStoreConfig {
             },
             max_retries: 5,
             local_mirror_path: None,
+            compress_objects: false,
+            encryption_key_path: None,
+            encrypted_buckets: vec![],
         }
     }
 }
@@ -82,6 +100,22 @@ pub enum ObjectStoreMode {
         /// Allows specifying bucket region (inferred from the env by default).
         region: Option<String>,
     },
+    /// Azure Blob Storage container.
+    AzureBlob {
+        /// URL of the container, e.g. `https://account.blob.core.windows.net/container`.
+        bucket_base_url: String,
+        /// Path to a file with a shared access signature (SAS) token for the container. If not specified,
+        /// the container is accessed anonymously (only works for public containers for read operations).
+        sas_token_file_path: Option<PathBuf>,
+    },
+    /// Generic HTTP server supporting `GET`, `HEAD`, `PUT` and `DELETE` requests for objects, such as a WebDAV server.
+    /// Objects are stored at `{bucket_base_url}/{bucket}/{key}`. Listing objects requires WebDAV `PROPFIND` support.
+    Http {
+        /// Base URL of the server.
+        bucket_base_url: String,
+        /// Path to a file with a bearer token used to authenticate requests. If not specified, requests are not authenticated.
+        auth_token_file_path: Option<PathBuf>,
+    },
     /// Stores files in a local filesystem. Mostly useful for local testing.
     #[config(default)]
     FileBacked {
@@ -107,6 +141,9 @@ mod tests {
             },
             max_retries: 5,
             local_mirror_path: Some("/var/cache".into()),
+            compress_objects: true,
+            encryption_key_path: Some("/path/to/encryption.key".into()),
+            encrypted_buckets: vec!["witness_inputs".to_owned(), "proofs_fri".to_owned()],
         }
     }
 
@@ -118,6 +155,9 @@ mod tests {
             OBJECT_STORE_GCS_CREDENTIAL_FILE_PATH="/path/to/credentials.json"
             OBJECT_STORE_MAX_RETRIES="5"
             OBJECT_STORE_LOCAL_MIRROR_PATH="/var/cache"
+            OBJECT_STORE_COMPRESS_OBJECTS="true"
+            OBJECT_STORE_ENCRYPTION_KEY_PATH="/path/to/encryption.key"
+            OBJECT_STORE_ENCRYPTED_BUCKETS="witness_inputs,proofs_fri"
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -127,6 +167,45 @@ mod tests {
         assert_eq!(config, expected_gcs_config("/base/url"));
     }
 
+    #[test]
+    fn azure_blob_from_env() {
+        let env = r#"
+            OBJECT_STORE_MODE="AzureBlob"
+            OBJECT_STORE_BUCKET_BASE_URL="https://account.blob.core.windows.net/container"
+            OBJECT_STORE_SAS_TOKEN_FILE_PATH="/path/to/sas_token"
+        "#;
+        let env = Environment::from_dotenv("test.env", env)
+            .unwrap()
+            .strip_prefix("OBJECT_STORE_");
+
+        let config: ObjectStoreConfig = test(env).unwrap();
+        assert_eq!(
+            config.mode,
+            ObjectStoreMode::AzureBlob {
+                bucket_base_url: "https://account.blob.core.windows.net/container".to_owned(),
+                sas_token_file_path: Some("/path/to/sas_token".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn http_from_yaml() {
+        let yaml = r#"
+          mode: Http
+          bucket_base_url: http://artifacts.local/store
+          max_retries: 3
+        "#;
+        let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
+        let config: ObjectStoreConfig = test(yaml).unwrap();
+        assert_eq!(
+            config.mode,
+            ObjectStoreMode::Http {
+                bucket_base_url: "http://artifacts.local/store".to_owned(),
+                auth_token_file_path: None,
+            }
+        );
+    }
+
     #[test]
     fn file_backed_from_env() {
         let env = r#"
@@ -153,6 +232,9 @@ mod tests {
             PUBLIC_OBJECT_STORE_MODE="GCSAnonymousReadOnly"
             PUBLIC_OBJECT_STORE_MAX_RETRIES="3"
             PUBLIC_OBJECT_STORE_LOCAL_MIRROR_PATH=/var/cache
+            PUBLIC_OBJECT_STORE_COMPRESS_OBJECTS=false
+            PUBLIC_OBJECT_STORE_ENCRYPTION_KEY_PATH=/etc/object-store/encryption.key
+            PUBLIC_OBJECT_STORE_ENCRYPTED_BUCKETS=witness_inputs
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -179,6 +261,9 @@ mod tests {
           file_backed_base_path: ./chains/era/artifacts/
           max_retries: 10
           local_mirror_path: /var/cache
+          compress_objects: true
+          encryption_key_path: null
+          encrypted_buckets: []
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: ObjectStoreConfig = test_complete(yaml).unwrap();
@@ -188,6 +273,9 @@ mod tests {
                 file_backed_base_path: "./chains/era/artifacts/".into(),
             }
         );
+        assert!(config.compress_objects);
+        assert_eq!(config.encryption_key_path, None);
+        assert!(config.encrypted_buckets.is_empty());
     }
 
     #[test]
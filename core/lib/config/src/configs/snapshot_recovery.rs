@@ -84,6 +84,9 @@ mod tests {
                 },
                 max_retries: 100,
                 local_mirror_path: None,
+                compress_objects: false,
+                encryption_key_path: None,
+                encrypted_buckets: vec![],
             }),
         }
     }
@@ -1,11 +1,13 @@
+use std::time::Duration;
+
 use serde::{de::Error as DeError, Deserialize};
 use serde_json::Value;
 use smart_config::{
-    de::{DeserializeContext, DeserializeParam},
+    de::{DeserializeContext, DeserializeParam, Serde},
     metadata::{BasicTypes, ParamMetadata},
     DescribeConfig, DeserializeConfig, ErrorWithOrigin,
 };
-use zksync_basic_types::{Address, H160, H256};
+use zksync_basic_types::{url::SensitiveUrl, Address, H160, H256};
 use zksync_crypto_primitives::K256PrivateKey;
 
 #[derive(Debug, Clone, DescribeConfig, DeserializeConfig)]
@@ -95,15 +97,54 @@ impl Wallet {
     }
 }
 
+/// Account managed by a remote signer implementing the Web3Signer `eth1` API (e.g., backed by an HSM).
+#[derive(Debug, Clone, DescribeConfig, DeserializeConfig)]
+pub struct RemoteSignerWallet {
+    /// Address of the account.
+    pub address: Address,
+    /// URL of the remote signer JSON-RPC API.
+    #[config(secret, with = Serde![str])]
+    pub url: SensitiveUrl,
+    /// Timeout for requests to the remote signer.
+    #[config(default_t = Duration::from_secs(30))]
+    pub request_timeout: Duration,
+}
+
+/// Wallet able to sign transactions, either with a locally stored private key or via a remote signer.
+#[derive(Debug, Clone)]
+pub enum SigningWallet {
+    PrivateKey(Wallet),
+    Remote(RemoteSignerWallet),
+}
+
+impl SigningWallet {
+    pub fn address(&self) -> Address {
+        match self {
+            Self::PrivateKey(wallet) => wallet.address(),
+            Self::Remote(wallet) => wallet.address,
+        }
+    }
+}
+
 #[derive(Debug, Clone, DescribeConfig, DeserializeConfig)]
 #[config(derive(Default))]
+#[config(validate(
+    Self::validate_remote_signers,
+    "remote signer addresses should correspond to wallet addresses"
+))]
 pub struct Wallets {
     /// Wallet for the SL operator.
     #[config(nest)]
     pub operator: Option<Wallet>,
+    /// Remote signer for the SL operator. If specified, takes precedence over `operator`.
+    #[config(nest)]
+    pub operator_remote_signer: Option<RemoteSignerWallet>,
     /// Wallet for the SL operator when using blob commitments.
     #[config(nest)]
     pub blob_operator: Option<Wallet>,
+    /// Remote signer for the SL operator when using blob commitments. If specified, takes precedence over `blob_operator`.
+    #[config(nest)]
+    pub blob_operator_remote_signer: Option<RemoteSignerWallet>,
     /// Fee account.
     #[config(nest)]
     pub fee_account: Option<AddressWallet>,
@@ -111,15 +152,58 @@ pub struct Wallets {
     pub token_multiplier_setter: Option<Wallet>,
     #[config(nest)]
     pub eth_proof_manager: Option<Wallet>,
+    /// Sequencer key used to sign transaction preconfirmations. If not specified, preconfirmations are not issued.
+    #[config(nest)]
+    pub sequencer: Option<Wallet>,
 }
 
 impl Wallets {
+    fn validate_remote_signers(&self) -> Result<(), ErrorWithOrigin> {
+        let pairs = [
+            (&self.operator, &self.operator_remote_signer),
+            (&self.blob_operator, &self.blob_operator_remote_signer),
+        ];
+        for (wallet, remote_signer) in pairs {
+            if let (Some(wallet), Some(remote_signer)) = (wallet, remote_signer) {
+                if wallet.address() != remote_signer.address {
+                    return Err(ErrorWithOrigin::custom(
+                        "Malformed wallets; remote signer `address` doesn't correspond to the wallet address",
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the signing wallet for the SL operator, preferring the remote signer if it's configured.
+    pub fn operator_signer(&self) -> Option<SigningWallet> {
+        Self::signer(&self.operator, &self.operator_remote_signer)
+    }
+
+    /// Returns the signing wallet for the SL blob operator, preferring the remote signer if it's configured.
+    pub fn blob_operator_signer(&self) -> Option<SigningWallet> {
+        Self::signer(&self.blob_operator, &self.blob_operator_remote_signer)
+    }
+
+    fn signer(
+        wallet: &Option<Wallet>,
+        remote_signer: &Option<RemoteSignerWallet>,
+    ) -> Option<SigningWallet> {
+        if let Some(remote_signer) = remote_signer {
+            Some(SigningWallet::Remote(remote_signer.clone()))
+        } else {
+            wallet.clone().map(SigningWallet::PrivateKey)
+        }
+    }
+
     pub fn for_tests() -> Wallets {
         Wallets {
             operator: Some(Wallet::from_private_key_bytes(H256::repeat_byte(0x1), None).unwrap()),
+            operator_remote_signer: None,
             blob_operator: Some(
                 Wallet::from_private_key_bytes(H256::repeat_byte(0x2), None).unwrap(),
             ),
+            blob_operator_remote_signer: None,
             fee_account: Some(AddressWallet::from_address(H160::repeat_byte(0x3))),
             token_multiplier_setter: Some(
                 Wallet::from_private_key_bytes(H256::repeat_byte(0x4), None).unwrap(),
@@ -127,6 +211,7 @@ impl Wallets {
             eth_proof_manager: Some(
                 Wallet::from_private_key_bytes(H256::repeat_byte(0x5), None).unwrap(),
             ),
+            sequencer: Some(Wallet::from_private_key_bytes(H256::repeat_byte(0x6), None).unwrap()),
         }
     }
 }
@@ -143,9 +228,17 @@ mod tests {
             operator:
               address: 0xabcf96e1ee478481042a0c4e34cdceceae01b154
               private_key: 0xf00bf4165f9e1a67841b981949033c06c1423dab34c33d6d1237ae14d85bd729
+            operator_remote_signer:
+              address: 0xabcf96e1ee478481042a0c4e34cdceceae01b154
+              url: http://127.0.0.1:9000/
+              request_timeout: 10s
             blob_operator:
               address: 0x5927c313861c01b82a026e35d93cc787e5356c0f
               private_key: 0xc9ee945b2f6d4c462a743f5af3904a4ee78aec0218f1f4f3c53d0bfbf809b520
+            blob_operator_remote_signer:
+              address: 0x5927c313861c01b82a026e35d93cc787e5356c0f
+              url: http://127.0.0.1:9001/
+              request_timeout: 15s
             fee_account:
               address: 0x7ea53e0f1eb0b3b578aeda336b2c3a778e04eebf
               private_key: 0xe338cadae0f665139a7a4f2b846b91e188a2d100dcd34f58771c903cd2b08cd1
@@ -155,10 +248,39 @@ mod tests {
             eth_proof_manager:
               address: 0x1900678c093afec2558642bc4cae038254b9e664
               private_key: 0x2137749ca460802189d3eeb9be411128c28ce67edf0d2fd750212f96a888cfa5
+            sequencer:
+              address: 0x7ea53e0f1eb0b3b578aeda336b2c3a778e04eebf
+              private_key: 0xe338cadae0f665139a7a4f2b846b91e188a2d100dcd34f58771c903cd2b08cd1
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
 
         let wallets: Wallets = test_complete(yaml).unwrap();
+        let operator_signer = wallets.operator_signer().unwrap();
+        let SigningWallet::Remote(remote_signer) = &operator_signer else {
+            panic!("unexpected signer: {operator_signer:?}");
+        };
+        assert_eq!(remote_signer.request_timeout, Duration::from_secs(10));
+        assert_eq!(
+            operator_signer.address(),
+            "0xabcf96e1ee478481042a0c4e34cdceceae01b154"
+                .parse()
+                .unwrap()
+        );
+        let blob_operator_signer = wallets.blob_operator_signer().unwrap();
+        assert_eq!(
+            blob_operator_signer.address(),
+            "0x5927c313861c01b82a026e35d93cc787e5356c0f"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(
+            wallets
+                .blob_operator_remote_signer
+                .unwrap()
+                .url
+                .expose_str(),
+            "http://127.0.0.1:9001/"
+        );
         assert_eq!(
             wallets.operator.unwrap().address(),
             "0xabcf96e1ee478481042a0c4e34cdceceae01b154"
@@ -189,6 +311,12 @@ mod tests {
                 .parse()
                 .unwrap()
         );
+        assert_eq!(
+            wallets.sequencer.unwrap().address(),
+            "0x7ea53e0f1eb0b3b578aeda336b2c3a778e04eebf"
+                .parse()
+                .unwrap()
+        );
     }
 
     #[test]
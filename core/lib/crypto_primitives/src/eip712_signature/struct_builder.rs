@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, VecDeque};
 
-use serde_json::Value;
+use serde_json::{Map, Value};
 use zksync_basic_types::H256;
 
 use crate::eip712_signature::typed_structure::{EncodedStructureMember, StructMember};
@@ -36,6 +36,29 @@ impl StructBuilder for TypeBuilder {
     }
 }
 
+/// Builder for collecting member values of the structure as JSON.
+pub(crate) struct JsonBuilder {
+    members: Map<String, Value>,
+}
+
+impl JsonBuilder {
+    pub fn build(self) -> Value {
+        Value::Object(self.members)
+    }
+}
+
+impl StructBuilder for JsonBuilder {
+    fn new() -> Self {
+        Self {
+            members: Map::new(),
+        }
+    }
+
+    fn add_member<MEMBER: StructMember>(&mut self, name: &str, member: &MEMBER) {
+        self.members.insert(name.to_owned(), member.to_json_value());
+    }
+}
+
 struct OuterTypeBuilder {
     inner_members_queue: VecDeque<EncodedStructureMember>,
 }
@@ -205,3 +205,43 @@ fn test_get_eip712_json() {
         serde_json::from_str::<serde_json::Value>(expected_value).unwrap()
     );
 }
+
+struct Payload {
+    nonce: u64,
+    amount: U256,
+    key: H256,
+    data: Vec<u8>,
+    hashes: Vec<H256>,
+}
+
+impl EIP712TypedStructure for Payload {
+    const TYPE_NAME: &'static str = "Payload";
+
+    fn build_structure<BUILDER: StructBuilder>(&self, builder: &mut BUILDER) {
+        builder.add_member("nonce", &self.nonce);
+        builder.add_member("amount", &self.amount);
+        builder.add_member("key", &self.key);
+        builder.add_member("data", &self.data.as_slice());
+        builder.add_member("hashes", &self.hashes.as_slice());
+    }
+}
+
+#[test]
+fn test_get_eip712_json_message() {
+    let payload = Payload {
+        nonce: u64::MAX,
+        amount: U256::from(255),
+        key: H256::from_low_u64_be(16),
+        data: vec![1, 2, 255],
+        hashes: vec![H256::repeat_byte(0xaa)],
+    };
+
+    let expected_value = serde_json::json!({
+        "nonce": "18446744073709551615",
+        "amount": "0xff",
+        "key": "0x10",
+        "data": "0x0102ff",
+        "hashes": [format!("0x{}", "aa".repeat(32))],
+    });
+    assert_eq!(payload.get_json_message(), expected_value);
+}
@@ -29,6 +29,11 @@ pub trait DataAvailabilityClient: Sync + Send + fmt::Debug {
     /// Fetches the inclusion data for a given blob_id.
     async fn get_inclusion_data(&self, blob_id: &str) -> Result<Option<InclusionData>, DAError>;
 
+    /// Fetches the data of a previously dispatched blob with the given `blob_id` (i.e., the ID returned
+    /// in [`FinalityResponse`]) back from the data availability layer. Returns `None` if the blob
+    /// is not (yet) available.
+    async fn get_blob(&self, blob_id: &str) -> Result<Option<Vec<u8>>, DAError>;
+
     /// Clones the client and wraps it in a Box.
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient>;
 
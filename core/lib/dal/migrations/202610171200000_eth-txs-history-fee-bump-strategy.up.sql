@@ -0,0 +1 @@
+ALTER TABLE eth_txs_history ADD COLUMN fee_bump_strategy TEXT;
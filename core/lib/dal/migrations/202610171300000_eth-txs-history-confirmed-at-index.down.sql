@@ -0,0 +1 @@
+DROP INDEX IF EXISTS eth_txs_history_confirmed_at;
@@ -0,0 +1,2 @@
+CREATE INDEX IF NOT EXISTS eth_txs_history_confirmed_at ON eth_txs_history (confirmed_at)
+WHERE confirmed_at IS NOT NULL;
@@ -161,6 +161,32 @@ impl BlocksDal<'_, '_> {
         Ok(row.map(|row| (L1BatchNumber(row.number as u32), row.timestamp as u64)))
     }
 
+    /// Returns the number of the last sealed L1 batch with the timestamp not exceeding the provided one,
+    /// or `None` if there are no such L1 batches.
+    pub async fn get_last_sealed_l1_batch_number_at_timestamp(
+        &mut self,
+        timestamp: u64,
+    ) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(number) AS "number"
+            FROM
+                l1_batches
+            WHERE
+                is_sealed
+                AND timestamp <= $1
+            "#,
+            timestamp as i64
+        )
+        .instrument("get_last_sealed_l1_batch_number_at_timestamp")
+        .with_arg("timestamp", &timestamp)
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row.number.map(|num| L1BatchNumber(num as u32)))
+    }
+
     /// Returns latest L1 batch's header (could be unsealed). The header contains fields that are
     /// common for both unsealed and sealed batches. Returns `None` if there are no L1 batches.
     pub async fn get_latest_l1_batch_header(&mut self) -> DalResult<Option<CommonL1BatchHeader>> {
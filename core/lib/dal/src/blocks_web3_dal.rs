@@ -1,3 +1,6 @@
+use std::ops;
+
+use sqlx::types::chrono::NaiveDateTime;
 use zksync_db_connection::{
     connection::Connection, error::DalResult, instrument::InstrumentExt, interpolate_query,
     match_query_as,
@@ -18,9 +21,9 @@ use crate::{
         bigdecimal_to_u256, parse_protocol_version,
         storage_block::{
             ResolvedL1BatchForL2Block, StorageBlockDetails, StorageL1BatchDetails,
-            LEGACY_BLOCK_GAS_LIMIT,
+            StorageL1BatchStatusUpdate, LEGACY_BLOCK_GAS_LIMIT,
         },
-        storage_transaction::CallTrace,
+        storage_transaction::{parse_call_trace, CallTrace},
     },
     Core, CoreDal,
 };
@@ -651,6 +654,57 @@ impl BlocksWeb3Dal<'_, '_> {
         .collect())
     }
 
+    /// Returns call traces for all transactions in the specified L2 block range, ordered by the block number
+    /// and then by the transaction index in block.
+    pub async fn get_traces_for_l2_block_range(
+        &mut self,
+        block_range: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<(Call, CallTraceMeta)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                transactions.hash AS tx_hash,
+                transactions.index_in_block AS tx_index_in_block,
+                transactions.error AS tx_error,
+                call_trace,
+                miniblocks.number AS block_number,
+                miniblocks.hash AS block_hash,
+                miniblocks.protocol_version
+            FROM
+                call_traces
+            INNER JOIN transactions ON tx_hash = transactions.hash
+            INNER JOIN miniblocks ON transactions.miniblock_number = miniblocks.number
+            WHERE
+                miniblocks.number BETWEEN $1 AND $2
+            ORDER BY
+                miniblocks.number,
+                transactions.index_in_block
+            "#,
+            i64::from(block_range.start().0),
+            i64::from(block_range.end().0)
+        )
+        .try_map(|row| {
+            let protocol_version = row
+                .protocol_version
+                .map(parse_protocol_version)
+                .transpose()?
+                .unwrap_or_else(ProtocolVersionId::last_potentially_undefined);
+            let meta = CallTraceMeta {
+                index_in_block: row.tx_index_in_block.unwrap_or_default() as usize,
+                tx_hash: H256::from_slice(&row.tx_hash),
+                block_number: row.block_number as u32,
+                block_hash: H256::from_slice(&row.block_hash),
+                internal_error: row.tx_error,
+            };
+            Ok((parse_call_trace(&row.call_trace, protocol_version), meta))
+        })
+        .instrument("get_traces_for_l2_block_range")
+        .with_arg("block_range", &block_range)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(rows)
+    }
+
     /// Returns `base_fee_per_gas` and `fair_pubdata_price` for L2 block range [min(newest_block - block_count + 1, 0), newest_block]
     /// in descending order of L2 block numbers.
     pub async fn get_fee_history(
@@ -1103,6 +1157,105 @@ impl BlocksWeb3Dal<'_, '_> {
 
         Ok(l1_batch_details.map(Into::into))
     }
+
+    /// Returns the latest status transitions of L1 batches updated after the specified timestamp, ordered by
+    /// the transition time. A batch is keyed on the maximum confirmation timestamp of its commit, prove and execute
+    /// transactions, so at most one update is returned per batch; it corresponds to the most recently confirmed
+    /// transaction (which may be a finality upgrade of an already confirmed transaction).
+    ///
+    /// Updated batches are looked up using the `eth_txs_history.confirmed_at` index, and each kind of operation is joined
+    /// with `l1_batches` separately so that the corresponding `l1_batches` index can be used.
+    pub async fn get_l1_batch_status_updates_after(
+        &mut self,
+        from_timestamp: NaiveDateTime,
+    ) -> DalResult<Vec<api::L1BatchStatusUpdate>> {
+        let updates = sqlx::query_as!(
+            StorageL1BatchStatusUpdate,
+            r#"
+            WITH
+            confirmed_txs AS (
+                SELECT
+                    eth_txs.id
+                FROM
+                    eth_txs_history
+                INNER JOIN eth_txs ON eth_txs.confirmed_eth_tx_history_id = eth_txs_history.id
+                WHERE
+                    eth_txs_history.confirmed_at > $1
+            ),
+            
+            updated_batches AS (
+                SELECT
+                    l1_batches.number,
+                    l1_batches.eth_commit_tx_id,
+                    l1_batches.eth_prove_tx_id,
+                    l1_batches.eth_execute_tx_id
+                FROM
+                    confirmed_txs
+                INNER JOIN l1_batches ON l1_batches.eth_commit_tx_id = confirmed_txs.id
+                UNION
+                SELECT
+                    l1_batches.number,
+                    l1_batches.eth_commit_tx_id,
+                    l1_batches.eth_prove_tx_id,
+                    l1_batches.eth_execute_tx_id
+                FROM
+                    confirmed_txs
+                INNER JOIN l1_batches ON l1_batches.eth_prove_tx_id = confirmed_txs.id
+                UNION
+                SELECT
+                    l1_batches.number,
+                    l1_batches.eth_commit_tx_id,
+                    l1_batches.eth_prove_tx_id,
+                    l1_batches.eth_execute_tx_id
+                FROM
+                    confirmed_txs
+                INNER JOIN l1_batches ON l1_batches.eth_execute_tx_id = confirmed_txs.id
+            ),
+            
+            latest_updates AS (
+                SELECT DISTINCT ON (updated_batches.number)
+                    updated_batches.number,
+                    eth_txs.tx_type,
+                    eth_txs.chain_id,
+                    eth_txs_history.tx_hash,
+                    eth_txs_history.finality_status,
+                    eth_txs_history.confirmed_at
+                FROM
+                    updated_batches
+                INNER JOIN eth_txs ON eth_txs.id IN (
+                    updated_batches.eth_commit_tx_id,
+                    updated_batches.eth_prove_tx_id,
+                    updated_batches.eth_execute_tx_id
+                )
+                INNER JOIN eth_txs_history ON eth_txs_history.id = eth_txs.confirmed_eth_tx_history_id
+                ORDER BY
+                    updated_batches.number,
+                    eth_txs_history.confirmed_at DESC,
+                    eth_txs.id DESC
+            )
+            
+            SELECT
+                number AS "number!",
+                tx_type AS "tx_type!",
+                chain_id,
+                tx_hash AS "tx_hash!",
+                finality_status AS "finality_status!",
+                confirmed_at AS "confirmed_at!"
+            FROM
+                latest_updates
+            ORDER BY
+                confirmed_at ASC,
+                number ASC
+            "#,
+            from_timestamp
+        )
+        .instrument("get_l1_batch_status_updates_after")
+        .with_arg("from_timestamp", &from_timestamp)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(updates.into_iter().map(Into::into).collect())
+    }
 }
 
 #[cfg(test)]
@@ -1338,6 +1491,7 @@ mod tests {
                 &[],
                 0,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -1363,6 +1517,256 @@ mod tests {
         assert_eq!(resolved_l2_block_number, Some(l2_block_header.number));
     }
 
+    async fn send_and_confirm_eth_tx(
+        conn: &mut Connection<'_, Core>,
+        l1_batches: ops::RangeInclusive<L1BatchNumber>,
+        action_type: L1BatchAggregatedActionType,
+        finality: EthTxFinalityStatus,
+    ) -> H256 {
+        let eth_tx = conn
+            .eth_sender_dal()
+            .save_eth_tx(
+                0,
+                vec![],
+                AggregatedActionType::L1Batch(action_type),
+                Address::default(),
+                None,
+                None,
+                None,
+                false,
+            )
+            .await
+            .unwrap();
+        let tx_hash = H256::random();
+        conn.eth_sender_dal()
+            .insert_tx_history(eth_tx.id, 0, 0, None, None, tx_hash, &[], 0, None, None)
+            .await
+            .unwrap();
+        conn.eth_sender_dal()
+            .confirm_tx(tx_hash, finality, U256::zero())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .set_eth_tx_id_for_l1_batches(
+                l1_batches,
+                eth_tx.id,
+                AggregatedActionType::L1Batch(action_type),
+            )
+            .await
+            .unwrap();
+        tx_hash
+    }
+
+    async fn prepare_l1_batches_for_status_updates(
+        conn: &mut Connection<'_, Core>,
+    ) -> NaiveDateTime {
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 1..=2 {
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(number))
+                .await
+                .unwrap();
+        }
+
+        let start = chrono::Utc::now().naive_utc() - chrono::Duration::seconds(1);
+        let updates = conn
+            .blocks_web3_dal()
+            .get_l1_batch_status_updates_after(start)
+            .await
+            .unwrap();
+        assert!(updates.is_empty(), "{updates:?}");
+        start
+    }
+
+    #[tokio::test]
+    async fn l1_batch_status_update_on_commit() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        let start = prepare_l1_batches_for_status_updates(&mut conn).await;
+
+        let commit_tx_hash = send_and_confirm_eth_tx(
+            &mut conn,
+            L1BatchNumber(1)..=L1BatchNumber(2),
+            L1BatchAggregatedActionType::Commit,
+            EthTxFinalityStatus::Finalized,
+        )
+        .await;
+        let updates = conn
+            .blocks_web3_dal()
+            .get_l1_batch_status_updates_after(start)
+            .await
+            .unwrap();
+        assert_eq!(updates.len(), 2, "{updates:?}");
+        for (update, number) in updates.iter().zip([1, 2]) {
+            assert_eq!(update.l1_batch_number, L1BatchNumber(number));
+            assert_eq!(update.stage, api::L1BatchStage::Committed);
+            assert_eq!(update.tx_hash, commit_tx_hash);
+            assert_eq!(update.finality, EthTxFinalityStatus::Finalized);
+        }
+
+        let updates = conn
+            .blocks_web3_dal()
+            .get_l1_batch_status_updates_after(updates[1].confirmed_at.naive_utc())
+            .await
+            .unwrap();
+        assert!(updates.is_empty(), "{updates:?}");
+    }
+
+    #[tokio::test]
+    async fn l1_batch_status_update_on_proof() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        let start = prepare_l1_batches_for_status_updates(&mut conn).await;
+
+        let commit_tx_hash = send_and_confirm_eth_tx(
+            &mut conn,
+            L1BatchNumber(1)..=L1BatchNumber(2),
+            L1BatchAggregatedActionType::Commit,
+            EthTxFinalityStatus::Finalized,
+        )
+        .await;
+        let commit_updates = conn
+            .blocks_web3_dal()
+            .get_l1_batch_status_updates_after(start)
+            .await
+            .unwrap();
+        let committed_at = commit_updates[1].confirmed_at.naive_utc();
+
+        let prove_tx_hash = send_and_confirm_eth_tx(
+            &mut conn,
+            L1BatchNumber(1)..=L1BatchNumber(1),
+            L1BatchAggregatedActionType::PublishProofOnchain,
+            EthTxFinalityStatus::FastFinalized,
+        )
+        .await;
+        let updates = conn
+            .blocks_web3_dal()
+            .get_l1_batch_status_updates_after(committed_at)
+            .await
+            .unwrap();
+        assert_eq!(updates.len(), 1, "{updates:?}");
+        assert_eq!(updates[0].l1_batch_number, L1BatchNumber(1));
+        assert_eq!(updates[0].stage, api::L1BatchStage::Proven);
+        assert_eq!(updates[0].tx_hash, prove_tx_hash);
+        assert_eq!(updates[0].finality, EthTxFinalityStatus::FastFinalized);
+        assert!(updates[0].confirmed_at.naive_utc() > committed_at);
+
+        // Batches are keyed on their latest transition, so batch #1 is only reported as proven.
+        let updates = conn
+            .blocks_web3_dal()
+            .get_l1_batch_status_updates_after(start)
+            .await
+            .unwrap();
+        let updates: Vec<_> = updates
+            .iter()
+            .map(|update| (update.l1_batch_number, update.stage, update.tx_hash))
+            .collect();
+        assert_eq!(
+            updates,
+            [
+                (
+                    L1BatchNumber(2),
+                    api::L1BatchStage::Committed,
+                    commit_tx_hash
+                ),
+                (L1BatchNumber(1), api::L1BatchStage::Proven, prove_tx_hash),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn l1_batch_status_update_on_execution() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        let start = prepare_l1_batches_for_status_updates(&mut conn).await;
+
+        for action_type in [
+            L1BatchAggregatedActionType::Commit,
+            L1BatchAggregatedActionType::PublishProofOnchain,
+        ] {
+            send_and_confirm_eth_tx(
+                &mut conn,
+                L1BatchNumber(1)..=L1BatchNumber(2),
+                action_type,
+                EthTxFinalityStatus::Finalized,
+            )
+            .await;
+        }
+        let prove_updates = conn
+            .blocks_web3_dal()
+            .get_l1_batch_status_updates_after(start)
+            .await
+            .unwrap();
+        assert_eq!(prove_updates.len(), 2, "{prove_updates:?}");
+        assert!(prove_updates
+            .iter()
+            .all(|update| update.stage == api::L1BatchStage::Proven));
+        let proven_at = prove_updates[1].confirmed_at.naive_utc();
+
+        let execute_tx_hash = send_and_confirm_eth_tx(
+            &mut conn,
+            L1BatchNumber(1)..=L1BatchNumber(2),
+            L1BatchAggregatedActionType::Execute,
+            EthTxFinalityStatus::Finalized,
+        )
+        .await;
+        for from_timestamp in [start, proven_at] {
+            let updates = conn
+                .blocks_web3_dal()
+                .get_l1_batch_status_updates_after(from_timestamp)
+                .await
+                .unwrap();
+            assert_eq!(updates.len(), 2, "{updates:?}");
+            for (update, number) in updates.iter().zip([1, 2]) {
+                assert_eq!(update.l1_batch_number, L1BatchNumber(number));
+                assert_eq!(update.stage, api::L1BatchStage::Executed);
+                assert_eq!(update.tx_hash, execute_tx_hash);
+                assert_eq!(update.finality, EthTxFinalityStatus::Finalized);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn l1_batch_status_update_on_finality_upgrade() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        let start = prepare_l1_batches_for_status_updates(&mut conn).await;
+
+        let commit_tx_hash = send_and_confirm_eth_tx(
+            &mut conn,
+            L1BatchNumber(1)..=L1BatchNumber(1),
+            L1BatchAggregatedActionType::Commit,
+            EthTxFinalityStatus::FastFinalized,
+        )
+        .await;
+        let updates = conn
+            .blocks_web3_dal()
+            .get_l1_batch_status_updates_after(start)
+            .await
+            .unwrap();
+        assert_eq!(updates.len(), 1, "{updates:?}");
+        assert_eq!(updates[0].finality, EthTxFinalityStatus::FastFinalized);
+        let fast_finalized_at = updates[0].confirmed_at.naive_utc();
+
+        conn.eth_sender_dal()
+            .confirm_tx(commit_tx_hash, EthTxFinalityStatus::Finalized, U256::zero())
+            .await
+            .unwrap();
+        let updates = conn
+            .blocks_web3_dal()
+            .get_l1_batch_status_updates_after(fast_finalized_at)
+            .await
+            .unwrap();
+        assert_eq!(updates.len(), 1, "{updates:?}");
+        assert_eq!(updates[0].l1_batch_number, L1BatchNumber(1));
+        assert_eq!(updates[0].stage, api::L1BatchStage::Committed);
+        assert_eq!(updates[0].tx_hash, commit_tx_hash);
+        assert_eq!(updates[0].finality, EthTxFinalityStatus::Finalized);
+    }
+
     #[tokio::test]
     async fn resolving_block_by_hash() {
         let connection_pool = ConnectionPool::<Core>::test_pool().await;
@@ -1448,5 +1852,18 @@ mod tests {
             assert_eq!(tx_result.hash, meta.tx_hash);
             assert_eq!(*trace, expected_trace);
         }
+
+        let range_traces = conn
+            .blocks_web3_dal()
+            .get_traces_for_l2_block_range(L2BlockNumber(0)..=L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(range_traces, traces);
+        let range_traces = conn
+            .blocks_web3_dal()
+            .get_traces_for_l2_block_range(L2BlockNumber(2)..=L2BlockNumber(10))
+            .await
+            .unwrap();
+        assert!(range_traces.is_empty());
     }
 }
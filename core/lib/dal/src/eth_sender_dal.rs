@@ -481,6 +481,7 @@ impl EthSenderDal<'_, '_> {
         raw_signed_tx: &[u8],
         sent_at_block: u32,
         predicted_gas_limit: Option<u64>,
+        fee_bump_strategy: Option<&str>,
     ) -> anyhow::Result<Option<u32>> {
         let priority_fee_per_gas =
             i64::try_from(priority_fee_per_gas).context("Can't convert u64 to i64")?;
@@ -505,11 +506,11 @@ impl EthSenderDal<'_, '_> {
                 sent_at_block,
                 sent_at,
                 sent_successfully,
-                finality_status
-            
+                finality_status,
+                fee_bump_strategy
             )
             VALUES
-            ($1, $2, $3, $4, $5, NOW(), NOW(), $6, $7, $8, $9, NOW(), FALSE, 'pending')
+            ($1, $2, $3, $4, $5, NOW(), NOW(), $6, $7, $8, $9, NOW(), FALSE, 'pending', $10)
             ON CONFLICT (tx_hash) DO UPDATE SET sent_at_block = $9
             RETURNING
             id
@@ -522,7 +523,8 @@ impl EthSenderDal<'_, '_> {
             blob_base_fee_per_gas.map(|v| v as i64),
             max_gas_per_pubdata.map(|v| v as i64),
             predicted_gas_limit.map(|v| v as i64),
-            sent_at_block as i32
+            sent_at_block as i32,
+            fee_bump_strategy
         )
         .fetch_optional(self.storage.conn())
         .await?
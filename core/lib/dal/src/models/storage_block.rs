@@ -5,6 +5,7 @@ use sqlx::types::chrono::{DateTime, NaiveDateTime, Utc};
 use thiserror::Error;
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_types::{
+    aggregated_operations::L1BatchAggregatedActionType,
     api,
     block::{CommonL1BatchHeader, L1BatchHeader, L2BlockHeader, UnsealedL1BatchHeader},
     commitment::{L1BatchMetaParameters, L1BatchMetadata, PubdataParams, PubdataType},
@@ -605,6 +606,37 @@ impl From<StorageL1BatchDetails> for api::L1BatchDetails {
     }
 }
 
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub(crate) struct StorageL1BatchStatusUpdate {
+    pub number: i64,
+    pub tx_type: String,
+    pub tx_hash: String,
+    pub finality_status: String,
+    pub chain_id: Option<i64>,
+    pub confirmed_at: NaiveDateTime,
+}
+
+impl From<StorageL1BatchStatusUpdate> for api::L1BatchStatusUpdate {
+    fn from(update: StorageL1BatchStatusUpdate) -> Self {
+        let action_type = L1BatchAggregatedActionType::from_str(&update.tx_type)
+            .expect("Incorrect aggregated action type");
+        let stage = match action_type {
+            L1BatchAggregatedActionType::Commit => api::L1BatchStage::Committed,
+            L1BatchAggregatedActionType::PublishProofOnchain => api::L1BatchStage::Proven,
+            L1BatchAggregatedActionType::Execute => api::L1BatchStage::Executed,
+        };
+        api::L1BatchStatusUpdate {
+            l1_batch_number: L1BatchNumber(update.number as u32),
+            stage,
+            tx_hash: H256::from_str(&update.tx_hash).expect("Incorrect eth tx hash"),
+            finality: EthTxFinalityStatus::from_str(&update.finality_status)
+                .expect("Incorrect finality status"),
+            chain_id: update.chain_id.map(|id| SLChainId(id as u64)),
+            confirmed_at: DateTime::from_naive_utc_and_offset(update.confirmed_at, Utc),
+        }
+    }
+}
+
 pub(crate) struct StorageL2BlockHeader {
     pub number: i64,
     pub timestamp: i64,
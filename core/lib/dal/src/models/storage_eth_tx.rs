@@ -67,6 +67,7 @@ pub struct StorageTxHistory {
     pub predicted_gas_limit: Option<i64>,
     pub sent_successfully: bool,
     pub finality_status: String,
+    pub fee_bump_strategy: Option<String>,
 }
 
 impl From<StorageEthTx> for EthTx {
@@ -114,6 +115,7 @@ impl From<StorageTxHistory> for TxHistory {
             sent_successfully: history.sent_successfully,
             eth_tx_finality_status: EthTxFinalityStatus::from_str(history.finality_status.as_ref())
                 .expect("Invalid finality status"),
+            fee_bump_strategy: history.fee_bump_strategy,
         }
     }
 }
@@ -10,9 +10,9 @@ use zksync_db_connection::{
     utils::pg_interval_from_duration,
 };
 use zksync_types::{
-    block::L2BlockExecutionData, debug_flat_call::CallTraceMeta, l1::L1Tx, l2::L2Tx,
+    block::L2BlockExecutionData, debug_flat_call::CallTraceMeta, fee::Fee, l1::L1Tx, l2::L2Tx,
     protocol_upgrade::ProtocolUpgradeTx, Address, ExecuteTransactionCommon, L1BatchNumber,
-    L1BlockNumber, L2BlockNumber, PriorityOpId, ProtocolVersionId, Transaction,
+    L1BlockNumber, L2BlockNumber, Nonce, PriorityOpId, ProtocolVersionId, Transaction,
     TransactionTimeRangeConstraint, H256, PROTOCOL_UPGRADE_TX_TYPE, U256,
 };
 use zksync_vm_interface::{
@@ -22,6 +22,7 @@ use zksync_vm_interface::{
 
 use crate::{
     models::{
+        bigdecimal_to_u256,
         storage_transaction::{parse_call_trace, serialize_call_into_bytes, StorageTransaction},
         u256_to_big_decimal,
     },
@@ -1768,6 +1769,78 @@ impl TransactionsDal<'_, '_> {
         Ok(result.count.unwrap_or_default() as usize)
     }
 
+    /// Returns the number of pending (i.e., not included into an L2 block) L2 transactions with the specified initiator.
+    pub async fn get_pending_l2_txs_count(
+        &mut self,
+        initiator_address: Address,
+    ) -> DalResult<usize> {
+        let result = sqlx::query!(
+            r#"
+            SELECT COUNT(*) FROM transactions
+            WHERE
+                initiator_address = $1
+                AND miniblock_number IS NULL
+                AND is_priority = FALSE
+                AND error IS NULL
+            "#,
+            initiator_address.as_bytes()
+        )
+        .instrument("get_pending_l2_txs_count")
+        .with_arg("initiator_address", &initiator_address)
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(result.count.unwrap_or_default() as usize)
+    }
+
+    /// Returns fee params of the pending L2 transaction with the specified initiator and nonce, if it exists.
+    pub async fn get_pending_l2_tx_fee(
+        &mut self,
+        initiator_address: Address,
+        nonce: Nonce,
+    ) -> DalResult<Option<Fee>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                gas_per_pubdata_limit
+            FROM
+                transactions
+            WHERE
+                initiator_address = $1
+                AND nonce = $2
+                AND miniblock_number IS NULL
+                AND is_priority = FALSE
+                AND error IS NULL
+            "#,
+            initiator_address.as_bytes(),
+            i64::from(nonce.0)
+        )
+        .instrument("get_pending_l2_tx_fee")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("nonce", &nonce)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| Fee {
+            gas_limit: row.gas_limit.map(bigdecimal_to_u256).unwrap_or_default(),
+            max_fee_per_gas: row
+                .max_fee_per_gas
+                .map(bigdecimal_to_u256)
+                .unwrap_or_default(),
+            max_priority_fee_per_gas: row
+                .max_priority_fee_per_gas
+                .map(bigdecimal_to_u256)
+                .unwrap_or_default(),
+            gas_per_pubdata_limit: row
+                .gas_per_pubdata_limit
+                .map(bigdecimal_to_u256)
+                .unwrap_or_default(),
+        }))
+    }
+
     /// Resets `in_mempool` to `FALSE` for the given transaction hashes.
     pub async fn reset_mempool_status(&mut self, transaction_hashes: &[H256]) -> DalResult<()> {
         // Convert H256 hashes into `&[u8]`
@@ -2445,4 +2518,52 @@ mod tests {
             .unwrap();
         assert_eq!(tx_from_db[0].hash, tx_hash);
     }
+
+    #[tokio::test]
+    async fn pending_l2_tx_queries_ignore_rejected_transactions() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+
+        let tx = mock_l2_transaction();
+        let initiator = tx.initiator_account();
+        conn.transactions_dal()
+            .insert_transaction_l2(
+                &tx,
+                TransactionExecutionMetrics::default(),
+                ValidationTraces::default(),
+            )
+            .await
+            .unwrap();
+
+        let pending_count = conn
+            .transactions_dal()
+            .get_pending_l2_txs_count(initiator)
+            .await
+            .unwrap();
+        assert_eq!(pending_count, 1);
+        let fee = conn
+            .transactions_dal()
+            .get_pending_l2_tx_fee(initiator, tx.nonce())
+            .await
+            .unwrap();
+        assert_eq!(fee, Some(tx.common_data.fee.clone()));
+
+        conn.transactions_dal()
+            .mark_tx_as_rejected(tx.hash(), "oops")
+            .await
+            .unwrap();
+
+        let pending_count = conn
+            .transactions_dal()
+            .get_pending_l2_txs_count(initiator)
+            .await
+            .unwrap();
+        assert_eq!(pending_count, 0);
+        let fee = conn
+            .transactions_dal()
+            .get_pending_l2_tx_fee(initiator, tx.nonce())
+            .await
+            .unwrap();
+        assert_eq!(fee, None);
+    }
 }
@@ -2,7 +2,9 @@ use std::{fmt, sync::Arc};
 
 use async_trait::async_trait;
 use zksync_contracts::hyperchain_contract;
-use zksync_eth_signer::{EthereumSigner, PrivateKeySigner, SignerError, TransactionParameters};
+use zksync_eth_signer::{
+    EthereumSigner, PrivateKeySigner, SignerError, TransactionParameters, Web3Signer,
+};
 use zksync_types::{
     api::TransactionRequest, ethabi, fee::Fee, l2::L2Tx, web3, Address, Eip712Domain,
     K256PrivateKey, Nonce, SLChainId, EIP_4844_TX_TYPE, EIP_712_TX_TYPE, H160, H256, U256,
@@ -41,6 +43,31 @@ impl<Net: Network> PKSigningClient<Net> {
     }
 }
 
+/// HTTP-based Ethereum client, backed by a remote signer (e.g., Web3Signer) to sign transactions.
+pub type Web3SigningClient<Net> = SigningClient<Web3Signer, Net>;
+
+impl<Net: Network> Web3SigningClient<Net> {
+    pub fn new_raw(
+        signer: Web3Signer,
+        diamond_proxy_addr: Address,
+        default_priority_fee_per_gas: u64,
+        chain_id: SLChainId,
+        query_client: Box<DynClient<Net>>,
+    ) -> Self {
+        let operator_address = signer.address();
+        tracing::info!("Operator address: {operator_address:?} (managed by remote signer)");
+        SigningClient::new(
+            query_client,
+            hyperchain_contract(),
+            operator_address,
+            signer,
+            diamond_proxy_addr,
+            default_priority_fee_per_gas.into(),
+            chain_id,
+        )
+    }
+}
+
 /// Gas limit value to be used in transaction if for some reason
 /// gas limit was not set for it.
 ///
@@ -6,6 +6,6 @@ mod mock;
 pub use zksync_web3_decl::client::{Client, DynClient, L1, L2};
 
 pub use self::{
-    http::{PKSigningClient, SigningClient},
+    http::{PKSigningClient, SigningClient, Web3SigningClient},
     mock::{MockSettlementLayer, MockSettlementLayerBuilder},
 };
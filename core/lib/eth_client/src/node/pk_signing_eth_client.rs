@@ -1,4 +1,5 @@
-use zksync_config::{configs::wallets, GasAdjusterConfig};
+use zksync_config::{configs::wallets::SigningWallet, GasAdjusterConfig};
+use zksync_eth_signer::Web3Signer;
 use zksync_node_framework::{
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
@@ -6,20 +7,24 @@ use zksync_node_framework::{
 use zksync_shared_resources::contracts::{
     L1ChainContractsResource, SettlementLayerContractsResource,
 };
+use zksync_types::{Address, SLChainId};
 use zksync_web3_decl::{
-    client::{DynClient, L1},
+    client::{DynClient, Network, L1},
     node::SettlementLayerClient,
 };
 
 use super::resources::{BoundEthInterfaceForBlobsResource, BoundEthInterfaceForL2Resource};
-use crate::{clients::PKSigningClient, BoundEthInterface, EthInterface};
+use crate::{
+    clients::{PKSigningClient, Web3SigningClient},
+    BoundEthInterface, EthInterface,
+};
 
-/// Wiring layer for [`PKSigningClient`].
+/// Wiring layer for [`PKSigningClient`] or [`Web3SigningClient`], depending on the operator wallet kind.
 #[derive(Debug)]
 pub struct PKSigningEthClientLayer {
     gas_adjuster_config: GasAdjusterConfig,
-    operator: wallets::Wallet,
-    blob_operator: Option<wallets::Wallet>,
+    operator: SigningWallet,
+    blob_operator: Option<SigningWallet>,
 }
 
 #[derive(Debug, FromContext)]
@@ -41,8 +46,8 @@ pub struct Output {
 impl PKSigningEthClientLayer {
     pub fn new(
         gas_adjuster_config: GasAdjusterConfig,
-        operator: wallets::Wallet,
-        blob_operator: Option<wallets::Wallet>,
+        operator: SigningWallet,
+        blob_operator: Option<SigningWallet>,
     ) -> Self {
         Self {
             gas_adjuster_config,
@@ -50,6 +55,48 @@ impl PKSigningEthClientLayer {
             blob_operator,
         }
     }
+
+    async fn check_remote_signer(wallet: &SigningWallet) -> Result<(), WiringError> {
+        if let SigningWallet::Remote(wallet) = wallet {
+            let signer = Web3Signer::new(&wallet.url, wallet.address, wallet.request_timeout)
+                .map_err(WiringError::internal)?;
+            signer
+                .check_account()
+                .await
+                .map_err(WiringError::internal)?;
+        }
+        Ok(())
+    }
+
+    fn create_client<Net: Network>(
+        &self,
+        wallet: &SigningWallet,
+        diamond_proxy_addr: Address,
+        chain_id: SLChainId,
+        query_client: Box<DynClient<Net>>,
+    ) -> Result<Box<dyn BoundEthInterface>, WiringError> {
+        let default_priority_fee_per_gas = self.gas_adjuster_config.default_priority_fee_per_gas;
+        Ok(match wallet {
+            SigningWallet::PrivateKey(wallet) => Box::new(PKSigningClient::new_raw(
+                wallet.private_key().clone(),
+                diamond_proxy_addr,
+                default_priority_fee_per_gas,
+                chain_id,
+                query_client,
+            )),
+            SigningWallet::Remote(wallet) => {
+                let signer = Web3Signer::new(&wallet.url, wallet.address, wallet.request_timeout)
+                    .map_err(WiringError::internal)?;
+                Box::new(Web3SigningClient::new_raw(
+                    signer,
+                    diamond_proxy_addr,
+                    default_priority_fee_per_gas,
+                    chain_id,
+                    query_client,
+                ))
+            }
+        })
+    }
 }
 
 #[async_trait::async_trait]
@@ -62,10 +109,14 @@ impl WiringLayer for PKSigningEthClientLayer {
     }
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
-        let private_key = self.operator.private_key();
-        let gas_adjuster_config = &self.gas_adjuster_config;
         let query_client = input.eth_client;
 
+        // Fail fast if a remote signer is misconfigured, rather than on the first transaction to be sent.
+        Self::check_remote_signer(&self.operator).await?;
+        if let Some(blob_operator) = &self.blob_operator {
+            Self::check_remote_signer(blob_operator).await?;
+        }
+
         let l1_diamond_proxy_addr = input
             .l1_contracts
             .0
@@ -76,44 +127,40 @@ impl WiringLayer for PKSigningEthClientLayer {
             .await
             .map_err(WiringError::internal)?;
 
-        let signing_client = PKSigningClient::new_raw(
-            private_key.clone(),
+        let signing_client = self.create_client(
+            &self.operator,
             l1_diamond_proxy_addr,
-            gas_adjuster_config.default_priority_fee_per_gas,
             l1_chain_id,
             query_client.clone(),
-        );
-        let signing_client = Box::new(signing_client);
-
-        let signing_client_for_blobs = self.blob_operator.map(|blob_operator| {
-            let private_key = blob_operator.private_key();
-            let signing_client_for_blobs = PKSigningClient::new_raw(
-                private_key.clone(),
-                l1_diamond_proxy_addr,
-                gas_adjuster_config.default_priority_fee_per_gas,
-                l1_chain_id,
-                query_client,
-            );
-            BoundEthInterfaceForBlobsResource(Box::new(signing_client_for_blobs))
-        });
+        )?;
+
+        let signing_client_for_blobs = self
+            .blob_operator
+            .as_ref()
+            .map(|blob_operator| {
+                self.create_client(
+                    blob_operator,
+                    l1_diamond_proxy_addr,
+                    l1_chain_id,
+                    query_client,
+                )
+            })
+            .transpose()?
+            .map(BoundEthInterfaceForBlobsResource);
 
         let signing_client_for_gateway = match input.gateway_client {
             SettlementLayerClient::Gateway(gateway_client) => {
-                let private_key = self.operator.private_key();
                 let l2_chain_id = gateway_client
                     .fetch_chain_id()
                     .await
                     .map_err(WiringError::internal)?;
-                let signing_client_for_blobs = PKSigningClient::new_raw(
-                    private_key.clone(),
+                let signing_client_for_gateway = self.create_client(
+                    &self.operator,
                     input.contracts.0.chain_contracts_config.diamond_proxy_addr,
-                    gas_adjuster_config.default_priority_fee_per_gas,
                     l2_chain_id,
                     gateway_client,
-                );
-                Some(BoundEthInterfaceForL2Resource(Box::new(
-                    signing_client_for_blobs,
-                )))
+                )?;
+                Some(BoundEthInterfaceForL2Resource(signing_client_for_gateway))
             }
             SettlementLayerClient::L1(_) => None,
         };
@@ -2,16 +2,21 @@ use async_trait::async_trait;
 use zksync_basic_types::Address;
 use zksync_crypto_primitives::{EIP712TypedStructure, Eip712Domain, PackedEthSignature};
 
-pub use crate::{pk_signer::PrivateKeySigner, raw_ethereum_tx::TransactionParameters};
+pub use crate::{
+    pk_signer::PrivateKeySigner, raw_ethereum_tx::TransactionParameters, web3_signer::Web3Signer,
+};
 
 mod pk_signer;
 mod raw_ethereum_tx;
+mod web3_signer;
 
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
 pub enum SignerError {
     #[error("Signing failed: {0}")]
     SigningFailed(String),
+    #[error("Remote signer error: {0}")]
+    Remote(String),
 }
 
 #[async_trait]
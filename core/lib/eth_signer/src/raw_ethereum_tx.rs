@@ -9,17 +9,18 @@
 //! In the case where it will be possible to use only the web3 library without copy-paste, the changes will be small and simple
 //! Link to @Deniallugo's PR to web3: https://github.com/tomusdrw/rust-web3/pull/630
 
-use rlp::RlpStream;
+use rlp::{DecoderError, Rlp, RlpStream};
 use zksync_basic_types::{
+    u256_to_h256,
     web3::{keccak256, AccessList, Signature, SignedTransaction},
     Address, H256, U256, U64,
 };
-use zksync_crypto_primitives::K256PrivateKey;
+use zksync_crypto_primitives::{K256PrivateKey, PackedEthSignature};
 
-const LEGACY_TX_ID: u64 = 0;
-const ACCESSLISTS_TX_ID: u64 = 1;
-const EIP1559_TX_ID: u64 = 2;
-const EIP4844_TX_ID: u64 = 3;
+pub(crate) const LEGACY_TX_ID: u64 = 0;
+pub(crate) const ACCESSLISTS_TX_ID: u64 = 1;
+pub(crate) const EIP1559_TX_ID: u64 = 2;
+pub(crate) const EIP4844_TX_ID: u64 = 3;
 
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct TransactionParameters {
@@ -70,6 +71,26 @@ pub struct Transaction {
     pub blob_versioned_hashes: Option<Vec<H256>>,
 }
 
+impl From<TransactionParameters> for Transaction {
+    fn from(params: TransactionParameters) -> Self {
+        // According to the code in web3 <https://docs.rs/web3/latest/src/web3/api/accounts.rs.html#86>
+        // We should use `max_fee_per_gas` as `gas_price` if we use EIP1559
+        Self {
+            to: params.to,
+            nonce: params.nonce,
+            gas: params.gas,
+            gas_price: params.max_fee_per_gas,
+            value: params.value,
+            data: params.data,
+            transaction_type: params.transaction_type,
+            access_list: params.access_list.unwrap_or_default(),
+            max_priority_fee_per_gas: params.max_priority_fee_per_gas,
+            max_fee_per_blob_gas: params.max_fee_per_blob_gas,
+            blob_versioned_hashes: params.blob_versioned_hashes,
+        }
+    }
+}
+
 impl Transaction {
     fn rlp_append_legacy(&self, stream: &mut RlpStream) {
         stream.append(&self.nonce);
@@ -240,12 +261,74 @@ impl Transaction {
         }
     }
 
-    /// Sign and return a raw signed transaction.
-    pub fn sign(self, private_key: &K256PrivateKey, chain_id: u64) -> SignedTransaction {
-        let adjust_v_value = matches!(
+    fn is_legacy(&self) -> bool {
+        matches!(
             self.transaction_type.map(|t| t.as_u64()),
             Some(LEGACY_TX_ID) | None
-        );
+        )
+    }
+
+    /// Checks that `signed_tx` is this transaction signed for the specified chain, and returns the signer address.
+    /// The check is performed by re-encoding the transaction with the signature from `signed_tx`, so it covers
+    /// all transaction fields (nonce, recipient, value, data, fees etc.).
+    pub(crate) fn recover_signer(
+        &self,
+        chain_id: u64,
+        signed_tx: &[u8],
+    ) -> Result<Address, String> {
+        let payload = if self.is_legacy() {
+            signed_tx
+        } else {
+            let tx_type = self.transaction_type.unwrap_or_default().as_u64();
+            match signed_tx.split_first() {
+                Some((&ty, payload)) if u64::from(ty) == tx_type => payload,
+                _ => return Err("signed transaction differs from the requested one".into()),
+            }
+        };
+        let rlp = Rlp::new(payload);
+        let item_count = rlp
+            .item_count()
+            .map_err(|err| format!("invalid signed transaction: {err}"))?;
+        if item_count < 3 {
+            return Err("signed transaction has no signature".into());
+        }
+        let map_err = |err: DecoderError| format!("invalid signature: {err}");
+        let v: u64 = rlp.val_at(item_count - 3).map_err(map_err)?;
+        let r: U256 = rlp.val_at(item_count - 2).map_err(map_err)?;
+        let s: U256 = rlp.val_at(item_count - 1).map_err(map_err)?;
+        let signature = Signature {
+            v,
+            r: u256_to_h256(r),
+            s: u256_to_h256(s),
+        };
+        if self.encode(chain_id, Some(&signature)) != signed_tx {
+            return Err("signed transaction differs from the requested one".into());
+        }
+
+        let recovery_id = if self.is_legacy() {
+            let (recovery_id, signed_chain_id) = PackedEthSignature::unpack_v(v)
+                .map_err(|err| format!("invalid signature: {err}"))?;
+            if signed_chain_id != Some(chain_id) {
+                return Err(format!(
+                    "transaction is signed for unexpected chain {signed_chain_id:?}"
+                ));
+            }
+            recovery_id
+        } else {
+            u8::try_from(v)
+                .ok()
+                .filter(|&v| v <= 1)
+                .ok_or_else(|| format!("invalid signature: unexpected v = {v}"))?
+        };
+        let message_hash = H256(keccak256(&self.encode(chain_id, None)));
+        PackedEthSignature::from_rsv(&signature.r, &signature.s, recovery_id)
+            .signature_recover_signer(&message_hash)
+            .map_err(|err| format!("invalid signature: {err}"))
+    }
+
+    /// Sign and return a raw signed transaction.
+    pub fn sign(self, private_key: &K256PrivateKey, chain_id: u64) -> SignedTransaction {
+        let adjust_v_value = self.is_legacy();
 
         let encoded = self.encode(chain_id, None);
         let message_hash = H256(keccak256(encoded.as_ref()));
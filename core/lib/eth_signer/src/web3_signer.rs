@@ -0,0 +1,442 @@
+use std::{fmt, time::Duration};
+
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::{client::ClientT, traits::ToRpcParams},
+    http_client::{HttpClient, HttpClientBuilder},
+    rpc_params,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use zksync_basic_types::{url::SensitiveUrl, web3::Bytes, Address, U64};
+use zksync_crypto_primitives::{
+    utils::get_eip712_json, EIP712TypedStructure, Eip712Domain, PackedEthSignature,
+};
+
+use crate::{
+    raw_ethereum_tx::{
+        Transaction, TransactionParameters, ACCESSLISTS_TX_ID, EIP1559_TX_ID, EIP4844_TX_ID,
+        LEGACY_TX_ID,
+    },
+    EthereumSigner, SignerError,
+};
+
+/// Signer delegating to a remote signer over HTTP, such as [Web3Signer](https://docs.web3signer.consensys.io/)
+/// in the `eth1` mode. The private key never leaves the remote signer, so it can be backed by an HSM
+/// or a cloud key vault.
+///
+/// The signer is bound to a single account managed by the remote signer.
+#[derive(Clone)]
+pub struct Web3Signer {
+    client: HttpClient,
+    address: Address,
+}
+
+impl fmt::Debug for Web3Signer {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The signer URL is not output since it may contain credentials.
+        formatter
+            .debug_struct("Web3Signer")
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Web3Signer {
+    /// Creates a signer for the account with the specified `address` managed by the remote signer at `url`.
+    pub fn new(
+        url: &SensitiveUrl,
+        address: Address,
+        request_timeout: Duration,
+    ) -> Result<Self, SignerError> {
+        let client = HttpClientBuilder::default()
+            .request_timeout(request_timeout)
+            .build(url.expose_str())
+            .map_err(|err| SignerError::Remote(format!("cannot create client: {err}")))?;
+        Ok(Self { client, address })
+    }
+
+    /// Gets the Ethereum address of the account this signer is bound to.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Checks that the remote signer manages the account this signer is bound to.
+    pub async fn check_account(&self) -> Result<(), SignerError> {
+        let accounts: Vec<Address> = self.request("eth_accounts", rpc_params![]).await?;
+        if accounts.contains(&self.address) {
+            Ok(())
+        } else {
+            Err(SignerError::Remote(format!(
+                "account {:?} is not managed by the remote signer",
+                self.address
+            )))
+        }
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: &'static str,
+        params: impl ToRpcParams + Send,
+    ) -> Result<T, SignerError> {
+        self.client
+            .request(method, params)
+            .await
+            .map_err(|err| SignerError::Remote(format!("`{method}` failed: {err}")))
+    }
+
+    /// Checks that the transaction can be encoded locally, which is required to verify the remote signature.
+    fn check_transaction(tx: &TransactionParameters) -> Result<(), SignerError> {
+        match tx.transaction_type.map(|ty| ty.as_u64()) {
+            None | Some(LEGACY_TX_ID | ACCESSLISTS_TX_ID | EIP1559_TX_ID) => Ok(()),
+            Some(EIP4844_TX_ID) => {
+                if tx.to.is_none()
+                    || tx.max_fee_per_blob_gas.is_none()
+                    || tx.blob_versioned_hashes.is_none()
+                {
+                    return Err(SignerError::SigningFailed(
+                        "EIP-4844 transaction must have recipient, max fee per blob gas and blob versioned hashes"
+                            .into(),
+                    ));
+                }
+                Ok(())
+            }
+            Some(ty) => Err(SignerError::SigningFailed(format!(
+                "unsupported transaction type: {ty}"
+            ))),
+        }
+    }
+
+    /// Converts transaction params to the format expected by `eth_signTransaction`.
+    fn transaction_to_json(&self, tx: TransactionParameters) -> Value {
+        let tx_type = tx.transaction_type.unwrap_or_default();
+        let mut json = Map::new();
+        json.insert("from".into(), to_json(self.address));
+        if let Some(to) = tx.to {
+            json.insert("to".into(), to_json(to));
+        }
+        json.insert("nonce".into(), to_json(tx.nonce));
+        json.insert("gas".into(), to_json(tx.gas));
+        json.insert("value".into(), to_json(tx.value));
+        json.insert("data".into(), to_json(Bytes(tx.data)));
+        json.insert("chainId".into(), to_json(U64::from(tx.chain_id)));
+
+        // Consistent with `PrivateKeySigner`, `max_fee_per_gas` is used as the gas price for pre-EIP-1559 transactions.
+        if matches!(tx_type.as_u64(), LEGACY_TX_ID | ACCESSLISTS_TX_ID) {
+            json.insert("gasPrice".into(), to_json(tx.max_fee_per_gas));
+        } else {
+            json.insert("maxFeePerGas".into(), to_json(tx.max_fee_per_gas));
+            json.insert(
+                "maxPriorityFeePerGas".into(),
+                to_json(tx.max_priority_fee_per_gas),
+            );
+        }
+        if tx_type.as_u64() != LEGACY_TX_ID {
+            json.insert("type".into(), to_json(tx_type));
+        }
+        if let Some(access_list) = tx.access_list {
+            json.insert("accessList".into(), to_json(access_list));
+        }
+        if let Some(max_fee_per_blob_gas) = tx.max_fee_per_blob_gas {
+            json.insert("maxFeePerBlobGas".into(), to_json(max_fee_per_blob_gas));
+        }
+        if let Some(blob_versioned_hashes) = tx.blob_versioned_hashes {
+            json.insert("blobVersionedHashes".into(), to_json(blob_versioned_hashes));
+        }
+        Value::Object(json)
+    }
+}
+
+fn to_json(value: impl Serialize) -> Value {
+    serde_json::to_value(value).expect("serialization fail")
+}
+
+#[async_trait]
+impl EthereumSigner for Web3Signer {
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        domain: &Eip712Domain,
+        typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        let typed_data = get_eip712_json(domain, typed_struct);
+        let signature: Bytes = self
+            .request("eth_signTypedData", rpc_params![self.address, typed_data])
+            .await?;
+        let signature = PackedEthSignature::deserialize_packed(&signature.0)
+            .map_err(|err| SignerError::Remote(format!("invalid signature: {err}")))?;
+
+        // Check that the remote signer has signed the expected data with the expected key.
+        let signed_bytes = PackedEthSignature::typed_data_to_signed_bytes(domain, typed_struct);
+        let signer = signature
+            .signature_recover_signer(&signed_bytes)
+            .map_err(|err| SignerError::Remote(format!("invalid signature: {err}")))?;
+        if signer != self.address {
+            return Err(SignerError::Remote(format!(
+                "signature is produced by unexpected account {signer:?}"
+            )));
+        }
+        Ok(signature)
+    }
+
+    async fn sign_transaction(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        Self::check_transaction(&raw_tx)?;
+        let chain_id = raw_tx.chain_id;
+        let tx = self.transaction_to_json(raw_tx.clone());
+        let signed_tx: Bytes = self.request("eth_signTransaction", rpc_params![tx]).await?;
+
+        // Check that the remote signer has signed the requested transaction with the expected key.
+        let signer = Transaction::from(raw_tx)
+            .recover_signer(chain_id, &signed_tx.0)
+            .map_err(SignerError::Remote)?;
+        if signer != self.address {
+            return Err(SignerError::Remote(format!(
+                "transaction is signed by unexpected account {signer:?}"
+            )));
+        }
+        Ok(signed_tx.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::VecDeque,
+        net::Ipv4Addr,
+        sync::{Arc, Mutex},
+    };
+
+    use jsonrpsee::{server::ServerBuilder, types::ErrorObjectOwned, RpcModule};
+    use zksync_basic_types::{L2ChainId, H256, U256};
+    use zksync_crypto_primitives::K256PrivateKey;
+
+    use super::*;
+    use crate::PrivateKeySigner;
+
+    /// Requests to and responses from the `eth_signTransaction` method of the mock remote signer.
+    #[derive(Debug, Default)]
+    struct TxSigning {
+        requests: Vec<Value>,
+        responses: VecDeque<Vec<u8>>,
+    }
+
+    /// Stand-in for the remote signer that signs using a local private key. Transaction signing responses
+    /// are supplied by tests.
+    async fn start_remote_signer(
+        signer: PrivateKeySigner,
+        tx_signing: Arc<Mutex<TxSigning>>,
+    ) -> SensitiveUrl {
+        let address = signer.address();
+        let mut rpc_module = RpcModule::new(());
+        rpc_module
+            .register_method("eth_accounts", move |_params, _ctx, _ext| {
+                Ok::<_, ErrorObjectOwned>(vec![address])
+            })
+            .unwrap();
+        rpc_module
+            .register_method("eth_signTransaction", move |params, _ctx, _ext| {
+                let tx: Value = params.one()?;
+                let mut tx_signing = tx_signing.lock().unwrap();
+                tx_signing.requests.push(tx);
+                let response = tx_signing.responses.pop_front().expect("no response");
+                Ok::<_, ErrorObjectOwned>(Bytes(response))
+            })
+            .unwrap();
+
+        let domain = Eip712Domain::new(L2ChainId::from(270));
+        let expected_signature = signer.sign_typed_data(&domain, &domain).unwrap();
+        rpc_module
+            .register_method("eth_signTypedData", move |params, _ctx, _ext| {
+                // Signs the data regardless of the requested account, to test signature verification.
+                let (_, typed_data): (Address, Value) = params.parse()?;
+                assert_eq!(typed_data["primaryType"], "EIP712Domain");
+                assert_eq!(typed_data["message"]["chainId"], "0x10e");
+                Ok::<_, ErrorObjectOwned>(Bytes(expected_signature.serialize_packed().to_vec()))
+            })
+            .unwrap();
+
+        let server = ServerBuilder::default()
+            .http_only()
+            .build((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .unwrap();
+        let local_addr = server.local_addr().unwrap();
+        let server_handle = server.start(rpc_module);
+        tokio::spawn(server_handle.stopped());
+        format!("http://{local_addr}/").parse().unwrap()
+    }
+
+    fn test_signer() -> PrivateKeySigner {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(5)).unwrap();
+        PrivateKeySigner::new(private_key)
+    }
+
+    fn eip1559_tx() -> TransactionParameters {
+        TransactionParameters {
+            nonce: U256::from(1),
+            to: Some(Address::repeat_byte(1)),
+            gas: U256::from(100_000),
+            max_fee_per_gas: U256::from(2),
+            max_priority_fee_per_gas: U256::from(1),
+            data: vec![1, 2, 3],
+            chain_id: 270,
+            transaction_type: Some(U64::from(2)),
+            ..TransactionParameters::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn signing_with_remote_signer() {
+        let local_signer = test_signer();
+        let tx_signing = Arc::<Mutex<TxSigning>>::default();
+        let url = start_remote_signer(local_signer.clone(), tx_signing.clone()).await;
+
+        let signer =
+            Web3Signer::new(&url, local_signer.address(), Duration::from_secs(10)).unwrap();
+        signer.check_account().await.unwrap();
+
+        let raw_tx = eip1559_tx();
+        let expected_signed_tx = local_signer.sign_transaction(raw_tx.clone());
+        tx_signing
+            .lock()
+            .unwrap()
+            .responses
+            .push_back(expected_signed_tx.clone());
+        let signed_tx = signer.sign_transaction(raw_tx).await.unwrap();
+        assert_eq!(signed_tx, expected_signed_tx);
+
+        let tx_requests = tx_signing.lock().unwrap().requests.clone();
+        assert_eq!(tx_requests.len(), 1);
+        let expected_tx = serde_json::json!({
+            "from": local_signer.address(),
+            "to": Address::repeat_byte(1),
+            "nonce": "0x1",
+            "gas": "0x186a0",
+            "value": "0x0",
+            "data": "0x010203",
+            "chainId": "0x10e",
+            "maxFeePerGas": "0x2",
+            "maxPriorityFeePerGas": "0x1",
+            "type": "0x2",
+        });
+        assert_eq!(tx_requests[0], expected_tx);
+
+        let domain = Eip712Domain::new(L2ChainId::from(270));
+        let signature = signer.sign_typed_data(&domain, &domain).await.unwrap();
+        assert_eq!(
+            signature,
+            local_signer.sign_typed_data(&domain, &domain).unwrap()
+        );
+
+        let other_signer =
+            Web3Signer::new(&url, Address::repeat_byte(0xff), Duration::from_secs(10)).unwrap();
+        let err = other_signer.check_account().await.unwrap_err();
+        assert!(err.to_string().contains("not managed"), "{err}");
+        let err = other_signer
+            .sign_typed_data(&domain, &domain)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("unexpected account"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn remote_signer_response_is_verified() {
+        let local_signer = test_signer();
+        let tx_signing = Arc::<Mutex<TxSigning>>::default();
+        let url = start_remote_signer(local_signer.clone(), tx_signing.clone()).await;
+        let signer =
+            Web3Signer::new(&url, local_signer.address(), Duration::from_secs(10)).unwrap();
+
+        let other_signer = PrivateKeySigner::new(K256PrivateKey::random());
+        let mut changed_nonce_tx = eip1559_tx();
+        changed_nonce_tx.nonce += U256::one();
+        let mut changed_value_tx = eip1559_tx();
+        changed_value_tx.value = U256::from(1_000);
+        let mut changed_chain_tx = eip1559_tx();
+        changed_chain_tx.chain_id = 1;
+        let legacy_tx = TransactionParameters {
+            transaction_type: None,
+            ..eip1559_tx()
+        };
+
+        let invalid_responses = [
+            (
+                other_signer.sign_transaction(eip1559_tx()),
+                "unexpected account",
+            ),
+            (local_signer.sign_transaction(changed_nonce_tx), "differs"),
+            (local_signer.sign_transaction(changed_value_tx), "differs"),
+            (local_signer.sign_transaction(changed_chain_tx), "differs"),
+            (local_signer.sign_transaction(legacy_tx), "differs"),
+            (vec![2, 1, 2, 3], "invalid signed transaction"),
+        ];
+        for (response, expected_err) in invalid_responses {
+            tx_signing.lock().unwrap().responses.push_back(response);
+            let err = signer.sign_transaction(eip1559_tx()).await.unwrap_err();
+            assert!(err.to_string().contains(expected_err), "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn signing_legacy_and_blob_transactions_with_remote_signer() {
+        let local_signer = test_signer();
+        let tx_signing = Arc::<Mutex<TxSigning>>::default();
+        let url = start_remote_signer(local_signer.clone(), tx_signing.clone()).await;
+        let signer =
+            Web3Signer::new(&url, local_signer.address(), Duration::from_secs(10)).unwrap();
+
+        let legacy_tx = TransactionParameters {
+            transaction_type: None,
+            ..eip1559_tx()
+        };
+        let blob_tx = TransactionParameters {
+            transaction_type: Some(U64::from(3)),
+            max_fee_per_blob_gas: Some(U256::from(3)),
+            blob_versioned_hashes: Some(vec![H256::repeat_byte(1)]),
+            ..eip1559_tx()
+        };
+        for raw_tx in [legacy_tx, blob_tx.clone()] {
+            let expected_signed_tx = local_signer.sign_transaction(raw_tx.clone());
+            tx_signing
+                .lock()
+                .unwrap()
+                .responses
+                .push_back(expected_signed_tx.clone());
+            let signed_tx = signer.sign_transaction(raw_tx).await.unwrap();
+            assert_eq!(signed_tx, expected_signed_tx);
+        }
+
+        let tx_requests = tx_signing.lock().unwrap().requests.clone();
+        assert_eq!(tx_requests.len(), 2);
+        assert_eq!(tx_requests[0]["gasPrice"], "0x2");
+        assert!(tx_requests[0].get("type").is_none(), "{tx_requests:?}");
+        assert_eq!(tx_requests[1]["type"], "0x3");
+        assert_eq!(tx_requests[1]["maxFeePerBlobGas"], "0x3");
+        assert_eq!(
+            tx_requests[1]["blobVersionedHashes"],
+            serde_json::json!([H256::repeat_byte(1)])
+        );
+
+        // Blob transactions cannot be contract deployments.
+        let invalid_blob_tx = TransactionParameters {
+            to: None,
+            ..blob_tx
+        };
+        let err = signer.sign_transaction(invalid_blob_tx).await.unwrap_err();
+        assert!(matches!(err, SignerError::SigningFailed(_)), "{err}");
+        let unsupported_tx = TransactionParameters {
+            transaction_type: Some(U64::from(0x71)),
+            ..eip1559_tx()
+        };
+        let err = signer.sign_transaction(unsupported_tx).await.unwrap_err();
+        assert!(err.to_string().contains("unsupported"), "{err}");
+        // Invalid transactions must not be sent to the remote signer.
+        assert_eq!(tx_signing.lock().unwrap().requests.len(), 2);
+    }
+}
@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::future;
+use zksync_types::{base_token_ratio::BaseTokenApiRatio, fee_model::ConversionRatio};
+
+use crate::{utils::get_fraction, APIToken, PriceApiClient};
+
+/// Price client aggregating ratios from several sources. Returns the median ratio, and refuses to return
+/// a ratio if too few sources respond or if the sources disagree too much.
+#[derive(Debug)]
+pub struct AggregatedPriceClient {
+    sources: Vec<Arc<dyn PriceApiClient>>,
+    min_sources: usize,
+    max_deviation: f64,
+}
+
+impl AggregatedPriceClient {
+    /// Creates a new client.
+    ///
+    /// - `min_sources` is the minimum number of sources that must successfully return a ratio.
+    /// - `max_deviation` is the maximum relative deviation of any source ratio from the median ratio
+    ///   (e.g., 0.05 for 5%).
+    pub fn new(
+        sources: Vec<Arc<dyn PriceApiClient>>,
+        min_sources: usize,
+        max_deviation: f64,
+    ) -> Self {
+        assert!(
+            min_sources > 0 && min_sources <= sources.len(),
+            "minimum number of sources must be in 1..={}",
+            sources.len()
+        );
+        assert!(max_deviation >= 0.0, "max deviation must be non-negative");
+        Self {
+            sources,
+            min_sources,
+            max_deviation,
+        }
+    }
+
+    fn ratio_value(ratio: &BaseTokenApiRatio) -> f64 {
+        ratio.ratio.numerator.get() as f64 / ratio.ratio.denominator.get() as f64
+    }
+}
+
+#[async_trait]
+impl PriceApiClient for AggregatedPriceClient {
+    async fn fetch_ratio(&self, token: APIToken) -> anyhow::Result<BaseTokenApiRatio> {
+        let results =
+            future::join_all(self.sources.iter().map(|source| source.fetch_ratio(token))).await;
+        let mut ratios = vec![];
+        for (i, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(ratio) => ratios.push(ratio),
+                Err(err) => tracing::warn!("Failed fetching ratio from price source #{i}: {err:#}"),
+            }
+        }
+        anyhow::ensure!(
+            ratios.len() >= self.min_sources,
+            "only {} out of {} price sources returned a ratio, while at least {} are required",
+            ratios.len(),
+            self.sources.len(),
+            self.min_sources
+        );
+
+        ratios.sort_unstable_by(|a, b| Self::ratio_value(a).total_cmp(&Self::ratio_value(b)));
+        let mid = ratios.len() / 2;
+        let median = if ratios.len() % 2 == 1 {
+            Self::ratio_value(&ratios[mid])
+        } else {
+            (Self::ratio_value(&ratios[mid - 1]) + Self::ratio_value(&ratios[mid])) / 2.0
+        };
+
+        // Since ratios are sorted, it's sufficient to check the extreme values.
+        let max_deviation = [&ratios[0], &ratios[ratios.len() - 1]]
+            .into_iter()
+            .map(|ratio| (Self::ratio_value(ratio) - median).abs() / median)
+            .fold(0.0, f64::max);
+        anyhow::ensure!(
+            max_deviation <= self.max_deviation,
+            "price sources disagree: ratios deviate from the median {median} by up to {:.2}%, \
+             while at most {:.2}% is allowed",
+            max_deviation * 100.0,
+            self.max_deviation * 100.0
+        );
+
+        let (numerator, denominator) = get_fraction(median)?;
+        // The aggregated ratio is only as fresh as the oldest ratio used to compute it.
+        let ratio_timestamp = ratios
+            .iter()
+            .map(|ratio| ratio.ratio_timestamp)
+            .min()
+            .unwrap();
+        Ok(BaseTokenApiRatio {
+            ratio: ConversionRatio {
+                numerator,
+                denominator,
+            },
+            ratio_timestamp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use chrono::Utc;
+    use zksync_types::Address;
+
+    use super::*;
+    use crate::tests::approximate_value;
+
+    #[derive(Debug)]
+    struct MockSource(Option<u64>);
+
+    #[async_trait]
+    impl PriceApiClient for MockSource {
+        async fn fetch_ratio(&self, _token: APIToken) -> anyhow::Result<BaseTokenApiRatio> {
+            let numerator = self.0.ok_or_else(|| anyhow::anyhow!("source is down"))?;
+            Ok(BaseTokenApiRatio {
+                ratio: ConversionRatio {
+                    numerator: NonZeroU64::new(numerator).unwrap(),
+                    denominator: NonZeroU64::new(1).unwrap(),
+                },
+                ratio_timestamp: Utc::now(),
+            })
+        }
+    }
+
+    fn client(ratios: &[Option<u64>], min_sources: usize) -> AggregatedPriceClient {
+        let sources = ratios
+            .iter()
+            .map(|&ratio| Arc::new(MockSource(ratio)) as Arc<dyn PriceApiClient>)
+            .collect();
+        AggregatedPriceClient::new(sources, min_sources, 0.05)
+    }
+
+    #[tokio::test]
+    async fn aggregating_ratios() {
+        let ratio = client(&[Some(100), Some(104), Some(102)], 2)
+            .fetch_ratio(APIToken::ERC20(Address::repeat_byte(1)))
+            .await
+            .unwrap();
+        assert_eq!(approximate_value(&ratio), 102.0);
+
+        let ratio = client(&[Some(100), Some(104)], 2)
+            .fetch_ratio(APIToken::ERC20(Address::repeat_byte(1)))
+            .await
+            .unwrap();
+        assert_eq!(approximate_value(&ratio), 102.0);
+
+        // Failing sources are tolerated as long as there are enough other sources.
+        let ratio = client(&[Some(100), None, Some(102)], 2)
+            .fetch_ratio(APIToken::ERC20(Address::repeat_byte(1)))
+            .await
+            .unwrap();
+        assert_eq!(approximate_value(&ratio), 101.0);
+    }
+
+    #[tokio::test]
+    async fn refusing_to_aggregate_ratios() {
+        let err = client(&[Some(100), None, None], 2)
+            .fetch_ratio(APIToken::ERC20(Address::repeat_byte(1)))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("only 1 out of 3"), "{err:#}");
+
+        let err = client(&[Some(100), Some(101), Some(120)], 2)
+            .fetch_ratio(APIToken::ERC20(Address::repeat_byte(1)))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("disagree"), "{err:#}");
+    }
+}
@@ -196,7 +196,7 @@ mod tests {
             base_url: Some(server.base_url()),
             api_key,
             client_timeout: Duration::from_secs(5),
-            forced: None,
+            ..ExternalPriceApiClientConfig::default()
         }))
     }
 
@@ -356,7 +356,7 @@ mod tests {
             base_url: None,
             client_timeout: Duration::from_secs(5),
             source: "coinmarketcap".to_string(),
-            forced: None,
+            ..ExternalPriceApiClientConfig::default()
         });
 
         let tether: Address = "0xdac17f958d2ee523a2206206994597c13d831ec7"
@@ -1,8 +1,10 @@
+pub mod aggregated_price_client;
 pub mod cmc_api;
 pub mod coingecko_api;
 pub mod forced_price_client;
 #[cfg(feature = "node_framework")]
 pub mod node;
+pub mod onchain_price_client;
 #[cfg(test)]
 mod tests;
 mod utils;
@@ -0,0 +1,483 @@
+use anyhow::Context as _;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use zksync_config::configs::external_price_api_client::{
+    ChainlinkPriceFeedConfig, UniswapV3TwapConfig,
+};
+use zksync_eth_client::{
+    web3_decl::client::{DynClient, L1},
+    CallFunctionArgs,
+};
+use zksync_types::{
+    base_token_ratio::BaseTokenApiRatio,
+    ethabi::{self, Token},
+    web3::contract::{Detokenize, Error as ContractError},
+    Address, U256,
+};
+
+use crate::{utils::eth_price_to_base_token_ratio, APIToken, PriceApiClient};
+
+const CHAINLINK_AGGREGATOR_ABI: &str = r#"[
+  {"type": "function", "name": "decimals", "stateMutability": "view", "inputs": [],
+   "outputs": [{"name": "", "type": "uint8"}]},
+  {"type": "function", "name": "latestRoundData", "stateMutability": "view", "inputs": [],
+   "outputs": [
+     {"name": "roundId", "type": "uint80"},
+     {"name": "answer", "type": "int256"},
+     {"name": "startedAt", "type": "uint256"},
+     {"name": "updatedAt", "type": "uint256"},
+     {"name": "answeredInRound", "type": "uint80"}
+   ]}
+]"#;
+
+const UNISWAP_V3_POOL_ABI: &str = r#"[
+  {"type": "function", "name": "token0", "stateMutability": "view", "inputs": [],
+   "outputs": [{"name": "", "type": "address"}]},
+  {"type": "function", "name": "token1", "stateMutability": "view", "inputs": [],
+   "outputs": [{"name": "", "type": "address"}]},
+  {"type": "function", "name": "observe", "stateMutability": "view",
+   "inputs": [{"name": "secondsAgos", "type": "uint32[]"}],
+   "outputs": [
+     {"name": "tickCumulatives", "type": "int56[]"},
+     {"name": "secondsPerLiquidityCumulativeX128s", "type": "uint160[]"}
+   ]}
+]"#;
+
+const ERC20_DECIMALS_ABI: &str = r#"[
+  {"type": "function", "name": "decimals", "stateMutability": "view", "inputs": [],
+   "outputs": [{"name": "", "type": "uint8"}]}
+]"#;
+
+static CHAINLINK_AGGREGATOR_CONTRACT: Lazy<ethabi::Contract> = Lazy::new(|| {
+    ethabi::Contract::load(CHAINLINK_AGGREGATOR_ABI.as_bytes())
+        .expect("invalid Chainlink aggregator ABI")
+});
+static UNISWAP_V3_POOL_CONTRACT: Lazy<ethabi::Contract> = Lazy::new(|| {
+    ethabi::Contract::load(UNISWAP_V3_POOL_ABI.as_bytes()).expect("invalid Uniswap V3 pool ABI")
+});
+static ERC20_CONTRACT: Lazy<ethabi::Contract> = Lazy::new(|| {
+    ethabi::Contract::load(ERC20_DECIMALS_ABI.as_bytes()).expect("invalid ERC-20 ABI")
+});
+
+/// Converts a sign-extended ABI-encoded integer fitting into `i64` (e.g., `int56`).
+fn token_to_i64(token: Token) -> Result<i64, ContractError> {
+    match token {
+        // Two's complement representation of sign-extended values coincides in the lowest 64 bits.
+        Token::Int(value) => Ok(value.low_u64() as i64),
+        other => Err(ContractError::InvalidOutputType(format!(
+            "expected `Int`, got {other:?}"
+        ))),
+    }
+}
+
+/// Output of the `latestRoundData()` call for a Chainlink aggregator.
+#[derive(Debug)]
+struct LatestRoundData {
+    answer: U256,
+    updated_at: U256,
+}
+
+impl Detokenize for LatestRoundData {
+    fn from_tokens(tokens: Vec<Token>) -> Result<Self, ContractError> {
+        let [_, Token::Int(answer), _, Token::Uint(updated_at), _] = <[Token; 5]>::try_from(tokens)
+            .map_err(|tokens| {
+                ContractError::InvalidOutputType(format!("expected 5 tokens, got {tokens:?}"))
+            })?
+        else {
+            return Err(ContractError::InvalidOutputType(
+                "unexpected `latestRoundData()` output types".into(),
+            ));
+        };
+        Ok(Self { answer, updated_at })
+    }
+}
+
+/// Output of the `observe()` call for a Uniswap V3 pool.
+#[derive(Debug)]
+struct TickCumulatives(Vec<i64>);
+
+impl Detokenize for TickCumulatives {
+    fn from_tokens(tokens: Vec<Token>) -> Result<Self, ContractError> {
+        let Some(Token::Array(tick_cumulatives)) = tokens.into_iter().next() else {
+            return Err(ContractError::InvalidOutputType(
+                "unexpected `observe()` output types".into(),
+            ));
+        };
+        let tick_cumulatives = tick_cumulatives
+            .into_iter()
+            .map(token_to_i64)
+            .collect::<Result<_, _>>()?;
+        Ok(Self(tick_cumulatives))
+    }
+}
+
+/// On-chain price oracle on L1 that can be used by [`OnChainPriceClient`].
+#[derive(Debug, Clone)]
+pub enum OnChainPriceFeed {
+    /// Chainlink `AggregatorV3` feed quoting the base token in ETH.
+    Chainlink(ChainlinkPriceFeedConfig),
+    /// Time-weighted average price of a Uniswap V3 pool pairing the base token with WETH.
+    UniswapV3Twap(UniswapV3TwapConfig),
+}
+
+/// Price client reading the base token price from an on-chain oracle on L1.
+#[derive(Debug)]
+pub struct OnChainPriceClient {
+    feed: OnChainPriceFeed,
+    client: Box<DynClient<L1>>,
+}
+
+impl OnChainPriceClient {
+    pub fn new(feed: OnChainPriceFeed, client: Box<DynClient<L1>>) -> Self {
+        Self {
+            feed,
+            client: client.for_component("external_price_api"),
+        }
+    }
+
+    async fn fetch_chainlink_ratio(
+        &self,
+        config: &ChainlinkPriceFeedConfig,
+    ) -> anyhow::Result<BaseTokenApiRatio> {
+        let feed_address = config.feed_address;
+        let decimals: U256 = CallFunctionArgs::new("decimals", ())
+            .for_contract(feed_address, &CHAINLINK_AGGREGATOR_CONTRACT)
+            .call(&self.client)
+            .await
+            .with_context(|| format!("failed getting decimals for price feed {feed_address:?}"))?;
+        let round: LatestRoundData = CallFunctionArgs::new("latestRoundData", ())
+            .for_contract(feed_address, &CHAINLINK_AGGREGATOR_CONTRACT)
+            .call(&self.client)
+            .await
+            .with_context(|| {
+                format!("failed getting latest round for price feed {feed_address:?}")
+            })?;
+
+        let updated_at = u64::try_from(round.updated_at)
+            .ok()
+            .and_then(|timestamp| DateTime::<Utc>::from_timestamp(timestamp as i64, 0))
+            .with_context(|| format!("invalid update timestamp: {}", round.updated_at))?;
+        let max_staleness =
+            chrono::Duration::from_std(config.max_staleness).context("invalid max staleness")?;
+        let age = Utc::now() - updated_at;
+        anyhow::ensure!(
+            age <= max_staleness,
+            "price feed {feed_address:?} is stale: last updated at {updated_at}"
+        );
+
+        // `int256` answers are encoded in two's complement, so negative answers have the highest bit set.
+        anyhow::ensure!(
+            !round.answer.is_zero() && !round.answer.bit(255),
+            "price feed {feed_address:?} returned non-positive answer"
+        );
+        let answer = u128::try_from(round.answer)
+            .map_err(|_| anyhow::anyhow!("price feed answer {} is too large", round.answer))?;
+        let price = answer as f64 / 10_f64.powi(decimals.low_u32() as i32);
+
+        let mut ratio = eth_price_to_base_token_ratio(price)?;
+        ratio.ratio_timestamp = updated_at;
+        Ok(ratio)
+    }
+
+    async fn fetch_token_decimals(&self, token: Address) -> anyhow::Result<i32> {
+        let decimals: U256 = CallFunctionArgs::new("decimals", ())
+            .for_contract(token, &ERC20_CONTRACT)
+            .call(&self.client)
+            .await
+            .with_context(|| format!("failed getting decimals for token {token:?}"))?;
+        Ok(decimals.low_u32() as i32)
+    }
+
+    async fn fetch_uniswap_v3_twap_ratio(
+        &self,
+        config: &UniswapV3TwapConfig,
+        token: Address,
+    ) -> anyhow::Result<BaseTokenApiRatio> {
+        let pool_address = config.pool_address;
+        let token0: Address = CallFunctionArgs::new("token0", ())
+            .for_contract(pool_address, &UNISWAP_V3_POOL_CONTRACT)
+            .call(&self.client)
+            .await
+            .with_context(|| format!("failed getting token0 for pool {pool_address:?}"))?;
+        let token1: Address = CallFunctionArgs::new("token1", ())
+            .for_contract(pool_address, &UNISWAP_V3_POOL_CONTRACT)
+            .call(&self.client)
+            .await
+            .with_context(|| format!("failed getting token1 for pool {pool_address:?}"))?;
+        // Otherwise, the pool would quote the base token in something other than ETH.
+        let weth = config.weth_address;
+        anyhow::ensure!(
+            token != weth,
+            "base token {token:?} cannot be priced using a WETH pool"
+        );
+        let base_token_is_token0 = if (token0, token1) == (token, weth) {
+            true
+        } else if (token0, token1) == (weth, token) {
+            false
+        } else {
+            anyhow::bail!(
+                "pool {pool_address:?} pairs {token0:?} and {token1:?} instead of base token {token:?} and WETH {weth:?}"
+            );
+        };
+        let decimals0 = self.fetch_token_decimals(token0).await?;
+        let decimals1 = self.fetch_token_decimals(token1).await?;
+
+        let interval = u32::try_from(config.twap_interval.as_secs())
+            .ok()
+            .filter(|&interval| interval > 0)
+            .context("TWAP interval must be positive and fit into u32 seconds")?;
+        let seconds_agos = Token::Array(vec![
+            Token::Uint(interval.into()),
+            Token::Uint(U256::zero()),
+        ]);
+        let tick_cumulatives: TickCumulatives = CallFunctionArgs::new("observe", seconds_agos)
+            .for_contract(pool_address, &UNISWAP_V3_POOL_CONTRACT)
+            .call(&self.client)
+            .await
+            .with_context(|| format!("failed observing pool {pool_address:?}"))?;
+        let [start, end] = <[i64; 2]>::try_from(tick_cumulatives.0).map_err(|cumulatives| {
+            anyhow::anyhow!("unexpected number of tick cumulatives: {cumulatives:?}")
+        })?;
+
+        // Rounds towards negative infinity, consistently with Uniswap's `OracleLibrary`.
+        let mean_tick = (end - start).div_euclid(interval.into());
+        let mean_tick = i32::try_from(mean_tick).context("mean tick is out of range")?;
+        // Price of token0 in token1, adjusted for token decimals.
+        let price0 = 1.0001_f64.powi(mean_tick) * 10_f64.powi(decimals0 - decimals1);
+        let price = if base_token_is_token0 {
+            price0
+        } else {
+            1.0 / price0
+        };
+        eth_price_to_base_token_ratio(price)
+    }
+}
+
+#[async_trait]
+impl PriceApiClient for OnChainPriceClient {
+    async fn fetch_ratio(&self, token: APIToken) -> anyhow::Result<BaseTokenApiRatio> {
+        let address = match token {
+            APIToken::Eth => return Ok(BaseTokenApiRatio::identity()),
+            APIToken::ERC20(address) => address,
+            APIToken::ZK => anyhow::bail!("ZK is not supported by on-chain price feeds"),
+        };
+
+        match &self.feed {
+            // The feed is configured for a specific token, so `address` is not checked.
+            OnChainPriceFeed::Chainlink(config) => self.fetch_chainlink_ratio(config).await,
+            OnChainPriceFeed::UniswapV3Twap(config) => {
+                self.fetch_uniswap_v3_twap_ratio(config, address).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use zksync_eth_client::web3_decl::client::MockClient;
+    use zksync_types::web3::{self, BlockId, CallRequest};
+
+    use super::*;
+    use crate::tests::approximate_value;
+
+    const FEED_ADDRESS: Address = Address([1; 20]);
+    const POOL_ADDRESS: Address = Address([2; 20]);
+    const BASE_TOKEN_ADDRESS: Address = Address([3; 20]);
+    const WETH_ADDRESS: Address = Address([4; 20]);
+
+    fn int_token(value: i64) -> Token {
+        let abs_value = U256::from(value.unsigned_abs());
+        Token::Int(if value < 0 {
+            U256::MAX - abs_value + 1
+        } else {
+            abs_value
+        })
+    }
+
+    fn encode_output(
+        contract: &ethabi::Contract,
+        data: &[u8],
+        outputs: &[(&str, Vec<Token>)],
+    ) -> web3::Bytes {
+        let (_, output) = outputs
+            .iter()
+            .find(|(name, _)| contract.function(name).unwrap().short_signature() == data[..4])
+            .expect("unexpected function call");
+        web3::Bytes(ethabi::encode(output))
+    }
+
+    fn mock_client(
+        updated_at: u64,
+        mean_tick: i64,
+        [token0, token1]: [Address; 2],
+    ) -> Box<DynClient<L1>> {
+        let client = MockClient::builder(L1::default())
+            .method("eth_call", move |req: CallRequest, _block_id: BlockId| {
+                let data = req.data.unwrap().0;
+                let to = req.to.unwrap();
+                let output = if to == FEED_ADDRESS {
+                    let outputs = [
+                        ("decimals", vec![Token::Uint(18.into())]),
+                        (
+                            "latestRoundData",
+                            vec![
+                                Token::Uint(1.into()),
+                                // 1 base token = 0.00269 ETH
+                                Token::Int(2_690_000_000_000_000_u64.into()),
+                                Token::Uint(updated_at.into()),
+                                Token::Uint(updated_at.into()),
+                                Token::Uint(1.into()),
+                            ],
+                        ),
+                    ];
+                    encode_output(&CHAINLINK_AGGREGATOR_CONTRACT, &data, &outputs)
+                } else if to == POOL_ADDRESS {
+                    let start = 1_000_000;
+                    let end = start + mean_tick * 600;
+                    let outputs = [
+                        ("token0", vec![Token::Address(token0)]),
+                        ("token1", vec![Token::Address(token1)]),
+                        (
+                            "observe",
+                            vec![
+                                Token::Array(vec![int_token(start), int_token(end)]),
+                                Token::Array(vec![Token::Uint(0.into()); 2]),
+                            ],
+                        ),
+                    ];
+                    encode_output(&UNISWAP_V3_POOL_CONTRACT, &data, &outputs)
+                } else if to == BASE_TOKEN_ADDRESS || to == WETH_ADDRESS {
+                    let outputs = [("decimals", vec![Token::Uint(18.into())])];
+                    encode_output(&ERC20_CONTRACT, &data, &outputs)
+                } else {
+                    panic!("unexpected call to {to:?}");
+                };
+                Ok(output)
+            })
+            .build();
+        Box::new(client)
+    }
+
+    fn chainlink_feed(max_staleness: Duration) -> OnChainPriceFeed {
+        OnChainPriceFeed::Chainlink(ChainlinkPriceFeedConfig {
+            feed_address: FEED_ADDRESS,
+            max_staleness,
+        })
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio_from_chainlink() {
+        let updated_at = Utc::now().timestamp() as u64 - 60;
+        let client = OnChainPriceClient::new(
+            chainlink_feed(Duration::from_secs(3_600)),
+            mock_client(updated_at, 0, [BASE_TOKEN_ADDRESS, WETH_ADDRESS]),
+        );
+
+        let ratio = client
+            .fetch_ratio(APIToken::ERC20(BASE_TOKEN_ADDRESS))
+            .await
+            .unwrap();
+        assert!(
+            (approximate_value(&ratio) - 371.74).abs() < 0.1,
+            "{ratio:?}"
+        );
+        assert_eq!(ratio.ratio_timestamp.timestamp() as u64, updated_at);
+
+        let ratio = client.fetch_ratio(APIToken::Eth).await.unwrap();
+        assert_eq!(approximate_value(&ratio), 1.0);
+    }
+
+    #[tokio::test]
+    async fn stale_chainlink_answer_is_rejected() {
+        let updated_at = Utc::now().timestamp() as u64 - 7_200;
+        let client = OnChainPriceClient::new(
+            chainlink_feed(Duration::from_secs(3_600)),
+            mock_client(updated_at, 0, [BASE_TOKEN_ADDRESS, WETH_ADDRESS]),
+        );
+
+        let err = client
+            .fetch_ratio(APIToken::ERC20(BASE_TOKEN_ADDRESS))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("stale"), "{err:#}");
+    }
+
+    fn uniswap_v3_feed() -> OnChainPriceFeed {
+        OnChainPriceFeed::UniswapV3Twap(UniswapV3TwapConfig {
+            pool_address: POOL_ADDRESS,
+            weth_address: WETH_ADDRESS,
+            twap_interval: Duration::from_secs(600),
+        })
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio_from_uniswap_v3_twap() {
+        // 1.0001^(-59186) ~= 0.00269
+        let mean_tick = -59_186;
+        let pool_tokens = [BASE_TOKEN_ADDRESS, WETH_ADDRESS];
+        let client =
+            OnChainPriceClient::new(uniswap_v3_feed(), mock_client(0, mean_tick, pool_tokens));
+
+        let ratio = client
+            .fetch_ratio(APIToken::ERC20(BASE_TOKEN_ADDRESS))
+            .await
+            .unwrap();
+        assert!(
+            (approximate_value(&ratio) - 371.74).abs() < 0.1,
+            "{ratio:?}"
+        );
+
+        let err = client
+            .fetch_ratio(APIToken::ERC20(Address::repeat_byte(0xff)))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("instead of base token"), "{err:#}");
+        let err = client
+            .fetch_ratio(APIToken::ERC20(WETH_ADDRESS))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot be priced"), "{err:#}");
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio_from_uniswap_v3_twap_with_reversed_tokens() {
+        // The pool quotes WETH in the base token, so the tick sign is flipped.
+        let mean_tick = 59_186;
+        let pool_tokens = [WETH_ADDRESS, BASE_TOKEN_ADDRESS];
+        let client =
+            OnChainPriceClient::new(uniswap_v3_feed(), mock_client(0, mean_tick, pool_tokens));
+
+        let ratio = client
+            .fetch_ratio(APIToken::ERC20(BASE_TOKEN_ADDRESS))
+            .await
+            .unwrap();
+        assert!(
+            (approximate_value(&ratio) - 371.74).abs() < 0.1,
+            "{ratio:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn uniswap_v3_pool_without_weth_is_rejected() {
+        let other_token = Address::repeat_byte(0xff);
+        for pool_tokens in [
+            [BASE_TOKEN_ADDRESS, other_token],
+            [other_token, WETH_ADDRESS],
+            [BASE_TOKEN_ADDRESS, BASE_TOKEN_ADDRESS],
+        ] {
+            let client =
+                OnChainPriceClient::new(uniswap_v3_feed(), mock_client(0, -59_186, pool_tokens));
+            let err = client
+                .fetch_ratio(APIToken::ERC20(BASE_TOKEN_ADDRESS))
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("instead of base token"),
+                "{pool_tokens:?}: {err:#}"
+            );
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use std::collections::{hash_map, BTreeMap, BTreeSet, HashMap};
 
 use zksync_types::{
-    l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, ProtocolVersionId,
-    Transaction, TransactionTimeRangeConstraint,
+    l1::L1Tx, l2::L2Tx, mempool::MempoolOrdering, Address, ExecuteTransactionCommon, Nonce,
+    PriorityOpId, ProtocolVersionId, Transaction, TransactionTimeRangeConstraint,
 };
 
 use crate::types::{AccountTransactions, AdvanceInput, L2TxFilter, MempoolScore};
@@ -39,9 +39,13 @@ pub struct MempoolStore {
     /// Next priority operation
     next_priority_id: PriorityOpId,
     stashed_accounts: Vec<Address>,
+    /// Accounts evicted from the mempool since the last `get_mempool_info()` call.
+    evicted_accounts: Vec<Address>,
     /// Number of L2 transactions in the mempool.
     size: u64,
     capacity: u64,
+    /// Policy used to order L2 transactions from different accounts.
+    ordering: MempoolOrdering,
 }
 
 impl MempoolStore {
@@ -61,11 +65,23 @@ impl MempoolStore {
             high_priority_l2_tx_protocol_version,
             next_priority_id,
             stashed_accounts: vec![],
+            evicted_accounts: vec![],
             size: 0,
             capacity,
+            ordering: MempoolOrdering::default(),
         }
     }
 
+    /// Sets the ordering policy for L2 transactions. Must be called before any transactions are inserted.
+    pub fn with_ordering(mut self, ordering: MempoolOrdering) -> Self {
+        assert_eq!(
+            self.size, 0,
+            "cannot change ordering of a non-empty mempool"
+        );
+        self.ordering = ordering;
+        self
+    }
+
     /// Inserts batch of new transactions to mempool
     /// `initial_nonces` provides current committed nonce information to mempool
     /// variable is used only if account is not present in mempool yet and we have to bootstrap it
@@ -112,6 +128,7 @@ impl MempoolStore {
                 }
             }
         }
+        self.evict_lowest_score_accounts();
     }
 
     #[cfg(test)]
@@ -136,6 +153,7 @@ impl MempoolStore {
         initial_nonces: &HashMap<Address, Nonce>,
     ) {
         let account = transaction.initiator_account();
+        let ordering = self.ordering;
 
         let (txs_per_account, priority_queue) =
             if Some(account) == self.high_priority_l2_tx_initiator {
@@ -155,7 +173,7 @@ impl MempoolStore {
             hash_map::Entry::Vacant(entry) => {
                 let account_nonce = initial_nonces.get(&account).cloned().unwrap_or(Nonce(0));
                 entry
-                    .insert(AccountTransactions::new(account_nonce))
+                    .insert(AccountTransactions::new(account_nonce, ordering))
                     .insert(transaction, constraint)
             }
         };
@@ -171,6 +189,54 @@ impl MempoolStore {
         }
     }
 
+    /// Evicts accounts from the mempool once it exceeds its capacity. Accounts without executable transactions
+    /// (i.e., with a nonce gap) are evicted first, then accounts with the lowest-scored transactions.
+    /// Transactions of the high-priority initiator are never evicted. At least one account with executable transactions
+    /// is always kept, otherwise mempool won't return any new L2 tx to process.
+    ///
+    /// Evicted accounts are reported as purged in [`Self::get_mempool_info()`].
+    fn evict_lowest_score_accounts(&mut self) {
+        if self.size <= self.capacity {
+            return;
+        }
+
+        let stuck_accounts: Vec<_> = self
+            .l2_transactions_per_account
+            .iter()
+            .filter_map(|(&account, txs)| txs.is_stuck().then_some(account))
+            .collect();
+        for account in stuck_accounts {
+            if self.size <= self.capacity {
+                return;
+            }
+            self.evict_account(account);
+        }
+
+        while self.size > self.capacity && self.l2_priority_queue.len() > 1 {
+            let pointer = self
+                .l2_priority_queue
+                .pop_first()
+                .expect("priority queue is not empty");
+            self.evict_account(pointer.account);
+        }
+    }
+
+    fn evict_account(&mut self, account: Address) {
+        let account_txs = self
+            .l2_transactions_per_account
+            .remove(&account)
+            .expect("mempool: evicted account is missing");
+        tracing::debug!(
+            "Evicting {} transactions of account {account:?} from mempool",
+            account_txs.len()
+        );
+        self.size = self
+            .size
+            .checked_sub(account_txs.len() as u64)
+            .expect("mempool size can't be negative");
+        self.evicted_accounts.push(account);
+    }
+
     /// Returns `true` if there is a transaction in the mempool satisfying the filter.
     pub fn has_next(&self, filter: &L2TxFilter) -> bool {
         let has_priority_tx =
@@ -347,9 +413,11 @@ impl MempoolStore {
     }
 
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
+        let mut purged_accounts = std::mem::take(&mut self.evicted_accounts);
+        purged_accounts.extend(self.gc());
         MempoolInfo {
             stashed_accounts: std::mem::take(&mut self.stashed_accounts),
-            purged_accounts: self.gc(),
+            purged_accounts,
         }
     }
 
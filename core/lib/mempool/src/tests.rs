@@ -8,6 +8,7 @@ use zksync_types::{
     helpers::unix_timestamp_ms,
     l1::{OpProcessingType, PriorityQueueType},
     l2::L2Tx,
+    mempool::MempoolOrdering,
     Address, Execute, ExecuteTransactionCommon, L1TxCommonData, Nonce, PriorityOpId,
     ProtocolVersionId, Transaction, TransactionTimeRangeConstraint, H256, U256,
 };
@@ -412,6 +413,51 @@ fn mempool_does_not_purge_all_accounts() {
     assert!(!mempool.has_next(&L2TxFilter::default()));
 }
 
+#[test]
+fn mempool_evicts_lowest_score_accounts() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 3, None, None)
+        .with_ordering(MempoolOrdering::PriorityFee);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let account2 = Address::random();
+    let transactions = vec![
+        gen_l2_tx_with_priority_fee(account0, Nonce(0), 1_000, 5),
+        gen_l2_tx_with_priority_fee(account0, Nonce(1), 1_001, 5),
+        gen_l2_tx_with_priority_fee(account1, Nonce(0), 1_002, 1),
+    ];
+    mempool.insert_without_constraints(transactions, HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+
+    // Mempool is at capacity; inserting a transaction with a higher score should evict the lowest-scored account.
+    mempool.insert_without_constraints(
+        vec![gen_l2_tx_with_priority_fee(account2, Nonce(0), 1_003, 10)],
+        HashMap::new(),
+    );
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+    assert_eq!(mempool.get_mempool_info().purged_accounts, [account1]);
+
+    // Inserting a transaction with the lowest score should evict it right away.
+    mempool.insert_without_constraints(
+        vec![gen_l2_tx_with_priority_fee(account1, Nonce(0), 1_004, 1)],
+        HashMap::new(),
+    );
+    assert_eq!(mempool.get_mempool_info().purged_accounts, [account1]);
+
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account2, 0)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 0)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 1)
+    );
+    assert!(!mempool.has_next(&L2TxFilter::default()));
+}
+
 #[test]
 fn advance_after_block_removes_processed_txs_and_updates_nonce() {
     let mut mempool = MempoolStore::new(PriorityOpId(0), 100, None, None);
@@ -613,6 +659,68 @@ fn high_priority_l2_txs_execute_after_protocol_version() {
     );
 }
 
+#[test]
+fn priority_fee_ordering() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, None, None)
+        .with_ordering(MempoolOrdering::PriorityFee);
+    let cheap_account = Address::random();
+    let expensive_account = Address::random();
+    let early_expensive_account = Address::random();
+    let transactions = vec![
+        gen_l2_tx_with_priority_fee(cheap_account, Nonce(0), 1_000, 1),
+        gen_l2_tx_with_priority_fee(cheap_account, Nonce(1), 1_001, 1),
+        gen_l2_tx_with_priority_fee(expensive_account, Nonce(0), 2_000, 10),
+        // Has a lower fee than the cheap account, so it should be executed last despite its predecessor
+        gen_l2_tx_with_priority_fee(expensive_account, Nonce(1), 2_001, 0),
+        // Same fee as for the expensive account, but received earlier
+        gen_l2_tx_with_priority_fee(early_expensive_account, Nonce(0), 1_500, 10),
+    ];
+    mempool.insert_without_constraints(transactions, HashMap::new());
+
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (early_expensive_account, 0)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (expensive_account, 0)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (cheap_account, 0)
+    );
+
+    // Reject the transaction; the account should be blocked until the transaction is re-inserted.
+    mempool.rollback(&gen_l2_tx_with_priority_fee(
+        cheap_account,
+        Nonce(0),
+        1_000,
+        1,
+    ));
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (expensive_account, 1)
+    );
+    mempool.insert_without_constraints(
+        vec![gen_l2_tx_with_priority_fee(
+            cheap_account,
+            Nonce(0),
+            1_000,
+            1,
+        )],
+        HashMap::new(),
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (cheap_account, 0)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (cheap_account, 1)
+    );
+    assert_eq!(mempool.next_transaction(&L2TxFilter::default()), None);
+}
+
 fn gen_l2_tx(address: Address, nonce: Nonce) -> Transaction {
     gen_l2_tx_with_timestamp(address, nonce, unix_timestamp_ms())
 }
@@ -632,6 +740,21 @@ fn gen_l2_tx_with_timestamp(address: Address, nonce: Nonce, received_at_ms: u64)
     txn.into()
 }
 
+fn gen_l2_tx_with_priority_fee(
+    address: Address,
+    nonce: Nonce,
+    received_at_ms: u64,
+    max_priority_fee_per_gas: u64,
+) -> Transaction {
+    let mut tx = gen_l2_tx_with_timestamp(address, nonce, received_at_ms);
+    let ExecuteTransactionCommon::L2(data) = &mut tx.common_data else {
+        unreachable!();
+    };
+    data.fee.max_fee_per_gas = U256::from(100);
+    data.fee.max_priority_fee_per_gas = U256::from(max_priority_fee_per_gas);
+    tx
+}
+
 fn gen_l1_tx(priority_id: PriorityOpId, address: Option<Address>) -> Transaction {
     let execute = Execute {
         contract_address: Some(Address::repeat_byte(0x11)),
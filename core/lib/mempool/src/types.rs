@@ -1,8 +1,8 @@
 use std::{cmp::Ordering, collections::BTreeMap};
 
 use zksync_types::{
-    fee::Fee, fee_model::BatchFeeInput, l2::L2Tx, Address, Nonce, PriorityOpId, ProtocolVersionId,
-    Transaction, TransactionTimeRangeConstraint, U256,
+    fee::Fee, fee_model::BatchFeeInput, l2::L2Tx, mempool::MempoolOrdering, Address, Nonce,
+    PriorityOpId, ProtocolVersionId, Transaction, TransactionTimeRangeConstraint, U256,
 };
 
 /// Pending mempool transactions of account
@@ -13,13 +13,16 @@ pub(crate) struct AccountTransactions {
     /// account nonce in mempool
     /// equals to committed nonce in db + number of transactions sent to state keeper
     nonce: Nonce,
+    /// ordering policy used to compute transaction scores
+    ordering: MempoolOrdering,
 }
 
 impl AccountTransactions {
-    pub fn new(nonce: Nonce) -> Self {
+    pub fn new(nonce: Nonce, ordering: MempoolOrdering) -> Self {
         Self {
             transactions: BTreeMap::new(),
             nonce,
+            ordering,
         }
     }
 
@@ -35,11 +38,12 @@ impl AccountTransactions {
         if nonce < self.nonce {
             return metadata;
         }
-        let new_score = Self::score_for_transaction(&transaction);
+        let ordering = self.ordering;
+        let new_score = MempoolScore::new(&transaction, ordering);
         let previous_score = self
             .transactions
             .insert(nonce, (transaction, constraint))
-            .map(|x| Self::score_for_transaction(&x.0));
+            .map(|x| MempoolScore::new(&x.0, ordering));
         metadata.is_new = previous_score.is_none();
         if nonce == self.nonce {
             metadata.new_score = Some(new_score);
@@ -57,11 +61,11 @@ impl AccountTransactions {
         let new_score = self
             .transactions
             .get(&nonce)
-            .map(|x| Self::score_for_transaction(&x.0));
+            .map(|x| MempoolScore::new(&x.0, self.ordering));
         let previous_score = self
             .transactions
             .get(&self.nonce)
-            .map(|x| Self::score_for_transaction(&x.0));
+            .map(|x| MempoolScore::new(&x.0, self.ordering));
 
         self.transactions = self.transactions.split_off(&nonce);
         self.nonce = nonce;
@@ -83,7 +87,7 @@ impl AccountTransactions {
         let score = self
             .transactions
             .get(&self.nonce)
-            .map(|(tx, _c)| Self::score_for_transaction(tx));
+            .map(|(tx, _c)| MempoolScore::new(tx, self.ordering));
         (transaction.0, transaction.1, score)
     }
 
@@ -100,13 +104,19 @@ impl AccountTransactions {
         self.nonce = self.nonce.min(tx_nonce);
         self.transactions
             .get(&(tx_nonce + 1))
-            .map(|(tx, c)| (Self::score_for_transaction(tx), c.clone()))
+            .map(|(tx, c)| (MempoolScore::new(tx, self.ordering), c.clone()))
     }
 
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
 
+    /// Returns `true` if the account has pending transactions, none of which can be executed
+    /// because of a nonce gap.
+    pub fn is_stuck(&self) -> bool {
+        !self.transactions.is_empty() && !self.transactions.contains_key(&self.nonce)
+    }
+
     pub fn nonce(&self) -> Nonce {
         self.nonce
     }
@@ -114,22 +124,17 @@ impl AccountTransactions {
     pub fn clear_txs(&mut self) {
         self.transactions.clear();
     }
-
-    fn score_for_transaction(transaction: &L2Tx) -> MempoolScore {
-        MempoolScore {
-            account: transaction.initiator_account(),
-            received_at_ms: transaction.received_timestamp_ms,
-            fee_data: transaction.common_data.fee.clone(),
-        }
-    }
 }
 
-/// Mempool score of transaction. Used to prioritize L2 transactions in mempool
-/// Currently trivial ordering is used based on received at timestamp
+/// Mempool score of transaction. Used to prioritize L2 transactions in mempool.
+/// Transactions are ordered by `priority_fee` (which is always zero for the FIFO ordering),
+/// then by received at timestamp.
 #[derive(Eq, PartialEq, Clone, Debug, Hash)]
 pub struct MempoolScore {
     pub account: Address,
     pub received_at_ms: u64,
+    /// Priority fee per gas used for scoring.
+    pub priority_fee: U256,
     // Not used for actual scoring, but state keeper would request
     // transactions that have acceptable fee values (so transactions
     // with fee too low would be ignored until prices go down).
@@ -137,6 +142,23 @@ pub struct MempoolScore {
 }
 
 impl MempoolScore {
+    fn new(transaction: &L2Tx, ordering: MempoolOrdering) -> Self {
+        let fee_data = &transaction.common_data.fee;
+        let priority_fee = match ordering {
+            MempoolOrdering::Fifo => U256::zero(),
+            // The priority fee cannot exceed the max fee, so it's capped to not let malformed transactions jump the queue.
+            MempoolOrdering::PriorityFee => fee_data
+                .max_priority_fee_per_gas
+                .min(fee_data.max_fee_per_gas),
+        };
+        Self {
+            account: transaction.initiator_account(),
+            received_at_ms: transaction.received_timestamp_ms,
+            priority_fee,
+            fee_data: fee_data.clone(),
+        }
+    }
+
     /// Checks whether transaction matches requirements provided by state keeper.
     pub fn matches_filter(&self, filter: &L2TxFilter) -> bool {
         self.fee_data.max_fee_per_gas >= U256::from(filter.fee_per_gas)
@@ -146,6 +168,10 @@ impl MempoolScore {
 
 impl Ord for MempoolScore {
     fn cmp(&self, other: &MempoolScore) -> Ordering {
+        match self.priority_fee.cmp(&other.priority_fee) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
         match self.received_at_ms.cmp(&other.received_at_ms).reverse() {
             Ordering::Equal => {}
             ordering => return ordering,
@@ -218,6 +244,7 @@ mod tests {
         let score = MempoolScore {
             account: Address::random(),
             received_at_ms: Default::default(), // Not important
+            priority_fee: U256::zero(),         // Not important
             fee_data: Fee {
                 gas_limit: Default::default(), // Not important
                 max_fee_per_gas: U256::from(MAX_FEE_PER_GAS),
@@ -278,7 +305,7 @@ mod tests {
 
     #[test]
     fn advance_removes_old_transactions_and_returns_metadata() {
-        let mut account = AccountTransactions::new(Nonce(0));
+        let mut account = AccountTransactions::new(Nonce(0), MempoolOrdering::Fifo);
 
         // Insert txs with nonces 0, 1, 2
         for i in 0..3 {
@@ -309,4 +336,26 @@ mod tests {
         assert_eq!(meta3.new_score, None);
         assert_eq!(meta3.previous_score.as_ref().unwrap().received_at_ms, 1002);
     }
+
+    #[test]
+    fn score_ordering() {
+        let mut early_cheap_tx = l2_tx(0, 1_000);
+        early_cheap_tx.common_data.fee.max_priority_fee_per_gas = 1_u32.into();
+        let mut late_expensive_tx = l2_tx(0, 2_000);
+        late_expensive_tx.common_data.fee.max_priority_fee_per_gas = 5_u32.into();
+        let mut late_overpriced_tx = l2_tx(0, 2_000);
+        // Priority fee exceeds max fee, so it should be capped
+        late_overpriced_tx.common_data.fee.max_priority_fee_per_gas = 100_u32.into();
+
+        let early_cheap = MempoolScore::new(&early_cheap_tx, MempoolOrdering::Fifo);
+        let late_expensive = MempoolScore::new(&late_expensive_tx, MempoolOrdering::Fifo);
+        assert!(early_cheap > late_expensive);
+
+        let early_cheap = MempoolScore::new(&early_cheap_tx, MempoolOrdering::PriorityFee);
+        let late_expensive = MempoolScore::new(&late_expensive_tx, MempoolOrdering::PriorityFee);
+        assert!(early_cheap < late_expensive);
+        let late_overpriced = MempoolScore::new(&late_overpriced_tx, MempoolOrdering::PriorityFee);
+        assert_eq!(late_overpriced.priority_fee, 10_u32.into());
+        assert!(late_overpriced > late_expensive);
+    }
 }
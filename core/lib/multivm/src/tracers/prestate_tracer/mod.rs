@@ -1,168 +1,134 @@
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    sync::Arc,
+};
 
 use once_cell::sync::OnceCell;
 use zksync_types::{
-    address_to_h256, get_code_key, get_nonce_key, h256_to_u256, web3::keccak256, AccountTreeId,
-    Address, StorageKey, StorageValue, H160, H256, L2_BASE_TOKEN_ADDRESS, U256,
+    get_code_key, get_nonce_key, h256_to_u256, u256_to_h256, utils::storage_key_for_eth_balance,
+    AccountTreeId, Address, StorageKey, StorageValue, U256,
 };
 
-use crate::interface::storage::{StoragePtr, WriteStorage};
+use crate::{
+    glue::tracers::IntoOldVmTracer,
+    interface::{
+        storage::{StoragePtr, WriteStorage},
+        PrestateAccount, PrestateState,
+    },
+};
 
 pub mod vm_1_4_1;
 pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
 pub mod vm_latest;
 pub mod vm_refunds_enhancement;
 pub mod vm_virtual_blocks;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Account {
-    pub balance: Option<U256>,
-    pub code: Option<U256>,
-    pub nonce: Option<U256>,
-    pub storage: Option<HashMap<H256, H256>>,
-}
-
-impl fmt::Display for Account {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "{{")?;
-        if let Some(balance) = self.balance {
-            writeln!(f, "  balance: \"0x{:x}\",", balance)?;
-        }
-        if let Some(code) = &self.code {
-            writeln!(f, "  code: \"{}\",", code)?;
-        }
-        if let Some(nonce) = self.nonce {
-            writeln!(f, "  nonce: {},", nonce)?;
-        }
-        if let Some(storage) = &self.storage {
-            writeln!(f, "  storage: {{")?;
-            for (key, value) in storage.iter() {
-                writeln!(f, "    {}: \"{}\",", key, value)?;
-            }
-            writeln!(f, "  }}")?;
-        }
-        writeln!(f, "}}")
-    }
-}
-
-type State = HashMap<Address, Account>;
+type State = PrestateState;
 
+/// Tracer recording the state of accounts touched by the execution. Values of storage slots before the execution
+/// are captured while tracing: on the first read of a slot, or before the first write to it.
 #[derive(Debug, Clone)]
 pub struct PrestateTracer {
-    pub pre: State,
-    pub post: State,
     pub config: PrestateTracerConfig,
+    /// Values of storage slots accessed during execution, as they were before the first access.
+    initial_values: HashMap<StorageKey, StorageValue>,
+    /// Accounts which code was executed (including delegate calls and system contracts).
+    touched_accounts: HashSet<Address>,
+    last_touched_account: Option<Address>,
     pub result: Arc<OnceCell<(State, State)>>,
 }
 
 impl PrestateTracer {
     pub fn new(diff_mode: bool, result: Arc<OnceCell<(State, State)>>) -> Self {
         Self {
-            pre: Default::default(),
-            post: Default::default(),
             config: PrestateTracerConfig { diff_mode },
+            initial_values: HashMap::new(),
+            touched_accounts: HashSet::new(),
+            last_touched_account: None,
             result,
         }
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct PrestateTracerConfig {
-    diff_mode: bool,
-}
-
-pub fn process_modified_storage_keys<S>(
-    prestate: State,
-    storage: &StoragePtr<S>,
-) -> HashMap<H160, Account>
-where
-    S: WriteStorage,
-{
-    let cloned_storage = &storage.clone();
-    let mut initial_storage_ref = cloned_storage.as_ref().borrow_mut();
+    /// Records the account executing the current opcode and, if the opcode accesses `storage_slot`,
+    /// the value of the slot before the opcode is executed.
+    fn record_access<S: WriteStorage>(
+        &mut self,
+        this_address: Address,
+        storage_slot: Option<U256>,
+        storage: &StoragePtr<S>,
+    ) {
+        if self.last_touched_account != Some(this_address) {
+            self.touched_accounts.insert(this_address);
+            self.last_touched_account = Some(this_address);
+        }
 
-    initial_storage_ref
-        .modified_storage_keys()
-        .clone()
-        .iter()
-        .filter(|k| !prestate.contains_key(k.0.account().address()))
-        .map(|k| {
-            (
-                *(k.0.account().address()),
-                Account {
-                    balance: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_balance_key(k.0.account())),
-                    )),
-                    code: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_code_key(k.0.account().address())),
-                    )),
-                    nonce: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_nonce_key(k.0.account().address())),
-                    )),
-                    storage: Some(get_storage_if_present(
-                        k.0.account(),
-                        initial_storage_ref.modified_storage_keys(),
-                    )),
-                },
-            )
-        })
-        .collect::<State>()
-}
+        if let Some(slot) = storage_slot {
+            let key = StorageKey::new(AccountTreeId::new(this_address), u256_to_h256(slot));
+            if let Entry::Vacant(entry) = self.initial_values.entry(key) {
+                entry.insert(storage.borrow_mut().read_value(&key));
+            }
+        }
+    }
 
-fn get_balance_key(account: &AccountTreeId) -> StorageKey {
-    let address_h256 = address_to_h256(account.address());
-    let bytes = [address_h256.as_bytes(), &[0; 32]].concat();
-    let balance_key: H256 = keccak256(&bytes).into();
-    StorageKey::new(AccountTreeId::new(L2_BASE_TOKEN_ADDRESS), balance_key)
-}
+    /// Builds the pre- and post-state from values captured during execution and the current `storage` state.
+    fn finish<S: WriteStorage>(&self, storage: &StoragePtr<S>) {
+        let mut accessed_slots = HashMap::<Address, Vec<StorageKey>>::new();
+        for key in self.initial_values.keys() {
+            accessed_slots.entry(*key.address()).or_default().push(*key);
+        }
 
-fn get_storage_if_present(
-    account: &AccountTreeId,
-    modified_storage_keys: &HashMap<StorageKey, StorageValue>,
-) -> HashMap<H256, H256> {
-    //check if there is a Storage Key struct with an account field that matches the account and return the key as the key and the Storage Value as the value
-    modified_storage_keys
-        .iter()
-        .filter(|(k, _)| k.account() == account)
-        .map(|(k, v)| (*k.key(), *v))
-        .collect()
-}
+        let mut storage = storage.borrow_mut();
+        let mut pre = State::new();
+        let mut post = State::new();
+        for &address in &self.touched_accounts {
+            let slots = accessed_slots.get(&address).map_or(&[][..], Vec::as_slice);
+            // Slots not accessed during execution (e.g., the balance of an account that wasn't read) are unchanged,
+            // so their current values are equal to the initial ones.
+            let pre_account = account_state(address, slots, |key| {
+                self.initial_values
+                    .get(key)
+                    .copied()
+                    .unwrap_or_else(|| storage.read_value(key))
+            });
+            if !self.config.diff_mode {
+                pre.insert(address, pre_account);
+                continue;
+            }
 
-fn process_result(result: &Arc<OnceCell<(State, State)>>, mut pre: State, post: State) {
-    pre.retain(|k, v| {
-        if let Some(post_v) = post.get(k) {
-            if v != post_v {
-                return true;
+            let post_account = account_state(address, slots, |key| storage.read_value(key));
+            if pre_account != post_account {
+                pre.insert(address, pre_account);
+                post.insert(address, post_account);
             }
         }
-        false
-    });
-    result.set((pre, post)).unwrap();
+        self.result.set((pre, post)).unwrap();
+    }
 }
 
-fn get_account_data<T: StorageAccess>(
-    account_key: &StorageKey,
-    state: &T,
-    storage: &HashMap<StorageKey, StorageValue>,
-) -> (Address, Account) {
-    let address = *(account_key.account().address());
-    let balance = state.read_from_storage(&get_balance_key(account_key.account()));
-    let code = state.read_from_storage(&get_code_key(account_key.account().address()));
-    let nonce = state.read_from_storage(&get_nonce_key(account_key.account().address()));
-    let storage = get_storage_if_present(account_key.account(), storage);
-
-    (
-        address,
-        Account {
-            balance: Some(balance),
-            code: Some(code),
-            nonce: Some(nonce),
-            storage: Some(storage),
-        },
-    )
+#[derive(Debug, Clone)]
+pub struct PrestateTracerConfig {
+    diff_mode: bool,
 }
 
-// Define a trait that abstracts storage access
-trait StorageAccess {
-    fn read_from_storage(&self, key: &StorageKey) -> U256;
+impl IntoOldVmTracer for PrestateTracer {}
+
+fn account_state(
+    address: Address,
+    slots: &[StorageKey],
+    mut read_value: impl FnMut(&StorageKey) -> StorageValue,
+) -> PrestateAccount {
+    PrestateAccount {
+        balance: Some(h256_to_u256(read_value(&storage_key_for_eth_balance(
+            &address,
+        )))),
+        code: Some(h256_to_u256(read_value(&get_code_key(&address)))),
+        nonce: Some(h256_to_u256(read_value(&get_nonce_key(&address)))),
+        storage: Some(
+            slots
+                .iter()
+                .map(|key| (*key.key(), read_value(key)))
+                .collect(),
+        ),
+    }
 }
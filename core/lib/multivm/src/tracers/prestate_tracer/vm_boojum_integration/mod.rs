@@ -0,0 +1,41 @@
+use zk_evm_1_4_0::{
+    tracing::{BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{LogOpcode, Opcode},
+};
+
+use super::PrestateTracer;
+use crate::{
+    interface::storage::{StoragePtr, WriteStorage},
+    tracers::dynamic::vm_1_4_0::DynTracer,
+    vm_boojum_integration::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for PrestateTracer {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        storage: StoragePtr<S>,
+    ) {
+        let storage_slot = match data.opcode.variant.opcode {
+            Opcode::Log(LogOpcode::StorageRead | LogOpcode::StorageWrite) => {
+                Some(data.src0_value.value)
+            }
+            _ => None,
+        };
+        let this_address = state.vm_local_state.callstack.current.this_address;
+        self.record_access(this_address, storage_slot, &storage);
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for PrestateTracer {
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: crate::interface::tracer::VmExecutionStopReason,
+    ) {
+        self.finish(&state.storage.storage.get_ptr());
+    }
+}
@@ -3,8 +3,8 @@ pub use zksync_vm2::interface;
 pub(crate) use self::version::FastVmVersion;
 pub use self::{
     tracers::{
-        CallTracer, FastValidationTracer, FullValidationTracer, StorageInvocationsTracer,
-        ValidationTracer,
+        CallTracer, FastValidationTracer, FullValidationTracer, ProfileTracer,
+        StorageInvocationsTracer, StructLogTracer, ValidationTracer,
     },
     vm::Vm,
 };
@@ -41,12 +41,14 @@ mod l2_blocks;
 mod mock_evm;
 mod nonce_holder;
 mod precompiles;
+mod profile;
 mod refunds;
 mod require_eip712;
 mod rollbacks;
 mod secp256r1;
 mod simple_execution;
 mod storage;
+mod struct_log;
 mod tracing_execution_error;
 mod transfer;
 mod upgrade;
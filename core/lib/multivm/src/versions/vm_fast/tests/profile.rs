@@ -0,0 +1,67 @@
+use zksync_test_contracts::TestContract;
+use zksync_types::{u256_to_h256, Address, Execute, U256};
+
+use crate::{
+    interface::{InspectExecutionMode, TxExecutionMode, VmInterface},
+    versions::testonly::{ContractToDeploy, VmTesterBuilder},
+    vm_fast::{FastValidationTracer, ProfileTracer, Vm},
+    vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+};
+
+#[test]
+fn profiling_transaction() {
+    let bytecode = TestContract::counter().bytecode.to_vec();
+    let address = Address::repeat_byte(0xA5);
+    let mut vm = VmTesterBuilder::new()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![ContractToDeploy::new(bytecode, address)])
+        .build::<Vm<_, ProfileTracer, FastValidationTracer>>();
+
+    let calldata = "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+    let tx = vm.rich_accounts[0].get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(address),
+            calldata: hex::decode(calldata).unwrap(),
+            value: 0.into(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+    vm.vm.push_transaction(tx);
+
+    let mut tracer = (ProfileTracer::new(), FastValidationTracer::default());
+    let result = vm.vm.inspect(&mut tracer, InspectExecutionMode::OneTx);
+    assert!(!result.result.is_failed(), "{:#?}", result.result);
+    let profile = tracer.0.into_result().unwrap();
+
+    for (i, frame) in profile.frames.iter().enumerate() {
+        if let Some(parent) = frame.parent {
+            assert!(parent < i, "{frame:?}");
+        }
+        assert!(frame.own.gas <= frame.total.gas, "{frame:?}");
+        assert!(frame.own.cycles <= frame.total.cycles, "{frame:?}");
+    }
+    let (counter_idx, counter_frame) = profile
+        .frames
+        .iter()
+        .enumerate()
+        .find(|(_, frame)| frame.address == address)
+        .expect("no counter call");
+    assert_eq!(counter_frame.selector, Some([0x7c, 0xf5, 0xda, 0xb0]));
+    assert!(counter_frame.total.gas > 0);
+    assert!(counter_frame.total.cycles > 0);
+
+    let counter_write = profile
+        .storage_writes
+        .iter()
+        .find(|write| write.frame == counter_idx)
+        .expect("no counter storage write");
+    assert_eq!(counter_write.address, address);
+    assert_eq!(counter_write.key, u256_to_h256(U256::zero()));
+    assert!(counter_write.resources.gas > 0);
+    assert!(counter_write.resources.gas <= counter_frame.own.gas);
+
+    assert_eq!(ProfileTracer::default().into_result(), None);
+}
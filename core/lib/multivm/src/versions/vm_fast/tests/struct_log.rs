@@ -0,0 +1,234 @@
+use assert_matches::assert_matches;
+use ethabi::Token;
+use zksync_test_contracts::{TestContract, TestEvmContract};
+use zksync_types::{u256_to_h256, Address, Execute, H256, U256};
+
+use crate::{
+    interface::{
+        ExecutionResult, InspectExecutionMode, StructLog, StructLogTracingParams, StructLogs,
+        TxExecutionMode, VmExecutionResultAndLogs, VmInterface,
+    },
+    versions::testonly::{ContractToDeploy, VmTesterBuilder},
+    vm_fast::{FastValidationTracer, StructLogTracer, Vm},
+    vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+};
+
+const DEFAULT_PARAMS: StructLogTracingParams = StructLogTracingParams {
+    enable_memory: false,
+    disable_stack: false,
+    disable_storage: false,
+    limit: None,
+    max_size: usize::MAX,
+};
+
+/// Opcode names recorded for EVM contracts.
+const EVM_OPCODES: &[&str] = &[
+    "SLOAD",
+    "SSTORE",
+    "TLOAD",
+    "TSTORE",
+    "LOG",
+    "CALL",
+    "DELEGATECALL",
+    "RETURN",
+    "REVERT",
+    "INVALID",
+];
+
+fn execute_counter_increment(
+    params: StructLogTracingParams,
+) -> (VmExecutionResultAndLogs, StructLogs) {
+    let bytecode = TestContract::counter().bytecode.to_vec();
+    let address = Address::repeat_byte(0xA5);
+    let mut vm = VmTesterBuilder::new()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![ContractToDeploy::new(bytecode, address)])
+        .build::<Vm<_, StructLogTracer, FastValidationTracer>>();
+
+    let calldata = "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+    let tx = vm.rich_accounts[0].get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(address),
+            calldata: hex::decode(calldata).unwrap(),
+            value: 0.into(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+    vm.vm.push_transaction(tx);
+
+    let mut tracer = (
+        StructLogTracer::new(params),
+        FastValidationTracer::default(),
+    );
+    let result = vm.vm.inspect(&mut tracer, InspectExecutionMode::OneTx);
+    (result, tracer.0.into_result().unwrap())
+}
+
+#[test]
+fn recording_struct_logs() {
+    let (result, logs) = execute_counter_increment(DEFAULT_PARAMS);
+    assert!(!result.result.is_failed(), "{:#?}", result.result);
+    assert!(!logs.size_limit_exceeded);
+    assert!(!logs.logs.is_empty());
+
+    for log in &logs.logs {
+        assert!(log.depth >= 1, "{log:?}");
+        assert_eq!(log.stack.as_ref().unwrap().len(), 15, "{log:?}");
+        assert_eq!(log.memory, None);
+        assert!(log.gas_cost <= log.gas, "{log:?}");
+    }
+
+    let storage_write = logs
+        .logs
+        .iter()
+        .find(|log| log.op == "StorageWrite")
+        .expect("no storage writes");
+    let storage = storage_write.storage.as_ref().unwrap();
+    let expected_value = u256_to_h256(U256::from(6));
+    assert!(
+        storage.values().any(|value| *value == expected_value),
+        "{storage:?}"
+    );
+}
+
+#[test]
+fn struct_log_options() {
+    let params = StructLogTracingParams {
+        enable_memory: true,
+        disable_stack: true,
+        disable_storage: true,
+        limit: Some(10),
+        ..DEFAULT_PARAMS
+    };
+    let (result, logs) = execute_counter_increment(params);
+    assert!(!result.result.is_failed(), "{:#?}", result.result);
+    assert_eq!(logs.logs.len(), 10);
+    for log in &logs.logs {
+        assert_eq!(log.stack, None);
+        assert_eq!(log.storage, None);
+        assert!(log.memory.is_some());
+    }
+}
+
+#[test]
+fn exceeding_struct_logs_size_limit() {
+    let params = StructLogTracingParams {
+        max_size: 10_000,
+        ..DEFAULT_PARAMS
+    };
+    let (result, logs) = execute_counter_increment(params);
+    assert!(logs.size_limit_exceeded);
+    assert!(!logs.logs.is_empty());
+    assert_matches!(result.result, ExecutionResult::Halt { .. });
+}
+
+fn execute_evm_contract(
+    contract: &TestEvmContract,
+    calldata: Vec<u8>,
+) -> (VmExecutionResultAndLogs, StructLogs) {
+    let address = Address::repeat_byte(0xE5);
+    let mut vm = VmTesterBuilder::new()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_evm_contracts(vec![ContractToDeploy::new(
+            contract.deployed_bytecode.to_vec(),
+            address,
+        )])
+        .build::<Vm<_, StructLogTracer, FastValidationTracer>>();
+
+    let tx = vm.rich_accounts[0].get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(address),
+            calldata,
+            value: 0.into(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+    vm.vm.push_transaction(tx);
+
+    let mut tracer = (
+        StructLogTracer::new(DEFAULT_PARAMS),
+        FastValidationTracer::default(),
+    );
+    let result = vm.vm.inspect(&mut tracer, InspectExecutionMode::OneTx);
+    (result, tracer.0.into_result().unwrap())
+}
+
+/// Returns logs recorded in the far call frame that contains the log with the specified index, excluding subcalls.
+fn frame_logs(logs: &[StructLog], idx: usize) -> Vec<&StructLog> {
+    let depth = logs[idx].depth;
+    let start = logs[..idx]
+        .iter()
+        .rposition(|log| log.depth < depth)
+        .map_or(0, |pos| pos + 1);
+    let end = logs[idx..]
+        .iter()
+        .position(|log| log.depth < depth)
+        .map_or(logs.len(), |pos| idx + pos);
+    logs[start..end]
+        .iter()
+        .filter(|log| log.depth == depth)
+        .collect()
+}
+
+fn assert_evm_frame(frame_logs: &[&StructLog], expected_ret: &str) {
+    for log in frame_logs {
+        assert!(EVM_OPCODES.contains(&log.op.as_str()), "{log:?}");
+    }
+    assert_eq!(frame_logs.last().unwrap().op, expected_ret);
+}
+
+#[test]
+fn recording_struct_logs_for_evm_contract() {
+    let counter = TestEvmContract::counter();
+    for should_revert in [false, true] {
+        let calldata = counter
+            .function("incrementWithRevert")
+            .encode_input(&[Token::Uint(6.into()), Token::Bool(should_revert)])
+            .unwrap();
+        let (result, logs) = execute_evm_contract(counter, calldata);
+        assert_eq!(
+            result.result.is_failed(),
+            should_revert,
+            "{:#?}",
+            result.result
+        );
+        let logs = logs.logs;
+
+        let storage_write_idx = logs
+            .iter()
+            .position(|log| log.op == "SSTORE")
+            .expect("no storage writes");
+        let storage = logs[storage_write_idx].storage.as_ref().unwrap();
+        assert_eq!(storage[&H256::zero()], u256_to_h256(6.into()));
+
+        let frame_logs = frame_logs(&logs, storage_write_idx);
+        assert!(
+            frame_logs.iter().any(|log| log.op == "SLOAD"),
+            "{frame_logs:#?}"
+        );
+        let expected_ret = if should_revert { "REVERT" } else { "RETURN" };
+        assert_evm_frame(&frame_logs, expected_ret);
+    }
+}
+
+#[test]
+fn recording_evm_events() {
+    let tester = TestEvmContract::evm_tester();
+    let calldata = tester.function("testEvents").encode_input(&[]).unwrap();
+    let (result, logs) = execute_evm_contract(tester, calldata);
+    assert!(!result.result.is_failed(), "{:#?}", result.result);
+    let logs = logs.logs;
+
+    let event_idx = logs
+        .iter()
+        .position(|log| log.op == "LOG")
+        .expect("no events");
+    let frame_logs = frame_logs(&logs, event_idx);
+    assert_evm_frame(&frame_logs, "RETURN");
+}
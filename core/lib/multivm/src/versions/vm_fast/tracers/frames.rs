@@ -0,0 +1,77 @@
+//! Far call frame bookkeeping shared by tracers.
+
+use zksync_vm2::interface::Opcode;
+
+#[derive(Debug)]
+struct StackEntry<F> {
+    frame: F,
+    near_calls: usize,
+}
+
+/// Stack of far call frames tracked by a tracer. Accounts for near calls within frames, so that a return
+/// from a near call is not mistaken for a return from the far call frame.
+///
+/// Frames are not pushed automatically since their contents are tracer-specific; the tracer should call
+/// [`Self::push()`] after a far call.
+#[derive(Debug)]
+pub(super) struct FarCallFrames<F> {
+    entries: Vec<StackEntry<F>>,
+}
+
+impl<F> Default for FarCallFrames<F> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<F> FarCallFrames<F> {
+    pub fn push(&mut self, frame: F) {
+        self.entries.push(StackEntry {
+            frame,
+            near_calls: 0,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn last(&self) -> Option<&F> {
+        self.entries.last().map(|entry| &entry.frame)
+    }
+
+    pub fn last_mut(&mut self) -> Option<&mut F> {
+        self.entries.last_mut().map(|entry| &mut entry.frame)
+    }
+
+    /// Checks whether the topmost frame is executing a near call.
+    pub fn in_near_call(&self) -> bool {
+        self.entries
+            .last()
+            .is_some_and(|entry| entry.near_calls > 0)
+    }
+
+    /// Updates the stack after an instruction is executed. Returns the popped frame if the instruction
+    /// is a return from the topmost far call frame.
+    pub fn after_instruction(&mut self, opcode: Opcode) -> Option<F> {
+        let entry = self.entries.last_mut()?;
+        match opcode {
+            Opcode::NearCall => {
+                entry.near_calls += 1;
+                None
+            }
+            Opcode::Ret(_) if entry.near_calls > 0 => {
+                entry.near_calls -= 1;
+                None
+            }
+            Opcode::Ret(_) => self.entries.pop().map(|entry| entry.frame),
+            _ => None,
+        }
+    }
+}
@@ -5,7 +5,9 @@ use zksync_vm2::interface::{CycleStats, GlobalStateInterface, OpcodeType, Should
 pub(super) use self::evm_deploy::DynamicBytecodes;
 pub use self::{
     calls::CallTracer,
+    profile::ProfileTracer,
     storage::StorageInvocationsTracer,
+    struct_log::StructLogTracer,
     validation::{FastValidationTracer, FullValidationTracer, ValidationTracer},
 };
 use self::{circuits::CircuitsTracer, evm_deploy::EvmDeployTracer};
@@ -14,7 +16,10 @@ use crate::interface::CircuitStatistic;
 mod calls;
 mod circuits;
 mod evm_deploy;
+mod frames;
+mod profile;
 mod storage;
+mod struct_log;
 mod validation;
 
 #[derive(Debug)]
@@ -0,0 +1,182 @@
+//! Tracer breaking down resources spent by a transaction per far call frame and storage write.
+
+use zksync_types::{u256_to_h256, H160, H256};
+use zksync_vm2::{
+    interface::{
+        CallframeInterface, CycleStats, GlobalStateInterface, Opcode, OpcodeType, ShouldStop,
+        Tracer,
+    },
+    FatPointer,
+};
+
+use super::{circuits::CircuitsTracer, frames::FarCallFrames};
+use crate::{
+    interface::{ProfiledFrame, ProfiledResources, ProfiledStorageWrite, TransactionProfile},
+    vm_fast::utils::{read_fat_pointer, read_storage_access_operands},
+};
+
+#[derive(Debug)]
+struct ActiveFrame {
+    /// Index of the frame in the output.
+    index: usize,
+    /// Gas available to the caller before the call, used to compute gas spent by the frame similarly to `CallTracer`.
+    parent_gas: u64,
+    /// Resources (except for gas) spent by the entire execution at the start of the frame.
+    start: ProfiledResources,
+    /// Total resources spent by subcalls.
+    children: ProfiledResources,
+}
+
+#[derive(Debug)]
+struct PendingStorageWrite {
+    address: H160,
+    key: H256,
+    gas: u64,
+    start: ProfiledResources,
+}
+
+/// Profiler for the fast VM. Records gas, pubdata and circuits spent by each far call made by the bootloader
+/// (including subcalls), and by each storage write within these calls.
+///
+/// The default instance is disabled and doesn't record anything.
+#[derive(Debug, Default)]
+pub struct ProfileTracer {
+    enabled: bool,
+    circuits: CircuitsTracer,
+    cycles: u64,
+    frames: FarCallFrames<ActiveFrame>,
+    pending_write: Option<PendingStorageWrite>,
+    output: TransactionProfile,
+}
+
+impl ProfileTracer {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            ..Self::default()
+        }
+    }
+
+    /// Converts this tracer into the recorded profile. Returns `None` if the tracer is disabled.
+    pub fn into_result(self) -> Option<TransactionProfile> {
+        self.enabled.then_some(self.output)
+    }
+
+    /// Returns resources spent by the entire execution so far. Gas is not filled since it's tracked per frame.
+    fn snapshot<S: GlobalStateInterface>(&self, state: &S) -> ProfiledResources {
+        ProfiledResources {
+            gas: 0,
+            pubdata: state.pubdata().into(),
+            cycles: self.cycles,
+            circuits: self.circuits.circuit_statistic().total_f32(),
+        }
+    }
+
+    fn read_selector<S: GlobalStateInterface>(state: &S) -> Option<[u8; 4]> {
+        if state.current_frame().gas() == 0 {
+            // Calldata pointer may be invalid if the call has failed immediately.
+            return None;
+        }
+        let mut pointer = FatPointer::from(state.read_register(1).0);
+        if pointer.length.saturating_sub(pointer.offset) < 4 {
+            return None;
+        }
+        pointer.length = pointer.offset + 4;
+        read_fat_pointer(state, pointer).try_into().ok()
+    }
+
+    fn start_frame<S: GlobalStateInterface>(&mut self, state: &mut S) {
+        let gas = u64::from(state.current_frame().gas());
+        // The previous frame always exists directly after a far call
+        let parent_gas = gas + u64::from(state.callframe(1).gas());
+        let index = self.output.frames.len();
+        self.output.frames.push(ProfiledFrame {
+            parent: self.frames.last().map(|frame| frame.index),
+            address: state.current_frame().address(),
+            selector: Self::read_selector(state),
+            total: ProfiledResources::default(),
+            own: ProfiledResources::default(),
+        });
+        self.frames.push(ActiveFrame {
+            index,
+            parent_gas,
+            start: self.snapshot(state),
+            children: ProfiledResources::default(),
+        });
+    }
+
+    fn finish_frame<S: GlobalStateInterface>(&mut self, state: &mut S, frame: ActiveFrame) {
+        let mut total = self.snapshot(state) - frame.start;
+        // Might overflow due to stipend
+        total.gas = frame
+            .parent_gas
+            .saturating_sub(u64::from(state.current_frame().gas()));
+
+        let output = &mut self.output.frames[frame.index];
+        output.total = total;
+        output.own = total - frame.children;
+        if let Some(parent) = self.frames.last_mut() {
+            parent.children += total;
+        }
+    }
+}
+
+impl Tracer for ProfileTracer {
+    fn before_instruction<OP: OpcodeType, S: GlobalStateInterface>(&mut self, state: &mut S) {
+        if !self.enabled || !matches!(OP::VALUE, Opcode::StorageWrite) {
+            return;
+        }
+        if self.frames.is_empty() {
+            return; // The bootloader is not profiled
+        }
+
+        let Some((key, _)) = read_storage_access_operands(state) else {
+            return;
+        };
+        self.pending_write = Some(PendingStorageWrite {
+            address: state.current_frame().address(),
+            key: u256_to_h256(key),
+            gas: state.current_frame().gas().into(),
+            start: self.snapshot(state),
+        });
+    }
+
+    fn after_instruction<OP: OpcodeType, S: GlobalStateInterface>(
+        &mut self,
+        state: &mut S,
+    ) -> ShouldStop {
+        if !self.enabled {
+            return ShouldStop::Continue;
+        }
+        self.circuits.after_instruction::<OP, _>(state);
+        self.cycles += 1;
+
+        if let Some(frame) = self.frames.after_instruction(OP::VALUE) {
+            self.finish_frame(state, frame);
+        }
+        match OP::VALUE {
+            Opcode::FarCall(_) => self.start_frame(state),
+            Opcode::StorageWrite => {
+                if let (Some(write), Some(frame)) = (self.pending_write.take(), self.frames.last())
+                {
+                    let mut resources = self.snapshot(state) - write.start;
+                    resources.gas = write.gas.saturating_sub(state.current_frame().gas().into());
+                    self.output.storage_writes.push(ProfiledStorageWrite {
+                        frame: frame.index,
+                        address: write.address,
+                        key: write.key,
+                        resources,
+                    });
+                }
+            }
+            _ => { /* do nothing */ }
+        }
+        ShouldStop::Continue
+    }
+
+    fn on_extra_prover_cycles(&mut self, stats: CycleStats) {
+        if self.enabled {
+            self.circuits.on_extra_prover_cycles(stats);
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Tracer recording a log entry for each executed VM instruction, similar to the default struct logger in Geth.
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+};
+
+use zksync_system_constants::ACCOUNT_CODE_STORAGE_ADDRESS;
+use zksync_types::{address_to_u256, bytecode::BytecodeMarker, u256_to_h256, H160, H256, U256};
+use zksync_vm2::interface::{
+    CallframeInterface, CallingMode, GlobalStateInterface, Opcode, OpcodeType, ReturnType,
+    ShouldStop, Tracer,
+};
+
+use super::frames::FarCallFrames;
+use crate::{
+    interface::{StructLog, StructLogTracingParams, StructLogs},
+    vm_fast::utils::read_storage_access_operands,
+};
+
+/// Estimated serialized size of a log entry without the stack, memory and storage.
+const BASE_LOG_SIZE: usize = 128;
+/// Estimated serialized size of a 32-byte word (a hex string with quotes and a delimiter).
+const WORD_SIZE: usize = 70;
+/// Number of general-purpose registers recorded as the stack (`r1..=r15`).
+const REGISTER_COUNT: u8 = 15;
+
+#[derive(Debug)]
+struct FarCallFrame {
+    address: H160,
+    /// Whether the frame executes an EVM bytecode using the EVM emulator.
+    is_evm: bool,
+}
+
+#[derive(Debug)]
+struct PendingLog {
+    log: StructLog,
+    callframe_count: usize,
+}
+
+/// Struct log tracer for the fast VM.
+///
+/// Logs are recorded only for far calls made by the bootloader and their subcalls; bootloader instructions are skipped.
+/// For EraVM contracts, a log is recorded for each executed instruction, and general-purpose registers are reported
+/// as the stack. For EVM contracts executed by the EVM emulator, recording each emulator instruction would be mostly noise;
+/// hence, only instructions observable on the EVM level (storage access, calls, returns and events) are recorded,
+/// with opcodes named after their EVM counterparts. In this case, the program counter refers to the emulator bytecode.
+/// EVM execution steps are not reconstructed: the EVM stack is not recorded, static calls and deployments are reported
+/// as `CALL`, events are reported as `LOG` regardless of the number of topics, and gas is measured in ergs.
+///
+/// The default instance is disabled and doesn't record anything.
+#[derive(Debug, Default)]
+pub struct StructLogTracer {
+    params: Option<StructLogTracingParams>,
+    frames: FarCallFrames<FarCallFrame>,
+    pending_log: Option<PendingLog>,
+    accessed_storage: HashMap<H160, BTreeMap<H256, H256>>,
+    output: StructLogs,
+    output_size: usize,
+}
+
+impl StructLogTracer {
+    pub fn new(params: StructLogTracingParams) -> Self {
+        Self {
+            params: Some(params),
+            ..Self::default()
+        }
+    }
+
+    /// Converts this tracer into the recorded logs. Returns `None` if the tracer is disabled.
+    pub fn into_result(self) -> Option<StructLogs> {
+        self.params.is_some().then_some(self.output)
+    }
+
+    fn evm_opcode_name(opcode: Opcode, in_near_call: bool) -> Option<&'static str> {
+        Some(match opcode {
+            Opcode::StorageRead => "SLOAD",
+            Opcode::StorageWrite => "SSTORE",
+            Opcode::TransientStorageRead => "TLOAD",
+            Opcode::TransientStorageWrite => "TSTORE",
+            Opcode::Event => "LOG",
+            Opcode::FarCall(CallingMode::Delegate) => "DELEGATECALL",
+            Opcode::FarCall(_) => "CALL",
+            // Near calls are used by the emulator internally, so we only record returns from the frame itself.
+            Opcode::Ret(_) if in_near_call => return None,
+            Opcode::Ret(ReturnType::Normal) => "RETURN",
+            Opcode::Ret(ReturnType::Revert) => "REVERT",
+            Opcode::Ret(ReturnType::Panic) => "INVALID",
+            _ => return None,
+        })
+    }
+
+    /// Reads the storage slot and value for a storage access instruction. Must be called before the instruction is executed.
+    fn read_storage_access(
+        state: &mut impl GlobalStateInterface,
+        address: H160,
+        opcode: Opcode,
+    ) -> Option<(U256, U256)> {
+        let (slot, value) = read_storage_access_operands(state)?;
+        let value = match opcode {
+            Opcode::StorageWrite => value,
+            _ => state.get_storage(address, slot),
+        };
+        Some((slot, value))
+    }
+
+    fn is_evm_contract(state: &mut impl GlobalStateInterface, code_address: H160) -> bool {
+        let code_hash =
+            state.get_storage(ACCOUNT_CODE_STORAGE_ADDRESS, address_to_u256(&code_address));
+        BytecodeMarker::new(u256_to_h256(code_hash)) == Some(BytecodeMarker::Evm)
+    }
+
+    fn create_log<OP: OpcodeType, S: GlobalStateInterface>(
+        &mut self,
+        params: StructLogTracingParams,
+        state: &mut S,
+    ) -> Option<StructLog> {
+        let frame = self.frames.last()?;
+        if params
+            .limit
+            .is_some_and(|limit| self.output.logs.len() >= limit)
+        {
+            return None;
+        }
+        let op = if frame.is_evm {
+            Cow::Borrowed(Self::evm_opcode_name(
+                OP::VALUE,
+                self.frames.in_near_call(),
+            )?)
+        } else {
+            Cow::Owned(format!("{:?}", OP::VALUE))
+        };
+        let address = frame.address;
+        let depth = self.frames.len();
+
+        let (pc, gas, heap, heap_bound) = {
+            let frame = state.current_frame();
+            let pc = frame.program_counter().map_or(0, u64::from);
+            (pc, frame.gas().into(), frame.heap(), frame.heap_bound())
+        };
+        let stack = (!params.disable_stack).then(|| {
+            (1..=REGISTER_COUNT)
+                .map(|register| state.read_register(register).0)
+                .collect()
+        });
+        let memory = if params.enable_memory {
+            let word_count = heap_bound.div_ceil(32);
+            // Check the size in advance since the heap can be large.
+            if self.output_size + word_count as usize * WORD_SIZE > params.max_size {
+                self.output.size_limit_exceeded = true;
+                return None;
+            }
+            Some(
+                (0..word_count)
+                    .map(|i| state.read_heap_u256(heap, i * 32))
+                    .collect(),
+            )
+        } else {
+            None
+        };
+
+        let is_persistent_storage_access =
+            matches!(OP::VALUE, Opcode::StorageRead | Opcode::StorageWrite);
+        let storage = if is_persistent_storage_access && !params.disable_storage {
+            let accessed_storage = self.accessed_storage.entry(address).or_default();
+            if let Some((slot, value)) = Self::read_storage_access(state, address, OP::VALUE) {
+                accessed_storage.insert(u256_to_h256(slot), u256_to_h256(value));
+            }
+            Some(accessed_storage.clone())
+        } else {
+            None
+        };
+
+        Some(StructLog {
+            pc,
+            op: op.into_owned(),
+            gas,
+            gas_cost: 0,
+            depth,
+            stack,
+            memory,
+            storage,
+            error: matches!(OP::VALUE, Opcode::Ret(ReturnType::Panic)).then(|| "Panic".to_owned()),
+        })
+    }
+
+    fn estimate_size(log: &StructLog) -> usize {
+        let word_count = log.stack.as_ref().map_or(0, Vec::len)
+            + log.memory.as_ref().map_or(0, Vec::len)
+            + log.storage.as_ref().map_or(0, |storage| storage.len() * 2);
+        BASE_LOG_SIZE + log.op.len() + word_count * WORD_SIZE
+    }
+
+    fn update_frames<OP: OpcodeType, S: GlobalStateInterface>(&mut self, state: &mut S) {
+        self.frames.after_instruction(OP::VALUE);
+        if let Opcode::FarCall(_) = OP::VALUE {
+            let (address, code_address) = {
+                let frame = state.current_frame();
+                (frame.address(), frame.code_address())
+            };
+            self.frames.push(FarCallFrame {
+                address,
+                is_evm: Self::is_evm_contract(state, code_address),
+            });
+        }
+    }
+}
+
+impl Tracer for StructLogTracer {
+    fn before_instruction<OP: OpcodeType, S: GlobalStateInterface>(&mut self, state: &mut S) {
+        let Some(params) = self.params else {
+            return;
+        };
+        if self.output.size_limit_exceeded {
+            return;
+        }
+
+        self.pending_log = self
+            .create_log::<OP, _>(params, state)
+            .map(|log| PendingLog {
+                log,
+                callframe_count: state.number_of_callframes(),
+            });
+    }
+
+    fn after_instruction<OP: OpcodeType, S: GlobalStateInterface>(
+        &mut self,
+        state: &mut S,
+    ) -> ShouldStop {
+        let Some(params) = self.params else {
+            return ShouldStop::Continue;
+        };
+
+        if let Some(PendingLog {
+            mut log,
+            callframe_count,
+        }) = self.pending_log.take()
+        {
+            // The gas cost is only well-defined if the instruction doesn't switch the call frame.
+            if state.number_of_callframes() == callframe_count {
+                let gas = u64::from(state.current_frame().gas());
+                log.gas_cost = log.gas.saturating_sub(gas);
+            }
+            self.output_size += Self::estimate_size(&log);
+            self.output.logs.push(log);
+            if self.output_size > params.max_size {
+                self.output.size_limit_exceeded = true;
+            }
+        }
+        self.update_frames::<OP, _>(state);
+
+        if self.output.size_limit_exceeded {
+            ShouldStop::Stop
+        } else {
+            ShouldStop::Continue
+        }
+    }
+}
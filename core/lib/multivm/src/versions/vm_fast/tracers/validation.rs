@@ -19,7 +19,7 @@ use crate::{
         Halt,
     },
     tracers::TIMESTAMP_ASSERTER_FUNCTION_SELECTOR,
-    vm_fast::utils::read_raw_fat_pointer,
+    vm_fast::utils::{read_raw_fat_pointer, read_storage_access_operands},
 };
 
 /// [`Tracer`] used for account validation per [EIP-4337] and [EIP-7562].
@@ -157,12 +157,7 @@ impl Tracer for FullValidationTracer {
                 let caller = state.current_frame().caller();
 
                 // Can unwrap because the instruction pointer does not point to a panic instruction
-                let pc = state.current_frame().program_counter().unwrap();
-                let word = pc / 4;
-                let part = pc % 4;
-                let instruction =
-                    state.current_frame().read_contract_code(word).0[3 - part as usize];
-                let slot = state.read_register((instruction >> 16) as u8 & 0b1111).0;
+                let (slot, _) = read_storage_access_operands(state).unwrap();
 
                 if self
                     .storage_containing_trusted_addresses
@@ -1,5 +1,8 @@
 use zksync_types::U256;
-use zksync_vm2::{interface::StateInterface, FatPointer};
+use zksync_vm2::{
+    interface::{CallframeInterface, StateInterface},
+    FatPointer,
+};
 
 pub(super) fn read_raw_fat_pointer<S: StateInterface>(state: &S, raw: U256) -> Vec<u8> {
     read_fat_pointer(state, FatPointer::from(raw))
@@ -14,3 +17,18 @@ pub(super) fn read_fat_pointer<S: StateInterface>(state: &S, pointer: FatPointer
     }
     result
 }
+
+/// Reads the register operands of the storage access instruction (e.g., `StorageRead` or `StorageWrite`)
+/// which is about to be executed. Returns the slot and the value operand; the latter is only meaningful for writes.
+/// Returns `None` if the current frame doesn't have a valid program counter, e.g. if it's about to panic.
+pub(super) fn read_storage_access_operands<S: StateInterface>(
+    state: &mut S,
+) -> Option<(U256, U256)> {
+    let pc = state.current_frame().program_counter()?;
+    let word = pc / 4;
+    let part = pc % 4;
+    let instruction = state.current_frame().read_contract_code(word).0[3 - part as usize];
+    let slot = state.read_register((instruction >> 16) as u8 & 0b1111).0;
+    let value = state.read_register((instruction >> 20) as u8 & 0b1111).0;
+    Some((slot, value))
+}
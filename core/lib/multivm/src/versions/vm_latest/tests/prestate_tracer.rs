@@ -1,17 +1,103 @@
 use std::sync::Arc;
 
+use ethabi::Token;
 use once_cell::sync::OnceCell;
 use zksync_test_contracts::{TestContract, TxType};
-use zksync_types::{utils::deployed_address_create, Execute, U256};
+use zksync_types::{
+    utils::{deployed_address_create, storage_key_for_eth_balance},
+    AccountTreeId, Address, Execute, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
+};
 
 use super::TestedLatestVm;
 use crate::{
-    interface::{InspectExecutionMode, TxExecutionMode, VmInterface, VmInterfaceExt},
+    interface::{
+        InspectExecutionMode, PrestateState, TxExecutionMode, VmInterface, VmInterfaceExt,
+    },
     tracers::PrestateTracer,
-    versions::testonly::VmTesterBuilder,
+    versions::testonly::{ContractToDeploy, VmTester, VmTesterBuilder},
     vm_latest::{constants::BATCH_COMPUTATIONAL_GAS_LIMIT, ToTracerPointer},
 };
 
+const COUNTER_ADDRESS: Address = Address::repeat_byte(0x23);
+const TRANSFER_RECIPIENT: Address = Address::repeat_byte(0x42);
+const TRANSFERRED_VALUE: u64 = 1_000;
+
+fn inspect_with_prestate_tracer(
+    vm: &mut VmTester<TestedLatestVm>,
+    diff_mode: bool,
+) -> (PrestateState, PrestateState) {
+    let prestate_tracer_result = Arc::new(OnceCell::default());
+    let prestate_tracer = PrestateTracer::new(diff_mode, prestate_tracer_result.clone());
+    let tracer_ptr = prestate_tracer.into_tracer_pointer();
+    let exec_result = vm
+        .vm
+        .inspect(&mut tracer_ptr.into(), InspectExecutionMode::OneTx);
+    assert!(!exec_result.result.is_failed(), "{exec_result:#?}");
+
+    Arc::try_unwrap(prestate_tracer_result)
+        .unwrap()
+        .take()
+        .unwrap()
+}
+
+/// Creates a VM with a pending base token transfer. Returns the VM and the transfer sender.
+fn prepare_transfer() -> (VmTester<TestedLatestVm>, Address) {
+    let mut vm = VmTesterBuilder::new()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .build::<TestedLatestVm>();
+    let account = &mut vm.rich_accounts[0];
+    let sender = account.address;
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(TRANSFER_RECIPIENT),
+            calldata: vec![],
+            value: TRANSFERRED_VALUE.into(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+    vm.vm.push_transaction(tx);
+    (vm, sender)
+}
+
+/// Creates a VM with the counter contract storing `initial_value`, and a pending transaction calling
+/// the specified counter function.
+fn prepare_counter_call(
+    initial_value: u64,
+    function: &str,
+    args: &[Token],
+) -> VmTester<TestedLatestVm> {
+    let value_key = StorageKey::new(AccountTreeId::new(COUNTER_ADDRESS), H256::zero());
+    let mut vm = VmTesterBuilder::new()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![ContractToDeploy::new(
+            TestContract::counter().bytecode.to_vec(),
+            COUNTER_ADDRESS,
+        )])
+        .with_storage_slots([(value_key, H256::from_low_u64_be(initial_value))])
+        .build::<TestedLatestVm>();
+    let calldata = TestContract::counter()
+        .function(function)
+        .encode_input(args)
+        .unwrap();
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(COUNTER_ADDRESS),
+            calldata,
+            value: 0.into(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+    vm.vm.push_transaction(tx);
+    vm
+}
+
 #[test]
 fn test_prestate_tracer() {
     let mut vm = VmTesterBuilder::new()
@@ -44,7 +130,9 @@ fn test_prestate_tracer() {
         .take()
         .unwrap_or_default();
 
-    assert!(prestate_result.1.contains_key(&contract_address));
+    // In the non-diff mode, the tracer only records the pre-state.
+    assert!(prestate_result.0.contains_key(&contract_address));
+    assert!(prestate_result.1.is_empty());
 }
 
 #[test]
@@ -127,3 +215,76 @@ fn test_prestate_tracer_diff_mode() {
         Some(U256::from(200000))
     );
 }
+
+#[test]
+fn prestate_tracer_diff_mode_for_transfer() {
+    let (mut vm, sender) = prepare_transfer();
+    let sender_balance = vm.get_eth_balance(sender);
+    let (pre, post) = inspect_with_prestate_tracer(&mut vm, true);
+
+    assert_eq!(pre[&TRANSFER_RECIPIENT].balance, Some(U256::zero()));
+    assert_eq!(
+        post[&TRANSFER_RECIPIENT].balance,
+        Some(TRANSFERRED_VALUE.into())
+    );
+
+    assert_eq!(pre[&sender].balance, Some(sender_balance));
+    let sender_post_balance = post[&sender].balance.unwrap();
+    // The sender pays for the transferred value and the fee.
+    assert!(sender_post_balance < sender_balance - U256::from(TRANSFERRED_VALUE));
+    assert_eq!(
+        post[&sender].nonce.unwrap(),
+        pre[&sender].nonce.unwrap() + 1
+    );
+
+    // Balances are stored in the base token contract storage, so its pre-state must contain original values as well.
+    let recipient_balance_key = storage_key_for_eth_balance(&TRANSFER_RECIPIENT);
+    let base_token_pre_storage = pre[&L2_BASE_TOKEN_ADDRESS].storage.as_ref().unwrap();
+    assert_eq!(
+        base_token_pre_storage[recipient_balance_key.key()],
+        H256::zero()
+    );
+    let base_token_post_storage = post[&L2_BASE_TOKEN_ADDRESS].storage.as_ref().unwrap();
+    assert_eq!(
+        base_token_post_storage[recipient_balance_key.key()],
+        H256::from_low_u64_be(TRANSFERRED_VALUE)
+    );
+}
+
+#[test]
+fn prestate_tracer_reports_original_values_for_transfer() {
+    let (mut vm, sender) = prepare_transfer();
+    let sender_balance = vm.get_eth_balance(sender);
+    let (pre, post) = inspect_with_prestate_tracer(&mut vm, false);
+
+    assert!(post.is_empty());
+    assert_eq!(pre[&TRANSFER_RECIPIENT].balance, Some(U256::zero()));
+    assert_eq!(pre[&sender].balance, Some(sender_balance));
+}
+
+#[test]
+fn prestate_tracer_diff_mode_for_storage_write() {
+    let mut vm = prepare_counter_call(5, "increment", &[Token::Uint(3.into())]);
+    let (pre, post) = inspect_with_prestate_tracer(&mut vm, true);
+
+    let pre_storage = pre[&COUNTER_ADDRESS].storage.as_ref().unwrap();
+    assert_eq!(pre_storage[&H256::zero()], H256::from_low_u64_be(5));
+    let post_storage = post[&COUNTER_ADDRESS].storage.as_ref().unwrap();
+    assert_eq!(post_storage[&H256::zero()], H256::from_low_u64_be(8));
+}
+
+#[test]
+fn prestate_tracer_reports_read_storage_slots() {
+    let mut vm = prepare_counter_call(5, "get", &[]);
+    let (pre, post) = inspect_with_prestate_tracer(&mut vm, false);
+
+    assert!(post.is_empty());
+    let pre_storage = pre[&COUNTER_ADDRESS].storage.as_ref().unwrap();
+    assert_eq!(pre_storage[&H256::zero()], H256::from_low_u64_be(5));
+
+    // The contract is not modified, so it must not be present in the diff.
+    let mut vm = prepare_counter_call(5, "get", &[]);
+    let (pre, post) = inspect_with_prestate_tracer(&mut vm, true);
+    assert!(!pre.contains_key(&COUNTER_ADDRESS));
+    assert!(!post.contains_key(&COUNTER_ADDRESS));
+}
@@ -0,0 +1,402 @@
+//! Azure Blob Storage-based [`ObjectStore`] implementation.
+
+use std::{fmt, path::Path};
+
+use async_trait::async_trait;
+use reqwest::{Client, Method, RequestBuilder, Url};
+
+use crate::{
+    http::{
+        check_response, http_client, parse_base_url, push_object_path, read_secret_file,
+        xml_element_texts,
+    },
+    raw::{Bucket, ObjectStore, ObjectStoreError},
+};
+
+/// Version of the Blob service REST API used by the store.
+const API_VERSION: &str = "2021-08-06";
+
+/// [`ObjectStore`] implementation based on Azure Blob Storage. Objects are stored as block blobs
+/// named `{bucket}/{key}` in a single container.
+pub struct AzureBlobStore {
+    container_url: Url,
+    sas_token: Option<String>,
+    client: Client,
+}
+
+impl fmt::Debug for AzureBlobStore {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("AzureBlobStore")
+            .field("container_url", &self.container_url.as_str())
+            // Skip `sas_token` as it's sensitive
+            .finish_non_exhaustive()
+    }
+}
+
+impl AzureBlobStore {
+    /// Creates a new Azure Blob store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the container URL is invalid, or the SAS token cannot be read.
+    pub async fn new(
+        container_url: &str,
+        sas_token_file_path: Option<&Path>,
+    ) -> Result<Self, ObjectStoreError> {
+        let container_url = parse_base_url(container_url)?;
+        let sas_token = if let Some(path) = sas_token_file_path {
+            let token = read_secret_file(path).await?;
+            Some(token.trim_start_matches('?').to_owned())
+        } else {
+            None
+        };
+        Ok(Self {
+            container_url,
+            sas_token,
+            client: http_client()?,
+        })
+    }
+
+    fn blob_name(bucket: Bucket, key: &str) -> String {
+        format!("{bucket}/{key}")
+    }
+
+    fn blob_url(&self, bucket: Bucket, key: &str) -> Url {
+        let mut url = self.container_url.clone();
+        push_object_path(&mut url, bucket, key);
+        url.set_query(self.sas_token.as_deref());
+        url
+    }
+
+    fn request(&self, method: Method, url: Url) -> RequestBuilder {
+        self.client
+            .request(method, url)
+            .header("x-ms-version", API_VERSION)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for AzureBlobStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        tracing::trace!(
+            "Fetching data from Azure for blob {} from container {}",
+            Self::blob_name(bucket, key),
+            self.container_url.path()
+        );
+
+        let url = self.blob_url(bucket, key);
+        let response = check_response(self.request(Method::GET, url).send().await?).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        tracing::trace!(
+            "Storing data to Azure for blob {} from container {}",
+            Self::blob_name(bucket, key),
+            self.container_url.path()
+        );
+
+        let url = self.blob_url(bucket, key);
+        let response = self
+            .request(Method::PUT, url)
+            .header("x-ms-blob-type", "BlockBlob")
+            .body(value)
+            .send()
+            .await?;
+        check_response(response).await?;
+        Ok(())
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        tracing::trace!(
+            "Removing data from Azure for blob {} from container {}",
+            Self::blob_name(bucket, key),
+            self.container_url.path()
+        );
+
+        let url = self.blob_url(bucket, key);
+        check_response(self.request(Method::DELETE, url).send().await?).await?;
+        Ok(())
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let full_prefix = Self::blob_name(bucket, prefix);
+        tracing::trace!(
+            "Listing blobs in Azure with prefix {full_prefix} from container {}",
+            self.container_url.path()
+        );
+
+        let bucket_prefix = Self::blob_name(bucket, "");
+        let mut keys = vec![];
+        let mut marker = None;
+        loop {
+            let mut url = self.container_url.clone();
+            url.set_query(self.sas_token.as_deref());
+            {
+                let mut query = url.query_pairs_mut();
+                query
+                    .append_pair("restype", "container")
+                    .append_pair("comp", "list")
+                    .append_pair("prefix", &full_prefix);
+                if let Some(marker) = &marker {
+                    query.append_pair("marker", marker);
+                }
+            }
+
+            let response = check_response(self.request(Method::GET, url).send().await?).await?;
+            let body = response.text().await?;
+            let names = xml_element_texts(&body, "Blob", "Name")?;
+            keys.extend(
+                names
+                    .iter()
+                    .filter_map(|name| name.strip_prefix(&bucket_prefix))
+                    .map(str::to_owned),
+            );
+
+            let next_marker = xml_element_texts(&body, "EnumerationResults", "NextMarker")?;
+            marker = next_marker.into_iter().find(|marker| !marker.is_empty());
+            if marker.is_none() {
+                break;
+            }
+        }
+        // Azure returns blobs in lexicographic order, but we sort keys just in case.
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        tracing::trace!(
+            "Checking existence of blob {} in Azure from container {}",
+            Self::blob_name(bucket, key),
+            self.container_url.path()
+        );
+
+        let url = self.blob_url(bucket, key);
+        match check_response(self.request(Method::HEAD, url).send().await?).await {
+            Ok(_) => Ok(true),
+            Err(ObjectStoreError::KeyNotFound(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        let mut url = self.container_url.clone();
+        push_object_path(&mut url, bucket, "");
+        url.as_str().trim_end_matches('/').to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        sync::{Arc, Mutex},
+    };
+
+    use assert_matches::assert_matches;
+    use axum::{
+        extract::{Query, State},
+        http::{HeaderMap, HeaderValue, StatusCode, Uri},
+        response::{IntoResponse, Response},
+        Router,
+    };
+    use tempfile::TempDir;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    const CONTAINER_PATH: &str = "/account/container";
+    const SAS_TOKEN: &str = "sv=2021-08-06&sr=c&sp=racwdl&sig=test";
+    /// Max number of blobs returned on a single page when listing blobs.
+    const PAGE_SIZE: usize = 2;
+
+    type Blobs = Arc<Mutex<BTreeMap<String, Vec<u8>>>>;
+
+    fn escape_xml(s: &str) -> String {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
+    fn list_blobs(blobs: &BTreeMap<String, Vec<u8>>, query: &HashMap<String, String>) -> Response {
+        let prefix = query.get("prefix").map_or("", String::as_str);
+        let marker = query.get("marker").map_or("", String::as_str);
+        let mut names = blobs
+            .keys()
+            .filter(|name| name.starts_with(prefix) && name.as_str() >= marker);
+        let page: Vec<_> = names.by_ref().take(PAGE_SIZE).collect();
+        let next_marker = names
+            .next()
+            .map(|name| escape_xml(name))
+            .unwrap_or_default();
+
+        let mut xml = r#"<?xml version="1.0" encoding="utf-8"?><EnumerationResults ContainerName="container">"#
+            .to_owned();
+        xml += &format!("<Prefix>{}</Prefix><Blobs>", escape_xml(prefix));
+        for name in page {
+            let name = escape_xml(name);
+            xml += &format!("<Blob><Name>{name}</Name><Properties><BlobType>BlockBlob</BlobType></Properties></Blob>");
+        }
+        xml += &format!("</Blobs><NextMarker>{next_marker}</NextMarker></EnumerationResults>");
+        xml.into_response()
+    }
+
+    /// Minimal in-memory stand-in for the Blob service REST API.
+    async fn handle_request(
+        State(blobs): State<Blobs>,
+        method: axum::http::Method,
+        uri: Uri,
+        Query(query): Query<HashMap<String, String>>,
+        headers: HeaderMap,
+        body: axum::body::Bytes,
+    ) -> Response {
+        if query.get("sig").map(String::as_str) != Some("test") {
+            return StatusCode::FORBIDDEN.into_response();
+        }
+        assert_eq!(headers["x-ms-version"], API_VERSION);
+
+        let mut blobs = blobs.lock().unwrap();
+        if uri.path() == CONTAINER_PATH {
+            assert_eq!(method, axum::http::Method::GET);
+            assert_eq!(query["restype"], "container");
+            assert_eq!(query["comp"], "list");
+            return list_blobs(&blobs, &query);
+        }
+
+        let name = uri
+            .path()
+            .strip_prefix(CONTAINER_PATH)
+            .and_then(|path| path.strip_prefix('/'))
+            .expect("unexpected path");
+        match method.as_str() {
+            "GET" => match blobs.get(name) {
+                Some(blob) => blob.clone().into_response(),
+                None => StatusCode::NOT_FOUND.into_response(),
+            },
+            "HEAD" if blobs.contains_key(name) => StatusCode::OK.into_response(),
+            "HEAD" => StatusCode::NOT_FOUND.into_response(),
+            "PUT" => {
+                if headers.get("x-ms-blob-type").map(HeaderValue::as_bytes) != Some(b"BlockBlob") {
+                    return StatusCode::BAD_REQUEST.into_response();
+                }
+                blobs.insert(name.to_owned(), body.to_vec());
+                StatusCode::CREATED.into_response()
+            }
+            "DELETE" if blobs.remove(name).is_some() => StatusCode::ACCEPTED.into_response(),
+            "DELETE" => StatusCode::NOT_FOUND.into_response(),
+            _ => StatusCode::METHOD_NOT_ALLOWED.into_response(),
+        }
+    }
+
+    async fn start_server() -> String {
+        let app = Router::new()
+            .fallback(handle_request)
+            .with_state(Blobs::default());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{local_addr}{CONTAINER_PATH}")
+    }
+
+    #[tokio::test]
+    async fn azure_store_basics() {
+        let container_url = start_server().await;
+        let dir = TempDir::new().unwrap();
+        let token_path = dir.path().join("sas_token");
+        tokio::fs::write(&token_path, format!("?{SAS_TOKEN}\n"))
+            .await
+            .unwrap();
+        let store = AzureBlobStore::new(&container_url, Some(&token_path))
+            .await
+            .unwrap();
+
+        let err = store
+            .get_raw(Bucket::WitnessInput, "witness_1.bin")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+        assert!(!store
+            .exists_raw(Bucket::WitnessInput, "witness_1.bin")
+            .await
+            .unwrap());
+
+        let keys = ["witness_1.bin", "witness_2.bin", "witness_3.bin", "a&b.bin"];
+        for (key, value) in keys.into_iter().zip(0_u8..) {
+            store
+                .put_raw(Bucket::WitnessInput, key, vec![value])
+                .await
+                .unwrap();
+        }
+        store
+            .put_raw(Bucket::ProofsFri, "proof_1.bin", vec![0])
+            .await
+            .unwrap();
+
+        let object = store
+            .get_raw(Bucket::WitnessInput, "witness_2.bin")
+            .await
+            .unwrap();
+        assert_eq!(object, [1]);
+        assert!(store
+            .exists_raw(Bucket::WitnessInput, "a&b.bin")
+            .await
+            .unwrap());
+
+        // Listing spans multiple pages.
+        let listed_keys = store.list_raw(Bucket::WitnessInput, "").await.unwrap();
+        assert_eq!(
+            listed_keys,
+            ["a&b.bin", "witness_1.bin", "witness_2.bin", "witness_3.bin"]
+        );
+        let listed_keys = store
+            .list_raw(Bucket::WitnessInput, "witness_")
+            .await
+            .unwrap();
+        assert_eq!(
+            listed_keys,
+            ["witness_1.bin", "witness_2.bin", "witness_3.bin"]
+        );
+
+        let removed_count = store
+            .remove_by_prefix_raw(Bucket::WitnessInput, "witness_")
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 3);
+        let listed_keys = store.list_raw(Bucket::WitnessInput, "").await.unwrap();
+        assert_eq!(listed_keys, ["a&b.bin"]);
+        let listed_keys = store.list_raw(Bucket::ProofsFri, "").await.unwrap();
+        assert_eq!(listed_keys, ["proof_1.bin"]);
+
+        assert_eq!(
+            store.storage_prefix_raw(Bucket::WitnessInput),
+            format!("{container_url}/witness_inputs")
+        );
+    }
+
+    #[tokio::test]
+    async fn azure_store_without_sas_token() {
+        let container_url = start_server().await;
+        let store = AzureBlobStore::new(&container_url, None).await.unwrap();
+        let err = store
+            .get_raw(Bucket::WitnessInput, "witness_1.bin")
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            ObjectStoreError::Initialization {
+                is_retriable: false,
+                ..
+            }
+        );
+    }
+}
@@ -0,0 +1,465 @@
+//! Object store wrapper transparently compressing and encrypting objects.
+
+use std::{collections::HashSet, fmt, path::Path};
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use async_trait::async_trait;
+use tokio::{fs, task};
+
+use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+
+/// Magic bytes prepended to encoded objects. Objects not starting with these bytes are considered
+/// to be stored as-is (e.g., because they were stored before encoding was enabled).
+const MAGIC: [u8; 6] = *b"zksobj";
+/// Version of the encoding format.
+const FORMAT_VERSION: u8 = 1;
+/// Header consists of magic bytes, format version and encoding flags.
+const HEADER_LEN: usize = MAGIC.len() + 2;
+const NONCE_LEN: usize = 12;
+
+const COMPRESSED_FLAG: u8 = 1;
+const ENCRYPTED_FLAG: u8 = 2;
+
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+/// AES-256-GCM key used to encrypt objects.
+#[derive(Clone)]
+pub(crate) struct EncryptionKey(Aes256Gcm);
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("EncryptionKey(_)")
+    }
+}
+
+impl EncryptionKey {
+    /// Reads a hex-encoded 32-byte key from the specified file.
+    pub async fn read(path: &Path) -> Result<Self, ObjectStoreError> {
+        let contents =
+            fs::read_to_string(path)
+                .await
+                .map_err(|err| ObjectStoreError::Initialization {
+                    source: err.into(),
+                    is_retriable: false,
+                })?;
+        Self::from_hex(contents.trim()).map_err(|err| ObjectStoreError::Initialization {
+            source: format!("invalid encryption key at `{}`: {err}", path.display()).into(),
+            is_retriable: false,
+        })
+    }
+
+    fn from_hex(hex_key: &str) -> Result<Self, String> {
+        let hex_key = hex_key.strip_prefix("0x").unwrap_or(hex_key);
+        let bytes = hex::decode(hex_key).map_err(|err| err.to_string())?;
+        let cipher = Aes256Gcm::new_from_slice(&bytes)
+            .map_err(|_| format!("expected 32-byte key, got {} bytes", bytes.len()))?;
+        Ok(Self(cipher))
+    }
+}
+
+/// Encryption options: the key and buckets in which stored objects are encrypted.
+#[derive(Debug, Clone)]
+pub(crate) struct ObjectEncryption {
+    pub key: EncryptionKey,
+    pub buckets: HashSet<Bucket>,
+}
+
+/// Encoding options applied to stored objects.
+#[derive(Debug, Clone)]
+struct ObjectEncoder {
+    compress: bool,
+    encryption: Option<ObjectEncryption>,
+}
+
+impl ObjectEncoder {
+    /// Associated data authenticated together with the encrypted object. Binds the object to its location
+    /// so that encrypted objects cannot be swapped.
+    fn associated_data(header: &[u8], bucket: Bucket, key: &str) -> Vec<u8> {
+        let mut data = header.to_vec();
+        data.extend_from_slice(bucket.as_str().as_bytes());
+        data.push(b'/');
+        data.extend_from_slice(key.as_bytes());
+        data
+    }
+
+    fn encode(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        // Objects in other buckets are never encrypted, but encrypted objects in them can still be decoded.
+        let encryption_key = self
+            .encryption
+            .as_ref()
+            .filter(|encryption| encryption.buckets.contains(&bucket))
+            .map(|encryption| &encryption.key);
+        if !self.compress && encryption_key.is_none() {
+            // Store the object as-is, so that it can be read by the components unaware of the encoding.
+            return Ok(value);
+        }
+
+        let mut flags = 0;
+        let payload = if self.compress {
+            flags |= COMPRESSED_FLAG;
+            zstd::encode_all(value.as_slice(), ZSTD_COMPRESSION_LEVEL)
+                .map_err(|err| ObjectStoreError::Serialization(err.into()))?
+        } else {
+            value
+        };
+        if encryption_key.is_some() {
+            flags |= ENCRYPTED_FLAG;
+        }
+
+        let mut output = MAGIC.to_vec();
+        output.extend_from_slice(&[FORMAT_VERSION, flags]);
+        if let Some(EncryptionKey(cipher)) = encryption_key {
+            let nonce: [u8; NONCE_LEN] = rand::random();
+            let aad = Self::associated_data(&output, bucket, key);
+            let payload = Payload {
+                msg: &payload,
+                aad: &aad,
+            };
+            let ciphertext = cipher
+                .encrypt(Nonce::from_slice(&nonce), payload)
+                .map_err(|_| ObjectStoreError::Serialization("failed encrypting object".into()))?;
+            output.extend_from_slice(&nonce);
+            output.extend_from_slice(&ciphertext);
+        } else {
+            output.extend_from_slice(&payload);
+        }
+        Ok(output)
+    }
+
+    fn decode(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        bytes: Vec<u8>,
+    ) -> Result<Vec<u8>, ObjectStoreError> {
+        if bytes.len() < HEADER_LEN || !bytes.starts_with(&MAGIC) {
+            return Ok(bytes);
+        }
+
+        let (header, payload) = bytes.split_at(HEADER_LEN);
+        let version = header[MAGIC.len()];
+        if version != FORMAT_VERSION {
+            let err = format!("unsupported object encoding version: {version}");
+            return Err(ObjectStoreError::Serialization(err.into()));
+        }
+        let flags = header[MAGIC.len() + 1];
+        if flags & !(COMPRESSED_FLAG | ENCRYPTED_FLAG) != 0 {
+            let err = format!("unsupported object encoding flags: {flags:#04x}");
+            return Err(ObjectStoreError::Serialization(err.into()));
+        }
+
+        let decrypted;
+        let payload = if flags & ENCRYPTED_FLAG != 0 {
+            let Some(ObjectEncryption {
+                key: EncryptionKey(cipher),
+                ..
+            }) = &self.encryption
+            else {
+                return Err(ObjectStoreError::Other {
+                    source: "object is encrypted, but encryption key is not configured".into(),
+                    is_retriable: false,
+                });
+            };
+            if payload.len() < NONCE_LEN {
+                return Err(ObjectStoreError::Serialization(
+                    "encrypted object is truncated".into(),
+                ));
+            }
+            let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+            let aad = Self::associated_data(header, bucket, key);
+            let payload = Payload {
+                msg: ciphertext,
+                aad: &aad,
+            };
+            decrypted = cipher
+                .decrypt(Nonce::from_slice(nonce), payload)
+                .map_err(|_| {
+                    ObjectStoreError::Serialization(
+                        "failed decrypting object; the encryption key may be incorrect".into(),
+                    )
+                })?;
+            decrypted.as_slice()
+        } else {
+            payload
+        };
+
+        if flags & COMPRESSED_FLAG != 0 {
+            zstd::decode_all(payload).map_err(|err| ObjectStoreError::Serialization(err.into()))
+        } else {
+            Ok(payload.to_vec())
+        }
+    }
+}
+
+/// [`ObjectStore`] wrapper compressing and / or encrypting objects before storing them in the underlying store.
+///
+/// Encoded objects are prefixed with a header specifying the applied transforms; objects without the header
+/// are returned as-is. Thus, objects remain readable if the encoding options change.
+#[derive(Debug)]
+pub(crate) struct EncodingObjectStore<S> {
+    inner: S,
+    encoder: ObjectEncoder,
+}
+
+impl<S: ObjectStore> EncodingObjectStore<S> {
+    pub fn new(inner: S, compress: bool, encryption: Option<ObjectEncryption>) -> Self {
+        if compress || encryption.is_some() {
+            tracing::info!(
+                compress,
+                encrypted_buckets = ?encryption.as_ref().map(|encryption| &encryption.buckets),
+                "Initializing object encoding for store {inner:?}"
+            );
+        }
+        Self {
+            inner,
+            encoder: ObjectEncoder {
+                compress,
+                encryption,
+            },
+        }
+    }
+}
+
+fn map_join_error(err: task::JoinError) -> ObjectStoreError {
+    ObjectStoreError::Other {
+        source: err.into(),
+        is_retriable: false,
+    }
+}
+
+#[async_trait]
+impl<S: ObjectStore> ObjectStore for EncodingObjectStore<S> {
+    #[tracing::instrument(name = "EncodingObjectStore::get_raw", skip(self))]
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let bytes = self.inner.get_raw(bucket, key).await?;
+        let encoder = self.encoder.clone();
+        let key = key.to_owned();
+        // Decompression and decryption are CPU-bound and can take a while for large objects.
+        task::spawn_blocking(move || encoder.decode(bucket, &key, bytes))
+            .await
+            .map_err(map_join_error)?
+    }
+
+    #[tracing::instrument(
+        name = "EncodingObjectStore::put_raw",
+        skip(self, value),
+        fields(value.len = value.len())
+    )]
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let encoder = self.encoder.clone();
+        let owned_key = key.to_owned();
+        let encoded = task::spawn_blocking(move || encoder.encode(bucket, &owned_key, value))
+            .await
+            .map_err(map_join_error)??;
+        self.inner.put_raw(bucket, key, encoded).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.inner.remove_raw(bucket, key).await
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        self.inner.list_raw(bucket, prefix).await
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        self.inner.exists_raw(bucket, key).await
+    }
+
+    async fn remove_by_prefix_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<usize, ObjectStoreError> {
+        self.inner.remove_by_prefix_raw(bucket, prefix).await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::MockObjectStore;
+
+    const KEY: &str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+
+    fn test_key() -> EncryptionKey {
+        EncryptionKey::from_hex(KEY).unwrap()
+    }
+
+    fn encryption(key: EncryptionKey) -> Option<ObjectEncryption> {
+        Some(ObjectEncryption {
+            key,
+            buckets: HashSet::from([Bucket::WitnessInput]),
+        })
+    }
+
+    #[test]
+    fn parsing_encryption_key() {
+        EncryptionKey::from_hex(KEY).unwrap();
+        EncryptionKey::from_hex(&KEY[2..]).unwrap();
+        let err = EncryptionKey::from_hex("0x0101").unwrap_err();
+        assert!(err.contains("32-byte"), "{err}");
+        EncryptionKey::from_hex("not a key").unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn encoding_roundtrip() {
+        let value = b"witness input ".repeat(100);
+        for (compress, encryption) in [
+            (true, None),
+            (false, encryption(test_key())),
+            (true, encryption(test_key())),
+        ] {
+            let encrypt = encryption.is_some();
+            let store = EncodingObjectStore::new(MockObjectStore::default(), compress, encryption);
+            store
+                .put_raw(Bucket::WitnessInput, "test", value.clone())
+                .await
+                .unwrap();
+
+            let stored = store
+                .inner
+                .get_raw(Bucket::WitnessInput, "test")
+                .await
+                .unwrap();
+            assert!(stored.starts_with(&MAGIC));
+            assert_eq!(
+                stored[MAGIC.len() + 1],
+                u8::from(compress) * COMPRESSED_FLAG + u8::from(encrypt) * ENCRYPTED_FLAG
+            );
+            if compress {
+                assert!(stored.len() < value.len());
+            }
+
+            let restored = store.get_raw(Bucket::WitnessInput, "test").await.unwrap();
+            assert_eq!(restored, value);
+        }
+    }
+
+    #[tokio::test]
+    async fn reading_objects_with_different_encoding() {
+        let inner = MockObjectStore::default();
+        inner
+            .put_raw(Bucket::WitnessInput, "legacy", vec![1, 2, 3])
+            .await
+            .unwrap();
+        let store = EncodingObjectStore::new(inner, true, encryption(test_key()));
+        store
+            .put_raw(Bucket::WitnessInput, "encoded", vec![4, 5, 6])
+            .await
+            .unwrap();
+        let object = store.get_raw(Bucket::WitnessInput, "legacy").await.unwrap();
+        assert_eq!(object, [1, 2, 3]);
+
+        // Check that encoded objects remain readable after disabling compression.
+        let store = EncodingObjectStore::new(store.inner, false, encryption(test_key()));
+        let object = store
+            .get_raw(Bucket::WitnessInput, "encoded")
+            .await
+            .unwrap();
+        assert_eq!(object, [4, 5, 6]);
+
+        // Encrypted objects cannot be read without the key...
+        let store = EncodingObjectStore::new(store.inner, false, None);
+        let err = store
+            .get_raw(Bucket::WitnessInput, "encoded")
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            ObjectStoreError::Other {
+                is_retriable: false,
+                ..
+            }
+        );
+        // ...or with another key.
+        let other_key = EncryptionKey::from_hex(&"02".repeat(32)).unwrap();
+        let store = EncodingObjectStore::new(store.inner, false, encryption(other_key));
+        let err = store
+            .get_raw(Bucket::WitnessInput, "encoded")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::Serialization(_));
+
+        // Encrypted objects are bound to their location.
+        let store = EncodingObjectStore::new(store.inner, false, encryption(test_key()));
+        let object = store
+            .inner
+            .get_raw(Bucket::WitnessInput, "encoded")
+            .await
+            .unwrap();
+        store
+            .inner
+            .put_raw(Bucket::WitnessInput, "moved", object)
+            .await
+            .unwrap();
+        let err = store
+            .get_raw(Bucket::WitnessInput, "moved")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::Serialization(_));
+    }
+
+    #[tokio::test]
+    async fn objects_are_stored_as_is_without_encoding() {
+        let store = EncodingObjectStore::new(MockObjectStore::default(), false, None);
+        store
+            .put_raw(Bucket::StorageSnapshot, "test", vec![1, 2, 3])
+            .await
+            .unwrap();
+        let stored = store
+            .inner
+            .get_raw(Bucket::StorageSnapshot, "test")
+            .await
+            .unwrap();
+        assert_eq!(stored, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn objects_are_only_encrypted_in_configured_buckets() {
+        let store =
+            EncodingObjectStore::new(MockObjectStore::default(), true, encryption(test_key()));
+        let value = b"snapshot chunk ".repeat(100);
+        store
+            .put_raw(Bucket::StorageSnapshot, "test", value.clone())
+            .await
+            .unwrap();
+        let stored = store
+            .inner
+            .get_raw(Bucket::StorageSnapshot, "test")
+            .await
+            .unwrap();
+        assert_eq!(stored[MAGIC.len() + 1], COMPRESSED_FLAG);
+
+        // The object is readable without the key.
+        let store = EncodingObjectStore::new(store.inner, false, None);
+        let restored = store
+            .get_raw(Bucket::StorageSnapshot, "test")
+            .await
+            .unwrap();
+        assert_eq!(restored, value);
+    }
+}
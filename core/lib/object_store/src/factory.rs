@@ -1,14 +1,17 @@
-use std::{path::Path, sync::Arc};
+use std::{collections::HashSet, sync::Arc};
 
 use anyhow::Context as _;
 use tokio::sync::OnceCell;
 use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
 
 use crate::{
+    azure::AzureBlobStore,
+    encoding::{EncodingObjectStore, EncryptionKey, ObjectEncryption},
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
+    http::HttpObjectStore,
     mirror::MirroringObjectStore,
-    raw::{ObjectStore, ObjectStoreError},
+    raw::{Bucket, ObjectStore, ObjectStoreError},
     retries::StoreWithRetries,
     s3::{S3Store, S3StoreAuthMode},
 };
@@ -71,7 +74,7 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await
+                Self::wrap_store(store, config).await
             }
             ObjectStoreMode::GCSWithCredentialFile {
                 bucket_base_url,
@@ -86,7 +89,7 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await
+                Self::wrap_store(store, config).await
             }
             ObjectStoreMode::GCSAnonymousReadOnly { bucket_base_url } => {
                 let store = StoreWithRetries::try_new(config.max_retries, || {
@@ -96,7 +99,7 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await
+                Self::wrap_store(store, config).await
             }
 
             ObjectStoreMode::S3WithCredentialFile {
@@ -116,7 +119,7 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await
+                Self::wrap_store(store, config).await
             }
             ObjectStoreMode::S3AnonymousReadOnly {
                 bucket_base_url,
@@ -132,7 +135,28 @@ impl ObjectStoreFactory {
                     )
                 })
                 .await?;
-                Self::wrap_mirroring(store, config.local_mirror_path.as_deref()).await
+                Self::wrap_store(store, config).await
+            }
+
+            ObjectStoreMode::AzureBlob {
+                bucket_base_url,
+                sas_token_file_path,
+            } => {
+                let store = StoreWithRetries::try_new(config.max_retries, || {
+                    AzureBlobStore::new(bucket_base_url, sas_token_file_path.as_deref())
+                })
+                .await?;
+                Self::wrap_store(store, config).await
+            }
+            ObjectStoreMode::Http {
+                bucket_base_url,
+                auth_token_file_path,
+            } => {
+                let store = StoreWithRetries::try_new(config.max_retries, || {
+                    HttpObjectStore::new(bucket_base_url, auth_token_file_path.as_deref())
+                })
+                .await?;
+                Self::wrap_store(store, config).await
             }
 
             ObjectStoreMode::FileBacked {
@@ -149,19 +173,139 @@ impl ObjectStoreFactory {
                         mirror_path.display()
                     );
                 }
-                Ok(Arc::new(store))
+                Ok(Arc::new(Self::wrap_encoding(store, config).await?))
             }
         }
     }
 
-    async fn wrap_mirroring(
+    async fn wrap_store(
         store: impl ObjectStore,
-        mirror_path: Option<&Path>,
+        config: &ObjectStoreConfig,
     ) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
-        Ok(if let Some(mirror_path) = mirror_path {
-            Arc::new(MirroringObjectStore::new(store, mirror_path.to_owned()).await?)
+        // Mirroring is applied before encoding, so that the mirror contains encoded objects. Otherwise,
+        // encrypted objects would be stored in plaintext in the mirror.
+        Ok(if let Some(mirror_path) = &config.local_mirror_path {
+            let store = MirroringObjectStore::new(store, mirror_path.clone()).await?;
+            Arc::new(Self::wrap_encoding(store, config).await?)
         } else {
-            Arc::new(store)
+            Arc::new(Self::wrap_encoding(store, config).await?)
         })
     }
+
+    async fn wrap_encoding<S: ObjectStore>(
+        store: S,
+        config: &ObjectStoreConfig,
+    ) -> Result<EncodingObjectStore<S>, ObjectStoreError> {
+        let encryption = match &config.encryption_key_path {
+            Some(path) => Some(ObjectEncryption {
+                key: EncryptionKey::read(path).await?,
+                buckets: Self::parse_encrypted_buckets(&config.encrypted_buckets)?,
+            }),
+            None => {
+                if !config.encrypted_buckets.is_empty() {
+                    tracing::warn!(
+                        "Encrypted buckets {:?} are specified, but the encryption key is not; objects will not be encrypted",
+                        config.encrypted_buckets
+                    );
+                }
+                None
+            }
+        };
+        // Encoding wrapper is applied even if no encoding is configured, so that previously encoded objects remain readable.
+        Ok(EncodingObjectStore::new(
+            store,
+            config.compress_objects,
+            encryption,
+        ))
+    }
+
+    fn parse_encrypted_buckets(names: &[String]) -> Result<HashSet<Bucket>, ObjectStoreError> {
+        let init_error = |message: String| ObjectStoreError::Initialization {
+            source: message.into(),
+            is_retriable: false,
+        };
+        if names.is_empty() {
+            return Err(init_error(
+                "encryption key is specified, but encrypted buckets are not".to_owned(),
+            ));
+        }
+        names
+            .iter()
+            .map(|name| {
+                Bucket::from_name(name)
+                    .ok_or_else(|| init_error(format!("unknown encrypted bucket `{name}`")))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use tempfile::TempDir;
+    use zksync_config::configs::object_store::ObjectStoreConfig;
+
+    use super::*;
+    use crate::MockObjectStore;
+
+    const KEY: &str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+
+    #[tokio::test]
+    async fn mirror_stores_encrypted_objects() {
+        let dir = TempDir::new().unwrap();
+        let key_path = dir.path().join("encryption.key");
+        tokio::fs::write(&key_path, KEY).await.unwrap();
+        let mirror_path = dir.path().join("mirror");
+        let config = ObjectStoreConfig {
+            local_mirror_path: Some(mirror_path.clone()),
+            encryption_key_path: Some(key_path),
+            encrypted_buckets: vec![Bucket::WitnessInput.as_str().to_owned()],
+            ..ObjectStoreConfig::for_tests()
+        };
+        let store = ObjectStoreFactory::wrap_store(MockObjectStore::default(), &config)
+            .await
+            .unwrap();
+
+        let value = b"witness input".to_vec();
+        store
+            .put_raw(Bucket::WitnessInput, "test", value.clone())
+            .await
+            .unwrap();
+        let mirror = FileBackedObjectStore::new(mirror_path).await.unwrap();
+        let mirrored = mirror.get_raw(Bucket::WitnessInput, "test").await.unwrap();
+        assert!(
+            !mirrored
+                .windows(value.len())
+                .any(|window| window == value.as_slice()),
+            "{mirrored:?}"
+        );
+        let restored = store.get_raw(Bucket::WitnessInput, "test").await.unwrap();
+        assert_eq!(restored, value);
+    }
+
+    #[tokio::test]
+    async fn encrypted_buckets_are_validated() {
+        let dir = TempDir::new().unwrap();
+        let key_path = dir.path().join("encryption.key");
+        tokio::fs::write(&key_path, KEY).await.unwrap();
+        let mut config = ObjectStoreConfig {
+            encryption_key_path: Some(key_path),
+            ..ObjectStoreConfig::for_tests()
+        };
+        for (buckets, expected_err) in [
+            (vec![], "encrypted buckets are not"),
+            (
+                vec!["witness_inputs".to_owned(), "unknown".to_owned()],
+                "unknown encrypted bucket",
+            ),
+        ] {
+            config.encrypted_buckets = buckets;
+            let err = ObjectStoreFactory::wrap_store(MockObjectStore::default(), &config)
+                .await
+                .unwrap_err();
+            let err =
+                assert_matches!(err, ObjectStoreError::Initialization { source, .. } => source);
+            assert!(err.to_string().contains(expected_err), "{err}");
+        }
+    }
 }
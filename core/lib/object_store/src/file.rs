@@ -77,6 +77,45 @@ impl ObjectStore for FileBackedObjectStore {
         fs::remove_file(filename).await.map_err(From::from)
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let bucket_path = self.base_dir.join(bucket.to_string());
+        let mut entries = match fs::read_dir(&bucket_path).await {
+            Ok(entries) => entries,
+            // Buckets not created on initialization are considered empty.
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut keys = vec![];
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_file() {
+                continue;
+            }
+            // Non-UTF8 file names cannot be produced by `put_raw()`, so it's safe to skip them.
+            let Ok(key) = entry.file_name().into_string() else {
+                continue;
+            };
+            if key.starts_with(prefix) {
+                keys.push(key);
+            }
+        }
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        let filename = self.filename(bucket, key);
+        match fs::metadata(filename).await {
+            Ok(metadata) => Ok(metadata.is_file()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.base_dir
             .join(bucket.to_string())
@@ -135,4 +174,47 @@ mod test {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_list_and_remove_by_prefix() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_owned();
+        let object_store = FileBackedObjectStore::new(path).await.unwrap();
+        for key in ["proof_2.bin", "proof_1.bin", "other.bin"] {
+            object_store
+                .put_raw(Bucket::ProofsFri, key, vec![0, 1])
+                .await
+                .unwrap();
+        }
+
+        let keys = object_store
+            .list_raw(Bucket::ProofsFri, "proof_")
+            .await
+            .unwrap();
+        assert_eq!(keys, ["proof_1.bin", "proof_2.bin"]);
+        let keys = object_store.list_raw(Bucket::ProofsFri, "").await.unwrap();
+        assert_eq!(keys, ["other.bin", "proof_1.bin", "proof_2.bin"]);
+        let keys = object_store
+            .list_raw(Bucket::DataAvailability, "")
+            .await
+            .unwrap();
+        assert!(keys.is_empty());
+
+        assert!(object_store
+            .exists_raw(Bucket::ProofsFri, "proof_1.bin")
+            .await
+            .unwrap());
+        assert!(!object_store
+            .exists_raw(Bucket::ProofsFri, "proof_3.bin")
+            .await
+            .unwrap());
+
+        let removed_count = object_store
+            .remove_by_prefix_raw(Bucket::ProofsFri, "proof_")
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 2);
+        let keys = object_store.list_raw(Bucket::ProofsFri, "").await.unwrap();
+        assert_eq!(keys, ["other.bin"]);
+    }
 }
@@ -11,6 +11,7 @@ use google_cloud_storage::{
             delete::DeleteObjectRequest,
             download::Range,
             get::GetObjectRequest,
+            list::ListObjectsRequest,
             upload::{Media, UploadObjectRequest, UploadType},
         },
         Error as HttpError,
@@ -117,7 +118,7 @@ impl From<AuthError> for ObjectStoreError {
     }
 }
 
-fn is_retriable_http_error(err: &reqwest::Error) -> bool {
+pub(crate) fn is_retriable_http_error(err: &reqwest::Error) -> bool {
     err.is_timeout()
         || err.is_connect()
         // Not all request errors are logically transient, but a significant part of them are (e.g.,
@@ -250,6 +251,69 @@ impl ObjectStore for GoogleCloudStore {
         Ok(())
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let full_prefix = Self::filename(bucket.as_str(), prefix);
+        tracing::trace!(
+            "Listing objects in GCS with prefix {full_prefix} from bucket {}",
+            self.bucket_prefix
+        );
+
+        let mut request = ListObjectsRequest {
+            bucket: self.bucket_prefix.clone(),
+            prefix: Some(full_prefix),
+            ..ListObjectsRequest::default()
+        };
+        let bucket_prefix = Self::filename(bucket.as_str(), "");
+        let mut keys = vec![];
+        loop {
+            let response = {
+                let _permit = self.semaphore.acquire().await?;
+                self.client.list_objects(&request).await?
+            };
+            let objects = response.items.unwrap_or_default();
+            keys.extend(
+                objects.into_iter().filter_map(|object| {
+                    object.name.strip_prefix(&bucket_prefix).map(str::to_owned)
+                }),
+            );
+
+            match response.next_page_token {
+                Some(token) => request.page_token = Some(token),
+                None => break,
+            }
+        }
+        // GCS returns objects in lexicographic order, but we sort keys just in case.
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        let _permit = self.semaphore.acquire().await?;
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Checking existence of key {filename} in GCS from bucket {}",
+            self.bucket_prefix
+        );
+
+        let request = GetObjectRequest {
+            bucket: self.bucket_prefix.clone(),
+            object: filename,
+            ..GetObjectRequest::default()
+        };
+        // Only fetches object metadata, not its contents.
+        match self.client.get_object(&request).await {
+            Ok(_) => Ok(true),
+            Err(err) => match ObjectStoreError::from(err) {
+                ObjectStoreError::KeyNotFound(_) => Ok(false),
+                err => Err(err),
+            },
+        }
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!(
             "https://storage.googleapis.com/{}/{}",
@@ -0,0 +1,601 @@
+//! [`ObjectStore`] implementation based on a generic HTTP server, and HTTP utils shared with other stores.
+
+use std::{fmt, path::Path, time::Duration};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use percent_encoding::percent_decode_str;
+use quick_xml::{events::Event, Reader};
+use reqwest::{header, Client, Method, RequestBuilder, Response, StatusCode, Url};
+
+use crate::{
+    gcs::is_retriable_http_error,
+    raw::{BoxedError, Bucket, ObjectStore, ObjectStoreError},
+};
+
+/// Maximum length of the response body included into error messages.
+const MAX_ERROR_BODY_LEN: usize = 256;
+/// Timeout for establishing a connection to the server.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Total timeout for a single request. Objects (e.g., witness inputs) may be quite large, so the timeout is generous.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(600);
+
+impl From<reqwest::Error> for ObjectStoreError {
+    fn from(err: reqwest::Error) -> Self {
+        let is_retriable = is_retriable_http_error(&err);
+        // URLs may contain sensitive info (e.g., SAS tokens), so we don't include them into errors.
+        ObjectStoreError::Other {
+            source: err.without_url().into(),
+            is_retriable,
+        }
+    }
+}
+
+/// Creates an HTTP client with connect and request timeouts, so that a stalled server cannot hang the store indefinitely.
+pub(crate) fn http_client() -> Result<Client, ObjectStoreError> {
+    build_http_client(CONNECT_TIMEOUT, REQUEST_TIMEOUT)
+}
+
+fn build_http_client(
+    connect_timeout: Duration,
+    request_timeout: Duration,
+) -> Result<Client, ObjectStoreError> {
+    Client::builder()
+        .connect_timeout(connect_timeout)
+        .timeout(request_timeout)
+        .build()
+        .map_err(|err| ObjectStoreError::Initialization {
+            source: err.into(),
+            is_retriable: false,
+        })
+}
+
+/// Reads a secret (e.g., an auth token) from the specified file.
+pub(crate) async fn read_secret_file(path: &Path) -> Result<String, ObjectStoreError> {
+    let contents =
+        tokio::fs::read_to_string(path)
+            .await
+            .map_err(|err| ObjectStoreError::Initialization {
+                source: format!("failed reading secret from `{}`: {err}", path.display()).into(),
+                is_retriable: false,
+            })?;
+    Ok(contents.trim().to_owned())
+}
+
+pub(crate) fn parse_base_url(url: &str) -> Result<Url, ObjectStoreError> {
+    let url: Url = url
+        .parse()
+        .map_err(|err| ObjectStoreError::Initialization {
+            source: format!("invalid base URL `{url}`: {err}").into(),
+            is_retriable: false,
+        })?;
+    if url.cannot_be_a_base() {
+        return Err(ObjectStoreError::Initialization {
+            source: format!("URL `{url}` cannot be a base").into(),
+            is_retriable: false,
+        });
+    }
+    Ok(url)
+}
+
+/// Appends path segments for the specified bucket and key to the base `url`. Slashes in the key are treated
+/// as path separators; other special chars are percent-encoded.
+pub(crate) fn push_object_path(url: &mut Url, bucket: Bucket, key: &str) {
+    url.path_segments_mut()
+        .expect("checked in `parse_base_url()`")
+        .pop_if_empty()
+        .push(bucket.as_str())
+        .extend(key.split('/'));
+}
+
+/// Converts an unsuccessful HTTP response into an error. The error does not include the full request URL,
+/// since it may contain sensitive info.
+pub(crate) async fn check_response(response: Response) -> Result<Response, ObjectStoreError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let path = response.url().path().to_owned();
+    let mut body = response.text().await.unwrap_or_default();
+    if body.len() > MAX_ERROR_BODY_LEN {
+        let mut end = MAX_ERROR_BODY_LEN;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body.truncate(end);
+        body.push_str("...");
+    }
+    let source: BoxedError = format!("request to `{path}` failed with {status}: {body}").into();
+    Err(match status {
+        StatusCode::NOT_FOUND => ObjectStoreError::KeyNotFound(source),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ObjectStoreError::Initialization {
+            source,
+            is_retriable: false,
+        },
+        _ => ObjectStoreError::Other {
+            source,
+            is_retriable: status.is_server_error()
+                || matches!(
+                    status,
+                    StatusCode::REQUEST_TIMEOUT | StatusCode::TOO_MANY_REQUESTS
+                ),
+        },
+    })
+}
+
+fn invalid_response(err: impl Into<BoxedError>) -> ObjectStoreError {
+    ObjectStoreError::Other {
+        source: err.into(),
+        is_retriable: false,
+    }
+}
+
+/// Extracts unescaped text contents of XML elements with the local name `name` directly nested in an element
+/// with the local name `parent`. Namespaces are ignored.
+pub(crate) fn xml_element_texts(
+    xml: &str,
+    parent: &str,
+    name: &str,
+) -> Result<Vec<String>, ObjectStoreError> {
+    let mut reader = Reader::from_str(xml);
+    let mut path = vec![];
+    let mut texts = vec![];
+    loop {
+        match reader.read_event().map_err(invalid_response)? {
+            Event::Start(start) => path.push(start.local_name().as_ref().to_vec()),
+            Event::End(_) => {
+                path.pop();
+            }
+            Event::Text(text) => {
+                if let [.., text_parent, text_name] = path.as_slice() {
+                    if text_parent == parent.as_bytes() && text_name == name.as_bytes() {
+                        texts.push(text.unescape().map_err(invalid_response)?.into_owned());
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => { /* Skip other events */ }
+        }
+    }
+    Ok(texts)
+}
+
+/// [`ObjectStore`] implementation based on a generic HTTP server, such as a WebDAV server or an artifact server.
+///
+/// Objects are stored at `{base_url}/{bucket}/{key}` and are accessed using `GET`, `HEAD`, `PUT` and `DELETE` requests.
+/// Listing objects uses the WebDAV `PROPFIND` method; only objects directly in the bucket collection are listed.
+pub struct HttpObjectStore {
+    base_url: Url,
+    auth_token: Option<String>,
+    client: Client,
+}
+
+impl fmt::Debug for HttpObjectStore {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("HttpObjectStore")
+            .field("base_url", &self.base_url.as_str())
+            // Skip `auth_token` as it's sensitive
+            .finish_non_exhaustive()
+    }
+}
+
+impl HttpObjectStore {
+    /// Creates a new HTTP store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the base URL is invalid, or the auth token cannot be read.
+    pub async fn new(
+        base_url: &str,
+        auth_token_file_path: Option<&Path>,
+    ) -> Result<Self, ObjectStoreError> {
+        let base_url = parse_base_url(base_url)?;
+        let auth_token = if let Some(path) = auth_token_file_path {
+            Some(read_secret_file(path).await?)
+        } else {
+            None
+        };
+        Ok(Self {
+            base_url,
+            auth_token,
+            client: http_client()?,
+        })
+    }
+
+    fn object_url(&self, bucket: Bucket, key: &str) -> Url {
+        let mut url = self.base_url.clone();
+        push_object_path(&mut url, bucket, key);
+        url
+    }
+
+    /// Returns the bucket URL with a trailing slash, as required for WebDAV collections.
+    fn bucket_url(&self, bucket: Bucket) -> Url {
+        self.object_url(bucket, "")
+    }
+
+    fn request(&self, method: Method, url: Url) -> RequestBuilder {
+        let request = self.client.request(method, url);
+        if let Some(token) = &self.auth_token {
+            request.bearer_auth(token)
+        } else {
+            request
+        }
+    }
+
+    async fn put_object(&self, url: Url, value: Bytes) -> Result<Response, ObjectStoreError> {
+        Ok(self.request(Method::PUT, url).body(value).send().await?)
+    }
+
+    /// Creates a WebDAV collection for the bucket.
+    async fn create_collection(&self, bucket: Bucket) -> Result<(), ObjectStoreError> {
+        let method = Method::from_bytes(b"MKCOL").unwrap();
+        let response = self.request(method, self.bucket_url(bucket)).send().await?;
+        // 405 Method Not Allowed is returned if the collection already exists
+        if response.status() != StatusCode::METHOD_NOT_ALLOWED {
+            check_response(response).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ObjectStore for HttpObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let url = self.object_url(bucket, key);
+        tracing::trace!("Fetching data from HTTP store at {}", url.path());
+
+        let response = self.request(Method::GET, url).send().await?;
+        let response = check_response(response).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let url = self.object_url(bucket, key);
+        tracing::trace!("Storing data to HTTP store at {}", url.path());
+
+        let value = Bytes::from(value);
+        let mut response = self.put_object(url.clone(), value.clone()).await?;
+        if response.status() == StatusCode::CONFLICT {
+            // WebDAV servers respond with 409 Conflict if the parent collection doesn't exist.
+            tracing::debug!("Creating collection for bucket {bucket}");
+            self.create_collection(bucket).await?;
+            response = self.put_object(url, value).await?;
+        }
+        check_response(response).await?;
+        Ok(())
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        let url = self.object_url(bucket, key);
+        tracing::trace!("Removing data from HTTP store at {}", url.path());
+
+        let response = self.request(Method::DELETE, url).send().await?;
+        check_response(response).await?;
+        Ok(())
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?><propfind xmlns="DAV:"><prop><resourcetype/></prop></propfind>"#;
+
+        let bucket_url = self.bucket_url(bucket);
+        tracing::trace!(
+            "Listing objects with prefix {prefix} in HTTP store at {}",
+            bucket_url.path()
+        );
+
+        let method = Method::from_bytes(b"PROPFIND").unwrap();
+        let response = self
+            .request(method, bucket_url.clone())
+            .header("Depth", "1")
+            .header(header::CONTENT_TYPE, "application/xml")
+            .body(PROPFIND_BODY)
+            .send()
+            .await?;
+        let response = match check_response(response).await {
+            Ok(response) => response,
+            // The bucket collection is not created yet.
+            Err(ObjectStoreError::KeyNotFound(_)) => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+        let body = response.text().await?;
+
+        let mut keys = vec![];
+        for href in xml_element_texts(&body, "response", "href")? {
+            let url = bucket_url.join(&href).map_err(invalid_response)?;
+            let Some(encoded_key) = url.path().strip_prefix(bucket_url.path()) else {
+                continue;
+            };
+            // Skip the collection itself and nested collections.
+            if encoded_key.is_empty() || encoded_key.ends_with('/') {
+                continue;
+            }
+            let key = percent_decode_str(encoded_key)
+                .decode_utf8()
+                .map_err(invalid_response)?;
+            if key.starts_with(prefix) {
+                keys.push(key.into_owned());
+            }
+        }
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        let url = self.object_url(bucket, key);
+        tracing::trace!(
+            "Checking existence of object in HTTP store at {}",
+            url.path()
+        );
+
+        let response = self.request(Method::HEAD, url).send().await?;
+        match check_response(response).await {
+            Ok(_) => Ok(true),
+            Err(ObjectStoreError::KeyNotFound(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.object_url(bucket, "")
+            .as_str()
+            .trim_end_matches('/')
+            .to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, HashSet},
+        sync::{Arc, Mutex},
+    };
+
+    use assert_matches::assert_matches;
+    use axum::{
+        extract::State,
+        http::{HeaderMap, HeaderValue, Uri},
+        response::{IntoResponse, Response as AxumResponse},
+        Router,
+    };
+    use tempfile::TempDir;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    const AUTH_TOKEN: &str = "test-token";
+
+    /// State of a minimal in-memory WebDAV server.
+    #[derive(Debug, Default)]
+    struct ServerState {
+        collections: HashSet<String>,
+        objects: BTreeMap<String, Vec<u8>>,
+    }
+
+    type SharedState = Arc<Mutex<ServerState>>;
+
+    async fn handle_request(
+        State(state): State<SharedState>,
+        method: axum::http::Method,
+        uri: Uri,
+        headers: HeaderMap,
+        body: axum::body::Bytes,
+    ) -> AxumResponse {
+        let expected_auth = format!("Bearer {AUTH_TOKEN}");
+        if headers
+            .get(header::AUTHORIZATION)
+            .map(HeaderValue::as_bytes)
+            != Some(expected_auth.as_bytes())
+        {
+            return axum::http::StatusCode::UNAUTHORIZED.into_response();
+        }
+
+        let path = uri.path().to_owned();
+        let mut state = state.lock().unwrap();
+        let status = |code: u16| axum::http::StatusCode::from_u16(code).unwrap();
+        match method.as_str() {
+            "GET" => match state.objects.get(&path) {
+                Some(object) => object.clone().into_response(),
+                None => status(404).into_response(),
+            },
+            "HEAD" => {
+                let code = if state.objects.contains_key(&path) {
+                    200
+                } else {
+                    404
+                };
+                status(code).into_response()
+            }
+            "PUT" => {
+                let (collection, _) = path.rsplit_once('/').unwrap();
+                if !state.collections.contains(&format!("{collection}/")) {
+                    return status(409).into_response();
+                }
+                state.objects.insert(path, body.to_vec());
+                status(201).into_response()
+            }
+            "DELETE" => {
+                let code = if state.objects.remove(&path).is_some() {
+                    204
+                } else {
+                    404
+                };
+                status(code).into_response()
+            }
+            "MKCOL" => {
+                let code = if state.collections.insert(path) {
+                    201
+                } else {
+                    405
+                };
+                status(code).into_response()
+            }
+            "PROPFIND" => {
+                assert_eq!(headers["Depth"], "1");
+                if !state.collections.contains(&path) {
+                    return status(404).into_response();
+                }
+                let mut xml = r#"<?xml version="1.0"?><D:multistatus xmlns:D="DAV:">"#.to_owned();
+                let hrefs = state
+                    .objects
+                    .keys()
+                    .filter(|object_path| object_path.starts_with(&path));
+                for href in std::iter::once(&path).chain(hrefs) {
+                    xml += &format!(
+                        "<D:response><D:href>{href}</D:href><D:propstat><D:prop><D:resourcetype/></D:prop></D:propstat></D:response>"
+                    );
+                }
+                xml += "</D:multistatus>";
+                (status(207), xml).into_response()
+            }
+            _ => status(405).into_response(),
+        }
+    }
+
+    async fn start_server() -> String {
+        let state = SharedState::default();
+        let app = Router::new().fallback(handle_request).with_state(state);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{local_addr}/store")
+    }
+
+    #[test]
+    fn extracting_xml_texts() {
+        let xml = r#"
+            <D:multistatus xmlns:D="DAV:">
+                <D:response><D:href>/store/a&amp;b</D:href></D:response>
+                <D:response><D:href>/store/c</D:href><D:propstat><D:href>ignored</D:href></D:propstat></D:response>
+            </D:multistatus>
+        "#;
+        let texts = xml_element_texts(xml, "response", "href").unwrap();
+        assert_eq!(texts, ["/store/a&b", "/store/c"]);
+
+        let err = xml_element_texts("<a><b></a>", "a", "b").unwrap_err();
+        assert_matches!(
+            err,
+            ObjectStoreError::Other {
+                is_retriable: false,
+                ..
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn http_store_basics() {
+        let base_url = start_server().await;
+        let dir = TempDir::new().unwrap();
+        let token_path = dir.path().join("token");
+        tokio::fs::write(&token_path, format!("{AUTH_TOKEN}\n"))
+            .await
+            .unwrap();
+        let store = HttpObjectStore::new(&base_url, Some(&token_path))
+            .await
+            .unwrap();
+
+        let err = store
+            .get_raw(Bucket::ProofsFri, "proof_1.bin")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+        assert!(!store
+            .exists_raw(Bucket::ProofsFri, "proof_1.bin")
+            .await
+            .unwrap());
+        let keys = store.list_raw(Bucket::ProofsFri, "").await.unwrap();
+        assert!(keys.is_empty());
+
+        for (key, value) in [
+            ("proof_2.bin", vec![2]),
+            ("proof_1.bin", vec![1]),
+            ("other proof.bin", vec![3]),
+        ] {
+            store.put_raw(Bucket::ProofsFri, key, value).await.unwrap();
+        }
+        let object = store
+            .get_raw(Bucket::ProofsFri, "other proof.bin")
+            .await
+            .unwrap();
+        assert_eq!(object, [3]);
+        assert!(store
+            .exists_raw(Bucket::ProofsFri, "proof_1.bin")
+            .await
+            .unwrap());
+
+        let keys = store.list_raw(Bucket::ProofsFri, "").await.unwrap();
+        assert_eq!(keys, ["other proof.bin", "proof_1.bin", "proof_2.bin"]);
+        let keys = store.list_raw(Bucket::ProofsFri, "proof_").await.unwrap();
+        assert_eq!(keys, ["proof_1.bin", "proof_2.bin"]);
+
+        let removed_count = store
+            .remove_by_prefix_raw(Bucket::ProofsFri, "proof_")
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 2);
+        let keys = store.list_raw(Bucket::ProofsFri, "").await.unwrap();
+        assert_eq!(keys, ["other proof.bin"]);
+        let err = store
+            .remove_raw(Bucket::ProofsFri, "proof_1.bin")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+
+        assert_eq!(
+            store.storage_prefix_raw(Bucket::ProofsFri),
+            format!("{base_url}/proofs_fri")
+        );
+    }
+
+    #[tokio::test]
+    async fn http_store_times_out_on_stalled_server() {
+        let app = Router::new().fallback(|| std::future::pending::<AxumResponse>());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut store = HttpObjectStore::new(&format!("http://{local_addr}/store"), None)
+            .await
+            .unwrap();
+        store.client =
+            build_http_client(Duration::from_secs(1), Duration::from_millis(100)).unwrap();
+        let err = store
+            .get_raw(Bucket::ProofsFri, "proof_1.bin")
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            ObjectStoreError::Other {
+                is_retriable: true,
+                ..
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn http_store_without_auth() {
+        let base_url = start_server().await;
+        let store = HttpObjectStore::new(&base_url, None).await.unwrap();
+        let err = store
+            .get_raw(Bucket::ProofsFri, "proof_1.bin")
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            ObjectStoreError::Initialization {
+                is_retriable: false,
+                ..
+            }
+        );
+    }
+}
@@ -1,13 +1,16 @@
 //! This crate provides the [object storage abstraction](ObjectStore) that allows to get,
-//! put and remove binary blobs. The following implementations are available:
+//! put, list and remove binary blobs. The following implementations are available:
 //!
 //! - [File-backed store](FileBackedObjectStore) saving blobs as separate files in the local filesystem
 //! - [GCS-based store](GoogleCloudStore)
+//! - S3-based store, Azure Blob Storage-based store, and a store based on a generic HTTP server (e.g., WebDAV),
+//!   which are available via [`ObjectStoreFactory`]
 //! - [Mock in-memory store](MockObjectStore)
 //!
 //! Normally, these implementations are not used directly. Instead, a store trait object (`Arc<dyn ObjectStore>`)
 //! can be constructed using an [`ObjectStoreFactory`] based on the configuration.
-//! This trait object is what should be used for dependency injection.
+//! This trait object is what should be used for dependency injection. Depending on the configuration,
+//! the factory can wrap the store to transparently compress and / or encrypt stored objects.
 //!
 //! Besides the lower-level storage abstraction, the crate provides high-level
 //! typesafe `<dyn ObjectStore>::get()` and `<dyn ObjectStore>::put()` methods
@@ -23,9 +26,12 @@
     clippy::doc_markdown
 )]
 
+mod azure;
+mod encoding;
 mod factory;
 mod file;
 mod gcs;
+mod http;
 mod metrics;
 mod mirror;
 mod mock;
@@ -90,6 +90,55 @@ impl<S: ObjectStore> ObjectStore for MirroringObjectStore<S> {
         Ok(())
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        // The mirror may contain only a subset of objects, so it cannot be used for listing.
+        self.inner.list_raw(bucket, prefix).await
+    }
+
+    #[tracing::instrument(name = "MirroringObjectStore::exists_raw", skip(self))]
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        match self.mirror_store.exists_raw(bucket, key).await {
+            Ok(true) => {
+                tracing::trace!("found object in mirror");
+                return Ok(true);
+            }
+            Ok(false) => { /* fall through to the underlying store */ }
+            Err(err) => {
+                tracing::warn!(
+                    "unexpected error calling local mirror store: {:#}",
+                    anyhow::Error::from(err)
+                );
+            }
+        }
+        self.inner.exists_raw(bucket, key).await
+    }
+
+    #[tracing::instrument(name = "MirroringObjectStore::remove_by_prefix_raw", skip(self))]
+    async fn remove_by_prefix_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<usize, ObjectStoreError> {
+        let removed_count = self.inner.remove_by_prefix_raw(bucket, prefix).await?;
+        // Only remove values from the mirror once they have been removed in the underlying store
+        match self.mirror_store.remove_by_prefix_raw(bucket, prefix).await {
+            Ok(mirror_removed_count) => {
+                tracing::trace!("removed {mirror_removed_count} objects from mirror");
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "failed removing objects from mirror: {:#}",
+                    anyhow::Error::from(err)
+                );
+            }
+        }
+        Ok(removed_count)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
@@ -155,5 +204,37 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(object, [3, 2, 1]);
+
+        let keys = mirroring_store
+            .list_raw(Bucket::StorageSnapshot, "")
+            .await
+            .unwrap();
+        assert_eq!(keys, ["other", "test"]);
+        assert!(mirroring_store
+            .exists_raw(Bucket::StorageSnapshot, "test")
+            .await
+            .unwrap());
+        assert!(!mirroring_store
+            .exists_raw(Bucket::StorageSnapshot, "missing")
+            .await
+            .unwrap());
+
+        let removed_count = mirroring_store
+            .remove_by_prefix_raw(Bucket::StorageSnapshot, "te")
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 1);
+        let keys = mirroring_store
+            .list_raw(Bucket::StorageSnapshot, "")
+            .await
+            .unwrap();
+        assert_eq!(keys, ["other"]);
+        // Check that the object got removed from the mirror.
+        let keys_in_mirror = mirroring_store
+            .mirror_store
+            .list_raw(Bucket::StorageSnapshot, "")
+            .await
+            .unwrap();
+        assert_eq!(keys_in_mirror, ["other"]);
     }
 }
@@ -54,6 +54,45 @@ impl ObjectStore for MockObjectStore {
         Ok(())
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let lock = self.inner.lock().await;
+        let Some(bucket_map) = lock.get(&bucket) else {
+            return Ok(vec![]);
+        };
+        let mut keys: Vec<_> = bucket_map
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect();
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        let lock = self.inner.lock().await;
+        Ok(lock
+            .get(&bucket)
+            .is_some_and(|bucket_map| bucket_map.contains_key(key)))
+    }
+
+    async fn remove_by_prefix_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<usize, ObjectStoreError> {
+        let mut lock = self.inner.lock().await;
+        let Some(bucket_map) = lock.get_mut(&bucket) else {
+            return Ok(0);
+        };
+        let prev_len = bucket_map.len();
+        bucket_map.retain(|key, _| !key.starts_with(prefix));
+        Ok(prev_len - bucket_map.len())
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         bucket.to_string()
     }
@@ -215,6 +215,24 @@ impl dyn ObjectStore + '_ {
         self.remove_raw(V::BUCKET, &key).await
     }
 
+    /// Checks whether a value associated with the key exists. Unlike [`Self::get()`],
+    /// does not take [`StoredObject::fallback_key()`] into account.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors specific to the storage.
+    #[tracing::instrument(
+        name = "ObjectStore::exists",
+        skip_all,
+        fields(key) // Will be recorded within the function.
+    )]
+    pub async fn exists<V: StoredObject>(&self, key: V::Key<'_>) -> Result<bool, ObjectStoreError> {
+        let key = V::encode_key(key);
+        // Record the key for tracing.
+        tracing::Span::current().record("key", key.as_str());
+        self.exists_raw(V::BUCKET, &key).await
+    }
+
     pub fn get_storage_prefix<V: StoredObject>(&self) -> String {
         self.storage_prefix_raw(V::BUCKET)
     }
@@ -307,4 +325,51 @@ mod tests {
         let reconstructed_factory_deps = store.get(key).await.unwrap();
         assert_eq!(factory_deps, reconstructed_factory_deps);
     }
+
+    #[tokio::test]
+    async fn listing_and_removing_objects_by_prefix() {
+        let store = MockObjectStore::arc();
+        let factory_deps = SnapshotFactoryDependencies {
+            factory_deps: vec![],
+        };
+        for l1_batch_number in [1, 2, 10] {
+            store
+                .put(L1BatchNumber(l1_batch_number), &factory_deps)
+                .await
+                .unwrap();
+        }
+        assert!(store
+            .exists::<SnapshotFactoryDependencies>(L1BatchNumber(2))
+            .await
+            .unwrap());
+        assert!(!store
+            .exists::<SnapshotFactoryDependencies>(L1BatchNumber(3))
+            .await
+            .unwrap());
+
+        let keys = store
+            .list_raw(Bucket::StorageSnapshot, "snapshot_l1_batch_1")
+            .await
+            .unwrap();
+        assert_eq!(
+            keys,
+            [
+                "snapshot_l1_batch_10_factory_deps.proto.gzip",
+                "snapshot_l1_batch_1_factory_deps.proto.gzip",
+            ]
+        );
+
+        let removed_count = store
+            .remove_by_prefix_raw(Bucket::StorageSnapshot, "snapshot_l1_batch_1")
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 2);
+        let keys = store.list_raw(Bucket::StorageSnapshot, "").await.unwrap();
+        assert_eq!(keys, ["snapshot_l1_batch_2_factory_deps.proto.gzip"]);
+        let removed_count = store
+            .remove_by_prefix_raw(Bucket::ProofsFri, "")
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 0);
+    }
 }
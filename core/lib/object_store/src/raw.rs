@@ -24,6 +24,29 @@ pub enum Bucket {
 }
 
 impl Bucket {
+    const ALL: [Self; 15] = [
+        Self::ProverJobs,
+        Self::WitnessInput,
+        Self::LeafAggregationWitnessJobs,
+        Self::NodeAggregationWitnessJobs,
+        Self::SchedulerWitnessJobs,
+        Self::ProverJobsFri,
+        Self::LeafAggregationWitnessJobsFri,
+        Self::NodeAggregationWitnessJobsFri,
+        Self::SchedulerWitnessJobsFri,
+        Self::ProofsFri,
+        Self::ProofsTee,
+        Self::StorageSnapshot,
+        Self::DataAvailability,
+        Self::VmDumps,
+        Self::PublicWitnessInputs,
+    ];
+
+    /// Parses a bucket from its [name](Self::as_str()).
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|bucket| bucket.as_str() == name)
+    }
+
     pub fn as_str(self) -> &'static str {
         match self {
             Self::ProverJobs => "prover_jobs",
@@ -159,5 +182,47 @@ pub trait ObjectStore: 'static + fmt::Debug + Send + Sync {
     /// Returns an error if removal fails.
     async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError>;
 
+    /// Lists keys in the given bucket starting with the specified `prefix`. An empty prefix lists all keys
+    /// in the bucket. Keys are returned relative to the bucket and are sorted lexicographically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bucket cannot be accessed.
+    async fn list_raw(&self, bucket: Bucket, prefix: &str)
+        -> Result<Vec<String>, ObjectStoreError>;
+
+    /// Checks whether an object with the given key exists in the bucket without fetching it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the existence check fails. A missing object is *not* an error.
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError>;
+
+    /// Removes all objects in the given bucket with keys starting with the specified `prefix`.
+    /// Returns the number of removed objects.
+    ///
+    /// The default implementation lists keys using [`Self::list_raw()`] and removes them one by one
+    /// using [`Self::remove_raw()`]. Objects removed concurrently with this call are not counted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if listing or removing objects fails. In this case, some objects may be already removed.
+    async fn remove_by_prefix_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<usize, ObjectStoreError> {
+        let keys = self.list_raw(bucket, prefix).await?;
+        let mut removed_count = 0;
+        for key in &keys {
+            match self.remove_raw(bucket, key).await {
+                Ok(()) => removed_count += 1,
+                Err(ObjectStoreError::KeyNotFound(_)) => { /* Removed concurrently; skip */ }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(removed_count)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String;
 }
@@ -16,6 +16,8 @@ enum Request<'a> {
     Get(Bucket, &'a str),
     Put(Bucket, &'a str),
     Remove(Bucket, &'a str),
+    List(Bucket, &'a str),
+    Exists(Bucket, &'a str),
 }
 
 impl Request<'_> {
@@ -126,6 +128,29 @@ impl<S: ObjectStore> ObjectStore for StoreWithRetries<S> {
             .await
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        Request::List(bucket, prefix)
+            .retry(&self.inner, self.max_retries, || {
+                self.inner.list_raw(bucket, prefix)
+            })
+            .await
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        Request::Exists(bucket, key)
+            .retry(&self.inner, self.max_retries, || {
+                self.inner.exists_raw(bucket, key)
+            })
+            .await
+    }
+
+    // `remove_by_prefix_raw()` is intentionally not overridden; the default implementation retries
+    // listing and removing each object separately, so that a transient error doesn't restart the entire operation.
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
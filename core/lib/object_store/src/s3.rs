@@ -215,6 +215,66 @@ impl ObjectStore for S3Store {
         Ok(())
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let full_prefix = Self::filename(bucket.as_str(), prefix);
+        tracing::trace!(
+            "Listing objects in S3 with prefix {full_prefix} from bucket {}",
+            self.bucket_prefix
+        );
+
+        let bucket_prefix = Self::filename(bucket.as_str(), "");
+        let mut keys = vec![];
+        let mut continuation_token = None;
+        loop {
+            let output = self
+                .client
+                .list_objects_v2()
+                .bucket(self.bucket_prefix.clone())
+                .prefix(full_prefix.clone())
+                .set_continuation_token(continuation_token)
+                .send()
+                .await?;
+            keys.extend(output.contents().iter().filter_map(|object| {
+                let key = object.key()?.strip_prefix(&bucket_prefix)?;
+                Some(key.to_owned())
+            }));
+
+            continuation_token = output.next_continuation_token().map(str::to_owned);
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Checking existence of key {filename} in S3 from bucket {}",
+            self.bucket_prefix
+        );
+
+        let result = self
+            .client
+            .head_object()
+            .bucket(self.bucket_prefix.clone())
+            .key(filename)
+            .send()
+            .await;
+        match result {
+            Ok(_) => Ok(true),
+            Err(err) => match ObjectStoreError::from(err) {
+                ObjectStoreError::KeyNotFound(_) => Ok(false),
+                err => Err(err),
+            },
+        }
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!(
             "{}/{}/{}",
@@ -1,8 +1,8 @@
 //! Shared API types used in dependency injection.
 
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use zksync_node_framework::Resource;
 use zksync_types::{api, Address};
 
@@ -46,3 +46,52 @@ impl Resource for BridgeAddressesHandle {
         "api/bridge_addresses".into()
     }
 }
+
+/// Broadcast channel for transaction preconfirmations. Preconfirmations are published by the state keeper
+/// and streamed to clients by the API server.
+#[derive(Debug, Clone)]
+pub struct TxPreconfirmations {
+    sender: broadcast::Sender<api::SignedTransactionPreconfirmation>,
+    signer: Arc<OnceLock<Address>>,
+}
+
+impl Default for TxPreconfirmations {
+    fn default() -> Self {
+        const CHANNEL_CAPACITY: usize = 1_024;
+
+        Self {
+            sender: broadcast::channel(CHANNEL_CAPACITY).0,
+            signer: Arc::default(),
+        }
+    }
+}
+
+impl TxPreconfirmations {
+    /// Marks preconfirmations as issued by the specified signer. Should be called by the publisher
+    /// once during node initialization.
+    pub fn set_signer(&self, signer: Address) {
+        if self.signer.set(signer).is_err() {
+            tracing::warn!("Preconfirmations signer is already set");
+        }
+    }
+
+    /// Returns the address of the preconfirmations signer, or `None` if preconfirmations are not issued by the node.
+    pub fn signer(&self) -> Option<Address> {
+        self.signer.get().copied()
+    }
+
+    pub fn publish(&self, preconfirmation: api::SignedTransactionPreconfirmation) {
+        // An error means that there are no subscribers, which is fine.
+        self.sender.send(preconfirmation).ok();
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<api::SignedTransactionPreconfirmation> {
+        self.sender.subscribe()
+    }
+}
+
+impl Resource for TxPreconfirmations {
+    fn name() -> String {
+        "api/tx_preconfirmations".into()
+    }
+}
@@ -162,6 +162,18 @@ impl ObjectStore for ObjectStoreWithErrors {
         unreachable!("Should not be used in snapshot applier")
     }
 
+    async fn list_raw(
+        &self,
+        _bucket: Bucket,
+        _prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        unreachable!("Should not be used in snapshot applier")
+    }
+
+    async fn exists_raw(&self, _bucket: Bucket, _key: &str) -> Result<bool, ObjectStoreError> {
+        unreachable!("Should not be used in snapshot applier")
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
@@ -433,6 +445,18 @@ impl ObjectStore for HangingObjectStore {
         unreachable!("Should not be used in snapshot applier")
     }
 
+    async fn list_raw(
+        &self,
+        _bucket: Bucket,
+        _prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        unreachable!("Should not be used in snapshot applier")
+    }
+
+    async fn exists_raw(&self, _bucket: Bucket, _key: &str) -> Result<bool, ObjectStoreError> {
+        unreachable!("Should not be used in snapshot applier")
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
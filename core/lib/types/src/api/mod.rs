@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use anyhow::Context;
 use chrono::{DateTime, Utc};
 use derive_more::Display;
@@ -8,7 +10,7 @@ use zksync_basic_types::{
     commitment::PubdataType,
     settlement::SettlementLayer,
     web3::{AccessList, Bytes, Index},
-    Bloom, L1BatchNumber, SLChainId, H160, H256, H64, U256, U64,
+    Bloom, L1BatchNumber, L2ChainId, SLChainId, H160, H256, H64, U256, U64,
 };
 use zksync_contracts::BaseSystemContractsHashes;
 
@@ -21,11 +23,15 @@ use crate::{
     protocol_version::L1VerifierConfig,
     server_notification::{GatewayMigrationNotification, GatewayMigrationState},
     tee_types::TeeType,
-    Address, L2BlockNumber, ProtocolVersionId,
+    Address, EIP712TypedStructure, Eip712Domain, K256PrivateKey, L2BlockNumber, PackedEthSignature,
+    ProtocolVersionId, StructBuilder,
 };
 
 pub mod en;
+pub mod profile;
+pub mod simulate;
 pub mod state_override;
+pub mod trace;
 
 /// Block Number
 #[derive(Copy, Clone, Debug, PartialEq, Display)]
@@ -881,12 +887,45 @@ impl TryFrom<ProtocolVersion> for ProtocolVersionInfo {
 pub enum SupportedTracers {
     CallTracer,
     FlatCallTracer,
+    PrestateTracer,
+    /// Opcode-level tracer similar to the default struct logger in Geth. Only supported by the fast VM.
+    ///
+    /// Logs are recorded for EraVM instructions. EVM contracts are executed by the EVM emulator, and the tracer
+    /// does not reconstruct EVM execution steps. Instead, it only records emulator instructions observable
+    /// on the EVM level, which has the following limitations:
+    ///
+    /// - Only `SLOAD`, `SSTORE`, `TLOAD`, `TSTORE`, `LOG`, `CALL`, `DELEGATECALL`, `RETURN`, `REVERT`
+    ///   and `INVALID` ops are reported. Other EVM opcodes (arithmetic, stack and memory manipulation, etc.)
+    ///   are not reported at all.
+    /// - `STATICCALL` is reported as `CALL`, and `CREATE` / `CREATE2` as a `CALL` to the contract deployer.
+    /// - Events are reported as `LOG` without the number of topics; a single EVM event may produce
+    ///   several `LOG` entries.
+    /// - `pc` refers to the emulator bytecode rather than the EVM bytecode.
+    /// - `gas` and `gasCost` are measured in EraVM gas (ergs) rather than EVM gas.
+    /// - `stack` contains EraVM registers of the emulator rather than the EVM stack.
+    StructLogger,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, Copy)]
 #[serde(rename_all = "camelCase")]
 pub struct CallTracerConfig {
     pub only_top_call: bool,
+    /// Only used by the prestate tracer. If set, the tracer returns the state of modified accounts before and after
+    /// the transaction, rather than the state of all touched accounts.
+    #[serde(default)]
+    pub diff_mode: bool,
+    /// Only used by the struct logger. If set, the heap of the current frame is returned for each log entry.
+    #[serde(default)]
+    pub enable_memory: bool,
+    /// Only used by the struct logger. If set, the stack (for EraVM, general-purpose registers) is not returned.
+    #[serde(default)]
+    pub disable_stack: bool,
+    /// Only used by the struct logger. If set, accessed storage slots are not returned.
+    #[serde(default)]
+    pub disable_storage: bool,
+    /// Only used by the struct logger. Maximum number of returned log entries; unlimited if not set.
+    #[serde(default)]
+    pub limit: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
@@ -901,9 +940,7 @@ impl Default for TracerConfig {
     fn default() -> Self {
         TracerConfig {
             tracer: SupportedTracers::CallTracer,
-            tracer_config: CallTracerConfig {
-                only_top_call: false,
-            },
+            tracer_config: CallTracerConfig::default(),
         }
     }
 }
@@ -923,20 +960,36 @@ pub enum BlockStatus {
 pub enum CallTracerBlockResult {
     CallTrace(Vec<ResultDebugCall>),
     FlatCallTrace(Vec<ResultDebugCallFlat>),
+    PrestateTrace(Vec<ResultDebugPrestate>),
+    StructLogTrace(Vec<ResultDebugStructLog>),
 }
 
 impl CallTracerBlockResult {
     pub fn unwrap_flat(self) -> Vec<ResultDebugCallFlat> {
         match self {
-            Self::CallTrace(_) => panic!("Result is a FlatCallTrace"),
             Self::FlatCallTrace(trace) => trace,
+            _ => panic!("Result is not a FlatCallTrace"),
         }
     }
 
     pub fn unwrap_default(self) -> Vec<ResultDebugCall> {
         match self {
             Self::CallTrace(trace) => trace,
-            Self::FlatCallTrace(_) => panic!("Result is a CallTrace"),
+            _ => panic!("Result is not a CallTrace"),
+        }
+    }
+
+    pub fn unwrap_prestate(self) -> Vec<ResultDebugPrestate> {
+        match self {
+            Self::PrestateTrace(trace) => trace,
+            _ => panic!("Result is not a PrestateTrace"),
+        }
+    }
+
+    pub fn unwrap_struct_logs(self) -> Vec<ResultDebugStructLog> {
+        match self {
+            Self::StructLogTrace(trace) => trace,
+            _ => panic!("Result is not a StructLogTrace"),
         }
     }
 }
@@ -947,24 +1000,129 @@ impl CallTracerBlockResult {
 pub enum CallTracerResult {
     CallTrace(DebugCall),
     FlatCallTrace(Vec<DebugCallFlat>),
+    PrestateTrace(PrestateTrace),
+    StructLogTrace(StructLogTrace),
 }
 
 impl CallTracerResult {
     pub fn unwrap_flat(self) -> Vec<DebugCallFlat> {
         match self {
-            Self::CallTrace(_) => panic!("Result is a FlatCallTrace"),
             Self::FlatCallTrace(trace) => trace,
+            _ => panic!("Result is not a FlatCallTrace"),
         }
     }
 
     pub fn unwrap_default(self) -> DebugCall {
         match self {
             Self::CallTrace(trace) => trace,
-            Self::FlatCallTrace(_) => panic!("Result is a CallTrace"),
+            _ => panic!("Result is not a CallTrace"),
+        }
+    }
+
+    pub fn unwrap_prestate(self) -> PrestateTrace {
+        match self {
+            Self::PrestateTrace(trace) => trace,
+            _ => panic!("Result is not a PrestateTrace"),
+        }
+    }
+
+    pub fn unwrap_struct_logs(self) -> StructLogTrace {
+        match self {
+            Self::StructLogTrace(trace) => trace,
+            _ => panic!("Result is not a StructLogTrace"),
         }
     }
 }
 
+/// Account state returned by the prestate tracer.
+///
+/// Unlike on Ethereum, balances, nonces and bytecodes are stored in the storage of system contracts
+/// (`L2BaseToken`, `NonceHolder` and `AccountCodeStorage` respectively), so the account set consists of contracts
+/// with touched storage slots.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// Transaction nonce of the account (i.e., excluding the deployment nonce).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    /// Bytecode of the account. Like for `eth_getCode`, EVM bytecodes are returned without padding.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// Output of the prestate tracer for a single transaction. Has the same shape as the output
+/// of the Geth `prestateTracer`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+    /// Output in the diff mode.
+    Diff {
+        pre: BTreeMap<Address, PrestateAccount>,
+        post: BTreeMap<Address, PrestateAccount>,
+    },
+    /// Output in the default mode.
+    Prestate(BTreeMap<Address, PrestateAccount>),
+}
+
+/// Prestate trace for a transaction in a block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultDebugPrestate {
+    pub tx_hash: H256,
+    pub result: PrestateTrace,
+}
+
+/// Log entry for a single VM instruction returned by the struct logger.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    /// Program counter in the executed EraVM bytecode. For EVM contracts, refers to the EVM emulator bytecode.
+    pub pc: u64,
+    /// Opcode name. See [`SupportedTracers::StructLogger`] for the opcodes reported for EVM contracts.
+    pub op: String,
+    /// Remaining EraVM gas (ergs) before the instruction.
+    pub gas: u64,
+    /// EraVM gas (ergs) spent by the instruction.
+    pub gas_cost: u64,
+    pub depth: usize,
+    /// Values of general-purpose registers `r1..=r15`. For EVM contracts, these are registers of the EVM emulator
+    /// rather than the EVM stack.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Vec<U256>>,
+    /// 32-byte words of the heap of the current frame.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Vec<H256>>,
+    /// Storage slots of the current contract accessed so far.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<H256, H256>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Output of the struct logger for a single transaction. Has the same shape as the output
+/// of the default Geth struct logger.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogTrace {
+    /// Gas used by the transaction.
+    pub gas: u64,
+    pub failed: bool,
+    pub return_value: Bytes,
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// Struct log trace for a transaction in a block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultDebugStructLog {
+    pub tx_hash: H256,
+    pub result: StructLogTrace,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockDetailsBase {
@@ -1017,6 +1175,30 @@ pub struct L1BatchDetails {
     pub base: BlockDetailsBase,
 }
 
+/// Lifecycle stage of an L1 batch on the settlement layer.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum L1BatchStage {
+    Committed,
+    Proven,
+    Executed,
+}
+
+/// L1 batch status transition pushed to `l1BatchStatus` subscribers. Emitted each time a settlement layer
+/// transaction for the batch is confirmed, including finality upgrades of an already confirmed transaction.
+/// Only the latest transition of a batch is reported per poll, so intermediate stages (e.g., `committed`
+/// if the batch was committed and proven between polls) may be skipped.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchStatusUpdate {
+    pub l1_batch_number: L1BatchNumber,
+    pub stage: L1BatchStage,
+    pub tx_hash: H256,
+    pub finality: EthTxFinalityStatus,
+    pub chain_id: Option<SLChainId>,
+    pub confirmed_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StorageProof {
@@ -1033,6 +1215,31 @@ pub struct Proof {
     pub storage_proof: Vec<StorageProof>,
 }
 
+/// Adaptation of the EIP-1186 account proof returned by `eth_getProof`.
+///
+/// ZKsync has a single Merkle tree for all accounts, which is updated once per L1 batch. Thus, all proofs are anchored
+/// to the tree root hash after the L1 batch containing the requested block (i.e., reflect the state at the end
+/// of this batch). Account fields are stored in system contracts; their proofs are provided in `account_proof`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthProof {
+    pub address: Address,
+    /// Base token balance of the account.
+    pub balance: U256,
+    /// Versioned bytecode hash of the account stored in `AccountCodeStorage`; zero for accounts without code.
+    pub code_hash: H256,
+    /// Transaction nonce of the account stored in `NonceHolder`.
+    pub nonce: U256,
+    /// Root hash of the Merkle tree after [`Self::l1_batch_number`]. All proofs are relative to this hash.
+    pub storage_hash: H256,
+    /// Proofs for the account code hash, full nonce and balance slots (in this order) in the corresponding system contracts.
+    pub account_proof: Vec<StorageProof>,
+    /// Proofs for the requested storage slots of the account.
+    pub storage_proof: Vec<StorageProof>,
+    /// L1 batch the proofs are anchored to.
+    pub l1_batch_number: L1BatchNumber,
+}
+
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -1057,6 +1264,80 @@ pub struct TransactionDetailedResult {
     pub transaction_hash: H256,
     pub storage_logs: Vec<ApiStorageLog>,
     pub events: Vec<Log>,
+    /// Preconfirmation issued by the sequencer for the transaction. Only present if the node issues
+    /// preconfirmations, and the transaction was included into an L2 block soon enough.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preconfirmation: Option<SignedTransactionPreconfirmation>,
+}
+
+/// Soft confirmation by the sequencer that a transaction was executed and included into the open
+/// (i.e., not yet sealed) L2 block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionPreconfirmation {
+    pub transaction_hash: H256,
+    pub l2_block_number: U64,
+    pub l2_block_timestamp: U64,
+    pub l1_batch_number: U64,
+    /// Index of the transaction in the L2 block.
+    pub transaction_index: U64,
+    /// Status of the transaction execution: 1 if succeeded, 0 if reverted.
+    pub status: U64,
+}
+
+impl TransactionPreconfirmation {
+    /// Returns the digest signed by the sequencer. The digest is the [EIP-712](https://eips.ethereum.org/EIPS/eip-712)
+    /// hash of the preconfirmation in the standard ZKsync domain for the specified chain, so that a preconfirmation
+    /// signature cannot be confused with a signature of any other message (e.g., a transaction).
+    pub fn signed_digest(&self, chain_id: L2ChainId) -> H256 {
+        PackedEthSignature::typed_data_to_signed_bytes(&Eip712Domain::new(chain_id), self)
+    }
+}
+
+impl EIP712TypedStructure for TransactionPreconfirmation {
+    const TYPE_NAME: &'static str = "TransactionPreconfirmation";
+
+    fn build_structure<BUILDER: StructBuilder>(&self, builder: &mut BUILDER) {
+        builder.add_member("transactionHash", &self.transaction_hash);
+        builder.add_member("l2BlockNumber", &self.l2_block_number.as_u64());
+        builder.add_member("l2BlockTimestamp", &self.l2_block_timestamp.as_u64());
+        builder.add_member("l1BatchNumber", &self.l1_batch_number.as_u64());
+        builder.add_member("transactionIndex", &self.transaction_index.as_u64());
+        builder.add_member("status", &self.status.as_u64());
+    }
+}
+
+/// [`TransactionPreconfirmation`] signed by the sequencer key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedTransactionPreconfirmation {
+    #[serde(flatten)]
+    pub preconfirmation: TransactionPreconfirmation,
+    pub signature: PackedEthSignature,
+}
+
+impl SignedTransactionPreconfirmation {
+    pub fn new(
+        preconfirmation: TransactionPreconfirmation,
+        signing_key: &K256PrivateKey,
+        chain_id: L2ChainId,
+    ) -> anyhow::Result<Self> {
+        let digest = preconfirmation.signed_digest(chain_id);
+        let signature = PackedEthSignature::sign_raw(signing_key, &digest)
+            .context("failed signing preconfirmation")?;
+        Ok(Self {
+            preconfirmation,
+            signature,
+        })
+    }
+
+    /// Recovers the address of the preconfirmation signer. Clients should compare it to the known sequencer address.
+    pub fn recover_signer(&self, chain_id: L2ChainId) -> anyhow::Result<Address> {
+        let digest = self.preconfirmation.signed_digest(chain_id);
+        self.signature
+            .signature_recover_signer(&digest)
+            .context("invalid preconfirmation signature")
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -1086,6 +1367,20 @@ pub struct FeeHistory {
     pub l2_pubdata_price: Vec<U256>,
 }
 
+/// The result of the `eth_createAccessList` call.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+    /// Storage slots accessed by the call, grouped by the contract address.
+    pub access_list: AccessList,
+    /// Estimated gas for the call with the access list applied. If the call has failed, this is the gas used
+    /// by the failed call.
+    pub gas_used: U256,
+    /// Error message if the call has failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// The data availability details type. Used exclusively in Validiums.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -1180,4 +1475,155 @@ mod tests {
         let block_number = BlockNumber::Number(U64::from(42));
         assert_eq!(format!("{}", block_number), "42");
     }
+
+    #[test]
+    fn prestate_tracer_config_and_output_serialization() {
+        let config: TracerConfig = serde_json::from_value(serde_json::json!({
+            "tracer": "prestateTracer",
+            "tracerConfig": { "diffMode": true },
+        }))
+        .unwrap();
+        assert!(matches!(config.tracer, SupportedTracers::PrestateTracer));
+        assert!(config.tracer_config.diff_mode);
+        assert!(!config.tracer_config.only_top_call);
+
+        let account = PrestateAccount {
+            balance: Some(U256::from(1)),
+            nonce: Some(2),
+            code: Some(Bytes(vec![0x60, 0x80])),
+            storage: Some(BTreeMap::from([(H256::zero(), H256::repeat_byte(1))])),
+        };
+        let address = Address::repeat_byte(0x11);
+        let trace = PrestateTrace::Diff {
+            pre: BTreeMap::from([(address, account.clone())]),
+            post: BTreeMap::new(),
+        };
+        let json = serde_json::to_value(CallTracerResult::PrestateTrace(trace.clone())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "pre": {
+                    "0x1111111111111111111111111111111111111111": {
+                        "balance": "0x1",
+                        "nonce": 2,
+                        "code": "0x6080",
+                        "storage": {
+                            "0x0000000000000000000000000000000000000000000000000000000000000000":
+                                "0x0101010101010101010101010101010101010101010101010101010101010101",
+                        },
+                    },
+                },
+                "post": {},
+            })
+        );
+        let restored: CallTracerResult = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.unwrap_prestate(), trace);
+
+        let trace = PrestateTrace::Prestate(BTreeMap::from([(address, account)]));
+        let json = serde_json::to_value(&trace).unwrap();
+        let restored: PrestateTrace = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, trace);
+    }
+
+    #[test]
+    fn struct_logger_config_and_output_serialization() {
+        let config: TracerConfig = serde_json::from_value(serde_json::json!({
+            "tracer": "structLogger",
+            "tracerConfig": { "enableMemory": true, "limit": 10 },
+        }))
+        .unwrap();
+        assert!(matches!(config.tracer, SupportedTracers::StructLogger));
+        assert!(config.tracer_config.enable_memory);
+        assert!(!config.tracer_config.disable_stack);
+        assert!(!config.tracer_config.disable_storage);
+        assert_eq!(config.tracer_config.limit, Some(10));
+
+        let trace = StructLogTrace {
+            gas: 21_000,
+            failed: false,
+            return_value: Bytes(vec![1]),
+            struct_logs: vec![StructLog {
+                pc: 3,
+                op: "StorageRead".to_owned(),
+                gas: 1_000,
+                gas_cost: 5,
+                depth: 1,
+                stack: Some(vec![U256::from(1)]),
+                memory: None,
+                storage: Some(BTreeMap::from([(H256::zero(), H256::repeat_byte(1))])),
+                error: None,
+            }],
+        };
+        let json = serde_json::to_value(CallTracerResult::StructLogTrace(trace.clone())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "gas": 21_000,
+                "failed": false,
+                "returnValue": "0x01",
+                "structLogs": [{
+                    "pc": 3,
+                    "op": "StorageRead",
+                    "gas": 1_000,
+                    "gasCost": 5,
+                    "depth": 1,
+                    "stack": ["0x1"],
+                    "storage": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000000":
+                            "0x0101010101010101010101010101010101010101010101010101010101010101",
+                    },
+                }],
+            })
+        );
+        let restored: CallTracerResult = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.unwrap_struct_logs(), trace);
+    }
+
+    #[test]
+    fn signing_transaction_preconfirmation() {
+        let signing_key = K256PrivateKey::from_bytes(H256::repeat_byte(1)).unwrap();
+        let chain_id = L2ChainId::from(270);
+        let preconfirmation = TransactionPreconfirmation {
+            transaction_hash: H256::repeat_byte(2),
+            l2_block_number: 10.into(),
+            l2_block_timestamp: 1_000.into(),
+            l1_batch_number: 3.into(),
+            transaction_index: 1.into(),
+            status: 1.into(),
+        };
+        assert_eq!(
+            preconfirmation.encode_type(),
+            "TransactionPreconfirmation(bytes32 transactionHash,uint64 l2BlockNumber,uint64 l2BlockTimestamp,\
+             uint64 l1BatchNumber,uint64 transactionIndex,uint64 status)"
+        );
+        let signed =
+            SignedTransactionPreconfirmation::new(preconfirmation, &signing_key, chain_id).unwrap();
+        // The signature must be bound to the domain; it's not valid for the bare struct hash.
+        let struct_hash = signed.preconfirmation.hash_struct();
+        assert_ne!(
+            signed
+                .signature
+                .signature_recover_signer(&struct_hash)
+                .unwrap(),
+            signing_key.address()
+        );
+        assert_eq!(
+            signed.recover_signer(chain_id).unwrap(),
+            signing_key.address()
+        );
+        assert_ne!(
+            signed.recover_signer(L2ChainId::from(271)).unwrap(),
+            signing_key.address()
+        );
+
+        let json = serde_json::to_value(&signed).unwrap();
+        assert_eq!(
+            json["transactionHash"],
+            serde_json::json!(H256::repeat_byte(2))
+        );
+        assert_eq!(json["l2BlockNumber"], "0xa");
+        assert!(json["signature"].is_string(), "{json}");
+        let restored: SignedTransactionPreconfirmation = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, signed);
+    }
 }
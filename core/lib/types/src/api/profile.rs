@@ -0,0 +1,258 @@
+//! API types related to the `unstable_profileTransaction` method.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{web3::Bytes, Address, H256};
+
+/// Resources spent by a part of transaction execution.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfiledResources {
+    pub gas: u64,
+    /// Pubdata bytes. May be negative, e.g. if a storage slot is reset to its initial value.
+    pub pubdata: i64,
+    /// Number of main VM cycles.
+    pub cycles: u64,
+    /// Estimated number of circuits, summed across all circuit types.
+    pub circuits: f32,
+}
+
+/// Far call frame in a [`TransactionProfile`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfiledFrame {
+    /// Index of the parent frame in [`TransactionProfile::frames`], or `None` for top-level calls.
+    pub parent: Option<usize>,
+    pub address: Address,
+    /// Function selector, i.e. the first 4 bytes of the calldata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<Bytes>,
+    /// Resources spent by the frame, including subcalls.
+    pub total: ProfiledResources,
+    /// Resources spent by the frame itself, excluding subcalls.
+    pub own: ProfiledResources,
+}
+
+/// Storage write in a [`TransactionProfile`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfiledStorageWrite {
+    /// Index of the frame performing the write in [`TransactionProfile::frames`].
+    pub frame: usize,
+    pub address: Address,
+    pub key: H256,
+    pub resources: ProfiledResources,
+}
+
+/// Resources spent by a transaction in the [folded stacks] format, which can be fed to flamegraph tools directly.
+/// Each line has the form `tx;0x<address>:0x<selector>;...;SSTORE:0x<key> <value>`; lines with non-positive values
+/// are omitted. Resources not attributed to any call (e.g., spent by the bootloader) are reported on the `tx` line.
+///
+/// Since folded stacks only support integer values, circuits are reported in thousandths of a circuit.
+///
+/// [folded stacks]: https://github.com/brendangregg/FlameGraph#2-fold-stacks
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FoldedStacks {
+    pub gas: Vec<String>,
+    pub pubdata: Vec<String>,
+    pub cycles: Vec<String>,
+    /// Estimated circuits, in thousandths of a circuit.
+    pub circuits: Vec<String>,
+}
+
+impl FoldedStacks {
+    const ROOT: &'static str = "tx";
+    /// Scale applied to circuit estimates so that they can be represented as integers.
+    const CIRCUITS_SCALE: f64 = 1_000.0;
+
+    /// Folds stacks for the provided frames and storage writes. `total` are resources spent by the entire transaction.
+    pub fn new(
+        total: &ProfiledResources,
+        frames: &[ProfiledFrame],
+        storage_writes: &[ProfiledStorageWrite],
+    ) -> Self {
+        let mut stacks = Vec::<String>::with_capacity(frames.len());
+        for frame in frames {
+            let parent_stack = frame.parent.map_or(Self::ROOT, |idx| stacks[idx].as_str());
+            let mut stack = format!("{parent_stack};{:?}", frame.address);
+            if let Some(selector) = &frame.selector {
+                stack += &format!(":0x{}", hex::encode(&selector.0));
+            }
+            stacks.push(stack);
+        }
+
+        let mut entries = BTreeMap::<&str, [i64; 4]>::new();
+        let mut write_entries = BTreeMap::<String, [i64; 4]>::new();
+        let add = |values: &mut [i64; 4], resources: &ProfiledResources, sign: i64| {
+            values[0] += sign * resources.gas as i64;
+            values[1] += sign * resources.pubdata;
+            values[2] += sign * resources.cycles as i64;
+            values[3] +=
+                sign * (f64::from(resources.circuits) * Self::CIRCUITS_SCALE).round() as i64;
+        };
+
+        let root = entries.entry(Self::ROOT).or_default();
+        add(root, total, 1);
+        for frame in frames.iter().filter(|frame| frame.parent.is_none()) {
+            add(root, &frame.total, -1);
+        }
+        for (frame, stack) in frames.iter().zip(&stacks) {
+            add(entries.entry(stack).or_default(), &frame.own, 1);
+        }
+        for write in storage_writes {
+            let stack = &stacks[write.frame];
+            // Storage writes are leaves of the frame performing the write, so they are excluded from its own resources.
+            add(entries.entry(stack).or_default(), &write.resources, -1);
+            let write_stack = format!("{stack};SSTORE:{:?}", write.key);
+            add(
+                write_entries.entry(write_stack).or_default(),
+                &write.resources,
+                1,
+            );
+        }
+
+        let mut this = Self::default();
+        let all_entries = entries.into_iter().chain(
+            write_entries
+                .iter()
+                .map(|(stack, values)| (stack.as_str(), *values)),
+        );
+        for (stack, [gas, pubdata, cycles, circuits]) in all_entries {
+            for (lines, value) in [
+                (&mut this.gas, gas),
+                (&mut this.pubdata, pubdata),
+                (&mut this.cycles, cycles),
+                (&mut this.circuits, circuits),
+            ] {
+                if value > 0 {
+                    lines.push(format!("{stack} {value}"));
+                }
+            }
+        }
+        this
+    }
+}
+
+/// Resource profile of a transaction returned by `unstable_profileTransaction`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionProfile {
+    pub transaction_hash: H256,
+    /// Resources spent by the entire transaction, including the bootloader overhead and before refunds.
+    pub total: ProfiledResources,
+    pub gas_refunded: u64,
+    /// Gas used by the transaction according to its receipt, i.e., as paid when the transaction was mined.
+    ///
+    /// All other resources are measured by re-executing the transaction on top of the state preceding it,
+    /// in a batch context recreated from the L2 block data. The recreated context (e.g., the bootloader memory layout
+    /// or the pubdata already published in the batch) may differ from the original one, so gas and pubdata
+    /// in the profile may slightly diverge from what the mined transaction paid; `total.gas - gas_refunded`
+    /// can be compared with this value to estimate the divergence.
+    pub receipt_gas_used: u64,
+    /// Far calls made by the transaction in the order of their start.
+    pub frames: Vec<ProfiledFrame>,
+    /// Storage writes made by the transaction in the execution order.
+    pub storage_writes: Vec<ProfiledStorageWrite>,
+    pub folded_stacks: FoldedStacks,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resources(gas: u64, pubdata: i64, cycles: u64, circuits: f32) -> ProfiledResources {
+        ProfiledResources {
+            gas,
+            pubdata,
+            cycles,
+            circuits,
+        }
+    }
+
+    #[test]
+    fn folding_stacks() {
+        let account = Address::repeat_byte(1);
+        let token = Address::repeat_byte(2);
+        let frames = [
+            ProfiledFrame {
+                parent: None,
+                address: account,
+                selector: None,
+                total: resources(1_000, 100, 50, 0.5),
+                own: resources(400, 0, 20, 0.125),
+            },
+            ProfiledFrame {
+                parent: Some(0),
+                address: token,
+                selector: Some(vec![0xa9, 0x05, 0x9c, 0xbb].into()),
+                total: resources(600, 100, 30, 0.375),
+                own: resources(600, 100, 30, 0.375),
+            },
+        ];
+        let storage_writes = [ProfiledStorageWrite {
+            frame: 1,
+            address: token,
+            key: H256::repeat_byte(3),
+            resources: resources(500, 64, 1, 0.25),
+        }];
+        let total = resources(1_500, 120, 200, 0.75);
+
+        let stacks = FoldedStacks::new(&total, &frames, &storage_writes);
+        let account_stack = format!("tx;{account:?}");
+        let token_stack = format!("{account_stack};{token:?}:0xa9059cbb");
+        let write_stack = format!("{token_stack};SSTORE:{:?}", H256::repeat_byte(3));
+        assert_eq!(
+            stacks.gas,
+            [
+                "tx 500".to_owned(),
+                format!("{account_stack} 400"),
+                format!("{token_stack} 100"),
+                format!("{write_stack} 500"),
+            ]
+        );
+        assert_eq!(
+            stacks.pubdata,
+            [
+                "tx 20".to_owned(),
+                format!("{token_stack} 36"),
+                format!("{write_stack} 64"),
+            ]
+        );
+        assert_eq!(
+            stacks.cycles,
+            [
+                "tx 150".to_owned(),
+                format!("{account_stack} 20"),
+                format!("{token_stack} 29"),
+                format!("{write_stack} 1"),
+            ]
+        );
+        assert_eq!(
+            stacks.circuits,
+            [
+                "tx 250".to_owned(),
+                format!("{account_stack} 125"),
+                format!("{token_stack} 125"),
+                format!("{write_stack} 250"),
+            ]
+        );
+
+        let profile = TransactionProfile {
+            transaction_hash: H256::zero(),
+            total,
+            gas_refunded: 0,
+            receipt_gas_used: 1_500,
+            frames: frames.to_vec(),
+            storage_writes: storage_writes.to_vec(),
+            folded_stacks: stacks,
+        };
+        let json = serde_json::to_value(&profile).unwrap();
+        assert_eq!(json["frames"][1]["selector"], "0xa9059cbb");
+        assert_eq!(json["storageWrites"][0]["resources"]["pubdata"], 64);
+        assert_eq!(json["receiptGasUsed"], 1_500);
+        let restored: TransactionProfile = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, profile);
+    }
+}
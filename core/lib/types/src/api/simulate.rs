@@ -0,0 +1,149 @@
+//! API types related to the `eth_simulateV1` method.
+
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{web3::Bytes, U256, U64};
+
+use super::{state_override::StateOverride, Log};
+use crate::transaction_request::CallRequest;
+
+/// Payload of the `eth_simulateV1` method.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// Simulated blocks, executed sequentially.
+    pub block_state_calls: Vec<SimulatedBlockCalls>,
+    /// Whether to emit synthetic logs for native token transfers. Not supported.
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// Whether to perform full transaction validation (nonce, balance etc.). Not supported.
+    #[serde(default)]
+    pub validation: bool,
+    /// Whether to return full transactions in simulated blocks. Has no effect since simulated blocks
+    /// don't contain transactions.
+    #[serde(default)]
+    pub return_full_transactions: bool,
+}
+
+/// Calls in a single simulated block together with block-level overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlockCalls {
+    /// Overrides for the block environment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+    /// Overrides for the state applied before executing calls in the block. The state produced by previous calls
+    /// (including calls in previous blocks) is retained unless overridden.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// Calls executed in the block.
+    #[serde(default)]
+    pub calls: Vec<CallRequest>,
+}
+
+/// Overrides for the simulated block environment. Unknown overrides (e.g., `number`) are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BlockOverrides {
+    /// Block timestamp in seconds. Must be strictly increasing across simulated blocks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<U64>,
+    /// Base fee per gas enforced for all calls in the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+}
+
+/// Result of simulating a single block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    /// Block number as observed by the simulated calls.
+    pub number: U64,
+    /// Block timestamp as observed by the simulated calls.
+    pub timestamp: U64,
+    /// Base fee per gas used for the block.
+    pub base_fee_per_gas: U256,
+    /// Total gas used by all calls in the block.
+    pub gas_used: U256,
+    /// Results of calls in the block.
+    pub calls: Vec<SimulatedCallResult>,
+}
+
+/// Result of a single simulated call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCallResult {
+    /// Data returned by the call (empty if the call has failed).
+    pub return_data: Bytes,
+    /// Logs emitted by the call.
+    pub logs: Vec<Log>,
+    /// Gas used by the call.
+    pub gas_used: U256,
+    /// 1 if the call has succeeded, 0 otherwise.
+    pub status: U64,
+    /// Error if the call has failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulatedCallError>,
+}
+
+/// Error of a failed simulated call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedCallError {
+    /// Error code: 3 for reverted calls, -32015 for other VM errors.
+    pub code: i32,
+    /// Human-readable error message.
+    pub message: String,
+    /// Revert data, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+}
+
+impl SimulatedCallError {
+    /// Error code for reverted calls.
+    pub const REVERTED_CODE: i32 = 3;
+    /// Error code for VM errors other than reverts.
+    pub const VM_ERROR_CODE: i32 = -32015;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simulate_payload_deserialization() {
+        let payload: SimulatePayload = serde_json::from_value(serde_json::json!({
+            "blockStateCalls": [
+                {
+                    "blockOverrides": { "time": "0x64", "baseFeePerGas": "0x3b9aca00" },
+                    "stateOverrides": {
+                        "0x0101010101010101010101010101010101010101": { "balance": "0x1" },
+                    },
+                    "calls": [
+                        { "to": "0x0202020202020202020202020202020202020202", "data": "0x" },
+                    ],
+                },
+                {},
+            ],
+        }))
+        .unwrap();
+
+        assert_eq!(payload.block_state_calls.len(), 2);
+        let first_block = &payload.block_state_calls[0];
+        assert_eq!(
+            first_block.block_overrides,
+            Some(BlockOverrides {
+                time: Some(100.into()),
+                base_fee_per_gas: Some(1_000_000_000.into()),
+            })
+        );
+        assert!(first_block.state_overrides.is_some());
+        assert_eq!(first_block.calls.len(), 1);
+        let second_block = &payload.block_state_calls[1];
+        assert!(second_block.block_overrides.is_none());
+        assert!(second_block.calls.is_empty());
+        assert!(!payload.trace_transfers && !payload.validation);
+
+        let err = serde_json::from_value::<BlockOverrides>(serde_json::json!({ "number": "0x1" }))
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown field"), "{err}");
+    }
+}
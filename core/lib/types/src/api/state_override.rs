@@ -29,6 +29,11 @@ impl StateOverride {
         self.0.get_mut(address)
     }
 
+    /// Gets mutable overrides for the specified account, inserting default (i.e., empty) overrides if necessary.
+    pub fn entry(&mut self, address: Address) -> &mut OverrideAccount {
+        self.0.entry(address).or_default()
+    }
+
     /// Iterates over all account overrides.
     pub fn iter(&self) -> impl Iterator<Item = (&Address, &OverrideAccount)> + '_ {
         self.0.iter()
@@ -0,0 +1,155 @@
+//! API types related to the Parity-style `trace` namespace.
+
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{web3::Bytes, H256};
+
+use super::BlockNumber;
+use crate::{debug_flat_call::DebugCallFlat, Address};
+
+/// Filter for `trace_filter` method.
+///
+/// A trace matches the filter if its sender is in `from_address` (or `from_address` is not specified),
+/// *and* its recipient is in `to_address` (or `to_address` is not specified).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+    /// First block to return traces for (inclusive). If not specified, the latest sealed block is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockNumber>,
+    /// Last block to return traces for (inclusive). If not specified, the latest sealed block is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockNumber>,
+    /// Senders to filter traces by.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_address: Option<Vec<Address>>,
+    /// Recipients to filter traces by.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_address: Option<Vec<Address>>,
+    /// Number of matching traces to skip.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<usize>,
+    /// Maximum number of traces to return.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+}
+
+impl TraceFilter {
+    /// Checks whether the provided trace matches this filter.
+    pub fn matches(&self, trace: &DebugCallFlat) -> bool {
+        let matches_address = |addresses: &Option<Vec<Address>>, address: &Address| {
+            addresses
+                .as_ref()
+                .is_none_or(|addresses| addresses.is_empty() || addresses.contains(address))
+        };
+        matches_address(&self.from_address, &trace.action.from)
+            && matches_address(&self.to_address, &trace.action.to)
+    }
+}
+
+/// Type of traces requested in `trace_replayBlockTransactions`. Only call traces are supported;
+/// requesting `vmTrace` or `stateDiff` will result in a deserialization error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceType {
+    /// Flat call traces.
+    Trace,
+}
+
+/// Result of replaying a single transaction in `trace_replayBlockTransactions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceResults {
+    /// Output of the top-level call.
+    pub output: Bytes,
+    /// Flat call traces; empty if [`TraceType::Trace`] was not requested.
+    pub trace: Vec<DebugCallFlat>,
+    /// Hash of the replayed transaction.
+    pub transaction_hash: H256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::DebugCallType,
+        debug_flat_call::{Action, CallResult},
+    };
+
+    fn mock_trace(from: Address, to: Address) -> DebugCallFlat {
+        DebugCallFlat {
+            action: Action {
+                call_type: DebugCallType::Call,
+                from,
+                to,
+                gas: 100.into(),
+                value: 0.into(),
+                input: Bytes::default(),
+            },
+            result: Some(CallResult {
+                output: Bytes::default(),
+                gas_used: 42.into(),
+            }),
+            subtraces: 0,
+            error: None,
+            trace_address: vec![],
+            transaction_position: 0,
+            transaction_hash: H256::zero(),
+            block_number: 1,
+            block_hash: H256::zero(),
+            r#type: DebugCallType::Call,
+        }
+    }
+
+    #[test]
+    fn trace_filter_deserialization() {
+        let filter: TraceFilter = serde_json::from_value(serde_json::json!({
+            "fromBlock": "0x1",
+            "toBlock": "latest",
+            "fromAddress": [Address::repeat_byte(1)],
+            "toAddress": null,
+            "count": 10,
+        }))
+        .unwrap();
+        assert_eq!(
+            filter,
+            TraceFilter {
+                from_block: Some(BlockNumber::Number(1.into())),
+                to_block: Some(BlockNumber::Latest),
+                from_address: Some(vec![Address::repeat_byte(1)]),
+                to_address: None,
+                after: None,
+                count: Some(10),
+            }
+        );
+
+        let trace_types: Vec<TraceType> = serde_json::from_str(r#"["trace"]"#).unwrap();
+        assert_eq!(trace_types, [TraceType::Trace]);
+        serde_json::from_str::<Vec<TraceType>>(r#"["stateDiff"]"#).unwrap_err();
+    }
+
+    #[test]
+    fn trace_filter_matching() {
+        let (alice, bob, carol) = (
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            Address::repeat_byte(3),
+        );
+        let trace = mock_trace(alice, bob);
+        assert!(TraceFilter::default().matches(&trace));
+
+        let filter = TraceFilter {
+            from_address: Some(vec![alice, carol]),
+            ..TraceFilter::default()
+        };
+        assert!(filter.matches(&trace));
+        assert!(!filter.matches(&mock_trace(bob, alice)));
+
+        let filter = TraceFilter {
+            from_address: Some(vec![alice]),
+            to_address: Some(vec![carol]),
+            ..TraceFilter::default()
+        };
+        assert!(!filter.matches(&trace));
+        assert!(filter.matches(&mock_trace(alice, carol)));
+    }
+}
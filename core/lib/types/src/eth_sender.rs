@@ -89,6 +89,8 @@ pub struct TxHistory {
     pub max_gas_per_pubdata: Option<u64>,
     pub eth_tx_finality_status: EthTxFinalityStatus,
     pub sent_successfully: bool,
+    /// Name of the fee bumping strategy used to compute fees for this attempt. `None` for the first attempt.
+    pub fee_bump_strategy: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
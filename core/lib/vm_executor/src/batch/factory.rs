@@ -300,6 +300,9 @@ impl<S: ReadStorage, Tr: BatchTracer> BatchVm<S, Tr> {
             tx_result: Box::new(tx_result),
             compression_result: compressed_bytecodes,
             call_traces,
+            prestate_trace: None,
+            struct_logs: None,
+            profile: None,
         }
     }
 }
@@ -546,6 +549,9 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
                 tx_result: res.tx_result,
                 compression_result: Ok(()),
                 call_traces: res.call_traces,
+                prestate_trace: None,
+                struct_logs: None,
+                profile: None,
             });
         }
 
@@ -561,6 +567,9 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
             tx_result: res.tx_result,
             compression_result: Ok(()),
             call_traces: res.call_traces,
+            prestate_trace: None,
+            struct_logs: None,
+            profile: None,
         })
     }
 
@@ -577,6 +586,9 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
                 tx_result: res.tx_result,
                 compression_result: Ok(()),
                 call_traces: res.call_traces,
+                prestate_trace: None,
+                struct_logs: None,
+                profile: None,
             })
         } else {
             // Transaction failed to publish bytecodes, we reject it so initiator doesn't pay fee.
@@ -588,6 +600,9 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
                 tx_result,
                 compression_result: Ok(()),
                 call_traces: vec![],
+                prestate_trace: None,
+                struct_logs: None,
+                profile: None,
             })
         }
     }
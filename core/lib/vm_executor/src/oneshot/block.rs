@@ -197,10 +197,20 @@ impl ResolvedBlockInfo {
         self.state_l2_block_number
     }
 
+    /// Hash of the L2 block with [`Self::state_l2_block_number()`].
+    pub fn state_l2_block_hash(&self) -> H256 {
+        self.state_l2_block_hash
+    }
+
     pub fn protocol_version(&self) -> ProtocolVersionId {
         self.protocol_version
     }
 
+    /// Timestamp of the L1 batch provided to the VM. For the pending block, this is also the timestamp of the executed L2 block.
+    pub fn l1_batch_timestamp(&self) -> u64 {
+        self.l1_batch_timestamp
+    }
+
     pub fn use_evm_emulator(&self) -> bool {
         self.use_evm_emulator
     }
@@ -100,4 +100,22 @@ impl OneshotEnvParameters<CallOrExecute> {
         )
         .await
     }
+
+    /// Prepares environment for re-executing a transaction already included into a block. Unlike [`Self::to_execute_env()`],
+    /// the base fee is not enforced and is derived from the provided fee input, which should be taken from the original block.
+    pub async fn to_replay_env(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        resolved_block_info: &ResolvedBlockInfo,
+        fee_input: BatchFeeInput,
+    ) -> anyhow::Result<OneshotEnv> {
+        self.to_env_inner(
+            connection,
+            TxExecutionMode::VerifyExecute,
+            resolved_block_info,
+            fee_input,
+            None,
+        )
+        .await
+    }
 }
@@ -6,7 +6,8 @@ use zksync_multivm::interface::{
     storage::ReadStorage,
     tracer::{ValidationError, ValidationParams, ValidationTraces},
     ExecutionResult, OneshotEnv, OneshotTracingParams, OneshotTransactionExecutionResult,
-    TxExecutionArgs, TxExecutionMode, VmExecutionResultAndLogs,
+    PrestateTrace, StructLogs, TransactionProfile, TxExecutionArgs, TxExecutionMode,
+    VmExecutionResultAndLogs,
 };
 use zksync_types::{l2::L2Tx, Transaction};
 
@@ -90,6 +91,14 @@ impl MockOneshotExecutor {
         self.tx_responses = Box::new(responses);
     }
 
+    /// Same as [`Self::set_call_responses()`], but allows to customize returned VM logs etc.
+    pub fn set_full_call_responses<F>(&mut self, responses: F)
+    where
+        F: Fn(&Transaction, &OneshotEnv) -> VmExecutionResultAndLogs + 'static + Send + Sync,
+    {
+        self.call_responses = Box::new(responses);
+    }
+
     /// Sets an artificial delay for each VM invocation.
     pub fn set_vm_delay(&mut self, delay: Duration) {
         self.vm_delay = delay;
@@ -121,12 +130,16 @@ where
         _storage: S,
         env: OneshotEnv,
         args: TxExecutionArgs,
-        _params: OneshotTracingParams,
+        params: OneshotTracingParams,
     ) -> anyhow::Result<OneshotTransactionExecutionResult> {
         Ok(OneshotTransactionExecutionResult {
             tx_result: Box::new(self.mock_inspect(&env, args).await),
             compression_result: Ok(()),
             call_traces: vec![],
+            // The mock doesn't track storage, so the trace is always empty.
+            prestate_trace: params.trace_prestate.map(|_| PrestateTrace::default()),
+            struct_logs: params.trace_struct_logs.map(|_| StructLogs::default()),
+            profile: params.profile.then(TransactionProfile::default),
         })
     }
 }
@@ -22,13 +22,15 @@ use zksync_multivm::{
         tracer::{ValidationError, ValidationParams, ValidationTraces},
         utils::{DivergenceHandler, ShadowMut, ShadowVm},
         Call, ExecutionResult, Halt, InspectExecutionMode, OneshotEnv, OneshotTracingParams,
-        OneshotTransactionExecutionResult, StoredL2BlockEnv, TxExecutionArgs, TxExecutionMode,
-        VmFactory, VmInterface,
+        OneshotTransactionExecutionResult, PrestateState, PrestateTrace, PrestateTracingMode,
+        StoredL2BlockEnv, TxExecutionArgs, TxExecutionMode, VmFactory, VmInterface,
     },
     is_supported_by_fast_vm,
-    tracers::{CallTracer, StorageInvocations, TracerDispatcher, ValidationTracer},
+    tracers::{CallTracer, PrestateTracer, StorageInvocations, TracerDispatcher, ValidationTracer},
     utils::adjust_pubdata_price_for_tx,
-    vm_fast::{self, FastValidationTracer, StorageInvocationsTracer},
+    vm_fast::{
+        self, FastValidationTracer, ProfileTracer, StorageInvocationsTracer, StructLogTracer,
+    },
     vm_latest::{HistoryDisabled, HistoryEnabled},
     zk_evm_latest::ethereum_types::U256,
     FastVmInstance, HistoryMode, LegacyVmInstance, MultiVmTracer, VmVersion,
@@ -124,8 +126,13 @@ impl MainOneshotExecutor {
         env: &OneshotEnv,
         tracing_params: &OneshotTracingParams,
     ) -> FastVmMode {
-        if tracing_params.trace_calls || !is_supported_by_fast_vm(env.system.version) {
-            FastVmMode::Old // the fast VM doesn't support call tracing or old protocol versions
+        if tracing_params.trace_calls
+            || tracing_params.trace_prestate.is_some()
+            || !is_supported_by_fast_vm(env.system.version)
+        {
+            FastVmMode::Old // the fast VM doesn't support call / prestate tracing or old protocol versions
+        } else if tracing_params.trace_struct_logs.is_some() || tracing_params.profile {
+            FastVmMode::New // struct logs and profiling are only supported by the fast VM
         } else {
             self.fast_vm_mode
         }
@@ -269,7 +276,12 @@ enum Vm<S: ReadStorage, Tr, Val> {
     Fast(StoragePtr<StorageView<S>>, FastVmInstance<S, Tr, Val>),
 }
 
-impl<S: ReadStorage> Vm<S, StorageInvocationsTracer<StorageView<S>>, FastValidationTracer> {
+type FastOneshotTracer<S> = (
+    StorageInvocationsTracer<StorageView<S>>,
+    (StructLogTracer, ProfileTracer),
+);
+
+impl<S: ReadStorage> Vm<S, FastOneshotTracer<S>, FastValidationTracer> {
     fn inspect_transaction_with_bytecode_compression(
         &mut self,
         stop_token: StopToken,
@@ -279,12 +291,18 @@ impl<S: ReadStorage> Vm<S, StorageInvocationsTracer<StorageView<S>>, FastValidat
         with_compression: bool,
     ) -> OneshotTransactionExecutionResult {
         let mut calls_result = Arc::<OnceCell<_>>::default();
+        let mut prestate_result = Arc::<OnceCell<_>>::default();
+        let mut struct_logs = None;
+        let mut profile = None;
         let (compression_result, tx_result) = match self {
             Self::Legacy(vm) => {
                 let mut tracers = Self::create_legacy_tracers(
                     stop_token,
                     missed_storage_invocation_limit,
                     params.trace_calls.then(|| calls_result.clone()),
+                    params
+                        .trace_prestate
+                        .map(|mode| (mode, prestate_result.clone())),
                 );
                 vm.inspect_transaction_with_bytecode_compression(&mut tracers, tx, with_compression)
             }
@@ -293,31 +311,59 @@ impl<S: ReadStorage> Vm<S, StorageInvocationsTracer<StorageView<S>>, FastValidat
                     !params.trace_calls,
                     "Call tracing is not supported by fast VM yet"
                 );
+                assert!(
+                    params.trace_prestate.is_none(),
+                    "Prestate tracing is not supported by fast VM yet"
+                );
                 let legacy_tracers = Self::create_legacy_tracers::<HistoryEnabled>(
                     stop_token.clone(),
                     missed_storage_invocation_limit,
                     None,
+                    None,
                 );
                 let tracer =
                     StorageInvocationsTracer::new(storage.clone(), missed_storage_invocation_limit)
                         .with_stop_token(stop_token);
+                let struct_log_tracer = params
+                    .trace_struct_logs
+                    .map(StructLogTracer::new)
+                    .unwrap_or_default();
+                let profile_tracer = if params.profile {
+                    ProfileTracer::new()
+                } else {
+                    ProfileTracer::default()
+                };
                 let mut full_tracer = (
                     legacy_tracers.into(),
-                    (tracer, FastValidationTracer::default()),
+                    (
+                        (tracer, (struct_log_tracer, profile_tracer)),
+                        FastValidationTracer::default(),
+                    ),
                 );
                 let mut result = vm.inspect_transaction_with_bytecode_compression(
                     &mut full_tracer,
                     tx,
                     with_compression,
                 );
+                let (struct_log_tracer, profile_tracer) = full_tracer.1 .0 .1;
+                struct_logs = struct_log_tracer.into_result();
+                profile = profile_tracer.into_result();
 
                 if let ExecutionResult::Halt {
                     reason: Halt::TracerCustom(msg),
                 } = &mut result.1.result
                 {
                     // Patch the halt message to be more specific; the fast VM provides a generic one since it doesn't know
-                    // which tracer(s) are run. Here, we do know that the only tracer capable of stopping VM execution is the storage limiter.
-                    *msg = "Storage invocations limit reached".to_owned();
+                    // which tracer(s) are run. Here, we do know that the only tracers capable of stopping VM execution are
+                    // the storage limiter and the struct log tracer.
+                    *msg = if struct_logs
+                        .as_ref()
+                        .is_some_and(|logs| logs.size_limit_exceeded)
+                    {
+                        "Struct logs size limit reached".to_owned()
+                    } else {
+                        "Storage invocations limit reached".to_owned()
+                    };
                 }
 
                 result
@@ -328,6 +374,11 @@ impl<S: ReadStorage> Vm<S, StorageInvocationsTracer<StorageView<S>>, FastValidat
             tx_result: Box::new(tx_result),
             compression_result: compression_result.map(drop),
             call_traces: Arc::make_mut(&mut calls_result).take().unwrap_or_default(),
+            prestate_trace: Arc::make_mut(&mut prestate_result)
+                .take()
+                .map(|(pre, post)| PrestateTrace { pre, post }),
+            struct_logs,
+            profile,
         }
     }
 
@@ -335,11 +386,19 @@ impl<S: ReadStorage> Vm<S, StorageInvocationsTracer<StorageView<S>>, FastValidat
         stop_token: StopToken,
         missed_storage_invocation_limit: usize,
         calls_result: Option<Arc<OnceCell<Vec<Call>>>>,
+        prestate_result: Option<(
+            PrestateTracingMode,
+            Arc<OnceCell<(PrestateState, PrestateState)>>,
+        )>,
     ) -> TracerDispatcher<StorageView<S>, H> {
         let mut tracers = vec![];
         if let Some(calls_result) = calls_result {
             tracers.push(CallTracer::new(calls_result).into_tracer_pointer());
         }
+        if let Some((mode, prestate_result)) = prestate_result {
+            let diff_mode = mode == PrestateTracingMode::Diff;
+            tracers.push(PrestateTracer::new(diff_mode, prestate_result).into_tracer_pointer());
+        }
         let storage_limiter =
             StorageInvocations::new(missed_storage_invocation_limit).with_stop_token(stop_token);
         tracers.push(storage_limiter.into_tracer_pointer());
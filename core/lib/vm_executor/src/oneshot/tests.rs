@@ -3,7 +3,7 @@
 use assert_matches::assert_matches;
 use test_casing::{test_casing, Product};
 use zksync_multivm::interface::storage::InMemoryStorage;
-use zksync_types::{ProtocolVersionId, H256};
+use zksync_types::{ProtocolVersionId, H256, NONCE_HOLDER_ADDRESS};
 
 use super::*;
 use crate::testonly::{
@@ -31,7 +31,19 @@ fn selecting_vm_for_execution() {
         assert_matches!(mode, FastVmMode::New);
 
         // Tracing calls is not supported by the new VM.
-        let mode = executor.select_fast_vm_mode(&env, &OneshotTracingParams { trace_calls: true });
+        let params = OneshotTracingParams {
+            trace_calls: true,
+            ..OneshotTracingParams::default()
+        };
+        let mode = executor.select_fast_vm_mode(&env, &params);
+        assert_matches!(mode, FastVmMode::Old);
+
+        // Same for prestate tracing.
+        let params = OneshotTracingParams {
+            trace_prestate: Some(PrestateTracingMode::Diff),
+            ..OneshotTracingParams::default()
+        };
+        let mode = executor.select_fast_vm_mode(&env, &params);
         assert_matches!(mode, FastVmMode::Old);
 
         // Old protocol versions are not supported either.
@@ -104,3 +116,66 @@ async fn inspecting_transfer(exec_mode: TxExecutionMode, fast_vm_mode: FastVmMod
     let exec_result = result.tx_result.result;
     assert!(!exec_result.is_failed(), "{exec_result:?}");
 }
+
+#[tokio::test]
+async fn inspecting_transfer_with_prestate_tracing() {
+    let tx = create_l2_transaction(1_000_000_000.into(), Nonce(0));
+    let mut storage = InMemoryStorage::with_system_contracts();
+    storage.set_value(
+        storage_key_for_eth_balance(&tx.initiator_account()),
+        u256_to_h256(u64::MAX.into()),
+    );
+    let storage = StorageWithOverrides::new(storage);
+
+    let l1_batch = default_l1_batch_env(1);
+    let env = OneshotEnv {
+        system: default_system_env(TxExecutionMode::VerifyExecute),
+        current_block: Some(StoredL2BlockEnv {
+            number: l1_batch.first_l2_block.number - 1,
+            timestamp: l1_batch.first_l2_block.timestamp - 1,
+            txs_rolling_hash: H256::zero(),
+        }),
+        l1_batch,
+    };
+    let initiator = tx.initiator_account();
+    let nonce_key = get_nonce_key(&initiator);
+    let args = TxExecutionArgs::for_validation(tx);
+    let tracing = OneshotTracingParams {
+        trace_prestate: Some(PrestateTracingMode::Diff),
+        ..OneshotTracingParams::default()
+    };
+
+    // Prestate tracing must force the legacy VM regardless of the configured mode.
+    let mut executor = MainOneshotExecutor::new(usize::MAX);
+    executor.set_fast_vm_mode(FastVmMode::New);
+    let result = executor
+        .inspect_transaction_with_bytecode_compression(storage, env, args, tracing)
+        .await
+        .unwrap();
+    result.compression_result.unwrap();
+    let exec_result = &result.tx_result.result;
+    assert!(!exec_result.is_failed(), "{exec_result:?}");
+
+    let prestate_trace = result.prestate_trace.expect("no prestate trace");
+    // The transaction increments the initiator's nonce, so `NonceHolder` storage must be modified.
+    let pre_nonce_holder_storage = prestate_trace.pre[&NONCE_HOLDER_ADDRESS]
+        .storage
+        .as_ref()
+        .unwrap();
+    assert_eq!(pre_nonce_holder_storage[nonce_key.key()], H256::zero());
+    let post_nonce_holder_storage = prestate_trace.post[&NONCE_HOLDER_ADDRESS]
+        .storage
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        post_nonce_holder_storage[nonce_key.key()],
+        H256::from_low_u64_be(1)
+    );
+
+    let pre_initiator_state = &prestate_trace.pre[&initiator];
+    assert_eq!(pre_initiator_state.balance, Some(u64::MAX.into()));
+    assert_eq!(pre_initiator_state.nonce, Some(0.into()));
+    let post_initiator_state = &prestate_trace.post[&initiator];
+    assert!(post_initiator_state.balance.unwrap() < u64::MAX.into());
+    assert_eq!(post_initiator_state.nonce, Some(1.into()));
+}
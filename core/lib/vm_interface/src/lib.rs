@@ -25,15 +25,18 @@ pub use crate::{
         },
         inputs::{
             InspectExecutionMode, L1BatchEnv, L2BlockEnv, OneshotEnv, OneshotTracingParams,
-            StoredL2BlockEnv, SystemEnv, TxExecutionArgs, TxExecutionMode, VmExecutionMode,
+            PrestateTracingMode, StoredL2BlockEnv, StructLogTracingParams, SystemEnv,
+            TxExecutionArgs, TxExecutionMode, VmExecutionMode,
         },
         outputs::{
             BatchTransactionExecutionResult, BootloaderMemory, Call, CallType, CircuitStatistic,
             CompressedBytecodeInfo, CurrentExecutionState, DeduplicatedWritesMetrics,
             ExecutionResult, FinishedL1Batch, L2Block, OneshotTransactionExecutionResult,
-            PushTransactionResult, Refunds, TransactionExecutionMetrics,
-            TransactionExecutionResult, TxExecutionStatus, VmEvent, VmExecutionLogs,
-            VmExecutionMetrics, VmExecutionResultAndLogs, VmExecutionStatistics, VmMemoryMetrics,
+            PrestateAccount, PrestateState, PrestateTrace, ProfiledFrame, ProfiledResources,
+            ProfiledStorageWrite, PushTransactionResult, Refunds, StructLog, StructLogs,
+            TransactionExecutionMetrics, TransactionExecutionResult, TransactionProfile,
+            TxExecutionStatus, VmEvent, VmExecutionLogs, VmExecutionMetrics,
+            VmExecutionResultAndLogs, VmExecutionStatistics, VmMemoryMetrics,
         },
         tracer,
     },
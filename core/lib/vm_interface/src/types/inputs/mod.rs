@@ -85,11 +85,54 @@ impl TxExecutionArgs {
             transaction,
         }
     }
+
+    /// Prepares arguments to re-execute a transaction that was already included into a block. The caller is responsible
+    /// for providing the storage state the transaction was originally executed on.
+    pub fn for_replay(transaction: Transaction) -> Self {
+        Self {
+            enforced_nonce: None,
+            added_balance: U256::zero(),
+            adjust_pubdata_price: false,
+            transaction,
+        }
+    }
 }
 
 /// Inputs and outputs for all tracers supported for oneshot transaction / call execution.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct OneshotTracingParams {
     /// Whether to trace contract calls.
     pub trace_calls: bool,
+    /// Whether to trace the state of touched accounts, and in which mode.
+    pub trace_prestate: Option<PrestateTracingMode>,
+    /// Whether to record a log for each executed VM instruction, and with which parameters.
+    /// Only supported by the fast VM.
+    pub trace_struct_logs: Option<StructLogTracingParams>,
+    /// Whether to profile resources spent by call frames and storage writes. Only supported by the fast VM.
+    pub profile: bool,
+}
+
+/// Mode of the prestate tracer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrestateTracingMode {
+    /// Record the state of all accounts touched by the transaction.
+    Prestate,
+    /// Record the state of modified accounts before and after the transaction.
+    Diff,
+}
+
+/// Parameters of the struct log tracer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StructLogTracingParams {
+    /// Whether to record heap contents of the current call frame.
+    pub enable_memory: bool,
+    /// Whether to omit register contents.
+    pub disable_stack: bool,
+    /// Whether to omit storage slots accessed by the current contract.
+    pub disable_storage: bool,
+    /// Maximum number of recorded logs. Logs after this limit are silently dropped.
+    pub limit: Option<usize>,
+    /// Maximum estimated size of recorded logs in bytes once serialized. If this size is exceeded,
+    /// VM execution is stopped.
+    pub max_size: usize,
 }
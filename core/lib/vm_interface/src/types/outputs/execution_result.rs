@@ -14,7 +14,8 @@ use zksync_types::{
 };
 
 use crate::{
-    BytecodeCompressionError, Halt, VmExecutionMetrics, VmExecutionStatistics, VmRevertReason,
+    BytecodeCompressionError, Halt, PrestateTrace, StructLogs, TransactionProfile,
+    VmExecutionMetrics, VmExecutionStatistics, VmRevertReason,
 };
 
 /// Event generated by the VM.
@@ -338,6 +339,12 @@ pub struct BatchTransactionExecutionResult {
     pub compression_result: Result<(), BytecodeCompressionError>,
     /// Call traces (if requested; otherwise, empty).
     pub call_traces: Vec<Call>,
+    /// Prestate trace (if requested; otherwise, `None`).
+    pub prestate_trace: Option<PrestateTrace>,
+    /// Struct logs (if requested and supported by the VM; otherwise, `None`).
+    pub struct_logs: Option<StructLogs>,
+    /// Gas, pubdata and circuits profile (if requested and supported by the VM; otherwise, `None`).
+    pub profile: Option<TransactionProfile>,
 }
 
 impl BatchTransactionExecutionResult {
@@ -10,10 +10,13 @@ pub use self::{
     execution_state::{BootloaderMemory, CurrentExecutionState},
     finished_l1batch::FinishedL1Batch,
     l2_block::L2Block,
+    prestate::{PrestateAccount, PrestateState, PrestateTrace},
+    profile::{ProfiledFrame, ProfiledResources, ProfiledStorageWrite, TransactionProfile},
     statistic::{
         CircuitStatistic, DeduplicatedWritesMetrics, TransactionExecutionMetrics,
         VmExecutionMetrics, VmExecutionStatistics, VmMemoryMetrics,
     },
+    struct_log::{StructLog, StructLogs},
 };
 
 mod bytecode;
@@ -21,7 +24,10 @@ mod execution_result;
 mod execution_state;
 mod finished_l1batch;
 mod l2_block;
+mod prestate;
+mod profile;
 mod statistic;
+mod struct_log;
 
 /// Result of pushing a transaction to the VM state without executing it.
 #[derive(Debug)]
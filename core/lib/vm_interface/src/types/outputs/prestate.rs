@@ -0,0 +1,50 @@
+use std::{collections::HashMap, fmt};
+
+use zksync_types::{Address, H256, U256};
+
+/// Account state recorded by the prestate tracer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrestateAccount {
+    pub balance: Option<U256>,
+    /// Versioned bytecode hash stored for the account in `AccountCodeStorage`.
+    pub code: Option<U256>,
+    /// Full nonce of the account (i.e., including the deployment nonce), as stored in `NonceHolder`.
+    pub nonce: Option<U256>,
+    pub storage: Option<HashMap<H256, H256>>,
+}
+
+impl fmt::Display for PrestateAccount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{{")?;
+        if let Some(balance) = self.balance {
+            writeln!(f, "  balance: \"0x{:x}\",", balance)?;
+        }
+        if let Some(code) = &self.code {
+            writeln!(f, "  code: \"{}\",", code)?;
+        }
+        if let Some(nonce) = self.nonce {
+            writeln!(f, "  nonce: {},", nonce)?;
+        }
+        if let Some(storage) = &self.storage {
+            writeln!(f, "  storage: {{")?;
+            for (key, value) in storage.iter() {
+                writeln!(f, "    {}: \"{}\",", key, value)?;
+            }
+            writeln!(f, "  }}")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// State of accounts touched by a transaction, keyed by the account address.
+pub type PrestateState = HashMap<Address, PrestateAccount>;
+
+/// Output of the prestate tracer for a single transaction.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrestateTrace {
+    /// State of accounts before the transaction. In the non-diff mode, contains all accounts touched by the transaction;
+    /// in the diff mode, only modified accounts. Storage contains all slots read or written by the transaction.
+    pub pre: PrestateState,
+    /// State of modified accounts after the transaction. Only populated in the diff mode.
+    pub post: PrestateState,
+}
@@ -0,0 +1,86 @@
+use std::ops;
+
+use zksync_types::{Address, H256};
+
+/// Resources spent by a part of VM execution.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProfiledResources {
+    /// Gas (ergs) spent.
+    pub gas: u64,
+    /// Pubdata bytes spent. May be negative, e.g. if a storage slot is reset to its initial value.
+    pub pubdata: i64,
+    /// Number of main VM cycles (i.e., executed instructions).
+    pub cycles: u64,
+    /// Estimated number of circuits, summed across all circuit types.
+    pub circuits: f32,
+}
+
+impl ops::Add for ProfiledResources {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            gas: self.gas + other.gas,
+            pubdata: self.pubdata + other.pubdata,
+            cycles: self.cycles + other.cycles,
+            circuits: self.circuits + other.circuits,
+        }
+    }
+}
+
+impl ops::AddAssign for ProfiledResources {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+/// Saturates for unsigned values and clamps circuits to be non-negative since they are estimated
+/// using floating-point arithmetic.
+impl ops::Sub for ProfiledResources {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            gas: self.gas.saturating_sub(other.gas),
+            pubdata: self.pubdata - other.pubdata,
+            cycles: self.cycles.saturating_sub(other.cycles),
+            circuits: (self.circuits - other.circuits).max(0.0),
+        }
+    }
+}
+
+/// Far call frame recorded by the profiler.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfiledFrame {
+    /// Index of the parent frame in [`TransactionProfile::frames`], or `None` for calls made by the bootloader.
+    pub parent: Option<usize>,
+    /// Address of the called contract.
+    pub address: Address,
+    /// First 4 bytes of the calldata, if the calldata is long enough.
+    pub selector: Option<[u8; 4]>,
+    /// Resources spent by the frame, including subcalls.
+    pub total: ProfiledResources,
+    /// Resources spent by the frame itself, excluding subcalls.
+    pub own: ProfiledResources,
+}
+
+/// Storage write recorded by the profiler.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfiledStorageWrite {
+    /// Index of the frame performing the write in [`TransactionProfile::frames`].
+    pub frame: usize,
+    pub address: Address,
+    pub key: H256,
+    /// Resources spent by the write instruction.
+    pub resources: ProfiledResources,
+}
+
+/// Output of the profiler for a single transaction. Only covers far calls made by the bootloader and their subcalls;
+/// resources spent by the bootloader itself are not recorded.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactionProfile {
+    /// Far call frames in the order of their start.
+    pub frames: Vec<ProfiledFrame>,
+    /// Storage writes in the execution order.
+    pub storage_writes: Vec<ProfiledStorageWrite>,
+}
@@ -0,0 +1,34 @@
+use std::collections::BTreeMap;
+
+use zksync_types::{H256, U256};
+
+/// Single log entry recorded by the struct log tracer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructLog {
+    /// Program counter in the executed bytecode.
+    pub pc: u64,
+    /// Name of the executed opcode.
+    pub op: String,
+    /// Gas (ergs) remaining before the instruction.
+    pub gas: u64,
+    /// Gas (ergs) spent by the instruction.
+    pub gas_cost: u64,
+    /// Depth of far calls; the first call from the bootloader has depth 1.
+    pub depth: usize,
+    /// Contents of general-purpose registers `r1..=r15` before the instruction, if recorded.
+    pub stack: Option<Vec<U256>>,
+    /// Heap contents of the current call frame split into 32-byte words, if recorded.
+    pub memory: Option<Vec<U256>>,
+    /// Storage slots of the current contract accessed so far. Only recorded for storage access instructions.
+    pub storage: Option<BTreeMap<H256, H256>>,
+    /// Error produced by the instruction, if any.
+    pub error: Option<String>,
+}
+
+/// Output of the struct log tracer for a single transaction.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StructLogs {
+    pub logs: Vec<StructLog>,
+    /// Set if VM execution was stopped because recorded logs exceeded the size limit.
+    pub size_limit_exceeded: bool,
+}
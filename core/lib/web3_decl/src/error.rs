@@ -39,6 +39,12 @@ pub enum Web3Error {
     LogsLimitExceeded(usize, u32, u32),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
+    #[error("Requested block range is too large; at most {0} blocks can be queried at once")]
+    BlockRangeLimitExceeded(u32),
+    #[error("Invalid simulation request: {0}")]
+    InvalidSimulation(String),
+    #[error("Trace exceeds the response size limit of {0} bytes")]
+    TraceTooLarge(usize),
     /// Weaker form of a "method not found" error; the method implementation is technically present,
     /// but the node configuration prevents the method from functioning.
     #[error("Method not implemented")]
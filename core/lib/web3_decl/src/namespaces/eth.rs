@@ -3,8 +3,10 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockId, BlockIdVariant, BlockNumber, FeeHistory,
-        Transaction, TransactionVariant,
+        simulate::{SimulatePayload, SimulatedBlock},
+        state_override::StateOverride,
+        AccessListResult, BlockId, BlockIdVariant, BlockNumber, EthProof, FeeHistory, Transaction,
+        TransactionVariant,
     },
     transaction_request::CallRequest,
     Address, H256,
@@ -49,6 +51,28 @@ pub trait EthNamespace {
         state_override: Option<StateOverride>,
     ) -> RpcResult<U256>;
 
+    #[method(name = "getProof")]
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Option<EthProof>>;
+
+    #[method(name = "createAccessList")]
+    async fn create_access_list(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<AccessListResult>;
+
+    #[method(name = "simulateV1")]
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Vec<SimulatedBlock>>;
+
     #[method(name = "gasPrice")]
     async fn gas_price(&self) -> RpcResult<U256>;
 
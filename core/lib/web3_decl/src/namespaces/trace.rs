@@ -0,0 +1,43 @@
+#[cfg_attr(not(feature = "server"), allow(unused_imports))]
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use zksync_types::{
+    api::{
+        trace::{TraceFilter, TraceResults, TraceType},
+        BlockNumber,
+    },
+    debug_flat_call::DebugCallFlat,
+};
+
+use crate::{
+    client::{ForWeb3Network, L2},
+    types::H256,
+};
+
+/// Parity-style tracing namespace. Traces are returned in the same format as for `debug_*` methods
+/// with the `flatCallTracer`.
+#[cfg_attr(
+    feature = "server",
+    rpc(server, client, namespace = "trace", client_bounds(Self: ForWeb3Network<Net = L2>))
+)]
+#[cfg_attr(
+    not(feature = "server"),
+    rpc(client, namespace = "trace", client_bounds(Self: ForWeb3Network<Net = L2>))
+)]
+pub trait TraceNamespace {
+    #[method(name = "block")]
+    async fn trace_block(&self, block: BlockNumber) -> RpcResult<Vec<DebugCallFlat>>;
+
+    #[method(name = "transaction")]
+    async fn trace_transaction(&self, tx_hash: H256) -> RpcResult<Option<Vec<DebugCallFlat>>>;
+
+    #[method(name = "filter")]
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<DebugCallFlat>>;
+
+    #[method(name = "replayBlockTransactions")]
+    async fn replay_block_transactions(
+        &self,
+        block: BlockNumber,
+        trace_types: Vec<TraceType>,
+    ) -> RpcResult<Vec<TraceResults>>;
+}
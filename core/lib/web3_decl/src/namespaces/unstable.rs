@@ -3,8 +3,9 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        ChainAggProof, DataAvailabilityDetails, GatewayMigrationStatus, L1ToL2TxsStatus, TeeProof,
-        TransactionDetailedResult, TransactionExecutionInfo,
+        profile::TransactionProfile, ChainAggProof, DataAvailabilityDetails,
+        GatewayMigrationStatus, L1ToL2TxsStatus, TeeProof, TransactionDetailedResult,
+        TransactionExecutionInfo,
     },
     tee_types::TeeType,
     L1BatchNumber, L2BlockNumber, L2ChainId, H256,
@@ -75,4 +76,9 @@ pub trait UnstableNamespace {
         &self,
         tx_bytes: Bytes,
     ) -> RpcResult<TransactionDetailedResult>;
+
+    /// Re-executes a mined transaction and breaks down gas, pubdata and VM cycles spent by it per call frame
+    /// and per storage write. Returns `None` if the transaction is not found or not mined yet.
+    #[method(name = "profileTransaction")]
+    async fn profile_transaction(&self, hash: H256) -> RpcResult<Option<TransactionProfile>>;
 }
@@ -9,8 +9,15 @@ use core::convert::{TryFrom, TryInto};
 
 use rlp::Rlp;
 use serde::{Deserialize, Serialize};
+use zksync_types::{
+    api::Transaction as ApiTransaction, commitment::L1BatchCommitmentMode,
+    protocol_version::ProtocolSemanticVersion, L1ChainId, L2ChainId,
+};
 pub use zksync_types::{
-    api::{Block, BlockNumber, Log, TransactionReceipt, TransactionRequest},
+    api::{
+        Block, BlockNumber, L1BatchStatusUpdate, Log, SignedTransactionPreconfirmation,
+        TransactionReceipt, TransactionRequest,
+    },
     ethabi,
     web3::{
         BlockHeader, Bytes, CallRequest, FeeHistory, Index, SyncState, TraceFilter, U64Number,
@@ -18,10 +25,6 @@ pub use zksync_types::{
     },
     Address, Transaction, H160, H256, H64, U256, U64,
 };
-use zksync_types::{
-    commitment::L1BatchCommitmentMode, protocol_version::ProtocolSemanticVersion, L1ChainId,
-    L2ChainId,
-};
 
 /// Token in the ZKsync network
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -204,12 +207,23 @@ impl FilterBuilder {
     }
 }
 
+/// Params for `eth_subscribe`. `address` and `topics` apply to `logs` subscriptions; other fields apply
+/// to `newPendingTransactions` subscriptions.
 #[derive(Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct PubSubFilter {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub address: Option<ValueOrArray<H160>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub topics: Option<Vec<Option<ValueOrArray<H256>>>>,
+    /// If set to `true`, full transactions are streamed instead of their hashes.
+    #[serde(rename = "fullTransactions", skip_serializing_if = "Option::is_none")]
+    pub full_transactions: Option<bool>,
+    /// If set, only transactions sent from one of these addresses are streamed.
+    #[serde(rename = "fromAddresses", skip_serializing_if = "Option::is_none")]
+    pub from_addresses: Option<Vec<Address>>,
+    /// If set, only transactions sent to one of these addresses are streamed.
+    #[serde(rename = "toAddresses", skip_serializing_if = "Option::is_none")]
+    pub to_addresses: Option<Vec<Address>>,
 }
 
 impl PubSubFilter {
@@ -234,6 +248,36 @@ impl PubSubFilter {
         }
         true
     }
+
+    pub fn matches_transaction(&self, transaction: &ApiTransaction) -> bool {
+        if let Some(from_addresses) = &self.from_addresses {
+            if !from_addresses
+                .iter()
+                .any(|addr| Some(*addr) == transaction.from)
+            {
+                return false;
+            }
+        }
+        if let Some(to_addresses) = &self.to_addresses {
+            if !to_addresses
+                .iter()
+                .any(|addr| Some(*addr) == transaction.to)
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn full_transactions(&self) -> bool {
+        self.full_transactions.unwrap_or(false)
+    }
+
+    /// Checks whether serving a `newPendingTransactions` subscription with this filter requires
+    /// loading transaction bodies (i.e., whether it's not enough to know transaction hashes).
+    pub fn requires_transaction_bodies(&self) -> bool {
+        self.full_transactions() || self.from_addresses.is_some() || self.to_addresses.is_some()
+    }
 }
 
 #[derive(Default, Clone)]
@@ -299,6 +343,9 @@ fn topic_to_option<T>(topic: ethabi::Topic<T>) -> Option<Vec<T>> {
 pub enum PubSubResult {
     Header(BlockHeader),
     Log(Log),
+    Preconfirmation(SignedTransactionPreconfirmation),
+    L1BatchStatus(L1BatchStatusUpdate),
+    FullTransaction(ApiTransaction),
     TxHash(H256),
     Syncing(bool),
 }
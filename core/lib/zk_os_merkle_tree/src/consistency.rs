@@ -8,7 +8,7 @@ use zksync_basic_types::H256;
 use crate::{
     leaf_nibbles, max_nibbles_for_internal_node, max_node_children,
     types::{InternalNode, KeyLookup, Leaf, Node, NodeKey},
-    Database, DeserializeError, HashTree, MerkleTree, TreeParams,
+    BatchOutput, Database, DeserializeError, HashTree, MerkleTree, PruneDatabase, TreeParams,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -36,6 +36,8 @@ pub enum ConsistencyError {
     MissingRoot(u64),
     #[error("missing min / max guards")]
     NoGuards,
+    #[error("failed proving reads for tree version {version}: {err:#}")]
+    Proof { version: u64, err: anyhow::Error },
 
     #[error("internal node with key {key} has unexpected number of children: expected {expected}, actual {actual}")]
     ChildCountMismatch {
@@ -134,6 +136,30 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
         Ok(())
     }
 
+    /// Verifies that the tree at the specified `version` produces a valid proof for reading the provided `keys`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version doesn't exist, or the proof cannot be created or doesn't verify.
+    pub fn verify_proof(&self, version: u64, keys: &[H256]) -> Result<(), ConsistencyError> {
+        let root = self
+            .db
+            .try_root(version)?
+            .ok_or(ConsistencyError::MissingRoot(version))?;
+        let output = BatchOutput {
+            root_hash: root.hash::<P>(&self.hasher),
+            leaf_count: root.leaf_count,
+        };
+
+        let proof = self
+            .prove(version, keys)
+            .map_err(|err| ConsistencyError::Proof { version, err })?;
+        proof
+            .verify_reads(&self.hasher, P::TREE_DEPTH, output, keys)
+            .map_err(|err| ConsistencyError::Proof { version, err })?;
+        Ok(())
+    }
+
     fn validate_internal_node(
         &self,
         node: &InternalNode,
@@ -289,6 +315,29 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
     }
 }
 
+impl<DB: PruneDatabase, P: TreeParams> MerkleTree<DB, P> {
+    /// Verifies all versions of the tree retained after pruning. For each version, checks its internal consistency
+    /// (see [`Self::verify_consistency()`]) and that it produces a valid proof for reading `keys`
+    /// (see [`Self::verify_proof()`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error (the first encountered one if there are multiple).
+    pub fn verify_retained_versions(&self, keys: &[H256]) -> Result<(), ConsistencyError> {
+        let Some(first_retained_version) = self.first_retained_version()? else {
+            return Ok(()); // The tree is empty
+        };
+        let manifest = self.db.try_manifest()?;
+        let version_count = manifest.map_or(0, |manifest| manifest.version_count);
+
+        for version in first_retained_version..version_count {
+            self.verify_consistency(version)?;
+            self.verify_proof(version, keys)?;
+        }
+        Ok(())
+    }
+}
+
 fn next_key(key: H256) -> Option<H256> {
     let mut bytes = key.0;
     for pos in (0..32).rev() {
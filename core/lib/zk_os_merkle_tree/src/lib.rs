@@ -9,8 +9,13 @@ pub use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 pub use self::{
     errors::DeserializeError,
     hasher::{BatchTreeProof, HashTree, TreeOperation},
+    pruning::{MerkleTreePruner, MerkleTreePrunerHandle, PrunerStoppedError},
     reader::MerkleTreeReader,
-    storage::{Database, MerkleTreeColumnFamily, PatchSet, Patched, RocksDBWrapper},
+    recovery::MerkleTreeRecovery,
+    storage::{
+        Database, MerkleTreeColumnFamily, PatchSet, Patched, PruneDatabase, PrunePatchSet,
+        RocksDBWrapper,
+    },
     types::{BatchOutput, TreeEntry},
 };
 use crate::{
@@ -23,7 +28,9 @@ mod consistency;
 mod errors;
 mod hasher;
 mod metrics;
+mod pruning;
 mod reader;
+mod recovery;
 mod storage;
 #[cfg(test)]
 mod tests;
@@ -130,6 +137,10 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
         let maybe_manifest = db.try_manifest().context("failed reading tree manifest")?;
         if let Some(manifest) = &maybe_manifest {
             manifest.tags.ensure_consistency::<P>(&hasher)?;
+            anyhow::ensure!(
+                !manifest.tags.is_recovering,
+                "Merkle tree is being recovered from a snapshot; finish recovery using `MerkleTreeRecovery`"
+            );
         }
 
         let info = MerkleTreeInfo {
@@ -323,12 +334,15 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
         Ok((output, proof.unwrap()))
     }
 
+    /// Removes the most recent versions from the database, together with stale keys produced by these versions.
+    ///
+    /// # Errors
+    ///
+    /// Proxies database I/O errors.
     pub fn truncate_recent_versions(&mut self, retained_version_count: u64) -> anyhow::Result<()> {
         let mut manifest = self.db.try_manifest()?.unwrap_or_default();
         let current_version_count = manifest.version_count;
         if current_version_count > retained_version_count {
-            // TODO: It is necessary to remove "future" stale keys since otherwise they may be used in future pruning and lead
-            //   to non-obsolete tree nodes getting removed.
             manifest.version_count = retained_version_count;
             self.db.truncate(manifest, ..current_version_count)?;
         }
@@ -336,6 +350,22 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
     }
 }
 
+impl<DB: PruneDatabase, P: TreeParams> MerkleTree<DB, P> {
+    /// Returns the first retained version of the tree, or `None` if the tree is empty.
+    pub fn first_retained_version(&self) -> Result<Option<u64>, DeserializeError> {
+        Ok(match self.db.min_stale_key_version() {
+            // Min stale key version is next after the first retained version since at least
+            // the root is updated on each version.
+            Some(version) => version.checked_sub(1),
+            // No stale keys means all past versions of the tree have been pruned
+            None => self
+                .db
+                .try_manifest()?
+                .and_then(|manifest| manifest.version_count.checked_sub(1)),
+        })
+    }
+}
+
 impl<DB: Database, P: TreeParams> MerkleTree<Patched<DB>, P> {
     /// Flushes changes to the underlying storage.
     pub fn flush(&mut self) -> anyhow::Result<()> {
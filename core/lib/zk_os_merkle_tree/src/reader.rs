@@ -7,7 +7,8 @@ use zksync_basic_types::H256;
 use crate::{
     hasher::BatchTreeProof,
     types::{NodeKey, RawNode},
-    Database, DefaultTreeParams, MerkleTree, RocksDBWrapper, TreeParams,
+    Database, DefaultTreeParams, DeserializeError, MerkleTree, PruneDatabase, RocksDBWrapper,
+    TreeParams,
 };
 
 pub struct MerkleTreeReader<DB, P: TreeParams = DefaultTreeParams>(MerkleTree<DB, P>);
@@ -77,6 +78,13 @@ impl<DB: Database, P: TreeParams> MerkleTreeReader<DB, P> {
     }
 }
 
+impl<DB: PruneDatabase, P: TreeParams> MerkleTreeReader<DB, P> {
+    /// Returns the first retained version of the tree, or `None` if the tree is empty.
+    pub fn first_retained_version(&self) -> Result<Option<u64>, DeserializeError> {
+        self.0.first_retained_version()
+    }
+}
+
 impl<P: TreeParams> MerkleTreeReader<RocksDBWrapper, P> {
     /// Returns raw nodes for the specified `keys`.
     pub fn raw_nodes(&self, node_keys: &[NodeKey]) -> anyhow::Result<Vec<Option<RawNode>>> {
@@ -0,0 +1,446 @@
+//! Merkle tree recovery logic.
+//!
+//! # Overview
+//!
+//! **Recovery process** is responsible for restoring a Merkle tree from a snapshot. A snapshot
+//! consists of all tree entries at a specific tree version together with their leaf indices. As a result of recovery,
+//! we create a Merkle tree with the same leaves as the snapshot. Any changes that are applied to the tree
+//! afterwards will have the same outcome as if they were applied to the original tree.
+//!
+//! Similar to the Era tree, a recovered tree is only *observably* identical to the original tree; in a recovered tree,
+//! all nodes have the same version (the snapshot version), while in the original tree, node versions are distributed
+//! from 0 to the snapshot version (both inclusive).
+//!
+//! Recovery process proceeds as follows:
+//!
+//! 1. Initialize a tree in the recovery mode. Until recovery is finished, the tree cannot be accessed
+//!    using ordinary [`MerkleTree`](crate::MerkleTree) APIs.
+//! 2. Update the tree from a snapshot, which [is fed to the tree](MerkleTreeRecovery::extend())
+//!    as chunks of `(leaf_index, entry)` pairs. Entries in each chunk must be ordered by increasing key,
+//!    but chunks themselves may be supplied in any order.
+//! 3. Finalize recovery using [`MerkleTreeRecovery::finalize()`]. To check integrity, you should compare
+//!    [`MerkleTreeRecovery::root_hash()`] to the reference value beforehand.
+//!
+//! The recovery process is tolerant to crashes and may be resumed from the middle. Each chunk is persisted atomically;
+//! to find out whether a chunk was already recovered, you may use [`MerkleTreeRecovery::entries()`].
+//!
+//! # Implementation details
+//!
+//! Since leaves in the tree are ordered by the leaf index rather than by key, recovered chunks generally
+//! leave holes in the tree (i.e., leaves with indices less than the leaf count that are not recovered yet).
+//! Holes have zero hashes in their parent nodes, so the tree root hash is only meaningful once all chunks are recovered.
+//!
+//! We require each chunk to fit between two adjacent recovered keys. This makes it sufficient to update
+//! `next_index` for a single recovered leaf per chunk, and ensures that chunks don't overlap.
+
+use anyhow::Context as _;
+use zksync_basic_types::H256;
+
+use crate::{
+    leaf_nibbles,
+    metrics::{RecoveryStage, RECOVERY_METRICS},
+    storage::{PatchSet, RecoveryUpdate, WorkingPatchSet},
+    types::{KeyLookup, Node, NodeKey},
+    Blake2Hasher, Database, DefaultTreeParams, MerkleTree, TreeEntry, TreeParams,
+};
+
+/// Handle to a Merkle tree during its recovery.
+#[derive(Debug)]
+pub struct MerkleTreeRecovery<DB, P: TreeParams = DefaultTreeParams> {
+    tree: MerkleTree<DB, P>,
+    recovered_version: u64,
+}
+
+impl<DB: Database> MerkleTreeRecovery<DB> {
+    /// Creates tree recovery with the default Blake2 hasher.
+    ///
+    /// # Errors
+    ///
+    /// Errors in the same situations as [`Self::with_hasher()`].
+    pub fn new(db: DB, recovered_version: u64) -> anyhow::Result<Self> {
+        Self::with_hasher(db, recovered_version, Blake2Hasher)
+    }
+}
+
+impl<DB: Database, P: TreeParams> MerkleTreeRecovery<DB, P> {
+    /// Loads a tree with the specified hasher.
+    ///
+    /// # Errors
+    ///
+    /// - Errors if the tree DB exists and it's not being recovered, or if it's being recovered
+    ///   for a different tree version.
+    /// - Errors if the hasher or basic tree parameters (e.g., the tree depth)
+    ///   do not match those of the tree loaded from the database.
+    pub fn with_hasher(
+        mut db: DB,
+        recovered_version: u64,
+        hasher: P::Hasher,
+    ) -> anyhow::Result<Self> {
+        let manifest = db.try_manifest().context("failed reading tree manifest")?;
+        if let Some(manifest) = manifest {
+            manifest.tags.ensure_consistency::<P>(&hasher)?;
+            anyhow::ensure!(
+                manifest.tags.is_recovering,
+                "Tree is expected to be in the process of recovery"
+            );
+            let expected_version = manifest.version_count.checked_sub(1);
+            anyhow::ensure!(
+                expected_version == Some(recovered_version),
+                "Requested to recover tree version {recovered_version}, but it is currently being recovered \
+                 for version {expected_version:?}"
+            );
+        } else {
+            // Initialize the tree with min / max guards, so that recovered chunks have neighbors to link to.
+            let mut patch = WorkingPatchSet::<P>::empty();
+            let update = patch.recover(RecoveryUpdate::for_empty_tree(recovered_version));
+            let patch = patch.finalize_recovery(&hasher, update);
+            db.apply_patch(patch)
+                .context("failed initializing tree recovery")?;
+        }
+
+        Ok(Self {
+            tree: MerkleTree { db, hasher },
+            recovered_version,
+        })
+    }
+
+    /// Returns the version of the tree being recovered.
+    pub fn recovered_version(&self) -> u64 {
+        self.recovered_version
+    }
+
+    /// Returns the root hash of the recovered tree at this point. The hash is only meaningful
+    /// after all snapshot chunks are recovered.
+    ///
+    /// # Errors
+    ///
+    /// Proxies database I/O errors.
+    pub fn root_hash(&self) -> anyhow::Result<H256> {
+        self.tree
+            .root_hash(self.recovered_version)?
+            .context("root of the tree being recovered is missing")
+    }
+
+    /// Returns the number of leaves in the tree (including the min / max guards and holes not recovered yet).
+    ///
+    /// # Errors
+    ///
+    /// Proxies database I/O errors.
+    pub fn leaf_count(&self) -> anyhow::Result<u64> {
+        let (_, leaf_count) = self
+            .tree
+            .root_info(self.recovered_version)?
+            .context("root of the tree being recovered is missing")?;
+        Ok(leaf_count)
+    }
+
+    /// Returns already recovered entries for the specified keys together with their leaf indices.
+    /// Can be used to check whether a certain chunk was recovered.
+    ///
+    /// # Errors
+    ///
+    /// Proxies database I/O errors.
+    pub fn entries(&self, keys: &[H256]) -> anyhow::Result<Vec<Option<(u64, TreeEntry)>>> {
+        let lookup = self
+            .tree
+            .db
+            .indices(self.recovered_version, keys)
+            .context("failed loading indices")?;
+        let existing_indices = lookup.iter().filter_map(|lookup| match lookup {
+            KeyLookup::Existing(idx) => Some(*idx),
+            KeyLookup::Missing { .. } => None,
+        });
+        // All nodes in the tree being recovered have the recovered version.
+        let leaf_keys: Vec<_> = existing_indices
+            .map(|index_on_level| NodeKey {
+                version: self.recovered_version,
+                nibble_count: leaf_nibbles::<P>(),
+                index_on_level,
+            })
+            .collect();
+        let mut leaves = self.tree.db.try_nodes(&leaf_keys)?.into_iter();
+
+        let entries = lookup.into_iter().map(|lookup| match lookup {
+            KeyLookup::Existing(idx) => match leaves.next().unwrap() {
+                Node::Leaf(leaf) => Some((
+                    idx,
+                    TreeEntry {
+                        key: leaf.key,
+                        value: leaf.value,
+                    },
+                )),
+                Node::Internal(_) => unreachable!(),
+            },
+            KeyLookup::Missing { .. } => None,
+        });
+        Ok(entries.collect())
+    }
+
+    /// Extends a tree with a chunk of entries together with their leaf indices.
+    ///
+    /// Entries must be ordered by increasing key, and must not overlap with the already recovered entries
+    /// (i.e., there must be no recovered keys between the first and the last key in the chunk).
+    /// Leaf indices must be distinct and not yet occupied.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an error if any of the requirements above is violated.
+    /// - Proxies database I/O errors.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(
+            recovered_version = self.recovered_version,
+            entries.len = entries.len(),
+            %entries.key_range = entries_key_range(entries),
+        ),
+    )]
+    pub fn extend(&mut self, entries: &[(u64, TreeEntry)]) -> anyhow::Result<()> {
+        tracing::debug!("Started extending tree");
+        RECOVERY_METRICS.chunk_size.observe(entries.len());
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        for window in entries.windows(2) {
+            let [(_, prev_entry), (_, entry)] = window else {
+                unreachable!();
+            };
+            anyhow::ensure!(
+                prev_entry.key < entry.key,
+                "Recovered entries must be ordered by increasing key; got {:?} after {:?}",
+                entry.key,
+                prev_entry.key
+            );
+        }
+        let (first_key, last_key) = (entries[0].1.key, entries[entries.len() - 1].1.key);
+        anyhow::ensure!(
+            first_key != TreeEntry::MIN_GUARD.key && last_key != TreeEntry::MAX_GUARD.key,
+            "Recovered entries must not contain min / max guard keys"
+        );
+
+        let stage_latency = RECOVERY_METRICS.stage_latency[&RecoveryStage::Extend].start();
+        let (mut patch, update) = self
+            .tree
+            .create_recovery_patch(self.recovered_version, entries)
+            .context("failed loading tree data")?;
+        let update = patch.recover(update);
+        let patch = patch.finalize_recovery(&self.tree.hasher, update);
+        let stage_latency = stage_latency.observe();
+        tracing::debug!("Finished processing entries; took {stage_latency:?}");
+
+        let stage_latency = RECOVERY_METRICS.stage_latency[&RecoveryStage::ApplyPatch].start();
+        self.tree
+            .db
+            .apply_patch(patch)
+            .context("failed persisting tree changes")?;
+        let stage_latency = stage_latency.observe();
+        tracing::debug!("Finished persisting to DB; took {stage_latency:?}");
+        Ok(())
+    }
+
+    /// Finalizes the recovery process marking it as complete in the tree manifest.
+    ///
+    /// # Errors
+    ///
+    /// Proxies database I/O errors.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(recovered_version = self.recovered_version),
+    )]
+    pub fn finalize(self) -> anyhow::Result<DB> {
+        let mut db = self.tree.db;
+        let mut manifest = db
+            .try_manifest()?
+            .context("Merkle tree manifest disappeared")?;
+        manifest.tags.is_recovering = false;
+        db.apply_patch(PatchSet::from_manifest(manifest))?;
+        tracing::debug!("Updated tree manifest to mark recovery as complete");
+        Ok(db)
+    }
+}
+
+fn entries_key_range(entries: &[(u64, TreeEntry)]) -> String {
+    let (Some((_, first)), Some((_, last))) = (entries.first(), entries.last()) else {
+        return "(empty)".to_owned();
+    };
+    format!("{:0>64x}..={:0>64x}", first.key, last.key)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::{PatchSet, RocksDBWrapper};
+
+    fn generate_entries(count: u64) -> Vec<TreeEntry> {
+        (0..count)
+            .map(|i| TreeEntry {
+                // Multiplication by an odd constant is a bijection, so keys are distinct and non-zero.
+                key: H256::from_low_u64_be((i + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15)),
+                value: H256::from_low_u64_be(i + 1),
+            })
+            .collect()
+    }
+
+    /// Builds a reference tree and returns snapshot entries (sorted by key) and the reference root hash.
+    fn create_snapshot(count: u64) -> (Vec<(u64, TreeEntry)>, H256) {
+        let entries = generate_entries(count);
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        tree.extend(&entries[..count as usize / 2]).unwrap();
+        let output = tree.extend(&entries[count as usize / 2..]).unwrap();
+
+        let mut snapshot: Vec<_> = (2..).zip(entries).collect();
+        snapshot.sort_unstable_by_key(|(_, entry)| entry.key);
+        (snapshot, output.root_hash)
+    }
+
+    fn test_recovery_in_chunks(mut db: impl Database, chunk_size: usize) {
+        const RECOVERED_VERSION: u64 = 10;
+
+        let (snapshot, expected_root_hash) = create_snapshot(100);
+        let mut chunks: Vec<_> = snapshot.chunks(chunk_size).collect();
+        chunks.shuffle(&mut StdRng::seed_from_u64(123));
+
+        let mut recovery = MerkleTreeRecovery::new(&mut db, RECOVERED_VERSION).unwrap();
+        for (i, chunk) in chunks.iter().enumerate() {
+            recovery.extend(chunk).unwrap();
+            if i == chunks.len() / 2 {
+                // Emulate restarting recovery.
+                drop(recovery);
+                recovery = MerkleTreeRecovery::new(&mut db, RECOVERED_VERSION).unwrap();
+            }
+
+            let keys: Vec<_> = chunk.iter().map(|(_, entry)| entry.key).collect();
+            let recovered_entries = recovery.entries(&keys).unwrap();
+            let expected_entries: Vec<_> = chunk.iter().copied().map(Some).collect();
+            assert_eq!(recovered_entries, expected_entries);
+        }
+
+        assert_eq!(recovery.leaf_count().unwrap(), 102);
+        assert_eq!(recovery.root_hash().unwrap(), expected_root_hash);
+        recovery.finalize().unwrap();
+
+        let mut tree = MerkleTree::new(&mut db).unwrap();
+        assert_eq!(tree.latest_version().unwrap(), Some(RECOVERED_VERSION));
+        assert_eq!(tree.latest_root_hash().unwrap(), Some(expected_root_hash));
+        tree.verify_consistency(RECOVERED_VERSION).unwrap();
+
+        // Check that the recovered tree can be extended.
+        let new_entries = [
+            TreeEntry {
+                key: H256::repeat_byte(0x23),
+                value: H256::repeat_byte(1),
+            },
+            TreeEntry {
+                key: snapshot[0].1.key,
+                value: H256::zero(),
+            },
+        ];
+        let output = tree.extend(&new_entries).unwrap();
+        assert_eq!(output.leaf_count, 103);
+        tree.verify_consistency(RECOVERED_VERSION + 1).unwrap();
+    }
+
+    #[test]
+    fn recovery_in_chunks() {
+        for chunk_size in [1, 7, 30, 100] {
+            tracing::info!(chunk_size, "Testing recovery in chunks");
+            test_recovery_in_chunks(PatchSet::default(), chunk_size);
+        }
+    }
+
+    #[test]
+    fn recovery_in_chunks_with_rocksdb() {
+        for chunk_size in [1, 7, 30, 100] {
+            tracing::info!(chunk_size, "Testing recovery in chunks");
+            let temp_dir = TempDir::new().unwrap();
+            let db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+            test_recovery_in_chunks(db, chunk_size);
+        }
+    }
+
+    #[test]
+    fn recovering_empty_tree() {
+        let db = MerkleTreeRecovery::new(PatchSet::default(), 42)
+            .unwrap()
+            .finalize()
+            .unwrap();
+        let tree = MerkleTree::new(db).unwrap();
+        assert_eq!(tree.latest_version().unwrap(), Some(42));
+        assert_eq!(
+            tree.latest_root_hash().unwrap(),
+            Some(MerkleTree::<PatchSet>::empty_tree_hash())
+        );
+    }
+
+    #[test]
+    fn recovery_for_initialized_tree() {
+        let mut db = PatchSet::default();
+        MerkleTreeRecovery::new(&mut db, 123)
+            .unwrap()
+            .finalize()
+            .unwrap();
+        let err = MerkleTreeRecovery::new(db, 123).unwrap_err().to_string();
+        assert!(
+            err.contains("Tree is expected to be in the process of recovery"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn recovery_for_different_version() {
+        let mut db = PatchSet::default();
+        MerkleTreeRecovery::new(&mut db, 123).unwrap();
+        let err = MerkleTreeRecovery::new(&mut db, 42)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Requested to recover tree version 42"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn tree_cannot_be_accessed_during_recovery() {
+        let mut db = PatchSet::default();
+        MerkleTreeRecovery::new(&mut db, 1).unwrap();
+        let err = MerkleTree::new(&mut db).unwrap_err().to_string();
+        assert!(err.contains("being recovered"), "{err}");
+    }
+
+    #[test]
+    fn overlapping_chunks_are_rejected() {
+        let (snapshot, _) = create_snapshot(20);
+        let mut recovery = MerkleTreeRecovery::new(PatchSet::default(), 1).unwrap();
+        recovery.extend(&snapshot[5..10]).unwrap();
+
+        let err = recovery.extend(&snapshot[..6]).unwrap_err().to_string();
+        assert!(err.contains("overlap"), "{err}");
+        let err = recovery.extend(&snapshot[9..]).unwrap_err().to_string();
+        assert!(err.contains("overlap"), "{err}");
+        let err = recovery
+            .extend(&[snapshot[0], snapshot[15]])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("overlap"), "{err}");
+    }
+
+    #[test]
+    fn occupied_leaf_indices_are_rejected() {
+        let (snapshot, _) = create_snapshot(20);
+        let mut recovery = MerkleTreeRecovery::new(PatchSet::default(), 1).unwrap();
+        recovery.extend(&snapshot[..5]).unwrap();
+
+        let (occupied_idx, _) = snapshot[0];
+        let (_, entry) = snapshot[10];
+        let err = recovery
+            .extend(&[(occupied_idx, entry)])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("already occupied"), "{err}");
+    }
+}
@@ -6,7 +6,7 @@ use std::{
 
 use zksync_basic_types::H256;
 
-pub(crate) use self::patch::{TreeUpdate, WorkingPatchSet};
+pub(crate) use self::patch::{RecoveryUpdate, TreeUpdate, WorkingPatchSet};
 pub use self::rocksdb::{MerkleTreeColumnFamily, RocksDBWrapper};
 use crate::{
     errors::{DeserializeContext, DeserializeError, DeserializeErrorKind},
@@ -88,6 +88,58 @@ pub trait Database: Send + Sync {
     ) -> anyhow::Result<()>;
 }
 
+/// Information about pruned nodes and stale keys used by [`PruneDatabase::prune()`].
+#[derive(Debug, Clone)]
+pub struct PrunePatchSet {
+    /// Keys of the nodes to be removed. All keys must have versions less than the minimum retained version.
+    pruned_node_keys: Vec<NodeKey>,
+    /// Range of replacing versions for stale keys that need to be removed.
+    deleted_stale_key_versions: ops::Range<u64>,
+}
+
+impl PrunePatchSet {
+    pub(crate) fn new(
+        pruned_node_keys: Vec<NodeKey>,
+        deleted_stale_key_versions: ops::Range<u64>,
+    ) -> Self {
+        Self {
+            pruned_node_keys,
+            deleted_stale_key_versions,
+        }
+    }
+}
+
+/// Functionality to prune past versions of the Merkle tree.
+pub trait PruneDatabase: Database {
+    /// Returns the minimum new version for stale keys present in this database, or `None`
+    /// if there are no stale keys.
+    fn min_stale_key_version(&self) -> Option<u64>;
+
+    /// Returns a list of node keys obsoleted in the specified `version` of the tree.
+    fn stale_keys(&self, version: u64) -> Vec<NodeKey>;
+
+    /// Atomically prunes the tree and removes the corresponding stale keys.
+    ///
+    /// # Errors
+    ///
+    /// Propagates database I/O errors.
+    fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()>;
+}
+
+impl<DB: PruneDatabase + ?Sized> PruneDatabase for &mut DB {
+    fn min_stale_key_version(&self) -> Option<u64> {
+        (**self).min_stale_key_version()
+    }
+
+    fn stale_keys(&self, version: u64) -> Vec<NodeKey> {
+        (**self).stale_keys(version)
+    }
+
+    fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
+        (**self).prune(patch)
+    }
+}
+
 impl<DB: Database + ?Sized> Database for &mut DB {
     fn indices(&self, version: u64, keys: &[H256]) -> Result<Vec<KeyLookup>, DeserializeError> {
         (**self).indices(version, keys)
@@ -136,11 +188,12 @@ struct PartialPatchSet {
 }
 
 impl PartialPatchSet {
-    fn root(&self) -> Root {
-        Root {
+    /// Returns `None` if the root was pruned.
+    fn root(&self) -> Option<Root> {
+        Some(Root {
             leaf_count: self.leaf_count,
-            root_node: self.internal[0][&0].clone(),
-        }
+            root_node: self.internal[0].get(&0)?.clone(),
+        })
     }
 
     fn node(&self, nibble_count: u8, index_on_level: u64) -> Option<Node> {
@@ -156,6 +209,33 @@ impl PartialPatchSet {
         })
     }
 
+    fn contains_node(&self, nibble_count: u8, index_on_level: u64) -> bool {
+        let nibble_count = usize::from(nibble_count);
+        if nibble_count < self.internal.len() {
+            self.internal[nibble_count].contains_key(&index_on_level)
+        } else {
+            self.leaves.contains_key(&index_on_level)
+        }
+    }
+
+    fn remove_node(&mut self, nibble_count: u8, index_on_level: u64) {
+        let nibble_count = usize::from(nibble_count);
+        if nibble_count < self.internal.len() {
+            self.internal[nibble_count].remove(&index_on_level);
+        } else {
+            self.leaves.remove(&index_on_level);
+        }
+    }
+
+    /// Merges nodes from a patch updating the same tree version in place.
+    fn merge(&mut self, other: Self) {
+        self.leaf_count = other.leaf_count;
+        for (level, other_level) in self.internal.iter_mut().zip(other.internal) {
+            level.extend(other_level);
+        }
+        self.leaves.extend(other.leaves);
+    }
+
     fn total_internal_nodes(&self) -> usize {
         self.internal.iter().map(HashMap::len).sum()
     }
@@ -168,10 +248,22 @@ pub struct PatchSet {
     patches_by_version: HashMap<u64, PartialPatchSet>,
     // We maintain a joint index for all versions to make it easier to use `PatchSet` as a `Database` or in a `Patched` wrapper.
     sorted_new_leaves: BTreeMap<H256, InsertedKeyEntry>,
-    // TODO: stale keys
+    /// Keys of the nodes that are no longer referenced starting from the specified version.
+    stale_keys_by_version: HashMap<u64, Vec<NodeKey>>,
+    /// Version updated in place by this patch (used during recovery). If present, `patches_by_version`
+    /// contains only this version, and nodes in the patch are merged with the existing nodes for the version.
+    updated_version: Option<u64>,
 }
 
 impl PatchSet {
+    /// Creates a patch that only updates the tree manifest.
+    pub(crate) fn from_manifest(manifest: Manifest) -> Self {
+        Self {
+            manifest,
+            ..Self::default()
+        }
+    }
+
     fn is_new_version(&self, version: u64) -> bool {
         version >= self.manifest.version_count // this patch truncates `version`
             || self.patches_by_version.contains_key(&version)
@@ -245,7 +337,7 @@ impl Database for PatchSet {
         Ok(self
             .patches_by_version
             .get(&version)
-            .map(PartialPatchSet::root))
+            .and_then(PartialPatchSet::root))
     }
 
     fn try_nodes(&self, keys: &[NodeKey]) -> Result<Vec<Node>, DeserializeError> {
@@ -272,8 +364,20 @@ impl Database for PatchSet {
         );
 
         self.manifest = patch.manifest;
-        self.patches_by_version.extend(patch.patches_by_version);
+        for (version, partial_patch) in patch.patches_by_version {
+            let existing_patch = self.patches_by_version.get_mut(&version);
+            match existing_patch {
+                Some(existing_patch) if patch.updated_version == Some(version) => {
+                    existing_patch.merge(partial_patch);
+                }
+                _ => {
+                    self.patches_by_version.insert(version, partial_patch);
+                }
+            }
+        }
         self.sorted_new_leaves.extend(patch.sorted_new_leaves);
+        self.stale_keys_by_version
+            .extend(patch.stale_keys_by_version);
         Ok(())
     }
 
@@ -288,12 +392,44 @@ impl Database for PatchSet {
         // This requires a full scan, but we assume there aren't that many data in a patch (it's mostly used as a `Database` for testing).
         self.sorted_new_leaves
             .retain(|_, entry| entry.inserted_at < new_version_count);
+        // Stale keys for the truncated versions must be removed; otherwise, they may lead to pruning nodes
+        // that are still referenced by the retained versions.
+        self.stale_keys_by_version
+            .retain(|&version, _| version < new_version_count);
 
         self.manifest = manifest;
         Ok(())
     }
 }
 
+impl PruneDatabase for PatchSet {
+    fn min_stale_key_version(&self) -> Option<u64> {
+        self.stale_keys_by_version
+            .iter()
+            .filter_map(|(&version, keys)| (!keys.is_empty()).then_some(version))
+            .min()
+    }
+
+    fn stale_keys(&self, version: u64) -> Vec<NodeKey> {
+        self.stale_keys_by_version
+            .get(&version)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
+        for key in &patch.pruned_node_keys {
+            if let Some(partial_patch) = self.patches_by_version.get_mut(&key.version) {
+                partial_patch.remove_node(key.nibble_count, key.index_on_level);
+            }
+        }
+
+        self.stale_keys_by_version
+            .retain(|version, _| !patch.deleted_stale_key_versions.contains(version));
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub struct Patched<DB> {
     inner: DB,
@@ -471,6 +607,7 @@ impl<DB: Database> Database for Patched<DB> {
         // in the following flushes.
         if patch.patches_by_version.is_empty() {
             assert!(patch.sorted_new_leaves.is_empty());
+            assert!(patch.stale_keys_by_version.is_empty());
 
             self.patch = None;
             self.inner.truncate(manifest, truncated_versions)?;
@@ -478,3 +615,28 @@ impl<DB: Database> Database for Patched<DB> {
         Ok(())
     }
 }
+
+impl<DB: PruneDatabase> PruneDatabase for Patched<DB> {
+    fn min_stale_key_version(&self) -> Option<u64> {
+        // Stale keys in the patch always have greater versions than ones in the underlying DB.
+        self.inner
+            .min_stale_key_version()
+            .or_else(|| self.patch.as_ref()?.min_stale_key_version())
+    }
+
+    fn stale_keys(&self, version: u64) -> Vec<NodeKey> {
+        if let Some(patch) = &self.patch {
+            if patch.is_new_version(version) {
+                return patch.stale_keys(version);
+            }
+        }
+        self.inner.stale_keys(version)
+    }
+
+    fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
+        if let Some(existing_patch) = &mut self.patch {
+            existing_patch.prune(patch.clone())?;
+        }
+        self.inner.prune(patch)
+    }
+}
@@ -134,6 +134,41 @@ pub(crate) struct FinalTreeUpdate {
     pub(super) sorted_new_leaves: BTreeMap<H256, InsertedKeyEntry>,
 }
 
+/// Information about a chunk of leaves inserted during tree recovery.
+#[must_use = "Should be applied to a `PartialPatchSet`"]
+#[derive(Debug)]
+pub(crate) struct RecoveryUpdate {
+    version: u64,
+    sorted_new_leaves: BTreeMap<H256, InsertedKeyEntry>,
+    /// Inserted leaves together with their indices.
+    leaves: Vec<(u64, Leaf)>,
+    /// Index of the already recovered leaf immediately preceding the inserted leaves by key.
+    /// `None` if the tree is initialized.
+    prev_index: Option<u64>,
+}
+
+impl RecoveryUpdate {
+    /// Creates an update inserting min / max guards into the empty tree being recovered.
+    pub(crate) fn for_empty_tree(version: u64) -> Self {
+        let guards = [(0, Leaf::MIN_GUARD), (1, Leaf::MAX_GUARD)];
+        Self {
+            version,
+            sorted_new_leaves: guards
+                .iter()
+                .map(|&(index, leaf)| {
+                    let entry = InsertedKeyEntry {
+                        index,
+                        inserted_at: version,
+                    };
+                    (leaf.key, entry)
+                })
+                .collect(),
+            leaves: guards.into(),
+            prev_index: None,
+        }
+    }
+}
+
 impl PartialPatchSet {
     /// Updates ancestor's `ChildRef` version for all loaded internal nodes. This should be called before adding new leaves
     /// to the tree; it works because the loaded leaves are exactly the leaves for which ancestor versions must be updated.
@@ -163,6 +198,45 @@ impl PartialPatchSet {
         }
     }
 
+    /// Adds / updates internal nodes on the paths to new leaves. `new_indexes` must start from the leaf count before the insertion.
+    fn add_internal_nodes<P: TreeParams>(
+        &mut self,
+        new_indexes: ops::RangeInclusive<u64>,
+        version: u64,
+    ) {
+        for (i, internal_level) in self.internal.iter_mut().enumerate() {
+            let nibble_count = i as u8;
+            let child_depth =
+                (max_nibbles_for_internal_node::<P>() - nibble_count) * P::INTERNAL_NODE_DEPTH;
+            let first_index_on_level =
+                (new_indexes.start() >> child_depth) / u64::from(max_node_children::<P>());
+            let last_child_index = new_indexes.end() >> child_depth;
+            let last_index_on_level = last_child_index / u64::from(max_node_children::<P>());
+
+            // Only `first_index_on_level` may exist already; all others are necessarily new.
+            let mut start_idx = first_index_on_level;
+            if let Some(parent) = internal_level.get_mut(&first_index_on_level) {
+                let expected_len = if last_index_on_level == first_index_on_level {
+                    (last_child_index % u64::from(max_node_children::<P>())) as usize + 1
+                } else {
+                    max_node_children::<P>().into()
+                };
+                parent.ensure_len(expected_len, version);
+                start_idx += 1;
+            }
+
+            let new_nodes = (start_idx..=last_index_on_level).map(|idx| {
+                let expected_len = if idx == last_index_on_level {
+                    (last_child_index % u64::from(max_node_children::<P>())) as usize + 1
+                } else {
+                    max_node_children::<P>().into()
+                };
+                (idx, InternalNode::new(expected_len, version))
+            });
+            internal_level.extend(new_nodes);
+        }
+    }
+
     fn remove_readonly_nodes(&mut self, updated_version: u64) -> usize {
         // We always retain the root node (i.e., the only node in `self.internal[0]`), even if the tree hasn't changed.
         let mut removed_node_count = 0;
@@ -183,6 +257,8 @@ impl PartialPatchSet {
 #[derive(Debug)]
 pub(crate) struct WorkingPatchSet<P> {
     inner: PartialPatchSet,
+    /// Keys of all nodes loaded from the database (except for the root). Used to compute stale keys on finalization.
+    loaded_keys: Vec<NodeKey>,
     _params: PhantomData<P>,
 }
 
@@ -204,6 +280,7 @@ impl<P: TreeParams> WorkingPatchSet<P> {
                 internal,
                 leaves: HashMap::new(),
             },
+            loaded_keys: vec![],
             _params: PhantomData,
         }
     }
@@ -218,9 +295,20 @@ impl<P: TreeParams> WorkingPatchSet<P> {
         &mut self,
         db: &impl Database,
         leaf_indices: impl Iterator<Item = u64> + Clone,
+    ) -> anyhow::Result<()> {
+        self.load_nodes_up_to(db, leaf_indices, leaf_nibbles::<P>())
+    }
+
+    /// Loads nodes on the paths to the specified leaves, down to (and including) the level with `max_nibble_count`.
+    /// `leaf_indices` must be sorted.
+    fn load_nodes_up_to(
+        &mut self,
+        db: &impl Database,
+        leaf_indices: impl Iterator<Item = u64> + Clone,
+        max_nibble_count: u8,
     ) -> anyhow::Result<()> {
         let this = &mut self.inner;
-        for nibble_count in 1..=leaf_nibbles::<P>() {
+        for nibble_count in 1..=max_nibble_count {
             let bit_shift = (leaf_nibbles::<P>() - nibble_count) * P::INTERNAL_NODE_DEPTH;
 
             let mut prev_index_on_level = None;
@@ -246,6 +334,7 @@ impl<P: TreeParams> WorkingPatchSet<P> {
             });
             let (indices, requested_keys): (Vec<_>, Vec<_>) = requested_keys.unzip();
             let loaded_nodes = db.try_nodes(&requested_keys)?;
+            self.loaded_keys.extend_from_slice(&requested_keys);
 
             if nibble_count == leaf_nibbles::<P>() {
                 this.leaves = loaded_nodes
@@ -281,6 +370,39 @@ impl<P: TreeParams> WorkingPatchSet<P> {
         Ok(())
     }
 
+    /// Loads a single leaf. All internal nodes on the path to the leaf must be loaded beforehand.
+    fn load_leaf(&mut self, db: &impl Database, idx: u64) -> anyhow::Result<()> {
+        let this = &mut self.inner;
+        let parent_level = &this.internal[usize::from(max_nibbles_for_internal_node::<P>())];
+        let parent = &parent_level[&(idx >> P::INTERNAL_NODE_DEPTH)];
+        let child_ref = parent.child_ref((idx % u64::from(max_node_children::<P>())) as usize);
+        let key = NodeKey {
+            version: child_ref.version,
+            nibble_count: leaf_nibbles::<P>(),
+            index_on_level: idx,
+        };
+
+        let leaf = match db.try_nodes(&[key])?.pop().unwrap() {
+            Node::Leaf(leaf) => leaf,
+            Node::Internal(_) => unreachable!(),
+        };
+        this.leaves.insert(idx, leaf);
+        self.loaded_keys.push(key);
+        Ok(())
+    }
+
+    /// Checks whether the leaf with the specified index is missing in the tree (i.e., it is a hole
+    /// left by recovery). Internal nodes on the path to the leaf must be loaded.
+    fn is_missing_leaf(&self, idx: u64) -> bool {
+        if idx >= self.inner.leaf_count {
+            return true;
+        }
+        let parent_level = &self.inner.internal[usize::from(max_nibbles_for_internal_node::<P>())];
+        let parent = &parent_level[&(idx >> P::INTERNAL_NODE_DEPTH)];
+        let child_ref = parent.child_ref((idx % u64::from(max_node_children::<P>())) as usize);
+        child_ref.hash.is_zero()
+    }
+
     pub(crate) fn loaded_leaves_count(&self) -> usize {
         self.inner.leaves.len()
     }
@@ -441,39 +563,33 @@ impl<P: TreeParams> WorkingPatchSet<P> {
             this.leaves.extend(new_indexes.clone().zip(inserts));
             this.leaf_count = *new_indexes.end() + 1;
 
-            // Add / update internal nodes.
-            for (i, internal_level) in this.internal.iter_mut().enumerate() {
-                let nibble_count = i as u8;
-                let child_depth =
-                    (max_nibbles_for_internal_node::<P>() - nibble_count) * P::INTERNAL_NODE_DEPTH;
-                let first_index_on_level =
-                    (new_indexes.start() >> child_depth) / u64::from(max_node_children::<P>());
-                let last_child_index = new_indexes.end() >> child_depth;
-                let last_index_on_level = last_child_index / u64::from(max_node_children::<P>());
-
-                // Only `first_index_on_level` may exist already; all others are necessarily new.
-                let mut start_idx = first_index_on_level;
-                if let Some(parent) = internal_level.get_mut(&first_index_on_level) {
-                    let expected_len = if last_index_on_level == first_index_on_level {
-                        (last_child_index % u64::from(max_node_children::<P>())) as usize + 1
-                    } else {
-                        max_node_children::<P>().into()
-                    };
-                    parent.ensure_len(expected_len, version);
-                    start_idx += 1;
-                }
+            this.add_internal_nodes::<P>(new_indexes, version);
+        }
 
-                let new_nodes = (start_idx..=last_index_on_level).map(|idx| {
-                    let expected_len = if idx == last_index_on_level {
-                        (last_child_index % u64::from(max_node_children::<P>())) as usize + 1
-                    } else {
-                        max_node_children::<P>().into()
-                    };
-                    (idx, InternalNode::new(expected_len, version))
-                });
-                internal_level.extend(new_nodes);
-            }
+        FinalTreeUpdate {
+            version,
+            sorted_new_leaves: update.sorted_new_leaves,
+        }
+    }
+
+    pub(crate) fn recover(&mut self, update: RecoveryUpdate) -> FinalTreeUpdate {
+        let this = &mut self.inner;
+        let version = update.version;
+
+        if let (Some(prev_idx), Some(&(first_idx, _))) = (update.prev_index, update.leaves.first())
+        {
+            this.leaves.get_mut(&prev_idx).unwrap().next_index = first_idx;
+        }
+
+        let max_idx = update.leaves.iter().map(|(idx, _)| *idx).max();
+        if let Some(max_idx) = max_idx.filter(|&idx| idx >= this.leaf_count) {
+            // Leaves with indices in `leaf_count..max_idx` not present in `update` are holes; they will be filled
+            // by the following updates. Until then, they have zero hashes in the parent nodes.
+            let first_new_idx = this.leaf_count;
+            this.leaf_count = max_idx + 1;
+            this.add_internal_nodes::<P>(first_new_idx..=max_idx, version);
         }
+        this.leaves.extend(update.leaves);
 
         FinalTreeUpdate {
             version,
@@ -481,6 +597,17 @@ impl<P: TreeParams> WorkingPatchSet<P> {
         }
     }
 
+    /// Same as [`Self::finalize()`], but for a recovery update. Since recovery updates nodes of a single version in place,
+    /// the returned patch doesn't contain stale keys and keeps the tree marked as being recovered.
+    pub(crate) fn finalize_recovery(self, hasher: &P::Hasher, update: FinalTreeUpdate) -> PatchSet {
+        let version = update.version;
+        let (mut patch, _) = self.finalize(hasher, update);
+        patch.stale_keys_by_version.clear();
+        patch.updated_version = Some(version);
+        patch.manifest.tags.is_recovering = true;
+        patch
+    }
+
     pub(crate) fn finalize(
         self,
         hasher: &P::Hasher,
@@ -520,6 +647,17 @@ impl<P: TreeParams> WorkingPatchSet<P> {
             root_hash,
         };
 
+        // All loaded nodes retained after the update (i.e., not readonly ones) are rewritten in the new version,
+        // so their old keys become stale. The same applies to the root of the previous version.
+        let mut stale_keys: Vec<_> = self
+            .loaded_keys
+            .into_iter()
+            .filter(|key| this.contains_node(key.nibble_count, key.index_on_level))
+            .collect();
+        if let Some(prev_version) = update.version.checked_sub(1) {
+            stale_keys.push(NodeKey::root(prev_version));
+        }
+
         // Release excessive capacity occupied by the partial patch set. We'll never modify it inside a `PatchSet`.
         this.leaves.shrink_to_fit();
         for internal_level in &mut this.internal {
@@ -533,6 +671,8 @@ impl<P: TreeParams> WorkingPatchSet<P> {
             },
             patches_by_version: HashMap::from([(update.version, this)]),
             sorted_new_leaves: update.sorted_new_leaves,
+            stale_keys_by_version: HashMap::from([(update.version, stale_keys)]),
+            updated_version: None,
         };
         (patch, output)
     }
@@ -713,4 +853,116 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
             },
         ))
     }
+
+    /// Loads data for inserting the specified entries into a tree being recovered. `entries` must be non-empty,
+    /// sorted by key, and must not contain guards.
+    pub(crate) fn create_recovery_patch(
+        &self,
+        version: u64,
+        entries: &[(u64, TreeEntry)],
+    ) -> anyhow::Result<(WorkingPatchSet<P>, RecoveryUpdate)> {
+        let root = self.db.try_root(version)?.ok_or_else(|| {
+            DeserializeError::from(DeserializeErrorKind::MissingNode)
+                .with_context(DeserializeContext::Node(NodeKey::root(version)))
+        })?;
+        let (first_key, last_key) = (entries[0].1.key, entries[entries.len() - 1].1.key);
+
+        let key_lookup_latency = METRICS.load_nodes_latency[&LoadStage::KeyLookup].start();
+        let lookup = self
+            .db
+            .indices(version, &[first_key, last_key])
+            .context("failed loading indices")?;
+        let elapsed = key_lookup_latency.observe();
+        tracing::debug!(?elapsed, "loaded lookup info");
+
+        // Entries must fit between two adjacent recovered leaves; otherwise, we'd need to update `next_index`
+        // for multiple existing leaves, and the entries would overlap with the already recovered ones.
+        let (prev_index, chunk_next_index) = match (&lookup[0], &lookup[1]) {
+            (
+                KeyLookup::Missing {
+                    prev_key_and_index: first_prev,
+                    next_key_and_index: first_next,
+                },
+                KeyLookup::Missing {
+                    prev_key_and_index: last_prev,
+                    next_key_and_index: last_next,
+                },
+            ) if first_prev == last_prev && first_next == last_next => (first_prev.1, first_next.1),
+            _ => anyhow::bail!(
+                "Recovered entries with keys {first_key:?}..={last_key:?} overlap with the already recovered entries"
+            ),
+        };
+
+        let mut sorted_new_leaves = BTreeMap::new();
+        let mut leaves = Vec::with_capacity(entries.len());
+        let mut distinct_indices = BTreeSet::from([prev_index]);
+        for (i, (idx, entry)) in entries.iter().enumerate() {
+            anyhow::ensure!(*idx >= 2, "Leaf index {idx} is reserved for guards");
+            let is_new = sorted_new_leaves
+                .insert(
+                    entry.key,
+                    InsertedKeyEntry {
+                        index: *idx,
+                        inserted_at: version,
+                    },
+                )
+                .is_none();
+            anyhow::ensure!(
+                is_new,
+                "Attempting to insert duplicate keys into a tree; please deduplicate keys on the caller side"
+            );
+
+            if *idx < root.leaf_count {
+                distinct_indices.insert(*idx);
+            }
+            let next_index = entries.get(i + 1).map_or(chunk_next_index, |(idx, _)| *idx);
+            let leaf = Leaf {
+                key: entry.key,
+                value: entry.value,
+                next_index,
+            };
+            leaves.push((*idx, leaf));
+        }
+
+        let max_idx = leaves.iter().map(|(idx, _)| *idx).max().unwrap();
+        if max_idx >= root.leaf_count {
+            // Need to load the ancestors of the latest existing leaf so that new ancestors can be correctly inserted.
+            distinct_indices.insert(root.leaf_count - 1);
+        }
+
+        let tree_nodes_latency = METRICS.load_nodes_latency[&LoadStage::TreeNodes].start();
+        let mut patch = WorkingPatchSet::new(root);
+        // Only internal nodes are loaded for the new leaves; leaves themselves are missing in the tree.
+        patch.load_nodes_up_to(
+            &self.db,
+            distinct_indices.iter().copied(),
+            max_nibbles_for_internal_node::<P>(),
+        )?;
+        patch.load_leaf(&self.db, prev_index)?;
+        let elapsed = tree_nodes_latency.observe();
+        tracing::debug!(
+            ?elapsed,
+            distinct_indices.len = distinct_indices.len(),
+            "loaded tree nodes"
+        );
+
+        let mut sorted_indices: Vec<_> = leaves.iter().map(|(idx, _)| *idx).collect();
+        sorted_indices.sort_unstable();
+        for (i, &idx) in sorted_indices.iter().enumerate() {
+            anyhow::ensure!(
+                sorted_indices.get(i + 1) != Some(&idx) && patch.is_missing_leaf(idx),
+                "Leaf index {idx} is already occupied"
+            );
+        }
+
+        Ok((
+            patch,
+            RecoveryUpdate {
+                version,
+                sorted_new_leaves,
+                leaves,
+                prev_index: Some(prev_index),
+            },
+        ))
+    }
 }
@@ -10,7 +10,7 @@ use zksync_storage::{db::NamedColumnFamily, rocksdb, rocksdb::DBPinnableSlice, R
 use crate::{
     errors::{DeserializeContext, DeserializeErrorKind},
     metrics::{LoadStage, METRICS},
-    storage::{InsertedKeyEntry, PartialPatchSet, PatchSet},
+    storage::{InsertedKeyEntry, PartialPatchSet, PatchSet, PruneDatabase, PrunePatchSet},
     types::{InternalNode, KeyLookup, Leaf, Manifest, Node, NodeKey, Root},
     Database, DeserializeError,
 };
@@ -25,6 +25,15 @@ impl NodeKey {
         buffer[9..].copy_from_slice(&self.index_on_level.to_be_bytes());
         buffer
     }
+
+    fn from_db_key(raw: &[u8]) -> Self {
+        assert_eq!(raw.len(), Self::DB_KEY_LEN, "unexpected node key length");
+        Self {
+            version: u64::from_be_bytes(raw[..8].try_into().unwrap()),
+            nibble_count: raw[8],
+            index_on_level: u64::from_be_bytes(raw[9..].try_into().unwrap()),
+        }
+    }
 }
 
 /// RocksDB column families used by the tree.
@@ -35,17 +44,20 @@ pub enum MerkleTreeColumnFamily {
     Tree,
     /// Resolves keys to (index, version) tuples.
     KeyIndices,
-    // TODO: stale keys
+    /// Column family containing stale node keys in the form of `version ++ NodeKey` with empty values,
+    /// where `version` is the tree version in which the node became stale.
+    StaleKeys,
 }
 
 impl NamedColumnFamily for MerkleTreeColumnFamily {
     const DB_NAME: &'static str = "zkos_merkle_tree";
-    const ALL: &'static [Self] = &[Self::Tree, Self::KeyIndices];
+    const ALL: &'static [Self] = &[Self::Tree, Self::KeyIndices, Self::StaleKeys];
 
     fn name(&self) -> &'static str {
         match self {
             Self::Tree => "default",
             Self::KeyIndices => "key_indices",
+            Self::StaleKeys => "stale_keys",
         }
     }
 
@@ -329,6 +341,7 @@ impl Database for RocksDBWrapper {
 
         let copied_hashes = patch.copied_hashes_count();
         let new_leaves = patch.sorted_new_leaves.len();
+        let stale_keys_count: usize = patch.stale_keys_by_version.values().map(Vec::len).sum();
         let total_leaves: usize = patch
             .patches_by_version
             .values()
@@ -355,15 +368,19 @@ impl Database for RocksDBWrapper {
 
         for (version, sub_patch) in patch.patches_by_version {
             let root_key = NodeKey::root(version);
-            // Delete the key range corresponding to the entire new version. This removes
-            // potential garbage left after reverting the tree to a previous version.
-            let next_root_key = NodeKey::root(version + 1);
-            let keys_to_delete = &root_key.as_db_key()[..]..&next_root_key.as_db_key()[..];
-            write_batch.delete_range_cf(tree_cf, keys_to_delete);
+            if patch.updated_version != Some(version) {
+                // Delete the key range corresponding to the entire new version. This removes
+                // potential garbage left after reverting the tree to a previous version.
+                let next_root_key = NodeKey::root(version + 1);
+                let keys_to_delete = &root_key.as_db_key()[..]..&next_root_key.as_db_key()[..];
+                write_batch.delete_range_cf(tree_cf, keys_to_delete);
+            }
 
-            node_bytes.clear();
-            sub_patch.root().serialize(&mut node_bytes);
-            write_batch.put_cf(tree_cf, &root_key.as_db_key(), &node_bytes);
+            if let Some(root) = sub_patch.root() {
+                node_bytes.clear();
+                root.serialize(&mut node_bytes);
+                write_batch.put_cf(tree_cf, &root_key.as_db_key(), &node_bytes);
+            }
 
             // The root is serialized above, hence `skip(1)`
             for (i, level) in sub_patch.internal.into_iter().enumerate().skip(1) {
@@ -392,6 +409,15 @@ impl Database for RocksDBWrapper {
             }
         }
 
+        let stale_keys_cf = MerkleTreeColumnFamily::StaleKeys;
+        for (version, stale_keys) in patch.stale_keys_by_version {
+            for stale_key in stale_keys {
+                let mut raw_key = version.to_be_bytes().to_vec();
+                raw_key.extend_from_slice(&stale_key.as_db_key());
+                write_batch.put_cf(stale_keys_cf, &raw_key, &[]);
+            }
+        }
+
         METRICS
             .apply_patch_key_lookup_entries_count
             .observe(new_leaves);
@@ -399,12 +425,16 @@ impl Database for RocksDBWrapper {
         METRICS
             .apply_patch_internal_nodes_count
             .observe(total_internal_nodes);
+        METRICS
+            .apply_patch_stale_keys_count
+            .observe(stale_keys_count);
         METRICS.apply_patch_copied_hashes.observe(copied_hashes);
         tracing::debug!(
             total_size = write_batch.size_in_bytes(),
             new_leaves,
             total_leaves,
             total_internal_nodes,
+            stale_keys_count,
             copied_hashes,
             "writing to RocksDB"
         );
@@ -475,6 +505,56 @@ impl Database for RocksDBWrapper {
             first_new_leaf_index = new_leaf_count;
         }
 
+        // Remove "future" stale keys since otherwise they may be used in future pruning and lead
+        // to non-obsolete tree nodes getting removed.
+        let first_truncated_version = &manifest.version_count.to_be_bytes() as &[_];
+        let last_truncated_version = &truncated_versions.end.to_be_bytes();
+        write_batch.delete_range_cf(
+            MerkleTreeColumnFamily::StaleKeys,
+            first_truncated_version..last_truncated_version,
+        );
+
+        self.db
+            .write(write_batch)
+            .context("Failed writing a batch to RocksDB")?;
+        Ok(())
+    }
+}
+
+impl PruneDatabase for RocksDBWrapper {
+    fn min_stale_key_version(&self) -> Option<u64> {
+        let stale_keys_cf = MerkleTreeColumnFamily::StaleKeys;
+        let (raw_key, _) = self.db.prefix_iterator_cf(stale_keys_cf, &[]).next()?;
+        let version_prefix: [u8; 8] = raw_key[..8].try_into().unwrap();
+        Some(u64::from_be_bytes(version_prefix))
+    }
+
+    fn stale_keys(&self, version: u64) -> Vec<NodeKey> {
+        let stale_keys_cf = MerkleTreeColumnFamily::StaleKeys;
+        let version_prefix = version.to_be_bytes();
+        let keys = self
+            .db
+            .prefix_iterator_cf(stale_keys_cf, &version_prefix)
+            .map(|(raw_key, _)| {
+                debug_assert_eq!(raw_key[..8], version_prefix);
+                NodeKey::from_db_key(&raw_key[8..])
+            });
+        keys.collect()
+    }
+
+    fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
+        let mut write_batch = self.db.new_write_batch();
+
+        let tree_cf = MerkleTreeColumnFamily::Tree;
+        for pruned_key in patch.pruned_node_keys {
+            write_batch.delete_cf(tree_cf, &pruned_key.as_db_key());
+        }
+
+        let stale_keys_cf = MerkleTreeColumnFamily::StaleKeys;
+        let first_version = &patch.deleted_stale_key_versions.start.to_be_bytes() as &[_];
+        let last_version = &patch.deleted_stale_key_versions.end.to_be_bytes();
+        write_batch.delete_range_cf(stale_keys_cf, first_version..last_version);
+
         self.db
             .write(write_batch)
             .context("Failed writing a batch to RocksDB")?;
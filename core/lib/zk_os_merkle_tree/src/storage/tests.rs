@@ -134,7 +134,7 @@ fn test_creating_empty_tree<P: TreeParams<Hasher = Blake2Hasher>>() {
         }
 
         assert_eq!(patch.leaf_count, 2);
-        assert_eq!(patch.root().root_node.children.len(), 1);
+        assert_eq!(patch.root().unwrap().root_node.children.len(), 1);
     }
 
     let (patch, ..) = patch.finalize(&Blake2Hasher, final_update);
@@ -264,7 +264,7 @@ where
     let (new_patch, ..) = patch.finalize(&Blake2Hasher, final_update);
     assert_eq!(new_patch.manifest.version_count, 2);
     assert_eq!(new_patch.patches_by_version.len(), 1);
-    let root = new_patch.patches_by_version[&1].root();
+    let root = new_patch.patches_by_version[&1].root().unwrap();
     let expected_root_hash: H256 =
         "0x08da20879eebed16fbd14e50b427bb97c8737aa860e6519877757e238df83a15"
             .parse()
@@ -494,7 +494,7 @@ fn patch_is_reduced_for_mixed_workload() {
         HashSet::from([0, 1])
     );
 
-    let _ = patch.update(update);
+    let final_update = patch.update(update);
     let expected_leaf = Leaf {
         key: H256::repeat_byte(1),
         value: H256::zero(),
@@ -504,6 +504,17 @@ fn patch_is_reduced_for_mixed_workload() {
     for internal_level in &patch.inner().internal {
         assert_eq!(internal_level.len(), 1, "{patch:#?}");
     }
+
+    // Only the updated leaf and its ancestors (including the root) should become stale; readonly guards are not stale.
+    let (patch, _) = patch.finalize(&Blake2Hasher, final_update);
+    let leaf_nibbles = leaf_nibbles::<DefaultTreeParams>();
+    let expected_stale_keys = (0..=leaf_nibbles).map(|nibble_count| NodeKey {
+        version: 0,
+        nibble_count,
+        index_on_level: if nibble_count == leaf_nibbles { 2 } else { 0 },
+    });
+    let stale_keys: HashSet<_> = patch.stale_keys(1).into_iter().collect();
+    assert_eq!(stale_keys, expected_stale_keys.collect());
 }
 
 fn assert_empty_tree(db: &impl Database) {
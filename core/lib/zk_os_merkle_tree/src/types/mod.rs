@@ -226,6 +226,8 @@ pub(crate) struct TreeTags {
     pub depth: u8,
     pub internal_node_depth: u8,
     pub hasher: String,
+    /// Set for the tree being recovered from a snapshot. Such a tree cannot be accessed using ordinary tree APIs.
+    pub is_recovering: bool,
 }
 
 impl Default for TreeTags {
@@ -243,6 +245,7 @@ impl TreeTags {
             depth: P::TREE_DEPTH,
             internal_node_depth: P::INTERNAL_NODE_DEPTH,
             hasher: hasher.name().to_owned(),
+            is_recovering: false,
         }
     }
 
@@ -287,6 +290,18 @@ pub struct Manifest {
     pub(crate) tags: TreeTags,
 }
 
+impl Manifest {
+    /// Returns the number of tree versions stored in the database.
+    pub fn version_count(&self) -> u64 {
+        self.version_count
+    }
+
+    /// Checks whether the tree is being recovered from a snapshot.
+    pub fn is_recovering(&self) -> bool {
+        self.tags.is_recovering
+    }
+}
+
 /// Output of updating / inserting data in a [`MerkleTree`](crate::MerkleTree).
 #[derive(Debug, Clone, Copy)]
 pub struct BatchOutput {
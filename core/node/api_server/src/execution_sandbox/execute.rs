@@ -1,6 +1,7 @@
 //! Implementation of "executing" methods, e.g. `eth_call`.
 
 use std::{
+    collections::{HashMap, HashSet},
     fmt,
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -19,16 +20,19 @@ use zksync_multivm::{
         storage::{ReadStorage, StorageWithOverrides},
         tracer::TimestampAsserterParams,
         utils::{DivergenceHandler, VmDump},
-        Call, DeduplicatedWritesMetrics, ExecutionResult, OneshotEnv, OneshotTracingParams,
-        TransactionExecutionMetrics, TxExecutionArgs, VmEvent,
+        Call, DeduplicatedWritesMetrics, ExecutionResult, L2BlockEnv, OneshotEnv,
+        OneshotTracingParams, PrestateTrace, StoredL2BlockEnv, StructLogs,
+        TransactionExecutionMetrics, TransactionProfile, TxExecutionArgs, VmEvent,
     },
     utils::StorageWritesDeduplicator,
 };
 use zksync_object_store::{Bucket, ObjectStore};
 use zksync_state::{PostgresStorage, PostgresStorageCaches};
 use zksync_types::{
-    api::state_override::StateOverride, fee_model::BatchFeeInput, l2::L2Tx, vm::FastVmMode,
-    StorageLog, Transaction,
+    api::state_override::StateOverride, block::L2BlockHasher, fee_model::BatchFeeInput,
+    h256_to_u256, l2::L2Tx, u256_to_h256, vm::FastVmMode, AccountTreeId, L2BlockNumber,
+    ProtocolVersionId, StorageKey, StorageLog, Transaction, H256, SYSTEM_CONTEXT_ADDRESS,
+    SYSTEM_CONTEXT_CURRENT_L2_BLOCK_HASHES_POSITION, SYSTEM_CONTEXT_STORED_L2_BLOCK_HASHES, U256,
 };
 use zksync_vm_executor::oneshot::{MainOneshotExecutor, MockOneshotExecutor};
 
@@ -37,6 +41,43 @@ use super::{vm_metrics::SandboxStage, BlockArgs, VmPermit, SANDBOX_METRICS};
 use crate::execution_sandbox::testonly;
 use crate::{execution_sandbox::storage::apply_state_override, tx_sender::SandboxExecutorOptions};
 
+/// L2 block simulated in the sandbox, e.g. in `eth_simulateV1`. Simulated blocks are not sealed; their hashes
+/// only account for the block number, timestamp and the previous block hash (i.e., the block is treated as empty).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SimulatedL2Block {
+    pub number: L2BlockNumber,
+    pub timestamp: u64,
+    pub prev_block_hash: H256,
+}
+
+impl SimulatedL2Block {
+    fn hash(&self, protocol_version: ProtocolVersionId) -> H256 {
+        L2BlockHasher::new(self.number, self.timestamp, self.prev_block_hash)
+            .finalize(protocol_version)
+    }
+
+    /// Returns the block following this one.
+    pub fn next(&self, timestamp: u64, protocol_version: ProtocolVersionId) -> Self {
+        Self {
+            number: self.number + 1,
+            timestamp,
+            prev_block_hash: self.hash(protocol_version),
+        }
+    }
+
+    /// Returns the `SystemContext` storage slot with the hash of the block preceding this one, or `None`
+    /// if this is the genesis block.
+    fn prev_block_hash_key(&self) -> Option<StorageKey> {
+        let prev_block_number = self.number.0.checked_sub(1)?;
+        let position = h256_to_u256(SYSTEM_CONTEXT_CURRENT_L2_BLOCK_HASHES_POSITION)
+            + U256::from(prev_block_number % SYSTEM_CONTEXT_STORED_L2_BLOCK_HASHES);
+        Some(StorageKey::new(
+            AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
+            u256_to_h256(position),
+        ))
+    }
+}
+
 /// Action that can be executed by [`SandboxExecutor`].
 #[derive(Debug)]
 pub(crate) enum SandboxAction {
@@ -47,7 +88,13 @@ pub(crate) enum SandboxAction {
         call: L2Tx,
         fee_input: BatchFeeInput,
         enforced_base_fee: Option<u64>,
+        /// Overrides the timestamp of the L1 batch and L2 block the call is executed in.
+        block_timestamp: Option<u64>,
+        /// If set, the call is executed in the L2 block following the specified one, rather than in the resolved block.
+        prev_simulated_block: Option<SimulatedL2Block>,
         tracing_params: OneshotTracingParams,
+        /// Whether to collect [`SandboxExecutionOutput::accessed_storage_keys`].
+        collect_accessed_keys: bool,
     },
     /// Estimate gas for a transaction.
     GasEstimation {
@@ -55,24 +102,42 @@ pub(crate) enum SandboxAction {
         fee_input: BatchFeeInput,
         base_fee: u64,
     },
+    /// Re-execute a transaction already included into a block, possibly with tracing. The storage state
+    /// preceding the transaction should be provided via a state override.
+    Replay {
+        tx: Transaction,
+        fee_input: BatchFeeInput,
+        tracing_params: OneshotTracingParams,
+    },
 }
 
 impl SandboxAction {
-    fn into_parts(self) -> (TxExecutionArgs, OneshotTracingParams) {
+    /// Returns execution args, tracing params and whether to collect accessed storage keys.
+    fn into_parts(self) -> (TxExecutionArgs, OneshotTracingParams, bool) {
         match self {
             Self::Execution { tx, .. } => (
                 TxExecutionArgs::for_validation(tx),
                 OneshotTracingParams::default(),
+                false,
             ),
             Self::GasEstimation { tx, .. } => (
                 TxExecutionArgs::for_gas_estimate(tx),
                 OneshotTracingParams::default(),
+                false,
             ),
             Self::Call {
                 call,
                 tracing_params,
+                collect_accessed_keys,
                 ..
-            } => (TxExecutionArgs::for_eth_call(call), tracing_params),
+            } => (
+                TxExecutionArgs::for_eth_call(call),
+                tracing_params,
+                collect_accessed_keys,
+            ),
+            Self::Replay {
+                tx, tracing_params, ..
+            } => (TxExecutionArgs::for_replay(tx), tracing_params, false),
         }
     }
 }
@@ -84,10 +149,21 @@ pub struct SandboxExecutionOutput {
     pub result: ExecutionResult,
     /// Write logs produced by the VM.
     pub write_logs: Vec<StorageLog>,
+    /// Storage keys read or written by the VM, deduplicated and ordered by the first access.
+    /// Only collected if requested by [`SandboxAction::Call`]; empty otherwise.
+    pub accessed_storage_keys: Vec<StorageKey>,
     /// Events produced by the VM.
     pub events: Vec<VmEvent>,
+    /// Bytecodes deployed during execution that are not published as factory dependencies (e.g., EVM bytecodes).
+    pub dynamic_factory_deps: HashMap<H256, Vec<u8>>,
     /// Traced calls if requested.
     pub call_traces: Vec<Call>,
+    /// Prestate trace if requested.
+    pub prestate_trace: Option<PrestateTrace>,
+    /// Struct logs if requested and supported by the VM.
+    pub struct_logs: Option<StructLogs>,
+    /// Resource profile if requested and supported by the VM.
+    pub profile: Option<TransactionProfile>,
     /// Execution metrics.
     pub metrics: TransactionExecutionMetrics,
     /// Were published bytecodes OK?
@@ -99,8 +175,13 @@ impl SandboxExecutionOutput {
         Self {
             result: ExecutionResult::Success { output: Vec::new() },
             write_logs: Vec::new(),
+            accessed_storage_keys: Vec::new(),
             events: Vec::new(),
+            dynamic_factory_deps: HashMap::new(),
             call_traces: Vec::new(),
+            prestate_trace: None,
+            struct_logs: None,
+            profile: None,
             metrics: TransactionExecutionMetrics {
                 writes: DeduplicatedWritesMetrics::default(),
                 vm: Default::default(),
@@ -125,6 +206,7 @@ pub(crate) trait SandboxExecutorEngine<S: ReadStorage = SandboxStorage>:
         env: OneshotEnv,
         args: TxExecutionArgs,
         tracing_params: OneshotTracingParams,
+        collect_accessed_keys: bool,
     ) -> anyhow::Result<SandboxExecutionOutput>;
 }
 
@@ -140,6 +222,7 @@ where
         env: OneshotEnv,
         args: TxExecutionArgs,
         tracing_params: OneshotTracingParams,
+        collect_accessed_keys: bool,
     ) -> anyhow::Result<SandboxExecutionOutput> {
         let result = self
             .inspect_transaction_with_bytecode_compression(storage, env, args, tracing_params)
@@ -153,14 +236,28 @@ where
         };
 
         let storage_logs = tx_result.logs.storage_logs;
+        let accessed_storage_keys = if collect_accessed_keys {
+            let mut accessed_keys = HashSet::with_capacity(storage_logs.len());
+            storage_logs
+                .iter()
+                .filter_map(|log| accessed_keys.insert(log.log.key).then_some(log.log.key))
+                .collect()
+        } else {
+            vec![]
+        };
         Ok(SandboxExecutionOutput {
             result: tx_result.result,
+            accessed_storage_keys,
             write_logs: storage_logs
                 .into_iter()
                 .filter_map(|log| log.log.is_write().then_some(log.log))
                 .collect(),
             events: tx_result.logs.events,
+            dynamic_factory_deps: tx_result.dynamic_factory_deps,
             call_traces: result.call_traces,
+            prestate_trace: result.prestate_trace,
+            struct_logs: result.struct_logs,
+            profile: result.profile,
             metrics,
             are_published_bytecodes_ok: result.compression_result.is_ok(),
         })
@@ -314,7 +411,7 @@ impl SandboxExecutor {
             .prepare_env_and_storage(connection, block_args, &action)
             .await?;
 
-        let storage = if let Some(state_override) = state_override {
+        let mut storage = if let Some(state_override) = state_override {
             tokio::task::spawn_blocking(|| apply_state_override(storage, state_override))
                 .await
                 .context("applying state override panicked")?
@@ -322,10 +419,26 @@ impl SandboxExecutor {
             // Do not spawn a new thread in the most frequent case.
             StorageWithOverrides::new(storage)
         };
+        if let SandboxAction::Call {
+            prev_simulated_block: Some(prev_block),
+            ..
+        } = &action
+        {
+            // `SystemContext` computes the hash of the previous block based on the hash of the block preceding it.
+            if let Some(key) = prev_block.prev_block_hash_key() {
+                storage.set_value(key, prev_block.prev_block_hash);
+            }
+        }
 
-        let (execution_args, tracing_params) = action.into_parts();
+        let (execution_args, tracing_params, collect_accessed_keys) = action.into_parts();
         self.engine
-            .execute_in_sandbox(storage, env, execution_args, tracing_params)
+            .execute_in_sandbox(
+                storage,
+                env,
+                execution_args,
+                tracing_params,
+                collect_accessed_keys,
+            )
             .await
     }
 
@@ -354,9 +467,12 @@ impl SandboxExecutor {
             &SandboxAction::Call {
                 fee_input,
                 enforced_base_fee,
+                block_timestamp,
+                prev_simulated_block,
                 ..
             } => {
-                self.options
+                let mut env = self
+                    .options
                     .eth_call
                     .to_call_env(
                         &mut connection,
@@ -364,7 +480,28 @@ impl SandboxExecutor {
                         fee_input,
                         enforced_base_fee,
                     )
-                    .await?
+                    .await?;
+                if let Some(timestamp) = block_timestamp {
+                    env.l1_batch.timestamp = timestamp;
+                    env.l1_batch.first_l2_block.timestamp = timestamp;
+                }
+                if let Some(prev_block) = prev_simulated_block {
+                    // Reset the current L2 block in storage to the previous simulated block, so that the call
+                    // is executed in the following block.
+                    env.current_block = Some(StoredL2BlockEnv {
+                        number: prev_block.number.0,
+                        timestamp: prev_block.timestamp,
+                        txs_rolling_hash: H256::zero(),
+                    });
+                    env.l1_batch.first_l2_block = L2BlockEnv {
+                        number: prev_block.number.0 + 1,
+                        timestamp: env.l1_batch.first_l2_block.timestamp,
+                        prev_block_hash: prev_block.hash(env.system.version),
+                        max_virtual_blocks_to_create: 1,
+                        interop_roots: vec![],
+                    };
+                }
+                env
             }
             &SandboxAction::GasEstimation {
                 fee_input,
@@ -376,6 +513,12 @@ impl SandboxExecutor {
                     .to_env(&mut connection, resolved_block_info, fee_input, base_fee)
                     .await?
             }
+            &SandboxAction::Replay { fee_input, .. } => {
+                self.options
+                    .eth_call
+                    .to_replay_env(&mut connection, resolved_block_info, fee_input)
+                    .await?
+            }
         };
 
         if block_args.resolves_to_latest_sealed_l2_block() {
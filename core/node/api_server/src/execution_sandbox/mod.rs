@@ -8,14 +8,16 @@ use rand::{thread_rng, Rng};
 use zksync_dal::{pruning_dal::PruningInfo, Connection, Core, CoreDal, DalError};
 use zksync_multivm::utils::get_eth_call_gas_limit;
 use zksync_types::{
-    api, fee_model::BatchFeeInput, L1BatchNumber, L2BlockNumber, ProtocolVersionId, U256,
+    api, block::L2BlockHasher, fee_model::BatchFeeInput, L1BatchNumber, L2BlockNumber,
+    ProtocolVersionId, H256, U256,
 };
 use zksync_vm_executor::oneshot::{BlockInfo, ResolvedBlockInfo};
 
 use self::vm_metrics::SandboxStage;
 pub(crate) use self::{
     error::SandboxExecutionError,
-    execute::{SandboxAction, SandboxExecutionOutput, SandboxExecutor},
+    execute::{SandboxAction, SandboxExecutionOutput, SandboxExecutor, SimulatedL2Block},
+    storage::{extend_state_override, extend_state_override_with_bytecodes, merge_state_override},
     validate::ValidationError,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
 };
@@ -388,6 +390,58 @@ impl BlockArgs {
         }
     }
 
+    /// Returns the timestamp of the L2 block in which transactions are executed.
+    pub async fn l2_block_timestamp(
+        &self,
+        connection: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<u64> {
+        if self.is_pending() {
+            return Ok(self.resolved.l1_batch_timestamp());
+        }
+
+        let block_number = self.inner.block_number();
+        let header = connection
+            .blocks_dal()
+            .get_l2_block_header(block_number)
+            .await?
+            .with_context(|| format!("missing header for resolved block #{block_number}"))?;
+        Ok(header.timestamp)
+    }
+
+    /// Returns the hash of the L2 block preceding the block in which transactions are executed.
+    pub async fn prev_l2_block_hash(
+        &self,
+        connection: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<H256> {
+        if self.is_pending() {
+            return Ok(self.resolved.state_l2_block_hash());
+        }
+
+        let Some(prev_block_number) = self.inner.block_number().0.checked_sub(1) else {
+            return Ok(L2BlockHasher::legacy_hash(L2BlockNumber(0)));
+        };
+        let prev_block_number = L2BlockNumber(prev_block_number);
+        let prev_block_hash = connection
+            .blocks_web3_dal()
+            .get_l2_block_hash(prev_block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        if let Some(hash) = prev_block_hash {
+            return Ok(hash);
+        }
+
+        // The previous block may be the snapshot block.
+        let snapshot_recovery = connection
+            .snapshot_recovery_dal()
+            .get_applied_snapshot_status()
+            .await
+            .map_err(DalError::generalize)?;
+        snapshot_recovery
+            .filter(|recovery| recovery.l2_block_number == prev_block_number)
+            .map(|recovery| recovery.l2_block_hash)
+            .with_context(|| format!("missing hash for L2 block #{prev_block_number}"))
+    }
+
     pub async fn default_eth_call_gas(
         &self,
         connection: &mut Connection<'_, Core>,
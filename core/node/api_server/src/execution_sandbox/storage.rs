@@ -1,15 +1,146 @@
 //! VM storage functionality specifically used in the VM sandbox.
 
+use std::collections::HashMap;
+
 use zksync_multivm::interface::storage::{ReadStorage, StorageWithOverrides};
 use zksync_types::{
     api::state_override::{BytecodeOverride, OverrideState, StateOverride},
-    bytecode::{pad_evm_bytecode, BytecodeHash, BytecodeMarker},
-    get_code_key, get_evm_code_hash_key, get_known_code_key, get_nonce_key, h256_to_u256,
-    u256_to_h256,
+    bytecode::{pad_evm_bytecode, trim_padded_evm_bytecode, BytecodeHash, BytecodeMarker},
+    get_code_key, get_evm_code_hash_key, get_known_code_key, get_nonce_key, h256_to_address,
+    h256_to_u256, u256_to_h256,
     utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
-    web3, AccountTreeId, StorageKey, H256,
+    web3, AccountTreeId, StorageKey, StorageLog, ACCOUNT_CODE_STORAGE_ADDRESS, H256,
 };
 
+/// Extends a state override with storage writes produced by a sandboxed execution, so that the writes are observed
+/// by subsequent executions using the override.
+pub(crate) fn extend_state_override(state_override: &mut StateOverride, write_logs: &[StorageLog]) {
+    for log in write_logs {
+        let account = state_override.entry(*log.key.address());
+        match &mut account.state {
+            Some(OverrideState::State(state) | OverrideState::StateDiff(state)) => {
+                state.insert(*log.key.key(), log.value);
+            }
+            None => {
+                let state_diff = HashMap::from([(*log.key.key(), log.value)]);
+                account.state = Some(OverrideState::StateDiff(state_diff));
+            }
+        }
+    }
+}
+
+/// Adds code overrides for contracts deployed by a sandboxed execution, so that deployed bytecodes are available
+/// to subsequent executions using the override. `bytecodes` should contain bytecodes that may be unknown to the storage
+/// (e.g., factory deps of the executed call and dynamically deployed EVM bytecodes) keyed by their hash.
+pub(crate) fn extend_state_override_with_bytecodes(
+    state_override: &mut StateOverride,
+    write_logs: &[StorageLog],
+    bytecodes: &HashMap<H256, Vec<u8>>,
+) {
+    for log in write_logs {
+        if *log.key.address() != ACCOUNT_CODE_STORAGE_ADDRESS || log.value.is_zero() {
+            continue;
+        }
+        let mut code_hash = log.value;
+        // Reset the marker set for contracts being constructed.
+        code_hash.0[1] = 0;
+        let Some(bytecode) = bytecodes.get(&code_hash) else {
+            // The bytecode is either known to the storage, or it's not a bytecode hash at all.
+            continue;
+        };
+        let Ok(code_hash) = BytecodeHash::try_from(code_hash) else {
+            continue;
+        };
+        let code = match code_hash.marker() {
+            BytecodeMarker::EraVm => BytecodeOverride::EraVm(bytecode.clone().into()),
+            BytecodeMarker::Evm => {
+                let Ok(raw_bytecode) = trim_padded_evm_bytecode(code_hash, bytecode) else {
+                    continue;
+                };
+                BytecodeOverride::Evm(raw_bytecode.to_vec().into())
+            }
+        };
+        let address = h256_to_address(log.key.key());
+        state_override.entry(address).code = Some(code);
+    }
+}
+
+/// Merges `overrides` into a state override accumulated from previous sandboxed executions (e.g., using
+/// [`extend_state_override()`]), so that `overrides` take precedence over the accumulated state.
+///
+/// Since the order in which account overrides are applied is not defined, balance and (if possible) nonce overrides
+/// are lowered to storage writes, so that they don't conflict with the accumulated writes to system contracts.
+pub(crate) fn merge_state_override(state_override: &mut StateOverride, overrides: StateOverride) {
+    let mut overrides: Vec<_> = overrides.into_iter().collect();
+    // Replace / extend storage first, so that it doesn't erase lowered writes for other accounts.
+    for (address, account_overrides) in &mut overrides {
+        match account_overrides.state.take() {
+            Some(OverrideState::State(state)) => {
+                state_override.entry(*address).state = Some(OverrideState::State(state));
+            }
+            Some(OverrideState::StateDiff(state_diff)) => {
+                let account = AccountTreeId::new(*address);
+                let write_logs: Vec<_> = state_diff
+                    .into_iter()
+                    .map(|(key, value)| {
+                        StorageLog::new_write_log(StorageKey::new(account, key), value)
+                    })
+                    .collect();
+                extend_state_override(state_override, &write_logs);
+            }
+            None => { /* do nothing */ }
+        }
+    }
+
+    for (address, account_overrides) in overrides {
+        let mut write_logs = vec![];
+        if let Some(balance) = account_overrides.balance {
+            let balance_key = storage_key_for_eth_balance(&address);
+            write_logs.push(StorageLog::new_write_log(
+                balance_key,
+                u256_to_h256(balance),
+            ));
+        }
+
+        if let Some(nonce) = account_overrides.nonce {
+            let nonce_key = get_nonce_key(&address);
+            if let Some(full_nonce) = overridden_value(state_override, &nonce_key) {
+                let (_, deployment_nonce) = decompose_full_nonce(h256_to_u256(full_nonce));
+                let new_full_nonce = u256_to_h256(nonces_to_full_nonce(nonce, deployment_nonce));
+                write_logs.push(StorageLog::new_write_log(nonce_key, new_full_nonce));
+            } else {
+                // The deployment nonce is not known without accessing storage, so we keep the override as is.
+                state_override.entry(address).nonce = Some(nonce);
+            }
+        }
+        extend_state_override(state_override, &write_logs);
+
+        if let Some(code) = account_overrides.code {
+            let code_key = get_code_key(&address);
+            if let Some(OverrideState::State(state) | OverrideState::StateDiff(state)) =
+                state_override
+                    .get_mut(code_key.address())
+                    .and_then(|account| account.state.as_mut())
+            {
+                state.remove(code_key.key());
+            }
+            state_override.entry(address).code = Some(code);
+        }
+    }
+}
+
+/// Returns the value of the specified storage slot as overridden by `state_override`, or `None` if the slot
+/// is not overridden.
+fn overridden_value(state_override: &StateOverride, key: &StorageKey) -> Option<H256> {
+    match &state_override.get(key.address())?.state {
+        Some(OverrideState::State(state)) => {
+            Some(state.get(key.key()).copied().unwrap_or_default())
+        }
+        Some(OverrideState::StateDiff(state_diff)) => state_diff.get(key.key()).copied(),
+        None => None,
+    }
+}
+
 /// This method is blocking.
 pub(super) fn apply_state_override<S: ReadStorage>(
     storage: S,
@@ -156,4 +287,174 @@ mod tests {
         let erased_value = storage.read_value(&erased_key);
         assert_eq!(erased_value, H256::zero());
     }
+
+    #[test]
+    fn extending_state_override() {
+        let address = Address::repeat_byte(1);
+        let mut state_override = StateOverride::new(HashMap::from([(
+            address,
+            OverrideAccount {
+                state: Some(OverrideState::State(HashMap::from([(
+                    H256::zero(),
+                    H256::repeat_byte(1),
+                )]))),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let other_address = Address::repeat_byte(2);
+        let write_logs = [
+            StorageLog::new_write_log(
+                StorageKey::new(AccountTreeId::new(address), H256::zero()),
+                H256::repeat_byte(2),
+            ),
+            StorageLog::new_write_log(
+                StorageKey::new(AccountTreeId::new(other_address), H256::zero()),
+                H256::repeat_byte(3),
+            ),
+        ];
+        extend_state_override(&mut state_override, &write_logs);
+
+        let mut storage = InMemoryStorage::default();
+        let retained_key =
+            StorageKey::new(AccountTreeId::new(other_address), H256::from_low_u64_be(1));
+        storage.set_value(retained_key, H256::repeat_byte(0xfe));
+        let mut storage = apply_state_override(storage, state_override);
+
+        let value = storage.read_value(&StorageKey::new(AccountTreeId::new(address), H256::zero()));
+        assert_eq!(value, H256::repeat_byte(2));
+        let value = storage.read_value(&StorageKey::new(
+            AccountTreeId::new(other_address),
+            H256::zero(),
+        ));
+        assert_eq!(value, H256::repeat_byte(3));
+        // Writes must be applied as a state diff, i.e., must not erase other slots.
+        assert_eq!(storage.read_value(&retained_key), H256::repeat_byte(0xfe));
+    }
+
+    #[test]
+    fn extending_state_override_with_bytecodes() {
+        let (era_contract, evm_contract, known_contract) = (
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            Address::repeat_byte(3),
+        );
+        let era_bytecode: Vec<u8> = (0..32).collect();
+        let era_hash = BytecodeHash::for_bytecode(&era_bytecode).value();
+        let raw_evm_bytecode = vec![0xfe; 10];
+        let evm_hash = BytecodeHash::for_raw_evm_bytecode(&raw_evm_bytecode).value();
+        let bytecodes = HashMap::from([
+            (era_hash, era_bytecode.clone()),
+            (evm_hash, pad_evm_bytecode(&raw_evm_bytecode)),
+        ]);
+
+        let mut constructing_era_hash = era_hash;
+        constructing_era_hash.0[1] = 1;
+        let write_logs = [
+            StorageLog::new_write_log(get_code_key(&era_contract), constructing_era_hash),
+            StorageLog::new_write_log(get_code_key(&era_contract), era_hash),
+            StorageLog::new_write_log(get_code_key(&evm_contract), evm_hash),
+            StorageLog::new_write_log(get_code_key(&known_contract), H256::repeat_byte(1)),
+        ];
+        let mut state_override = StateOverride::default();
+        extend_state_override(&mut state_override, &write_logs);
+        extend_state_override_with_bytecodes(&mut state_override, &write_logs, &bytecodes);
+
+        assert!(state_override.get(&known_contract).is_none());
+        let mut storage = apply_state_override(InMemoryStorage::default(), state_override);
+        assert_eq!(storage.read_value(&get_code_key(&era_contract)), era_hash);
+        assert_eq!(storage.load_factory_dep(era_hash), Some(era_bytecode));
+        assert_eq!(storage.read_value(&get_code_key(&evm_contract)), evm_hash);
+        assert_eq!(
+            storage.load_factory_dep(evm_hash),
+            Some(pad_evm_bytecode(&raw_evm_bytecode))
+        );
+    }
+
+    #[test]
+    fn merging_state_overrides() {
+        let (alice, bob, contract) = (
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            Address::repeat_byte(3),
+        );
+        let bob_nonce_key = get_nonce_key(&bob);
+        // Full nonce with the account nonce 1 and deployment nonce 2.
+        let bob_full_nonce = u256_to_h256(nonces_to_full_nonce(1.into(), 2.into()));
+        let mut state_override = StateOverride::default();
+        extend_state_override(
+            &mut state_override,
+            &[
+                StorageLog::new_write_log(
+                    storage_key_for_eth_balance(&alice),
+                    H256::repeat_byte(1),
+                ),
+                StorageLog::new_write_log(bob_nonce_key, bob_full_nonce),
+                StorageLog::new_write_log(get_code_key(&contract), H256::repeat_byte(0xc0)),
+                StorageLog::new_write_log(
+                    StorageKey::new(AccountTreeId::new(contract), H256::zero()),
+                    H256::repeat_byte(2),
+                ),
+            ],
+        );
+
+        let overrides = StateOverride::new(HashMap::from([
+            (
+                alice,
+                OverrideAccount {
+                    balance: Some(42.into()),
+                    nonce: Some(3.into()),
+                    ..OverrideAccount::default()
+                },
+            ),
+            (
+                bob,
+                OverrideAccount {
+                    nonce: Some(5.into()),
+                    ..OverrideAccount::default()
+                },
+            ),
+            (
+                contract,
+                OverrideAccount {
+                    code: Some(BytecodeOverride::EraVm(web3::Bytes((0..32).collect()))),
+                    state: Some(OverrideState::State(HashMap::from([(
+                        H256::from_low_u64_be(1),
+                        H256::repeat_byte(3),
+                    )]))),
+                    ..OverrideAccount::default()
+                },
+            ),
+        ]));
+        merge_state_override(&mut state_override, overrides);
+
+        assert_eq!(
+            overridden_value(&state_override, &storage_key_for_eth_balance(&alice)),
+            Some(H256::from_low_u64_be(42))
+        );
+        // Alice's nonce is not overridden in storage, so it cannot be lowered to a write.
+        assert_eq!(state_override.get(&alice).unwrap().nonce, Some(3.into()));
+        let bob_full_nonce = overridden_value(&state_override, &bob_nonce_key).unwrap();
+        assert_eq!(
+            decompose_full_nonce(h256_to_u256(bob_full_nonce)),
+            (5.into(), 2.into())
+        );
+        assert_eq!(state_override.get(&bob).unwrap().nonce, None);
+
+        assert_eq!(
+            overridden_value(&state_override, &get_code_key(&contract)),
+            None
+        );
+        assert!(state_override.get(&contract).unwrap().code.is_some());
+        let slot = StorageKey::new(AccountTreeId::new(contract), H256::zero());
+        assert_eq!(overridden_value(&state_override, &slot), Some(H256::zero()));
+
+        let mut storage = apply_state_override(InMemoryStorage::default(), state_override);
+        let balance = storage.read_value(&storage_key_for_eth_balance(&alice));
+        assert_eq!(balance, H256::from_low_u64_be(42));
+        let nonce = storage.read_value(&get_nonce_key(&alice));
+        assert_eq!(nonce, H256::from_low_u64_be(3));
+        let code_hash = storage.read_value(&get_code_key(&contract));
+        assert_ne!(code_hash, H256::repeat_byte(0xc0));
+        assert!(storage.load_factory_dep(code_hash).is_some());
+    }
 }
@@ -156,10 +156,11 @@ impl SandboxExecutorEngine for SlowExecutor {
         env: OneshotEnv,
         args: TxExecutionArgs,
         tracing_params: OneshotTracingParams,
+        collect_accessed_keys: bool,
     ) -> anyhow::Result<SandboxExecutionOutput> {
         let storage = self.patch_storage(storage);
         self.inner
-            .execute_in_sandbox(storage, env, args, tracing_params)
+            .execute_in_sandbox(storage, env, args, tracing_params, collect_accessed_keys)
             .await
     }
 }
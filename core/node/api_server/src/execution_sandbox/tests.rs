@@ -5,7 +5,10 @@ use std::collections::HashMap;
 use assert_matches::assert_matches;
 use test_casing::test_casing;
 use zksync_dal::ConnectionPool;
-use zksync_multivm::{interface::ExecutionResult, utils::derive_base_fee_and_gas_per_pubdata};
+use zksync_multivm::{
+    interface::{ExecutionResult, OneshotTracingParams},
+    utils::derive_base_fee_and_gas_per_pubdata,
+};
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l1_batch, create_l2_block, prepare_recovery_snapshot};
 use zksync_state::PostgresStorageCaches;
@@ -253,6 +256,73 @@ async fn test_instantiating_vm(connection: Connection<'static, Core>, block_args
     assert!(!tx_result.is_failed(), "{tx_result:#?}");
 }
 
+#[tokio::test]
+async fn executing_calls_in_simulated_blocks() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut connection = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut connection, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let block_args = BlockArgs::pending(&mut connection).await.unwrap();
+    let base_timestamp = block_args
+        .l2_block_timestamp(&mut connection)
+        .await
+        .unwrap();
+    let prev_block_hash = block_args
+        .prev_l2_block_hash(&mut connection)
+        .await
+        .unwrap();
+    drop(connection);
+
+    let executor = SandboxExecutor::real(
+        SandboxExecutorOptions::mock().await,
+        PostgresStorageCaches::new(1, 1),
+        usize::MAX,
+        None,
+    );
+    let fee_input = BatchFeeInput::l1_pegged(55, 555);
+    let (base_fee, gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+    let fee = Fee {
+        gas_limit: 200_000.into(),
+        max_fee_per_gas: base_fee.into(),
+        max_priority_fee_per_gas: 0.into(),
+        gas_per_pubdata_limit: gas_per_pubdata.into(),
+    };
+    let (limiter, _) = VmConcurrencyLimiter::new(1);
+    let vm_permit = limiter.acquire().await.unwrap();
+
+    let mut prev_block = SimulatedL2Block {
+        number: block_args.resolved_block_number(),
+        timestamp: base_timestamp,
+        prev_block_hash,
+    };
+    // Simulate several blocks to check that hashes of the previous blocks are chained correctly.
+    for _ in 0..3 {
+        let timestamp = prev_block.timestamp + 1;
+        let call =
+            Account::random().create_transfer_with_fee(Address::random(), 0.into(), fee.clone());
+        let action = SandboxAction::Call {
+            call,
+            fee_input,
+            enforced_base_fee: None,
+            block_timestamp: Some(timestamp),
+            prev_simulated_block: Some(prev_block),
+            tracing_params: OneshotTracingParams::default(),
+            collect_accessed_keys: false,
+        };
+        let connection = pool.connection().await.unwrap();
+        let output = executor
+            .execute_in_sandbox(vm_permit.clone(), connection, action, &block_args, None)
+            .await
+            .unwrap();
+        assert_matches!(output.result, ExecutionResult::Success { .. });
+
+        prev_block = prev_block.next(timestamp, block_args.protocol_version());
+    }
+}
+
 #[test_casing(2, [false, true])]
 #[tokio::test]
 async fn validating_transaction(set_balance: bool) {
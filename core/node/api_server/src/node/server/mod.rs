@@ -1,6 +1,6 @@
 use std::{collections::HashSet, num::NonZeroU32, sync::Arc, time::Duration};
 
-use zksync_config::configs::api::{MaxResponseSize, Namespace};
+use zksync_config::configs::api::{HttpRateLimitsConfig, MaxResponseSize, Namespace};
 use zksync_dal::node::{PoolResource, ReplicaPool};
 use zksync_health_check::AppHealthCheck;
 use zksync_node_framework::{
@@ -10,7 +10,7 @@ use zksync_node_framework::{
     FromContext, IntoContext,
 };
 use zksync_shared_resources::{
-    api::{BridgeAddressesHandle, SyncState},
+    api::{BridgeAddressesHandle, SyncState, TxPreconfirmations},
     contracts::{L1ChainContractsResource, L1EcosystemContractsResource, L2ContractsResource},
     tree::TreeApiClient,
     DummyVerifierResource, L1BatchCommitmentModeResource,
@@ -43,6 +43,7 @@ pub struct Web3ServerOptionalConfig {
     pub batch_request_size_limit: usize,
     pub response_body_size_limit: MaxResponseSize,
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    pub http_rate_limits: Option<HttpRateLimitsConfig>,
     pub request_timeout: Option<Duration>,
     pub with_extended_tracing: bool,
     pub polling_interval: Duration,
@@ -66,6 +67,9 @@ impl Web3ServerOptionalConfig {
             api_builder = api_builder
                 .with_websocket_requests_per_minute_limit(websocket_requests_per_minute_limit);
         }
+        if let Some(http_rate_limits) = self.http_rate_limits {
+            api_builder = api_builder.with_http_rate_limits(http_rate_limits);
+        }
         if let Some(request_timeout) = self.request_timeout {
             api_builder = api_builder.with_request_timeout(request_timeout);
         }
@@ -110,6 +114,8 @@ pub struct Input {
     replica_pool: PoolResource<ReplicaPool>,
     tx_sender: TxSender,
     sync_state: Option<SyncState>,
+    #[context(default)]
+    tx_preconfirmations: TxPreconfirmations,
     tree_api_client: Option<Arc<dyn TreeApiClient>>,
     mempool_cache: MempoolCache,
     #[context(default)]
@@ -134,6 +140,10 @@ pub struct Output {
     #[context(task)]
     pub_sub_logs_task: Option<PubSubNotifier>,
     #[context(task)]
+    pub_sub_preconfirmations_task: Option<PubSubNotifier>,
+    #[context(task)]
+    pub_sub_l1_batch_status_task: Option<PubSubNotifier>,
+    #[context(task)]
     sealed_l2_block_updater_task: SealedL2BlockUpdaterTask,
 }
 
@@ -186,6 +196,7 @@ impl WiringLayer for Web3ServerLayer {
         let mempool_cache = input.mempool_cache;
         let sync_state = input.sync_state;
         let tree_api_client = input.tree_api_client;
+        let tx_preconfirmations = input.tx_preconfirmations;
 
         let l1_contracts = input.l1_contracts.0;
         let internal_api_config = InternalApiConfig::from_base_and_contracts(
@@ -214,7 +225,11 @@ impl WiringLayer for Web3ServerLayer {
             self.optional_config.namespaces.contains(&Namespace::Pubsub);
         let enable_pub_sub = matches!(self.transport, Transport::Ws) && contains_pub_sub_namespace;
         let polling_interval = self.optional_config.polling_interval;
-        let pub_sub = enable_pub_sub.then(|| EthSubscribe::new(polling_interval));
+        let l2_chain_id = internal_api_config.l2_chain_id;
+        let pub_sub = enable_pub_sub.then(|| {
+            EthSubscribe::new(polling_interval, l2_chain_id)
+                .with_tx_preconfirmations(tx_preconfirmations.clone())
+        });
         let pub_sub_blocks_task = pub_sub
             .as_ref()
             .map(|pub_sub| pub_sub.create_notifier(SubscriptionType::Blocks, replica_pool.clone()));
@@ -224,13 +239,20 @@ impl WiringLayer for Web3ServerLayer {
         let pub_sub_logs_task = pub_sub
             .as_ref()
             .map(|pub_sub| pub_sub.create_notifier(SubscriptionType::Logs, replica_pool.clone()));
+        let pub_sub_preconfirmations_task = pub_sub.as_ref().map(|pub_sub| {
+            pub_sub.create_notifier(SubscriptionType::Preconfirmations, replica_pool.clone())
+        });
+        let pub_sub_l1_batch_status_task = pub_sub.as_ref().map(|pub_sub| {
+            pub_sub.create_notifier(SubscriptionType::L1BatchStatus, replica_pool.clone())
+        });
 
         // Build server.
         let mut api_builder = ApiBuilder::new(internal_api_config, replica_pool.clone())
             .with_tx_sender(tx_sender)
             .with_mempool_cache(mempool_cache)
             .with_sealed_l2_block_handle(sealed_l2_block_handle)
-            .with_bridge_addresses_handle(bridge_addresses);
+            .with_bridge_addresses_handle(bridge_addresses)
+            .with_tx_preconfirmations(tx_preconfirmations);
         if let Some(client) = tree_api_client {
             api_builder = api_builder.with_tree_api(client);
         }
@@ -270,6 +292,8 @@ impl WiringLayer for Web3ServerLayer {
             pub_sub_blocks_task,
             pub_sub_transactions_task,
             pub_sub_logs_task,
+            pub_sub_preconfirmations_task,
+            pub_sub_l1_batch_status_task,
             sealed_l2_block_updater_task,
         })
     }
@@ -282,6 +306,8 @@ impl Task for PubSubNotifier {
             SubscriptionType::Blocks => "api/pub_sub_notifiers/blocks".into(),
             SubscriptionType::Txs => "api/pub_sub_notifiers/txs".into(),
             SubscriptionType::Logs => "api/pub_sub_notifiers/logs".into(),
+            SubscriptionType::Preconfirmations => "api/pub_sub_notifiers/preconfirmations".into(),
+            SubscriptionType::L1BatchStatus => "api/pub_sub_notifiers/l1_batch_status".into(),
         }
     }
 
@@ -1,6 +1,7 @@
 //! Helper module to submit transactions into the ZKsync Network.
 
 use std::{
+    num::NonZeroU32,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
@@ -44,8 +45,11 @@ use zksync_vm_executor::{
     oneshot::{CallOrExecute, EstimateGas, MultiVmBaseSystemContracts, OneshotEnvParameters},
 };
 
-pub(super) use self::{gas_estimation::BinarySearchKind, result::SubmitTxError};
-use self::{master_pool_sink::MasterPoolSink, result::ApiCallResult, tx_sink::TxSink};
+pub(super) use self::{
+    gas_estimation::BinarySearchKind,
+    result::{ApiCallResult, SubmitTxError},
+};
+use self::{master_pool_sink::MasterPoolSink, tx_sink::TxSink};
 use crate::execution_sandbox::{
     BlockArgs, SandboxAction, SandboxExecutionOutput, SandboxExecutor, SubmitTxStage,
     VmConcurrencyBarrier, VmConcurrencyLimiter, SANDBOX_METRICS,
@@ -260,6 +264,8 @@ pub struct TxSenderConfig {
     pub fee_account_addr: Address,
     pub gas_price_scale_factor: f64,
     pub max_nonce_ahead: u32,
+    pub max_pending_txs_per_account: Option<NonZeroU32>,
+    pub replacement_fee_bump_percent: u32,
     pub max_allowed_l2_tx_gas_limit: u64,
     pub vm_execution_cache_misses_limit: Option<usize>,
     pub validation_computational_gas_limit: u32,
@@ -285,6 +291,8 @@ impl TxSenderConfig {
             fee_account_addr,
             gas_price_scale_factor: web3_json_config.gas_price_scale_factor,
             max_nonce_ahead: web3_json_config.max_nonce_ahead,
+            max_pending_txs_per_account: web3_json_config.max_pending_txs_per_account,
+            replacement_fee_bump_percent: web3_json_config.replacement_fee_bump_percent,
             max_allowed_l2_tx_gas_limit: state_keeper_config.max_allowed_l2_tx_gas_limit,
             vm_execution_cache_misses_limit: web3_json_config.vm_execution_cache_misses_limit,
             validation_computational_gas_limit: state_keeper_config
@@ -581,6 +589,7 @@ impl TxSender {
         // We still double-check the nonce manually
         // to make sure that only the correct nonce is submitted and the transaction's hashes never repeat
         self.validate_account_nonce(tx).await?;
+        self.validate_pending_txs(tx).await?;
         // Even though without enough balance the tx will not pass anyway
         // we check the user for enough balance explicitly here for better DevEx.
         self.validate_enough_balance(tx).await?;
@@ -618,6 +627,55 @@ impl TxSender {
         }
     }
 
+    /// Checks the limit on pending transactions for the initiator account, and that the fees are bumped enough
+    /// if the transaction replaces a pending one.
+    async fn validate_pending_txs(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
+        let config = &self.0.sender_config;
+        if config.max_pending_txs_per_account.is_none() && config.replacement_fee_bump_percent == 0
+        {
+            return Ok(());
+        }
+
+        let initiator_account = tx.initiator_account();
+        let mut storage = self.acquire_replica_connection().await?;
+        let replaced_fee = storage
+            .transactions_dal()
+            .get_pending_l2_tx_fee(initiator_account, tx.nonce())
+            .await
+            .context("failed getting pending transaction")?;
+
+        if let Some(replaced_fee) = replaced_fee {
+            if config.replacement_fee_bump_percent == 0 {
+                return Ok(());
+            }
+            let multiplier = U256::from(100 + config.replacement_fee_bump_percent);
+            let min_max_fee_per_gas = replaced_fee.max_fee_per_gas.saturating_mul(multiplier) / 100;
+            let min_max_priority_fee_per_gas = replaced_fee
+                .max_priority_fee_per_gas
+                .saturating_mul(multiplier)
+                / 100;
+            let fee = &tx.common_data.fee;
+            if fee.max_fee_per_gas < min_max_fee_per_gas
+                || fee.max_priority_fee_per_gas < min_max_priority_fee_per_gas
+            {
+                return Err(SubmitTxError::ReplacementUnderpriced(
+                    min_max_fee_per_gas,
+                    min_max_priority_fee_per_gas,
+                ));
+            }
+        } else if let Some(limit) = config.max_pending_txs_per_account {
+            let pending_count = storage
+                .transactions_dal()
+                .get_pending_l2_txs_count(initiator_account)
+                .await
+                .context("failed getting pending transactions count")?;
+            if pending_count >= limit.get() as usize {
+                return Err(SubmitTxError::TooManyPendingTxs(limit.get()));
+            }
+        }
+        Ok(())
+    }
+
     async fn get_expected_nonce(&self, initiator_account: Address) -> anyhow::Result<Nonce> {
         let mut storage = self.acquire_replica_connection().await?;
         let latest_block_number = storage
@@ -691,6 +749,21 @@ impl TxSender {
         call: L2Tx,
         state_override: Option<StateOverride>,
     ) -> Result<Vec<u8>, SubmitTxError> {
+        let output = self
+            .execute_call(block_args, call_overrides, call, state_override, false)
+            .await?;
+        output.result.into_api_call_result()
+    }
+
+    /// Same as [`Self::eth_call()`], but returns the full sandbox output without checking the execution result.
+    pub(crate) async fn execute_call(
+        &self,
+        block_args: BlockArgs,
+        call_overrides: CallOverrides,
+        call: L2Tx,
+        state_override: Option<StateOverride>,
+        collect_accessed_keys: bool,
+    ) -> Result<SandboxExecutionOutput, SubmitTxError> {
         let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
 
@@ -713,14 +786,16 @@ impl TxSender {
             call,
             fee_input,
             enforced_base_fee: call_overrides.enforced_base_fee,
+            block_timestamp: None,
+            prev_simulated_block: None,
             tracing_params: OneshotTracingParams::default(),
+            collect_accessed_keys,
         };
-        let result = self
+        Ok(self
             .0
             .executor
             .execute_in_sandbox(vm_permit, connection, action, &block_args, state_override)
-            .await?;
-        result.result.into_api_call_result()
+            .await?)
     }
 
     pub async fn gas_price_and_gas_per_pubdata(&self) -> anyhow::Result<(u64, u64)> {
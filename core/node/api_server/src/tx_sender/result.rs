@@ -14,6 +14,10 @@ pub enum SubmitTxError {
     NonceIsTooLow(u32, u32, u32),
     #[error("insertion of another transaction with the same nonce is in progress")]
     InsertionInProgress,
+    #[error("too many pending transactions for the account. limit: {0}")]
+    TooManyPendingTxs(u32),
+    #[error("replacement transaction underpriced. min max fee per gas: {0}, min max priority fee per gas: {1}")]
+    ReplacementUnderpriced(U256, U256),
     #[error("{0}")]
     IncorrectTx(#[from] TxCheckError),
     #[error("insufficient funds for gas + value. balance: {0}, fee: {1}, value: {2}")]
@@ -79,6 +83,8 @@ impl SubmitTxError {
             Self::NonceIsTooHigh(_, _, _) => "nonce-is-too-high",
             Self::NonceIsTooLow(_, _, _) => "nonce-is-too-low",
             Self::InsertionInProgress => "insertion-in-progress",
+            Self::TooManyPendingTxs(_) => "too-many-pending-txs",
+            Self::ReplacementUnderpriced(_, _) => "replacement-underpriced",
             Self::IncorrectTx(_) => "incorrect-tx",
             Self::NotEnoughBalanceForFeeValue(_, _, _) => "not-enough-balance-for-fee",
             Self::ExecutionReverted(_, _) => "execution-reverted",
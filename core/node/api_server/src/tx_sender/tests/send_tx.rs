@@ -116,6 +116,58 @@ async fn nonce_validation_errors() {
     );
 }
 
+#[tokio::test]
+async fn pending_txs_validation_errors() {
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    drop(storage);
+
+    let l2_chain_id = L2ChainId::default();
+    let tx_executor = SandboxExecutor::mock(MockOneshotExecutor::default()).await;
+    let (mut tx_sender, _) = create_test_tx_sender(pool.clone(), l2_chain_id, tx_executor).await;
+    let sender_config = &mut Arc::get_mut(&mut tx_sender.0).unwrap().sender_config;
+    sender_config.max_pending_txs_per_account = NonZeroU32::new(2);
+    sender_config.replacement_fee_bump_percent = 10;
+
+    let mut tx = create_l2_transaction(100, 50);
+    tx_sender.validate_pending_txs(&tx).await.unwrap();
+
+    let mut storage = pool.connection().await.unwrap();
+    for nonce in 0..2 {
+        let mut pending_tx = tx.clone();
+        pending_tx.common_data.nonce = Nonce(nonce);
+        pending_tx.set_input(H256::random().0.to_vec(), H256::random());
+        storage
+            .transactions_dal()
+            .insert_transaction_l2(
+                &pending_tx,
+                TransactionExecutionMetrics::default(),
+                ValidationTraces::default(),
+            )
+            .await
+            .unwrap();
+    }
+    drop(storage);
+
+    tx.common_data.nonce = Nonce(2);
+    let err = tx_sender.validate_pending_txs(&tx).await.unwrap_err();
+    assert_matches!(err, SubmitTxError::TooManyPendingTxs(2));
+
+    // Replacing a pending transaction isn't limited by the number of pending transactions, but requires a fee bump.
+    tx.common_data.nonce = Nonce(1);
+    let err = tx_sender.validate_pending_txs(&tx).await.unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::ReplacementUnderpriced(max_fee, max_priority_fee)
+            if max_fee == 110.into() && max_priority_fee.is_zero()
+    );
+    tx.common_data.fee.max_fee_per_gas = 110.into();
+    tx_sender.validate_pending_txs(&tx).await.unwrap();
+}
+
 #[tokio::test]
 async fn fee_validation_errors() {
     let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
@@ -89,6 +89,12 @@ impl MethodTracer {
         }
     }
 
+    /// Returns the name of the current JSON-RPC method, or `None` if called outside method handlers.
+    pub fn method_name(&self) -> Option<&'static str> {
+        let cell = self.inner.get_or_default();
+        cell.borrow().as_ref().map(|metadata| metadata.name)
+    }
+
     /// Observes state override metrics.
     pub fn observe_state_override(&self, state_override: Option<&StateOverride>) {
         let cell = self.inner.get_or_default();
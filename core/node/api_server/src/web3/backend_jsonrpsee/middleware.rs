@@ -1,15 +1,16 @@
 use std::{
     cell::RefCell,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     future::Future,
     mem,
     num::NonZeroU32,
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
     time::{Duration, Instant},
 };
 
+use anyhow::Context as _;
 use governor::{
     clock::DefaultClock,
     middleware::NoOpMiddleware,
@@ -22,8 +23,10 @@ use rand::{rngs::SmallRng, RngCore, SeedableRng};
 use tokio::sync::watch;
 use tracing::instrument::{Instrument, Instrumented};
 use vise::{
-    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, GaugeGuard, Histogram, Metrics,
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, GaugeGuard, Histogram,
+    LabeledFamily, Metrics, Unit,
 };
+use zksync_config::configs::api::{HttpRateLimitsConfig, MethodWeights};
 use zksync_instrument::alloc::AllocationAccumulator;
 use zksync_web3_decl::jsonrpsee::{
     server::middleware::rpc::{layer::ResponseFuture, RpcServiceT},
@@ -107,6 +110,366 @@ where
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(crate) enum RateLimitKind {
+    Requests,
+    ComputeUnits,
+    ResponseSize,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "api_jsonrpc_backend_api_key")]
+struct ApiKeyMetrics {
+    /// Number of requests accepted for each API key.
+    #[metrics(labels = ["api_key"])]
+    requests: LabeledFamily<String, Counter>,
+    /// Number of compute units spent by each API key.
+    #[metrics(labels = ["api_key"])]
+    compute_units: LabeledFamily<String, Counter>,
+    /// Total size of responses returned for each API key.
+    #[metrics(labels = ["api_key"], unit = Unit::Bytes)]
+    response_size: LabeledFamily<String, Counter>,
+    /// Number of requests rejected because of an exhausted budget.
+    #[metrics(labels = ["api_key", "limit"])]
+    rejected: LabeledFamily<(String, RateLimitKind), Counter, 2>,
+}
+
+#[vise::register]
+static API_KEY_METRICS: vise::Global<ApiKeyMetrics> = vise::Global::new();
+
+/// Human-readable name of the API key used for a request. Inserted into HTTP request extensions by [`ApiKeyLayer`],
+/// from which `jsonrpsee` propagates it to RPC requests.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct ApiKeyName(Arc<str>);
+
+impl ApiKeyName {
+    const ANONYMOUS: &'static str = "anonymous";
+
+    fn metric_label(&self) -> String {
+        self.0.as_ref().to_owned()
+    }
+}
+
+/// Continuously replenished budget. The budget can be overdrawn, in which case it needs to be replenished
+/// to a positive value before it can be spent again.
+#[derive(Debug)]
+struct Budget {
+    capacity_per_minute: f64,
+    available: f64,
+    updated_at: Instant,
+}
+
+impl Budget {
+    fn new(capacity_per_minute: u64, now: Instant) -> Self {
+        let capacity_per_minute = capacity_per_minute as f64;
+        Self {
+            capacity_per_minute,
+            available: capacity_per_minute,
+            updated_at: now,
+        }
+    }
+
+    fn replenish(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at);
+        let replenished = self.capacity_per_minute * elapsed.as_secs_f64() / 60.0;
+        self.available = (self.available + replenished).min(self.capacity_per_minute);
+        self.updated_at = now;
+    }
+}
+
+/// Budgets for a single API key.
+#[derive(Debug)]
+struct ApiKeyBudgets {
+    requests: Option<Budget>,
+    compute_units: Option<Budget>,
+    response_size: Option<Budget>,
+}
+
+impl ApiKeyBudgets {
+    /// Checks all budgets for a call with the specified weight, and spends them if the call is allowed.
+    fn try_start_call(&mut self, weight: NonZeroU32, now: Instant) -> Result<(), RateLimitKind> {
+        for budget in [
+            &mut self.requests,
+            &mut self.compute_units,
+            &mut self.response_size,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            budget.replenish(now);
+        }
+
+        if self
+            .requests
+            .as_ref()
+            .is_some_and(|budget| budget.available < 1.0)
+        {
+            return Err(RateLimitKind::Requests);
+        }
+        // Cap the weight so that heavy methods can still be called at least once per minute.
+        let weight = f64::from(weight.get());
+        let compute_units = self
+            .compute_units
+            .as_ref()
+            .map(|budget| weight.min(budget.capacity_per_minute));
+        if let (Some(budget), Some(compute_units)) = (&self.compute_units, compute_units) {
+            if budget.available < compute_units {
+                return Err(RateLimitKind::ComputeUnits);
+            }
+        }
+        // The response size isn't known in advance, so we only check that the budget isn't exhausted.
+        if self
+            .response_size
+            .as_ref()
+            .is_some_and(|budget| budget.available <= 0.0)
+        {
+            return Err(RateLimitKind::ResponseSize);
+        }
+
+        if let Some(budget) = &mut self.requests {
+            budget.available -= 1.0;
+        }
+        if let (Some(budget), Some(compute_units)) = (&mut self.compute_units, compute_units) {
+            budget.available -= compute_units;
+        }
+        Ok(())
+    }
+
+    fn finish_call(&mut self, response_size: usize, now: Instant) {
+        if let Some(budget) = &mut self.response_size {
+            budget.replenish(now);
+            budget.available -= response_size as f64;
+        }
+    }
+}
+
+/// Per-API key limiter shared by [`ApiKeyLayer`] and [`ApiKeyLimitMiddleware`].
+#[derive(Debug)]
+pub(crate) struct ApiKeyLimiter {
+    names_by_key: HashMap<String, ApiKeyName>,
+    anonymous: ApiKeyName,
+    header_name: http::HeaderName,
+    key_in_path: bool,
+    requests_per_minute: Option<NonZeroU32>,
+    compute_units_per_minute: Option<NonZeroU32>,
+    response_size_per_minute: Option<u64>,
+    method_weights: MethodWeights,
+    // The number of entries is bounded by the number of configured API keys (+1 for the anonymous budget).
+    budgets: Mutex<HashMap<ApiKeyName, ApiKeyBudgets>>,
+}
+
+impl ApiKeyLimiter {
+    pub(crate) fn new(config: &HttpRateLimitsConfig) -> anyhow::Result<Self> {
+        let header_name = http::HeaderName::try_from(config.api_key_header.as_str())
+            .with_context(|| format!("invalid API key header `{}`", config.api_key_header))?;
+        let names_by_key = config
+            .api_keys
+            .iter()
+            .map(|(name, key)| (key.to_owned(), ApiKeyName(name.into())))
+            .collect();
+        Ok(Self {
+            names_by_key,
+            anonymous: ApiKeyName(ApiKeyName::ANONYMOUS.into()),
+            header_name,
+            key_in_path: config.api_key_in_path,
+            requests_per_minute: config.requests_per_minute,
+            compute_units_per_minute: config.compute_units_per_minute,
+            response_size_per_minute: config.response_size_per_minute.map(|size| size.0),
+            method_weights: config.method_weights.clone(),
+            budgets: Mutex::default(),
+        })
+    }
+
+    pub(crate) fn header_name(&self) -> &http::HeaderName {
+        &self.header_name
+    }
+
+    /// Resolves the API key from the request header or, if allowed, from the first segment of the URL path.
+    /// Missing or unknown keys are mapped to the anonymous key.
+    fn resolve_api_key<B>(&self, request: &http::Request<B>) -> ApiKeyName {
+        let from_header = request
+            .headers()
+            .get(&self.header_name)
+            .and_then(|value| value.to_str().ok());
+        let from_path = || {
+            let path = request.uri().path().trim_start_matches('/');
+            let segment = path.split('/').next().unwrap_or_default();
+            (!segment.is_empty()).then_some(segment)
+        };
+        let key = from_header.or_else(|| self.key_in_path.then(from_path).flatten());
+        key.and_then(|key| self.names_by_key.get(key))
+            .unwrap_or(&self.anonymous)
+            .clone()
+    }
+
+    fn try_start_call(
+        &self,
+        api_key: &ApiKeyName,
+        method_name: &str,
+        now: Instant,
+    ) -> Result<(), RateLimitKind> {
+        let weight = self.method_weights.get(method_name);
+        let mut budgets = self.budgets.lock().expect("API key budgets are poisoned");
+        let budgets = budgets
+            .entry(api_key.clone())
+            .or_insert_with(|| ApiKeyBudgets {
+                requests: self
+                    .requests_per_minute
+                    .map(|limit| Budget::new(limit.get().into(), now)),
+                compute_units: self
+                    .compute_units_per_minute
+                    .map(|limit| Budget::new(limit.get().into(), now)),
+                response_size: self
+                    .response_size_per_minute
+                    .map(|limit| Budget::new(limit, now)),
+            });
+
+        let label = api_key.metric_label();
+        if let Err(kind) = budgets.try_start_call(weight, now) {
+            API_KEY_METRICS.rejected[&(label, kind)].inc();
+            return Err(kind);
+        }
+        API_KEY_METRICS.requests[&label].inc();
+        API_KEY_METRICS.compute_units[&label].inc_by(weight.get().into());
+        Ok(())
+    }
+
+    fn finish_call(&self, api_key: &ApiKeyName, response_size: usize, now: Instant) {
+        API_KEY_METRICS.response_size[&api_key.metric_label()].inc_by(response_size as u64);
+        let mut budgets = self.budgets.lock().expect("API key budgets are poisoned");
+        if let Some(budgets) = budgets.get_mut(api_key) {
+            budgets.finish_call(response_size, now);
+        }
+    }
+}
+
+/// HTTP middleware layer that resolves the API key for each request and records its name in request extensions.
+#[derive(Debug, Clone)]
+pub(crate) struct ApiKeyLayer {
+    limiter: Arc<ApiKeyLimiter>,
+}
+
+impl ApiKeyLayer {
+    pub fn new(limiter: Arc<ApiKeyLimiter>) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<Svc> tower::Layer<Svc> for ApiKeyLayer {
+    type Service = ApiKeyService<Svc>;
+
+    fn layer(&self, inner: Svc) -> Self::Service {
+        ApiKeyService {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// HTTP service produced by [`ApiKeyLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct ApiKeyService<S> {
+    inner: S,
+    limiter: Arc<ApiKeyLimiter>,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for ApiKeyService<S>
+where
+    S: tower::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        let api_key = self.limiter.resolve_api_key(&request);
+        request.extensions_mut().insert(api_key);
+        self.inner.call(request)
+    }
+}
+
+/// RPC-level middleware enforcing request, compute unit and response size budgets per API key.
+/// Relies on [`ApiKeyLayer`] to resolve API keys; requests without a resolved key are treated as anonymous.
+#[derive(Debug)]
+pub(crate) struct ApiKeyLimitMiddleware<S> {
+    inner: S,
+    limiter: Arc<ApiKeyLimiter>,
+}
+
+impl<S> ApiKeyLimitMiddleware<S> {
+    pub fn new(inner: S, limiter: Arc<ApiKeyLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for ApiKeyLimitMiddleware<S>
+where
+    S: Send + Sync + RpcServiceT<'a>,
+{
+    type Future = ResponseFuture<WithResponseSize<S::Future>>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        let api_key = request
+            .extensions()
+            .get::<ApiKeyName>()
+            .unwrap_or(&self.limiter.anonymous)
+            .clone();
+        let now = Instant::now();
+        if self
+            .limiter
+            .try_start_call(&api_key, request.method_name(), now)
+            .is_err()
+        {
+            let rp = MethodResponse::error(
+                request.id,
+                ErrorObject::borrowed(
+                    ErrorCode::ServerError(http::StatusCode::TOO_MANY_REQUESTS.as_u16().into())
+                        .code(),
+                    "Too many requests",
+                    None,
+                ),
+            );
+            return ResponseFuture::ready(rp);
+        }
+
+        ResponseFuture::future(WithResponseSize {
+            inner: self.inner.call(request),
+            limiter: self.limiter.clone(),
+            api_key,
+        })
+    }
+}
+
+pin_project! {
+    /// Future accounting the response size in the API key budget.
+    #[derive(Debug)]
+    pub(crate) struct WithResponseSize<F> {
+        #[pin]
+        inner: F,
+        limiter: Arc<ApiKeyLimiter>,
+        api_key: ApiKeyName,
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for WithResponseSize<F> {
+    type Output = MethodResponse;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let projection = self.project();
+        let response = futures::ready!(projection.inner.poll(cx));
+        let response_size = response.as_result().len();
+        projection
+            .limiter
+            .finish_call(projection.api_key, response_size, Instant::now());
+        Poll::Ready(response)
+    }
+}
+
 /// RPC-level middleware that adds [`MethodCall`] metadata to method logic. Method handlers can then access this metadata
 /// using [`MethodTracer`], which is a part of `RpcState`. When the handler completes or is dropped, the results are reported
 /// as metrics.
@@ -468,6 +831,103 @@ mod tests {
         }
     }
 
+    fn test_limiter() -> ApiKeyLimiter {
+        let config = HttpRateLimitsConfig {
+            api_keys: [("alice", "alice-key")].into_iter().collect(),
+            api_key_in_path: true,
+            requests_per_minute: NonZeroU32::new(60),
+            compute_units_per_minute: NonZeroU32::new(100),
+            method_weights: [("eth_call", NonZeroU32::new(30).unwrap())]
+                .into_iter()
+                .collect(),
+            ..HttpRateLimitsConfig::default()
+        };
+        let mut limiter = ApiKeyLimiter::new(&config).unwrap();
+        limiter.response_size_per_minute = Some(1_000);
+        limiter
+    }
+
+    #[test]
+    fn resolving_api_keys() {
+        let limiter = test_limiter();
+        let alice = ApiKeyName("alice".into());
+
+        let request = http::Request::builder()
+            .header("x-api-key", "alice-key")
+            .body(())
+            .unwrap();
+        assert_eq!(limiter.resolve_api_key(&request), alice);
+        let request = http::Request::builder().uri("/alice-key").body(()).unwrap();
+        assert_eq!(limiter.resolve_api_key(&request), alice);
+
+        for request in [
+            http::Request::builder().body(()).unwrap(),
+            http::Request::builder().uri("/bogus").body(()).unwrap(),
+            http::Request::builder()
+                .header("x-api-key", "bogus")
+                .body(())
+                .unwrap(),
+        ] {
+            assert_eq!(limiter.resolve_api_key(&request), limiter.anonymous);
+        }
+    }
+
+    #[test]
+    fn api_key_budgets() {
+        let limiter = test_limiter();
+        let alice = ApiKeyName("alice".into());
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            limiter.try_start_call(&alice, "eth_call", now).unwrap();
+        }
+        // Compute units: 3 * 30 = 90 spent out of 100
+        let err = limiter.try_start_call(&alice, "eth_call", now).unwrap_err();
+        assert_eq!(err, RateLimitKind::ComputeUnits);
+        // Budgets are independent for different API keys.
+        limiter
+            .try_start_call(&limiter.anonymous, "eth_call", now)
+            .unwrap();
+
+        // 18 seconds replenish 30 compute units.
+        let now = now + Duration::from_secs(18);
+        limiter.try_start_call(&alice, "eth_call", now).unwrap();
+
+        limiter.finish_call(&alice, 2_000, now);
+        let err = limiter
+            .try_start_call(&alice, "eth_chainId", now)
+            .unwrap_err();
+        assert_eq!(err, RateLimitKind::ResponseSize);
+        // The overdrawn response size budget needs more than a minute to be replenished.
+        let now = now + Duration::from_secs(60);
+        let err = limiter
+            .try_start_call(&alice, "eth_chainId", now)
+            .unwrap_err();
+        assert_eq!(err, RateLimitKind::ResponseSize);
+        let now = now + Duration::from_secs(1);
+        limiter.try_start_call(&alice, "eth_chainId", now).unwrap();
+    }
+
+    #[test]
+    fn requests_budget() {
+        let limiter = test_limiter();
+        let now = Instant::now();
+        for _ in 0..60 {
+            limiter
+                .try_start_call(&limiter.anonymous, "eth_chainId", now)
+                .unwrap();
+        }
+        let err = limiter
+            .try_start_call(&limiter.anonymous, "eth_chainId", now)
+            .unwrap_err();
+        assert_eq!(err, RateLimitKind::Requests);
+
+        let now = now + Duration::from_secs(1);
+        limiter
+            .try_start_call(&limiter.anonymous, "eth_chainId", now)
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn traffic_tracker_basics() {
         let traffic_tracker = TrafficTracker::default();
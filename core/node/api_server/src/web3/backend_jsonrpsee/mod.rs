@@ -4,14 +4,17 @@
 /// namespace structures defined in `zksync_core`.
 use zksync_web3_decl::{
     error::Web3Error,
-    jsonrpsee::types::{error::ErrorCode, ErrorObjectOwned},
+    jsonrpsee::types::{
+        error::{ErrorCode, OVERSIZED_RESPONSE_CODE},
+        ErrorObjectOwned,
+    },
 };
 
 pub(crate) use self::{
     metadata::{MethodMetadata, MethodTracer},
     middleware::{
-        CorrelationMiddleware, LimitMiddleware, MetadataLayer, ServerTimeoutMiddleware,
-        ShutdownMiddleware, TrafficTracker,
+        ApiKeyLayer, ApiKeyLimitMiddleware, ApiKeyLimiter, CorrelationMiddleware, LimitMiddleware,
+        MetadataLayer, ServerTimeoutMiddleware, ShutdownMiddleware, TrafficTracker,
     },
 };
 use crate::tx_sender::SubmitTxError;
@@ -40,11 +43,14 @@ impl MethodTracer {
             | Web3Error::TooManyTopics
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFilterBlockHash
-            | Web3Error::LogsLimitExceeded(_, _, _) => ErrorCode::InvalidParams.code(),
+            | Web3Error::LogsLimitExceeded(_, _, _)
+            | Web3Error::BlockRangeLimitExceeded(_)
+            | Web3Error::InvalidSimulation(_) => ErrorCode::InvalidParams.code(),
             Web3Error::SubmitTransactionError(_, _)
             | Web3Error::SerializationError(_)
             | Web3Error::ProxyError(_) => 3,
             Web3Error::TreeApiUnavailable => 6,
+            Web3Error::TraceTooLarge(_) => OVERSIZED_RESPONSE_CODE,
             Web3Error::ServerShuttingDown => ErrorCode::ServerIsBusy.code(),
         };
         let message = match err {
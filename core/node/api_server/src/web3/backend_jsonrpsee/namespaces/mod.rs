@@ -3,6 +3,7 @@ pub mod en;
 pub mod eth;
 pub mod net;
 pub mod snapshots;
+pub mod trace;
 pub mod unstable;
 pub mod web3;
 pub mod zks;
@@ -0,0 +1,45 @@
+use zksync_types::{
+    api::{
+        trace::{TraceFilter, TraceResults, TraceType},
+        BlockNumber,
+    },
+    debug_flat_call::DebugCallFlat,
+    H256,
+};
+use zksync_web3_decl::{
+    jsonrpsee::core::{async_trait, RpcResult},
+    namespaces::TraceNamespaceServer,
+};
+
+use crate::web3::namespaces::TraceNamespace;
+
+#[async_trait]
+impl TraceNamespaceServer for TraceNamespace {
+    async fn trace_block(&self, block: BlockNumber) -> RpcResult<Vec<DebugCallFlat>> {
+        self.trace_block_impl(block)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn trace_transaction(&self, tx_hash: H256) -> RpcResult<Option<Vec<DebugCallFlat>>> {
+        self.trace_transaction_impl(tx_hash)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<DebugCallFlat>> {
+        self.trace_filter_impl(filter)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn replay_block_transactions(
+        &self,
+        block: BlockNumber,
+        trace_types: Vec<TraceType>,
+    ) -> RpcResult<Vec<TraceResults>> {
+        self.replay_block_transactions_impl(block, trace_types)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+}
@@ -1,7 +1,8 @@
 use zksync_types::{
     api::{
-        ChainAggProof, DataAvailabilityDetails, GatewayMigrationStatus, L1ToL2TxsStatus, TeeProof,
-        TransactionDetailedResult, TransactionExecutionInfo,
+        profile::TransactionProfile, ChainAggProof, DataAvailabilityDetails,
+        GatewayMigrationStatus, L1ToL2TxsStatus, TeeProof, TransactionDetailedResult,
+        TransactionExecutionInfo,
     },
     tee_types::TeeType,
     web3, L1BatchNumber, L2BlockNumber, L2ChainId, H256,
@@ -93,4 +94,10 @@ impl UnstableNamespaceServer for UnstableNamespace {
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn profile_transaction(&self, hash: H256) -> RpcResult<Option<TransactionProfile>> {
+        self.profile_transaction_impl(hash)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
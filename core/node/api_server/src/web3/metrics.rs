@@ -172,6 +172,9 @@ enum Web3ErrorKind {
     FilterNotFound,
     LogsLimitExceeded,
     InvalidFilterBlockHash,
+    BlockRangeLimitExceeded,
+    InvalidSimulation,
+    TraceTooLarge,
     TreeApiUnavailable,
     Internal,
 }
@@ -188,6 +191,9 @@ impl Web3ErrorKind {
             Web3Error::FilterNotFound => Self::FilterNotFound,
             Web3Error::LogsLimitExceeded(..) => Self::LogsLimitExceeded,
             Web3Error::InvalidFilterBlockHash => Self::InvalidFilterBlockHash,
+            Web3Error::BlockRangeLimitExceeded(_) => Self::BlockRangeLimitExceeded,
+            Web3Error::InvalidSimulation(_) => Self::InvalidSimulation,
+            Web3Error::TraceTooLarge(_) => Self::TraceTooLarge,
             Web3Error::TreeApiUnavailable => Self::TreeApiUnavailable,
             Web3Error::InternalError(_)
             | Web3Error::MethodNotImplemented
@@ -399,6 +405,8 @@ pub enum SubscriptionType {
     Blocks,
     Txs,
     Logs,
+    Preconfirmations,
+    L1BatchStatus,
 }
 
 #[derive(Debug, Metrics)]
@@ -8,11 +8,13 @@ use tokio::{
     task::JoinHandle,
 };
 use tower_http::{cors::CorsLayer, metrics::InFlightRequestsLayer};
-use zksync_config::configs::api::{MaxResponseSize, MaxResponseSizeOverrides, Namespace};
+use zksync_config::configs::api::{
+    HttpRateLimitsConfig, MaxResponseSize, MaxResponseSizeOverrides, Namespace,
+};
 use zksync_dal::{helpers::wait_for_l1_batch, ConnectionPool, Core};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_shared_resources::{
-    api::{BridgeAddressesHandle, SyncState},
+    api::{BridgeAddressesHandle, SyncState, TxPreconfirmations},
     tree::TreeApiClient,
 };
 use zksync_types::{try_stoppable, L2BlockNumber, StopContext};
@@ -26,22 +28,22 @@ use zksync_web3_decl::{
     },
     namespaces::{
         DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer, EthPubSubServer,
-        NetNamespaceServer, SnapshotsNamespaceServer, UnstableNamespaceServer, Web3NamespaceServer,
-        ZksNamespaceServer,
+        NetNamespaceServer, SnapshotsNamespaceServer, TraceNamespaceServer,
+        UnstableNamespaceServer, Web3NamespaceServer, ZksNamespaceServer,
     },
     types::Filter,
 };
 
 use self::{
     backend_jsonrpsee::{
-        CorrelationMiddleware, LimitMiddleware, MetadataLayer, MethodTracer, ShutdownMiddleware,
-        TrafficTracker,
+        ApiKeyLayer, ApiKeyLimitMiddleware, ApiKeyLimiter, CorrelationMiddleware, LimitMiddleware,
+        MetadataLayer, MethodTracer, ShutdownMiddleware, TrafficTracker,
     },
     mempool_cache::MempoolCache,
     metrics::API_METRICS,
     namespaces::{
         DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace,
-        UnstableNamespace, Web3Namespace, ZksNamespace,
+        TraceNamespace, UnstableNamespace, Web3Namespace, ZksNamespace,
     },
     pubsub::{EthSubscribe, EthSubscriptionIdProvider, PubSubEvent},
     receipts::AccountTypesCache,
@@ -97,11 +99,13 @@ enum ApiTransport {
 struct OptionalApiParams {
     vm_barrier: Option<VmConcurrencyBarrier>,
     sync_state: Option<SyncState>,
+    tx_preconfirmations: Option<TxPreconfirmations>,
     filters_limit: Option<usize>,
     subscriptions_limit: Option<usize>,
     batch_request_size_limit: Option<usize>,
     response_body_size_limit: Option<MaxResponseSize>,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    http_rate_limits: Option<HttpRateLimitsConfig>,
     request_timeout: Option<Duration>,
     tree_api: Option<Arc<dyn TreeApiClient>>,
     mempool_cache: Option<MempoolCache>,
@@ -211,6 +215,11 @@ impl ApiBuilder {
         self
     }
 
+    pub fn with_http_rate_limits(mut self, http_rate_limits: HttpRateLimitsConfig) -> Self {
+        self.optional.http_rate_limits = Some(http_rate_limits);
+        self
+    }
+
     pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
         self.optional.request_timeout = Some(timeout);
         self
@@ -221,6 +230,11 @@ impl ApiBuilder {
         self
     }
 
+    pub fn with_tx_preconfirmations(mut self, tx_preconfirmations: TxPreconfirmations) -> Self {
+        self.optional.tx_preconfirmations = Some(tx_preconfirmations);
+        self
+    }
+
     pub fn with_pruning_info_refresh_interval(mut self, interval: Duration) -> Self {
         self.pruning_info_refresh_interval = interval;
         self
@@ -339,6 +353,7 @@ impl ApiServer {
             connection_pool: self.pool,
             tx_sender: self.tx_sender,
             sync_state: self.optional.sync_state,
+            tx_preconfirmations: self.optional.tx_preconfirmations,
             api_config: self.config,
             start_info,
             mempool_cache: self.optional.mempool_cache,
@@ -347,6 +362,7 @@ impl ApiServer {
             bridge_addresses_handle: self.bridge_addresses_handle,
             tree_api: self.optional.tree_api,
             l2_l1_log_proof_handler: self.optional.l2_l1_log_proof_handler,
+            response_size_limit: self.optional.response_body_size_limit,
         })
     }
 
@@ -393,6 +409,10 @@ impl ApiServer {
             rpc.merge(SnapshotsNamespace::new(rpc_state.clone()).into_rpc())
                 .context("cannot merge snapshots namespace")?;
         }
+        if namespaces.contains(&Namespace::Trace) {
+            rpc.merge(TraceNamespace::new(rpc_state.clone()).into_rpc())
+                .context("cannot merge trace namespace")?;
+        }
         if namespaces.contains(&Namespace::Unstable) {
             rpc.merge(UnstableNamespace::new(rpc_state).into_rpc())
                 .context("cannot merge unstable namespace")?;
@@ -545,6 +565,18 @@ impl ApiServer {
                 (u32::MAX, MaxResponseSizeOverrides::empty())
             };
         let websocket_requests_per_minute_limit = self.optional.websocket_requests_per_minute_limit;
+        let api_key_limiter = match &self.optional.http_rate_limits {
+            Some(config) if config.is_enabled() => {
+                if is_http {
+                    tracing::info!("Enabled per-API key rate limits for {transport_str} API server: {config:?}");
+                    Some(Arc::new(ApiKeyLimiter::new(config)?))
+                } else {
+                    tracing::warn!("Per-API key rate limits are not supported for {transport_str} API server; ignoring");
+                    None
+                }
+            }
+            _ => None,
+        };
         let subscriptions_limit = self.optional.subscriptions_limit;
         let server_request_timeout = self.optional.request_timeout;
         let vm_barrier = self.optional.vm_barrier.clone();
@@ -571,7 +603,16 @@ impl ApiServer {
                 .allow_methods([http::Method::POST])
                 // Allow requests from any origin
                 .allow_origin(tower_http::cors::Any)
-                .allow_headers([http::header::CONTENT_TYPE])
+                .allow_headers(
+                    [http::header::CONTENT_TYPE]
+                        .into_iter()
+                        .chain(
+                            api_key_limiter
+                                .as_ref()
+                                .map(|limiter| limiter.header_name().clone()),
+                        )
+                        .collect::<Vec<_>>(),
+                )
         });
         // Setup metrics for the number of in-flight requests.
         let (in_flight_requests, counter) = InFlightRequestsLayer::pair();
@@ -584,7 +625,8 @@ impl ApiServer {
         // Assemble server middleware.
         let middleware = tower::ServiceBuilder::new()
             .layer(in_flight_requests)
-            .option_layer(cors);
+            .option_layer(cors)
+            .option_layer(api_key_limiter.clone().map(ApiKeyLayer::new));
 
         // Settings shared by HTTP and WS servers.
         let max_connections = !is_http
@@ -615,11 +657,14 @@ impl ApiServer {
             .option_layer(server_request_timeout.map(|timeout| {
                 tower::layer::layer_fn(move |svc| ServerTimeoutMiddleware::new(svc, timeout))
             }))
-            // We want to capture limit middleware errors with `metadata_layer`; hence, `LimitMiddleware` is placed after it.
+            // We want to capture limit middleware errors with `metadata_layer`; hence, `LimitMiddleware` and `ApiKeyLimitMiddleware` are placed after it.
             .option_layer((!is_http).then(|| {
                 tower::layer::layer_fn(move |svc| {
                     LimitMiddleware::new(svc, websocket_requests_per_minute_limit)
                 })
+            }))
+            .option_layer(api_key_limiter.map(|limiter| {
+                tower::layer::layer_fn(move |svc| ApiKeyLimitMiddleware::new(svc, limiter.clone()))
             }));
 
         let server_builder = ServerBuilder::default()
@@ -1,24 +1,33 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
 use anyhow::Context as _;
 use zksync_dal::{CoreDal, DalError};
-use zksync_multivm::interface::{Call, CallType, ExecutionResult, OneshotTracingParams};
+use zksync_multivm::interface::{
+    Call, CallType, ExecutionResult, OneshotTracingParams, PrestateAccount, PrestateState,
+    PrestateTrace, PrestateTracingMode, StructLog, StructLogTracingParams,
+};
 use zksync_system_constants::MAX_ENCODED_TX_SIZE;
 use zksync_types::{
     api::{
-        BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, DebugCall, DebugCallType,
-        ResultDebugCall, SupportedTracers, TracerConfig,
+        self, BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, DebugCall,
+        DebugCallType, ResultDebugCall, ResultDebugPrestate, ResultDebugStructLog,
+        SupportedTracers, TracerConfig,
     },
+    bytecode::{trim_padded_evm_bytecode, validate_bytecode, BytecodeHash, BytecodeMarker},
     debug_flat_call::{Action, CallResult, CallTraceMeta, DebugCallFlat, ResultDebugCallFlat},
     l2::L2Tx,
     transaction_request::CallRequest,
+    u256_to_h256,
+    utils::decompose_full_nonce,
     web3,
     web3::Bytes,
     zk_evm_types::FarCallOpcode,
-    H256, U256,
+    Address, L2BlockNumber, H256, U256,
 };
 use zksync_web3_decl::error::Web3Error;
 
 use crate::{
-    execution_sandbox::SandboxAction,
+    execution_sandbox::{SandboxAction, SandboxExecutionOutput},
     web3::{backend_jsonrpsee::MethodTracer, namespaces::validate_gas_cap, state::RpcState},
 };
 
@@ -55,7 +64,184 @@ impl DebugNamespace {
                 );
                 CallTracerResult::FlatCallTrace(calls)
             }
+            SupportedTracers::PrestateTracer | SupportedTracers::StructLogger => {
+                unreachable!("prestate traces and struct logs cannot be produced from call traces")
+            }
+        }
+    }
+
+    fn prestate_tracing_mode(tracer_option: &TracerConfig) -> PrestateTracingMode {
+        if tracer_option.tracer_config.diff_mode {
+            PrestateTracingMode::Diff
+        } else {
+            PrestateTracingMode::Prestate
+        }
+    }
+
+    fn struct_log_tracing_params(&self, tracer_option: &TracerConfig) -> StructLogTracingParams {
+        let max_size = self
+            .state
+            .response_size_limit
+            .as_ref()
+            .zip(self.current_method().method_name())
+            .map_or(usize::MAX, |(limit, method_name)| {
+                limit.for_method(method_name)
+            });
+        StructLogTracingParams {
+            enable_memory: tracer_option.tracer_config.enable_memory,
+            disable_stack: tracer_option.tracer_config.disable_stack,
+            disable_storage: tracer_option.tracer_config.disable_storage,
+            limit: tracer_option.tracer_config.limit,
+            max_size,
+        }
+    }
+
+    fn map_struct_logs(
+        output: SandboxExecutionOutput,
+        params: StructLogTracingParams,
+    ) -> Result<api::StructLogTrace, Web3Error> {
+        // Struct logs are only produced by the fast VM, which may be disabled or unsupported for the protocol version.
+        let struct_logs = output.struct_logs.ok_or(Web3Error::MethodNotImplemented)?;
+        if struct_logs.size_limit_exceeded {
+            return Err(Web3Error::TraceTooLarge(params.max_size));
+        }
+
+        let (failed, return_value) = match output.result {
+            ExecutionResult::Success { output } => (false, output),
+            ExecutionResult::Revert { output } => (true, output.encoded_data()),
+            ExecutionResult::Halt { .. } => (true, vec![]),
+        };
+        Ok(api::StructLogTrace {
+            gas: output.metrics.vm.gas_used as u64,
+            failed,
+            return_value: Bytes(return_value),
+            struct_logs: struct_logs
+                .logs
+                .into_iter()
+                .map(Self::map_struct_log)
+                .collect(),
+        })
+    }
+
+    fn map_struct_log(log: StructLog) -> api::StructLog {
+        api::StructLog {
+            pc: log.pc,
+            op: log.op,
+            gas: log.gas,
+            gas_cost: log.gas_cost,
+            depth: log.depth,
+            stack: log.stack,
+            memory: log
+                .memory
+                .map(|words| words.into_iter().map(u256_to_h256).collect()),
+            storage: log.storage,
+            error: log.error,
+        }
+    }
+
+    /// Maps a prestate trace to the API format. `bytecodes` must contain bytecodes for all code hashes in the trace,
+    /// as returned by [`Self::load_prestate_bytecodes()`].
+    pub(crate) fn map_prestate_trace(
+        trace: PrestateTrace,
+        mode: PrestateTracingMode,
+        bytecodes: &HashMap<H256, Bytes>,
+    ) -> api::PrestateTrace {
+        match mode {
+            PrestateTracingMode::Diff => api::PrestateTrace::Diff {
+                pre: Self::map_prestate_state(trace.pre, bytecodes),
+                post: Self::map_prestate_state(trace.post, bytecodes),
+            },
+            PrestateTracingMode::Prestate => {
+                api::PrestateTrace::Prestate(Self::map_prestate_state(trace.pre, bytecodes))
+            }
+        }
+    }
+
+    fn map_prestate_state(
+        state: PrestateState,
+        bytecodes: &HashMap<H256, Bytes>,
+    ) -> BTreeMap<Address, api::PrestateAccount> {
+        state
+            .into_iter()
+            .map(|(address, account)| (address, Self::map_prestate_account(account, bytecodes)))
+            .collect()
+    }
+
+    fn map_prestate_account(
+        account: PrestateAccount,
+        bytecodes: &HashMap<H256, Bytes>,
+    ) -> api::PrestateAccount {
+        api::PrestateAccount {
+            balance: account.balance,
+            nonce: account
+                .nonce
+                .map(|full_nonce| decompose_full_nonce(full_nonce).0.low_u64()),
+            code: account
+                .code
+                .and_then(Self::prestate_code_hash)
+                .and_then(|hash| bytecodes.get(&hash).cloned()),
+            storage: account.storage.map(|storage| storage.into_iter().collect()),
+        }
+    }
+
+    /// Converts a value from `AccountCodeStorage` to a bytecode hash, or returns `None` for accounts without code.
+    fn prestate_code_hash(code: U256) -> Option<H256> {
+        if code.is_zero() {
+            return None;
+        }
+        let mut code_hash = u256_to_h256(code);
+        // Reset the marker set for contracts being constructed.
+        code_hash.0[1] = 0;
+        Some(code_hash)
+    }
+
+    /// Loads bytecodes for all code hashes in the provided prestate traces. `deployed_bytecodes` should contain
+    /// bytecodes that may be unknown to the storage (e.g., deployed by a traced call); other bytecodes are loaded
+    /// from the storage.
+    async fn load_prestate_bytecodes<'a>(
+        &self,
+        traces: impl Iterator<Item = &'a PrestateTrace>,
+        mut deployed_bytecodes: HashMap<H256, Vec<u8>>,
+    ) -> Result<HashMap<H256, Bytes>, Web3Error> {
+        let code_hashes: HashSet<_> = traces
+            .flat_map(|trace| trace.pre.values().chain(trace.post.values()))
+            .filter_map(|account| account.code.and_then(Self::prestate_code_hash))
+            .collect();
+        let missing_hashes: HashSet<_> = code_hashes
+            .iter()
+            .filter(|hash| !deployed_bytecodes.contains_key(hash))
+            .copied()
+            .collect();
+        if !missing_hashes.is_empty() {
+            let mut connection = self.state.acquire_connection().await?;
+            let stored_bytecodes = connection
+                .factory_deps_dal()
+                .get_factory_deps(&missing_hashes)
+                .await;
+            deployed_bytecodes.extend(
+                stored_bytecodes
+                    .into_iter()
+                    .map(|(hash, bytecode)| (u256_to_h256(hash), bytecode)),
+            );
+        }
+
+        let mut bytecodes = HashMap::with_capacity(code_hashes.len());
+        for code_hash in code_hashes {
+            let Some(bytecode) = deployed_bytecodes.remove(&code_hash) else {
+                continue;
+            };
+            // Same as in `eth_getCode`, EVM bytecodes are returned without padding.
+            let bytecode = match BytecodeHash::try_from(code_hash) {
+                Ok(hash) if hash.marker() == BytecodeMarker::Evm => {
+                    trim_padded_evm_bytecode(hash, &bytecode)
+                        .with_context(|| format!("malformed EVM bytecode, hash = {code_hash:?}"))?
+                        .to_vec()
+                }
+                _ => bytecode,
+            };
+            bytecodes.insert(code_hash, bytecode.into());
         }
+        Ok(bytecodes)
     }
 
     pub(crate) fn map_default_call(
@@ -94,7 +280,7 @@ impl DebugNamespace {
         }
     }
 
-    fn flatten_call(
+    pub(crate) fn flatten_call(
         call: Call,
         calls: &mut Vec<DebugCallFlat>,
         trace_address: &mut Vec<usize>,
@@ -191,13 +377,34 @@ impl DebugNamespace {
         self.current_method()
             .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
 
+        let options = options.unwrap_or_default();
+        match options.tracer {
+            SupportedTracers::PrestateTracer => {
+                drop(connection);
+                let mode = Self::prestate_tracing_mode(&options);
+                let traces = self
+                    .replay_block_with_prestate(block_number, None, mode)
+                    .await?;
+                return Ok(CallTracerBlockResult::PrestateTrace(traces));
+            }
+            SupportedTracers::StructLogger => {
+                drop(connection);
+                let params = self.struct_log_tracing_params(&options);
+                let traces = self
+                    .replay_block_with_struct_logs(block_number, None, params)
+                    .await?;
+                return Ok(CallTracerBlockResult::StructLogTrace(traces));
+            }
+            SupportedTracers::CallTracer | SupportedTracers::FlatCallTracer => { /* handled below */
+            }
+        }
+
         let call_traces = connection
             .blocks_web3_dal()
             .get_traces_for_l2_block(block_number)
             .await
             .map_err(DalError::generalize)?;
 
-        let options = options.unwrap_or_default();
         let result = match options.tracer {
             SupportedTracers::CallTracer => CallTracerBlockResult::CallTrace(
                 call_traces
@@ -232,24 +439,131 @@ impl DebugNamespace {
                     .collect();
                 CallTracerBlockResult::FlatCallTrace(res)
             }
+            SupportedTracers::PrestateTracer | SupportedTracers::StructLogger => {
+                unreachable!("handled above")
+            }
         };
         Ok(result)
     }
 
+    /// Re-executes transactions in the specified L2 block with the prestate tracer.
+    /// See [`RpcState::replay_block()`] for details.
+    async fn replay_block_with_prestate(
+        &self,
+        block_number: L2BlockNumber,
+        target_tx_hash: Option<H256>,
+        mode: PrestateTracingMode,
+    ) -> Result<Vec<ResultDebugPrestate>, Web3Error> {
+        let tracing_params = OneshotTracingParams {
+            trace_prestate: Some(mode),
+            ..OneshotTracingParams::default()
+        };
+        let outputs = self
+            .state
+            .replay_block(block_number, target_tx_hash, tracing_params)
+            .await?;
+        let traces = outputs
+            .into_iter()
+            .map(|(tx_hash, output)| {
+                let trace = output
+                    .prestate_trace
+                    .context("prestate trace is missing in execution output")?;
+                Ok((tx_hash, trace))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        // All bytecodes deployed by replayed transactions are persisted, so they can be loaded from the storage.
+        let bytecodes = self
+            .load_prestate_bytecodes(traces.iter().map(|(_, trace)| trace), HashMap::new())
+            .await?;
+        Ok(traces
+            .into_iter()
+            .map(|(tx_hash, trace)| ResultDebugPrestate {
+                tx_hash,
+                result: Self::map_prestate_trace(trace, mode, &bytecodes),
+            })
+            .collect())
+    }
+
+    /// Re-executes transactions in the specified L2 block with the struct logger.
+    /// See [`RpcState::replay_block()`] for details.
+    async fn replay_block_with_struct_logs(
+        &self,
+        block_number: L2BlockNumber,
+        target_tx_hash: Option<H256>,
+        params: StructLogTracingParams,
+    ) -> Result<Vec<ResultDebugStructLog>, Web3Error> {
+        let tracing_params = OneshotTracingParams {
+            trace_struct_logs: Some(params),
+            ..OneshotTracingParams::default()
+        };
+        let outputs = self
+            .state
+            .replay_block(block_number, target_tx_hash, tracing_params)
+            .await?;
+
+        let mut remaining_size = params.max_size;
+        let mut traces = Vec::with_capacity(outputs.len());
+        for (tx_hash, output) in outputs {
+            let result = Self::map_struct_logs(output, params)?;
+            // The size limit is enforced by the tracer for each transaction; here, we enforce it for the entire block.
+            let size = serde_json::to_vec(&result)
+                .context("failed serializing struct logs")?
+                .len();
+            remaining_size = remaining_size
+                .checked_sub(size)
+                .ok_or(Web3Error::TraceTooLarge(params.max_size))?;
+            traces.push(ResultDebugStructLog { tx_hash, result });
+        }
+        Ok(traces)
+    }
+
     pub async fn debug_trace_transaction_impl(
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
     ) -> Result<Option<CallTracerResult>, Web3Error> {
+        let options = options.unwrap_or_default();
         let mut connection = self.state.acquire_connection().await?;
+        if let SupportedTracers::PrestateTracer | SupportedTracers::StructLogger = options.tracer {
+            let tx = connection
+                .transactions_web3_dal()
+                .get_transaction_by_hash(tx_hash, self.state.api_config.l2_chain_id)
+                .await
+                .map_err(DalError::generalize)?;
+            let Some(block_number) = tx.and_then(|tx| tx.block_number) else {
+                return Ok(None);
+            };
+            drop(connection);
+
+            let block_number = L2BlockNumber(block_number.as_u32());
+            let block_id = BlockId::Number(BlockNumber::Number(block_number.0.into()));
+            self.current_method().set_block_id(block_id);
+            let result = if let SupportedTracers::StructLogger = options.tracer {
+                let params = self.struct_log_tracing_params(&options);
+                let trace = self
+                    .replay_block_with_struct_logs(block_number, Some(tx_hash), params)
+                    .await?
+                    .pop()
+                    .context("transaction was not traced")?;
+                CallTracerResult::StructLogTrace(trace.result)
+            } else {
+                let mode = Self::prestate_tracing_mode(&options);
+                let trace = self
+                    .replay_block_with_prestate(block_number, Some(tx_hash), mode)
+                    .await?
+                    .pop()
+                    .context("transaction was not traced")?;
+                CallTracerResult::PrestateTrace(trace.result)
+            };
+            return Ok(Some(result));
+        }
+
         let call_trace = connection
             .transactions_dal()
             .get_call_trace(tx_hash)
             .await
             .map_err(DalError::generalize)?;
-        Ok(call_trace.map(|(call_trace, meta)| {
-            Self::map_call(call_trace, meta, options.unwrap_or_default())
-        }))
+        Ok(call_trace.map(|(call_trace, meta)| Self::map_call(call_trace, meta, options)))
     }
 
     pub async fn debug_trace_call_impl(
@@ -324,9 +638,26 @@ impl DebugNamespace {
             .await;
         let vm_permit = vm_permit.context("cannot acquire VM permit")?;
 
-        // We don't need properly trace if we only need top call
-        let tracing_params = OneshotTracingParams {
-            trace_calls: !options.tracer_config.only_top_call,
+        let prestate_mode = matches!(options.tracer, SupportedTracers::PrestateTracer)
+            .then(|| Self::prestate_tracing_mode(&options));
+        let struct_log_params = matches!(options.tracer, SupportedTracers::StructLogger)
+            .then(|| self.struct_log_tracing_params(&options));
+        let tracing_params = if prestate_mode.is_some() {
+            OneshotTracingParams {
+                trace_prestate: prestate_mode,
+                ..OneshotTracingParams::default()
+            }
+        } else if struct_log_params.is_some() {
+            OneshotTracingParams {
+                trace_struct_logs: struct_log_params,
+                ..OneshotTracingParams::default()
+            }
+        } else {
+            // We don't need properly trace if we only need top call
+            OneshotTracingParams {
+                trace_calls: !options.tracer_config.only_top_call,
+                ..OneshotTracingParams::default()
+            }
         };
 
         let connection = self.state.acquire_connection().await?;
@@ -339,13 +670,41 @@ impl DebugNamespace {
                     call: call.clone(),
                     fee_input,
                     enforced_base_fee: call_overrides.enforced_base_fee,
+                    block_timestamp: None,
+                    prev_simulated_block: None,
                     tracing_params,
+                    collect_accessed_keys: false,
                 },
                 &block_args,
                 None,
             )
             .await?;
 
+        if let Some(mode) = prestate_mode {
+            let trace = result
+                .prestate_trace
+                .context("prestate trace is missing in execution output")?;
+            let mut deployed_bytecodes = result.dynamic_factory_deps;
+            deployed_bytecodes.extend(
+                call.execute
+                    .factory_deps
+                    .iter()
+                    .filter(|dep| validate_bytecode(dep).is_ok())
+                    .map(|dep| (BytecodeHash::for_bytecode(dep).value(), dep.clone())),
+            );
+            let bytecodes = self
+                .load_prestate_bytecodes(std::iter::once(&trace), deployed_bytecodes)
+                .await?;
+            return Ok(CallTracerResult::PrestateTrace(Self::map_prestate_trace(
+                trace, mode, &bytecodes,
+            )));
+        }
+        if let Some(params) = struct_log_params {
+            return Ok(CallTracerResult::StructLogTrace(Self::map_struct_logs(
+                result, params,
+            )?));
+        }
+
         let (output, revert_reason) = match result.result {
             ExecutionResult::Success { output, .. } => (output, None),
             ExecutionResult::Revert { output } => (vec![], Some(output.to_string())),
@@ -1,16 +1,26 @@
+use std::collections::HashMap;
+
 use anyhow::Context as _;
 use zksync_dal::{CoreDal, DalError};
-use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
+use zksync_multivm::{
+    interface::{ExecutionResult, OneshotTracingParams},
+    utils::derive_base_fee_and_gas_per_pubdata,
+};
+use zksync_system_constants::{DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE, SYSTEM_CONTEXT_ADDRESS};
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockId, BlockNumber, FeeHistory, GetLogsFilter,
-        Transaction, TransactionId, TransactionReceipt, TransactionVariant,
+        simulate::{SimulatePayload, SimulatedBlock, SimulatedCallError, SimulatedCallResult},
+        state_override::StateOverride,
+        AccessListResult, BlockId, BlockNumber, EthProof, FeeHistory, GetLogsFilter, Transaction,
+        TransactionId, TransactionReceipt, TransactionVariant,
     },
-    bytecode::{trim_padded_evm_bytecode, BytecodeHash, BytecodeMarker},
+    bytecode::{trim_padded_evm_bytecode, validate_bytecode, BytecodeHash, BytecodeMarker},
+    get_code_key, get_nonce_key, h256_to_u256,
     l2::{L2Tx, TransactionType},
     transaction_request::CallRequest,
     u256_to_h256,
-    web3::{self, Bytes, SyncInfo, SyncState},
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
+    web3::{self, AccessListItem, Bytes, SyncInfo, SyncState},
     AccountTreeId, L2BlockNumber, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
 };
 use zksync_web3_decl::{
@@ -19,17 +29,28 @@ use zksync_web3_decl::{
 };
 
 use crate::{
-    execution_sandbox::BlockArgs,
-    tx_sender::BinarySearchKind,
+    execution_sandbox::{
+        extend_state_override, extend_state_override_with_bytecodes, merge_state_override,
+        BlockArgs, SandboxAction, SandboxExecutionError, SimulatedL2Block,
+    },
+    tx_sender::{ApiCallResult, BinarySearchKind, SubmitTxError},
     utils::open_readonly_transaction,
     web3::{
-        backend_jsonrpsee::MethodTracer, namespaces::validate_gas_cap,
-        receipts::fill_transaction_receipts, state::RpcState, TypedFilter,
+        backend_jsonrpsee::MethodTracer,
+        namespaces::{get_tree_proofs, validate_gas_cap},
+        receipts::fill_transaction_receipts,
+        state::RpcState,
+        TypedFilter,
     },
 };
 
 pub const EVENT_TOPIC_NUMBER_LIMIT: usize = 4;
 
+/// Checks whether the address is in the kernel space (i.e., below `2^16`), which contains system contracts and precompiles.
+fn is_kernel_space_address(address: &Address) -> bool {
+    address.as_bytes()[..18] == [0; 18]
+}
+
 #[derive(Debug)]
 pub(crate) struct EthNamespace {
     state: RpcState,
@@ -114,6 +135,364 @@ impl EthNamespace {
         Ok(call_result.into())
     }
 
+    /// Executes a call and returns storage slots accessed by it. Since access lists don't influence execution or gas costs
+    /// in EraVM, the returned list is not iteratively refined (unlike in Geth). The gas is estimated in the same way
+    /// as in `eth_estimateGas`, but on top of the requested block.
+    ///
+    /// Slots of system contracts in the kernel space (e.g., nonces, base token balances and known bytecodes touched
+    /// by the bootloader for every transaction) are not included into the list.
+    pub async fn create_access_list_impl(
+        &self,
+        mut request: CallRequest,
+        block_id: Option<BlockId>,
+    ) -> Result<AccessListResult, Web3Error> {
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+        let mut estimate_request = request.clone();
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+
+        validate_gas_cap(
+            &request,
+            block_id,
+            &block_args,
+            &mut connection,
+            self.state.api_config.eth_call_gas_cap,
+            self.current_method(),
+        )
+        .await?;
+        if request.gas.is_none() {
+            request.gas = Some(
+                block_args
+                    .default_eth_call_gas(&mut connection, self.state.api_config.eth_call_gas_cap)
+                    .await?,
+            );
+        }
+        drop(connection);
+
+        let call_overrides = request.get_call_overrides()?;
+        let tx = L2Tx::from_request(
+            request.into(),
+            self.state.api_config.max_tx_size,
+            block_args.use_evm_emulator(),
+        )?;
+        let output = self
+            .state
+            .tx_sender
+            .execute_call(block_args.clone(), call_overrides, tx, None, true)
+            .await
+            .map_err(|err| self.current_method().map_submit_err(err))?;
+
+        let mut access_list: Vec<AccessListItem> = vec![];
+        for key in &output.accessed_storage_keys {
+            let address = *key.address();
+            if is_kernel_space_address(&address) {
+                continue;
+            }
+            let index = match access_list.iter().position(|item| item.address == address) {
+                Some(index) => index,
+                None => {
+                    access_list.push(AccessListItem {
+                        address,
+                        storage_keys: vec![],
+                    });
+                    access_list.len() - 1
+                }
+            };
+            access_list[index].storage_keys.push(*key.key());
+        }
+
+        if let Err(err) = output.result.check_api_call_result() {
+            return Ok(AccessListResult {
+                access_list,
+                gas_used: output.metrics.vm.gas_used.into(),
+                error: Some(err.to_string()),
+            });
+        }
+
+        estimate_request.access_list = Some(access_list.clone());
+        let gas_used = self
+            .estimate_gas_for_block(estimate_request, block_args, None)
+            .await?;
+        Ok(AccessListResult {
+            access_list,
+            gas_used,
+            error: None,
+        })
+    }
+
+    /// Simulates a sequence of calls across several blocks. State changes produced by each call (including deployed
+    /// contracts) are observed by subsequent calls.
+    ///
+    /// The first simulated block is executed in the base block; each following block has the next block number.
+    /// Only the timestamp and base fee can be overridden. Hashes of simulated blocks don't account for simulated calls.
+    pub async fn simulate_v1_impl(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, Web3Error> {
+        const MAX_SIMULATED_BLOCKS: usize = 256;
+        const MAX_SIMULATED_CALLS: usize = 1_000;
+
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+
+        if payload.trace_transfers {
+            return Err(Web3Error::InvalidSimulation(
+                "tracing transfers is not supported".to_owned(),
+            ));
+        }
+        if payload.validation {
+            return Err(Web3Error::InvalidSimulation(
+                "validation mode is not supported".to_owned(),
+            ));
+        }
+        if payload.block_state_calls.len() > MAX_SIMULATED_BLOCKS {
+            return Err(Web3Error::InvalidSimulation(format!(
+                "at most {MAX_SIMULATED_BLOCKS} blocks can be simulated at once"
+            )));
+        }
+        let calls_count: usize = payload
+            .block_state_calls
+            .iter()
+            .map(|block| block.calls.len())
+            .sum();
+        if calls_count > MAX_SIMULATED_CALLS {
+            return Err(Web3Error::InvalidSimulation(format!(
+                "at most {MAX_SIMULATED_CALLS} calls can be simulated at once"
+            )));
+        }
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+
+        for request in payload
+            .block_state_calls
+            .iter()
+            .flat_map(|block| &block.calls)
+        {
+            validate_gas_cap(
+                request,
+                block_id,
+                &block_args,
+                &mut connection,
+                self.state.api_config.eth_call_gas_cap,
+                self.current_method(),
+            )
+            .await?;
+        }
+        let default_gas = block_args
+            .default_eth_call_gas(&mut connection, self.state.api_config.eth_call_gas_cap)
+            .await?;
+        let base_timestamp = block_args.l2_block_timestamp(&mut connection).await?;
+        let base_prev_block_hash = block_args.prev_l2_block_hash(&mut connection).await?;
+
+        let fee_input = if block_args.resolves_to_latest_sealed_l2_block() {
+            // It is important to drop a DB connection before calling the provider, since it acquires a connection internally
+            // on the main node.
+            drop(connection);
+            self.state.tx_sender.scaled_batch_fee_input().await?
+        } else {
+            let fee_input = block_args.historical_fee_input(&mut connection).await?;
+            drop(connection);
+            fee_input
+        };
+        let (default_base_fee, _) =
+            derive_base_fee_and_gas_per_pubdata(fee_input, block_args.protocol_version().into());
+
+        let vm_permit = self
+            .state
+            .tx_sender
+            .vm_concurrency_limiter()
+            .acquire()
+            .await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+        let executor = &self.state.tx_sender.0.executor;
+
+        let protocol_version = block_args.protocol_version();
+        let mut state_override = StateOverride::default();
+        // Bytecodes deployed by simulated calls, which are not available in the storage.
+        let mut deployed_bytecodes = HashMap::new();
+        let mut prev_block: Option<SimulatedL2Block> = None;
+        let mut simulated_blocks = Vec::with_capacity(payload.block_state_calls.len());
+        for block_calls in payload.block_state_calls {
+            let block_overrides = block_calls.block_overrides.unwrap_or_default();
+            let min_timestamp = prev_block.map_or(base_timestamp, |block| block.timestamp + 1);
+            let timestamp = match block_overrides.time {
+                Some(time) if time.as_u64() < min_timestamp => {
+                    return Err(Web3Error::InvalidSimulation(format!(
+                        "block timestamp {time} is lower than the minimum allowed timestamp {min_timestamp}"
+                    )));
+                }
+                Some(time) => time.as_u64(),
+                None => min_timestamp,
+            };
+            let block = match &prev_block {
+                Some(prev_block) => prev_block.next(timestamp, protocol_version),
+                None => SimulatedL2Block {
+                    number: block_args.resolved_block_number(),
+                    timestamp,
+                    prev_block_hash: base_prev_block_hash,
+                },
+            };
+            let block_number = block.number;
+            let enforced_base_fee = block_overrides
+                .base_fee_per_gas
+                .map(|base_fee| {
+                    u64::try_from(base_fee).map_err(|_| {
+                        Web3Error::InvalidSimulation(format!("base fee {base_fee} is too large"))
+                    })
+                })
+                .transpose()?;
+
+            if let Some(overrides) = block_calls.state_overrides {
+                self.current_method()
+                    .observe_state_override(Some(&overrides));
+                merge_state_override(&mut state_override, overrides);
+            }
+
+            let mut block_gas_used = U256::zero();
+            let mut log_index = 0_u64;
+            let mut call_results = Vec::with_capacity(block_calls.calls.len());
+            for (call_index, mut request) in block_calls.calls.into_iter().enumerate() {
+                if request.gas.is_none() {
+                    request.gas = Some(default_gas);
+                }
+                let call_overrides = request.get_call_overrides()?;
+                let call = L2Tx::from_request(
+                    request.into(),
+                    self.state.api_config.max_tx_size,
+                    block_args.use_evm_emulator(),
+                )?;
+                let tx_hash = call.hash();
+                deployed_bytecodes.extend(
+                    call.execute
+                        .factory_deps
+                        .iter()
+                        .filter(|dep| validate_bytecode(dep).is_ok())
+                        .map(|dep| (BytecodeHash::for_bytecode(dep).value(), dep.clone())),
+                );
+
+                let connection = self.state.acquire_connection().await?;
+                let action = SandboxAction::Call {
+                    call,
+                    fee_input,
+                    enforced_base_fee: enforced_base_fee.or(call_overrides.enforced_base_fee),
+                    block_timestamp: Some(timestamp),
+                    prev_simulated_block: prev_block,
+                    tracing_params: OneshotTracingParams::default(),
+                    collect_accessed_keys: false,
+                };
+                let result = executor
+                    .execute_in_sandbox(
+                        vm_permit.clone(),
+                        connection,
+                        action,
+                        &block_args,
+                        Some(state_override.clone()),
+                    )
+                    .await?;
+
+                // System context writes are excluded since they would conflict with the block environment
+                // set up for subsequent calls.
+                let write_logs: Vec<_> = result
+                    .write_logs
+                    .into_iter()
+                    .filter(|log| *log.key.address() != SYSTEM_CONTEXT_ADDRESS)
+                    .collect();
+                extend_state_override(&mut state_override, &write_logs);
+                deployed_bytecodes.extend(result.dynamic_factory_deps);
+                extend_state_override_with_bytecodes(
+                    &mut state_override,
+                    &write_logs,
+                    &deployed_bytecodes,
+                );
+
+                let logs = result
+                    .events
+                    .into_iter()
+                    .enumerate()
+                    .map(|(tx_log_index, event)| {
+                        let log = Log {
+                            address: event.address,
+                            topics: event.indexed_topics,
+                            data: event.value.into(),
+                            block_hash: None,
+                            block_number: Some(block_number.0.into()),
+                            l1_batch_number: None,
+                            transaction_hash: Some(tx_hash),
+                            transaction_index: Some(call_index.into()),
+                            log_index: Some(log_index.into()),
+                            transaction_log_index: Some(tx_log_index.into()),
+                            log_type: None,
+                            removed: Some(false),
+                            block_timestamp: Some(timestamp.into()),
+                        };
+                        log_index += 1;
+                        log
+                    })
+                    .collect();
+
+                let gas_used = U256::from(result.metrics.vm.gas_used);
+                block_gas_used += gas_used;
+                let (status, return_data, error) = match result.result {
+                    ExecutionResult::Success { output } => (U64::one(), output, None),
+                    ExecutionResult::Revert { output } => {
+                        let message = output.to_user_friendly_string();
+                        let err = SimulatedCallError {
+                            code: SimulatedCallError::REVERTED_CODE,
+                            message: SubmitTxError::ExecutionReverted(message, vec![]).to_string(),
+                            data: Some(output.encoded_data().into()),
+                        };
+                        (U64::zero(), vec![], Some(err))
+                    }
+                    ExecutionResult::Halt { reason } => {
+                        let err = SimulatedCallError {
+                            code: SimulatedCallError::VM_ERROR_CODE,
+                            message: SandboxExecutionError::from(reason).to_string(),
+                            data: None,
+                        };
+                        (U64::zero(), vec![], Some(err))
+                    }
+                };
+                call_results.push(SimulatedCallResult {
+                    return_data: return_data.into(),
+                    logs,
+                    gas_used,
+                    status,
+                    error,
+                });
+            }
+
+            simulated_blocks.push(SimulatedBlock {
+                number: block_number.0.into(),
+                timestamp: timestamp.into(),
+                base_fee_per_gas: enforced_base_fee.unwrap_or(default_base_fee).into(),
+                gas_used: block_gas_used,
+                calls: call_results,
+            });
+            prev_block = Some(block);
+        }
+        Ok(simulated_blocks)
+    }
+
     pub async fn estimate_gas_impl(
         &self,
         request: CallRequest,
@@ -123,6 +502,20 @@ impl EthNamespace {
         self.current_method()
             .observe_state_override(state_override.as_ref());
 
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = BlockArgs::pending(&mut connection).await?;
+        drop(connection);
+        self.estimate_gas_for_block(request, block_args, state_override)
+            .await
+    }
+
+    /// Estimates gas for a call request executed on top of the specified block.
+    async fn estimate_gas_for_block(
+        &self,
+        request: CallRequest,
+        block_args: BlockArgs,
+        state_override: Option<StateOverride>,
+    ) -> Result<U256, Web3Error> {
         let mut request_with_gas_per_pubdata_overridden = request;
         self.state
             .set_nonce_for_call_request(&mut request_with_gas_per_pubdata_overridden)
@@ -137,9 +530,6 @@ impl EthNamespace {
         let is_eip712 = request_with_gas_per_pubdata_overridden
             .eip712_meta
             .is_some();
-        let mut connection = self.state.acquire_connection().await?;
-        let block_args = BlockArgs::pending(&mut connection).await?;
-        drop(connection);
         let mut tx: L2Tx = L2Tx::from_request(
             request_with_gas_per_pubdata_overridden.into(),
             self.state.api_config.max_tx_size,
@@ -475,6 +865,76 @@ impl EthNamespace {
         Ok(value)
     }
 
+    /// Returns proofs for the account fields and the specified storage slots. Returns `Ok(None)` if the L1 batch containing
+    /// the requested block is not sealed or not processed by the Merkle tree yet.
+    pub async fn get_proof_impl(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block_id: Option<BlockId>,
+    ) -> Result<Option<EthProof>, Web3Error> {
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Latest));
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        self.set_block_diff(block_number);
+        let resolved_l1_batch = connection
+            .storage_web3_dal()
+            .resolve_l1_batch_number_of_l2_block(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(l1_batch_number) = resolved_l1_batch.block_l1_batch else {
+            return Ok(None);
+        };
+        self.state
+            .start_info
+            .ensure_not_pruned(l1_batch_number, &mut connection)
+            .await?;
+        let Some(root_hash) = connection
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        drop(connection);
+
+        let account = AccountTreeId::new(address);
+        let account_keys = [
+            get_code_key(&address),
+            get_nonce_key(&address),
+            storage_key_for_eth_balance(&address),
+        ];
+        let keys: Vec<_> = account_keys
+            .into_iter()
+            .chain(keys.into_iter().map(|key| StorageKey::new(account, key)))
+            .collect();
+        let tree_api = self.state.tree_api.as_deref();
+        let Some(mut account_proof) = get_tree_proofs(tree_api, l1_batch_number, &keys).await?
+        else {
+            return Ok(None);
+        };
+        let storage_proof = account_proof.split_off(account_keys.len());
+        let [code_proof, nonce_proof, balance_proof] = account_proof.as_slice() else {
+            let err = anyhow::anyhow!("unexpected number of proofs returned by Merkle tree API");
+            return Err(Web3Error::InternalError(err));
+        };
+        let (nonce, _) = decompose_full_nonce(h256_to_u256(nonce_proof.value));
+
+        Ok(Some(EthProof {
+            address,
+            balance: h256_to_u256(balance_proof.value),
+            code_hash: code_proof.value,
+            nonce,
+            storage_hash: root_hash,
+            account_proof,
+            storage_proof,
+            l1_batch_number,
+        }))
+    }
+
     /// Account nonce.
     pub async fn get_transaction_count_impl(
         &self,
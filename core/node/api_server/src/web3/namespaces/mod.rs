@@ -6,6 +6,7 @@ mod en;
 pub(crate) mod eth;
 mod net;
 mod snapshots;
+mod trace;
 mod unstable;
 mod utils;
 mod web3;
@@ -15,6 +16,6 @@ pub(crate) use utils::*;
 
 pub(super) use self::{
     debug::DebugNamespace, en::EnNamespace, eth::EthNamespace, net::NetNamespace,
-    snapshots::SnapshotsNamespace, unstable::UnstableNamespace, web3::Web3Namespace,
-    zks::ZksNamespace,
+    snapshots::SnapshotsNamespace, trace::TraceNamespace, unstable::UnstableNamespace,
+    web3::Web3Namespace, zks::ZksNamespace,
 };
@@ -0,0 +1,183 @@
+use zksync_dal::{CoreDal, DalError};
+use zksync_multivm::interface::Call;
+use zksync_types::{
+    api::{
+        trace::{TraceFilter, TraceResults, TraceType},
+        BlockId, BlockNumber,
+    },
+    debug_flat_call::{CallTraceMeta, DebugCallFlat},
+    L2BlockNumber, H256,
+};
+use zksync_web3_decl::error::Web3Error;
+
+use crate::web3::{backend_jsonrpsee::MethodTracer, namespaces::DebugNamespace, state::RpcState};
+
+/// Number of L2 blocks which call traces are loaded from Postgres at once in `trace_filter`.
+const TRACE_FILTER_PAGE_SIZE: u32 = 20;
+
+/// Parity-style `trace_*` methods. All methods are served from the call traces persisted in Postgres;
+/// no transactions are re-executed.
+#[derive(Debug, Clone)]
+pub(crate) struct TraceNamespace {
+    state: RpcState,
+}
+
+impl TraceNamespace {
+    pub fn new(state: RpcState) -> Self {
+        Self { state }
+    }
+
+    pub(crate) fn current_method(&self) -> &MethodTracer {
+        &self.state.current_method
+    }
+
+    /// Flattens the call trace of a single transaction. Unlike `debug_*` methods, trace addresses
+    /// are relative to the top-level call, as in Parity / Erigon.
+    fn flatten_call(call: Call, mut meta: CallTraceMeta) -> Vec<DebugCallFlat> {
+        let mut calls = vec![];
+        DebugNamespace::flatten_call(call, &mut calls, &mut vec![], false, &mut meta);
+        calls
+    }
+
+    /// Resolves the block and returns call traces for all its transactions.
+    async fn block_call_traces(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Vec<(Call, CallTraceMeta)>, Web3Error> {
+        let block_id = BlockId::Number(block_number);
+        self.current_method().set_block_id(block_id);
+        if matches!(block_number, BlockNumber::Pending) {
+            // See `EthNamespace::get_block_impl()` for an explanation why this check is needed.
+            return Ok(vec![]);
+        }
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        self.current_method()
+            .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+
+        Ok(connection
+            .blocks_web3_dal()
+            .get_traces_for_l2_block(block_number)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
+    pub async fn trace_block_impl(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Vec<DebugCallFlat>, Web3Error> {
+        let call_traces = self.block_call_traces(block_number).await?;
+        Ok(call_traces
+            .into_iter()
+            .flat_map(|(call, meta)| Self::flatten_call(call, meta))
+            .collect())
+    }
+
+    pub async fn trace_transaction_impl(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<Vec<DebugCallFlat>>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let call_trace = connection
+            .transactions_dal()
+            .get_call_trace(tx_hash)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(call_trace.map(|(call, meta)| Self::flatten_call(call, meta)))
+    }
+
+    pub async fn trace_filter_impl(
+        &self,
+        filter: TraceFilter,
+    ) -> Result<Vec<DebugCallFlat>, Web3Error> {
+        let from_block = self
+            .state
+            .resolve_filter_block_number(filter.from_block)
+            .await?;
+        let to_block = self
+            .state
+            .resolve_filter_block_number(filter.to_block)
+            .await?;
+        self.current_method()
+            .set_block_id(BlockId::Number(BlockNumber::Number(from_block.0.into())));
+        if from_block > to_block {
+            return Ok(vec![]);
+        }
+        let block_range_limit = self.state.api_config.trace_filter_block_range_limit;
+        if to_block.0 - from_block.0 >= block_range_limit {
+            return Err(Web3Error::BlockRangeLimitExceeded(block_range_limit));
+        }
+
+        let mut connection = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(
+                BlockId::Number(BlockNumber::Number(from_block.0.into())),
+                &mut connection,
+            )
+            .await?;
+
+        // The number of returned traces is additionally capped by the entity limit; clients can use `after`
+        // to paginate over the remaining traces.
+        let limit = self.state.api_config.req_entities_limit;
+        let count = filter.count.map_or(limit, |count| count.min(limit));
+        let mut traces_to_skip = filter.after.unwrap_or(0);
+        let mut traces = vec![];
+        // Call traces are loaded page by page, so that the loading stops as soon as enough traces are collected.
+        for page_start in (from_block.0..=to_block.0).step_by(TRACE_FILTER_PAGE_SIZE as usize) {
+            if traces.len() >= count {
+                break;
+            }
+            let page_end = to_block
+                .0
+                .min(page_start.saturating_add(TRACE_FILTER_PAGE_SIZE - 1));
+            let call_traces = connection
+                .blocks_web3_dal()
+                .get_traces_for_l2_block_range(L2BlockNumber(page_start)..=L2BlockNumber(page_end))
+                .await
+                .map_err(DalError::generalize)?;
+
+            let matching_traces = call_traces
+                .into_iter()
+                .flat_map(|(call, meta)| Self::flatten_call(call, meta))
+                .filter(|trace| filter.matches(trace));
+            for trace in matching_traces {
+                if traces_to_skip > 0 {
+                    traces_to_skip -= 1;
+                } else if traces.len() < count {
+                    traces.push(trace);
+                } else {
+                    break;
+                }
+            }
+        }
+        Ok(traces)
+    }
+
+    pub async fn replay_block_transactions_impl(
+        &self,
+        block_number: BlockNumber,
+        trace_types: Vec<TraceType>,
+    ) -> Result<Vec<TraceResults>, Web3Error> {
+        let include_traces = trace_types.contains(&TraceType::Trace);
+        let call_traces = self.block_call_traces(block_number).await?;
+        Ok(call_traces
+            .into_iter()
+            .map(|(call, meta)| {
+                let transaction_hash = meta.tx_hash;
+                let output = call.output.clone().into();
+                let trace = if include_traces {
+                    Self::flatten_call(call, meta)
+                } else {
+                    vec![]
+                };
+                TraceResults {
+                    output,
+                    trace,
+                    transaction_hash,
+                }
+            })
+            .collect())
+    }
+}
@@ -1,16 +1,24 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
+use tokio::sync::broadcast;
 use utils::{
     chain_id_leaf_preimage, get_chain_count, get_chain_id_from_index, get_chain_root_from_id,
 };
 use zksync_crypto_primitives::hasher::keccak::KeccakHasher;
 use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_mini_merkle_tree::MiniMerkleTree;
-use zksync_multivm::{interface::VmEvent, zk_evm_latest::ethereum_types::U64};
+use zksync_multivm::{
+    interface::{OneshotTracingParams, ProfiledResources, TransactionProfile, VmEvent},
+    zk_evm_latest::ethereum_types::U64,
+};
 use zksync_types::{
     api,
     api::{
-        ChainAggProof, DataAvailabilityDetails, GatewayMigrationStatus, L1ToL2TxsStatus, TeeProof,
+        profile, BlockId, BlockNumber, ChainAggProof, DataAvailabilityDetails,
+        GatewayMigrationStatus, L1ToL2TxsStatus, SignedTransactionPreconfirmation, TeeProof,
         TransactionDetailedResult, TransactionExecutionInfo,
     },
     server_notification::GatewayMigrationState,
@@ -22,7 +30,7 @@ use zksync_types::{
 use zksync_web3_decl::{error::Web3Error, types::H256};
 
 use crate::{
-    execution_sandbox::BlockArgs,
+    execution_sandbox::{BlockArgs, SandboxExecutionOutput},
     web3::{backend_jsonrpsee::MethodTracer, RpcState},
 };
 
@@ -273,12 +281,27 @@ impl UnstableNamespace {
             .parse_transaction_bytes(&tx_bytes.0, &block_args)?;
         tx.set_input(tx_bytes.0, tx_hash);
 
+        // Subscribe before submitting the transaction so that the preconfirmation cannot be missed.
+        let preconfirmations = self
+            .state
+            .tx_preconfirmations
+            .as_ref()
+            .filter(|preconfirmations| preconfirmations.signer().is_some())
+            .map(|preconfirmations| preconfirmations.subscribe());
+
         let submit_output = self
             .state
             .tx_sender
             .submit_tx(tx, block_args)
             .await
             .map_err(|err| self.current_method().map_submit_err(err))?;
+        let preconfirmation = if let Some(preconfirmations) = preconfirmations {
+            let timeout = self.state.api_config.preconfirmation_timeout;
+            wait_for_preconfirmation(preconfirmations, tx_hash, timeout).await
+        } else {
+            None
+        };
+
         Ok(TransactionDetailedResult {
             transaction_hash: tx_hash,
             storage_logs: submit_output
@@ -291,7 +314,129 @@ impl UnstableNamespace {
                 .into_iter()
                 .map(|event| map_event(event, tx_hash))
                 .collect(),
+            preconfirmation,
+        })
+    }
+
+    pub async fn profile_transaction_impl(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<profile::TransactionProfile>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let receipt = connection
+            .transactions_web3_dal()
+            .get_transaction_receipts(&[tx_hash])
+            .await
+            .map_err(DalError::generalize)?
+            .pop();
+        let Some(receipt) = receipt else {
+            return Ok(None);
+        };
+        drop(connection);
+
+        let block_number = L2BlockNumber(receipt.inner.block_number.as_u32());
+        let block_id = BlockId::Number(BlockNumber::Number(block_number.0.into()));
+        self.current_method().set_block_id(block_id);
+        let tracing_params = OneshotTracingParams {
+            profile: true,
+            ..OneshotTracingParams::default()
+        };
+        let (_, output) = self
+            .state
+            .replay_block(block_number, Some(tx_hash), tracing_params)
+            .await?
+            .pop()
+            .context("transaction was not profiled")?;
+        let receipt_gas_used = receipt.inner.gas_used.unwrap_or_default();
+        map_profile(tx_hash, receipt_gas_used.as_u64(), output).map(Some)
+    }
+}
+
+/// Waits for the state keeper to preconfirm the transaction with the specified hash. Returns `None`
+/// if the transaction wasn't preconfirmed in the specified `timeout`.
+async fn wait_for_preconfirmation(
+    mut preconfirmations: broadcast::Receiver<SignedTransactionPreconfirmation>,
+    tx_hash: H256,
+    timeout: Duration,
+) -> Option<SignedTransactionPreconfirmation> {
+    let wait = async {
+        loop {
+            match preconfirmations.recv().await {
+                Ok(preconfirmation)
+                    if preconfirmation.preconfirmation.transaction_hash == tx_hash =>
+                {
+                    return Some(preconfirmation);
+                }
+                Ok(_) => { /* preconfirmation for another transaction */ }
+                Err(broadcast::error::RecvError::Lagged(count)) => {
+                    tracing::debug!(
+                        "Lagged by {count} preconfirmations waiting for transaction {tx_hash:?}"
+                    );
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    };
+    tokio::time::timeout(timeout, wait).await.ok().flatten()
+}
+
+/// Maps the profile obtained by replaying a transaction. The transaction is replayed in a batch context recreated
+/// from the L2 block data rather than in the original one, so the measured gas and pubdata may differ from what
+/// the mined transaction paid; hence, the gas used from the transaction receipt is returned alongside.
+fn map_profile(
+    tx_hash: H256,
+    receipt_gas_used: u64,
+    output: SandboxExecutionOutput,
+) -> Result<profile::TransactionProfile, Web3Error> {
+    // The profile is missing if the fast VM doesn't support the protocol version of the transaction.
+    let TransactionProfile {
+        frames,
+        storage_writes,
+    } = output.profile.ok_or(Web3Error::MethodNotImplemented)?;
+    let vm_metrics = &output.metrics.vm;
+    let total = profile::ProfiledResources {
+        gas: vm_metrics.gas_used as u64,
+        pubdata: vm_metrics.pubdata_published.into(),
+        cycles: vm_metrics.cycles_used.into(),
+        circuits: vm_metrics.circuit_statistic.total_f32(),
+    };
+    let frames: Vec<_> = frames
+        .into_iter()
+        .map(|frame| profile::ProfiledFrame {
+            parent: frame.parent,
+            address: frame.address,
+            selector: frame.selector.map(|selector| selector.to_vec().into()),
+            total: map_profiled_resources(frame.total),
+            own: map_profiled_resources(frame.own),
         })
+        .collect();
+    let storage_writes: Vec<_> = storage_writes
+        .into_iter()
+        .map(|write| profile::ProfiledStorageWrite {
+            frame: write.frame,
+            address: write.address,
+            key: write.key,
+            resources: map_profiled_resources(write.resources),
+        })
+        .collect();
+
+    Ok(profile::TransactionProfile {
+        transaction_hash: tx_hash,
+        folded_stacks: profile::FoldedStacks::new(&total, &frames, &storage_writes),
+        total,
+        gas_refunded: output.metrics.gas_refunded,
+        receipt_gas_used,
+        frames,
+        storage_writes,
+    })
+}
+
+fn map_profiled_resources(resources: ProfiledResources) -> profile::ProfiledResources {
+    profile::ProfiledResources {
+        gas: resources.gas,
+        pubdata: resources.pubdata,
+        cycles: resources.cycles,
+        circuits: resources.circuits,
     }
 }
 
@@ -1,8 +1,10 @@
 use anyhow::Context as _;
 use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_shared_resources::tree::{TreeApiClient, TreeApiError};
 use zksync_types::{
-    api::{BlockId, BlockNumber},
+    api::{BlockId, BlockNumber, StorageProof},
     transaction_request::CallRequest,
+    L1BatchNumber, StorageKey,
 };
 use zksync_web3_decl::error::Web3Error;
 
@@ -35,6 +37,53 @@ pub async fn validate_gas_cap(
     Ok(())
 }
 
+/// Fetches Merkle tree proofs for the specified storage keys after the specified L1 batch.
+/// Returns `Ok(None)` if the L1 batch is not processed by the tree yet.
+pub async fn get_tree_proofs(
+    tree_api: Option<&dyn TreeApiClient>,
+    l1_batch_number: L1BatchNumber,
+    keys: &[StorageKey],
+) -> Result<Option<Vec<StorageProof>>, Web3Error> {
+    let tree_api = tree_api.ok_or(Web3Error::MethodNotImplemented)?;
+    let hashed_keys = keys.iter().map(StorageKey::hashed_key_u256).collect();
+    let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
+    let proofs = match proofs_result {
+        Ok(proofs) => proofs,
+        Err(TreeApiError::NotReady(_)) => return Err(Web3Error::TreeApiUnavailable),
+        Err(TreeApiError::NoVersion {
+            missing_version,
+            version_count,
+        }) => {
+            return if missing_version > version_count {
+                Ok(None)
+            } else {
+                Err(Web3Error::InternalError(anyhow::anyhow!(
+                    "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
+                )))
+            };
+        }
+        Err(TreeApiError::Internal(err)) => return Err(Web3Error::InternalError(err)),
+        Err(_) => {
+            // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
+            return Err(Web3Error::InternalError(anyhow::anyhow!(
+                "Unspecified tree API error"
+            )));
+        }
+    };
+
+    let storage_proofs = proofs
+        .into_iter()
+        .zip(keys)
+        .map(|(proof, key)| StorageProof {
+            key: *key.key(),
+            proof: proof.merkle_path,
+            value: proof.value,
+            index: proof.index,
+        })
+        .collect();
+    Ok(Some(storage_proofs))
+}
+
 /// Gets the protocol version for a given block, handling both pending and historical blocks.
 pub async fn get_protocol_version_for_block(
     block_id: BlockId,
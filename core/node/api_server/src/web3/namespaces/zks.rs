@@ -1,12 +1,11 @@
 use zksync_crypto_primitives::hasher::{keccak::KeccakHasher, Hasher};
 use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_mini_merkle_tree::MiniMerkleTree;
-use zksync_shared_resources::tree::TreeApiError;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
         state_override::StateOverride, BlockDetails, BridgeAddresses, InteropMode, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, StorageProof, TransactionDetails,
+        L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -27,7 +26,7 @@ use crate::{
     execution_sandbox::BlockArgs,
     tx_sender::BinarySearchKind,
     utils::open_readonly_transaction,
-    web3::{backend_jsonrpsee::MethodTracer, RpcState},
+    web3::{backend_jsonrpsee::MethodTracer, namespaces::get_tree_proofs, RpcState},
 };
 
 #[derive(Debug)]
@@ -492,50 +491,16 @@ impl ZksNamespace {
             .start_info
             .ensure_not_pruned(l1_batch_number, &mut storage)
             .await?;
-        let hashed_keys = keys
-            .iter()
-            .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
-            .collect();
-        let tree_api = self
-            .state
-            .tree_api
-            .as_deref()
-            .ok_or(Web3Error::MethodNotImplemented)?;
-        let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
-        let proofs = match proofs_result {
-            Ok(proofs) => proofs,
-            Err(TreeApiError::NotReady(_)) => return Err(Web3Error::TreeApiUnavailable),
-            Err(TreeApiError::NoVersion {
-                missing_version,
-                version_count,
-            }) => {
-                return if missing_version > version_count {
-                    Ok(None)
-                } else {
-                    Err(Web3Error::InternalError(anyhow::anyhow!(
-                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
-                    )))
-                };
-            }
-            Err(TreeApiError::Internal(err)) => return Err(Web3Error::InternalError(err)),
-            Err(_) => {
-                // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
-                return Err(Web3Error::InternalError(anyhow::anyhow!(
-                    "Unspecified tree API error"
-                )));
-            }
-        };
+        drop(storage);
 
-        let storage_proof = proofs
+        let keys: Vec<_> = keys
             .into_iter()
-            .zip(keys)
-            .map(|(proof, key)| StorageProof {
-                key,
-                proof: proof.merkle_path,
-                value: proof.value,
-                index: proof.index,
-            })
+            .map(|key| StorageKey::new(AccountTreeId::new(address), key))
             .collect();
+        let tree_api = self.state.tree_api.as_deref();
+        let Some(storage_proof) = get_tree_proofs(tree_api, l1_batch_number, &keys).await? else {
+            return Ok(None);
+        };
 
         Ok(Some(Proof {
             address,
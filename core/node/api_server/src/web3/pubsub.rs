@@ -1,6 +1,13 @@
 //! (Largely) backend-agnostic logic for dealing with Web3 subscriptions.
 
-use std::time::Duration;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use chrono::NaiveDateTime;
 use futures::FutureExt;
@@ -10,7 +17,8 @@ use tokio::{
 };
 use tracing::Instrument as _;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
-use zksync_types::{L2BlockNumber, H128, H256};
+use zksync_shared_resources::api::TxPreconfirmations;
+use zksync_types::{api, L1BatchNumber, L2BlockNumber, L2ChainId, H128, H256};
 use zksync_web3_decl::{
     jsonrpsee::{
         core::{server::SubscriptionMessage, SubscriptionResult},
@@ -29,6 +37,10 @@ use super::{
 
 const BROADCAST_CHANNEL_CAPACITY: usize = 1024;
 const SUBSCRIPTION_SINK_SEND_TIMEOUT: Duration = Duration::from_secs(1);
+/// `eth_txs_history.confirmed_at` is set when the confirming Postgres transaction starts, so a confirmation may become
+/// visible after confirmations with a later timestamp. To not miss such confirmations, L1 batch status updates
+/// are re-queried for this interval before the last seen confirmation.
+const L1_BATCH_STATUS_LOOKBACK: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone, Copy)]
 pub struct EthSubscriptionIdProvider;
@@ -48,13 +60,41 @@ pub enum PubSubEvent {
     L2BlockAdvanced(SubscriptionType, L2BlockNumber),
 }
 
+/// Counter of `newPendingTransactions` subscribers that need transaction bodies (as opposed to just hashes).
+#[derive(Debug, Clone, Default)]
+struct TxBodySubscribers(Arc<AtomicUsize>);
+
+impl TxBodySubscribers {
+    fn is_empty(&self) -> bool {
+        self.0.load(Ordering::Acquire) == 0
+    }
+
+    fn register(&self) -> TxBodySubscriberGuard {
+        self.0.fetch_add(1, Ordering::AcqRel);
+        TxBodySubscriberGuard(self.0.clone())
+    }
+}
+
+/// Unregisters a subscriber from [`TxBodySubscribers`] on drop.
+#[derive(Debug)]
+struct TxBodySubscriberGuard(Arc<AtomicUsize>);
+
+impl Drop for TxBodySubscriberGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
 /// Manager of notifications for a certain type of subscriptions.
 #[derive(Debug)]
 pub(crate) struct PubSubNotifier {
     ty: SubscriptionType,
     sender: broadcast::Sender<Vec<PubSubResult>>,
+    tx_body_subscribers: TxBodySubscribers,
     connection_pool: ConnectionPool<Core>,
     polling_interval: Duration,
+    l2_chain_id: L2ChainId,
+    tx_preconfirmations: Option<TxPreconfirmations>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -171,11 +211,17 @@ impl PubSubNotifier {
 
             if let Some((new_last_time, _)) = new_txs.last() {
                 last_time = *new_last_time;
-                let new_txs = new_txs
-                    .into_iter()
-                    .map(|(_, tx_hash)| PubSubResult::TxHash(tx_hash))
-                    .collect();
-                self.send_pub_sub_results(new_txs, SubscriptionType::Txs);
+                if self.sender.receiver_count() > 0 {
+                    let tx_hashes: Vec<_> = new_txs.into_iter().map(|(_, hash)| hash).collect();
+                    // Transaction bodies are only required to stream full transactions or to filter transactions
+                    // by addresses, so we only load them if any subscriber has requested this.
+                    let new_txs = if self.tx_body_subscribers.is_empty() {
+                        tx_hashes.into_iter().map(PubSubResult::TxHash).collect()
+                    } else {
+                        self.load_txs(&tx_hashes).await?
+                    };
+                    self.send_pub_sub_results(new_txs, SubscriptionType::Txs);
+                }
             }
             self.emit_event(PubSubEvent::NotifyIterationFinished(SubscriptionType::Txs));
         }
@@ -197,6 +243,24 @@ impl PubSubNotifier {
             .map_err(Into::into)
     }
 
+    /// Loads transactions with the specified hashes preserving their order. Transactions that were removed
+    /// from the storage in the meantime are skipped.
+    async fn load_txs(&self, tx_hashes: &[H256]) -> anyhow::Result<Vec<PubSubResult>> {
+        let txs = self
+            .connection_pool
+            .connection_tagged("api")
+            .await?
+            .transactions_web3_dal()
+            .get_transactions(tx_hashes, self.l2_chain_id)
+            .await?;
+        let mut txs_by_hash: HashMap<_, _> = txs.into_iter().map(|tx| (tx.hash, tx)).collect();
+        Ok(tx_hashes
+            .iter()
+            .filter_map(|hash| txs_by_hash.remove(hash))
+            .map(PubSubResult::FullTransaction)
+            .collect())
+    }
+
     async fn notify_logs(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let Some(mut last_block_number) = self
             .get_starting_l2_block_number(&mut stop_receiver)
@@ -244,11 +308,112 @@ impl PubSubNotifier {
             .map_err(Into::into)
     }
 
+    async fn notify_l1_batch_status(
+        self,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let lookback = chrono::Duration::from_std(L1_BATCH_STATUS_LOOKBACK).unwrap();
+        let mut last_time = chrono::Utc::now().naive_utc();
+        // Updates sent within the lookback interval, used to deduplicate re-queried updates. The confirmation timestamp
+        // is a part of the key since finality upgrades re-confirm the same transaction.
+        let mut sent_updates = HashSet::<(H256, L1BatchNumber, NaiveDateTime)>::new();
+        let mut timer = tokio::time::interval(self.polling_interval);
+        while !*stop_receiver.borrow() {
+            tokio::select! {
+                _ = stop_receiver.changed() => break,
+                _ = timer.tick() => { /* continue processing */ }
+            }
+
+            let db_latency = PUB_SUB_METRICS[&SubscriptionType::L1BatchStatus]
+                .db_poll_latency
+                .start();
+            let new_updates = self
+                .new_l1_batch_status_updates(last_time - lookback)
+                .await?;
+            db_latency.observe();
+
+            let new_updates: Vec<_> = new_updates
+                .into_iter()
+                .filter(|update| {
+                    let confirmed_at = update.confirmed_at.naive_utc();
+                    sent_updates.insert((update.tx_hash, update.l1_batch_number, confirmed_at))
+                })
+                .collect();
+            if let Some(last_update) = new_updates.last() {
+                last_time = last_time.max(last_update.confirmed_at.naive_utc());
+                sent_updates.retain(|(_, _, confirmed_at)| *confirmed_at > last_time - lookback);
+                let new_updates = new_updates
+                    .into_iter()
+                    .map(PubSubResult::L1BatchStatus)
+                    .collect();
+                self.send_pub_sub_results(new_updates, SubscriptionType::L1BatchStatus);
+            }
+            self.emit_event(PubSubEvent::NotifyIterationFinished(
+                SubscriptionType::L1BatchStatus,
+            ));
+        }
+
+        tracing::info!("Stop request received, pubsub_l1_batch_status_notifier is shutting down");
+        Ok(())
+    }
+
+    async fn new_l1_batch_status_updates(
+        &self,
+        last_time: NaiveDateTime,
+    ) -> anyhow::Result<Vec<api::L1BatchStatusUpdate>> {
+        self.connection_pool
+            .connection_tagged("api")
+            .await?
+            .blocks_web3_dal()
+            .get_l1_batch_status_updates_after(last_time)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Unlike other notifiers, doesn't poll Postgres; preconfirmations are received directly from the state keeper.
+    async fn notify_preconfirmations(
+        self,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let Some(tx_preconfirmations) = &self.tx_preconfirmations else {
+            // No preconfirmations source; wait for the stop request so that the task doesn't exit prematurely.
+            stop_receiver.changed().await.ok();
+            return Ok(());
+        };
+        let mut receiver = tx_preconfirmations.subscribe();
+
+        while !*stop_receiver.borrow() {
+            let preconfirmation = tokio::select! {
+                _ = stop_receiver.changed() => break,
+                res = receiver.recv() => res,
+            };
+            match preconfirmation {
+                Ok(preconfirmation) => {
+                    self.send_pub_sub_results(
+                        vec![PubSubResult::Preconfirmation(preconfirmation)],
+                        SubscriptionType::Preconfirmations,
+                    );
+                }
+                Err(broadcast::error::RecvError::Lagged(message_count)) => {
+                    PUB_SUB_METRICS[&SubscriptionType::Preconfirmations]
+                        .skipped_broadcast_messages
+                        .observe(message_count);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+
+        tracing::info!("Stop request received, pubsub_preconfirmations_notifier is shutting down");
+        Ok(())
+    }
+
     pub(crate) async fn run(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         match self.ty {
             SubscriptionType::Blocks => self.notify_blocks(stop_receiver).await,
             SubscriptionType::Txs => self.notify_txs(stop_receiver).await,
             SubscriptionType::Logs => self.notify_logs(stop_receiver).await,
+            SubscriptionType::Preconfirmations => self.notify_preconfirmations(stop_receiver).await,
+            SubscriptionType::L1BatchStatus => self.notify_l1_batch_status(stop_receiver).await,
         }
     }
 }
@@ -257,27 +422,45 @@ impl PubSubNotifier {
 #[derive(Debug)]
 pub(crate) struct EthSubscribe {
     polling_interval: Duration,
+    l2_chain_id: L2ChainId,
     blocks: broadcast::Sender<Vec<PubSubResult>>,
     transactions: broadcast::Sender<Vec<PubSubResult>>,
+    tx_body_subscribers: TxBodySubscribers,
     logs: broadcast::Sender<Vec<PubSubResult>>,
+    preconfirmations: broadcast::Sender<Vec<PubSubResult>>,
+    l1_batch_statuses: broadcast::Sender<Vec<PubSubResult>>,
+    tx_preconfirmations: Option<TxPreconfirmations>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
 impl EthSubscribe {
-    pub fn new(polling_interval: Duration) -> Self {
+    pub fn new(polling_interval: Duration, l2_chain_id: L2ChainId) -> Self {
         let (blocks, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (transactions, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (logs, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (preconfirmations, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (l1_batch_statuses, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
 
         Self {
             polling_interval,
+            l2_chain_id,
             blocks,
             transactions,
+            tx_body_subscribers: TxBodySubscribers::default(),
             logs,
+            preconfirmations,
+            l1_batch_statuses,
+            tx_preconfirmations: None,
             events_sender: None,
         }
     }
 
+    /// Enables the `preconfirmations` subscription sourcing preconfirmations from the provided channel.
+    pub fn with_tx_preconfirmations(mut self, tx_preconfirmations: TxPreconfirmations) -> Self {
+        self.tx_preconfirmations = Some(tx_preconfirmations);
+        self
+    }
+
     pub fn set_events_sender(&mut self, sender: mpsc::UnboundedSender<PubSubEvent>) {
         self.events_sender = Some(sender);
     }
@@ -350,13 +533,27 @@ impl EthSubscribe {
         let metrics = &PUB_SUB_METRICS[&subscription_type];
         let notify_latency = metrics.notify_subscribers_latency.start();
         for item in new_items {
-            if let PubSubResult::Log(log) = &item {
-                if let Some(filter) = &filter {
-                    if !filter.matches(log) {
+            let item = match item {
+                PubSubResult::Log(log) => {
+                    if filter.is_some_and(|filter| !filter.matches(&log)) {
                         continue;
                     }
+                    PubSubResult::Log(log)
                 }
-            }
+                PubSubResult::FullTransaction(tx) => match filter {
+                    Some(filter) if !filter.matches_transaction(&tx) => continue,
+                    Some(filter) if filter.full_transactions() => PubSubResult::FullTransaction(tx),
+                    _ => PubSubResult::TxHash(tx.hash),
+                },
+                // Can happen for a subscriber requiring tx bodies right after it was created, since the notifier
+                // may not have observed it yet. Such a subscriber cannot be served without tx bodies.
+                PubSubResult::TxHash(_)
+                    if filter.is_some_and(PubSubFilter::requires_transaction_bodies) =>
+                {
+                    continue;
+                }
+                item => item,
+            };
 
             sink.send_timeout(
                 SubscriptionMessage::from_json(&item)
@@ -396,10 +593,18 @@ impl EthSubscribe {
                 let Ok(sink) = pending_sink.accept().await else {
                     return;
                 };
+                let body_subscriber_guard = params
+                    .as_ref()
+                    .is_some_and(PubSubFilter::requires_transaction_bodies)
+                    .then(|| self.tx_body_subscribers.register());
                 let transactions_rx = self.transactions.subscribe();
                 tokio::spawn(
-                    Self::run_subscriber(sink, SubscriptionType::Txs, transactions_rx, None)
-                        .in_current_span(),
+                    async move {
+                        let _guard = body_subscriber_guard;
+                        Self::run_subscriber(sink, SubscriptionType::Txs, transactions_rx, params)
+                            .await;
+                    }
+                    .in_current_span(),
                 );
                 Some(SubscriptionType::Txs)
             }
@@ -422,6 +627,47 @@ impl EthSubscribe {
                     Some(SubscriptionType::Logs)
                 }
             }
+            "preconfirmations" => {
+                let issues_preconfirmations = self
+                    .tx_preconfirmations
+                    .as_ref()
+                    .is_some_and(|preconfirmations| preconfirmations.signer().is_some());
+                if issues_preconfirmations {
+                    let Ok(sink) = pending_sink.accept().await else {
+                        return;
+                    };
+                    let preconfirmations_rx = self.preconfirmations.subscribe();
+                    tokio::spawn(
+                        Self::run_subscriber(
+                            sink,
+                            SubscriptionType::Preconfirmations,
+                            preconfirmations_rx,
+                            None,
+                        )
+                        .in_current_span(),
+                    );
+                    Some(SubscriptionType::Preconfirmations)
+                } else {
+                    Self::reject(pending_sink).await;
+                    None
+                }
+            }
+            "l1BatchStatus" => {
+                let Ok(sink) = pending_sink.accept().await else {
+                    return;
+                };
+                let l1_batch_statuses_rx = self.l1_batch_statuses.subscribe();
+                tokio::spawn(
+                    Self::run_subscriber(
+                        sink,
+                        SubscriptionType::L1BatchStatus,
+                        l1_batch_statuses_rx,
+                        None,
+                    )
+                    .in_current_span(),
+                );
+                Some(SubscriptionType::L1BatchStatus)
+            }
             "syncing" => {
                 let Ok(sink) = pending_sink.accept().await else {
                     return;
@@ -458,18 +704,23 @@ impl EthSubscribe {
             SubscriptionType::Blocks => self.blocks.clone(),
             SubscriptionType::Txs => self.transactions.clone(),
             SubscriptionType::Logs => self.logs.clone(),
+            SubscriptionType::Preconfirmations => self.preconfirmations.clone(),
+            SubscriptionType::L1BatchStatus => self.l1_batch_statuses.clone(),
         };
 
         PubSubNotifier {
             ty,
             sender,
+            tx_body_subscribers: self.tx_body_subscribers.clone(),
             connection_pool,
             polling_interval: self.polling_interval,
+            l2_chain_id: self.l2_chain_id,
+            tx_preconfirmations: self.tx_preconfirmations.clone(),
             events_sender: self.events_sender.clone(),
         }
     }
 
-    /// Test-only helper spawning all 3 notifier tasks.
+    /// Test-only helper spawning all notifier tasks.
     pub(crate) fn spawn_notifiers(
         &self,
         connection_pool: ConnectionPool<Core>,
@@ -479,6 +730,8 @@ impl EthSubscribe {
             SubscriptionType::Blocks,
             SubscriptionType::Txs,
             SubscriptionType::Logs,
+            SubscriptionType::Preconfirmations,
+            SubscriptionType::L1BatchStatus,
         ]
         .into_iter()
         .map(|ty| {
@@ -1,10 +1,11 @@
 use std::{
+    collections::HashMap,
     future::Future,
     sync::{
         atomic::{AtomicU32, Ordering},
         Arc,
     },
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use anyhow::Context as _;
@@ -14,7 +15,7 @@ use tokio::sync::Mutex;
 use vise::GaugeGuard;
 use zksync_config::{
     configs::{
-        api::Web3JsonRpcConfig,
+        api::{MaxResponseSize, Web3JsonRpcConfig},
         contracts::{
             chain::L2Contracts,
             ecosystem::{EcosystemCommonContracts, L1SpecificContracts},
@@ -24,14 +25,19 @@ use zksync_config::{
     GenesisConfig,
 };
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
+use zksync_multivm::interface::OneshotTracingParams;
 use zksync_shared_resources::{
-    api::{BridgeAddressesHandle, SyncState},
+    api::{BridgeAddressesHandle, SyncState, TxPreconfirmations},
     tree::TreeApiClient,
 };
 use zksync_types::{
-    api, commitment::L1BatchCommitmentMode, l2::L2Tx, settlement::SettlementLayer,
-    transaction_request::CallRequest, Address, L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId,
-    H256, U256, U64,
+    api::{self, state_override::StateOverride},
+    bytecode::{validate_bytecode, BytecodeHash},
+    commitment::L1BatchCommitmentMode,
+    l2::L2Tx,
+    settlement::SettlementLayer,
+    transaction_request::CallRequest,
+    Address, L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId, H256, U256, U64,
 };
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -47,7 +53,10 @@ use super::{
     TypedFilter,
 };
 use crate::{
-    execution_sandbox::{BlockArgs, BlockArgsError, BlockStartInfo},
+    execution_sandbox::{
+        extend_state_override, extend_state_override_with_bytecodes, BlockArgs, BlockArgsError,
+        BlockStartInfo, SandboxAction, SandboxExecutionOutput,
+    },
     tx_sender::{tx_sink::TxSink, TxSender},
     web3::metrics::FilterMetrics,
 };
@@ -119,9 +128,11 @@ pub struct InternalApiConfigBase {
     pub estimate_gas_optimize_search: bool,
     pub req_entities_limit: usize,
     pub fee_history_limit: u64,
+    pub trace_filter_block_range_limit: u32,
     pub filters_disabled: bool,
     pub l1_to_l2_txs_paused: bool,
     pub eth_call_gas_cap: Option<u64>,
+    pub preconfirmation_timeout: Duration,
 }
 
 impl InternalApiConfigBase {
@@ -136,9 +147,11 @@ impl InternalApiConfigBase {
             estimate_gas_optimize_search: web3_config.estimate_gas_optimize_search,
             req_entities_limit: web3_config.req_entities_limit as usize,
             fee_history_limit: web3_config.fee_history_limit,
+            trace_filter_block_range_limit: web3_config.trace_filter_block_range_limit,
             filters_disabled: web3_config.filters_disabled,
             l1_to_l2_txs_paused: false,
             eth_call_gas_cap: web3_config.eth_call_gas_cap,
+            preconfirmation_timeout: web3_config.preconfirmation_timeout,
         }
     }
 
@@ -171,6 +184,7 @@ pub struct InternalApiConfig {
     pub l2_testnet_paymaster_addr: Option<Address>,
     pub req_entities_limit: usize,
     pub fee_history_limit: u64,
+    pub trace_filter_block_range_limit: u32,
     pub base_token_address: Option<Address>,
     pub filters_disabled: bool,
     pub dummy_verifier: bool,
@@ -180,6 +194,8 @@ pub struct InternalApiConfig {
     pub l1_to_l2_txs_paused: bool,
     pub settlement_layer: Option<SettlementLayer>,
     pub eth_call_gas_cap: Option<u64>,
+    /// Maximum time to wait for a transaction preconfirmation in `unstable_sendRawTransactionWithDetailedOutput`.
+    pub preconfirmation_timeout: Duration,
 }
 
 impl InternalApiConfig {
@@ -219,6 +235,7 @@ impl InternalApiConfig {
             l2_testnet_paymaster_addr: l2_contracts.testnet_paymaster_addr,
             req_entities_limit: base.req_entities_limit,
             fee_history_limit: base.fee_history_limit,
+            trace_filter_block_range_limit: base.trace_filter_block_range_limit,
             base_token_address: Some(l1_ecosystem_contracts.base_token_address),
             filters_disabled: base.filters_disabled,
             dummy_verifier,
@@ -228,6 +245,7 @@ impl InternalApiConfig {
             l1_to_l2_txs_paused: base.l1_to_l2_txs_paused,
             settlement_layer,
             eth_call_gas_cap: base.eth_call_gas_cap,
+            preconfirmation_timeout: base.preconfirmation_timeout,
         }
     }
 
@@ -301,6 +319,7 @@ pub(crate) struct RpcState {
     pub(super) tree_api: Option<Arc<dyn TreeApiClient>>,
     pub(super) tx_sender: TxSender,
     pub(super) sync_state: Option<SyncState>,
+    pub(super) tx_preconfirmations: Option<TxPreconfirmations>,
     pub(super) api_config: InternalApiConfig,
     /// Number of the first locally available L2 block / L1 batch. May differ from 0 if the node state was recovered
     /// from a snapshot.
@@ -310,6 +329,8 @@ pub(crate) struct RpcState {
     pub(super) last_sealed_l2_block: SealedL2BlockNumber,
     pub(super) bridge_addresses_handle: BridgeAddressesHandle,
     pub(super) l2_l1_log_proof_handler: Option<Box<DynClient<L2>>>,
+    /// Response size limits; used to bound the size of traces collected by the server.
+    pub(super) response_size_limit: Option<MaxResponseSize>,
 }
 
 impl RpcState {
@@ -492,6 +513,113 @@ impl RpcState {
         call_request.nonce = Some(address_historical_nonce);
         Ok(())
     }
+
+    /// Re-executes transactions in the specified L2 block. Transactions are executed on top of the state
+    /// after the previous L2 block; storage writes of each transaction are carried over to the following ones.
+    /// If `target_tx_hash` is specified, only transactions up to and including this transaction are executed,
+    /// and only this transaction is traced using `tracing_params`. Returns execution outputs for traced transactions.
+    ///
+    /// Transactions are executed in the block context of the previous L2 block, so the block number and timestamp
+    /// observed by contracts may differ from the original execution.
+    pub(crate) async fn replay_block(
+        &self,
+        block_number: L2BlockNumber,
+        target_tx_hash: Option<H256>,
+        tracing_params: OneshotTracingParams,
+    ) -> Result<Vec<(H256, SandboxExecutionOutput)>, Web3Error> {
+        let Some(prev_block_number) = block_number.0.checked_sub(1) else {
+            // The genesis block doesn't contain transactions that could be replayed.
+            return Ok(vec![]);
+        };
+        let prev_block_id =
+            api::BlockId::Number(api::BlockNumber::Number(prev_block_number.into()));
+
+        let mut connection = self.acquire_connection().await?;
+        let block_args = self
+            .resolve_block_args(&mut connection, prev_block_id)
+            .await?;
+        let fee_input = connection
+            .blocks_dal()
+            .get_l2_block_header(block_number)
+            .await
+            .map_err(DalError::generalize)?
+            .with_context(|| format!("L2 block #{block_number} disappeared from storage"))?
+            .batch_fee_input;
+        let mut transactions = connection
+            .transactions_web3_dal()
+            .get_raw_l2_block_transactions(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        drop(connection);
+
+        if let Some(target_tx_hash) = target_tx_hash {
+            let position = transactions
+                .iter()
+                .position(|tx| tx.hash() == target_tx_hash)
+                .with_context(|| {
+                    format!("transaction {target_tx_hash:?} is not in L2 block #{block_number}")
+                })?;
+            transactions.truncate(position + 1);
+        }
+
+        let vm_permit = self.tx_sender.vm_concurrency_limiter().acquire().await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+        let executor = &self.tx_sender.0.executor;
+
+        let mut state_override = StateOverride::default();
+        // Bytecodes deployed by the replayed transactions are not visible in the storage snapshot
+        // for the previous block, so they are carried between transactions as code overrides.
+        let mut deployed_bytecodes = HashMap::new();
+        let mut outputs = vec![];
+        for tx in transactions {
+            let tx_hash = tx.hash();
+            deployed_bytecodes.extend(
+                tx.execute
+                    .factory_deps
+                    .iter()
+                    .filter(|dep| validate_bytecode(dep).is_ok())
+                    .map(|dep| (BytecodeHash::for_bytecode(dep).value(), dep.clone())),
+            );
+            let is_traced = target_tx_hash.is_none_or(|hash| hash == tx_hash);
+            let tracing_params = if is_traced {
+                tracing_params.clone()
+            } else {
+                OneshotTracingParams::default()
+            };
+
+            let connection = self.acquire_connection().await?;
+            let result = executor
+                .execute_in_sandbox(
+                    vm_permit.clone(),
+                    connection,
+                    SandboxAction::Replay {
+                        tx,
+                        fee_input,
+                        tracing_params,
+                    },
+                    &block_args,
+                    Some(state_override.clone()),
+                )
+                .await?;
+            extend_state_override(&mut state_override, &result.write_logs);
+            deployed_bytecodes.extend(
+                result
+                    .dynamic_factory_deps
+                    .iter()
+                    .map(|(hash, bytecode)| (*hash, bytecode.clone())),
+            );
+            extend_state_override_with_bytecodes(
+                &mut state_override,
+                &result.write_logs,
+                &deployed_bytecodes,
+            );
+
+            if is_traced {
+                outputs.push((tx_hash, result));
+            }
+        }
+        Ok(outputs)
+    }
 }
 
 /// Contains mapping from index to `Filter`s with optional location.
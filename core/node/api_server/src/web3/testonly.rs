@@ -113,6 +113,8 @@ pub struct TestServerBuilder {
     tx_executor: MockOneshotExecutor,
     executor_options: Option<SandboxExecutorOptions>,
     method_tracer: Arc<MethodTracer>,
+    tx_preconfirmations: Option<TxPreconfirmations>,
+    tree_api: Option<Arc<dyn TreeApiClient>>,
 }
 
 impl TestServerBuilder {
@@ -125,6 +127,8 @@ impl TestServerBuilder {
             tx_executor: MockOneshotExecutor::default(),
             executor_options: None,
             method_tracer: Arc::default(),
+            tx_preconfirmations: None,
+            tree_api: None,
         }
     }
 
@@ -154,6 +158,20 @@ impl TestServerBuilder {
         self
     }
 
+    /// Sets a source of transaction preconfirmations, emulating the state keeper issuing them.
+    #[must_use]
+    pub fn with_tx_preconfirmations(mut self, tx_preconfirmations: TxPreconfirmations) -> Self {
+        self.tx_preconfirmations = Some(tx_preconfirmations);
+        self
+    }
+
+    /// Sets a Merkle tree API client used by proof-related methods.
+    #[must_use]
+    pub fn with_tree_api(mut self, tree_api: Arc<dyn TreeApiClient>) -> Self {
+        self.tree_api = Some(tree_api);
+        self
+    }
+
     /// Builds an HTTP server.
     pub async fn build_http(self, stop_receiver: watch::Receiver<bool>) -> ApiServerHandles {
         self.spawn_server(ApiTransportLabel::Http, None, stop_receiver)
@@ -188,6 +206,8 @@ impl TestServerBuilder {
             pool,
             api_config,
             method_tracer,
+            tx_preconfirmations,
+            tree_api,
         } = self;
 
         let tx_executor = if let Some(options) = executor_options {
@@ -200,7 +220,12 @@ impl TestServerBuilder {
         let (pub_sub_events_sender, pub_sub_events_receiver) = mpsc::unbounded_channel();
 
         let mut namespaces = HashSet::from(Namespace::DEFAULT);
-        namespaces.extend([Namespace::Debug, Namespace::Snapshots, Namespace::Unstable]);
+        namespaces.extend([
+            Namespace::Debug,
+            Namespace::Snapshots,
+            Namespace::Trace,
+            Namespace::Unstable,
+        ]);
         let sealed_l2_block_handle = SealedL2BlockNumber::default();
         let bridge_addresses_handle =
             BridgeAddressesHandle::new(api_config.bridge_addresses.clone());
@@ -209,7 +234,10 @@ impl TestServerBuilder {
         let (pub_sub, server_builder) = match transport {
             ApiTransportLabel::Http => (None, ApiBuilder::new(api_config, pool).http(0)),
             ApiTransportLabel::Ws => {
-                let mut pub_sub = EthSubscribe::new(POLL_INTERVAL);
+                let mut pub_sub = EthSubscribe::new(POLL_INTERVAL, api_config.l2_chain_id);
+                if let Some(tx_preconfirmations) = &tx_preconfirmations {
+                    pub_sub = pub_sub.with_tx_preconfirmations(tx_preconfirmations.clone());
+                }
                 pub_sub.set_events_sender(pub_sub_events_sender);
                 server_tasks.extend(pub_sub.spawn_notifiers(pool.clone(), &stop_receiver));
 
@@ -237,6 +265,12 @@ impl TestServerBuilder {
         if let Some(timeout) = request_timeout {
             server_builder = server_builder.with_request_timeout(timeout);
         }
+        if let Some(tx_preconfirmations) = tx_preconfirmations {
+            server_builder = server_builder.with_tx_preconfirmations(tx_preconfirmations);
+        }
+        if let Some(tree_api) = tree_api {
+            server_builder = server_builder.with_tree_api(tree_api);
+        }
 
         let server = server_builder.build().expect("Unable to build API server");
         let health_check = server.health_check();
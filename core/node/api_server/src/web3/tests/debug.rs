@@ -1,8 +1,10 @@
 //! Tests for the `debug` Web3 namespace.
 
-use zksync_multivm::interface::{Call, TransactionExecutionResult};
+use std::sync::Mutex;
+
+use zksync_multivm::interface::{Call, ExecutionResult, TransactionExecutionResult};
 use zksync_types::{
-    api::{CallTracerConfig, SupportedTracers, TracerConfig},
+    api::{CallTracerConfig, PrestateTrace, StructLogTrace, SupportedTracers, TracerConfig},
     ExecuteTransactionCommon, BOOTLOADER_ADDRESS,
 };
 use zksync_web3_decl::{
@@ -12,7 +14,7 @@ use zksync_web3_decl::{
 
 use super::*;
 
-fn execute_l2_transaction_with_traces(index_in_block: u8) -> TransactionExecutionResult {
+pub(super) fn execute_l2_transaction_with_traces(index_in_block: u8) -> TransactionExecutionResult {
     let first_call_trace = Call {
         from: Address::repeat_byte(index_in_block),
         to: Address::repeat_byte(index_in_block + 1),
@@ -133,6 +135,7 @@ impl HttpTest for TraceBlockFlatTest {
                             tracer: SupportedTracers::FlatCallTracer,
                             tracer_config: CallTracerConfig {
                                 only_top_call: false,
+                                ..CallTracerConfig::default()
                             },
                         }),
                     )
@@ -173,6 +176,7 @@ impl HttpTest for TraceBlockFlatTest {
                     tracer: SupportedTracers::FlatCallTracer,
                     tracer_config: CallTracerConfig {
                         only_top_call: false,
+                        ..CallTracerConfig::default()
                     },
                 }),
             )
@@ -238,6 +242,170 @@ async fn tracing_transaction() {
     test_http_server(TraceTransactionTest).await;
 }
 
+#[derive(Debug, Default)]
+struct TracePrestateTest {
+    executed_tx_hashes: Arc<Mutex<Vec<H256>>>,
+}
+
+#[async_trait]
+impl HttpTest for TracePrestateTest {
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let executed_tx_hashes = self.executed_tx_hashes.clone();
+        let mut tx_executor = MockOneshotExecutor::default();
+        tx_executor.set_tx_responses(move |tx, env| {
+            // Transactions must be replayed on top of the previous L2 block.
+            assert_eq!(env.l1_batch.first_l2_block.number, 1);
+            executed_tx_hashes.lock().unwrap().push(tx.hash());
+            ExecutionResult::Success { output: vec![] }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [0, 1, 2].map(execute_l2_transaction_with_traces);
+        let tx_hashes: Vec<_> = tx_results.iter().map(|tx| tx.hash).collect();
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let diff_config = TracerConfig {
+            tracer: SupportedTracers::PrestateTracer,
+            tracer_config: CallTracerConfig {
+                diff_mode: true,
+                ..CallTracerConfig::default()
+            },
+        };
+        let result = client
+            .trace_transaction(tx_hashes[1], Some(diff_config))
+            .await?
+            .context("no transaction traces")?
+            .unwrap_prestate();
+        assert_eq!(
+            result,
+            PrestateTrace::Diff {
+                pre: Default::default(),
+                post: Default::default(),
+            }
+        );
+        // Only transactions up to and including the traced one should be executed.
+        let executed_tx_hashes = std::mem::take(&mut *self.executed_tx_hashes.lock().unwrap());
+        assert_eq!(executed_tx_hashes, tx_hashes[..2]);
+
+        let prestate_config = TracerConfig {
+            tracer: SupportedTracers::PrestateTracer,
+            tracer_config: CallTracerConfig::default(),
+        };
+        let block_traces = client
+            .trace_block_by_number(1_u32.into(), Some(prestate_config))
+            .await?
+            .unwrap_prestate();
+        let traced_hashes: Vec<_> = block_traces.iter().map(|trace| trace.tx_hash).collect();
+        assert_eq!(traced_hashes, tx_hashes);
+        for trace in block_traces {
+            assert_eq!(trace.result, PrestateTrace::Prestate(Default::default()));
+        }
+        let executed_tx_hashes = std::mem::take(&mut *self.executed_tx_hashes.lock().unwrap());
+        assert_eq!(executed_tx_hashes, tx_hashes);
+
+        let missing_tx_result = client
+            .trace_transaction(H256::repeat_byte(0xff), Some(prestate_config))
+            .await?;
+        assert!(missing_tx_result.is_none());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_prestate() {
+    test_http_server(TracePrestateTest::default()).await;
+}
+
+#[derive(Debug, Default)]
+struct TraceStructLogsTest {
+    executed_tx_hashes: Arc<Mutex<Vec<H256>>>,
+}
+
+#[async_trait]
+impl HttpTest for TraceStructLogsTest {
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let executed_tx_hashes = self.executed_tx_hashes.clone();
+        let mut tx_executor = MockOneshotExecutor::default();
+        tx_executor.set_tx_responses(move |tx, env| {
+            // Transactions must be replayed on top of the previous L2 block.
+            assert_eq!(env.l1_batch.first_l2_block.number, 1);
+            executed_tx_hashes.lock().unwrap().push(tx.hash());
+            ExecutionResult::Success {
+                output: vec![1, 2, 3],
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [0, 1, 2].map(execute_l2_transaction_with_traces);
+        let tx_hashes: Vec<_> = tx_results.iter().map(|tx| tx.hash).collect();
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let struct_log_config = TracerConfig {
+            tracer: SupportedTracers::StructLogger,
+            tracer_config: CallTracerConfig {
+                enable_memory: true,
+                limit: Some(10),
+                ..CallTracerConfig::default()
+            },
+        };
+        let trace = client
+            .trace_transaction(tx_hashes[1], Some(struct_log_config))
+            .await?
+            .context("no transaction traces")?
+            .unwrap_struct_logs();
+        // The mock executor doesn't record logs, so only the execution outcome is checked.
+        let expected_trace = StructLogTrace {
+            gas: 0,
+            failed: false,
+            return_value: vec![1, 2, 3].into(),
+            struct_logs: vec![],
+        };
+        assert_eq!(trace, expected_trace);
+        // Only transactions up to and including the traced one should be executed.
+        let executed_tx_hashes = std::mem::take(&mut *self.executed_tx_hashes.lock().unwrap());
+        assert_eq!(executed_tx_hashes, tx_hashes[..2]);
+
+        let block_traces = client
+            .trace_block_by_number(1_u32.into(), Some(struct_log_config))
+            .await?
+            .unwrap_struct_logs();
+        let traced_hashes: Vec<_> = block_traces.iter().map(|trace| trace.tx_hash).collect();
+        assert_eq!(traced_hashes, tx_hashes);
+        for trace in block_traces {
+            assert_eq!(trace.result, expected_trace);
+        }
+        let executed_tx_hashes = std::mem::take(&mut *self.executed_tx_hashes.lock().unwrap());
+        assert_eq!(executed_tx_hashes, tx_hashes);
+
+        let missing_tx_result = client
+            .trace_transaction(H256::repeat_byte(0xff), Some(struct_log_config))
+            .await?;
+        assert!(missing_tx_result.is_none());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_struct_logs() {
+    test_http_server(TraceStructLogsTest::default()).await;
+}
+
 #[derive(Debug)]
 struct TraceBlockTestWithSnapshotRecovery;
 
@@ -20,6 +20,7 @@ use zksync_node_test_utils::{
     create_l1_batch, create_l1_batch_metadata, create_l2_block, create_l2_transaction,
     l1_batch_metadata_to_commitment_artifacts, prepare_recovery_snapshot,
 };
+use zksync_shared_resources::tree::{MerkleTreeInfo, TreeApiError, TreeEntryWithProof};
 use zksync_system_constants::{
     SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
 };
@@ -40,7 +41,7 @@ use zksync_types::{
     system_contracts::get_system_smart_contracts,
     tx::IncludedTxLocation,
     u256_to_h256,
-    utils::storage_key_for_eth_balance,
+    utils::{nonces_to_full_nonce, storage_key_for_eth_balance},
     AccountTreeId, Address, L1BatchNumber, Nonce, StorageKey, StorageLog, H256, U256, U64,
 };
 use zksync_vm_executor::oneshot::MockOneshotExecutor;
@@ -68,6 +69,7 @@ use crate::{
 mod debug;
 mod filters;
 mod snapshots;
+mod trace;
 mod unstable;
 mod vm;
 mod ws;
@@ -160,6 +162,16 @@ trait HttpTest: Send + Sync {
         Web3JsonRpcConfig::for_tests()
     }
 
+    /// Provides a source of transaction preconfirmations. By default, preconfirmations are not issued.
+    fn tx_preconfirmations(&self) -> Option<TxPreconfirmations> {
+        None
+    }
+
+    /// Provides a Merkle tree API client. By default, the tree API is not available.
+    fn tree_api(&self) -> Option<Arc<dyn TreeApiClient>> {
+        None
+    }
+
     async fn test(&self, client: &DynClient<L2>, pool: &ConnectionPool<Core>)
         -> anyhow::Result<()>;
 }
@@ -295,6 +307,12 @@ async fn test_http_server(test: impl HttpTest) {
     if let Some(executor_options) = test.executor_options() {
         server_builder = server_builder.with_executor_options(executor_options);
     }
+    if let Some(tx_preconfirmations) = test.tx_preconfirmations() {
+        server_builder = server_builder.with_tx_preconfirmations(tx_preconfirmations);
+    }
+    if let Some(tree_api) = test.tree_api() {
+        server_builder = server_builder.with_tree_api(tree_api);
+    }
     let mut server_handles = server_builder.build_http(stop_receiver).await;
 
     let local_addr = server_handles.wait_until_ready().await;
@@ -1180,6 +1198,199 @@ async fn getting_bytecodes() {
     test_http_server(GetBytecodeTest).await;
 }
 
+#[derive(Debug)]
+struct GetProofTest;
+
+#[async_trait]
+impl HttpTest for GetProofTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut connection = pool.connection().await?;
+        store_l2_block(&mut connection, L2BlockNumber(1), &[]).await?;
+
+        let address = Address::repeat_byte(1);
+        // The L1 batch containing the block is not sealed yet.
+        let proof = EthNamespaceClient::get_proof(
+            &client,
+            address,
+            vec![H256::zero()],
+            Some(api::BlockIdVariant::BlockNumber(api::BlockNumber::Latest)),
+        )
+        .await?;
+        assert!(proof.is_none(), "{proof:?}");
+
+        seal_l1_batch(&mut connection, L1BatchNumber(1)).await?;
+        // The test server doesn't have a Merkle tree API configured.
+        let err = EthNamespaceClient::get_proof(
+            &client,
+            address,
+            vec![H256::zero()],
+            Some(api::BlockIdVariant::BlockNumber(api::BlockNumber::Latest)),
+        )
+        .await
+        .unwrap_err();
+        if let ClientError::Call(err) = err {
+            assert_eq!(err.code(), ErrorCode::MethodNotFound.code());
+        } else {
+            panic!("Unexpected error: {err:?}");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_proofs() {
+    test_http_server(GetProofTest).await;
+}
+
+/// Mock Merkle tree API returning preset entries for a single L1 batch.
+#[derive(Debug)]
+struct MockTreeApi {
+    l1_batch_number: L1BatchNumber,
+    entries: HashMap<U256, TreeEntryWithProof>,
+}
+
+impl MockTreeApi {
+    fn entry(value: H256, index: u64) -> TreeEntryWithProof {
+        TreeEntryWithProof {
+            value,
+            index,
+            merkle_path: vec![H256::from_low_u64_be(index)],
+        }
+    }
+}
+
+#[async_trait]
+impl TreeApiClient for MockTreeApi {
+    async fn get_info(&self) -> Result<MerkleTreeInfo, TreeApiError> {
+        Err(TreeApiError::NotReady(None))
+    }
+
+    async fn get_proofs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeEntryWithProof>, TreeApiError> {
+        assert_eq!(l1_batch_number, self.l1_batch_number);
+        Ok(hashed_keys
+            .into_iter()
+            .map(|key| {
+                self.entries
+                    .get(&key)
+                    .cloned()
+                    .unwrap_or(TreeEntryWithProof {
+                        value: H256::zero(),
+                        index: 0,
+                        merkle_path: vec![],
+                    })
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug)]
+struct GetProofWithTreeTest;
+
+impl GetProofWithTreeTest {
+    const ADDRESS: Address = Address::repeat_byte(1);
+    const SLOT: H256 = H256::repeat_byte(2);
+    const MISSING_SLOT: H256 = H256::repeat_byte(3);
+}
+
+#[async_trait]
+impl HttpTest for GetProofWithTreeTest {
+    fn tree_api(&self) -> Option<Arc<dyn TreeApiClient>> {
+        let address = Self::ADDRESS;
+        let full_nonce = nonces_to_full_nonce(5.into(), 3.into());
+        let slot_key = StorageKey::new(AccountTreeId::new(address), Self::SLOT);
+        let entries = [
+            (
+                get_code_key(&address),
+                MockTreeApi::entry(H256::repeat_byte(0xc0), 10),
+            ),
+            (
+                get_nonce_key(&address),
+                MockTreeApi::entry(u256_to_h256(full_nonce), 11),
+            ),
+            (
+                storage_key_for_eth_balance(&address),
+                MockTreeApi::entry(u256_to_h256(1_000.into()), 12),
+            ),
+            (slot_key, MockTreeApi::entry(H256::repeat_byte(0x11), 13)),
+        ];
+        let entries = entries
+            .into_iter()
+            .map(|(key, entry)| (key.hashed_key_u256(), entry))
+            .collect();
+        Some(Arc::new(MockTreeApi {
+            l1_batch_number: L1BatchNumber(1),
+            entries,
+        }))
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut connection = pool.connection().await?;
+        store_l2_block(&mut connection, L2BlockNumber(1), &[]).await?;
+        seal_l1_batch(&mut connection, L1BatchNumber(1)).await?;
+
+        let address = Self::ADDRESS;
+        let proof = EthNamespaceClient::get_proof(
+            &client,
+            address,
+            vec![Self::SLOT, Self::MISSING_SLOT],
+            Some(api::BlockIdVariant::BlockNumber(api::BlockNumber::Latest)),
+        )
+        .await?
+        .context("no proof")?;
+
+        assert_eq!(proof.address, address);
+        assert_eq!(proof.l1_batch_number, L1BatchNumber(1));
+        assert_eq!(proof.storage_hash, create_l1_batch_metadata(1).root_hash);
+        assert_eq!(proof.code_hash, H256::repeat_byte(0xc0));
+        // Only the transaction nonce should be returned, without the deployment nonce.
+        assert_eq!(proof.nonce, 5.into());
+        assert_eq!(proof.balance, 1_000.into());
+
+        let account_keys = [
+            get_code_key(&address),
+            get_nonce_key(&address),
+            storage_key_for_eth_balance(&address),
+        ];
+        assert_eq!(proof.account_proof.len(), account_keys.len());
+        for (i, (account_proof, key)) in proof.account_proof.iter().zip(&account_keys).enumerate() {
+            let expected_index = 10 + i as u64;
+            assert_eq!(account_proof.key, *key.key());
+            assert_eq!(account_proof.index, expected_index);
+            assert_eq!(account_proof.proof, [H256::from_low_u64_be(expected_index)]);
+        }
+
+        let [slot_proof, missing_slot_proof] = proof.storage_proof.as_slice() else {
+            panic!("Unexpected storage proofs: {:?}", proof.storage_proof);
+        };
+        assert_eq!(slot_proof.key, Self::SLOT);
+        assert_eq!(slot_proof.value, H256::repeat_byte(0x11));
+        assert_eq!(slot_proof.index, 13);
+        assert_eq!(slot_proof.proof, [H256::from_low_u64_be(13)]);
+        assert_eq!(missing_slot_proof.key, Self::MISSING_SLOT);
+        assert_eq!(missing_slot_proof.value, H256::zero());
+        assert_eq!(missing_slot_proof.index, 0);
+        assert!(missing_slot_proof.proof.is_empty());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_proofs_with_tree_api() {
+    test_http_server(GetProofWithTreeTest).await;
+}
+
 #[derive(Debug)]
 struct FeeHistoryTest;
 
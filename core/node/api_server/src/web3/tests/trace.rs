@@ -0,0 +1,208 @@
+//! Tests for the `trace` Web3 namespace.
+
+use zksync_types::{
+    api::trace::{TraceFilter, TraceType},
+    BOOTLOADER_ADDRESS,
+};
+use zksync_web3_decl::namespaces::TraceNamespaceClient;
+
+use super::{debug::execute_l2_transaction_with_traces, *};
+
+#[derive(Debug)]
+struct TraceNamespaceTest;
+
+#[async_trait]
+impl HttpTest for TraceNamespaceTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [0, 1, 2].map(execute_l2_transaction_with_traces);
+        let mut storage = pool.connection().await?;
+        let new_l2_block = store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let block_traces = client.trace_block(1_u32.into()).await?;
+        // Each transaction has a top-level bootloader call with 2 child calls.
+        assert_eq!(block_traces.len(), 3 * tx_results.len());
+        for (tx_traces, tx_result) in block_traces.chunks(3).zip(&tx_results) {
+            let top_trace = &tx_traces[0];
+            assert_eq!(top_trace.action.to, BOOTLOADER_ADDRESS);
+            assert!(top_trace.trace_address.is_empty());
+            assert_eq!(top_trace.subtraces, 2);
+            for (i, (trace, call)) in tx_traces[1..]
+                .iter()
+                .zip(&tx_result.call_traces)
+                .enumerate()
+            {
+                assert_eq!(trace.trace_address, [i]);
+                assert_eq!(trace.action.from, call.from);
+                assert_eq!(trace.action.to, call.to);
+            }
+            for trace in tx_traces {
+                assert_eq!(trace.transaction_hash, tx_result.hash);
+                assert_eq!(trace.block_number, 1);
+                assert_eq!(trace.block_hash, new_l2_block.hash);
+            }
+        }
+
+        let tx_traces = client
+            .trace_transaction(tx_results[1].hash)
+            .await?
+            .context("no transaction traces")?;
+        assert_eq!(tx_traces, block_traces[3..6]);
+        let missing_tx_traces = client.trace_transaction(H256::repeat_byte(0xff)).await?;
+        assert!(missing_tx_traces.is_none());
+
+        let filter = TraceFilter {
+            from_block: Some(1_u32.into()),
+            to_block: Some(api::BlockNumber::Latest),
+            from_address: Some(vec![Address::repeat_byte(1)]),
+            ..TraceFilter::default()
+        };
+        let filtered_traces = client.trace_filter(filter).await?;
+        assert_eq!(filtered_traces, [block_traces[4].clone()]);
+
+        let filter = TraceFilter {
+            to_address: Some(vec![BOOTLOADER_ADDRESS]),
+            after: Some(1),
+            count: Some(1),
+            ..TraceFilter::default()
+        };
+        let filtered_traces = client.trace_filter(filter).await?;
+        assert_eq!(filtered_traces, [block_traces[3].clone()]);
+
+        let filter = TraceFilter {
+            from_block: Some(0_u32.into()),
+            to_block: Some(10_000_u32.into()),
+            ..TraceFilter::default()
+        };
+        let err = client.trace_filter(filter).await.unwrap_err();
+        if let ClientError::Call(err) = err {
+            assert_eq!(err.code(), ErrorCode::InvalidParams.code());
+            assert!(err.message().contains("block range"), "{err:?}");
+        } else {
+            panic!("Unexpected error: {err:?}");
+        }
+
+        let replay_results = client
+            .replay_block_transactions(1_u32.into(), vec![TraceType::Trace])
+            .await?;
+        assert_eq!(replay_results.len(), tx_results.len());
+        for ((result, tx_traces), tx_result) in replay_results
+            .iter()
+            .zip(block_traces.chunks(3))
+            .zip(&tx_results)
+        {
+            assert_eq!(result.transaction_hash, tx_result.hash);
+            assert_eq!(result.trace, tx_traces);
+        }
+        let replay_results = client
+            .replay_block_transactions(1_u32.into(), vec![])
+            .await?;
+        assert_eq!(replay_results.len(), tx_results.len());
+        assert!(replay_results.iter().all(|result| result.trace.is_empty()));
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn trace_namespace_basics() {
+    test_http_server(TraceNamespaceTest).await;
+}
+
+#[derive(Debug)]
+struct TraceFilterPaginationTest;
+
+impl TraceFilterPaginationTest {
+    /// Exceeds the number of blocks loaded at once by `trace_filter`.
+    const BLOCK_COUNT: u32 = 25;
+    const BLOCK_RANGE_LIMIT: u32 = 30;
+}
+
+#[async_trait]
+impl HttpTest for TraceFilterPaginationTest {
+    fn web3_config(&self) -> Web3JsonRpcConfig {
+        Web3JsonRpcConfig {
+            trace_filter_block_range_limit: Self::BLOCK_RANGE_LIMIT,
+            ..Web3JsonRpcConfig::for_tests()
+        }
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        let mut tx_hashes = vec![];
+        for number in 1..=Self::BLOCK_COUNT {
+            let tx_result = execute_l2_transaction_with_traces(0);
+            tx_hashes.push(tx_result.hash);
+            store_l2_block(&mut storage, L2BlockNumber(number), &[tx_result]).await?;
+        }
+        drop(storage);
+
+        // Only top-level calls are made to the bootloader, so there's a single matching trace per block.
+        let filter = TraceFilter {
+            from_block: Some(1_u32.into()),
+            to_address: Some(vec![BOOTLOADER_ADDRESS]),
+            after: Some(18),
+            count: Some(4),
+            ..TraceFilter::default()
+        };
+        let filtered_traces = client.trace_filter(filter.clone()).await?;
+        let filtered_hashes: Vec<_> = filtered_traces
+            .iter()
+            .map(|trace| trace.transaction_hash)
+            .collect();
+        assert_eq!(filtered_hashes, tx_hashes[18..22]);
+        let filtered_blocks: Vec<_> = filtered_traces
+            .iter()
+            .map(|trace| trace.block_number)
+            .collect();
+        assert_eq!(filtered_blocks, [19, 20, 21, 22]);
+
+        let filter = TraceFilter {
+            after: Some(Self::BLOCK_COUNT as usize - 1),
+            count: None,
+            ..filter
+        };
+        let filtered_traces = client.trace_filter(filter).await?;
+        let filtered_hashes: Vec<_> = filtered_traces
+            .iter()
+            .map(|trace| trace.transaction_hash)
+            .collect();
+        assert_eq!(filtered_hashes, [*tx_hashes.last().unwrap()]);
+
+        let filter = TraceFilter {
+            from_block: Some(1_u32.into()),
+            to_block: Some(Self::BLOCK_RANGE_LIMIT.into()),
+            ..TraceFilter::default()
+        };
+        let all_traces = client.trace_filter(filter).await?;
+        // Each transaction has a top-level bootloader call with 2 child calls.
+        assert_eq!(all_traces.len(), 3 * Self::BLOCK_COUNT as usize);
+
+        let filter = TraceFilter {
+            from_block: Some(0_u32.into()),
+            to_block: Some(Self::BLOCK_RANGE_LIMIT.into()),
+            ..TraceFilter::default()
+        };
+        let err = client.trace_filter(filter).await.unwrap_err();
+        if let ClientError::Call(err) = err {
+            assert_eq!(err.code(), ErrorCode::InvalidParams.code());
+            assert!(err.message().contains("block range"), "{err:?}");
+        } else {
+            panic!("Unexpected error: {err:?}");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn trace_filter_pagination() {
+    test_http_server(TraceFilterPaginationTest).await;
+}
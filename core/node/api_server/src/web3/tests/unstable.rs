@@ -1,9 +1,12 @@
 //! Tests for the `unstable` Web3 namespace.
 
+use std::sync::Mutex;
+
+use zksync_multivm::interface::ExecutionResult;
 use zksync_types::tee_types::TeeType;
 use zksync_web3_decl::namespaces::UnstableNamespaceClient;
 
-use super::*;
+use super::{debug::execute_l2_transaction_with_traces, *};
 
 #[derive(Debug)]
 struct GetTeeProofsTest {}
@@ -62,3 +65,60 @@ impl HttpTest for GetTeeProofsTest {
 async fn get_tee_proofs() {
     test_http_server(GetTeeProofsTest::new()).await;
 }
+
+#[derive(Debug, Default)]
+struct ProfileTransactionTest {
+    executed_tx_hashes: Arc<Mutex<Vec<H256>>>,
+}
+
+#[async_trait]
+impl HttpTest for ProfileTransactionTest {
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let executed_tx_hashes = self.executed_tx_hashes.clone();
+        let mut tx_executor = MockOneshotExecutor::default();
+        tx_executor.set_tx_responses(move |tx, env| {
+            // Transactions must be replayed on top of the previous L2 block.
+            assert_eq!(env.l1_batch.first_l2_block.number, 1);
+            executed_tx_hashes.lock().unwrap().push(tx.hash());
+            ExecutionResult::Success { output: vec![] }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [0, 1, 2].map(execute_l2_transaction_with_traces);
+        let tx_hashes: Vec<_> = tx_results.iter().map(|tx| tx.hash).collect();
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let profile = client
+            .profile_transaction(tx_hashes[1])
+            .await?
+            .context("no transaction profile")?;
+        assert_eq!(profile.transaction_hash, tx_hashes[1]);
+        assert!(profile.frames.is_empty());
+        assert!(profile.storage_writes.is_empty());
+        let receipt = client
+            .get_transaction_receipt(tx_hashes[1])
+            .await?
+            .context("no transaction receipt")?;
+        assert_eq!(Some(U256::from(profile.receipt_gas_used)), receipt.gas_used);
+        // Only transactions up to and including the profiled one should be executed.
+        let executed_tx_hashes = std::mem::take(&mut *self.executed_tx_hashes.lock().unwrap());
+        assert_eq!(executed_tx_hashes, tx_hashes[..2]);
+
+        let missing_profile = client.profile_transaction(H256::repeat_byte(0xff)).await?;
+        assert!(missing_profile.is_none());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn profiling_transaction() {
+    test_http_server(ProfileTransactionTest::default()).await;
+}
@@ -6,18 +6,23 @@ use std::{
         atomic::{AtomicU32, Ordering},
         Mutex,
     },
+    time::Instant,
 };
 
-use api::state_override::{OverrideAccount, StateOverride};
+use api::{
+    simulate::{BlockOverrides, SimulatePayload, SimulatedBlockCalls, SimulatedCallError},
+    state_override::{OverrideAccount, StateOverride},
+};
 use test_casing::test_casing;
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_multivm::interface::{
     ExecutionResult, OneshotEnv, VmExecutionLogs, VmExecutionResultAndLogs, VmRevertReason,
 };
+use zksync_system_constants::KNOWN_CODES_STORAGE_ADDRESS;
 use zksync_types::{
     api::ApiStorageLog, fee_model::BatchFeeInput, get_intrinsic_constants,
-    transaction_request::CallRequest, u256_to_h256, K256PrivateKey, L2ChainId, PackedEthSignature,
-    StorageLogKind, StorageLogWithPreviousValue, Transaction, U256,
+    transaction_request::CallRequest, u256_to_h256, web3::AccessListItem, K256PrivateKey,
+    L2ChainId, PackedEthSignature, StorageLogKind, StorageLogWithPreviousValue, Transaction, U256,
 };
 use zksync_vm_executor::oneshot::MockOneshotExecutor;
 use zksync_web3_decl::namespaces::{DebugNamespaceClient, UnstableNamespaceClient};
@@ -402,6 +407,269 @@ async fn call_method_with_slow_vm() {
     test_http_server(CallTestWithSlowVm).await;
 }
 
+#[derive(Debug)]
+struct CreateAccessListTest;
+
+impl CreateAccessListTest {
+    fn storage_logs() -> Vec<StorageLogWithPreviousValue> {
+        let contract = AccountTreeId::new(Address::repeat_byte(2));
+        let other_contract = AccountTreeId::new(Address::repeat_byte(3));
+        let read_log = |key| StorageLog {
+            kind: StorageLogKind::Read,
+            key,
+            value: H256::zero(),
+        };
+        [
+            read_log(StorageKey::new(contract, H256::zero())),
+            read_log(StorageKey::new(other_contract, H256::zero())),
+            StorageLog {
+                kind: StorageLogKind::InitialWrite,
+                ..read_log(StorageKey::new(contract, H256::from_low_u64_be(1)))
+            },
+            // Repeated access; must be deduplicated.
+            read_log(StorageKey::new(contract, H256::zero())),
+            // Slots of system contracts touched by the bootloader; must be filtered out.
+            read_log(get_nonce_key(&Address::repeat_byte(1))),
+            read_log(storage_key_for_eth_balance(&Address::repeat_byte(1))),
+            read_log(StorageKey::new(
+                AccountTreeId::new(KNOWN_CODES_STORAGE_ADDRESS),
+                H256::repeat_byte(4),
+            )),
+        ]
+        .into_iter()
+        .map(|log| StorageLogWithPreviousValue {
+            log,
+            previous_value: H256::zero(),
+        })
+        .collect()
+    }
+
+    /// Calls are executed on top of L2 block #1; the pending block is #2.
+    fn assert_block_number(calldata: &[u8], env: &OneshotEnv) {
+        let expected_block_number = if calldata.ends_with(b"latest") { 1 } else { 2 };
+        assert_eq!(env.l1_batch.first_l2_block.number, expected_block_number);
+    }
+}
+
+#[async_trait]
+impl HttpTest for CreateAccessListTest {
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let mut tx_executor = MockOneshotExecutor::default();
+        tx_executor.set_full_call_responses(|tx, env| {
+            Self::assert_block_number(tx.execute.calldata(), env);
+            let result = if tx.execute.calldata() == b"revert" {
+                ExecutionResult::Revert {
+                    output: VmRevertReason::VmError,
+                }
+            } else {
+                ExecutionResult::Success { output: vec![] }
+            };
+            let mut output = VmExecutionResultAndLogs::mock(result);
+            output.logs.storage_logs = Self::storage_logs();
+            output
+        });
+        tx_executor.set_tx_responses(|tx, env| {
+            let calldata = tx.execute.calldata();
+            assert!(calldata.starts_with(b"call"), "{calldata:?}");
+            // Gas must be estimated on top of the same block as the access list.
+            Self::assert_block_number(calldata, env);
+            ExecutionResult::Success { output: vec![] }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut connection = pool.connection().await?;
+        store_l2_block(&mut connection, L2BlockNumber(1), &[]).await?;
+        drop(connection);
+
+        let expected_access_list = [
+            AccessListItem {
+                address: Address::repeat_byte(2),
+                storage_keys: vec![H256::zero(), H256::from_low_u64_be(1)],
+            },
+            AccessListItem {
+                address: Address::repeat_byte(3),
+                storage_keys: vec![H256::zero()],
+            },
+        ];
+        let mut request = CallTest::call_request(b"call");
+        request.value = None;
+        request.gas = None;
+        let result = client.create_access_list(request, None).await?;
+        assert_eq!(result.access_list, expected_access_list);
+        assert!(result.error.is_none(), "{result:?}");
+        assert!(result.gas_used > U256::zero());
+
+        let mut request = CallTest::call_request(b"call_latest");
+        request.value = None;
+        request.gas = None;
+        let result = client
+            .create_access_list(
+                request,
+                Some(api::BlockIdVariant::BlockNumber(api::BlockNumber::Latest)),
+            )
+            .await?;
+        assert_eq!(result.access_list, expected_access_list);
+        assert!(result.error.is_none(), "{result:?}");
+
+        let result = client
+            .create_access_list(CallTest::call_request(b"revert"), None)
+            .await?;
+        assert_eq!(result.access_list, expected_access_list);
+        let err = result.error.unwrap();
+        assert!(err.contains("reverted"), "{err}");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn create_access_list_basics() {
+    test_http_server(CreateAccessListTest).await;
+}
+
+#[derive(Debug)]
+struct SimulateV1Test;
+
+impl SimulateV1Test {
+    const TIMESTAMP: u64 = 10_000_000_000;
+}
+
+#[async_trait]
+impl HttpTest for SimulateV1Test {
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let mut tx_executor = MockOneshotExecutor::default();
+        tx_executor.set_call_responses(|tx, env| {
+            assert_eq!(
+                env.l1_batch.timestamp,
+                env.l1_batch.first_l2_block.timestamp
+            );
+            // The first simulated block is the pending block; the following blocks have increasing numbers.
+            let block_offset = env.l1_batch.timestamp - Self::TIMESTAMP;
+            assert_eq!(
+                u64::from(env.l1_batch.first_l2_block.number),
+                2 + block_offset
+            );
+            if block_offset == 0 {
+                assert!(env.current_block.is_none());
+            } else {
+                let current_block = env.current_block.as_ref().unwrap();
+                assert_eq!(current_block.number, env.l1_batch.first_l2_block.number - 1);
+                assert_eq!(current_block.timestamp, env.l1_batch.timestamp - 1);
+            }
+            if tx.execute.calldata() == b"revert" {
+                return ExecutionResult::Revert {
+                    output: VmRevertReason::General {
+                        msg: "oops".to_owned(),
+                        data: vec![],
+                    },
+                };
+            }
+
+            let mut output = env.l1_batch.first_l2_block.timestamp.to_be_bytes().to_vec();
+            let base_fee = env.l1_batch.enforced_base_fee.unwrap_or_default();
+            output.extend_from_slice(&base_fee.to_be_bytes());
+            ExecutionResult::Success { output }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut connection = pool.connection().await?;
+        store_l2_block(&mut connection, L2BlockNumber(1), &[]).await?;
+        drop(connection);
+
+        let payload = SimulatePayload {
+            block_state_calls: vec![
+                SimulatedBlockCalls {
+                    block_overrides: Some(BlockOverrides {
+                        time: Some(Self::TIMESTAMP.into()),
+                        base_fee_per_gas: Some(1_000.into()),
+                    }),
+                    calls: vec![
+                        CallTest::call_request(b"call"),
+                        CallTest::call_request(b"revert"),
+                    ],
+                    ..SimulatedBlockCalls::default()
+                },
+                SimulatedBlockCalls {
+                    calls: vec![CallTest::call_request(b"call")],
+                    ..SimulatedBlockCalls::default()
+                },
+            ],
+            ..SimulatePayload::default()
+        };
+        let blocks = client.simulate_v1(payload, None).await?;
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].number, 2.into());
+        assert_eq!(blocks[0].timestamp, Self::TIMESTAMP.into());
+        assert_eq!(blocks[0].base_fee_per_gas, 1_000.into());
+        assert_eq!(blocks[1].number, 3.into());
+        assert_eq!(blocks[1].timestamp, (Self::TIMESTAMP + 1).into());
+
+        let [success, revert] = blocks[0].calls.as_slice() else {
+            panic!("unexpected calls: {:?}", blocks[0].calls);
+        };
+        assert_eq!(success.status, 1.into());
+        assert_eq!(success.return_data.0[..8], Self::TIMESTAMP.to_be_bytes());
+        assert_eq!(success.return_data.0[8..], 1_000_u64.to_be_bytes());
+        assert_eq!(revert.status, 0.into());
+        let err = revert.error.as_ref().unwrap();
+        assert_eq!(err.code, SimulatedCallError::REVERTED_CODE);
+        assert!(err.message.contains("oops"), "{err:?}");
+
+        let [call] = blocks[1].calls.as_slice() else {
+            panic!("unexpected calls: {:?}", blocks[1].calls);
+        };
+        assert_eq!(call.status, 1.into());
+        assert_eq!(call.return_data.0[..8], (Self::TIMESTAMP + 1).to_be_bytes());
+        // The base fee override must not be carried over to the next block.
+        assert_eq!(call.return_data.0[8..], 0_u64.to_be_bytes());
+
+        let payload = SimulatePayload {
+            block_state_calls: vec![
+                SimulatedBlockCalls {
+                    block_overrides: Some(BlockOverrides {
+                        time: Some(Self::TIMESTAMP.into()),
+                        ..BlockOverrides::default()
+                    }),
+                    ..SimulatedBlockCalls::default()
+                },
+                SimulatedBlockCalls {
+                    block_overrides: Some(BlockOverrides {
+                        time: Some(Self::TIMESTAMP.into()),
+                        ..BlockOverrides::default()
+                    }),
+                    ..SimulatedBlockCalls::default()
+                },
+            ],
+            ..SimulatePayload::default()
+        };
+        let err = client.simulate_v1(payload, None).await.unwrap_err();
+        if let ClientError::Call(err) = err {
+            assert_eq!(err.code(), ErrorCode::InvalidParams.code());
+            assert!(err.message().contains("timestamp"), "{err:?}");
+        } else {
+            panic!("Unexpected error: {err:?}");
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn simulate_v1_basics() {
+    test_http_server(SimulateV1Test).await;
+}
+
 #[derive(Debug)]
 struct SendRawTransactionTest {
     snapshot_recovery: bool,
@@ -709,6 +977,142 @@ async fn send_raw_transaction_with_detailed_output() {
     test_http_server(SendTransactionWithDetailedOutputTest).await;
 }
 
+#[derive(Debug)]
+struct SendTransactionWithPreconfirmationTest {
+    preconfirmations: TxPreconfirmations,
+    /// Whether a preconfirmation is issued for the sent transaction.
+    preconfirm: bool,
+}
+
+impl SendTransactionWithPreconfirmationTest {
+    const PRECONFIRMATION_TIMEOUT: Duration = Duration::from_millis(500);
+
+    fn new(preconfirm: bool) -> Self {
+        let preconfirmations = TxPreconfirmations::default();
+        preconfirmations.set_signer(Self::signing_key().address());
+        Self {
+            preconfirmations,
+            preconfirm,
+        }
+    }
+
+    fn signing_key() -> K256PrivateKey {
+        K256PrivateKey::from_bytes(H256::repeat_byte(0x42)).unwrap()
+    }
+
+    fn preconfirmation(tx_hash: H256) -> api::SignedTransactionPreconfirmation {
+        let preconfirmation = api::TransactionPreconfirmation {
+            transaction_hash: tx_hash,
+            l2_block_number: 1.into(),
+            l2_block_timestamp: 1_000.into(),
+            l1_batch_number: 1.into(),
+            transaction_index: 0.into(),
+            status: 1.into(),
+        };
+        api::SignedTransactionPreconfirmation::new(
+            preconfirmation,
+            &Self::signing_key(),
+            L2ChainId::default(),
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait]
+impl HttpTest for SendTransactionWithPreconfirmationTest {
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let mut tx_executor = MockOneshotExecutor::default();
+        tx_executor.set_tx_responses(|tx, _| {
+            assert_eq!(
+                tx.hash(),
+                SendRawTransactionTest::transaction_bytes_and_hash(true).1
+            );
+            ExecutionResult::Success { output: vec![] }
+        });
+        tx_executor
+    }
+
+    fn web3_config(&self) -> Web3JsonRpcConfig {
+        Web3JsonRpcConfig {
+            preconfirmation_timeout: Self::PRECONFIRMATION_TIMEOUT,
+            ..Web3JsonRpcConfig::for_tests()
+        }
+    }
+
+    fn tx_preconfirmations(&self) -> Option<TxPreconfirmations> {
+        Some(self.preconfirmations.clone())
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        // Manually set sufficient balance for the transaction account.
+        let mut storage = pool.connection().await?;
+        storage
+            .storage_logs_dal()
+            .append_storage_logs(
+                L2BlockNumber(0),
+                &[SendRawTransactionTest::balance_storage_log()],
+            )
+            .await?;
+        drop(storage);
+
+        let (tx_bytes, tx_hash) = SendRawTransactionTest::transaction_bytes_and_hash(true);
+        // Emulate the state keeper issuing preconfirmations. The server only listens to preconfirmations
+        // while handling the request, so they are published periodically.
+        let preconfirmations = self.preconfirmations.clone();
+        let preconfirm = self.preconfirm;
+        let publisher_task = tokio::spawn(async move {
+            loop {
+                preconfirmations.publish(Self::preconfirmation(H256::repeat_byte(0xff)));
+                if preconfirm {
+                    preconfirmations.publish(Self::preconfirmation(tx_hash));
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        });
+
+        let started_at = Instant::now();
+        let send_result = client
+            .send_raw_transaction_with_detailed_output(tx_bytes.into())
+            .await;
+        let elapsed = started_at.elapsed();
+        publisher_task.abort();
+        let send_result = send_result?;
+        assert_eq!(send_result.transaction_hash, tx_hash);
+
+        if self.preconfirm {
+            let preconfirmation = send_result
+                .preconfirmation
+                .context("missing preconfirmation")?;
+            assert_eq!(preconfirmation, Self::preconfirmation(tx_hash));
+            assert_eq!(
+                preconfirmation.recover_signer(L2ChainId::default())?,
+                Self::signing_key().address()
+            );
+        } else {
+            assert_eq!(send_result.preconfirmation, None);
+            assert!(
+                elapsed >= Self::PRECONFIRMATION_TIMEOUT,
+                "Response was returned before the preconfirmation timeout: {elapsed:?}"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn send_raw_transaction_with_preconfirmation() {
+    test_http_server(SendTransactionWithPreconfirmationTest::new(true)).await;
+}
+
+#[tokio::test]
+async fn send_raw_transaction_without_preconfirmation() {
+    test_http_server(SendTransactionWithPreconfirmationTest::new(false)).await;
+}
+
 #[derive(Debug, Default)]
 struct TraceCallTest {
     fee_input: ExpectedFeeInput,
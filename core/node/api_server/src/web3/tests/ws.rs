@@ -8,7 +8,8 @@ use http::StatusCode;
 use tokio::sync::watch;
 use zksync_dal::ConnectionPool;
 use zksync_types::{
-    api, settlement::SettlementLayer, Address, Bloom, L1BatchNumber, H160, H256, U64,
+    api, settlement::SettlementLayer, Address, Bloom, K256PrivateKey, L1BatchNumber, L2ChainId,
+    H160, H256, U64,
 };
 use zksync_web3_decl::{
     client::{WsClient, L2},
@@ -110,7 +111,7 @@ async fn notifiers_start_after_snapshot_recovery() {
 
     let (stop_sender, stop_receiver) = watch::channel(false);
     let (events_sender, mut events_receiver) = mpsc::unbounded_channel();
-    let mut subscribe_logic = EthSubscribe::new(POLL_INTERVAL);
+    let mut subscribe_logic = EthSubscribe::new(POLL_INTERVAL, L2ChainId::default());
     subscribe_logic.set_events_sender(events_sender);
     let notifier_handles = subscribe_logic.spawn_notifiers(pool.clone(), &stop_receiver);
     assert!(!notifier_handles.is_empty());
@@ -160,6 +161,11 @@ trait WsTest: Send + Sync {
     fn websocket_requests_per_minute_limit(&self) -> Option<NonZeroU32> {
         None
     }
+
+    /// Provides a source of transaction preconfirmations. By default, preconfirmations are not issued.
+    fn tx_preconfirmations(&self) -> Option<TxPreconfirmations> {
+        None
+    }
 }
 
 async fn test_ws_server(test: impl WsTest) {
@@ -184,7 +190,11 @@ async fn test_ws_server(test: impl WsTest) {
     drop(storage);
 
     let (stop_sender, stop_receiver) = watch::channel(false);
-    let (mut server_handles, pub_sub_events) = TestServerBuilder::new(pool.clone(), api_config)
+    let mut server_builder = TestServerBuilder::new(pool.clone(), api_config);
+    if let Some(tx_preconfirmations) = test.tx_preconfirmations() {
+        server_builder = server_builder.with_tx_preconfirmations(tx_preconfirmations);
+    }
+    let (mut server_handles, pub_sub_events) = server_builder
         .build_ws(test.websocket_requests_per_minute_limit(), stop_receiver)
         .await;
 
@@ -351,6 +361,245 @@ async fn basic_subscriptions_after_snapshot_recovery() {
     .await;
 }
 
+#[derive(Debug)]
+struct FilteredTransactionsSubscriptionTest;
+
+#[async_trait]
+impl WsTest for FilteredTransactionsSubscriptionTest {
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::Txs]).await;
+
+        let tx = create_l2_transaction(1, 2);
+        let other_tx = create_l2_transaction(1, 2);
+        let from_filter = PubSubFilter {
+            full_transactions: Some(true),
+            from_addresses: Some(vec![tx.initiator_account()]),
+            ..PubSubFilter::default()
+        };
+        let params = rpc_params!["newPendingTransactions", from_filter];
+        let mut full_txs_subscription = client
+            .subscribe::<api::Transaction, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        let to_filter = PubSubFilter {
+            to_addresses: Some(vec![other_tx.recipient_account().unwrap()]),
+            ..PubSubFilter::default()
+        };
+        let params = rpc_params!["newPendingTransactions", to_filter];
+        let mut tx_hashes_subscription = client
+            .subscribe::<H256, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        for _ in 0..2 {
+            wait_for_subscription(&mut pub_sub_events, SubscriptionType::Txs).await;
+        }
+
+        let mut storage = pool.connection().await?;
+        let tx_results = [
+            mock_execute_transaction(tx.clone().into()),
+            mock_execute_transaction(other_tx.clone().into()),
+        ];
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let received_tx = tokio::time::timeout(TEST_TIMEOUT, full_txs_subscription.next())
+            .await
+            .context("Timed out waiting for new tx")?
+            .context("Full txs subscription terminated")??;
+        assert_eq!(received_tx.hash, tx.hash());
+        assert_eq!(received_tx.from, Some(tx.initiator_account()));
+        assert_eq!(received_tx.to, tx.recipient_account());
+
+        let received_tx_hash = tokio::time::timeout(TEST_TIMEOUT, tx_hashes_subscription.next())
+            .await
+            .context("Timed out waiting for new tx hash")?
+            .context("Pending txs subscription terminated")??;
+        assert_eq!(received_tx_hash, other_tx.hash());
+
+        full_txs_subscription.unsubscribe().await?;
+        tx_hashes_subscription.unsubscribe().await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn filtered_transactions_subscription() {
+    test_ws_server(FilteredTransactionsSubscriptionTest).await;
+}
+
+#[derive(Debug)]
+struct MixedTransactionsSubscriptionTest;
+
+#[async_trait]
+impl WsTest for MixedTransactionsSubscriptionTest {
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::Txs]).await;
+
+        // Only hashes are requested at this point, so transaction bodies shouldn't be loaded.
+        let params = rpc_params!["newPendingTransactions"];
+        let mut tx_hashes_subscription = client
+            .subscribe::<H256, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Txs).await;
+
+        let mut storage = pool.connection().await?;
+        let tx = create_l2_transaction(1, 2);
+        store_l2_block(
+            &mut storage,
+            L2BlockNumber(1),
+            &[mock_execute_transaction(tx.clone().into())],
+        )
+        .await?;
+
+        let received_tx_hash = tokio::time::timeout(TEST_TIMEOUT, tx_hashes_subscription.next())
+            .await
+            .context("Timed out waiting for new tx hash")?
+            .context("Pending txs subscription terminated")??;
+        assert_eq!(received_tx_hash, tx.hash());
+
+        let filter = PubSubFilter {
+            full_transactions: Some(true),
+            ..PubSubFilter::default()
+        };
+        let params = rpc_params!["newPendingTransactions", filter];
+        let mut full_txs_subscription = client
+            .subscribe::<api::Transaction, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Txs).await;
+
+        let other_tx = create_l2_transaction(1, 2);
+        store_l2_block(
+            &mut storage,
+            L2BlockNumber(2),
+            &[mock_execute_transaction(other_tx.clone().into())],
+        )
+        .await?;
+        drop(storage);
+
+        let received_tx = tokio::time::timeout(TEST_TIMEOUT, full_txs_subscription.next())
+            .await
+            .context("Timed out waiting for new tx")?
+            .context("Full txs subscription terminated")??;
+        assert_eq!(received_tx.hash, other_tx.hash());
+        assert_eq!(received_tx.from, Some(other_tx.initiator_account()));
+
+        let received_tx_hash = tokio::time::timeout(TEST_TIMEOUT, tx_hashes_subscription.next())
+            .await
+            .context("Timed out waiting for new tx hash")?
+            .context("Pending txs subscription terminated")??;
+        assert_eq!(received_tx_hash, other_tx.hash());
+
+        full_txs_subscription.unsubscribe().await?;
+        tx_hashes_subscription.unsubscribe().await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn mixed_transactions_subscriptions() {
+    test_ws_server(MixedTransactionsSubscriptionTest).await;
+}
+
+#[derive(Debug)]
+struct L1BatchStatusSubscriptionTest;
+
+#[async_trait]
+impl WsTest for L1BatchStatusSubscriptionTest {
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::L1BatchStatus]).await;
+
+        let params = rpc_params!["l1BatchStatus"];
+        let mut subscription = client
+            .subscribe::<api::L1BatchStatusUpdate, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::L1BatchStatus).await;
+
+        let mut storage = pool.connection().await?;
+        let l1_batch_number = L1BatchNumber(1);
+        store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        seal_l1_batch(&mut storage, l1_batch_number).await?;
+        let commit_tx_hash = save_eth_tx(
+            &mut storage,
+            l1_batch_number,
+            L1BatchAggregatedActionType::Commit,
+        )
+        .await;
+        storage
+            .eth_sender_dal()
+            .confirm_tx(
+                commit_tx_hash,
+                EthTxFinalityStatus::FastFinalized,
+                U256::zero(),
+            )
+            .await?;
+
+        let update = tokio::time::timeout(TEST_TIMEOUT, subscription.next())
+            .await
+            .context("Timed out waiting for L1 batch status update")?
+            .context("L1 batch status subscription terminated")??;
+        assert_eq!(update.l1_batch_number, l1_batch_number);
+        assert_eq!(update.stage, api::L1BatchStage::Committed);
+        assert_eq!(update.tx_hash, commit_tx_hash);
+        assert_eq!(update.finality, EthTxFinalityStatus::FastFinalized);
+
+        // Finality upgrades of the same transaction should be reported as well.
+        storage
+            .eth_sender_dal()
+            .confirm_tx(commit_tx_hash, EthTxFinalityStatus::Finalized, U256::zero())
+            .await?;
+        let update = tokio::time::timeout(TEST_TIMEOUT, subscription.next())
+            .await
+            .context("Timed out waiting for L1 batch status update")?
+            .context("L1 batch status subscription terminated")??;
+        assert_eq!(update.stage, api::L1BatchStage::Committed);
+        assert_eq!(update.tx_hash, commit_tx_hash);
+        assert_eq!(update.finality, EthTxFinalityStatus::Finalized);
+
+        let execute_tx_hash = save_eth_tx(
+            &mut storage,
+            l1_batch_number,
+            L1BatchAggregatedActionType::Execute,
+        )
+        .await;
+        storage
+            .eth_sender_dal()
+            .confirm_tx(
+                execute_tx_hash,
+                EthTxFinalityStatus::Finalized,
+                U256::zero(),
+            )
+            .await?;
+        let update = tokio::time::timeout(TEST_TIMEOUT, subscription.next())
+            .await
+            .context("Timed out waiting for L1 batch status update")?
+            .context("L1 batch status subscription terminated")??;
+        assert_eq!(update.l1_batch_number, l1_batch_number);
+        assert_eq!(update.stage, api::L1BatchStage::Executed);
+        assert_eq!(update.tx_hash, execute_tx_hash);
+
+        subscription.unsubscribe().await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn l1_batch_status_subscription() {
+    test_ws_server(L1BatchStatusSubscriptionTest).await;
+}
+
 #[derive(Debug)]
 struct LogSubscriptionsTest {
     snapshot_recovery: bool,
@@ -378,15 +627,15 @@ impl LogSubscriptions {
             .await?;
         let address_filter = PubSubFilter {
             address: Some(Address::repeat_byte(23).into()),
-            topics: None,
+            ..PubSubFilter::default()
         };
         let params = rpc_params!["logs", address_filter];
         let address_subscription = client
             .subscribe::<api::Log, _>("eth_subscribe", params, "eth_unsubscribe")
             .await?;
         let topic_filter = PubSubFilter {
-            address: None,
             topics: Some(vec![Some(H256::repeat_byte(42).into())]),
+            ..PubSubFilter::default()
         };
         let params = rpc_params!["logs", topic_filter];
         let topic_subscription = client
@@ -626,6 +875,7 @@ impl WsTest for LogSubscriptionsWithDelayTest {
         let address_and_topic_filter = PubSubFilter {
             address: Some(Address::repeat_byte(23).into()),
             topics: Some(vec![Some(H256::repeat_byte(42).into())]),
+            ..PubSubFilter::default()
         };
         let params = rpc_params!["logs", address_and_topic_filter];
         let mut address_and_topic_subscription = client
@@ -662,6 +912,100 @@ async fn log_subscriptions_with_delay() {
     test_ws_server(LogSubscriptionsWithDelayTest).await;
 }
 
+#[derive(Debug)]
+struct PreconfirmationsSubscriptionTest {
+    preconfirmations: TxPreconfirmations,
+    signing_key: Option<K256PrivateKey>,
+}
+
+impl PreconfirmationsSubscriptionTest {
+    fn new(issues_preconfirmations: bool) -> Self {
+        let preconfirmations = TxPreconfirmations::default();
+        let signing_key = issues_preconfirmations
+            .then(|| K256PrivateKey::from_bytes(H256::repeat_byte(1)).unwrap());
+        if let Some(signing_key) = &signing_key {
+            preconfirmations.set_signer(signing_key.address());
+        }
+        Self {
+            preconfirmations,
+            signing_key,
+        }
+    }
+}
+
+#[async_trait]
+impl WsTest for PreconfirmationsSubscriptionTest {
+    fn tx_preconfirmations(&self) -> Option<TxPreconfirmations> {
+        Some(self.preconfirmations.clone())
+    }
+
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        _pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        let params = rpc_params!["preconfirmations"];
+        let subscription = client
+            .subscribe::<api::SignedTransactionPreconfirmation, _>(
+                "eth_subscribe",
+                params,
+                "eth_unsubscribe",
+            )
+            .await;
+        let Some(signing_key) = &self.signing_key else {
+            // The node doesn't issue preconfirmations, so the subscription must be rejected.
+            let err = subscription.unwrap_err();
+            assert_matches!(err, ClientError::Call(err) if err.code() == INVALID_PARAMS_CODE);
+            return Ok(());
+        };
+        let mut subscription = subscription?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Preconfirmations).await;
+
+        let chain_id = L2ChainId::default();
+        let preconfirmation = api::TransactionPreconfirmation {
+            transaction_hash: H256::repeat_byte(0x23),
+            l2_block_number: 1.into(),
+            l2_block_timestamp: 1_000.into(),
+            l1_batch_number: 1.into(),
+            transaction_index: 0.into(),
+            status: 1.into(),
+        };
+        let expected =
+            api::SignedTransactionPreconfirmation::new(preconfirmation, signing_key, chain_id)?;
+
+        // The notifier may start listening to preconfirmations after the subscription is established,
+        // so we publish the preconfirmation until it's received.
+        let received = tokio::time::timeout(TEST_TIMEOUT, async {
+            loop {
+                self.preconfirmations.publish(expected.clone());
+                tokio::select! {
+                    res = subscription.next() => break res,
+                    () = tokio::time::sleep(POLL_INTERVAL) => { /* publish again */ }
+                }
+            }
+        })
+        .await
+        .context("Timed out waiting for preconfirmation")?
+        .context("Preconfirmations subscription terminated")??;
+        assert_eq!(received, expected);
+        assert_eq!(received.recover_signer(chain_id)?, signing_key.address());
+
+        subscription.unsubscribe().await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn preconfirmations_subscription() {
+    test_ws_server(PreconfirmationsSubscriptionTest::new(true)).await;
+}
+
+#[tokio::test]
+async fn preconfirmations_subscription_is_rejected_without_signer() {
+    test_ws_server(PreconfirmationsSubscriptionTest::new(false)).await;
+}
+
 #[derive(Debug)]
 struct RateLimitingTest;
 
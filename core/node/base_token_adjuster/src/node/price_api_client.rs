@@ -1,11 +1,17 @@
 use std::{str::FromStr, sync::Arc};
 
+use anyhow::Context as _;
 use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_eth_client::web3_decl::client::{DynClient, L1};
 use zksync_external_price_api::{
-    cmc_api::CmcPriceApiClient, coingecko_api::CoinGeckoPriceAPIClient,
-    forced_price_client::ForcedPriceClient, NoOpPriceApiClient, PriceApiClient,
+    aggregated_price_client::AggregatedPriceClient,
+    cmc_api::CmcPriceApiClient,
+    coingecko_api::CoinGeckoPriceAPIClient,
+    forced_price_client::ForcedPriceClient,
+    onchain_price_client::{OnChainPriceClient, OnChainPriceFeed},
+    NoOpPriceApiClient, PriceApiClient,
 };
-use zksync_node_framework::{WiringError, WiringLayer};
+use zksync_node_framework::{FromContext, WiringError, WiringLayer};
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
 enum ExternalPriceApiKind {
@@ -14,6 +20,9 @@ enum ExternalPriceApiKind {
     Forced,
     CoinGecko,
     CoinMarketCap,
+    Chainlink,
+    UniswapV3Twap,
+    Aggregated,
 }
 
 impl FromStr for ExternalPriceApiKind {
@@ -25,19 +34,82 @@ impl FromStr for ExternalPriceApiKind {
             "forced" => Self::Forced,
             "coingecko" => Self::CoinGecko,
             "coinmarketcap" => Self::CoinMarketCap,
+            "chainlink" => Self::Chainlink,
+            "uniswap_v3" | "uniswap-v3" => Self::UniswapV3Twap,
+            "aggregated" => Self::Aggregated,
             _ => anyhow::bail!("Unknown external price API client source: {s:?}"),
         })
     }
 }
 
 impl ExternalPriceApiKind {
-    fn instantiate(&self, config: ExternalPriceApiClientConfig) -> Arc<dyn PriceApiClient> {
-        match self {
+    fn instantiate(
+        &self,
+        config: &ExternalPriceApiClientConfig,
+        eth_client: Option<&DynClient<L1>>,
+    ) -> anyhow::Result<Arc<dyn PriceApiClient>> {
+        Ok(match self {
             Self::NoOp => Arc::new(NoOpPriceApiClient),
-            Self::Forced => Arc::new(ForcedPriceClient::new(config)),
-            Self::CoinGecko => Arc::new(CoinGeckoPriceAPIClient::new(config)),
-            Self::CoinMarketCap => Arc::new(CmcPriceApiClient::new(config)),
-        }
+            Self::Forced => Arc::new(ForcedPriceClient::new(config.clone())),
+            Self::CoinGecko => Arc::new(CoinGeckoPriceAPIClient::new(config.clone())),
+            Self::CoinMarketCap => Arc::new(CmcPriceApiClient::new(config.clone())),
+            Self::Chainlink => {
+                let feed_config = config
+                    .chainlink
+                    .clone()
+                    .context("Chainlink price client started with no config")?;
+                let eth_client = eth_client.context("Chainlink price client requires L1 client")?;
+                Arc::new(OnChainPriceClient::new(
+                    OnChainPriceFeed::Chainlink(feed_config),
+                    eth_client.clone_boxed(),
+                ))
+            }
+            Self::UniswapV3Twap => {
+                let pool_config = config
+                    .uniswap_v3
+                    .clone()
+                    .context("Uniswap V3 price client started with no config")?;
+                let eth_client =
+                    eth_client.context("Uniswap V3 price client requires L1 client")?;
+                Arc::new(OnChainPriceClient::new(
+                    OnChainPriceFeed::UniswapV3Twap(pool_config),
+                    eth_client.clone_boxed(),
+                ))
+            }
+            Self::Aggregated => {
+                let aggregated_config = config
+                    .aggregated
+                    .as_ref()
+                    .context("aggregated price client started with no config")?;
+                let sources = aggregated_config
+                    .sources
+                    .iter()
+                    .map(|source| {
+                        let kind: Self = source.parse()?;
+                        anyhow::ensure!(
+                            !matches!(kind, Self::Aggregated),
+                            "aggregated price client cannot aggregate itself"
+                        );
+                        kind.instantiate(config, eth_client)
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                anyhow::ensure!(
+                    aggregated_config.min_sources > 0
+                        && aggregated_config.min_sources <= sources.len(),
+                    "minimum number of sources for aggregated price client must be in 1..={}",
+                    sources.len()
+                );
+                anyhow::ensure!(
+                    aggregated_config.max_deviation >= 0.0,
+                    "max deviation for aggregated price client must be non-negative"
+                );
+                Arc::new(AggregatedPriceClient::new(
+                    sources,
+                    aggregated_config.min_sources,
+                    aggregated_config.max_deviation,
+                ))
+            }
+        })
     }
 }
 
@@ -58,16 +130,24 @@ impl TryFrom<ExternalPriceApiClientConfig> for ExternalPriceApiLayer {
     }
 }
 
+#[derive(Debug, FromContext)]
+pub struct Input {
+    /// Only required by on-chain price clients.
+    eth_client: Option<Box<DynClient<L1>>>,
+}
+
 #[async_trait::async_trait]
 impl WiringLayer for ExternalPriceApiLayer {
-    type Input = ();
+    type Input = Input;
     type Output = Arc<dyn PriceApiClient>;
 
     fn layer_name(&self) -> &'static str {
         "external_price_api"
     }
 
-    async fn wire(self, (): Self::Input) -> Result<Self::Output, WiringError> {
-        Ok(self.kind.instantiate(self.config))
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        self.kind
+            .instantiate(&self.config, input.eth_client.as_deref())
+            .map_err(|err| WiringError::Configuration(format!("{err:#}")))
     }
 }
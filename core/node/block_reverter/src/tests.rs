@@ -395,6 +395,26 @@ impl ObjectStore for ErroneousStore {
         })
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+    ) -> Result<Vec<String>, ObjectStoreError> {
+        let object_keys = self.object_keys.lock().unwrap();
+        let mut keys: Vec<_> = object_keys
+            .iter()
+            .filter(|(key_bucket, key)| *key_bucket == bucket && key.starts_with(prefix))
+            .map(|(_, key)| key.clone())
+            .collect();
+        keys.sort_unstable();
+        Ok(keys)
+    }
+
+    async fn exists_raw(&self, bucket: Bucket, key: &str) -> Result<bool, ObjectStoreError> {
+        let object_keys = self.object_keys.lock().unwrap();
+        Ok(object_keys.contains(&(bucket, key.to_owned())))
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         bucket.to_string()
     }
@@ -20,7 +20,7 @@ use zksync_types::{
 };
 
 use crate::{
-    avail::sdk::{GasRelayClient, RawAvailClient},
+    avail::sdk::{decode_submit_data_extrinsic, GasRelayClient, RawAvailClient},
     utils::{to_non_retriable_da_error, to_retriable_da_error},
 };
 
@@ -336,6 +336,45 @@ impl DataAvailabilityClient for AvailClient {
         }
     }
 
+    async fn get_blob(&self, blob_id: &str) -> Result<Option<Vec<u8>>, DAError> {
+        let (block_hash, tx_idx) = blob_id.split_once(':').ok_or_else(|| DAError {
+            error: anyhow!("Invalid blob ID format"),
+            is_retriable: false,
+        })?;
+        let tx_idx: usize = tx_idx.parse().map_err(|_| DAError {
+            error: anyhow!("Invalid extrinsic index in blob ID"),
+            is_retriable: false,
+        })?;
+
+        let api_node_url = match &self.config.config {
+            AvailClientConfig::FullClient(conf) => conf.api_node_url.as_str(),
+            // The gas relay API doesn't provide access to submitted data, so it's fetched from an Avail node directly.
+            AvailClientConfig::GasRelay(conf) => {
+                conf.retrieval_node_url.as_deref().ok_or_else(|| DAError {
+                    error: anyhow!("Avail node URL for fetching blobs is not configured"),
+                    is_retriable: false,
+                })?
+            }
+        };
+        let ws_client = WsClientBuilder::default()
+            .build(api_node_url)
+            .await
+            .map_err(to_non_retriable_da_error)?;
+
+        let Some(extrinsic) = RawAvailClient::fetch_extrinsic(&ws_client, block_hash, tx_idx)
+            .await
+            .map_err(to_retriable_da_error)?
+        else {
+            return Ok(None);
+        };
+        let call_index = RawAvailClient::fetch_submit_data_call_index(&ws_client)
+            .await
+            .map_err(to_retriable_da_error)?;
+        let data = decode_submit_data_extrinsic(&extrinsic, call_index)
+            .map_err(to_non_retriable_da_error)?;
+        Ok(Some(data))
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
@@ -386,3 +425,42 @@ fn bridge_response_to_merkle_proof_input(
         leaf_index: bridge_api_response.leaf_index?,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use zksync_basic_types::secrets::APIKey;
+    use zksync_config::configs::da_client::avail::AvailGasRelayConfig;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn fetching_blob_in_gas_relay_mode_requires_node_url() {
+        let config = AvailConfig {
+            bridge_api_url: "http://localhost:3000".to_owned(),
+            timeout: Duration::from_secs(1),
+            config: AvailClientConfig::GasRelay(AvailGasRelayConfig {
+                gas_relay_api_url: "http://localhost:3001".to_owned(),
+                max_retries: 1,
+                retrieval_node_url: None,
+            }),
+        };
+        let secrets = AvailSecrets {
+            seed_phrase: None,
+            gas_relay_api_key: Some(APIKey(String::from("test").into())),
+        };
+        let client = AvailClient::new(config, secrets, SLChainId(1))
+            .await
+            .unwrap();
+
+        let err = client
+            .get_blob(&format!("{:x}:1", H256::zero()))
+            .await
+            .unwrap_err();
+        assert!(!err.is_retriable);
+        assert!(err.error.to_string().contains("not configured"), "{err:?}");
+        let err = client.get_blob("invalid").await.unwrap_err();
+        assert!(!err.is_retriable);
+    }
+}
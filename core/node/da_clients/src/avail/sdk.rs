@@ -91,12 +91,8 @@ impl RawAvailClient {
         Ok(hex::encode(&ext))
     }
 
-    /// Returns an encoded call data
-    async fn get_encoded_call(
-        &self,
-        client: &Client,
-        data: Vec<u8>,
-    ) -> anyhow::Result<Vec<u8>, anyhow::Error> {
+    /// Queries a node for the runtime metadata
+    async fn fetch_metadata(client: &Client) -> anyhow::Result<subxt_metadata::Metadata> {
         let resp: serde_json::Value = client.request("state_getMetadata", rpc_params![]).await?;
 
         let resp = resp
@@ -108,7 +104,28 @@ impl RawAvailClient {
             resp.strip_prefix("0x")
                 .ok_or_else(|| anyhow::anyhow!("Metadata doesn't have 0x prefix"))?,
         )?;
-        let meta = subxt_metadata::Metadata::decode(&mut &metadata_bytes[..])?;
+        Ok(subxt_metadata::Metadata::decode(&mut &metadata_bytes[..])?)
+    }
+
+    /// Returns the pallet and call indices of the `DataAvailability.submit_data` call
+    pub(crate) async fn fetch_submit_data_call_index(client: &Client) -> anyhow::Result<(u8, u8)> {
+        let meta = Self::fetch_metadata(client).await?;
+        let pallet = meta
+            .pallet_by_name("DataAvailability")
+            .ok_or_else(|| anyhow::anyhow!("DataAvailability pallet not found"))?;
+        let call = pallet
+            .call_variant_by_name("submit_data")
+            .ok_or_else(|| anyhow::anyhow!("submit_data call not found"))?;
+        Ok((pallet.index(), call.index))
+    }
+
+    /// Returns an encoded call data
+    async fn get_encoded_call(
+        &self,
+        client: &Client,
+        data: Vec<u8>,
+    ) -> anyhow::Result<Vec<u8>, anyhow::Error> {
+        let meta = Self::fetch_metadata(client).await?;
 
         let pallet = meta
             .pallet_by_name("DataAvailability")
@@ -372,6 +389,43 @@ impl RawAvailClient {
         Ok(None)
     }
 
+    /// Returns the encoded extrinsic with the specified index in the block with the specified hash.
+    /// Returns `None` if the block or the extrinsic is not found
+    pub(crate) async fn fetch_extrinsic(
+        client: &Client,
+        block_hash: &str,
+        extrinsic_index: usize,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        let block_hash = format!("0x{}", block_hash.strip_prefix("0x").unwrap_or(block_hash));
+        let block_result: serde_json::Value = client
+            .request("chain_getBlock", rpc_params![block_hash])
+            .await
+            .context("Error calling chain_getBlock RPC")?;
+        if block_result.is_null() {
+            return Ok(None);
+        }
+
+        let extrinsics = block_result
+            .get("block")
+            .ok_or_else(|| anyhow::anyhow!("Invalid block"))?
+            .get("extrinsics")
+            .ok_or_else(|| anyhow::anyhow!("No field named extrinsics in block"))?
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Extrinsics field is not an array"))?;
+        let Some(ext) = extrinsics.get(extrinsic_index) else {
+            return Ok(None);
+        };
+
+        let ext = ext
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Extrinsic is not a string"))?
+            .strip_prefix("0x")
+            .ok_or_else(|| anyhow::anyhow!("Extrinsic doesn't have 0x prefix"))?;
+        Ok(Some(
+            hex::decode(ext).context("Failed to decode extrinsic")?,
+        ))
+    }
+
     /// Finds the extrinsic with a certain hash in the array of extrinsics
     async fn find_ext_in_array(
         extrinsics: &[serde_json::Value],
@@ -410,6 +464,69 @@ impl RawAvailClient {
     }
 }
 
+/// Extracts the submitted data from an encoded signed `DataAvailability.submit_data` extrinsic.
+/// This is the inverse of [`RawAvailClient::build_extrinsic()`], with the call identified by its
+/// pallet and call indices.
+pub(crate) fn decode_submit_data_extrinsic(
+    mut bytes: &[u8],
+    submit_data_call_index: (u8, u8),
+) -> anyhow::Result<Vec<u8>> {
+    let input = &mut bytes;
+    let Compact(len) = Compact::<u32>::decode(input).context("extrinsic length")?;
+    anyhow::ensure!(
+        usize::try_from(len)? == input.len(),
+        "Extrinsic length mismatch: expected {len}, got {}",
+        input.len()
+    );
+
+    let version = u8::decode(input).context("extrinsic version")?;
+    anyhow::ensure!(
+        version == 0b10000000 + PROTOCOL_VERSION,
+        "Unexpected extrinsic version: {version:#x}"
+    );
+
+    // sender
+    let address_kind = u8::decode(input).context("sender kind")?;
+    anyhow::ensure!(address_kind == 0, "Unsupported sender kind: {address_kind}");
+    <[u8; 32]>::decode(input).context("sender")?;
+
+    // signature
+    match u8::decode(input).context("signature kind")? {
+        0 | 1 => {
+            // Ed25519 / Sr25519 signature
+            <[u8; 64]>::decode(input).context("signature")?;
+        }
+        2 => {
+            // ECDSA signature
+            <[u8; 65]>::decode(input).context("signature")?;
+        }
+        kind => bail!("Unsupported signature kind: {kind}"),
+    }
+
+    // extra params: era, nonce, tip and app ID
+    let era = u8::decode(input).context("era")?;
+    if era != 0 {
+        // mortal era is encoded in 2 bytes
+        u8::decode(input).context("era")?;
+    }
+    Compact::<u64>::decode(input).context("nonce")?;
+    Compact::<u128>::decode(input).context("tip")?;
+    Compact::<u32>::decode(input).context("app ID")?;
+
+    // call data
+    let call_index = (
+        u8::decode(input).context("pallet index")?,
+        u8::decode(input).context("call index")?,
+    );
+    anyhow::ensure!(
+        call_index == submit_data_call_index,
+        "Extrinsic is not a submit_data call: {call_index:?}"
+    );
+    let data = Vec::<u8>::decode(input).context("submitted data")?;
+    anyhow::ensure!(input.is_empty(), "Extrinsic has trailing bytes");
+    Ok(data)
+}
+
 fn blake2<const N: usize>(data: Vec<u8>) -> [u8; N] {
     blake2b_simd::Params::new()
         .hash_length(N)
@@ -584,3 +701,39 @@ impl GasRelayClient {
 fn is_empty_json(bytes: &[u8]) -> bool {
     bytes.is_empty() || bytes == b"{}"
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SEED_PHRASE: &str =
+        "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+    #[tokio::test]
+    async fn decoding_submit_data_extrinsic() {
+        let client = RawAvailClient::new(1, TEST_SEED_PHRASE, 5).await.unwrap();
+        let data = b"pubdata".repeat(100);
+        let call_index = (29, 1);
+
+        let mut call_data = vec![call_index.0, call_index.1];
+        SubmitData {
+            data: BoundedVec(data.clone()),
+        }
+        .encode_to(&mut call_data);
+        let mut extra_params = vec![0]; // immortal era
+        Compact(42_u64).encode_to(&mut extra_params);
+        Compact(0_u128).encode_to(&mut extra_params);
+        Compact(1_u32).encode_to(&mut extra_params);
+        let signature = client.get_signature(&call_data, &extra_params, &[]);
+        let extrinsic = client.get_submittable_extrinsic(signature, &extra_params, &call_data);
+
+        let decoded = decode_submit_data_extrinsic(&extrinsic, call_index).unwrap();
+        assert_eq!(decoded, data);
+
+        let err = decode_submit_data_extrinsic(&extrinsic, (29, 0)).unwrap_err();
+        assert!(err.to_string().contains("not a submit_data call"), "{err}");
+        let err = decode_submit_data_extrinsic(&extrinsic[..extrinsic.len() - 1], call_index)
+            .unwrap_err();
+        assert!(err.to_string().contains("length mismatch"), "{err}");
+    }
+}
@@ -18,7 +18,7 @@ use zksync_da_client::{
 
 use crate::{
     celestia::sdk::{BlobTxHash, RawCelestiaClient},
-    utils::to_non_retriable_da_error,
+    utils::{to_non_retriable_da_error, to_retriable_da_error},
 };
 
 /// An implementation of the `DataAvailabilityClient` trait that interacts with the Avail network.
@@ -44,6 +44,12 @@ impl CelestiaClient {
             client: Arc::new(client),
         })
     }
+
+    fn namespace(&self) -> Result<Namespace, DAError> {
+        let namespace_bytes =
+            hex::decode(&self.config.namespace).map_err(to_non_retriable_da_error)?;
+        Namespace::new_v0(namespace_bytes.as_slice()).map_err(to_non_retriable_da_error)
+    }
 }
 #[derive(Serialize, Deserialize)]
 pub struct BlobId {
@@ -58,10 +64,7 @@ impl DataAvailabilityClient for CelestiaClient {
         _: u32, // batch number
         data: Vec<u8>,
     ) -> Result<DispatchResponse, DAError> {
-        let namespace_bytes =
-            hex::decode(&self.config.namespace).map_err(to_non_retriable_da_error)?;
-        let namespace =
-            Namespace::new_v0(namespace_bytes.as_slice()).map_err(to_non_retriable_da_error)?;
+        let namespace = self.namespace()?;
         let blob = Blob::new(namespace, data).map_err(to_non_retriable_da_error)?;
 
         let commitment = blob.commitment;
@@ -101,6 +104,22 @@ impl DataAvailabilityClient for CelestiaClient {
         Ok(Some(InclusionData { data: vec![] }))
     }
 
+    async fn get_blob(&self, blob_id: &str) -> Result<Option<Vec<u8>>, DAError> {
+        let blob_id_bytes = hex::decode(blob_id).map_err(to_non_retriable_da_error)?;
+        let blob_id: BlobId =
+            bincode::deserialize(&blob_id_bytes).map_err(to_non_retriable_da_error)?;
+
+        let blobs = self
+            .client
+            .get_blobs(blob_id.height, self.namespace()?)
+            .await
+            .map_err(to_retriable_da_error)?;
+        Ok(blobs
+            .into_iter()
+            .find(|blob| blob.commitment == blob_id.commitment)
+            .map(|blob| blob.data))
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
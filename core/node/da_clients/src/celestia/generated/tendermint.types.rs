@@ -49,3 +49,22 @@ impl ::prost::Name for BlobTx {
         "/tendermint.types.BlobTx".into()
     }
 }
+/// Data contains the set of transactions included in the block
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Data {
+    /// Txs that will be applied by state @ block.Height+1.
+    /// NOTE: not all txs here are valid.  We're just agreeing on the order first.
+    /// This means that block.AppHash does not include these txs.
+    #[prost(bytes = "bytes", repeated, tag = "1")]
+    pub txs: ::prost::alloc::vec::Vec<::prost::bytes::Bytes>,
+}
+impl ::prost::Name for Data {
+    const NAME: &'static str = "Data";
+    const PACKAGE: &'static str = "tendermint.types";
+    fn full_name() -> ::prost::alloc::string::String {
+        "tendermint.types.Data".into()
+    }
+    fn type_url() -> ::prost::alloc::string::String {
+        "/tendermint.types.Data".into()
+    }
+}
@@ -28,6 +28,10 @@ pub mod cosmos {
         pub mod query {
             include!("generated/cosmos.base.query.v1beta1.rs");
         }
+
+        pub mod tendermint {
+            include!("generated/cosmos.base.tendermint.v1beta1.rs");
+        }
     }
 
     pub mod bank {
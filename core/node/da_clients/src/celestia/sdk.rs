@@ -4,7 +4,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use celestia_types::Blob;
+use celestia_types::{nmt::Namespace, Blob};
 use prost::{bytes::Bytes, Message, Name};
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
 use sha2::Digest;
@@ -26,6 +26,9 @@ use super::{
                 service_client::ServiceClient as MinGasPriceClient,
                 ConfigRequest as MinGasPriceRequest,
             },
+            tendermint::{
+                service_client::ServiceClient as TendermintClient, GetBlockByHeightRequest,
+            },
             v1beta1::Coin,
         },
         crypto::secp256k1 as ec_proto,
@@ -43,6 +46,8 @@ const UNITS_SUFFIX: &str = "utia";
 pub const ADDRESS_LENGTH: usize = 20;
 const ACCOUNT_ADDRESS_PREFIX: bech32::Hrp = bech32::Hrp::parse_unchecked("celestia");
 const BLOB_TX_TYPE_ID: &str = "BLOB";
+/// Max size of a gRPC message with a block; blocks can be larger than the default 4 MB limit.
+const MAX_BLOCK_MESSAGE_SIZE: usize = 128 * 1024 * 1024;
 
 #[derive(Clone)]
 pub(crate) struct RawCelestiaClient {
@@ -124,6 +129,57 @@ impl RawCelestiaClient {
         Ok(height)
     }
 
+    /// Fetches blobs in the specified namespace from the block at the specified height.
+    pub(super) async fn get_blobs(
+        &self,
+        height: u64,
+        namespace: Namespace,
+    ) -> anyhow::Result<Vec<Blob>> {
+        let mut client = TendermintClient::new(self.grpc_channel.clone())
+            .max_decoding_message_size(MAX_BLOCK_MESSAGE_SIZE);
+        let request = GetBlockByHeightRequest {
+            height: i64::try_from(height)?,
+        };
+        let response = client
+            .get_block_by_height(request)
+            .await
+            .map_err(|status| {
+                anyhow::anyhow!(
+                    "failed to get block at height {}, code: {}, message: {}",
+                    height,
+                    status.code(),
+                    status.message()
+                )
+            })?;
+
+        let txs = response
+            .into_inner()
+            .sdk_block
+            .and_then(|block| block.data)
+            .map(|data| data.txs)
+            .unwrap_or_default();
+        let mut blobs = vec![];
+        for tx in txs {
+            // Ordinary transactions are not wrapped into `BlobTx`
+            let Ok(blob_tx) = BlobTx::decode(tx) else {
+                continue;
+            };
+            if blob_tx.type_id != BLOB_TX_TYPE_ID {
+                continue;
+            }
+
+            for blob in blob_tx.blobs {
+                if blob.namespace_id.as_ref() != namespace.id()
+                    || blob.namespace_version != u32::from(namespace.version())
+                {
+                    continue;
+                }
+                blobs.push(Blob::new(namespace, blob.data.to_vec())?);
+            }
+        }
+        Ok(blobs)
+    }
+
     /// Fetches the gas cost per byte for blobs from the node.
     async fn get_gas_per_blob_byte(&self) -> anyhow::Result<u32> {
         let mut blob_query_client = BlobQueryClient::new(self.grpc_channel.clone());
@@ -1,14 +1,18 @@
-use std::str::FromStr;
+use std::{fmt, str::FromStr, sync::Arc, time::Duration};
 
+use anyhow::Context as _;
 use chrono::{DateTime, Utc};
 use rust_eigenda_signers::signers::private_key::Signer;
 use rust_eigenda_v2_client::{
     core::BlobKey,
     payload_disperser::{PayloadDisperser, PayloadDisperserConfig},
+    relay_client::{RelayClient, RelayClientConfig},
+    relay_payload_retriever::{RelayPayloadRetriever, RelayPayloadRetrieverConfig, SRSConfig},
     utils::SecretUrl as SecretUrlV2,
 };
 use rust_eigenda_v2_common::{Payload, PayloadForm};
 use subxt_signer::ExposeSecret;
+use tokio::sync::Mutex;
 use url::Url;
 use zksync_config::{configs::da_client::eigen::EigenSecrets, EigenConfig};
 use zksync_da_client::{
@@ -18,10 +22,31 @@ use zksync_da_client::{
 
 use crate::utils::{to_non_retriable_da_error, to_retriable_da_error};
 
+/// Order of the EigenDA structured reference string (SRS).
+const SRS_ORDER: u32 = 1 << 28;
+/// Number of bytes encoded by a single SRS point (i.e., a field element of a blob).
+const BYTES_PER_SRS_POINT: usize = 32;
+/// Maximum size of gRPC messages received from relays.
+const RELAY_MAX_GRPC_MESSAGE_SIZE: usize = 100 * 1024 * 1024;
+/// Timeout for fetching a single payload from relays.
+const RETRIEVAL_TIMEOUT: Duration = Duration::from_secs(60);
+
 // We can't implement DataAvailabilityClient for an outside struct, so it is needed to defined this intermediate struct
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct EigenDAClient {
     client: PayloadDisperser,
+    /// Fetches dispersed payloads from EigenDA relays; `None` if relays are not configured.
+    payload_retriever: Option<Arc<Mutex<RelayPayloadRetriever>>>,
+}
+
+impl fmt::Debug for EigenDAClient {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("EigenDAClient")
+            .field("client", &self.client)
+            .field("fetches_blobs", &self.payload_retriever.is_some())
+            .finish()
+    }
 }
 
 impl EigenDAClient {
@@ -39,7 +64,7 @@ impl EigenDAClient {
             polynomial_form: PayloadForm::Coeff,
             blob_version: config.blob_version,
             cert_verifier_router_address: config.cert_verifier_router_addr,
-            eth_rpc_url: SecretUrlV2::new(url),
+            eth_rpc_url: SecretUrlV2::new(url.clone()),
             disperser_rpc: config.disperser_rpc,
             use_secure_grpc_flag: true,
             operator_state_retriever_addr: config.operator_state_retriever_addr,
@@ -50,10 +75,74 @@ impl EigenDAClient {
         let signer = Signer::new(private_key);
         let client = PayloadDisperser::new(payload_disperser_config, signer).await?;
 
-        Ok(Self { client })
+        let payload_retriever = match (&config.relay_registry_addr, &config.srs_points_path) {
+            (Some(relay_registry_addr), Some(srs_points_path)) => {
+                let relay_client_config = RelayClientConfig {
+                    max_grpc_message_size: RELAY_MAX_GRPC_MESSAGE_SIZE,
+                    relay_clients_keys: config.relay_client_keys.clone(),
+                    relay_registry_address: relay_registry_addr
+                        .parse()
+                        .context("invalid relay registry address")?,
+                    eth_rpc_url: SecretUrlV2::new(url),
+                };
+                let relay_client =
+                    RelayClient::new(relay_client_config, Signer::new(private_key)).await?;
+                let srs_config = SRSConfig {
+                    source_path: srs_points_path
+                        .to_str()
+                        .context("SRS points path is not valid UTF-8")?
+                        .to_owned(),
+                    order: SRS_ORDER,
+                    points_to_load: Self::srs_points_to_load(),
+                };
+                let retriever_config = RelayPayloadRetrieverConfig {
+                    payload_form: PayloadForm::Coeff,
+                    retrieval_timeout_secs: RETRIEVAL_TIMEOUT,
+                };
+                let retriever =
+                    RelayPayloadRetriever::new(retriever_config, srs_config, relay_client)?;
+                Some(Arc::new(Mutex::new(retriever)))
+            }
+            _ => {
+                tracing::info!(
+                    "EigenDA relay registry or SRS points are not configured; fetching blobs is disabled"
+                );
+                None
+            }
+        };
+
+        Ok(Self {
+            client,
+            payload_retriever,
+        })
+    }
+
+    /// Returns the number of SRS points sufficient to verify a blob of the maximum size.
+    fn srs_points_to_load() -> u32 {
+        PayloadDisperser::<Signer>::blob_size_limit().map_or(SRS_ORDER, |limit| {
+            let points = limit.div_ceil(BYTES_PER_SRS_POINT);
+            u32::try_from(points).map_or(SRS_ORDER, |points| points.min(SRS_ORDER))
+        })
     }
 }
 
+/// Parses a blob key from the hex-encoded ID returned by [`EigenDAClient::dispatch_blob()`].
+fn parse_blob_key(blob_id: &str) -> Result<BlobKey, DAError> {
+    let bytes = hex::decode(blob_id).map_err(|err| {
+        to_non_retriable_da_error(anyhow::anyhow!(
+            "Failed to decode blob id: {}: {}",
+            blob_id,
+            err
+        ))
+    })?;
+    let bytes: [u8; 32] = bytes.try_into().map_err(|_| {
+        to_non_retriable_da_error(anyhow::anyhow!(
+            "Failed to convert bytes to a 32-byte array"
+        ))
+    })?;
+    Ok(BlobKey::from_bytes(bytes))
+}
+
 #[async_trait::async_trait]
 impl DataAvailabilityClient for EigenDAClient {
     async fn dispatch_blob(
@@ -76,17 +165,7 @@ impl DataAvailabilityClient for EigenDAClient {
         dispatch_request_id: String,
         _: DateTime<Utc>,
     ) -> Result<Option<FinalityResponse>, DAError> {
-        let bytes = hex::decode(dispatch_request_id.clone()).map_err(|_| {
-            to_non_retriable_da_error(anyhow::anyhow!(
-                "Failed to decode blob id: {}",
-                dispatch_request_id
-            ))
-        })?;
-        let blob_key = BlobKey::from_bytes(bytes.try_into().map_err(|_| {
-            to_non_retriable_da_error(anyhow::anyhow!(
-                "Failed to convert bytes to a 32-byte array"
-            ))
-        })?);
+        let blob_key = parse_blob_key(&dispatch_request_id)?;
         let eigenda_cert = self
             .client
             .get_cert(&blob_key)
@@ -102,18 +181,7 @@ impl DataAvailabilityClient for EigenDAClient {
     }
 
     async fn get_inclusion_data(&self, blob_id: &str) -> Result<Option<InclusionData>, DAError> {
-        let bytes = hex::decode(blob_id).map_err(|err| {
-            to_non_retriable_da_error(anyhow::anyhow!(
-                "Failed to decode blob id: {}: {}",
-                blob_id,
-                err
-            ))
-        })?;
-        let blob_key = BlobKey::from_bytes(bytes.try_into().map_err(|_| {
-            to_non_retriable_da_error(anyhow::anyhow!(
-                "Failed to convert bytes to a 32-byte array"
-            ))
-        })?);
+        let blob_key = parse_blob_key(blob_id)?;
         let eigenda_cert = self
             .client
             .get_cert(&blob_key)
@@ -133,6 +201,30 @@ impl DataAvailabilityClient for EigenDAClient {
         }
     }
 
+    async fn get_blob(&self, blob_id: &str) -> Result<Option<Vec<u8>>, DAError> {
+        let Some(payload_retriever) = &self.payload_retriever else {
+            return Err(to_non_retriable_da_error(anyhow::anyhow!(
+                "Fetching blobs requires EigenDA relay registry and SRS points to be configured"
+            )));
+        };
+        let blob_key = parse_blob_key(blob_id)?;
+        let Some(eigenda_cert) = self
+            .client
+            .get_cert(&blob_key)
+            .await
+            .map_err(to_retriable_da_error)?
+        else {
+            return Ok(None);
+        };
+        let payload = payload_retriever
+            .lock()
+            .await
+            .get_payload(eigenda_cert)
+            .await
+            .map_err(to_retriable_da_error)?;
+        Ok(Some(payload.serialize()))
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
@@ -149,3 +241,29 @@ impl DataAvailabilityClient for EigenDAClient {
         Ok(0) // TODO fetch from API when payments are enabled in Eigen (PE-305)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_blob_key() {
+        let blob_key = BlobKey::from_bytes([0xab; 32]);
+        let blob_id = blob_key.to_hex();
+        assert_eq!(parse_blob_key(&blob_id).unwrap().to_hex(), blob_id);
+
+        let err = parse_blob_key("not a hex string").unwrap_err();
+        assert!(!err.is_retriable);
+        let err = parse_blob_key(&hex::encode([0xab; 31])).unwrap_err();
+        assert!(!err.is_retriable);
+    }
+
+    #[test]
+    fn srs_points_cover_max_blob_size() {
+        let points = EigenDAClient::srs_points_to_load();
+        assert!(points > 0 && points <= SRS_ORDER);
+        if let Some(limit) = PayloadDisperser::<Signer>::blob_size_limit() {
+            assert!(points as usize * BYTES_PER_SRS_POINT >= limit);
+        }
+    }
+}
@@ -27,6 +27,11 @@ impl DataAvailabilityClient for NoDAClient {
         Ok(Some(InclusionData::default()))
     }
 
+    async fn get_blob(&self, _: &str) -> Result<Option<Vec<u8>>, DAError> {
+        // Pubdata isn't stored anywhere, so there's nothing to fetch.
+        Ok(None)
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
@@ -12,7 +12,7 @@ use zksync_da_client::{
     DataAvailabilityClient,
 };
 use zksync_object_store::{
-    Bucket, ObjectStore, ObjectStoreFactory, StoredObject, _reexports::BoxedError,
+    Bucket, ObjectStore, ObjectStoreError, ObjectStoreFactory, StoredObject, _reexports::BoxedError,
 };
 use zksync_types::L1BatchNumber;
 
@@ -30,6 +30,26 @@ impl ObjectStoreDAClient {
                 .await?,
         })
     }
+
+    async fn get_pubdata(&self, key: &str) -> Result<Option<StorablePubdata>, DAError> {
+        let key_u32 = key.parse::<u32>().map_err(|err| DAError {
+            error: anyhow::Error::from(err).context(format!("Failed to parse blob key: {}", key)),
+            is_retriable: false,
+        })?;
+
+        match self
+            .object_store
+            .get::<StorablePubdata>(L1BatchNumber(key_u32))
+            .await
+        {
+            Ok(pubdata) => Ok(Some(pubdata)),
+            Err(ObjectStoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(DAError {
+                is_retriable: err.is_retriable(),
+                error: anyhow::Error::from(err),
+            }),
+        }
+    }
 }
 
 #[async_trait]
@@ -66,24 +86,8 @@ impl DataAvailabilityClient for ObjectStoreDAClient {
     }
 
     async fn get_inclusion_data(&self, key: &str) -> Result<Option<InclusionData>, DAError> {
-        let key_u32 = key.parse::<u32>().map_err(|err| DAError {
-            error: anyhow::Error::from(err).context(format!("Failed to parse blob key: {}", key)),
-            is_retriable: false,
-        })?;
-
-        if let Err(err) = self
-            .object_store
-            .get::<StorablePubdata>(L1BatchNumber(key_u32))
-            .await
-        {
-            if let zksync_object_store::ObjectStoreError::KeyNotFound(_) = err {
-                return Ok(None);
-            }
-
-            return Err(DAError {
-                is_retriable: err.is_retriable(),
-                error: anyhow::Error::from(err),
-            });
+        if self.get_pubdata(key).await?.is_none() {
+            return Ok(None);
         }
 
         // Using default here because we don't get any inclusion data from object store, thus
@@ -91,6 +95,10 @@ impl DataAvailabilityClient for ObjectStoreDAClient {
         return Ok(Some(InclusionData::default()));
     }
 
+    async fn get_blob(&self, key: &str) -> Result<Option<Vec<u8>>, DAError> {
+        Ok(self.get_pubdata(key).await?.map(|pubdata| pubdata.data))
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
@@ -144,10 +152,11 @@ impl StoredObject for StorablePubdata {
 #[cfg(test)]
 mod tests {
     use tokio::fs;
+    use zksync_da_client::DataAvailabilityClient;
     use zksync_object_store::{MockObjectStore, StoredObject};
     use zksync_types::L1BatchNumber;
 
-    use super::StorablePubdata;
+    use super::{ObjectStoreDAClient, StorablePubdata};
 
     #[tokio::test]
     async fn test_storable_pubdata_deserialization() {
@@ -182,4 +191,21 @@ mod tests {
 
         assert_eq!(data, resp.data);
     }
+
+    #[tokio::test]
+    async fn dispatched_blob_can_be_fetched() {
+        let client = ObjectStoreDAClient {
+            object_store: MockObjectStore::arc(),
+        };
+        let data = vec![1, 2, 3, 4, 5, 6, 123, 255, 0, 0];
+
+        let response = client.dispatch_blob(123, data.clone()).await.unwrap();
+        let blob = client.get_blob(&response.request_id).await.unwrap();
+        assert_eq!(blob, Some(data));
+
+        let missing_blob = client.get_blob("124").await.unwrap();
+        assert_eq!(missing_blob, None);
+        let err = client.get_blob("not a number").await.unwrap_err();
+        assert!(!err.is_retriable());
+    }
 }
@@ -67,6 +67,9 @@ impl TestContext {
                 },
                 max_retries: 10,
                 local_mirror_path: None,
+                compress_objects: false,
+                encryption_key_path: None,
+                encrypted_buckets: vec![],
             },
             event_poll_interval: Duration::from_secs(1),
             request_sending_interval: Duration::from_secs(1),
@@ -1,6 +1,11 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
-use chrono::Utc;
 use zksync_config::configs::eth_sender::{PrecommitParams, ProofSendingMode, SenderConfig};
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{blocks_dal::TxForPrecommit, Connection, ConnectionPool, Core, CoreDal};
@@ -33,6 +38,30 @@ use crate::{
     EthSenderError,
 };
 
+/// Source of the current time for [`Aggregator`] and its publish criteria.
+#[derive(Debug, Clone, Default)]
+pub enum AggregatorClock {
+    /// Wall-clock time.
+    #[default]
+    System,
+    /// Externally controlled time in milliseconds since UNIX epoch. Used when replaying aggregation offline;
+    /// L1 batches sealed after the current time are treated as not sealed yet.
+    Simulated(Arc<AtomicU64>),
+}
+
+impl AggregatorClock {
+    pub(crate) fn now_ms(&self) -> u64 {
+        match self {
+            Self::System => unix_timestamp_ms(),
+            Self::Simulated(now_ms) => now_ms.load(Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) fn now_secs(&self) -> u64 {
+        self.now_ms() / 1_000
+    }
+}
+
 #[derive(Debug)]
 pub struct Aggregator {
     commit_criteria: Vec<Box<dyn L1BatchPublishCriterion>>,
@@ -51,6 +80,7 @@ pub struct Aggregator {
     commitment_mode: L1BatchCommitmentMode,
     priority_merkle_tree: Option<MiniMerkleTree<L1Tx>>,
     settlement_layer: SettlementLayer,
+    clock: AggregatorClock,
 }
 
 /// Denotes whether there are any restrictions on sending either
@@ -135,6 +165,27 @@ impl Aggregator {
         commitment_mode: L1BatchCommitmentMode,
         pool: ConnectionPool<Core>,
         settlement_layer: SettlementLayer,
+    ) -> anyhow::Result<Self> {
+        Self::with_clock(
+            config,
+            blob_store,
+            custom_commit_sender_addr,
+            commitment_mode,
+            pool,
+            settlement_layer,
+            AggregatorClock::System,
+        )
+        .await
+    }
+
+    pub(crate) async fn with_clock(
+        config: SenderConfig,
+        blob_store: Arc<dyn ObjectStore>,
+        custom_commit_sender_addr: bool,
+        commitment_mode: L1BatchCommitmentMode,
+        pool: ConnectionPool<Core>,
+        settlement_layer: SettlementLayer,
+        clock: AggregatorClock,
     ) -> anyhow::Result<Self> {
         let operate_4844_mode: bool = custom_commit_sender_addr && !settlement_layer.is_gateway();
 
@@ -164,6 +215,7 @@ impl Aggregator {
                     op: L1BatchAggregatedActionType::Execute,
                     deadline: config.aggregated_block_execute_deadline,
                     max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
+                    clock: clock.clone(),
                 }),
                 Box::from(L1GasCriterion::new(
                     config.max_aggregated_tx_gas,
@@ -185,6 +237,7 @@ impl Aggregator {
                         op: L1BatchAggregatedActionType::Commit,
                         deadline: config.aggregated_block_commit_deadline,
                         max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
+                        clock: clock.clone(),
                     }),
                     Box::from(L1GasCriterion::new(
                         config.max_aggregated_tx_gas,
@@ -220,6 +273,7 @@ impl Aggregator {
             priority_merkle_tree: None,
             pool,
             settlement_layer,
+            clock,
         })
     }
 
@@ -235,12 +289,16 @@ impl Aggregator {
         precommit_params: Option<&PrecommitParams>,
         execution_delay: Duration,
     ) -> Result<Option<AggregatedOperation>, EthSenderError> {
-        let Some(last_sealed_l1_batch_number) = storage
-            .blocks_dal()
-            .get_sealed_l1_batch_number()
-            .await
-            .unwrap()
-        else {
+        let last_sealed_l1_batch_number = match &self.clock {
+            AggregatorClock::System => storage.blocks_dal().get_sealed_l1_batch_number().await,
+            AggregatorClock::Simulated(_) => {
+                storage
+                    .blocks_dal()
+                    .get_last_sealed_l1_batch_number_at_timestamp(self.clock.now_secs())
+                    .await
+            }
+        };
+        let Some(last_sealed_l1_batch_number) = last_sealed_l1_batch_number.unwrap() else {
             return Ok(None); // No L1 batches in Postgres; no operations are ready yet
         };
 
@@ -374,7 +432,7 @@ impl Aggregator {
                 &first_tx,
                 last_tx,
                 precommit_params,
-                Utc::now().timestamp(),
+                self.clock.now_secs() as i64,
             )
         {
             return Ok(None);
@@ -412,7 +470,7 @@ impl Aggregator {
         execution_delay: Duration,
     ) -> Result<Option<ExecuteBatches>, EthSenderError> {
         let mut max_l1_batch_timestamp_millis =
-            Some(unix_timestamp_ms() - execution_delay.as_millis() as u64);
+            Some(self.clock.now_ms() - execution_delay.as_millis() as u64);
 
         // Add safety margin for L1 block inclusion delays
         // On L1 time is discrete and in worst case if you send time at X,
@@ -878,6 +936,11 @@ async fn extract_ready_subrange(
     last_sealed_l1_batch: L1BatchNumber,
     is_gateway: bool,
 ) -> Option<Vec<L1BatchWithMetadata>> {
+    // Only has an effect when replaying with a simulated clock; otherwise, all unpublished L1 batches are sealed.
+    let unpublished_l1_batches: Vec<_> = unpublished_l1_batches
+        .into_iter()
+        .take_while(|l1_batch| l1_batch.header.number <= last_sealed_l1_batch)
+        .collect();
     let mut last_l1_batch: Option<L1BatchNumber> = None;
     for criterion in publish_criteria {
         let l1_batch_by_criterion = criterion
@@ -939,6 +1002,7 @@ fn ready_to_create_precommit_operation(
 
 #[cfg(test)]
 mod tests {
+    use chrono::Utc;
     use zksync_types::L2BlockNumber;
 
     use super::*;
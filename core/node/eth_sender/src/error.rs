@@ -12,6 +12,12 @@ pub enum EthSenderError {
     Parse(#[from] contract::Error),
     #[error("Max base fee exceeded")]
     ExceedMaxBaseFee,
+    #[error("Bumped {fee_type} {fee} for a resent transaction exceeds the cap {cap}")]
+    FeeBumpCapExceeded {
+        fee_type: &'static str,
+        fee: u64,
+        cap: u64,
+    },
     #[error("Dal error: {0}")]
     Dal(#[from] DalError),
 }
@@ -1,16 +1,15 @@
-use std::{
-    cmp::{max, min},
-    fmt,
-    sync::Arc,
-};
+use std::{cmp::min, fmt, sync::Arc};
 
-use zksync_eth_client::{ClientError, EnrichedClientError};
 use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_types::eth_sender::TxHistory;
 
-use crate::{abstract_l1_interface::OperatorType, EthSenderError};
+use crate::{
+    abstract_l1_interface::OperatorType,
+    fee_bumping::{FeeBumpRequest, FeeBumpingStrategies},
+    EthSenderError,
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub(crate) struct EthFees {
     pub(crate) base_fee_per_gas: u64,
     pub(crate) priority_fee_per_gas: u64,
@@ -19,12 +18,16 @@ pub(crate) struct EthFees {
 }
 
 pub(crate) trait EthFeesOracle: 'static + Sync + Send + fmt::Debug {
+    /// Calculates fees for a transaction with the specified successfully sent attempts, ordered from the latest one.
     fn calculate_fees(
         &self,
-        previous_sent_tx: &Option<TxHistory>,
+        sent_attempts: &[TxHistory],
         time_in_mempool_in_l1_blocks: u32,
         operator_type: OperatorType,
     ) -> Result<EthFees, EthSenderError>;
+
+    /// Returns the name of the strategy used to bump fees for resent transactions of the specified operator.
+    fn fee_bump_strategy(&self, operator_type: OperatorType) -> &'static str;
 }
 
 #[derive(Debug)]
@@ -33,6 +36,7 @@ pub(crate) struct GasAdjusterFeesOracle {
     pub max_acceptable_priority_fee_in_gwei: u64,
     pub time_in_mempool_in_l1_blocks_cap: u32,
     pub max_acceptable_base_fee_in_wei: u64,
+    pub fee_bumping: FeeBumpingStrategies,
 }
 
 impl GasAdjusterFeesOracle {
@@ -57,13 +61,40 @@ impl GasAdjusterFeesOracle {
         false
     }
 
+    /// Computes fees for resending a transaction using the fee bumping strategy configured for `operator_type`.
+    fn bump_fees(
+        &self,
+        operator_type: OperatorType,
+        request: FeeBumpRequest<'_>,
+    ) -> Result<EthFees, EthSenderError> {
+        let fees = self.fee_bumping.get(operator_type).bump_fees(&request)?;
+        if self.is_base_fee_exceeding_limit(fees.base_fee_per_gas) {
+            return Err(EthSenderError::ExceedMaxBaseFee);
+        }
+        // Unlike suggested fees, bumped priority fees exceeding the limit are expected for long-stuck transactions,
+        // so they stop resends instead of panicking.
+        if fees.priority_fee_per_gas > self.max_acceptable_priority_fee_in_gwei {
+            tracing::info!(
+                "Priority fee per gas {} for resending tx {} exceeds max acceptable {}, skipping resend",
+                fees.priority_fee_per_gas,
+                request.previous.eth_tx_id,
+                self.max_acceptable_priority_fee_in_gwei
+            );
+            return Err(EthSenderError::FeeBumpCapExceeded {
+                fee_type: "priority_fee_per_gas",
+                fee: fees.priority_fee_per_gas,
+                cap: self.max_acceptable_priority_fee_in_gwei,
+            });
+        }
+        Ok(fees)
+    }
+
     fn calculate_fees_with_blob_sidecar(
         &self,
-        previous_sent_tx: &Option<TxHistory>,
+        sent_attempts: &[TxHistory],
         time_in_mempool_in_l1_blocks: u32,
     ) -> Result<EthFees, EthSenderError> {
         const MIN_PRICE_BUMP_MULTIPLIER: f64 = 2.00;
-        const MIN_PRICE_BUMP_MULTIPLIER_U64: u64 = 2;
 
         // we cap it to not allow nearly infinite values when a tx is stuck for a long time
         let capped_time_in_mempool_in_l1_blocks = min(
@@ -71,68 +102,40 @@ impl GasAdjusterFeesOracle {
             self.time_in_mempool_in_l1_blocks_cap,
         );
 
-        let mut base_fee_per_gas = self
+        let base_fee_per_gas = self
             .gas_adjuster
             .get_blob_tx_base_fee(capped_time_in_mempool_in_l1_blocks);
         self.assert_fee_is_not_zero(base_fee_per_gas, "base");
         if self.is_base_fee_exceeding_limit(base_fee_per_gas) {
             return Err(EthSenderError::ExceedMaxBaseFee);
         }
-        let mut blob_base_fee_per_gas = self
+        let blob_base_fee_per_gas = self
             .gas_adjuster
             .get_blob_tx_blob_base_fee(capped_time_in_mempool_in_l1_blocks);
         self.assert_fee_is_not_zero(blob_base_fee_per_gas, "blob");
 
-        let mut priority_fee_per_gas = self.gas_adjuster.get_blob_tx_priority_fee();
-        if let Some(previous_sent_tx) = previous_sent_tx {
-            let blob_result = self.verify_base_fee_not_too_low_on_resend(
-                previous_sent_tx.id,
-                previous_sent_tx.blob_base_fee_per_gas.unwrap_or(0),
-                blob_base_fee_per_gas,
-                self.gas_adjuster.get_next_block_minimal_blob_base_fee(),
-                MIN_PRICE_BUMP_MULTIPLIER,
-                "blob_base_fee_per_gas",
-            );
-
-            let base_result = self.verify_base_fee_not_too_low_on_resend(
-                previous_sent_tx.id,
-                previous_sent_tx.base_fee_per_gas,
-                base_fee_per_gas,
-                self.gas_adjuster.get_next_block_minimal_base_fee(),
-                MIN_PRICE_BUMP_MULTIPLIER,
-                "base_fee_per_gas",
-            );
-
-            match (blob_result, base_result) {
-                (Ok(_), Ok(_)) => {}
-                (Err(err), Err(_)) => return Err(err),
-                // If we're ready to increase one of the fees then we force-increase another one.
-                (Ok(_), Err(_)) => {
-                    base_fee_per_gas =
-                        previous_sent_tx.base_fee_per_gas * MIN_PRICE_BUMP_MULTIPLIER_U64;
-                }
-                (Err(_), Ok(_)) => {
-                    blob_base_fee_per_gas = previous_sent_tx.blob_base_fee_per_gas.unwrap()
-                        * MIN_PRICE_BUMP_MULTIPLIER_U64;
-                }
-            }
-
-            priority_fee_per_gas = max(
-                priority_fee_per_gas,
-                previous_sent_tx.priority_fee_per_gas * MIN_PRICE_BUMP_MULTIPLIER_U64,
-            );
-        }
-        Ok(EthFees {
+        let suggested = EthFees {
             base_fee_per_gas,
-            priority_fee_per_gas,
+            priority_fee_per_gas: self.gas_adjuster.get_blob_tx_priority_fee(),
             blob_base_fee_per_gas: Some(blob_base_fee_per_gas),
             max_gas_per_pubdata_price: None,
-        })
+        };
+        let Some(request) = FeeBumpRequest::new(
+            suggested,
+            sent_attempts,
+            time_in_mempool_in_l1_blocks,
+            MIN_PRICE_BUMP_MULTIPLIER,
+            self.gas_adjuster.get_next_block_minimal_base_fee(),
+            Some(self.gas_adjuster.get_next_block_minimal_blob_base_fee()),
+        ) else {
+            return Ok(suggested);
+        };
+        self.bump_fees(OperatorType::Blob, request)
     }
 
     fn calculate_fees_no_blob_sidecar(
         &self,
-        previous_sent_tx: &Option<TxHistory>,
+        sent_attempts: &[TxHistory],
         time_in_mempool_in_l1_blocks: u32,
     ) -> Result<EthFees, EthSenderError> {
         const MIN_PRICE_BUMP_MULTIPLIER: f64 = 1.10;
@@ -150,45 +153,38 @@ impl GasAdjusterFeesOracle {
             return Err(EthSenderError::ExceedMaxBaseFee);
         }
 
-        let mut priority_fee_per_gas = self.gas_adjuster.get_priority_fee();
-
-        if let Some(previous_sent_tx) = previous_sent_tx {
-            self.verify_base_fee_not_too_low_on_resend(
-                previous_sent_tx.id,
-                previous_sent_tx.base_fee_per_gas,
-                base_fee_per_gas,
-                self.gas_adjuster.get_next_block_minimal_base_fee(),
-                MIN_PRICE_BUMP_MULTIPLIER,
-                "base_fee_per_gas",
-            )?;
-
-            priority_fee_per_gas = max(
-                priority_fee_per_gas,
-                (previous_sent_tx.priority_fee_per_gas as f64 * MIN_PRICE_BUMP_MULTIPLIER).ceil()
-                    as u64,
-            );
+        let mut fees = EthFees {
+            base_fee_per_gas,
+            priority_fee_per_gas: self.gas_adjuster.get_priority_fee(),
+            blob_base_fee_per_gas: None,
+            max_gas_per_pubdata_price: None,
+        };
+        let request = FeeBumpRequest::new(
+            fees,
+            sent_attempts,
+            time_in_mempool_in_l1_blocks,
+            MIN_PRICE_BUMP_MULTIPLIER,
+            self.gas_adjuster.get_next_block_minimal_base_fee(),
+            None,
+        );
+        if let Some(request) = request {
+            fees = self.bump_fees(OperatorType::NonBlob, request)?;
         }
 
         // Extra check to prevent sending transaction will extremely high priority fee.
-        if priority_fee_per_gas > self.max_acceptable_priority_fee_in_gwei {
+        if fees.priority_fee_per_gas > self.max_acceptable_priority_fee_in_gwei {
             panic!(
                 "Extremely high value of priority_fee_per_gas is suggested: {}, while max acceptable is {}",
-                priority_fee_per_gas,
+                fees.priority_fee_per_gas,
                 self.max_acceptable_priority_fee_in_gwei
             );
         }
-
-        Ok(EthFees {
-            base_fee_per_gas,
-            blob_base_fee_per_gas: None,
-            priority_fee_per_gas,
-            max_gas_per_pubdata_price: None,
-        })
+        Ok(fees)
     }
 
     fn calculate_fees_for_gateway_tx(
         &self,
-        previous_sent_tx: &Option<TxHistory>,
+        sent_attempts: &[TxHistory],
         time_in_mempool_in_l1_blocks: u32,
     ) -> Result<EthFees, EthSenderError> {
         const MIN_PRICE_BUMP_MULTIPLIER: f64 = 1.10;
@@ -206,96 +202,199 @@ impl GasAdjusterFeesOracle {
             return Err(EthSenderError::ExceedMaxBaseFee);
         }
 
-        let mut gas_per_pubdata = self
+        let gas_per_pubdata = self
             .gas_adjuster
             .get_gateway_price_per_pubdata(capped_time_in_mempool_in_l1_blocks);
-
-        if let Some(previous_sent_tx) = previous_sent_tx {
-            self.verify_base_fee_not_too_low_on_resend(
-                previous_sent_tx.id,
-                previous_sent_tx.base_fee_per_gas,
-                base_fee_per_gas,
-                self.gas_adjuster.get_next_block_minimal_base_fee(),
-                MIN_PRICE_BUMP_MULTIPLIER,
-                "base_fee_per_gas",
-            )?;
-
-            // Increase `gas_per_pubdata_fee`.
-            gas_per_pubdata =
-                if let Some(prev_gas_per_pubdata) = previous_sent_tx.max_gas_per_pubdata {
-                    max(
-                        gas_per_pubdata,
-                        (prev_gas_per_pubdata as f64 * MIN_PRICE_BUMP_MULTIPLIER).ceil() as u64,
-                    )
-                } else {
-                    gas_per_pubdata
-                };
-        }
-
-        Ok(EthFees {
+        let suggested = EthFees {
             base_fee_per_gas,
             blob_base_fee_per_gas: None,
             // We ignore priority fee for gateway
             priority_fee_per_gas: 0,
             max_gas_per_pubdata_price: Some(gas_per_pubdata),
-        })
-    }
-
-    fn verify_base_fee_not_too_low_on_resend(
-        &self,
-        tx_id: u32,
-        previous_fee: u64,
-        fee_to_use: u64,
-        next_block_minimal_fee: u64,
-        min_price_bump_multiplier: f64,
-        fee_type: &str,
-    ) -> Result<(), EthSenderError> {
-        let fee_to_use = fee_to_use as f64;
-        if fee_to_use < (next_block_minimal_fee as f64)
-            || fee_to_use < (previous_fee as f64 * min_price_bump_multiplier).ceil()
-        {
-            // If the fee is lower than the previous used one multiplied by the required factor
-            // or is lower than the minimal possible value for the next block, sending is skipped.
-            tracing::info!(
-                "{fee_type} too low for resend detected for tx {}, \
-                 suggested fee {:?}, \
-                 previous_fee {:?}, \
-                 next_block_minimal_fee {:?}, \
-                 min_price_bump_multiplier {:?}",
-                tx_id,
-                fee_to_use,
-                previous_fee,
-                next_block_minimal_fee,
-                min_price_bump_multiplier
-            );
-            let err = ClientError::Custom(format!("{fee_type} is too low"));
-            let err = EnrichedClientError::new(err, "verify_base_fee_not_too_low_on_resend")
-                .with_arg("fee_to_use", &fee_to_use)
-                .with_arg("previous_fee", &previous_fee)
-                .with_arg("next_block_minimal_fee", &next_block_minimal_fee)
-                .with_arg("min_price_bump_multiplier", &min_price_bump_multiplier);
-            return Err(err.into());
-        }
-        Ok(())
+        };
+        let Some(request) = FeeBumpRequest::new(
+            suggested,
+            sent_attempts,
+            time_in_mempool_in_l1_blocks,
+            MIN_PRICE_BUMP_MULTIPLIER,
+            self.gas_adjuster.get_next_block_minimal_base_fee(),
+            None,
+        ) else {
+            return Ok(suggested);
+        };
+        self.bump_fees(OperatorType::Gateway, request)
     }
 }
 
 impl EthFeesOracle for GasAdjusterFeesOracle {
     fn calculate_fees(
         &self,
-        previous_sent_tx: &Option<TxHistory>,
+        sent_attempts: &[TxHistory],
         time_in_mempool_in_l1_blocks: u32,
         operator_type: OperatorType,
     ) -> Result<EthFees, EthSenderError> {
         match operator_type {
             OperatorType::NonBlob => {
-                self.calculate_fees_no_blob_sidecar(previous_sent_tx, time_in_mempool_in_l1_blocks)
+                self.calculate_fees_no_blob_sidecar(sent_attempts, time_in_mempool_in_l1_blocks)
+            }
+            OperatorType::Blob => {
+                self.calculate_fees_with_blob_sidecar(sent_attempts, time_in_mempool_in_l1_blocks)
             }
-            OperatorType::Blob => self
-                .calculate_fees_with_blob_sidecar(previous_sent_tx, time_in_mempool_in_l1_blocks),
             OperatorType::Gateway => {
-                self.calculate_fees_for_gateway_tx(previous_sent_tx, time_in_mempool_in_l1_blocks)
+                self.calculate_fees_for_gateway_tx(sent_attempts, time_in_mempool_in_l1_blocks)
             }
         }
     }
+
+    fn fee_bump_strategy(&self, operator_type: OperatorType) -> &'static str {
+        self.fee_bumping.get(operator_type).name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use zksync_config::configs::eth_sender::{FeeBumpingConfig, FeeBumpingStrategy};
+    use zksync_types::{
+        aggregated_operations::{AggregatedActionType, L1BatchAggregatedActionType},
+        eth_sender::EthTxFinalityStatus,
+        H256,
+    };
+
+    use super::*;
+
+    const GWEI: u64 = 1_000_000_000;
+
+    /// Provider suggesting the same fees regardless of L1 state.
+    #[derive(Debug)]
+    struct ConstantTxParams;
+
+    impl TxParamsProvider for ConstantTxParams {
+        fn get_base_fee(&self, _time_in_mempool_in_l1_blocks: u32) -> u64 {
+            10 * GWEI
+        }
+
+        fn gateway_get_base_fee(&self, _time_in_mempool_in_l1_blocks: u32) -> u64 {
+            10 * GWEI
+        }
+
+        fn get_priority_fee(&self) -> u64 {
+            2 * GWEI
+        }
+
+        fn get_next_block_minimal_base_fee(&self) -> u64 {
+            10 * GWEI
+        }
+
+        fn get_next_block_minimal_blob_base_fee(&self) -> u64 {
+            GWEI
+        }
+
+        fn get_blob_tx_base_fee(&self, _time_in_mempool_in_l1_blocks: u32) -> u64 {
+            10 * GWEI
+        }
+
+        fn get_blob_tx_blob_base_fee(&self, _time_in_mempool_in_l1_blocks: u32) -> u64 {
+            GWEI
+        }
+
+        fn get_blob_tx_priority_fee(&self) -> u64 {
+            2 * GWEI
+        }
+
+        fn get_gateway_price_per_pubdata(&self, _time_in_mempool_in_l1_blocks: u32) -> u64 {
+            1_000
+        }
+
+        fn get_gateway_l2_pubdata_price(&self, _time_in_mempool_in_l1_blocks: u32) -> u64 {
+            1_000
+        }
+
+        fn get_parameter_b(&self) -> f64 {
+            1.0
+        }
+    }
+
+    fn sent_attempt(id: u32, fees: &EthFees, sent_at_block: u32) -> TxHistory {
+        TxHistory {
+            id,
+            eth_tx_id: 1,
+            chain_id: None,
+            tx_type: AggregatedActionType::L1Batch(L1BatchAggregatedActionType::Commit),
+            base_fee_per_gas: fees.base_fee_per_gas,
+            priority_fee_per_gas: fees.priority_fee_per_gas,
+            blob_base_fee_per_gas: fees.blob_base_fee_per_gas,
+            tx_hash: H256::zero(),
+            signed_raw_tx: vec![],
+            sent_at_block: Some(sent_at_block),
+            max_gas_per_pubdata: fees.max_gas_per_pubdata_price,
+            eth_tx_finality_status: EthTxFinalityStatus::Pending,
+            sent_successfully: true,
+            fee_bump_strategy: None,
+        }
+    }
+
+    #[test]
+    fn resending_transaction_many_times_respects_caps() {
+        const MAX_PRIORITY_FEE: u64 = 20 * GWEI;
+
+        let oracle = GasAdjusterFeesOracle {
+            gas_adjuster: Arc::new(ConstantTxParams),
+            max_acceptable_priority_fee_in_gwei: MAX_PRIORITY_FEE,
+            time_in_mempool_in_l1_blocks_cap: 100,
+            max_acceptable_base_fee_in_wei: u64::MAX,
+            fee_bumping: FeeBumpingStrategies::new(&FeeBumpingConfig {
+                non_blob: FeeBumpingStrategy::Linear {
+                    step_percent: 15,
+                    max_fee_per_gas: 1_000 * GWEI,
+                },
+                blob: FeeBumpingStrategy::Exponential {
+                    multiplier: 1.5,
+                    max_fee_per_gas: 100 * GWEI,
+                },
+                gateway: FeeBumpingStrategy::Deadline {
+                    target_inclusion_blocks: 10,
+                    max_multiplier: 3.0,
+                    max_fee_per_gas: 100 * GWEI,
+                },
+            }),
+        };
+
+        // The priority fee of non-blob transactions reaches its limit before the max fee reaches the strategy cap.
+        let cases = [
+            (OperatorType::NonBlob, 1_000 * GWEI, "priority_fee_per_gas"),
+            (OperatorType::Blob, 100 * GWEI, "max_fee_per_gas"),
+            (OperatorType::Gateway, 100 * GWEI, "max_fee_per_gas"),
+        ];
+        for (operator_type, max_fee_per_gas, capped_fee_type) in cases {
+            println!("Testing {operator_type:?} operator");
+            // Attempts are ordered from the latest one, as in the DAL.
+            let mut sent_attempts = vec![];
+            let mut cap_error = None;
+            for block in 0..50 {
+                match oracle.calculate_fees(&sent_attempts, block, operator_type) {
+                    Ok(fees) => {
+                        assert!(cap_error.is_none(), "resend after reaching the cap");
+                        assert!(
+                            fees.base_fee_per_gas + fees.priority_fee_per_gas <= max_fee_per_gas,
+                            "{fees:?}"
+                        );
+                        assert!(fees.priority_fee_per_gas <= MAX_PRIORITY_FEE, "{fees:?}");
+                        if let Some(previous) = sent_attempts.first() {
+                            assert!(fees.base_fee_per_gas > previous.base_fee_per_gas);
+                        }
+                        sent_attempts.insert(0, sent_attempt(block, &fees, block));
+                    }
+                    Err(err) => cap_error = Some(err),
+                }
+            }
+
+            assert!(sent_attempts.len() > 3, "{sent_attempts:?}");
+            let cap_error = cap_error.expect("cap was never reached");
+            assert_matches!(
+                cap_error,
+                EthSenderError::FeeBumpCapExceeded { fee_type, .. } if fee_type == capped_fee_type
+            );
+        }
+    }
 }
@@ -45,8 +45,8 @@ use crate::{
 
 #[derive(Debug)]
 pub struct DAValidatorPair {
-    l1_validator: Address,
-    l2_validator: Address,
+    pub(crate) l1_validator: Address,
+    pub(crate) l2_validator: Address,
 }
 
 /// Data queried from L1 using multicall contract.
@@ -662,19 +662,11 @@ impl EthTxAggregator {
         &mut self,
         storage: &mut Connection<'_, Core>,
     ) -> Result<(), EthSenderError> {
-        let gateway_migration_state = self.gateway_status(storage).await;
-        let MulticallData {
-            base_system_contracts_hashes,
-            verifier_address,
-            chain_protocol_version_id,
-            stm_protocol_version_id,
-            stm_validator_timelock_address,
-            da_validator_pair,
-            execution_delay,
-        } = self.get_multicall_data().await.map_err(|err| {
+        let multicall_data = self.get_multicall_data().await.map_err(|err| {
             tracing::error!("Failed to get multicall data {err:?}");
             err
         })?;
+        let verifier_address = multicall_data.verifier_address;
 
         let snark_wrapper_vk_hash = self
             .get_snark_wrapper_vk_hash(verifier_address)
@@ -704,6 +696,35 @@ impl EthTxAggregator {
                     get_priority_tree_start_index(self.eth_client.as_ref()).await?;
                 self.priority_tree_start_index
             };
+        self.save_next_operation(
+            storage,
+            multicall_data,
+            l1_verifier_config,
+            priority_tree_start_index,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Saves an `eth_tx` for the next ready aggregated operation (if any) based on the provided settlement layer state.
+    /// Returns the saved operation together with the `eth_tx`.
+    pub(crate) async fn save_next_operation(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        multicall_data: MulticallData,
+        l1_verifier_config: L1VerifierConfig,
+        priority_tree_start_index: Option<usize>,
+    ) -> Result<Option<(AggregatedOperation, EthTx)>, EthSenderError> {
+        let gateway_migration_state = self.gateway_status(storage).await;
+        let MulticallData {
+            base_system_contracts_hashes,
+            chain_protocol_version_id,
+            stm_protocol_version_id,
+            stm_validator_timelock_address,
+            da_validator_pair,
+            execution_delay,
+            ..
+        } = multicall_data;
         let commit_restriction = self
             .config
             .tx_aggregation_only_prove_and_execute
@@ -764,6 +785,7 @@ impl EthTxAggregator {
             .precommit_params(storage, chain_protocol_version_id)
             .await?;
 
+        let mut saved_operation = None;
         if let Some(agg_op) = self
             .aggregator
             .get_next_ready_operation(
@@ -800,6 +822,7 @@ impl EthTxAggregator {
                 }
                 .into(),
             );
+            saved_operation = Some((agg_op, tx));
         }
 
         if precommit_params.is_some() {
@@ -807,7 +830,7 @@ impl EthTxAggregator {
             // we need to set the final precommit operation for l1 batches
             self.set_final_precommit_operation(storage).await?;
         }
-        Ok(())
+        Ok(saved_operation)
     }
 
     /// If we need to disable precommit operations, we can't do it straight away,
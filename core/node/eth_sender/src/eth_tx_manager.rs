@@ -23,6 +23,7 @@ use super::{metrics::METRICS, EthSenderError};
 use crate::{
     abstract_l1_interface::{AbstractL1Interface, OperatorNonce, OperatorType, RealL1Interface},
     eth_fees_oracle::{EthFees, EthFeesOracle, GasAdjusterFeesOracle},
+    fee_bumping::FeeBumpingStrategies,
     health::{EthTxDetails, EthTxManagerHealthDetails},
     metrics::TransactionType,
 };
@@ -54,6 +55,21 @@ impl EthTxManager {
         let ethereum_client = ethereum_client.map(|eth| eth.for_component("eth_tx_manager"));
         let ethereum_client_blobs =
             ethereum_client_blobs.map(|eth| eth.for_component("eth_tx_manager"));
+        let l1_interface = Box::new(RealL1Interface {
+            ethereum_client,
+            ethereum_client_blobs,
+            sl_client: l2_client,
+            wait_confirmations: config.wait_confirmations,
+        });
+        Self::with_l1_interface(pool, config, gas_adjuster, l1_interface)
+    }
+
+    pub(crate) fn with_l1_interface(
+        pool: ConnectionPool<Core>,
+        config: SenderConfig,
+        gas_adjuster: Arc<dyn TxParamsProvider>,
+        l1_interface: Box<dyn AbstractL1Interface>,
+    ) -> Self {
         // If `time_in_mempool_multiplier_cap` is set in config then we use it to derive cap for `l1_blocks_cap`.
         // Otherwise we use `time_in_mempool_in_l1_blocks_cap`.
         let time_in_mempool_in_l1_blocks_cap =
@@ -67,13 +83,8 @@ impl EthTxManager {
             max_acceptable_priority_fee_in_gwei: config.max_acceptable_priority_fee_in_gwei,
             time_in_mempool_in_l1_blocks_cap,
             max_acceptable_base_fee_in_wei: config.max_acceptable_base_fee_in_wei,
+            fee_bumping: FeeBumpingStrategies::new(&config.fee_bumping),
         };
-        let l1_interface = Box::new(RealL1Interface {
-            ethereum_client,
-            ethereum_client_blobs,
-            sl_client: l2_client,
-            wait_confirmations: config.wait_confirmations,
-        });
         tracing::info!(
             "Started eth_tx_manager supporting {:?} operators",
             l1_interface.supported_operator_types()
@@ -134,11 +145,16 @@ impl EthTxManager {
         time_in_mempool_in_l1_blocks: u32,
         current_block: L1BlockNumber,
     ) -> Result<H256, EthSenderError> {
-        let previous_sent_tx = storage
+        // Attempts are ordered from the latest one.
+        let sent_attempts: Vec<_> = storage
             .eth_sender_dal()
-            .get_last_sent_successfully_eth_tx(tx.id)
+            .get_tx_history_to_check(tx.id)
             .await
-            .unwrap();
+            .unwrap()
+            .into_iter()
+            .filter(|attempt| attempt.sent_successfully)
+            .collect();
+        let previous_sent_tx = sent_attempts.first();
 
         let operator_type = self.operator_type(tx);
         let EthFees {
@@ -147,7 +163,7 @@ impl EthTxManager {
             blob_base_fee_per_gas,
             max_gas_per_pubdata_price,
         } = self.fees_oracle.calculate_fees(
-            &previous_sent_tx,
+            &sent_attempts,
             time_in_mempool_in_l1_blocks,
             operator_type,
         )?;
@@ -163,6 +179,9 @@ impl EthTxManager {
         };
 
         let gas_limit = self.gas_limit(tx, max_gas_per_pubdata_price);
+        let fee_bump_strategy = previous_sent_tx
+            .is_some()
+            .then(|| self.fees_oracle.fee_bump_strategy(operator_type));
 
         if let Some(previous_sent_tx) = previous_sent_tx {
             METRICS.transaction_resent.inc();
@@ -244,6 +263,7 @@ impl EthTxManager {
                 signed_tx.raw_tx.as_ref(),
                 current_block.0,
                 Some(gas_limit.as_u64()),
+                fee_bump_strategy,
             )
             .await
             .unwrap();
@@ -0,0 +1,508 @@
+//! Strategies escalating fees of in-flight transactions that must be resent.
+
+use std::{cmp::max, fmt};
+
+use zksync_config::configs::eth_sender::{
+    FeeBumpingConfig, FeeBumpingStrategy as FeeBumpingStrategyConfig,
+};
+use zksync_eth_client::{ClientError, EnrichedClientError};
+use zksync_types::eth_sender::TxHistory;
+
+use crate::{abstract_l1_interface::OperatorType, eth_fees_oracle::EthFees, EthSenderError};
+
+/// Inputs for computing fees of a resent transaction.
+#[derive(Debug)]
+pub(crate) struct FeeBumpRequest<'a> {
+    /// Fees suggested by the gas adjuster for the current L1 state.
+    pub suggested: EthFees,
+    /// First successfully sent attempt of the transaction.
+    pub first: &'a TxHistory,
+    /// Last successfully sent attempt of the transaction.
+    pub previous: &'a TxHistory,
+    /// Number of successfully sent attempts of the transaction, i.e., the number of the resend being computed.
+    pub attempts: u32,
+    /// Number of L1 blocks since the transaction was first sent.
+    pub blocks_since_first_send: u32,
+    /// Minimum multiplier over the previous fees required for the replacement to be accepted by L1 nodes.
+    pub min_bump_multiplier: f64,
+    /// Minimum base fee allowing inclusion into the next L1 block.
+    pub next_block_minimal_base_fee: u64,
+    /// Minimum blob base fee allowing inclusion into the next L1 block. Only set for blob transactions.
+    pub next_block_minimal_blob_base_fee: Option<u64>,
+}
+
+impl<'a> FeeBumpRequest<'a> {
+    /// Creates a request for the transaction with the specified successfully sent attempts, ordered from the latest one.
+    /// Returns `None` if the transaction wasn't sent yet.
+    pub fn new(
+        suggested: EthFees,
+        sent_attempts: &'a [TxHistory],
+        blocks_since_first_send: u32,
+        min_bump_multiplier: f64,
+        next_block_minimal_base_fee: u64,
+        next_block_minimal_blob_base_fee: Option<u64>,
+    ) -> Option<Self> {
+        Some(Self {
+            suggested,
+            first: sent_attempts.last()?,
+            previous: sent_attempts.first()?,
+            attempts: sent_attempts.len().try_into().unwrap_or(u32::MAX),
+            blocks_since_first_send,
+            min_bump_multiplier,
+            next_block_minimal_base_fee,
+            next_block_minimal_blob_base_fee,
+        })
+    }
+}
+
+/// Strategy computing fees of a resent transaction based on the fees of its previous attempts.
+pub(crate) trait FeeBumpingStrategy: 'static + Send + Sync + fmt::Debug {
+    /// Name of the strategy persisted in the transaction history.
+    fn name(&self) -> &'static str;
+
+    /// Computes fees for the next attempt. Returns an error if the transaction should not be resent yet.
+    fn bump_fees(&self, request: &FeeBumpRequest<'_>) -> Result<EthFees, EthSenderError>;
+}
+
+/// Fee bumping strategies for all operator types.
+#[derive(Debug)]
+pub(crate) struct FeeBumpingStrategies {
+    non_blob: Box<dyn FeeBumpingStrategy>,
+    blob: Box<dyn FeeBumpingStrategy>,
+    gateway: Box<dyn FeeBumpingStrategy>,
+}
+
+impl FeeBumpingStrategies {
+    pub fn new(config: &FeeBumpingConfig) -> Self {
+        Self {
+            non_blob: Self::strategy(&config.non_blob),
+            blob: Self::strategy(&config.blob),
+            gateway: Self::strategy(&config.gateway),
+        }
+    }
+
+    fn strategy(config: &FeeBumpingStrategyConfig) -> Box<dyn FeeBumpingStrategy> {
+        match *config {
+            FeeBumpingStrategyConfig::GasAdjuster => Box::new(GasAdjusterBumping),
+            FeeBumpingStrategyConfig::Linear {
+                step_percent,
+                max_fee_per_gas,
+            } => Box::new(LinearBumping {
+                step_percent,
+                max_fee_per_gas,
+            }),
+            FeeBumpingStrategyConfig::Exponential {
+                multiplier,
+                max_fee_per_gas,
+            } => Box::new(ExponentialBumping {
+                multiplier,
+                max_fee_per_gas,
+            }),
+            FeeBumpingStrategyConfig::Deadline {
+                target_inclusion_blocks,
+                max_multiplier,
+                max_fee_per_gas,
+            } => Box::new(DeadlineBumping {
+                target_inclusion_blocks,
+                max_multiplier,
+                max_fee_per_gas,
+            }),
+        }
+    }
+
+    pub fn get(&self, operator_type: OperatorType) -> &dyn FeeBumpingStrategy {
+        match operator_type {
+            OperatorType::NonBlob => self.non_blob.as_ref(),
+            OperatorType::Blob => self.blob.as_ref(),
+            OperatorType::Gateway => self.gateway.as_ref(),
+        }
+    }
+}
+
+fn scale_fee(fee: u64, multiplier: f64) -> u64 {
+    (fee as f64 * multiplier).ceil() as u64
+}
+
+fn verify_fee_not_too_low_on_resend(
+    tx_id: u32,
+    previous_fee: u64,
+    fee_to_use: u64,
+    next_block_minimal_fee: u64,
+    min_price_bump_multiplier: f64,
+    fee_type: &str,
+) -> Result<(), EthSenderError> {
+    let fee_to_use = fee_to_use as f64;
+    if fee_to_use < (next_block_minimal_fee as f64)
+        || fee_to_use < (previous_fee as f64 * min_price_bump_multiplier).ceil()
+    {
+        // If the fee is lower than the previous used one multiplied by the required factor
+        // or is lower than the minimal possible value for the next block, sending is skipped.
+        tracing::info!(
+            "{fee_type} too low for resend detected for tx {}, \
+             suggested fee {:?}, \
+             previous_fee {:?}, \
+             next_block_minimal_fee {:?}, \
+             min_price_bump_multiplier {:?}",
+            tx_id,
+            fee_to_use,
+            previous_fee,
+            next_block_minimal_fee,
+            min_price_bump_multiplier
+        );
+        let err = ClientError::Custom(format!("{fee_type} is too low"));
+        let err = EnrichedClientError::new(err, "verify_base_fee_not_too_low_on_resend")
+            .with_arg("fee_to_use", &fee_to_use)
+            .with_arg("previous_fee", &previous_fee)
+            .with_arg("next_block_minimal_fee", &next_block_minimal_fee)
+            .with_arg("min_price_bump_multiplier", &min_price_bump_multiplier);
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+/// Uses fees suggested by the gas adjuster as is, skipping the resend if they don't exceed the previous fees
+/// by the minimum replacement bump.
+#[derive(Debug)]
+struct GasAdjusterBumping;
+
+impl FeeBumpingStrategy for GasAdjusterBumping {
+    fn name(&self) -> &'static str {
+        "gas_adjuster"
+    }
+
+    fn bump_fees(&self, request: &FeeBumpRequest<'_>) -> Result<EthFees, EthSenderError> {
+        let previous = request.previous;
+        let multiplier = request.min_bump_multiplier;
+        let mut fees = request.suggested;
+
+        let base_result = verify_fee_not_too_low_on_resend(
+            previous.id,
+            previous.base_fee_per_gas,
+            fees.base_fee_per_gas,
+            request.next_block_minimal_base_fee,
+            multiplier,
+            "base_fee_per_gas",
+        );
+        if let (Some(blob_base_fee_per_gas), Some(next_block_minimal_blob_base_fee)) = (
+            fees.blob_base_fee_per_gas,
+            request.next_block_minimal_blob_base_fee,
+        ) {
+            let previous_blob_base_fee_per_gas = previous.blob_base_fee_per_gas.unwrap_or(0);
+            let blob_result = verify_fee_not_too_low_on_resend(
+                previous.id,
+                previous_blob_base_fee_per_gas,
+                blob_base_fee_per_gas,
+                next_block_minimal_blob_base_fee,
+                multiplier,
+                "blob_base_fee_per_gas",
+            );
+            match (blob_result, base_result) {
+                (Ok(()), Ok(())) => {}
+                (Err(err), Err(_)) => return Err(err),
+                // If we're ready to increase one of the fees then we force-increase another one.
+                (Ok(()), Err(_)) => {
+                    fees.base_fee_per_gas = scale_fee(previous.base_fee_per_gas, multiplier);
+                }
+                (Err(_), Ok(())) => {
+                    fees.blob_base_fee_per_gas =
+                        Some(scale_fee(previous_blob_base_fee_per_gas, multiplier));
+                }
+            }
+        } else {
+            base_result?;
+        }
+
+        if let Some(max_gas_per_pubdata_price) = fees.max_gas_per_pubdata_price {
+            // Priority fee is ignored for Gateway; increase `gas_per_pubdata_fee` instead.
+            if let Some(previous_max_gas_per_pubdata) = previous.max_gas_per_pubdata {
+                fees.max_gas_per_pubdata_price = Some(max(
+                    max_gas_per_pubdata_price,
+                    scale_fee(previous_max_gas_per_pubdata, multiplier),
+                ));
+            }
+        } else {
+            fees.priority_fee_per_gas = max(
+                fees.priority_fee_per_gas,
+                scale_fee(previous.priority_fee_per_gas, multiplier),
+            );
+        }
+        Ok(fees)
+    }
+}
+
+/// Computes each fee component using `target_fee(suggested, first)`, where `first` is the fee used in the first attempt,
+/// and raises it to the minimum replacement bump over the previous attempt (and, for base fees, to the minimum fee
+/// for the next block). Returns an error if the resulting max fee per gas exceeds `max_fee_per_gas`.
+fn bump_each_fee(
+    request: &FeeBumpRequest<'_>,
+    max_fee_per_gas: u64,
+    target_fee: impl Fn(u64, u64) -> u64,
+) -> Result<EthFees, EthSenderError> {
+    let suggested = &request.suggested;
+    let first = request.first;
+    let previous = request.previous;
+    let bump = |suggested: u64, first: u64, previous: u64, next_block_minimal_fee: u64| {
+        target_fee(suggested, first)
+            .max(scale_fee(previous, request.min_bump_multiplier))
+            .max(next_block_minimal_fee)
+    };
+
+    let fees = EthFees {
+        base_fee_per_gas: bump(
+            suggested.base_fee_per_gas,
+            first.base_fee_per_gas,
+            previous.base_fee_per_gas,
+            request.next_block_minimal_base_fee,
+        ),
+        priority_fee_per_gas: bump(
+            suggested.priority_fee_per_gas,
+            first.priority_fee_per_gas,
+            previous.priority_fee_per_gas,
+            0,
+        ),
+        blob_base_fee_per_gas: suggested.blob_base_fee_per_gas.map(|fee| {
+            bump(
+                fee,
+                first.blob_base_fee_per_gas.unwrap_or(0),
+                previous.blob_base_fee_per_gas.unwrap_or(0),
+                request.next_block_minimal_blob_base_fee.unwrap_or(0),
+            )
+        }),
+        max_gas_per_pubdata_price: suggested.max_gas_per_pubdata_price.map(|price| {
+            match (first.max_gas_per_pubdata, previous.max_gas_per_pubdata) {
+                (Some(first_price), Some(previous_price)) => {
+                    bump(price, first_price, previous_price, 0)
+                }
+                _ => price,
+            }
+        }),
+    };
+
+    let bumped_max_fee_per_gas = fees
+        .base_fee_per_gas
+        .saturating_add(fees.priority_fee_per_gas);
+    if bumped_max_fee_per_gas > max_fee_per_gas {
+        tracing::info!(
+            "Max fee per gas {bumped_max_fee_per_gas} for resending tx {} exceeds the cap {max_fee_per_gas}, \
+             skipping resend",
+            request.previous.eth_tx_id
+        );
+        return Err(EthSenderError::FeeBumpCapExceeded {
+            fee_type: "max_fee_per_gas",
+            fee: bumped_max_fee_per_gas,
+            cap: max_fee_per_gas,
+        });
+    }
+    Ok(fees)
+}
+
+/// Increases the first attempt fees by a fixed share on each resend, up to a cap.
+#[derive(Debug)]
+struct LinearBumping {
+    step_percent: u32,
+    max_fee_per_gas: u64,
+}
+
+impl FeeBumpingStrategy for LinearBumping {
+    fn name(&self) -> &'static str {
+        "linear"
+    }
+
+    fn bump_fees(&self, request: &FeeBumpRequest<'_>) -> Result<EthFees, EthSenderError> {
+        let multiplier = 1.0 + f64::from(self.step_percent) / 100.0 * f64::from(request.attempts);
+        bump_each_fee(request, self.max_fee_per_gas, |suggested, first| {
+            scale_fee(suggested.max(first), multiplier)
+        })
+    }
+}
+
+/// Multiplies the first attempt fees by a fixed factor on each resend, up to a cap.
+#[derive(Debug)]
+struct ExponentialBumping {
+    multiplier: f64,
+    max_fee_per_gas: u64,
+}
+
+impl FeeBumpingStrategy for ExponentialBumping {
+    fn name(&self) -> &'static str {
+        "exponential"
+    }
+
+    fn bump_fees(&self, request: &FeeBumpRequest<'_>) -> Result<EthFees, EthSenderError> {
+        let attempts = i32::try_from(request.attempts).unwrap_or(i32::MAX);
+        let multiplier = self.multiplier.powi(attempts);
+        bump_each_fee(request, self.max_fee_per_gas, |suggested, first| {
+            scale_fee(suggested.max(first), multiplier)
+        })
+    }
+}
+
+/// Multiplies the suggested fees by a factor growing linearly with the time since the first send, so that it reaches
+/// `max_multiplier` at the inclusion deadline. Fees may exceed this target only to satisfy the minimum replacement bump,
+/// and never exceed the cap.
+#[derive(Debug)]
+struct DeadlineBumping {
+    target_inclusion_blocks: u32,
+    max_multiplier: f64,
+    max_fee_per_gas: u64,
+}
+
+impl DeadlineBumping {
+    fn multiplier(&self, blocks_since_first_send: u32) -> f64 {
+        let progress = if self.target_inclusion_blocks == 0 {
+            1.0
+        } else {
+            f64::from(blocks_since_first_send.min(self.target_inclusion_blocks))
+                / f64::from(self.target_inclusion_blocks)
+        };
+        1.0 + (self.max_multiplier - 1.0).max(0.0) * progress
+    }
+}
+
+impl FeeBumpingStrategy for DeadlineBumping {
+    fn name(&self) -> &'static str {
+        "deadline"
+    }
+
+    fn bump_fees(&self, request: &FeeBumpRequest<'_>) -> Result<EthFees, EthSenderError> {
+        let multiplier = self.multiplier(request.blocks_since_first_send);
+        bump_each_fee(request, self.max_fee_per_gas, |suggested, _| {
+            scale_fee(suggested, multiplier)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use zksync_types::{
+        aggregated_operations::{AggregatedActionType, L1BatchAggregatedActionType},
+        eth_sender::EthTxFinalityStatus,
+        H256,
+    };
+
+    use super::*;
+
+    fn previous_tx(base_fee_per_gas: u64, priority_fee_per_gas: u64) -> TxHistory {
+        TxHistory {
+            id: 1,
+            eth_tx_id: 1,
+            chain_id: None,
+            tx_type: AggregatedActionType::L1Batch(L1BatchAggregatedActionType::Commit),
+            base_fee_per_gas,
+            priority_fee_per_gas,
+            blob_base_fee_per_gas: None,
+            tx_hash: H256::zero(),
+            signed_raw_tx: vec![],
+            sent_at_block: Some(1),
+            max_gas_per_pubdata: None,
+            eth_tx_finality_status: EthTxFinalityStatus::Pending,
+            sent_successfully: true,
+            fee_bump_strategy: None,
+        }
+    }
+
+    /// Creates a request for the transaction with the specified sent attempts, ordered from the latest one.
+    fn request(
+        sent_attempts: &[TxHistory],
+        suggested_base_fee: u64,
+        blocks: u32,
+    ) -> FeeBumpRequest<'_> {
+        let suggested = EthFees {
+            base_fee_per_gas: suggested_base_fee,
+            priority_fee_per_gas: 10,
+            blob_base_fee_per_gas: None,
+            max_gas_per_pubdata_price: None,
+        };
+        FeeBumpRequest::new(suggested, sent_attempts, blocks, 1.25, 50, None).unwrap()
+    }
+
+    #[test]
+    fn gas_adjuster_bumping_skips_resend_with_low_fees() {
+        let attempts = [previous_tx(100, 10)];
+        GasAdjusterBumping
+            .bump_fees(&request(&attempts, 105, 1))
+            .unwrap_err();
+
+        let fees = GasAdjusterBumping
+            .bump_fees(&request(&attempts, 130, 1))
+            .unwrap();
+        assert_eq!(fees.base_fee_per_gas, 130);
+        assert_eq!(fees.priority_fee_per_gas, 13);
+    }
+
+    #[test]
+    fn linear_bumping() {
+        let first = previous_tx(100, 10);
+        let strategy = LinearBumping {
+            step_percent: 50,
+            max_fee_per_gas: 1_000,
+        };
+        let attempts = [first.clone()];
+        let fees = strategy.bump_fees(&request(&attempts, 100, 1)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 150);
+        assert_eq!(fees.priority_fee_per_gas, 15);
+
+        // Suggested fees are used as the base if they are higher.
+        let fees = strategy.bump_fees(&request(&attempts, 300, 1)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 450);
+
+        // Fees grow linearly with the number of attempts, rather than compound over the previous attempt.
+        let attempts = [previous_tx(150, 15), first.clone()];
+        let fees = strategy.bump_fees(&request(&attempts, 100, 2)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 200);
+        assert_eq!(fees.priority_fee_per_gas, 20);
+
+        // The minimum replacement bump is enforced.
+        let strategy = LinearBumping {
+            step_percent: 1,
+            max_fee_per_gas: 1_000,
+        };
+        let attempts = [first];
+        let fees = strategy.bump_fees(&request(&attempts, 100, 1)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 125);
+    }
+
+    #[test]
+    fn exponential_bumping_with_cap() {
+        let first = previous_tx(100, 10);
+        let strategy = ExponentialBumping {
+            multiplier: 2.0,
+            max_fee_per_gas: 250,
+        };
+        let attempts = [first.clone()];
+        let fees = strategy.bump_fees(&request(&attempts, 80, 1)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 200);
+        assert_eq!(fees.priority_fee_per_gas, 20);
+
+        let attempts = [previous_tx(200, 20), first];
+        let err = strategy.bump_fees(&request(&attempts, 80, 2)).unwrap_err();
+        assert_matches!(
+            err,
+            EthSenderError::FeeBumpCapExceeded {
+                fee: 440,
+                cap: 250,
+                ..
+            }
+        );
+    }
+
+    #[test]
+    fn deadline_bumping() {
+        let attempts = [previous_tx(100, 10)];
+        let strategy = DeadlineBumping {
+            target_inclusion_blocks: 10,
+            max_multiplier: 3.0,
+            max_fee_per_gas: 1_000,
+        };
+        // Early on, only the minimum replacement bump is applied.
+        let fees = strategy.bump_fees(&request(&attempts, 100, 1)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 125);
+        let fees = strategy.bump_fees(&request(&attempts, 100, 5)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 200);
+        // After the deadline, the multiplier is capped.
+        let fees = strategy.bump_fees(&request(&attempts, 100, 20)).unwrap();
+        assert_eq!(fees.base_fee_per_gas, 300);
+        assert_eq!(fees.priority_fee_per_gas, 30);
+    }
+}
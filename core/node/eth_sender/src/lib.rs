@@ -7,11 +7,13 @@ mod health;
 mod metrics;
 pub mod node;
 mod publish_criterion;
+pub mod replay;
 mod zksync_functions;
 
 mod abstract_l1_interface;
 
 mod eth_fees_oracle;
+mod fee_bumping;
 #[cfg(test)]
 mod tests;
 
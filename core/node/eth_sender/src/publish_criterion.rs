@@ -1,14 +1,13 @@
 use std::{fmt, ops, time::Duration};
 
 use async_trait::async_trait;
-use chrono::Utc;
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_types::{
     aggregated_operations::L1BatchAggregatedActionType, commitment::L1BatchWithMetadata,
     L1BatchNumber,
 };
 
-use super::metrics::METRICS;
+use super::{aggregator::AggregatorClock, metrics::METRICS};
 
 #[async_trait]
 pub trait L1BatchPublishCriterion: fmt::Debug + Send + Sync {
@@ -79,6 +78,8 @@ pub struct TimestampDeadlineCriterion {
     /// it means that sender is lagging significantly and we shouldn't apply this criteria to use all capacity
     /// and avoid packing small ranges.
     pub max_allowed_lag: Option<usize>,
+    /// Clock used to determine L1 batch ages.
+    pub clock: AggregatorClock,
 }
 
 #[async_trait]
@@ -101,8 +102,10 @@ impl L1BatchPublishCriterion for TimestampDeadlineCriterion {
                 return None;
             }
         }
-        let oldest_l1_batch_age_seconds =
-            Utc::now().timestamp() as u64 - first_l1_batch.header.timestamp;
+        let oldest_l1_batch_age_seconds = self
+            .clock
+            .now_secs()
+            .saturating_sub(first_l1_batch.header.timestamp);
         if oldest_l1_batch_age_seconds >= self.deadline.as_secs() {
             let result = consecutive_l1_batches
                 .last()
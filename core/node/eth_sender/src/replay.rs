@@ -0,0 +1,916 @@
+//! Offline replay of the `eth_sender` aggregation loop.
+//!
+//! The replay runs [`Aggregator`] and [`EthTxManager`] against a Postgres snapshot of L1 batches and historical
+//! L1 fees loaded from a file, and reports which operations would be sent when and at what cost. L1 is emulated
+//! by [`ReplayL1Interface`], so no network access is required. This allows tuning publish criteria and fee settings
+//! without deploying them.
+//!
+//! The replay deletes `eth_txs` for replayed L1 batches and writes new ones. All these changes are made
+//! in a single database transaction, which is always rolled back once the replay finishes, so the replay
+//! doesn't modify the database and can be run against a live one. Components only use the connection pool
+//! directly to read data not touched by the replay (e.g., priority transactions).
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use zksync_config::{
+    configs::eth_sender::{ProofSendingMode, SenderConfig},
+    GasAdjusterConfig,
+};
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_eth_client::{
+    clients::{DynClient, MockSettlementLayer, L1},
+    BaseFees, BoundEthInterface, EnrichedClientResult, ExecutedTxStatus, FailureInfo,
+    RawTransactionBytes, SignedCallResult,
+};
+use zksync_node_fee_model::l1_gas_price::{GasAdjuster, GasAdjusterClient};
+use zksync_object_store::MockObjectStore;
+#[cfg(test)]
+use zksync_types::web3;
+use zksync_types::{
+    commitment::L1BatchCommitmentMode,
+    eth_sender::{EthTx, EthTxBlobSidecar, EthTxFinalityStatus, L1BlockNumbers},
+    protocol_version::L1VerifierConfig,
+    settlement::SettlementLayer,
+    web3::TransactionReceipt,
+    Address, L1BatchNumber, L1BlockNumber, L2ChainId, Nonce, SLChainId, EIP_1559_TX_TYPE,
+    EIP_4844_TX_TYPE, H256, U256,
+};
+
+use crate::{
+    abstract_l1_interface::{AbstractL1Interface, OperatorNonce, OperatorType},
+    aggregated_operations::AggregatedOperation,
+    aggregator::AggregatorClock,
+    eth_tx_aggregator::{DAValidatorPair, MulticallData},
+    Aggregator, EthSenderError, EthTxAggregator, EthTxManager,
+};
+
+/// Blob gas consumed by a single blob (EIP-4844).
+const BLOB_GAS_PER_BLOB: u64 = 131_072;
+
+/// Fees of a single historical L1 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L1FeeRecord {
+    pub block_number: u32,
+    /// Block timestamp in seconds since UNIX epoch.
+    pub timestamp: u64,
+    pub base_fee_per_gas: u64,
+    #[serde(default)]
+    pub base_fee_per_blob_gas: u64,
+}
+
+impl From<L1FeeRecord> for BaseFees {
+    fn from(record: L1FeeRecord) -> Self {
+        Self {
+            base_fee_per_gas: record.base_fee_per_gas,
+            base_fee_per_blob_gas: record.base_fee_per_blob_gas.into(),
+            l2_pubdata_price: U256::zero(),
+        }
+    }
+}
+
+/// Loads historical L1 fees from a JSON file containing an array of [`L1FeeRecord`]s ordered by block number.
+/// This method is blocking.
+pub fn load_fee_history(path: &Path) -> anyhow::Result<Vec<L1FeeRecord>> {
+    let raw =
+        fs::read(path).with_context(|| format!("failed reading fee history from {path:?}"))?;
+    let records: Vec<L1FeeRecord> = serde_json::from_slice(&raw)
+        .with_context(|| format!("failed parsing fee history from {path:?}"))?;
+    anyhow::ensure!(!records.is_empty(), "fee history at {path:?} is empty");
+    for window in records.windows(2) {
+        anyhow::ensure!(
+            window[0].block_number < window[1].block_number
+                && window[0].timestamp <= window[1].timestamp,
+            "fee history is not ordered: block #{} follows block #{}",
+            window[1].block_number,
+            window[0].block_number
+        );
+    }
+    Ok(records)
+}
+
+/// Parameters of the replay.
+#[derive(Debug, Clone)]
+pub struct ReplayParams {
+    /// Sender config being evaluated. Proofs are never sent during the replay; a proof is assumed to be available
+    /// as soon as the corresponding commit operation is confirmed.
+    pub sender: SenderConfig,
+    /// Gas adjuster config being evaluated.
+    pub gas_adjuster: GasAdjusterConfig,
+    pub commitment_mode: L1BatchCommitmentMode,
+    pub l1_chain_id: SLChainId,
+    pub l2_chain_id: L2ChainId,
+    pub diamond_proxy_address: Address,
+    pub validator_timelock_address: Address,
+    /// Address of the main operator.
+    pub operator_address: Address,
+    /// Address of the operator sending commit transactions, if it differs from the main operator.
+    pub blob_operator_address: Option<Address>,
+    /// Operations for L1 batches after this one are removed from the database and replayed.
+    pub last_l1_batch_to_keep: L1BatchNumber,
+    /// Execution delay enforced by the validator timelock.
+    pub execution_delay: Duration,
+}
+
+/// Operation produced during the replay.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayedOperation {
+    pub eth_tx_id: u32,
+    /// Operation type, e.g. `Commit` or `Execute`.
+    pub operation: String,
+    /// Inclusive range of L1 batches (or L2 blocks for precommit operations) covered by the operation.
+    pub range: (u32, u32),
+    /// Timestamp (in seconds) at which the operation was created by the aggregator.
+    pub created_at: u64,
+    /// L1 block at which the operation was first sent.
+    pub first_sent_at_block: Option<u32>,
+    /// Number of sent transactions (i.e., the original one and its replacements).
+    pub attempts: usize,
+    /// L1 block in which the operation was included.
+    pub included_at_block: Option<u32>,
+    /// Timestamp of the L1 block in which the operation was included.
+    pub included_at: Option<u64>,
+    /// Gas used by the operation. Since the replay doesn't execute transactions, this is the gas limit.
+    pub gas_used: U256,
+    pub effective_gas_price: Option<u64>,
+    pub blob_gas_used: u64,
+    pub blob_gas_price: Option<u64>,
+    /// Total cost of the operation in wei. `None` if the operation wasn't included by the end of the replay.
+    pub cost: Option<U256>,
+}
+
+/// Report produced by [`replay()`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    pub first_l1_block: u32,
+    pub last_l1_block: u32,
+    pub operations: Vec<ReplayedOperation>,
+}
+
+impl ReplayReport {
+    /// Returns the total cost of all included operations in wei.
+    pub fn total_cost(&self) -> U256 {
+        self.operations
+            .iter()
+            .filter_map(|op| op.cost)
+            .fold(U256::zero(), |acc, cost| acc + cost)
+    }
+}
+
+/// Replays the aggregation loop over the provided L1 fee history.
+///
+/// Each fee record corresponds to a single iteration: the simulated time is set to the record timestamp,
+/// transactions paying enough fees are included into the emulated L1 block, the aggregator saves all ready operations,
+/// and the tx manager sends, resends and confirms transactions. Database changes are rolled back after the replay.
+pub async fn replay(
+    pool: ConnectionPool<Core>,
+    params: ReplayParams,
+    fee_history: &[L1FeeRecord],
+) -> anyhow::Result<ReplayReport> {
+    let first_record = *fee_history.first().context("fee history is empty")?;
+    let last_record = *fee_history.last().unwrap();
+    let config = SenderConfig {
+        proof_sending_mode: ProofSendingMode::SkipEveryProof,
+        ..params.sender.clone()
+    };
+    let settlement_layer = SettlementLayer::L1(params.l1_chain_id);
+
+    let mut connection = pool.connection_tagged("eth_sender_replay").await?;
+    // The transaction is never committed; it's rolled back on return (including returns on errors).
+    let mut storage = connection.start_transaction().await?;
+    tracing::info!(
+        "Deleting eth_txs for L1 batches after #{} in the replay transaction",
+        params.last_l1_batch_to_keep
+    );
+    storage
+        .eth_sender_dal()
+        .delete_eth_txs(params.last_l1_batch_to_keep)
+        .await?;
+    let unconfirmed_tx_count = storage.eth_sender_dal().get_unconfirmed_txs_count().await?;
+    anyhow::ensure!(
+        unconfirmed_tx_count == 0,
+        "there are {unconfirmed_tx_count} unconfirmed eth_txs for L1 batches up to #{}; \
+         choose an earlier L1 batch to keep",
+        params.last_l1_batch_to_keep
+    );
+    let mut initial_nonces = HashMap::new();
+    initial_nonces.insert(
+        OperatorType::NonBlob,
+        next_nonce(&mut storage, params.operator_address).await?,
+    );
+    if let Some(address) = params.blob_operator_address {
+        initial_nonces.insert(OperatorType::Blob, next_nonce(&mut storage, address).await?);
+    }
+
+    // The mock settlement layer serves the fee history to `GasAdjuster`. Its blocks are offset so that `GasAdjuster`
+    // is initialized with a full window of samples.
+    let warmup_blocks = params
+        .gas_adjuster
+        .max_base_fee_samples
+        .max(params.gas_adjuster.num_samples_for_blob_base_fee_estimate);
+    let base_fees: Vec<BaseFees> = std::iter::repeat_n(first_record, warmup_blocks)
+        .chain(fee_history.iter().copied())
+        .map(BaseFees::from)
+        .collect();
+    let build_client = |sender| {
+        MockSettlementLayer::<L1>::builder()
+            .with_fee_history(base_fees.clone())
+            .with_chain_id(params.l1_chain_id.0)
+            .with_sender(sender)
+            .build()
+    };
+    let eth_client = build_client(params.operator_address);
+    eth_client.advance_block_number(warmup_blocks as u64, EthTxFinalityStatus::Finalized);
+    let eth_client_blobs = params.blob_operator_address.map(build_client);
+
+    let fee_client: Box<DynClient<L1>> = Box::new(eth_client.clone().into_client());
+    let gas_adjuster = Arc::new(
+        GasAdjuster::new(
+            GasAdjusterClient::from(fee_client),
+            params.gas_adjuster.clone(),
+            config.pubdata_sending_mode,
+            params.commitment_mode,
+            pool.clone(),
+        )
+        .await
+        .context("GasAdjuster::new()")?,
+    );
+
+    let now_ms = Arc::new(AtomicU64::new(first_record.timestamp * 1_000));
+    let aggregator = Aggregator::with_clock(
+        config.clone(),
+        MockObjectStore::arc(),
+        eth_client_blobs.is_some(),
+        params.commitment_mode,
+        pool.clone(),
+        settlement_layer,
+        AggregatorClock::Simulated(now_ms.clone()),
+    )
+    .await?;
+    let mut eth_tx_aggregator = EthTxAggregator::new(
+        pool.clone(),
+        config.clone(),
+        aggregator,
+        Box::new(eth_client.clone()),
+        eth_client_blobs.map(|client| Box::new(client) as Box<dyn BoundEthInterface>),
+        params.validator_timelock_address,
+        // State transition manager and multicall addresses are only used to query L1.
+        Address::zero(),
+        Address::zero(),
+        params.diamond_proxy_address,
+        params.l2_chain_id,
+        Some(settlement_layer),
+    )
+    .await;
+
+    let l1_interface = ReplayL1Interface::new(
+        params.operator_address,
+        params.blob_operator_address,
+        initial_nonces,
+        config.wait_confirmations,
+    );
+    let mut manager = EthTxManager::with_l1_interface(
+        pool.clone(),
+        config.clone(),
+        gas_adjuster.clone(),
+        Box::new(l1_interface.clone()),
+    );
+
+    let mut created_operations = vec![];
+    for &record in fee_history {
+        now_ms.store(record.timestamp * 1_000, Ordering::Relaxed);
+        eth_client.advance_block_number(1, EthTxFinalityStatus::Finalized);
+        gas_adjuster.keep_updated().await?;
+        l1_interface.advance_to(record);
+
+        while let Some(l1_state) = load_l1_state(&mut storage, record.timestamp, &params).await? {
+            let saved_operation = eth_tx_aggregator
+                .save_next_operation(&mut storage, l1_state, L1VerifierConfig::default(), None)
+                .await?;
+            let Some((operation, eth_tx)) = saved_operation else {
+                break;
+            };
+            let range = match &operation {
+                AggregatedOperation::L1Batch(op) => {
+                    let range = op.l1_batch_range();
+                    (range.start().0, range.end().0)
+                }
+                AggregatedOperation::L2Block(op) => {
+                    let range = op.l2_blocks_range();
+                    (range.start().0, range.end().0)
+                }
+            };
+            tracing::info!(
+                "Replay: saved {} operation for range {range:?} at L1 block #{}",
+                operation.get_action_type(),
+                record.block_number
+            );
+            created_operations.push((
+                eth_tx.id,
+                operation.get_action_type(),
+                range,
+                record.timestamp,
+            ));
+        }
+        manager.loop_iteration(&mut storage).await;
+    }
+    storage
+        .rollback()
+        .await
+        .context("failed rolling back replay transaction")?;
+
+    let block_timestamps: HashMap<_, _> = fee_history
+        .iter()
+        .map(|record| (record.block_number, record.timestamp))
+        .collect();
+    let state = l1_interface.state.lock().unwrap();
+    let operations = created_operations
+        .into_iter()
+        .map(|(eth_tx_id, action_type, range, created_at)| {
+            let txs: Vec<_> = state
+                .sent_txs
+                .iter()
+                .filter(|tx| tx.eth_tx_id == eth_tx_id)
+                .collect();
+            let included_tx = txs.iter().find(|tx| tx.inclusion.is_some());
+            let inclusion = included_tx.and_then(|tx| tx.inclusion);
+            let gas_used = included_tx
+                .or(txs.last())
+                .map_or(U256::zero(), |tx| tx.gas_limit);
+            let blob_gas_used = txs.first().map_or(0, |tx| tx.blob_gas_used());
+            ReplayedOperation {
+                eth_tx_id,
+                operation: action_type.to_string(),
+                range,
+                created_at,
+                first_sent_at_block: txs.first().map(|tx| tx.sent_at_block.0),
+                attempts: txs.len(),
+                included_at_block: inclusion.map(|inclusion| inclusion.block_number.0),
+                included_at: inclusion
+                    .and_then(|inclusion| block_timestamps.get(&inclusion.block_number.0))
+                    .copied(),
+                gas_used,
+                effective_gas_price: inclusion.map(|inclusion| inclusion.effective_gas_price),
+                blob_gas_used,
+                blob_gas_price: inclusion.and_then(|inclusion| inclusion.blob_gas_price),
+                cost: inclusion.map(|inclusion| {
+                    gas_used * inclusion.effective_gas_price
+                        + U256::from(blob_gas_used) * inclusion.blob_gas_price.unwrap_or(0)
+                }),
+            }
+        })
+        .collect();
+
+    Ok(ReplayReport {
+        first_l1_block: first_record.block_number,
+        last_l1_block: last_record.block_number,
+        operations,
+    })
+}
+
+async fn next_nonce(storage: &mut Connection<'_, Core>, address: Address) -> anyhow::Result<u32> {
+    let nonce = storage
+        .eth_sender_dal()
+        .get_next_nonce(address, false)
+        .await?
+        .unwrap_or(0);
+    nonce.try_into().context("nonce overflow")
+}
+
+/// Emulates settlement layer state normally queried by [`EthTxAggregator`] using the state of L1 batches sealed
+/// by the specified time. Returns `None` if no L1 batches are sealed yet.
+async fn load_l1_state(
+    storage: &mut Connection<'_, Core>,
+    timestamp: u64,
+    params: &ReplayParams,
+) -> anyhow::Result<Option<MulticallData>> {
+    let Some(last_sealed_l1_batch) = storage
+        .blocks_dal()
+        .get_last_sealed_l1_batch_number_at_timestamp(timestamp)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let next_l1_batch_to_commit = storage
+        .blocks_dal()
+        .get_last_committed_to_eth_l1_batch()
+        .await?
+        .map_or(L1BatchNumber(1), |batch| batch.header.number + 1);
+    let l1_batch = next_l1_batch_to_commit.min(last_sealed_l1_batch);
+    let header = storage
+        .blocks_dal()
+        .get_l1_batch_header(l1_batch)
+        .await?
+        .with_context(|| format!("L1 batch #{l1_batch} is missing"))?;
+    let protocol_version = header
+        .protocol_version
+        .with_context(|| format!("L1 batch #{l1_batch} has no protocol version"))?;
+
+    Ok(Some(MulticallData {
+        base_system_contracts_hashes: header.base_system_contracts_hashes,
+        verifier_address: Address::zero(),
+        chain_protocol_version_id: protocol_version,
+        stm_validator_timelock_address: params.validator_timelock_address,
+        stm_protocol_version_id: protocol_version,
+        // The pair is only checked to be set.
+        da_validator_pair: DAValidatorPair {
+            l1_validator: Address::repeat_byte(1),
+            l2_validator: Address::repeat_byte(1),
+        },
+        execution_delay: params.execution_delay,
+    }))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TxInclusion {
+    block_number: L1BlockNumber,
+    effective_gas_price: u64,
+    blob_gas_price: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+struct ReplayedTx {
+    eth_tx_id: u32,
+    operator_type: OperatorType,
+    nonce: Nonce,
+    hash: H256,
+    max_fee_per_gas: u64,
+    max_priority_fee_per_gas: u64,
+    max_fee_per_blob_gas: Option<U256>,
+    gas_limit: U256,
+    blob_count: usize,
+    sent_at_block: L1BlockNumber,
+    inclusion: Option<TxInclusion>,
+}
+
+impl ReplayedTx {
+    fn blob_gas_used(&self) -> u64 {
+        self.blob_count as u64 * BLOB_GAS_PER_BLOB
+    }
+
+    fn inclusion_in(&self, block: &L1FeeRecord) -> Option<TxInclusion> {
+        if self.max_fee_per_gas < block.base_fee_per_gas {
+            return None;
+        }
+        let blob_gas_price = if let Some(max_fee_per_blob_gas) = self.max_fee_per_blob_gas {
+            if max_fee_per_blob_gas < block.base_fee_per_blob_gas.into() {
+                return None;
+            }
+            Some(block.base_fee_per_blob_gas)
+        } else {
+            None
+        };
+        Some(TxInclusion {
+            block_number: L1BlockNumber(block.block_number),
+            effective_gas_price: self
+                .max_fee_per_gas
+                .min(block.base_fee_per_gas + self.max_priority_fee_per_gas),
+            blob_gas_price,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct ReplayL1State {
+    current_block: Option<L1FeeRecord>,
+    initial_nonces: HashMap<OperatorType, u32>,
+    /// Transactions signed, but not sent yet.
+    signed_txs: HashMap<OperatorType, ReplayedTx>,
+    sent_txs: Vec<ReplayedTx>,
+}
+
+impl ReplayL1State {
+    fn current_block_number(&self) -> L1BlockNumber {
+        L1BlockNumber(
+            self.current_block
+                .expect("L1 block is not initialized")
+                .block_number,
+        )
+    }
+
+    fn nonce_at(&self, operator_type: OperatorType, block_number: L1BlockNumber) -> u32 {
+        let included_count = self
+            .sent_txs
+            .iter()
+            .filter(|tx| {
+                tx.operator_type == operator_type
+                    && tx
+                        .inclusion
+                        .is_some_and(|inclusion| inclusion.block_number <= block_number)
+            })
+            .count();
+        self.initial_nonces[&operator_type] + included_count as u32
+    }
+
+    /// Includes pending transactions paying enough fees into the current block. For each nonce, the latest
+    /// replacement transaction is considered.
+    fn include_txs(&mut self) {
+        let block = self.current_block.unwrap();
+        let block_number = L1BlockNumber(block.block_number);
+        let operator_types: Vec<_> = self.initial_nonces.keys().copied().collect();
+        for operator_type in operator_types {
+            loop {
+                let nonce = self.nonce_at(operator_type, block_number);
+                let Some(tx) = self.sent_txs.iter_mut().rev().find(|tx| {
+                    tx.operator_type == operator_type
+                        && tx.nonce.0 == nonce
+                        && tx.sent_at_block < block_number
+                }) else {
+                    break;
+                };
+                let Some(inclusion) = tx.inclusion_in(&block) else {
+                    break;
+                };
+                tx.inclusion = Some(inclusion);
+            }
+        }
+    }
+}
+
+/// [`AbstractL1Interface`] emulating L1 based on historical fees. A transaction is included into the next L1 block
+/// if it pays at least the base fee (and the blob base fee for blob transactions) of this block.
+#[derive(Debug, Clone)]
+pub(crate) struct ReplayL1Interface {
+    operator_address: Address,
+    blob_operator_address: Option<Address>,
+    wait_confirmations: u64,
+    state: Arc<Mutex<ReplayL1State>>,
+}
+
+impl ReplayL1Interface {
+    fn new(
+        operator_address: Address,
+        blob_operator_address: Option<Address>,
+        initial_nonces: HashMap<OperatorType, u32>,
+        wait_confirmations: Option<u64>,
+    ) -> Self {
+        Self {
+            operator_address,
+            blob_operator_address,
+            wait_confirmations: wait_confirmations.unwrap_or(0),
+            state: Arc::new(Mutex::new(ReplayL1State {
+                initial_nonces,
+                ..ReplayL1State::default()
+            })),
+        }
+    }
+
+    /// Advances emulated L1 to the specified block, including pending transactions into it.
+    fn advance_to(&self, block: L1FeeRecord) {
+        let mut state = self.state.lock().unwrap();
+        state.current_block = Some(block);
+        state.include_txs();
+    }
+}
+
+#[async_trait]
+impl AbstractL1Interface for ReplayL1Interface {
+    fn supported_operator_types(&self) -> Vec<OperatorType> {
+        let mut result = vec![];
+        if self.blob_operator_address.is_some() {
+            result.push(OperatorType::Blob);
+        }
+        result.push(OperatorType::NonBlob);
+        result
+    }
+
+    async fn failure_reason(
+        &self,
+        _tx_hash: H256,
+        _operator_type: OperatorType,
+    ) -> Option<FailureInfo> {
+        None // Transactions never fail during the replay
+    }
+
+    #[cfg(test)]
+    async fn get_tx(
+        &self,
+        _tx_hash: H256,
+        _operator_type: OperatorType,
+    ) -> EnrichedClientResult<Option<web3::Transaction>> {
+        Ok(None)
+    }
+
+    async fn get_tx_status(
+        &self,
+        tx_hash: H256,
+        _operator_type: OperatorType,
+    ) -> Result<Option<ExecutedTxStatus>, EthSenderError> {
+        let state = self.state.lock().unwrap();
+        let Some((tx, inclusion)) = state
+            .sent_txs
+            .iter()
+            .find(|tx| tx.hash == tx_hash)
+            .and_then(|tx| Some((tx, tx.inclusion?)))
+        else {
+            return Ok(None);
+        };
+        Ok(Some(ExecutedTxStatus {
+            tx_hash,
+            success: true,
+            receipt: TransactionReceipt {
+                transaction_hash: tx_hash,
+                block_number: Some(inclusion.block_number.0.into()),
+                gas_used: Some(tx.gas_limit),
+                effective_gas_price: Some(inclusion.effective_gas_price.into()),
+                status: Some(1.into()),
+                ..TransactionReceipt::default()
+            },
+        }))
+    }
+
+    async fn send_raw_tx(
+        &self,
+        _tx_bytes: RawTransactionBytes,
+        operator_type: OperatorType,
+    ) -> EnrichedClientResult<H256> {
+        let mut state = self.state.lock().unwrap();
+        // `EthTxManager` always sends a transaction immediately after signing it.
+        let mut tx = state
+            .signed_txs
+            .remove(&operator_type)
+            .expect("sent transaction was not signed");
+        tx.sent_at_block = state.current_block_number();
+        let hash = tx.hash;
+        state.sent_txs.push(tx);
+        Ok(hash)
+    }
+
+    fn get_operator_account(&self, operator_type: OperatorType) -> Address {
+        match operator_type {
+            OperatorType::NonBlob => self.operator_address,
+            OperatorType::Blob => self
+                .blob_operator_address
+                .expect("blob operator is not configured"),
+            OperatorType::Gateway => panic!("replaying gateway settlement is not supported"),
+        }
+    }
+
+    async fn get_operator_nonce(
+        &self,
+        block_numbers: L1BlockNumbers,
+        operator_type: OperatorType,
+    ) -> Result<Option<OperatorNonce>, EthSenderError> {
+        let state = self.state.lock().unwrap();
+        Ok(Some(OperatorNonce {
+            finalized: Nonce(state.nonce_at(operator_type, block_numbers.finalized)),
+            latest: Nonce(state.nonce_at(operator_type, block_numbers.latest)),
+            fast_finality: Nonce(state.nonce_at(operator_type, block_numbers.fast_finality)),
+        }))
+    }
+
+    async fn sign_tx(
+        &self,
+        tx: &EthTx,
+        base_fee_per_gas: u64,
+        priority_fee_per_gas: u64,
+        blob_gas_price: Option<U256>,
+        gas: U256,
+        operator_type: OperatorType,
+        _max_gas_per_pubdata: Option<U256>,
+    ) -> SignedCallResult {
+        let max_fee_per_gas = base_fee_per_gas + priority_fee_per_gas;
+        let mut state = self.state.lock().unwrap();
+        // Hashes only need to be unique across all sent transactions.
+        let hash = H256::from_low_u64_be(state.sent_txs.len() as u64 + 1);
+        let blob_count = match &tx.blob_sidecar {
+            Some(EthTxBlobSidecar::EthTxBlobSidecarV1(sidecar)) => sidecar.blobs.len(),
+            None => 0,
+        };
+        let tx_type = if blob_count > 0 {
+            EIP_4844_TX_TYPE
+        } else {
+            EIP_1559_TX_TYPE
+        };
+        let mut raw_tx = vec![tx_type];
+        raw_tx.extend_from_slice(hash.as_bytes());
+
+        let replayed_tx = ReplayedTx {
+            eth_tx_id: tx.id,
+            operator_type,
+            nonce: tx.nonce,
+            hash,
+            max_fee_per_gas,
+            max_priority_fee_per_gas: priority_fee_per_gas,
+            max_fee_per_blob_gas: blob_gas_price,
+            gas_limit: gas,
+            blob_count,
+            sent_at_block: state.current_block_number(),
+            inclusion: None,
+        };
+        state.signed_txs.insert(operator_type, replayed_tx);
+        SignedCallResult::new(
+            RawTransactionBytes::new_unchecked(raw_tx),
+            priority_fee_per_gas.into(),
+            max_fee_per_gas.into(),
+            tx.nonce.0.into(),
+            hash,
+        )
+    }
+
+    async fn get_l1_block_numbers(
+        &self,
+        _operator_type: OperatorType,
+    ) -> Result<L1BlockNumbers, EthSenderError> {
+        let latest = self.state.lock().unwrap().current_block_number();
+        let finalized = L1BlockNumber(latest.0.saturating_sub(self.wait_confirmations as u32));
+        Ok(L1BlockNumbers {
+            fast_finality: finalized,
+            finalized,
+            latest,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_node_test_utils::create_l1_batch;
+    use zksync_types::{
+        aggregated_operations::{AggregatedActionType, L1BatchAggregatedActionType},
+        ProtocolVersion,
+    };
+
+    use super::*;
+
+    fn fee_record(block_number: u32, base_fee_per_gas: u64) -> L1FeeRecord {
+        L1FeeRecord {
+            block_number,
+            timestamp: u64::from(block_number) * 12,
+            base_fee_per_gas,
+            base_fee_per_blob_gas: 1,
+        }
+    }
+
+    fn eth_tx(nonce: u32) -> EthTx {
+        EthTx {
+            id: nonce + 1,
+            nonce: Nonce(nonce),
+            contract_address: Address::repeat_byte(2),
+            raw_tx: vec![],
+            tx_type: AggregatedActionType::L1Batch(L1BatchAggregatedActionType::Execute),
+            created_at_timestamp: 0,
+            predicted_gas_cost: Some(100_000),
+            from_addr: None,
+            blob_sidecar: None,
+            is_gateway: false,
+            chain_id: None,
+        }
+    }
+
+    async fn send_tx(l1: &ReplayL1Interface, tx: &EthTx, max_fee_per_gas: u64) -> H256 {
+        let signed = l1
+            .sign_tx(
+                tx,
+                max_fee_per_gas - 1,
+                1,
+                None,
+                100_000.into(),
+                OperatorType::NonBlob,
+                None,
+            )
+            .await;
+        l1.send_raw_tx(signed.raw_tx, OperatorType::NonBlob)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn replay_does_not_modify_database() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch(1))
+            .await
+            .unwrap();
+        let commit_tx_type = AggregatedActionType::L1Batch(L1BatchAggregatedActionType::Commit);
+        let mut eth_txs = vec![];
+        for nonce in 0..2 {
+            let eth_tx = storage
+                .eth_sender_dal()
+                .save_eth_tx(
+                    nonce,
+                    vec![],
+                    commit_tx_type,
+                    Address::repeat_byte(1),
+                    Some(100_000),
+                    None,
+                    None,
+                    false,
+                )
+                .await
+                .unwrap();
+            eth_txs.push(eth_tx);
+        }
+        // The second transaction is unconfirmed and isn't linked to replayed L1 batches, so the replay fails
+        // after it has deleted the first one.
+        let replayed_tx = &eth_txs[0];
+        storage
+            .blocks_dal()
+            .set_eth_tx_id_for_l1_batches(
+                L1BatchNumber(1)..=L1BatchNumber(1),
+                replayed_tx.id,
+                commit_tx_type,
+            )
+            .await
+            .unwrap();
+
+        let config = zksync_config::configs::eth_sender::EthConfig::for_tests();
+        let params = ReplayParams {
+            sender: config.sender,
+            gas_adjuster: config.gas_adjuster,
+            commitment_mode: L1BatchCommitmentMode::Rollup,
+            l1_chain_id: SLChainId(9),
+            l2_chain_id: L2ChainId::default(),
+            diamond_proxy_address: Address::repeat_byte(1),
+            validator_timelock_address: Address::repeat_byte(2),
+            operator_address: Address::repeat_byte(3),
+            blob_operator_address: None,
+            last_l1_batch_to_keep: L1BatchNumber(0),
+            execution_delay: Duration::ZERO,
+        };
+        let err = replay(pool, params, &[fee_record(100, 10)])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("unconfirmed eth_txs"), "{err:#}");
+
+        for eth_tx in &eth_txs {
+            let stored_tx = storage
+                .eth_sender_dal()
+                .get_eth_tx(eth_tx.id)
+                .await
+                .unwrap();
+            assert!(stored_tx.is_some(), "{eth_tx:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn replay_l1_includes_txs_paying_enough_fees() {
+        let l1 = ReplayL1Interface::new(
+            Address::repeat_byte(1),
+            None,
+            HashMap::from([(OperatorType::NonBlob, 0)]),
+            Some(1),
+        );
+        l1.advance_to(fee_record(100, 10));
+        let underpriced_hash = send_tx(&l1, &eth_tx(0), 10).await;
+        l1.advance_to(fee_record(101, 20));
+        let status = l1
+            .get_tx_status(underpriced_hash, OperatorType::NonBlob)
+            .await
+            .unwrap();
+        assert!(status.is_none());
+
+        // Replace the transaction and send the next one.
+        let hash = send_tx(&l1, &eth_tx(0), 25).await;
+        let next_hash = send_tx(&l1, &eth_tx(1), 25).await;
+        l1.advance_to(fee_record(102, 20));
+        let status = l1
+            .get_tx_status(hash, OperatorType::NonBlob)
+            .await
+            .unwrap()
+            .expect("transaction not included");
+        assert_eq!(status.receipt.block_number, Some(102.into()));
+        assert_eq!(status.receipt.effective_gas_price, Some(21.into()));
+        let status = l1
+            .get_tx_status(next_hash, OperatorType::NonBlob)
+            .await
+            .unwrap();
+        assert!(status.is_some());
+        let status = l1
+            .get_tx_status(underpriced_hash, OperatorType::NonBlob)
+            .await
+            .unwrap();
+        assert!(status.is_none());
+
+        let block_numbers = l1
+            .get_l1_block_numbers(OperatorType::NonBlob)
+            .await
+            .unwrap();
+        assert_eq!(block_numbers.latest, L1BlockNumber(102));
+        assert_eq!(block_numbers.finalized, L1BlockNumber(101));
+        let nonce = l1
+            .get_operator_nonce(block_numbers, OperatorType::NonBlob)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(nonce.latest, Nonce(2));
+        assert_eq!(nonce.finalized, Nonce(0));
+    }
+}
@@ -381,6 +381,21 @@ async fn resend_each_block(commitment_mode: L1BatchCommitmentMode) -> anyhow::Re
         30.into() // `5 * 3 * 2^1`
     );
 
+    // check that the fee bumping strategy is recorded for the resent transaction only
+    let mut history = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_tx_history_to_check(to_resend.id)
+        .await
+        .unwrap();
+    history.sort_by_key(|tx| tx.id);
+    let strategies: Vec<_> = history
+        .iter()
+        .map(|tx| tx.fee_bump_strategy.as_deref())
+        .collect();
+    assert_eq!(strategies, [None, Some("gas_adjuster")]);
+
     Ok(())
 }
 
@@ -962,7 +962,7 @@ async fn setup_batch_roots(
         let tx_hash = H256::random();
         connection
             .eth_sender_dal()
-            .insert_tx_history(eth_tx_id, 0, 0, None, None, tx_hash, &[], 0, None)
+            .insert_tx_history(eth_tx_id, 0, 0, None, None, tx_hash, &[], 0, None, None)
             .await
             .unwrap();
         connection
@@ -1,4 +1,7 @@
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use test_casing::test_casing;
 use zksync_contracts::BaseSystemContractsHashes;
@@ -10,7 +13,7 @@ use zksync_multivm::{
     },
     utils::derive_base_fee_and_gas_per_pubdata,
 };
-use zksync_node_test_utils::prepare_recovery_snapshot;
+use zksync_node_test_utils::{create_l2_transaction, prepare_recovery_snapshot};
 use zksync_system_constants::KNOWN_CODES_STORAGE_ADDRESS;
 use zksync_types::{
     block::L2BlockHasher,
@@ -18,9 +21,10 @@ use zksync_types::{
     commitment::{L1BatchCommitmentMode, PubdataParams},
     fee_model::{BatchFeeInput, PubdataIndependentBatchFeeModelInput},
     l2::L2Tx,
+    mempool::MempoolOrdering,
     protocol_upgrade::ProtocolUpgradeTx,
     protocol_version::ProtocolSemanticVersion,
-    AccountTreeId, Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersion,
+    AccountTreeId, Address, L1BatchNumber, L2BlockNumber, L2ChainId, Nonce, ProtocolVersion,
     ProtocolVersionId, StorageKey, TransactionTimeRangeConstraint, H256, U256,
 };
 
@@ -903,6 +907,66 @@ async fn test_mempool_with_timestamp_assertion() {
     );
 }
 
+#[tokio::test]
+async fn mempool_io_picks_transactions_by_priority_fee() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(2).await;
+    let tester = Tester::new(L1BatchCommitmentMode::Rollup);
+    tester.genesis(&connection_pool).await;
+    let tx_result = tester
+        .insert_l2_block(&connection_pool, 1, 5, BatchFeeInput::l1_pegged(55, 555))
+        .await;
+    tester
+        .insert_sealed_batch(&connection_pool, 1, &[tx_result.hash])
+        .await;
+
+    let filter = l2_tx_filter(
+        &tester.create_batch_fee_input_provider().await,
+        ProtocolVersionId::latest(),
+    )
+    .await
+    .unwrap();
+    let (mut mempool, guard) = tester
+        .create_test_mempool_io_with_ordering(connection_pool, MempoolOrdering::PriorityFee)
+        .await;
+    mempool.initialize().await.unwrap();
+
+    let gas_per_pubdata = u64::from(filter.gas_per_pubdata);
+    let first_tx = create_l2_transaction(filter.fee_per_gas, gas_per_pubdata);
+    // The follow-up transaction from the same account has the highest priority fee, but it must still wait
+    // for the preceding nonce.
+    let mut follow_up_tx = create_l2_transaction(filter.fee_per_gas, gas_per_pubdata);
+    follow_up_tx.common_data.initiator_address = first_tx.initiator_account();
+    follow_up_tx.common_data.nonce = Nonce(1);
+    follow_up_tx.common_data.fee.max_priority_fee_per_gas = filter.fee_per_gas.into();
+    // Received later than other transactions, but pays a non-zero priority fee.
+    let mut priority_tx = create_l2_transaction(filter.fee_per_gas, gas_per_pubdata);
+    priority_tx.common_data.fee.max_priority_fee_per_gas = (filter.fee_per_gas / 2).into();
+    priority_tx.received_timestamp_ms = first_tx.received_timestamp_ms + 1;
+
+    guard.insert(
+        [&first_tx, &follow_up_tx, &priority_tx]
+            .into_iter()
+            .map(|tx| (tx.clone().into(), TransactionTimeRangeConstraint::default()))
+            .collect(),
+        HashMap::new(),
+    );
+
+    let l2_block_timestamp = seconds_since_epoch();
+    for expected_tx in [&priority_tx, &first_tx, &follow_up_tx] {
+        let tx = mempool
+            .wait_for_next_tx(Duration::from_secs(2), l2_block_timestamp)
+            .await
+            .unwrap()
+            .expect("No expected transaction in the mempool");
+        assert_eq!(tx.hash(), expected_tx.hash());
+    }
+    let next_tx = mempool
+        .wait_for_next_tx(Duration::from_millis(100), l2_block_timestamp)
+        .await
+        .unwrap();
+    assert!(next_tx.is_none());
+}
+
 #[tokio::test]
 async fn test_batch_params_with_protocol_upgrade_tx() {
     let connection_pool = ConnectionPool::<Core>::constrained_test_pool(2).await;
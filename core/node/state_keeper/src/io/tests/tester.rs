@@ -31,6 +31,7 @@ use zksync_types::{
     commitment::L1BatchCommitmentMode,
     fee_model::{BaseTokenConversionRatio, BatchFeeInput, FeeModelConfig, FeeModelConfigV2},
     l2::L2Tx,
+    mempool::MempoolOrdering,
     protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
     pubdata_da::PubdataSendingMode,
     settlement::SettlementLayer,
@@ -127,6 +128,15 @@ impl Tester {
     pub(super) async fn create_test_mempool_io(
         &self,
         pool: ConnectionPool<Core>,
+    ) -> (MempoolIO, MempoolGuard) {
+        self.create_test_mempool_io_with_ordering(pool, MempoolOrdering::Fifo)
+            .await
+    }
+
+    pub(super) async fn create_test_mempool_io_with_ordering(
+        &self,
+        pool: ConnectionPool<Core>,
+        ordering: MempoolOrdering,
     ) -> (MempoolIO, MempoolGuard) {
         let gas_adjuster = Arc::new(self.create_gas_adjuster().await);
         let batch_fee_input_provider = MainNodeFeeInputProvider::new(
@@ -143,7 +153,7 @@ impl Tester {
         );
 
         let chain_id = SLChainId(505);
-        let mempool = MempoolGuard::new(PriorityOpId(0), 100, None, None);
+        let mempool = MempoolGuard::new(PriorityOpId(0), 100, None, None, ordering);
         let config = StateKeeperConfig {
             minimal_l2_gas_price: self.minimal_l2_gas_price(),
             validation_computational_gas_limit: BATCH_COMPUTATIONAL_GAS_LIMIT,
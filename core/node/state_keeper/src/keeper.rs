@@ -25,7 +25,10 @@ use crate::{
     health::StateKeeperHealthDetails,
     io::{BatchInitParams, IoCursor, L1BatchParams, L2BlockParams, OutputHandler, StateKeeperIO},
     metrics::{AGGREGATION_METRICS, KEEPER_METRICS},
-    seal_criteria::{ConditionalSealer, SealData, SealResolution, UnexecutableReason},
+    preconfirmations::PreconfirmationIssuer,
+    seal_criteria::{
+        ConditionalSealer, ContractUsage, SealData, SealResolution, UnexecutableReason,
+    },
     updates::UpdatesManager,
     utils::is_canceled,
 };
@@ -127,6 +130,7 @@ pub struct StateKeeperBuilder {
     health_updater: HealthUpdater,
     deployment_tx_filter: Option<DeploymentTxFilter>,
     leader_rotation: bool,
+    preconfirmation_issuer: Option<PreconfirmationIssuer>,
 }
 
 /// Helper struct that encapsulates some private state keeper methods.
@@ -140,6 +144,7 @@ pub(super) struct StateKeeperInner {
     health_updater: HealthUpdater,
     deployment_tx_filter: Option<DeploymentTxFilter>,
     leader_rotation: bool,
+    preconfirmation_issuer: Option<PreconfirmationIssuer>,
 }
 
 impl From<StateKeeperBuilder> for StateKeeperInner {
@@ -153,6 +158,7 @@ impl From<StateKeeperBuilder> for StateKeeperInner {
             health_updater: b.health_updater,
             deployment_tx_filter: b.deployment_tx_filter,
             leader_rotation: b.leader_rotation,
+            preconfirmation_issuer: b.preconfirmation_issuer,
         }
     }
 }
@@ -175,6 +181,7 @@ impl StateKeeperBuilder {
             health_updater: ReactiveHealthCheck::new("state_keeper").1,
             deployment_tx_filter,
             leader_rotation: false,
+            preconfirmation_issuer: None,
         }
     }
 
@@ -183,6 +190,12 @@ impl StateKeeperBuilder {
         self
     }
 
+    /// Enables issuing preconfirmations for transactions included into the open L2 block.
+    pub fn with_preconfirmation_issuer(mut self, issuer: PreconfirmationIssuer) -> Self {
+        self.preconfirmation_issuer = Some(issuer);
+        self
+    }
+
     pub async fn build(
         self,
         stop_receiver: &watch::Receiver<bool>,
@@ -826,11 +839,18 @@ impl StateKeeperInner {
                 let tx_writes_metrics =
                     StorageWritesDeduplicator::apply_on_empty_state(logs_to_apply_iter);
 
+                let tx_contract_usage =
+                    ContractUsage::for_transaction(&tx, tx_execution_metrics.gas_used);
+                let pending_contract_usage = ContractUsage::target(&tx)
+                    .map(|contract| updates_manager.pending_contract_usage(contract))
+                    .unwrap_or_default();
+
                 let tx_data = SealData {
                     execution_metrics: **tx_execution_metrics,
                     cumulative_size: encoding_len,
                     writes_metrics: tx_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    contract_usage: tx_contract_usage,
                 };
                 let block_data = SealData {
                     execution_metrics: tx_data.execution_metrics
@@ -839,6 +859,7 @@ impl StateKeeperInner {
                         + updates_manager.pending_txs_encoding_size(),
                     writes_metrics: block_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    contract_usage: tx_contract_usage + pending_contract_usage,
                 };
                 let is_tx_l1 = tx.is_l1() as usize;
 
@@ -857,6 +878,35 @@ impl StateKeeperInner {
         Ok((resolution, exec_result))
     }
 
+    /// Checks whether a successfully executed transaction should be postponed to the next L2 block
+    /// because its target contract has exhausted the per-L2 block quota. The first transaction in an L2 block
+    /// is never postponed; if it exceeds the quota on its own, it is rejected by the conditional sealer.
+    fn should_exclude_from_l2_block(
+        &self,
+        updates_manager: &UpdatesManager,
+        tx: &Transaction,
+        exec_result: &TxExecutionResult,
+    ) -> bool {
+        let TxExecutionResult::Success { tx_metrics, .. } = exec_result else {
+            return false;
+        };
+        let Some(contract) = ContractUsage::target(tx) else {
+            return false;
+        };
+        let l2_block = updates_manager.last_pending_l2_block();
+        if l2_block.executed_transactions.is_empty() {
+            return false;
+        }
+
+        let l2_block_usage = l2_block
+            .contract_usage
+            .get(&contract)
+            .copied()
+            .unwrap_or_default()
+            + ContractUsage::for_transaction(tx, tx_metrics.gas_used);
+        self.sealer.should_exclude_from_l2_block(l2_block_usage)
+    }
+
     fn report_seal_criteria_capacity(&self, manager: &UpdatesManager) {
         let block_writes_metrics = manager.storage_writes_deduplicator().metrics();
 
@@ -864,7 +914,8 @@ impl StateKeeperInner {
             execution_metrics: manager.pending_execution_metrics(),
             cumulative_size: manager.pending_txs_encoding_size(),
             writes_metrics: block_writes_metrics,
-            gas_remaining: u32::MAX, // not used
+            gas_remaining: u32::MAX,                  // not used
+            contract_usage: ContractUsage::default(), // not used
         };
 
         let capacities = self.sealer.capacity_filled(
@@ -1039,6 +1090,26 @@ impl StateKeeper {
             .process_one_tx(batch_executor, updates_manager, tx.clone())
             .await?;
 
+        if matches!(
+            seal_resolution,
+            SealResolution::NoSeal | SealResolution::IncludeAndSeal
+        ) && inner.should_exclude_from_l2_block(updates_manager, &tx, &exec_result)
+        {
+            batch_executor.rollback_last_tx().await.with_context(|| {
+                format!("failed rolling back transaction {tx_hash:?} in batch executor")
+            })?;
+            inner
+                .io
+                .rollback(tx)
+                .await
+                .with_context(|| format!("failed rolling back transaction {tx_hash:?} in I/O"))?;
+            tracing::debug!(
+                "L2 block #{} should be sealed as per per-contract quota after executing transaction {tx_hash}",
+                updates_manager.last_pending_l2_block().number
+            );
+            return Ok(Some(ProcessBlockIterationOutcome::SealBlock));
+        }
+
         let latency = KEEPER_METRICS.match_seal_resolution.start();
         match &seal_resolution {
             SealResolution::NoSeal | SealResolution::IncludeAndSeal => {
@@ -1059,6 +1130,9 @@ impl StateKeeper {
                     *tx_execution_metrics,
                     call_tracer_result,
                 );
+                if let Some(issuer) = &inner.preconfirmation_issuer {
+                    issuer.issue_for_last_tx(updates_manager);
+                }
             }
             SealResolution::ExcludeAndSeal => {
                 batch_executor.rollback_last_tx().await.with_context(|| {
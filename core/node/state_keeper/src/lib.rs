@@ -6,6 +6,7 @@ pub use self::{
     keeper::{StateKeeper, StateKeeperBuilder},
     mempool_actor::MempoolFetcher,
     mempool_guard::MempoolGuard,
+    preconfirmations::PreconfirmationIssuer,
     seal_criteria::SequencerSealer,
     state_keeper_storage::AsyncRocksdbCache,
     updates::UpdatesManager,
@@ -19,6 +20,7 @@ mod mempool_actor;
 pub(crate) mod mempool_guard;
 pub mod metrics;
 pub mod node;
+mod preconfirmations;
 pub mod seal_criteria;
 mod state_keeper_storage;
 pub mod testonly;
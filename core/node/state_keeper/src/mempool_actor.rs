@@ -209,7 +209,8 @@ mod tests {
     use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
     use zksync_node_test_utils::create_l2_transaction;
     use zksync_types::{
-        u256_to_h256, L2BlockNumber, PriorityOpId, ProtocolVersionId, StorageLog, H256,
+        mempool::MempoolOrdering, u256_to_h256, L2BlockNumber, PriorityOpId, ProtocolVersionId,
+        StorageLog, H256,
     };
 
     use super::*;
@@ -224,6 +225,7 @@ mod tests {
         l1_to_l2_txs_paused: false,
         high_priority_l2_tx_initiator: None,
         high_priority_l2_tx_protocol_version: Some(29),
+        ordering: MempoolOrdering::Fifo,
     };
 
     #[tokio::test]
@@ -279,6 +281,7 @@ mod tests {
             TEST_MEMPOOL_CONFIG
                 .high_priority_l2_tx_protocol_version
                 .map(|v| (v as u16).try_into().unwrap()),
+            TEST_MEMPOOL_CONFIG.ordering,
         );
         let fee_params_provider: Arc<dyn BatchFeeModelInputProvider> =
             Arc::new(MockBatchFeeParamsProvider::default());
@@ -349,6 +352,7 @@ mod tests {
             TEST_MEMPOOL_CONFIG
                 .high_priority_l2_tx_protocol_version
                 .map(|v| (v as u16).try_into().unwrap()),
+            TEST_MEMPOOL_CONFIG.ordering,
         );
         let fee_params_provider: Arc<dyn BatchFeeModelInputProvider> =
             Arc::new(MockBatchFeeParamsProvider::default());
@@ -402,6 +406,7 @@ mod tests {
             TEST_MEMPOOL_CONFIG
                 .high_priority_l2_tx_protocol_version
                 .map(|v| (v as u16).try_into().unwrap()),
+            TEST_MEMPOOL_CONFIG.ordering,
         );
         let fee_params_provider: Arc<dyn BatchFeeModelInputProvider> =
             Arc::new(MockBatchFeeParamsProvider::default());
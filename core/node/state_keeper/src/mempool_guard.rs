@@ -7,7 +7,8 @@ use tokio::sync::{Mutex as TokioMutex, MutexGuard};
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_mempool::{AdvanceInput, L2TxFilter, MempoolInfo, MempoolStore};
 use zksync_types::{
-    Address, Nonce, PriorityOpId, ProtocolVersionId, Transaction, TransactionTimeRangeConstraint,
+    mempool::MempoolOrdering, Address, Nonce, PriorityOpId, ProtocolVersionId, Transaction,
+    TransactionTimeRangeConstraint,
 };
 
 use super::metrics::StateKeeperGauges;
@@ -24,17 +25,20 @@ impl MempoolGuard {
         capacity: u64,
         high_priority_l2_tx_initiator: Option<Address>,
         high_priority_l2_tx_protocol_version: Option<ProtocolVersionId>,
+        ordering: MempoolOrdering,
     ) -> Self {
         let next_priority_id = storage_processor
             .transactions_dal()
             .next_priority_id()
             .await;
-        Self::new(
+        let store = MempoolStore::new(
             next_priority_id,
             capacity,
             high_priority_l2_tx_initiator,
             high_priority_l2_tx_protocol_version,
         )
+        .with_ordering(ordering);
+        Self::from_store(store)
     }
 
     pub(super) fn new(
@@ -42,13 +46,19 @@ impl MempoolGuard {
         capacity: u64,
         high_priority_l2_tx_initiator: Option<Address>,
         high_priority_l2_tx_protocol_version: Option<ProtocolVersionId>,
+        ordering: MempoolOrdering,
     ) -> Self {
         let store = MempoolStore::new(
             next_priority_id,
             capacity,
             high_priority_l2_tx_initiator,
             high_priority_l2_tx_protocol_version,
-        );
+        )
+        .with_ordering(ordering);
+        Self::from_store(store)
+    }
+
+    fn from_store(store: MempoolStore) -> Self {
         Self {
             mempool: Arc::new(Mutex::new(store)),
             critical_mutex: Arc::new(TokioMutex::new(())),
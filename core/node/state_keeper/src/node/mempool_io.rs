@@ -100,6 +100,7 @@ impl MempoolIOLayer {
             self.mempool_config
                 .high_priority_l2_tx_protocol_version
                 .map(|v| (v as u16).try_into().unwrap()),
+            self.mempool_config.ordering,
         )
         .await;
         mempool.register_metrics();
@@ -7,19 +7,23 @@ use zksync_node_framework::{
     service::ShutdownHook, task::TaskKind, FromContext, IntoContext, StopReceiver, Task, TaskId,
     WiringError, WiringLayer,
 };
+use zksync_shared_resources::api::TxPreconfirmations;
 use zksync_state::{AsyncCatchupTask, RocksdbStorageOptions};
 use zksync_storage::RocksDB;
-use zksync_types::try_stoppable;
+use zksync_types::{try_stoppable, K256PrivateKey, L2ChainId};
 use zksync_vm_executor::whitelist::{DeploymentTxFilter, SharedAllowList};
 
 use super::resources::{BatchExecutorResource, OutputHandlerResource, StateKeeperIOResource};
-use crate::{seal_criteria::ConditionalSealer, AsyncRocksdbCache, StateKeeperBuilder};
+use crate::{
+    seal_criteria::ConditionalSealer, AsyncRocksdbCache, PreconfirmationIssuer, StateKeeperBuilder,
+};
 
 /// Wiring layer for the state keeper.
 #[derive(Debug)]
 pub struct StateKeeperLayer {
     state_keeper_db_path: PathBuf,
     rocksdb_options: RocksdbStorageOptions,
+    preconfirmation_signer: Option<(K256PrivateKey, L2ChainId)>,
 }
 
 #[derive(Debug, FromContext)]
@@ -32,6 +36,8 @@ pub struct Input {
     replica_pool: PoolResource<ReplicaPool>,
     shared_allow_list: Option<SharedAllowList>,
     #[context(default)]
+    tx_preconfirmations: TxPreconfirmations,
+    #[context(default)]
     app_health: Arc<AppHealthCheck>,
 }
 
@@ -49,8 +55,19 @@ impl StateKeeperLayer {
         Self {
             state_keeper_db_path,
             rocksdb_options,
+            preconfirmation_signer: None,
         }
     }
+
+    /// Enables signing and publishing transaction preconfirmations with the specified sequencer key.
+    pub fn with_preconfirmation_signer(
+        mut self,
+        signing_key: K256PrivateKey,
+        chain_id: L2ChainId,
+    ) -> Self {
+        self.preconfirmation_signer = Some((signing_key, chain_id));
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -96,7 +113,7 @@ impl WiringLayer for StateKeeperLayer {
             .await?;
         rocksdb_catchup = rocksdb_catchup.with_recovery_pool(recovery_pool);
 
-        let state_keeper_builder = StateKeeperBuilder::new(
+        let mut state_keeper_builder = StateKeeperBuilder::new(
             io,
             batch_executor_base,
             output_handler,
@@ -104,6 +121,11 @@ impl WiringLayer for StateKeeperLayer {
             Arc::new(storage_factory),
             input.shared_allow_list.map(DeploymentTxFilter::new),
         );
+        if let Some((signing_key, chain_id)) = self.preconfirmation_signer {
+            let issuer =
+                PreconfirmationIssuer::new(signing_key, chain_id, input.tx_preconfirmations);
+            state_keeper_builder = state_keeper_builder.with_preconfirmation_issuer(issuer);
+        }
 
         input
             .app_health
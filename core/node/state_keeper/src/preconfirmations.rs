@@ -0,0 +1,146 @@
+//! Preconfirmations for transactions included into the open L2 block.
+
+use zksync_multivm::interface::TxExecutionStatus;
+use zksync_shared_resources::api::TxPreconfirmations;
+use zksync_types::{
+    api::{SignedTransactionPreconfirmation, TransactionPreconfirmation},
+    K256PrivateKey, L2ChainId,
+};
+
+use crate::updates::UpdatesManager;
+
+/// Signs preconfirmations for transactions as soon as they are included into the open L2 block, and publishes them
+/// via [`TxPreconfirmations`].
+///
+/// A preconfirmation is a promise by the sequencer rather than a guarantee; the transaction only becomes final
+/// once the L2 block containing it is sealed.
+#[derive(Debug)]
+pub struct PreconfirmationIssuer {
+    signing_key: K256PrivateKey,
+    chain_id: L2ChainId,
+    preconfirmations: TxPreconfirmations,
+}
+
+impl PreconfirmationIssuer {
+    pub fn new(
+        signing_key: K256PrivateKey,
+        chain_id: L2ChainId,
+        preconfirmations: TxPreconfirmations,
+    ) -> Self {
+        preconfirmations.set_signer(signing_key.address());
+        Self {
+            signing_key,
+            chain_id,
+            preconfirmations,
+        }
+    }
+
+    /// Issues a preconfirmation for the last transaction included into the open L2 block.
+    pub(crate) fn issue_for_last_tx(&self, updates_manager: &UpdatesManager) {
+        let l2_block = updates_manager.last_pending_l2_block();
+        let Some(tx) = l2_block.executed_transactions.last() else {
+            return;
+        };
+
+        let preconfirmation = TransactionPreconfirmation {
+            transaction_hash: tx.hash,
+            l2_block_number: l2_block.number.0.into(),
+            l2_block_timestamp: l2_block.timestamp().into(),
+            l1_batch_number: updates_manager.l1_batch_number().0.into(),
+            transaction_index: (l2_block.executed_transactions.len() as u64 - 1).into(),
+            status: u64::from(tx.execution_status == TxExecutionStatus::Success).into(),
+        };
+        match SignedTransactionPreconfirmation::new(
+            preconfirmation,
+            &self.signing_key,
+            self.chain_id,
+        ) {
+            Ok(signed) => self.preconfirmations.publish(signed),
+            Err(err) => {
+                tracing::warn!(
+                    "Failed issuing preconfirmation for transaction {:?}: {err:#}",
+                    tx.hash
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_multivm::interface::{
+        ExecutionResult, VmExecutionMetrics, VmExecutionResultAndLogs, VmRevertReason,
+    };
+    use zksync_types::H256;
+
+    use super::*;
+    use crate::tests::{create_execution_result, create_transaction, create_updates_manager};
+
+    #[test]
+    fn issuing_preconfirmations() {
+        let signing_key = K256PrivateKey::from_bytes(H256::repeat_byte(1)).unwrap();
+        let chain_id = L2ChainId::from(270);
+        let preconfirmations = TxPreconfirmations::default();
+        let mut preconfirmations_rx = preconfirmations.subscribe();
+        let issuer = PreconfirmationIssuer::new(signing_key.clone(), chain_id, preconfirmations);
+        assert_eq!(
+            issuer.preconfirmations.signer(),
+            Some(signing_key.address())
+        );
+
+        let mut updates_manager = create_updates_manager();
+        // No transactions in the open L2 block; nothing should be issued.
+        issuer.issue_for_last_tx(&updates_manager);
+        assert!(preconfirmations_rx.try_recv().is_err());
+
+        let tx = create_transaction(10, 100);
+        let tx_hash = tx.hash();
+        updates_manager.extend_from_executed_transaction(
+            tx,
+            create_execution_result([]),
+            VmExecutionMetrics::default(),
+            vec![],
+        );
+        issuer.issue_for_last_tx(&updates_manager);
+
+        let signed = preconfirmations_rx.try_recv().unwrap();
+        assert_eq!(
+            signed.recover_signer(chain_id).unwrap(),
+            signing_key.address()
+        );
+        let l2_block = updates_manager.last_pending_l2_block();
+        assert_eq!(
+            signed.preconfirmation,
+            TransactionPreconfirmation {
+                transaction_hash: tx_hash,
+                l2_block_number: l2_block.number.0.into(),
+                l2_block_timestamp: l2_block.timestamp().into(),
+                l1_batch_number: updates_manager.l1_batch_number().0.into(),
+                transaction_index: 0.into(),
+                status: 1.into(),
+            }
+        );
+
+        let reverted_tx = create_transaction(10, 100);
+        let reverted_tx_hash = reverted_tx.hash();
+        let revert_result = VmExecutionResultAndLogs::mock(ExecutionResult::Revert {
+            output: VmRevertReason::General {
+                msg: "oops".to_owned(),
+                data: vec![],
+            },
+        });
+        updates_manager.extend_from_executed_transaction(
+            reverted_tx,
+            revert_result,
+            VmExecutionMetrics::default(),
+            vec![],
+        );
+        issuer.issue_for_last_tx(&updates_manager);
+
+        let signed = preconfirmations_rx.try_recv().unwrap();
+        assert_eq!(signed.preconfirmation.transaction_hash, reverted_tx_hash);
+        assert_eq!(signed.preconfirmation.transaction_index, 1.into());
+        assert_eq!(signed.preconfirmation.status, 0.into());
+        assert!(preconfirmations_rx.try_recv().is_err());
+    }
+}
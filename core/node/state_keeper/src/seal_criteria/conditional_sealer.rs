@@ -18,7 +18,9 @@ use zksync_multivm::{
 use zksync_types::{commitment::L1BatchCommitmentMode, ProtocolVersionId, Transaction};
 use zksync_vm_executor::interface::TransactionFilter;
 
-use super::{criteria, SealCriterion, SealData, SealResolution, AGGREGATION_METRICS};
+use super::{
+    criteria, ContractUsage, SealCriterion, SealData, SealResolution, AGGREGATION_METRICS,
+};
 
 /// Checks if an L1 batch should be sealed after executing a transaction.
 pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
@@ -44,6 +46,13 @@ pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
         block_data: &SealData,
         protocol_version: ProtocolVersionId,
     ) -> Vec<(&'static str, f64)>;
+
+    /// Returns whether the executed transaction should be excluded from the current L2 block and become the first
+    /// transaction in the next one. `l2_block_usage` is the usage of the transaction's target contract
+    /// in the current L2 block, including the transaction itself.
+    fn should_exclude_from_l2_block(&self, _l2_block_usage: ContractUsage) -> bool {
+        false
+    }
 }
 
 /// Implementation of [`ConditionalSealer`] used by the main node.
@@ -166,6 +175,10 @@ impl ConditionalSealer for SequencerSealer {
             })
             .collect()
     }
+
+    fn should_exclude_from_l2_block(&self, l2_block_usage: ContractUsage) -> bool {
+        criteria::ContractQuotaCriterion::exceeds_l2_block_quota(&self.config, l2_block_usage)
+    }
 }
 
 /// Sealers excluding pubdata for verifying blocks produced by sequencer
@@ -191,7 +204,9 @@ fn default_sealers() -> Vec<Box<dyn SealCriterion>> {
 
 impl SequencerSealer {
     pub fn new(config: SealCriteriaConfig) -> Self {
-        let sealers = default_sealers();
+        let mut sealers = default_sealers();
+        // Per-contract quotas are a sequencer policy rather than a protocol limit, so they are not checked by `PanicSealer`.
+        sealers.push(Box::new(criteria::ContractQuotaCriterion));
         Self { config, sealers }
     }
 
@@ -279,6 +294,10 @@ impl PanicSealer {
             close_block_at_eth_params_percentage: 1.0,
             close_block_at_gas_percentage: 1.0,
             max_circuits_per_batch: get_max_batch_base_layer_circuits(protocol_version.into()),
+            max_gas_per_contract_per_l2_block: None,
+            max_txs_per_contract_per_l2_block: None,
+            max_gas_per_contract_per_batch: None,
+            max_txs_per_contract_per_batch: None,
         }
     }
 }
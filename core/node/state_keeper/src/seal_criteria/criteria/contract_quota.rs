@@ -0,0 +1,176 @@
+use zksync_types::ProtocolVersionId;
+
+use crate::seal_criteria::{
+    ContractUsage, SealCriteriaConfig, SealCriterion, SealData, SealResolution, UnexecutableReason,
+};
+
+/// Caps gas and the number of transactions that a single target contract may use per L2 block and per L1 batch,
+/// so that a hot contract doesn't starve other transactions.
+///
+/// The L1 batch limits are checked in [`SealCriterion::should_seal()`]. The L2 block limits are checked separately
+/// in [`Self::exceeds_l2_block_quota()`], since sealing an L2 block is outside the scope of [`SealCriterion`]s.
+#[derive(Debug)]
+pub struct ContractQuotaCriterion;
+
+impl ContractQuotaCriterion {
+    fn exceeds(usage: ContractUsage, max_gas: Option<u64>, max_txs: Option<u32>) -> bool {
+        max_gas.is_some_and(|max_gas| usage.gas_used > max_gas)
+            || max_txs.is_some_and(|max_txs| usage.tx_count > max_txs)
+    }
+
+    /// Checks whether the contract usage in an L2 block exceeds the per-block quota. Used by the state keeper
+    /// to postpone transactions to the next L2 block.
+    pub(crate) fn exceeds_l2_block_quota(
+        config: &SealCriteriaConfig,
+        usage: ContractUsage,
+    ) -> bool {
+        Self::exceeds(
+            usage,
+            config.max_gas_per_contract_per_l2_block.map(u64::from),
+            config.max_txs_per_contract_per_l2_block.map(u32::from),
+        )
+    }
+
+    fn exceeds_batch_quota(config: &SealCriteriaConfig, usage: ContractUsage) -> bool {
+        Self::exceeds(
+            usage,
+            config.max_gas_per_contract_per_batch.map(u64::from),
+            config.max_txs_per_contract_per_batch.map(u32::from),
+        )
+    }
+}
+
+impl SealCriterion for ContractQuotaCriterion {
+    fn should_seal(
+        &self,
+        config: &SealCriteriaConfig,
+        _tx_count: usize,
+        _l1_tx_count: usize,
+        _interop_roots_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        _protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        if Self::exceeds_l2_block_quota(config, tx_data.contract_usage)
+            || Self::exceeds_batch_quota(config, tx_data.contract_usage)
+        {
+            UnexecutableReason::ContractQuotaExceeded.into()
+        } else if Self::exceeds_batch_quota(config, block_data.contract_usage) {
+            SealResolution::ExcludeAndSeal
+        } else {
+            SealResolution::NoSeal
+        }
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "contract_quota"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroU32, NonZeroU64};
+
+    use super::*;
+
+    #[test]
+    fn test_contract_quota_seal_criterion() {
+        let config = SealCriteriaConfig {
+            max_gas_per_contract_per_l2_block: NonZeroU64::new(1_000),
+            max_txs_per_contract_per_l2_block: NonZeroU32::new(2),
+            max_gas_per_contract_per_batch: NonZeroU64::new(5_000),
+            max_txs_per_contract_per_batch: NonZeroU32::new(5),
+            ..SealCriteriaConfig::for_tests()
+        };
+        let criterion = ContractQuotaCriterion;
+        let seal_data = |gas_used, tx_count| SealData {
+            contract_usage: ContractUsage { gas_used, tx_count },
+            ..SealData::default()
+        };
+
+        let tx_data = seal_data(500, 1);
+        let resolution = criterion.should_seal(
+            &config,
+            3,
+            0,
+            0,
+            &seal_data(2_000, 3),
+            &tx_data,
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+
+        let resolution = criterion.should_seal(
+            &config,
+            6,
+            0,
+            0,
+            &seal_data(3_000, 6),
+            &tx_data,
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::ExcludeAndSeal);
+
+        let resolution = criterion.should_seal(
+            &config,
+            4,
+            0,
+            0,
+            &seal_data(5_500, 4),
+            &tx_data,
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::ExcludeAndSeal);
+
+        // A transaction exceeding the L2 block quota on its own can never be included.
+        let tx_data = seal_data(1_001, 1);
+        let resolution = criterion.should_seal(
+            &config,
+            1,
+            0,
+            0,
+            &tx_data,
+            &tx_data,
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(
+            resolution,
+            SealResolution::Unexecutable(UnexecutableReason::ContractQuotaExceeded)
+        );
+
+        assert!(!ContractQuotaCriterion::exceeds_l2_block_quota(
+            &config,
+            ContractUsage {
+                gas_used: 1_000,
+                tx_count: 2
+            }
+        ));
+        assert!(ContractQuotaCriterion::exceeds_l2_block_quota(
+            &config,
+            ContractUsage {
+                gas_used: 900,
+                tx_count: 3
+            }
+        ));
+        assert!(ContractQuotaCriterion::exceeds_l2_block_quota(
+            &config,
+            ContractUsage {
+                gas_used: 1_100,
+                tx_count: 2
+            }
+        ));
+
+        // Quotas are disabled by default.
+        let config = SealCriteriaConfig::for_tests();
+        let resolution = criterion.should_seal(
+            &config,
+            1,
+            0,
+            0,
+            &seal_data(u64::MAX, u32::MAX),
+            &seal_data(u64::MAX, 1),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+    }
+}
@@ -1,3 +1,4 @@
+mod contract_quota;
 mod gas_for_batch_tip;
 mod geometry_seal_criteria;
 mod interop_roots;
@@ -8,8 +9,9 @@ mod slots;
 mod tx_encoding_size;
 
 pub(crate) use self::{
-    gas_for_batch_tip::GasForBatchTipCriterion, geometry_seal_criteria::CircuitsCriterion,
-    interop_roots::InteropRootsCriterion, l1_l2_txs::L1L2TxsCriterion,
-    l2_l1_logs::L2L1LogsCriterion, pubdata_bytes::PubDataBytesCriterion, slots::SlotsCriterion,
+    contract_quota::ContractQuotaCriterion, gas_for_batch_tip::GasForBatchTipCriterion,
+    geometry_seal_criteria::CircuitsCriterion, interop_roots::InteropRootsCriterion,
+    l1_l2_txs::L1L2TxsCriterion, l2_l1_logs::L2L1LogsCriterion,
+    pubdata_bytes::PubDataBytesCriterion, slots::SlotsCriterion,
     tx_encoding_size::TxEncodingSizeCriterion,
 };
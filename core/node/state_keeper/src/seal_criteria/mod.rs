@@ -10,14 +10,14 @@
 //! Maintaining all the criteria in one place has proven itself to be very error-prone,
 //! thus now every criterion is independent of the others.
 
-use std::fmt;
+use std::{fmt, ops};
 
 use zksync_config::configs::chain::SealCriteriaConfig;
 use zksync_multivm::{
     interface::{DeduplicatedWritesMetrics, Halt, TransactionExecutionMetrics, VmExecutionMetrics},
     vm_latest::TransactionVmExt,
 };
-use zksync_types::{ProtocolVersionId, Transaction};
+use zksync_types::{Address, ExecuteTransactionCommon, ProtocolVersionId, Transaction};
 
 pub use self::{
     conditional_sealer::{ConditionalSealer, NoopSealer, PanicSealer, SequencerSealer},
@@ -68,6 +68,7 @@ pub enum UnexecutableReason {
     NotEnoughGasProvided,
     TooMuchUserL2L1Logs,
     DeploymentNotAllowed,
+    ContractQuotaExceeded,
 }
 
 impl UnexecutableReason {
@@ -84,6 +85,7 @@ impl UnexecutableReason {
             UnexecutableReason::NotEnoughGasProvided => "NotEnoughGasProvided",
             UnexecutableReason::TooMuchUserL2L1Logs => "TooMuchUserL2L1Logs",
             UnexecutableReason::DeploymentNotAllowed => "DeploymentNotAllowed",
+            UnexecutableReason::ContractQuotaExceeded => "ContractQuotaExceeded",
         }
     }
 }
@@ -110,6 +112,9 @@ impl fmt::Display for UnexecutableReason {
             UnexecutableReason::NotEnoughGasProvided => write!(f, "Not enough gas provided"),
             UnexecutableReason::TooMuchUserL2L1Logs => write!(f, "Too much user l2 l1 logs"),
             UnexecutableReason::DeploymentNotAllowed => write!(f, "Deployment not allowed"),
+            UnexecutableReason::ContractQuotaExceeded => {
+                write!(f, "Per-contract gas or transaction quota exceeded")
+            }
         }
     }
 }
@@ -158,6 +163,54 @@ impl SealResolution {
     }
 }
 
+/// Gas and number of transactions attributed to a single target contract, either by a transaction,
+/// or cumulatively in an L2 block / L1 batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContractUsage {
+    pub gas_used: u64,
+    pub tx_count: u32,
+}
+
+impl ContractUsage {
+    /// Returns the contract that the `transaction` is attributed to. Only L2 transactions calling a contract
+    /// are attributed; L1 and upgrade transactions, and EVM deployments are not subject to per-contract quotas.
+    pub fn target(transaction: &Transaction) -> Option<Address> {
+        match &transaction.common_data {
+            ExecuteTransactionCommon::L2(_) => transaction.execute.contract_address,
+            _ => None,
+        }
+    }
+
+    /// Creates usage for a single executed `transaction`. Zero if the transaction is not attributed to a contract.
+    pub(crate) fn for_transaction(transaction: &Transaction, gas_used: usize) -> Self {
+        if Self::target(transaction).is_some() {
+            Self {
+                gas_used: gas_used as u64,
+                tx_count: 1,
+            }
+        } else {
+            Self::default()
+        }
+    }
+}
+
+impl ops::Add for ContractUsage {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            gas_used: self.gas_used + other.gas_used,
+            tx_count: self.tx_count + other.tx_count,
+        }
+    }
+}
+
+impl ops::AddAssign for ContractUsage {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
 /// Information about transaction or block applicable either to a single transaction, or
 /// to the entire L2 block / L1 batch.
 #[derive(Debug, Default)]
@@ -166,6 +219,8 @@ pub struct SealData {
     pub(super) cumulative_size: usize,
     pub(super) writes_metrics: DeduplicatedWritesMetrics,
     pub(super) gas_remaining: u32,
+    /// Usage of the transaction's [target contract](ContractUsage::target()).
+    pub(super) contract_usage: ContractUsage,
 }
 
 impl SealData {
@@ -180,6 +235,7 @@ impl SealData {
             cumulative_size: transaction.bootloader_encoding_size(),
             writes_metrics: tx_metrics.writes,
             gas_remaining: tx_metrics.gas_remaining,
+            contract_usage: ContractUsage::for_transaction(transaction, tx_metrics.vm.gas_used),
         }
     }
 }
@@ -32,6 +32,9 @@ pub(crate) fn successful_exec() -> BatchTransactionExecutionResult {
         tx_result: Box::new(VmExecutionResultAndLogs::mock_success()),
         compression_result: Ok(()),
         call_traces: vec![],
+        prestate_trace: None,
+        struct_logs: None,
+        profile: None,
     }
 }
 
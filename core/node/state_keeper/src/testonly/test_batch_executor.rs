@@ -327,6 +327,9 @@ pub(crate) fn successful_exec_with_log() -> BatchTransactionExecutionResult {
         }),
         compression_result: Ok(()),
         call_traces: vec![],
+        prestate_trace: None,
+        struct_logs: None,
+        profile: None,
     }
 }
 
@@ -338,6 +341,9 @@ pub(crate) fn rejected_exec(reason: Halt) -> BatchTransactionExecutionResult {
         })),
         compression_result: Ok(()),
         call_traces: vec![],
+        prestate_trace: None,
+        struct_logs: None,
+        profile: None,
     }
 }
 
@@ -452,6 +458,9 @@ impl TestBatchExecutorBuilder {
                         tx_result: result.tx_result.clone(),
                         compression_result: Ok(()),
                         call_traces: result.call_traces.clone(),
+                        prestate_trace: None,
+                        struct_logs: None,
+                        profile: None,
                     };
 
                     if let Some(txs) = batch_txs.get_mut(&tx.hash()) {
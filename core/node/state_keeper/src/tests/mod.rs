@@ -1,4 +1,5 @@
 use std::{
+    num::NonZeroU32,
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
@@ -344,6 +345,56 @@ async fn bootloader_tip_out_of_gas_flow() {
         .await;
 }
 
+#[tokio::test]
+async fn tx_exceeding_contract_quota_is_postponed_to_next_l2_block() {
+    let config = SealCriteriaConfig {
+        transaction_slots: 3,
+        max_txs_per_contract_per_l2_block: NonZeroU32::new(1),
+        ..SealCriteriaConfig::for_tests()
+    };
+    let sealer = SequencerSealer::with_sealers(config, vec![Box::new(SlotsCriterion)]);
+
+    let contract = Address::repeat_byte(1);
+    let mut first_tx = random_tx(1);
+    first_tx.execute.contract_address = Some(contract);
+    let mut postponed_tx = random_tx(2);
+    postponed_tx.execute.contract_address = Some(contract);
+    let mut other_tx = random_tx(3);
+    other_tx.execute.contract_address = Some(Address::repeat_byte(2));
+
+    TestScenario::new()
+        .next_tx("First tx to the contract", first_tx, successful_exec())
+        .next_tx(
+            "Second tx to the contract exceeds the L2 block quota",
+            postponed_tx.clone(),
+            successful_exec(),
+        )
+        .tx_rollback("Second tx rolled back", postponed_tx.clone())
+        .l2_block_sealed_with("L2 block sealed with 1 tx", |updates| {
+            assert_eq!(
+                updates.last_pending_l2_block().executed_transactions.len(),
+                1,
+                "The postponed tx should not be included into the L2 block"
+            );
+        })
+        .next_tx(
+            "Postponed tx is included into the next L2 block",
+            postponed_tx,
+            successful_exec(),
+        )
+        .next_tx("Tx to another contract", other_tx, successful_exec())
+        .l2_block_sealed_with("L2 block sealed with 2 txs", |updates| {
+            assert_eq!(
+                updates.last_pending_l2_block().executed_transactions.len(),
+                2,
+                "The L2 block should have 2 txs"
+            );
+        })
+        .batch_sealed("Batch sealed with 3 txs")
+        .run_success(Arc::new(sealer))
+        .await;
+}
+
 #[tokio::test]
 async fn pending_batch_is_applied() {
     let config = SealCriteriaConfig {
@@ -1,11 +1,12 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use zksync_multivm::interface::{FinishedL1Batch, VmExecutionMetrics};
 use zksync_types::{
-    priority_op_onchain_data::PriorityOpOnchainData, ExecuteTransactionCommon, InteropRoot, H256,
+    priority_op_onchain_data::PriorityOpOnchainData, Address, ExecuteTransactionCommon,
+    InteropRoot, H256,
 };
 
-use crate::updates::l2_block_updates::L2BlockUpdates;
+use crate::{seal_criteria::ContractUsage, updates::l2_block_updates::L2BlockUpdates};
 
 #[derive(Debug)]
 pub struct CommittedUpdates {
@@ -16,6 +17,7 @@ pub struct CommittedUpdates {
     pub l1_tx_count: usize,
     pub finished: Option<FinishedL1Batch>,
     pub interop_roots: HashSet<InteropRoot>,
+    pub contract_usage: HashMap<Address, ContractUsage>,
 }
 
 impl CommittedUpdates {
@@ -28,6 +30,7 @@ impl CommittedUpdates {
             l1_tx_count: 0,
             finished: None,
             interop_roots: HashSet::new(),
+            contract_usage: HashMap::new(),
         }
     }
 
@@ -53,6 +56,9 @@ impl CommittedUpdates {
         self.block_execution_metrics += l2_block_updates.block_execution_metrics;
         self.txs_encoding_size += l2_block_updates.txs_encoding_size;
         self.l1_tx_count += l2_block_updates.l1_tx_count;
+        for (contract, usage) in l2_block_updates.contract_usage {
+            *self.contract_usage.entry(contract).or_default() += usage;
+        }
     }
 }
 
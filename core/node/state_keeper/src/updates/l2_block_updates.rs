@@ -13,10 +13,11 @@ use zksync_types::{
     l2_to_l1_log::{SystemL2ToL1Log, UserL2ToL1Log},
     transaction_status_commitment::TransactionStatusCommitment,
     web3::{keccak256, keccak256_concat},
-    InteropRoot, L2BlockNumber, ProtocolVersionId, StorageLogWithPreviousValue, Transaction, H256,
+    Address, InteropRoot, L2BlockNumber, ProtocolVersionId, StorageLogWithPreviousValue,
+    Transaction, H256,
 };
 
-use crate::metrics::KEEPER_METRICS;
+use crate::{metrics::KEEPER_METRICS, seal_criteria::ContractUsage};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct L2BlockUpdates {
@@ -35,6 +36,8 @@ pub struct L2BlockUpdates {
     pub virtual_blocks: u32,
     pub protocol_version: ProtocolVersionId,
     pub interop_roots: Vec<InteropRoot>,
+    /// Gas and transactions attributed to each target contract in this L2 block.
+    pub contract_usage: HashMap<Address, ContractUsage>,
     timestamp_ms: u64,
 }
 
@@ -64,6 +67,7 @@ impl L2BlockUpdates {
             virtual_blocks,
             protocol_version,
             interop_roots,
+            contract_usage: HashMap::new(),
         }
     }
 
@@ -155,6 +159,10 @@ impl L2BlockUpdates {
         if tx.is_l1() {
             self.l1_tx_count += 1;
         }
+        if let Some(contract) = ContractUsage::target(&tx) {
+            *self.contract_usage.entry(contract).or_default() +=
+                ContractUsage::for_transaction(&tx, execution_metrics.gas_used);
+        }
 
         self.executed_transactions.push(TransactionExecutionResult {
             hash: tx.hash(),
@@ -221,6 +229,7 @@ impl L2BlockUpdates {
             virtual_blocks: Default::default(),
             protocol_version: ProtocolVersionId::latest(),
             interop_roots: vec![],
+            contract_usage: HashMap::new(),
         }
     }
 
@@ -22,6 +22,7 @@ use super::{
 };
 use crate::{
     metrics::{L2BlockSealStage, L2_BLOCK_METRICS},
+    seal_criteria::ContractUsage,
     updates::l2_block_updates::RollingTxHashUpdates,
 };
 
@@ -328,6 +329,20 @@ impl UpdatesManager {
                 })
     }
 
+    /// Returns gas and transactions attributed to the `contract` in the current L1 batch.
+    pub(crate) fn pending_contract_usage(&self, contract: Address) -> ContractUsage {
+        let committed_usage = self
+            .committed_updates
+            .contract_usage
+            .get(&contract)
+            .copied()
+            .unwrap_or_default();
+        self.pending_l2_blocks
+            .iter()
+            .filter_map(|b| b.contract_usage.get(&contract))
+            .fold(committed_usage, |sum, usage| sum + *usage)
+    }
+
     pub(crate) fn pending_txs_encoding_size(&self) -> usize {
         self.committed_updates.txs_encoding_size
             + self
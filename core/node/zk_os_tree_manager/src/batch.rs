@@ -7,11 +7,30 @@ use zksync_shared_metrics::tree::{LoadChangesStage, TreeUpdateStage, METRICS};
 use zksync_types::{
     block::{CommonBlockStatistics, L1BatchTreeData},
     writes::TreeWrite,
-    AccountTreeId, L1BatchNumber, StorageKey,
+    AccountTreeId, L1BatchNumber, StorageKey, H256,
 };
 
 use crate::helpers::AsyncMerkleTree;
 
+/// Minimum enumeration index of a storage slot in Postgres that can be mapped to a ZK OS tree leaf.
+///
+/// Leaf indices in the ZK OS tree coincide with enumeration indices of initial writes in Postgres
+/// (i.e., `initial_writes.index`). The tree reserves leaf 0 for the min guard and leaf 1 for the max guard.
+/// Postgres never assigns index 0, and index 1 must be reserved for the max guard key (`0xff..ff`) by inserting
+/// its initial write before genesis. Thus, real storage slots have enumeration indices starting from 2,
+/// and the next enumeration index to be assigned is equal to the tree leaf count.
+pub(crate) const MIN_SLOT_ENUM_INDEX: u64 = 2;
+
+/// Maps the Postgres enumeration index of `key` to the ZK OS tree leaf index.
+pub(crate) fn leaf_index(key: H256, enum_index: u64) -> anyhow::Result<u64> {
+    anyhow::ensure!(
+        enum_index >= MIN_SLOT_ENUM_INDEX,
+        "Enumeration index {enum_index} of key {key:?} is reserved for tree guards; \
+         this means that Postgres doesn't reserve index 1 for the max guard key"
+    );
+    Ok(enum_index)
+}
+
 #[derive(Debug)]
 pub(crate) struct L1BatchWithLogs {
     pub(crate) stats: CommonBlockStatistics,
@@ -134,7 +153,7 @@ impl L1BatchWithLogs {
         // Sort tree logs by the enumeration index to get the correct order for inserts.
         let mut tree_logs = BTreeMap::new();
         for (storage_key, value) in touched_slots {
-            if let Some(&(initial_write_batch_for_key, leaf_index)) =
+            if let Some(&(initial_write_batch_for_key, enum_index)) =
                 l1_batches_for_initial_writes.get(&storage_key.hashed_key())
             {
                 anyhow::ensure!(
@@ -143,13 +162,8 @@ impl L1BatchWithLogs {
                      greater than the batch in which it's written ({l1_batch_number})"
                 );
 
-                tree_logs.insert(
-                    leaf_index,
-                    TreeEntry {
-                        key: storage_key.hashed_key(),
-                        value,
-                    },
-                );
+                let key = storage_key.hashed_key();
+                tree_logs.insert(leaf_index(key, enum_index)?, TreeEntry { key, value });
             }
         }
 
@@ -166,7 +180,8 @@ impl AsyncMerkleTree {
             let output = tree.extend_with_reference(&batch.tree_logs)?;
             Ok(L1BatchTreeData {
                 hash: output.root_hash,
-                rollup_last_leaf_index: output.leaf_count + 1,
+                // The tree leaf count is the next enumeration index; see `MIN_SLOT_ENUM_INDEX`.
+                rollup_last_leaf_index: output.leaf_count,
             })
         })
         .await
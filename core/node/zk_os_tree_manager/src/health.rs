@@ -21,13 +21,19 @@ pub struct MerkleTreeInfo {
 #[serde(tag = "stage", rename_all = "snake_case")]
 pub(super) enum MerkleTreeHealth {
     Initialization,
+    Recovery {
+        chunk_count: u64,
+        recovered_chunk_count: u64,
+    },
     MainLoop(MerkleTreeInfo),
 }
 
 impl From<MerkleTreeHealth> for Health {
     fn from(details: MerkleTreeHealth) -> Self {
         let status = match &details {
-            MerkleTreeHealth::Initialization => HealthStatus::Affected,
+            MerkleTreeHealth::Initialization | MerkleTreeHealth::Recovery { .. } => {
+                HealthStatus::Affected
+            }
             MerkleTreeHealth::MainLoop(_) => HealthStatus::Ready,
         };
         Self::from(status).with_details(details)
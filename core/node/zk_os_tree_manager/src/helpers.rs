@@ -1,8 +1,9 @@
 use anyhow::Context as _;
 use tokio::sync::watch;
 use zk_os_merkle_tree::{
-    unstable, BatchTreeProof, MerkleTree, MerkleTreeColumnFamily, MerkleTreeReader, Patched,
-    RocksDBWrapper,
+    unstable, BatchTreeProof, Database, MerkleTree, MerkleTreeColumnFamily, MerkleTreePruner,
+    MerkleTreePrunerHandle, MerkleTreeReader, MerkleTreeRecovery, Patched, RocksDBWrapper,
+    TreeEntry,
 };
 use zksync_storage::{RocksDB, RocksDBOptions, StalledWritesRetries, WeakRocksDB};
 use zksync_types::{block::L1BatchTreeData, L1BatchNumber, H256};
@@ -47,8 +48,7 @@ impl AsyncTreeReader {
                 break Ok(MerkleTreeInfo {
                     root_hash,
                     next_version: latest_version.map_or(0, |ver| ver + 1),
-                    // Valid because pruning isn't implemented yet
-                    min_version: latest_version.is_some().then_some(0),
+                    min_version: self.inner.first_retained_version()?,
                     leaf_count,
                 });
             }
@@ -195,19 +195,46 @@ impl AsyncMerkleTree {
                 .root_info(l1_batch_number.0.into())?
                 .map(|(hash, leaf_count)| L1BatchTreeData {
                     hash,
-                    rollup_last_leaf_index: leaf_count + 1,
+                    rollup_last_leaf_index: leaf_count,
                 }))
         })
         .await
     }
 
+    pub(crate) fn pruner(&self) -> (MerkleTreePruner<RocksDBWrapper>, MerkleTreePrunerHandle) {
+        let db = self
+            .inner
+            .as_ref()
+            .expect(Self::INCONSISTENT_MSG)
+            .db()
+            .inner()
+            .clone();
+        MerkleTreePruner::new(db)
+    }
+
+    /// Verifies consistency of all tree versions retained after pruning.
+    pub(crate) async fn verify_retained_versions(&mut self) -> anyhow::Result<()> {
+        // Keys used to check that each retained version produces valid proofs. Missing keys are fine;
+        // they are proven to be absent from the tree.
+        const PROVEN_KEYS: [H256; 2] = [H256::zero(), H256::repeat_byte(0x80)];
+
+        self.try_invoke_tree(|tree| {
+            tree.verify_retained_versions(&PROVEN_KEYS)
+                .context("Merkle tree is inconsistent")
+        })
+        .await
+    }
+
     pub(crate) async fn min_l1_batch_number(&mut self) -> anyhow::Result<Option<L1BatchNumber>> {
-        Ok(if self.is_empty().await? {
-            None
-        } else {
-            // TODO: change once tree pruning is implemented
-            Some(L1BatchNumber(0))
+        self.try_invoke_tree(|tree| {
+            let Some(version) = tree.first_retained_version()? else {
+                return Ok(None);
+            };
+            Ok(Some(L1BatchNumber(
+                version.try_into().context("tree version overflow")?,
+            )))
         })
+        .await
     }
 
     pub(crate) async fn save(&mut self) -> anyhow::Result<()> {
@@ -224,6 +251,108 @@ impl AsyncMerkleTree {
     }
 }
 
+/// Async wrapper for [`MerkleTreeRecovery`].
+///
+/// Async methods provided by this wrapper are not cancel-safe!
+#[derive(Debug)]
+pub(crate) struct AsyncTreeRecovery {
+    inner: Option<MerkleTreeRecovery<RocksDBWrapper>>,
+}
+
+impl AsyncTreeRecovery {
+    const INCONSISTENT_MSG: &'static str =
+        "`AsyncTreeRecovery` is in inconsistent state, which could occur after one of its async methods was cancelled or returned an error";
+
+    pub(crate) async fn new(db: RocksDBWrapper, recovered_version: u64) -> anyhow::Result<Self> {
+        tokio::task::spawn_blocking(move || {
+            let recovery = MerkleTreeRecovery::new(db, recovered_version)?;
+            Ok(Self {
+                inner: Some(recovery),
+            })
+        })
+        .await
+        .context("panicked initializing Merkle tree recovery")?
+    }
+
+    pub(crate) fn recovered_version(&self) -> u64 {
+        self.inner
+            .as_ref()
+            .expect(Self::INCONSISTENT_MSG)
+            .recovered_version()
+    }
+
+    async fn try_invoke_tree<T, F>(&mut self, f: F) -> anyhow::Result<T>
+    where
+        T: 'static + Send,
+        F: FnOnce(&mut MerkleTreeRecovery<RocksDBWrapper>) -> anyhow::Result<T> + 'static + Send,
+    {
+        let mut tree = self.inner.take().context(Self::INCONSISTENT_MSG)?;
+        let (output, tree) = tokio::task::spawn_blocking(|| (f(&mut tree), tree))
+            .await
+            .context("tree recovery action panicked")?;
+        self.inner = Some(tree);
+        output
+    }
+
+    pub(crate) async fn entries(
+        &mut self,
+        keys: Vec<H256>,
+    ) -> anyhow::Result<Vec<Option<(u64, TreeEntry)>>> {
+        self.try_invoke_tree(move |tree| tree.entries(&keys)).await
+    }
+
+    pub(crate) async fn extend(&mut self, entries: Vec<(u64, TreeEntry)>) -> anyhow::Result<()> {
+        self.try_invoke_tree(move |tree| tree.extend(&entries))
+            .await
+    }
+
+    pub(crate) async fn root_hash(&mut self) -> anyhow::Result<H256> {
+        self.try_invoke_tree(|tree| tree.root_hash()).await
+    }
+
+    pub(crate) async fn finalize(self) -> anyhow::Result<AsyncMerkleTree> {
+        let tree = self.inner.context(Self::INCONSISTENT_MSG)?;
+        let db = tokio::task::spawn_blocking(|| tree.finalize())
+            .await
+            .context("panicked finalizing tree recovery")??;
+        AsyncMerkleTree::new(db).await
+    }
+}
+
+/// Tree at any stage of its life cycle.
+#[derive(Debug)]
+pub(crate) enum GenericAsyncTree {
+    /// Uninitialized tree.
+    Empty(RocksDBWrapper),
+    /// The tree during recovery.
+    Recovering(AsyncTreeRecovery),
+    /// The tree after recovery.
+    Ready(AsyncMerkleTree),
+}
+
+impl GenericAsyncTree {
+    pub(crate) async fn new(db: RocksDBWrapper) -> anyhow::Result<Self> {
+        let manifest = tokio::task::spawn_blocking({
+            let db = db.clone();
+            move || db.try_manifest()
+        })
+        .await
+        .context("panicked reading tree manifest")??;
+
+        Ok(match manifest {
+            None => Self::Empty(db),
+            Some(manifest) if manifest.is_recovering() => {
+                let recovered_version = manifest
+                    .version_count()
+                    .checked_sub(1)
+                    .context("tree being recovered has no versions")?;
+                Self::Recovering(AsyncTreeRecovery::new(db, recovered_version).await?)
+            }
+            Some(_) => Self::Ready(AsyncMerkleTree::new(db).await?),
+        })
+    }
+}
+
 /// Creates a RocksDB wrapper with the specified params.
 pub(crate) async fn create_db(config: TreeManagerConfig) -> anyhow::Result<RocksDBWrapper> {
     tokio::task::spawn_blocking(move || create_db_sync(&config))
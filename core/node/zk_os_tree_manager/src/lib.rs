@@ -1,5 +1,5 @@
 use std::{
-    num::{NonZeroU32, NonZeroUsize},
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
     path::PathBuf,
     time::{Duration, Instant},
 };
@@ -16,18 +16,35 @@ use zksync_types::L1BatchNumber;
 pub use self::helpers::LazyAsyncTreeReader;
 use crate::{
     health::{MerkleTreeHealth, MerkleTreeHealthCheck},
-    helpers::{create_db, AsyncMerkleTree, AsyncTreeReader},
-    updater::TreeUpdater,
+    helpers::{create_db, AsyncTreeReader, GenericAsyncTree},
+    updater::{TreePruning, TreeUpdater},
 };
 
 pub mod api;
 mod batch;
 mod health;
 mod helpers;
+mod recovery;
 #[cfg(test)]
 mod tests;
 mod updater;
 
+/// Configuration of Merkle tree recovery from a snapshot.
+#[derive(Debug, Clone)]
+pub struct TreeRecoveryConfig {
+    /// Approximate chunk size (measured in the number of entries) to recover in a single iteration.
+    /// Must not be changed while recovery is in progress; otherwise, recovery will fail.
+    pub desired_chunk_size: NonZeroU64,
+}
+
+impl Default for TreeRecoveryConfig {
+    fn default() -> Self {
+        Self {
+            desired_chunk_size: NonZeroU64::new(200_000).unwrap(),
+        }
+    }
+}
+
 /// Configuration of [`TreeManager`].
 #[derive(Debug, Clone)]
 pub struct TreeManagerConfig {
@@ -49,6 +66,14 @@ pub struct TreeManagerConfig {
     /// being loaded entirely into RAM on the RocksDB initialization. The block cache capacity should be increased
     /// correspondingly; otherwise, RocksDB performance can significantly degrade.
     pub include_indices_and_filters_in_block_cache: bool,
+    /// Number of the latest tree versions (= L1 batches) retained by the tree; older versions are pruned.
+    /// If not set, the tree is not pruned.
+    pub retained_version_count: Option<NonZeroU64>,
+    /// Whether to verify consistency of all tree versions retained after pruning on startup. This traverses the entire tree
+    /// for each retained version, so it can take a long time for large trees.
+    pub verify_retained_versions_on_startup: bool,
+    /// Configuration of tree recovery from a Postgres snapshot.
+    pub recovery: TreeRecoveryConfig,
 }
 
 impl TreeManagerConfig {
@@ -114,7 +139,7 @@ impl TreeManager {
         self.next_l1_batch_sender.subscribe()
     }
 
-    async fn create_tree(&self) -> anyhow::Result<AsyncMerkleTree> {
+    async fn create_tree(&self) -> anyhow::Result<GenericAsyncTree> {
         self.health_updater
             .update(MerkleTreeHealth::Initialization.into());
 
@@ -131,11 +156,20 @@ impl TreeManager {
             started_at.elapsed()
         );
 
-        AsyncMerkleTree::new(db).await
+        GenericAsyncTree::new(db).await
     }
 
     pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
-        let mut tree = self.create_tree().await?;
+        let tree = self.create_tree().await?;
+        let mut tree = try_stoppable!(
+            tree.ensure_ready(
+                &self.config.recovery,
+                &self.pool,
+                &self.health_updater,
+                &stop_receiver
+            )
+            .await
+        );
 
         // Set a tree reader before the tree is fully initialized to not wait for the first L1 batch to appear in Postgres.
         let tree_reader = tree.reader();
@@ -145,20 +179,51 @@ impl TreeManager {
             tree.ensure_consistency(self.config.delay_interval, &self.pool, &mut stop_receiver)
                 .await
         );
+        if self.config.verify_retained_versions_on_startup {
+            let started_at = Instant::now();
+            tree.verify_retained_versions().await?;
+            tracing::info!(
+                "Verified retained Merkle tree versions in {:?}",
+                started_at.elapsed()
+            );
+        }
 
         let tree_info = tree.reader().info().await.context("cannot get tree info")?;
         tracing::info!("Merkle tree is initialized and ready to process L1 batches: {tree_info:?}");
         self.health_updater
             .update(MerkleTreeHealth::MainLoop(tree_info).into());
 
+        let mut pruner_task = None;
+        let pruning = self.config.retained_version_count.map(|retained_version_count| {
+            let (mut pruner, handle) = tree.pruner();
+            pruner.set_poll_interval(self.config.delay_interval);
+            pruner_task = Some(tokio::task::spawn_blocking(|| pruner.run()));
+            tracing::info!("Started Merkle tree pruner retaining {retained_version_count} latest tree versions");
+            TreePruning {
+                handle,
+                retained_version_count,
+            }
+        });
+
         let updater = TreeUpdater {
             tree,
             max_l1_batches_per_iter: self.config.max_l1_batches_per_iter.get(),
+            pruning,
             #[cfg(test)]
             next_l1_batch_sender: self.next_l1_batch_sender,
         };
-        updater
+        let updater_result = updater
             .loop_updating_tree(self.config.delay_interval, &self.pool, stop_receiver)
-            .await
+            .await;
+
+        // The updater is dropped at this point together with the pruner handle, which stops the pruner.
+        // If the updater has failed because the pruner has stopped, the pruner error is more informative.
+        if let Some(pruner_task) = pruner_task {
+            pruner_task
+                .await
+                .context("Merkle tree pruner panicked")?
+                .context("Merkle tree pruner failed")?;
+        }
+        updater_result
     }
 }
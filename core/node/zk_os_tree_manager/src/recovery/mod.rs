@@ -0,0 +1,412 @@
+//! High-level recovery logic for the ZK OS Merkle tree.
+//!
+//! # Overview
+//!
+//! Similar to the Era metadata calculator, tree recovery works by checking Postgres and Merkle tree state
+//! on the tree manager initialization. Depending on these states, we can have one of the following situations:
+//!
+//! - Tree is recovering.
+//! - Tree is empty and should be recovered (i.e., there's a snapshot in Postgres).
+//! - Tree is empty and should be built from scratch.
+//! - Tree is ready for normal operation (i.e., it's not empty and is not recovering).
+//!
+//! If recovery is necessary, it starts / resumes by loading the Postgres snapshot in chunks
+//! and feeding each chunk to the tree. Chunks are loaded concurrently since this is the most
+//! I/O-heavy operation; the concurrency is limited by the number of connections in the supplied
+//! connection pool. Before starting recovery in chunks, we filter out chunks that have already been recovered
+//! by checking if the first key in a chunk is present in the tree. (Note that for this to work, chunks **must**
+//! always be defined in the same way, i.e., the desired chunk size must not change during recovery.)
+//!
+//! The recovery logic is fault-tolerant and supports graceful shutdown. If recovery is interrupted,
+//! recovery of the remaining chunks will continue when the tree manager is restarted.
+//!
+//! Leaf indices of recovered entries are taken from enumeration indices of initial writes in Postgres, in the same way
+//! as for the normal tree operation (see [`MIN_SLOT_ENUM_INDEX`](crate::batch::MIN_SLOT_ENUM_INDEX)). Thus, a recovered tree
+//! is identical to the tree built by processing L1 batches from genesis.
+//!
+//! Recovery performs basic sanity checks to ensure that the tree won't end up containing garbage data.
+//! E.g., it's checked that the tree always recovers from the same snapshot; that the tree root hash
+//! after recovery matches one in the Postgres snapshot etc.
+
+use std::{
+    ops,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
+};
+
+use anyhow::Context as _;
+use futures::future;
+use tokio::sync::{watch, Mutex, Semaphore};
+use zk_os_merkle_tree::TreeEntry;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_health_check::HealthUpdater;
+use zksync_shared_metrics::{SnapshotRecoveryStage, APP_METRICS};
+use zksync_types::{
+    snapshots::uniform_hashed_keys_chunk, L1BatchNumber, L2BlockNumber, OrStopped, H256,
+};
+
+use crate::{
+    batch::leaf_index,
+    health::MerkleTreeHealth,
+    helpers::{AsyncMerkleTree, AsyncTreeRecovery, GenericAsyncTree},
+    TreeRecoveryConfig,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Reports recovery progress via a [`HealthUpdater`].
+#[derive(Debug)]
+struct RecoveryHealthUpdater<'a> {
+    inner: &'a HealthUpdater,
+    chunk_count: u64,
+    recovered_chunk_count: AtomicU64,
+}
+
+impl<'a> RecoveryHealthUpdater<'a> {
+    fn new(inner: &'a HealthUpdater, chunk_count: u64, recovered_chunk_count: u64) -> Self {
+        Self {
+            inner,
+            chunk_count,
+            recovered_chunk_count: AtomicU64::new(recovered_chunk_count),
+        }
+    }
+
+    fn chunk_recovered(&self) {
+        let recovered_chunk_count = self.recovered_chunk_count.fetch_add(1, Ordering::SeqCst) + 1;
+        let chunks_left = self.chunk_count.saturating_sub(recovered_chunk_count);
+        tracing::info!(
+            "Recovered {recovered_chunk_count}/{} Merkle tree chunks, there are {chunks_left} left to process",
+            self.chunk_count
+        );
+        let health = MerkleTreeHealth::Recovery {
+            chunk_count: self.chunk_count,
+            recovered_chunk_count,
+        };
+        self.inner.update(health.into());
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct InitParameters {
+    l1_batch: L1BatchNumber,
+    l2_block: L2BlockNumber,
+    expected_root_hash: Option<H256>,
+    log_count: u64,
+    desired_chunk_size: u64,
+}
+
+impl InitParameters {
+    async fn new(
+        pool: &ConnectionPool<Core>,
+        config: &TreeRecoveryConfig,
+    ) -> anyhow::Result<Option<Self>> {
+        let mut storage = pool.connection_tagged("zk_os_tree_manager").await?;
+        let recovery_status = storage
+            .snapshot_recovery_dal()
+            .get_applied_snapshot_status()
+            .await?;
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+
+        let (l1_batch, l2_block);
+        let mut expected_root_hash = None;
+        match (recovery_status, pruning_info.last_hard_pruned) {
+            (Some(recovery), None) => {
+                tracing::warn!(
+                    "Snapshot recovery {recovery:?} is present on the node, but pruning info is empty; assuming no pruning happened"
+                );
+                l1_batch = recovery.l1_batch_number;
+                l2_block = recovery.l2_block_number;
+                expected_root_hash = Some(recovery.l1_batch_root_hash);
+            }
+            (Some(recovery), Some(pruned)) => {
+                // We have both recovery and some pruning on top of it.
+                l2_block = pruned.l2_block.max(recovery.l2_block_number);
+                l1_batch = pruned.l1_batch;
+                if let Some(root_hash) = pruned.l1_batch_root_hash {
+                    expected_root_hash = Some(root_hash);
+                } else if l1_batch == recovery.l1_batch_number {
+                    expected_root_hash = Some(recovery.l1_batch_root_hash);
+                }
+            }
+            (None, Some(pruned)) => {
+                l2_block = pruned.l2_block;
+                l1_batch = pruned.l1_batch;
+                expected_root_hash = pruned.l1_batch_root_hash;
+            }
+            (None, None) => return Ok(None),
+        };
+
+        let log_count = storage
+            .storage_logs_dal()
+            .get_storage_logs_row_count(l2_block)
+            .await?;
+
+        Ok(Some(Self {
+            l1_batch,
+            l2_block,
+            expected_root_hash,
+            log_count,
+            desired_chunk_size: config.desired_chunk_size.get(),
+        }))
+    }
+
+    fn chunk_count(&self) -> u64 {
+        self.log_count.div_ceil(self.desired_chunk_size)
+    }
+}
+
+impl GenericAsyncTree {
+    /// Ensures that the tree is ready for the normal operation, recovering it from a Postgres snapshot
+    /// if necessary.
+    pub(crate) async fn ensure_ready(
+        self,
+        config: &TreeRecoveryConfig,
+        pool: &ConnectionPool<Core>,
+        health_updater: &HealthUpdater,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> Result<AsyncMerkleTree, OrStopped> {
+        let started_at = Instant::now();
+        let (tree, init_params) = match self {
+            Self::Ready(tree) => return Ok(tree),
+            Self::Recovering(tree) => {
+                let params = InitParameters::new(pool, config).await?.context(
+                    "Merkle tree is recovering, but Postgres doesn't contain snapshot recovery information",
+                )?;
+
+                let recovered_version = tree.recovered_version();
+                if u64::from(params.l1_batch.0) != recovered_version {
+                    let err = anyhow::anyhow!(
+                        "Snapshot L1 batch in Postgres ({params:?}) differs from the recovered Merkle tree version \
+                         ({recovered_version})"
+                    );
+                    return Err(err.into());
+                }
+
+                tracing::info!("Resuming tree recovery with status: {params:?}");
+                (tree, params)
+            }
+            Self::Empty(db) => {
+                if let Some(params) = InitParameters::new(pool, config).await? {
+                    tracing::info!("Starting Merkle tree recovery with status {params:?}");
+                    let tree = AsyncTreeRecovery::new(db, params.l1_batch.0.into()).await?;
+                    (tree, params)
+                } else {
+                    // The genesis batch will be processed in `TreeUpdater::ensure_consistency()`.
+                    tracing::info!("Starting Merkle tree from scratch");
+                    return Ok(AsyncMerkleTree::new(db).await?);
+                }
+            }
+        };
+
+        tracing::debug!(
+            "Obtained recovery init parameters: {init_params:?} based on recovery configuration {config:?}"
+        );
+        let concurrency_limit = pool.max_size() as usize;
+        let tree = tree
+            .recover(
+                init_params,
+                concurrency_limit,
+                pool,
+                health_updater,
+                stop_receiver,
+            )
+            .await?;
+        // Only report latency if recovery wasn't canceled
+        let elapsed = started_at.elapsed();
+        APP_METRICS.snapshot_recovery_latency[&SnapshotRecoveryStage::Tree].set(elapsed);
+        tracing::info!("Recovered Merkle tree from snapshot in {elapsed:?}");
+        Ok(tree)
+    }
+}
+
+impl AsyncTreeRecovery {
+    async fn recover(
+        mut self,
+        init_params: InitParameters,
+        concurrency_limit: usize,
+        pool: &ConnectionPool<Core>,
+        health_updater: &HealthUpdater,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> Result<AsyncMerkleTree, OrStopped> {
+        let start_time = Instant::now();
+        let chunk_count = init_params.chunk_count();
+        let chunks: Vec<_> = (0..chunk_count)
+            .map(|chunk_id| uniform_hashed_keys_chunk(chunk_id, chunk_count))
+            .collect();
+        tracing::info!(
+            "Recovering Merkle tree from Postgres snapshot in {chunk_count} chunks with max concurrency {concurrency_limit}. \
+             Be aware that enabling node pruning during recovery will probably result in a recovery error; always disable pruning \
+             until recovery is complete"
+        );
+
+        let mut storage = pool.connection_tagged("zk_os_tree_manager").await?;
+        let remaining_chunks = self
+            .filter_chunks(&mut storage, init_params.l2_block, &chunks)
+            .await?;
+        drop(storage);
+        let health_updater = RecoveryHealthUpdater::new(
+            health_updater,
+            chunk_count,
+            chunk_count - remaining_chunks.len() as u64,
+        );
+        tracing::info!(
+            "Filtered recovered key chunks; {} / {chunk_count} chunks remaining",
+            remaining_chunks.len()
+        );
+
+        let tree = Mutex::new(self);
+        let semaphore = Semaphore::new(concurrency_limit);
+        let chunk_tasks = remaining_chunks.into_iter().map(|chunk| async {
+            let _permit = semaphore
+                .acquire()
+                .await
+                .context("semaphore is never closed")?;
+            if Self::recover_key_chunk(&tree, init_params.l2_block, chunk, pool, stop_receiver)
+                .await?
+            {
+                health_updater.chunk_recovered();
+            }
+            anyhow::Ok(())
+        });
+        future::try_join_all(chunk_tasks).await?;
+
+        let mut tree = tree.into_inner();
+        if *stop_receiver.borrow() {
+            return Err(OrStopped::Stopped);
+        }
+
+        let actual_root_hash = tree.root_hash().await?;
+        if let Some(expected_root_hash) = init_params.expected_root_hash {
+            if actual_root_hash != expected_root_hash {
+                let err = anyhow::anyhow!(
+                    "Root hash of recovered tree {actual_root_hash:?} differs from expected root hash {expected_root_hash:?}"
+                );
+                return Err(err.into());
+            }
+        }
+
+        // Check pruning info one last time before finalizing the tree.
+        let mut storage = pool.connection_tagged("zk_os_tree_manager").await?;
+        Self::check_pruning_info(&mut storage, init_params.l2_block).await?;
+        drop(storage);
+
+        let tree = tree.finalize().await?;
+        tracing::info!(
+            "Tree recovery has finished, the recovery took {:?}! resuming normal tree operation",
+            start_time.elapsed()
+        );
+        Ok(tree)
+    }
+
+    /// Filters out `key_chunks` for which recovery was successfully performed.
+    async fn filter_chunks(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        snapshot_l2_block: L2BlockNumber,
+        key_chunks: &[ops::RangeInclusive<H256>],
+    ) -> anyhow::Result<Vec<ops::RangeInclusive<H256>>> {
+        let chunk_starts = storage
+            .storage_logs_dal()
+            .get_chunk_starts_for_l2_block(snapshot_l2_block, key_chunks)
+            .await?;
+
+        let existing_starts = chunk_starts
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &start)| Some((i, start?)));
+        let start_keys = existing_starts
+            .clone()
+            .map(|(_, start_entry)| start_entry.key)
+            .collect();
+        let tree_entries = self.entries(start_keys).await?;
+
+        let mut output = vec![];
+        for (tree_entry, (i, db_entry)) in tree_entries.into_iter().zip(existing_starts) {
+            let Some((tree_leaf_index, tree_entry)) = tree_entry else {
+                output.push(key_chunks[i].clone());
+                continue;
+            };
+            let db_leaf_index = leaf_index(db_entry.key, db_entry.leaf_index)?;
+            anyhow::ensure!(
+                tree_entry.value == db_entry.value && tree_leaf_index == db_leaf_index,
+                "Mismatch between entry for key {:?} in Postgres snapshot for L2 block #{snapshot_l2_block} \
+                 ({db_entry:?}) and tree ({tree_leaf_index}, {tree_entry:?}); the recovery procedure may be corrupted",
+                db_entry.key
+            );
+        }
+        Ok(output)
+    }
+
+    async fn check_pruning_info(
+        storage: &mut Connection<'_, Core>,
+        snapshot_l2_block: L2BlockNumber,
+    ) -> anyhow::Result<()> {
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        if let Some(pruned) = pruning_info.last_hard_pruned {
+            anyhow::ensure!(
+                pruned.l2_block == snapshot_l2_block,
+                "Additional data was pruned compared to tree recovery L2 block #{snapshot_l2_block}: {pruning_info:?}. \
+                 Continuing recovery is impossible; to recover the tree, drop its RocksDB directory, stop pruning and restart recovery"
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns `Ok(true)` if the chunk was recovered, `Ok(false)` if the recovery process was interrupted.
+    async fn recover_key_chunk(
+        tree: &Mutex<AsyncTreeRecovery>,
+        snapshot_l2_block: L2BlockNumber,
+        key_chunk: ops::RangeInclusive<H256>,
+        pool: &ConnectionPool<Core>,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> anyhow::Result<bool> {
+        let mut storage = pool.connection_tagged("zk_os_tree_manager").await?;
+        if *stop_receiver.borrow() {
+            return Ok(false);
+        }
+
+        let started_at = Instant::now();
+        let all_entries = storage
+            .storage_logs_dal()
+            .get_tree_entries_for_l2_block(snapshot_l2_block, key_chunk.clone())
+            .await?;
+        Self::check_pruning_info(&mut storage, snapshot_l2_block).await?;
+        drop(storage);
+        tracing::debug!(
+            "Loaded {} entries for chunk {key_chunk:?} in {:?}",
+            all_entries.len(),
+            started_at.elapsed()
+        );
+
+        if *stop_receiver.borrow() {
+            return Ok(false);
+        }
+
+        // Entries are sorted by the hashed key, which is exactly the tree key, so distinct keys are checked by the tree.
+        let all_entries = all_entries
+            .into_iter()
+            .map(|entry| {
+                let tree_entry = TreeEntry {
+                    key: entry.key,
+                    value: entry.value,
+                };
+                anyhow::Ok((leaf_index(entry.key, entry.leaf_index)?, tree_entry))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let mut tree = tree.lock().await;
+        if *stop_receiver.borrow() {
+            return Ok(false);
+        }
+
+        let started_at = Instant::now();
+        tree.extend(all_entries)
+            .await
+            .with_context(|| format!("failed recovering chunk {key_chunk:?}"))?;
+        tracing::debug!(
+            "Extended Merkle tree with entries for chunk {key_chunk:?} in {:?}",
+            started_at.elapsed()
+        );
+        Ok(true)
+    }
+}
@@ -0,0 +1,251 @@
+//! Tests for ZK OS tree snapshot recovery.
+
+use std::{num::NonZeroU64, path::Path};
+
+use assert_matches::assert_matches;
+use tempfile::TempDir;
+use test_casing::test_casing;
+use zksync_health_check::{CheckHealth, ReactiveHealthCheck};
+use zksync_node_test_utils::generate_storage_logs;
+use zksync_types::{block::L1BatchTreeData, snapshots::SnapshotRecoveryStatus, ProtocolVersionId};
+
+use super::*;
+use crate::{
+    helpers::create_db,
+    tests::{insert_l1_batch, mock_config, setup_tree_manager},
+};
+
+// `insert_l1_batch()` creates a single L2 block per L1 batch with the same number.
+const SNAPSHOT_L1_BATCH: L1BatchNumber = L1BatchNumber(4);
+const SNAPSHOT_L2_BLOCK: L2BlockNumber = L2BlockNumber(4);
+
+#[test]
+fn calculating_chunk_count() {
+    let mut snapshot = InitParameters {
+        l1_batch: L1BatchNumber(1),
+        l2_block: L2BlockNumber(1),
+        log_count: 160_000_000,
+        expected_root_hash: Some(H256::zero()),
+        desired_chunk_size: 200_000,
+    };
+    assert_eq!(snapshot.chunk_count(), 800);
+
+    snapshot.log_count += 1;
+    assert_eq!(snapshot.chunk_count(), 801);
+
+    snapshot.log_count = 100;
+    assert_eq!(snapshot.chunk_count(), 1);
+}
+
+/// Builds the tree for several L1 batches via the normal tree manager workflow and marks the last of these batches
+/// as a snapshot in Postgres. Returns tree data for the snapshot L1 batch persisted by the tree manager.
+async fn prepare_snapshot(pool: &ConnectionPool<Core>) -> L1BatchTreeData {
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let tree_manager = setup_tree_manager(temp_dir.path(), pool.clone()).await;
+    let mut storage = pool.connection().await.unwrap();
+    let logs = generate_storage_logs(0..200);
+    for batch_logs in logs.chunks(logs.len() / SNAPSHOT_L1_BATCH.0 as usize) {
+        insert_l1_batch(&mut storage, batch_logs).await;
+    }
+
+    let mut batches_subscriber = tree_manager.subscribe_to_l1_batches();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let manager_task = tokio::spawn(tree_manager.run(stop_receiver));
+    batches_subscriber
+        .wait_for(|&batch| batch == SNAPSHOT_L1_BATCH + 1)
+        .await
+        .unwrap();
+    stop_sender.send_replace(true);
+    manager_task.await.unwrap().unwrap();
+
+    let tree_data = storage
+        .blocks_dal()
+        .get_l1_batch_tree_data(SNAPSHOT_L1_BATCH)
+        .await
+        .unwrap()
+        .expect("no tree data for snapshot L1 batch");
+    let l1_batch_header = storage
+        .blocks_dal()
+        .get_l1_batch_header(SNAPSHOT_L1_BATCH)
+        .await
+        .unwrap()
+        .expect("no snapshot L1 batch");
+    let l2_block_header = storage
+        .blocks_dal()
+        .get_l2_block_header(SNAPSHOT_L2_BLOCK)
+        .await
+        .unwrap()
+        .expect("no snapshot L2 block");
+    let status = SnapshotRecoveryStatus {
+        l1_batch_number: SNAPSHOT_L1_BATCH,
+        l1_batch_root_hash: tree_data.hash,
+        l1_batch_timestamp: l1_batch_header.timestamp,
+        l2_block_number: SNAPSHOT_L2_BLOCK,
+        l2_block_hash: l2_block_header.hash,
+        l2_block_timestamp: l2_block_header.timestamp,
+        protocol_version: ProtocolVersionId::latest(),
+        storage_logs_chunks_processed: vec![true],
+    };
+    storage
+        .snapshot_recovery_dal()
+        .insert_initial_recovery_status(&status)
+        .await
+        .unwrap();
+    tree_data
+}
+
+async fn init_params(pool: &ConnectionPool<Core>, chunk_size: u64) -> InitParameters {
+    let config = TreeRecoveryConfig {
+        desired_chunk_size: NonZeroU64::new(chunk_size).unwrap(),
+    };
+    let init_params = InitParameters::new(pool, &config)
+        .await
+        .unwrap()
+        .expect("no init params");
+    assert_eq!(init_params.l1_batch, SNAPSHOT_L1_BATCH);
+    assert_eq!(init_params.l2_block, SNAPSHOT_L2_BLOCK);
+    // Besides the generated logs, the snapshot contains logs from the genesis batch.
+    assert!(init_params.log_count > 200, "{init_params:?}");
+    init_params
+}
+
+async fn create_tree_recovery(path: &Path) -> AsyncTreeRecovery {
+    let db = create_db(mock_config(path)).await.unwrap();
+    AsyncTreeRecovery::new(db, SNAPSHOT_L1_BATCH.0.into())
+        .await
+        .unwrap()
+}
+
+#[test_casing(4, [1, 10, 57, 200])]
+#[tokio::test]
+async fn basic_recovery_workflow(chunk_size: u64) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let snapshot_tree_data = prepare_snapshot(&pool).await;
+    let init_params = init_params(&pool, chunk_size).await;
+    assert_eq!(
+        init_params.expected_root_hash,
+        Some(snapshot_tree_data.hash)
+    );
+
+    let tree = create_tree_recovery(temp_dir.path()).await;
+    let (health_check, health_updater) = ReactiveHealthCheck::new("tree");
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let tree = tree
+        .recover(init_params, 4, &pool, &health_updater, &stop_receiver)
+        .await
+        .unwrap();
+
+    let tree_info = tree.reader().info().await.unwrap();
+    assert_eq!(tree_info.root_hash, snapshot_tree_data.hash);
+    assert_eq!(tree_info.next_version, u64::from(SNAPSHOT_L1_BATCH.0) + 1);
+    assert_eq!(
+        tree_info.leaf_count,
+        snapshot_tree_data.rollup_last_leaf_index
+    );
+    let health = health_check.check_health().await;
+    let details = health.details().unwrap();
+    assert_eq!(
+        details["recovered_chunk_count"],
+        serde_json::json!(init_params.chunk_count())
+    );
+}
+
+#[tokio::test]
+async fn resuming_recovery() {
+    const CHUNK_SIZE: u64 = 20;
+
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let snapshot_tree_data = prepare_snapshot(&pool).await;
+    let init_params = init_params(&pool, CHUNK_SIZE).await;
+    let chunk_count = init_params.chunk_count();
+    let chunks: Vec<_> = (0..chunk_count)
+        .map(|chunk_id| uniform_hashed_keys_chunk(chunk_id, chunk_count))
+        .collect();
+
+    // Recover a couple of chunks out of order.
+    let tree = Mutex::new(create_tree_recovery(temp_dir.path()).await);
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    for chunk_id in [5, 1] {
+        let chunk = chunks[chunk_id].clone();
+        let is_recovered = AsyncTreeRecovery::recover_key_chunk(
+            &tree,
+            SNAPSHOT_L2_BLOCK,
+            chunk,
+            &pool,
+            &stop_receiver,
+        )
+        .await
+        .unwrap();
+        assert!(is_recovered);
+    }
+    drop(tree);
+
+    let db = create_db(mock_config(temp_dir.path())).await.unwrap();
+    let tree = GenericAsyncTree::new(db).await.unwrap();
+    let mut tree = assert_matches!(tree, GenericAsyncTree::Recovering(tree) => tree);
+    assert_eq!(tree.recovered_version(), SNAPSHOT_L1_BATCH.0.into());
+
+    let mut storage = pool.connection().await.unwrap();
+    let remaining_chunks = tree
+        .filter_chunks(&mut storage, SNAPSHOT_L2_BLOCK, &chunks)
+        .await
+        .unwrap();
+    drop(storage);
+    let expected_remaining_chunks: Vec<_> = chunks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, chunk)| (i != 1 && i != 5).then(|| chunk.clone()))
+        .collect();
+    assert_eq!(remaining_chunks, expected_remaining_chunks);
+
+    let (_health_check, health_updater) = ReactiveHealthCheck::new("tree");
+    let tree = tree
+        .recover(init_params, 4, &pool, &health_updater, &stop_receiver)
+        .await
+        .unwrap();
+    let tree_info = tree.reader().info().await.unwrap();
+    assert_eq!(tree_info.root_hash, snapshot_tree_data.hash);
+}
+
+#[tokio::test]
+async fn recovery_fails_on_root_hash_mismatch() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    prepare_snapshot(&pool).await;
+    let mut init_params = init_params(&pool, 50).await;
+    init_params.expected_root_hash = Some(H256::repeat_byte(1));
+
+    let tree = create_tree_recovery(temp_dir.path()).await;
+    let (_health_check, health_updater) = ReactiveHealthCheck::new("tree");
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = tree
+        .recover(init_params, 4, &pool, &health_updater, &stop_receiver)
+        .await
+        .unwrap_err();
+    let err = assert_matches!(err, OrStopped::Internal(err) => err.to_string());
+    assert!(err.contains("differs from expected root hash"), "{err}");
+}
+
+#[tokio::test]
+async fn recovery_is_stopped_on_request() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    prepare_snapshot(&pool).await;
+    let init_params = init_params(&pool, 10).await;
+
+    let tree = create_tree_recovery(temp_dir.path()).await;
+    let (_health_check, health_updater) = ReactiveHealthCheck::new("tree");
+    let (_stop_sender, stop_receiver) = watch::channel(true);
+    let err = tree
+        .recover(init_params, 4, &pool, &health_updater, &stop_receiver)
+        .await
+        .unwrap_err();
+    assert_matches!(err, OrStopped::Stopped);
+
+    // The tree must remain in the recovery mode.
+    let db = create_db(mock_config(temp_dir.path())).await.unwrap();
+    let tree = GenericAsyncTree::new(db).await.unwrap();
+    assert_matches!(tree, GenericAsyncTree::Recovering(_));
+}
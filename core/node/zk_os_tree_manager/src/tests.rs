@@ -7,7 +7,8 @@ use tempfile::TempDir;
 use test_casing::test_casing;
 use tokio::sync::Barrier;
 use zk_os_merkle_tree::{
-    BatchOutput, Blake2Hasher, DefaultTreeParams, MerkleTree, PatchSet, TreeOperation, TreeParams,
+    BatchOutput, Blake2Hasher, DefaultTreeParams, MerkleTree, PatchSet, RocksDBWrapper,
+    TreeOperation, TreeParams,
 };
 use zksync_dal::{Connection, CoreDal};
 use zksync_health_check::HealthStatus;
@@ -36,7 +37,11 @@ pub(crate) async fn setup_tree_manager(db_path: &Path, pool: ConnectionPool<Core
             .unwrap();
     }
 
-    let config = TreeManagerConfig {
+    TreeManager::new(mock_config(db_path), pool)
+}
+
+pub(crate) fn mock_config(db_path: &Path) -> TreeManagerConfig {
+    TreeManagerConfig {
         db_path: db_path.to_owned(),
         max_open_files: None,
         delay_interval: Duration::from_millis(10),
@@ -44,11 +49,13 @@ pub(crate) async fn setup_tree_manager(db_path: &Path, pool: ConnectionPool<Core
         multi_get_chunk_size: 500,
         block_cache_capacity: 16 << 20,
         include_indices_and_filters_in_block_cache: false,
-    };
-    TreeManager::new(config, pool)
+        retained_version_count: None,
+        verify_retained_versions_on_startup: false,
+        recovery: TreeRecoveryConfig::default(),
+    }
 }
 
-async fn insert_l1_batch(conn: &mut Connection<'_, Core>, storage_logs: &[StorageLog]) {
+pub(crate) async fn insert_l1_batch(conn: &mut Connection<'_, Core>, storage_logs: &[StorageLog]) {
     let mut conn = conn.start_transaction().await.unwrap();
     let l1_batch_number = conn
         .blocks_dal()
@@ -252,7 +259,7 @@ async fn basic_workflow(start_after_batch: bool) {
         .unwrap()
         .expect("no tree data");
     assert_eq!(tree_data.hash, expected_tree_hash);
-    assert_eq!(tree_data.rollup_last_leaf_index, tree_info.leaf_count + 1);
+    assert_eq!(tree_data.rollup_last_leaf_index, tree_info.leaf_count);
 
     stop_sender.send_replace(true);
     manager_task.await.unwrap().unwrap();
@@ -298,3 +305,73 @@ async fn workflow_with_multiple_batches(sleep_between_batches: bool) {
 
     assert_leaf_indices(&mut conn, tree_reader, &tree_info).await;
 }
+
+#[tokio::test]
+async fn workflow_with_pruning() {
+    const BATCH_COUNT: usize = 5;
+    const RETAINED_VERSION_COUNT: u64 = 2;
+
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed to get temporary directory for RocksDB");
+
+    let mut tree_manager = setup_tree_manager(temp_dir.path(), pool.clone()).await;
+    tree_manager.config.retained_version_count = NonZeroU64::new(RETAINED_VERSION_COUNT);
+    tree_manager.config.max_l1_batches_per_iter = NonZeroUsize::new(1).unwrap();
+    let tree_reader = tree_manager.tree_reader();
+    let mut batches_subscriber = tree_manager.subscribe_to_l1_batches();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let manager_task = tokio::spawn(tree_manager.run(stop_receiver));
+
+    let mut conn = pool.connection().await.unwrap();
+    let all_storage_logs = generate_storage_logs(100..200);
+    for storage_logs in all_storage_logs.chunks(all_storage_logs.len() / BATCH_COUNT) {
+        insert_l1_batch(&mut conn, storage_logs).await;
+    }
+    batches_subscriber
+        .wait_for(|&batch| batch == L1BatchNumber(BATCH_COUNT as u32) + 1)
+        .await
+        .unwrap();
+
+    // Wait until the pruner catches up with the tree.
+    let tree_reader = tree_reader.wait().await.unwrap();
+    let expected_min_version = BATCH_COUNT as u64 + 1 - RETAINED_VERSION_COUNT;
+    let tree_info = loop {
+        let tree_info = tree_reader.clone().info().await.unwrap();
+        assert_eq!(tree_info.next_version, BATCH_COUNT as u64 + 1);
+        if tree_info.min_version == Some(expected_min_version) {
+            break tree_info;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    let expected_tree_hash = expected_tree_hash(&mut conn).await;
+    assert_eq!(tree_info.root_hash, expected_tree_hash);
+    assert_leaf_indices(&mut conn, tree_reader, &tree_info).await;
+
+    stop_sender.send_replace(true);
+    manager_task.await.unwrap().unwrap();
+
+    // Restart the manager, so that it verifies the pruned tree.
+    let mut tree_manager = setup_tree_manager(temp_dir.path(), pool.clone()).await;
+    tree_manager.config.retained_version_count = NonZeroU64::new(RETAINED_VERSION_COUNT);
+    tree_manager.config.verify_retained_versions_on_startup = true;
+    let mut batches_subscriber = tree_manager.subscribe_to_l1_batches();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let manager_task = tokio::spawn(tree_manager.run(stop_receiver));
+    batches_subscriber
+        .wait_for(|&batch| batch == L1BatchNumber(BATCH_COUNT as u32) + 1)
+        .await
+        .unwrap();
+    stop_sender.send_replace(true);
+    manager_task.await.unwrap().unwrap();
+
+    let db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+    let tree = MerkleTree::new(db).unwrap();
+    for version in 0..expected_min_version {
+        assert_eq!(tree.root_info(version).unwrap(), None);
+    }
+    let keys: Vec<_> = all_storage_logs
+        .iter()
+        .map(|log| log.key.hashed_key())
+        .collect();
+    tree.verify_retained_versions(&keys).unwrap();
+}
@@ -1,11 +1,14 @@
 use std::{
-    future, ops,
+    future,
+    num::NonZeroU64,
+    ops,
     time::{Duration, Instant},
 };
 
 use anyhow::Context;
 use futures::FutureExt;
 use tokio::sync::watch;
+use zk_os_merkle_tree::MerkleTreePrunerHandle;
 use zksync_dal::{helpers::wait_for_l1_batch, Connection, ConnectionPool, Core, CoreDal};
 use zksync_shared_metrics::tree::{update_tree_metrics, TreeUpdateStage, METRICS};
 use zksync_types::{
@@ -15,10 +18,38 @@ use zksync_types::{
 
 use crate::{batch::L1BatchWithLogs, helpers::AsyncMerkleTree};
 
+/// Pruning settings for [`TreeUpdater`].
+#[derive(Debug)]
+pub(crate) struct TreePruning {
+    pub(crate) handle: MerkleTreePrunerHandle,
+    pub(crate) retained_version_count: NonZeroU64,
+}
+
+impl TreePruning {
+    fn update(&self, next_l1_batch_number: L1BatchNumber) -> anyhow::Result<()> {
+        let next_version = u64::from(next_l1_batch_number.0);
+        let target_retained_version =
+            next_version.saturating_sub(self.retained_version_count.get());
+        let Ok(prev_target_version) = self
+            .handle
+            .set_target_retained_version(target_retained_version)
+        else {
+            anyhow::bail!("Merkle tree pruner unexpectedly stopped");
+        };
+        if prev_target_version < target_retained_version {
+            tracing::info!(
+                "Set target retained tree version from {prev_target_version} to {target_retained_version}"
+            );
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct TreeUpdater {
     pub(crate) tree: AsyncMerkleTree,
     pub(crate) max_l1_batches_per_iter: usize,
+    pub(crate) pruning: Option<TreePruning>,
     #[cfg(test)]
     pub(crate) next_l1_batch_sender: watch::Sender<L1BatchNumber>,
 }
@@ -153,7 +184,11 @@ impl TreeUpdater {
         save_rocksdb_latency.observe();
         update_tree_metrics(&updated_batch_stats, total_logs, start);
 
-        Ok(last_l1_batch_number + 1)
+        let next_l1_batch_number = last_l1_batch_number + 1;
+        if let Some(pruning) = &self.pruning {
+            pruning.update(next_l1_batch_number)?;
+        }
+        Ok(next_l1_batch_number)
     }
 
     pub(crate) async fn loop_updating_tree(
@@ -224,7 +259,8 @@ impl AsyncMerkleTree {
 
         anyhow::ensure!(
             earliest_l1_batch == L1BatchNumber(0),
-            "Non-zero earliest L1 batch #{earliest_l1_batch} is not supported, and tree recovery isn't supported either"
+            "Earliest L1 batch #{earliest_l1_batch} in Postgres is not genesis, but there is no snapshot recovery / pruning info \
+             to recover the tree from"
         );
         let batch = L1BatchWithLogs::new(storage, earliest_l1_batch)
             .await
@@ -227,6 +227,9 @@ async fn main() -> anyhow::Result<()> {
         },
         max_retries: 1,
         local_mirror_path: None,
+        compress_objects: false,
+        encryption_key_path: None,
+        encrypted_buckets: vec![],
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()